@@ -0,0 +1,185 @@
+use anchor_lang::prelude::Pubkey;
+use chrono::NaiveDate;
+use clap::{error::ErrorKind, Args, CommandFactory, Parser, Subcommand};
+use solana_derivation_path::DerivationPath;
+use stablecoin_client::StableAmount;
+
+use crate::{
+    decode::{parse_input, Input},
+    output::OutputFormat,
+    propose::TxEncoding,
+    signer::parse_derivation_path,
+};
+
+const EXIT_CODES: &str = "\
+Exit codes:
+  0  success
+  1  other failure
+  2  invalid arguments
+  3  bad config file, profile or keypair
+  4  RPC request failed
+  5  transaction rejected by the program
+  6  transaction expired without confirming
+  7  account not found";
+
+#[derive(Debug, Parser)]
+#[command(
+    name = "stablecoin-cli",
+    version,
+    about = "Operate the stablecoin program",
+    after_help = EXIT_CODES
+)]
+pub struct Cli {
+    #[command(flatten)]
+    pub global: GlobalArgs,
+    #[command(flatten)]
+    pub proposal: ProposalArgs,
+    #[command(subcommand)]
+    pub command: Command,
+}
+
+impl Cli {
+    /// Parse the arguments, exiting on invalid ones
+    pub fn parse_args() -> Self {
+        let cli = Self::parse();
+        // clap cannot require one of two global flags from a subcommand
+        let proposal = &cli.proposal;
+        if proposal.authority.is_some() && !proposal.propose && !proposal.dry_run {
+            Self::command()
+                .error(
+                    ErrorKind::MissingRequiredArgument,
+                    "--authority requires --propose or --dry-run",
+                )
+                .exit();
+        }
+        cli
+    }
+}
+
+#[derive(Debug, Args)]
+pub struct GlobalArgs {
+    /// Solana CLI config file [default: ~/.config/solana/cli/config.yml]
+    #[arg(short = 'C', long, global = true, value_name = "PATH")]
+    pub config: Option<String>,
+    /// Named profile from the profiles file, such as devnet or mainnet
+    #[arg(long, global = true, value_name = "NAME")]
+    pub profile: Option<String>,
+    /// Profiles file [default: ~/.config/stablecoin/profiles.toml]
+    #[arg(long, global = true, value_name = "PATH")]
+    pub profiles_file: Option<String>,
+    /// RPC URL or moniker (mainnet-beta, testnet, devnet, localhost)
+    #[arg(short = 'u', long, global = true, value_name = "URL_OR_MONIKER")]
+    pub url: Option<String>,
+    /// Signer and fee payer, the admin, minter or token owner: a keypair
+    /// file, usb://ledger[?key=ACCOUNT[/CHANGE]] or, to type a seed phrase,
+    /// prompt://[?key=ACCOUNT[/CHANGE]]
+    #[arg(short = 'k', long, global = true, value_name = "PATH_OR_URI")]
+    pub keypair: Option<String>,
+    /// Derivation path for a prompt:// or usb:// signer: ACCOUNT[/CHANGE] or
+    /// a full path such as m/44'/501'/0'/0'
+    #[arg(long, global = true, value_name = "PATH", value_parser = parse_derivation_path)]
+    pub derivation_path: Option<DerivationPath>,
+    /// Commitment to read and confirm at
+    #[arg(long, global = true, value_parser = ["processed", "confirmed", "finalized"])]
+    pub commitment: Option<String>,
+    /// Print results as text or JSON
+    #[arg(long, global = true, value_enum, default_value_t)]
+    pub output: OutputFormat,
+}
+
+/// Review the transaction instead of sending it: simulate it, or build it for
+/// offline multisig signing
+#[derive(Debug, Args)]
+#[command(next_help_heading = "Review")]
+pub struct ProposalArgs {
+    /// Simulate and print the state changes instead of sending
+    #[arg(long, global = true, conflicts_with = "propose")]
+    pub dry_run: bool,
+    /// Print the unsigned transaction instead of sending it
+    #[arg(long, global = true)]
+    pub propose: bool,
+    /// Admin, minter or token owner to build for, instead of the --keypair
+    /// signer
+    #[arg(long, global = true, value_name = "PUBKEY")]
+    pub authority: Option<Pubkey>,
+    /// Fee payer [default: the authority]
+    #[arg(long, global = true, value_name = "PUBKEY", requires = "propose")]
+    pub fee_payer: Option<Pubkey>,
+    /// Durable nonce account, so signatures can be collected over any length
+    /// of time
+    #[arg(long, global = true, value_name = "PUBKEY", requires = "propose")]
+    pub nonce: Option<Pubkey>,
+    /// Nonce authority [default: the fee payer]
+    #[arg(long, global = true, value_name = "PUBKEY", requires = "nonce")]
+    pub nonce_authority: Option<Pubkey>,
+    /// Encoding of the printed transaction
+    #[arg(long, global = true, value_enum, default_value_t, requires = "propose")]
+    pub encoding: TxEncoding,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum Command {
+    /// Create the config and mint, with the signer as admin
+    Initialize,
+    /// Add a minter, or replace an existing minter's allowance
+    ConfigureMinter {
+        minter: Pubkey,
+        /// Total the minter may mint, in tokens
+        allowance: StableAmount,
+    },
+    /// Remove a minter for good, closing its config; its key cannot be
+    /// configured again
+    RemoveMinter { minter: Pubkey },
+    /// Mint as the signer, who must be a minter
+    Mint {
+        /// Amount in tokens, such as 12.5
+        amount: StableAmount,
+        /// Wallet to mint to [default: the signer]
+        recipient: Option<Pubkey>,
+    },
+    /// Stop the signer's own minting until the admin resumes it
+    PauseSelf,
+    /// Let a minter that paused itself mint again
+    ResumeMinter { minter: Pubkey },
+    /// Burn from the signer's token account
+    Burn {
+        /// Amount in tokens, such as 12.5
+        amount: StableAmount,
+    },
+    /// Stop minting
+    Pause,
+    /// Resume minting
+    Unpause,
+    /// List minters with their allowances
+    Minters,
+    /// Decode a transaction's instructions and, for one that was sent, its
+    /// events and error
+    Decode {
+        /// Signature to fetch, or a transaction in base64 or base58 such as
+        /// --propose prints
+        #[arg(value_parser = parse_input)]
+        transaction: Input,
+    },
+    /// Program history
+    History {
+        #[command(subcommand)]
+        command: HistoryCommand,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+pub enum HistoryCommand {
+    /// Write every mint, burn and admin action to a CSV file and check the
+    /// totals against the mint's supply
+    Export {
+        /// CSV file to write
+        #[arg(long, value_name = "PATH")]
+        out: String,
+        /// First UTC date to include, as YYYY-MM-DD
+        #[arg(long, value_name = "DATE")]
+        from: Option<NaiveDate>,
+        /// UTC date to stop before, as YYYY-MM-DD
+        #[arg(long, value_name = "DATE")]
+        to: Option<NaiveDate>,
+    },
+}
@@ -0,0 +1,159 @@
+//! Cluster and signer settings
+//!
+//! Flags win over the `--profile` selected from the profiles file, which
+//! wins over `~/.config/solana/cli/config.yml`, which wins over the built-in
+//! defaults.
+//!
+//! The profiles file, `~/.config/stablecoin/profiles.toml` by default, holds
+//! one table per environment:
+//!
+//! ```toml
+//! [profiles.mainnet]
+//! url = "mainnet-beta"
+//! # Refuse to run if this build targets a different program
+//! program_id = "..."
+//! commitment = "finalized"
+//! keypair = "usb://ledger?key=0"
+//! # Priority fee, in micro-lamports per compute unit
+//! compute_unit_price = 10000
+//! ```
+
+use std::collections::HashMap;
+
+use serde::Deserialize;
+use solana_cli_config::{Config, ConfigInput, CONFIG_FILE};
+use solana_commitment_config::CommitmentConfig;
+use solana_derivation_path::DerivationPath;
+use solana_rpc_client::nonblocking::rpc_client::RpcClient;
+use solana_signer::Signer;
+
+use crate::{cli::GlobalArgs, error::CliError, signer::signer_from_path};
+
+/// One environment's settings; anything left out falls through to the
+/// Solana CLI config
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Profile {
+    pub url: Option<String>,
+    pub program_id: Option<String>,
+    pub commitment: Option<String>,
+    pub keypair: Option<String>,
+    pub compute_unit_price: Option<u64>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ProfilesFile {
+    #[serde(default)]
+    profiles: HashMap<String, Profile>,
+}
+
+fn default_profiles_file() -> Option<String> {
+    dirs_next::home_dir().map(|mut path| {
+        path.extend([".config", "stablecoin", "profiles.toml"]);
+        path.to_string_lossy().into_owned()
+    })
+}
+
+/// Load `name` from the profiles file and check it is for this program
+pub fn load_profile(path: Option<&str>, name: &str) -> Result<Profile, CliError> {
+    let path = path
+        .map(str::to_string)
+        .or_else(default_profiles_file)
+        .ok_or_else(|| CliError::Profiles {
+            path: "~/.config/stablecoin/profiles.toml".to_string(),
+            message: "no home directory".to_string(),
+        })?;
+    let profiles_error = |message: String| CliError::Profiles {
+        path: path.clone(),
+        message,
+    };
+    let contents = std::fs::read_to_string(&path).map_err(|err| profiles_error(err.to_string()))?;
+    let mut file: ProfilesFile =
+        toml::from_str(&contents).map_err(|err| profiles_error(err.to_string()))?;
+
+    let profile = file
+        .profiles
+        .remove(name)
+        .ok_or_else(|| CliError::ProfileNotFound {
+            name: name.to_string(),
+            path: path.clone(),
+        })?;
+    if let Some(program_id) = &profile.program_id {
+        if *program_id != stablecoin_client::PROGRAM_ID.to_string() {
+            return Err(CliError::ProgramIdMismatch {
+                profile: name.to_string(),
+                program_id: program_id.clone(),
+            });
+        }
+    }
+    Ok(profile)
+}
+
+/// Settings resolved from flags, the profile and the Solana CLI config file
+pub struct Settings {
+    pub json_rpc_url: String,
+    pub keypair_path: String,
+    pub derivation_path: Option<DerivationPath>,
+    pub commitment: CommitmentConfig,
+    pub compute_unit_price: Option<u64>,
+}
+
+impl Settings {
+    pub fn resolve(args: &GlobalArgs) -> Result<Self, CliError> {
+        let profile = match &args.profile {
+            Some(name) => load_profile(args.profiles_file.as_deref(), name)?,
+            None => Profile::default(),
+        };
+
+        let config = match args.config.as_deref().or(CONFIG_FILE.as_deref()) {
+            // An explicit config file must exist; the default one may not
+            Some(path) if args.config.is_some() => {
+                Config::load(path).map_err(|source| CliError::Config {
+                    path: path.to_string(),
+                    source,
+                })?
+            }
+            Some(path) => Config::load(path).unwrap_or_default(),
+            None => Config::default(),
+        };
+
+        let (_, json_rpc_url) = ConfigInput::compute_json_rpc_url_setting(
+            args.url
+                .as_deref()
+                .or(profile.url.as_deref())
+                .unwrap_or_default(),
+            &config.json_rpc_url,
+        );
+        let (_, keypair_path) = ConfigInput::compute_keypair_path_setting(
+            args.keypair
+                .as_deref()
+                .or(profile.keypair.as_deref())
+                .unwrap_or_default(),
+            &config.keypair_path,
+        );
+        let (_, commitment) = ConfigInput::compute_commitment_config(
+            args.commitment
+                .as_deref()
+                .or(profile.commitment.as_deref())
+                .unwrap_or_default(),
+            &config.commitment,
+        );
+
+        Ok(Self {
+            json_rpc_url,
+            keypair_path,
+            derivation_path: args.derivation_path.clone(),
+            commitment,
+            compute_unit_price: profile.compute_unit_price,
+        })
+    }
+
+    pub fn rpc_client(&self) -> RpcClient {
+        RpcClient::new_with_commitment(self.json_rpc_url.clone(), self.commitment)
+    }
+
+    pub fn signer(&self) -> Result<Box<dyn Signer>, CliError> {
+        signer_from_path(&self.keypair_path, self.derivation_path.as_ref())
+    }
+}
@@ -0,0 +1,381 @@
+//! `decode`: a transaction in this program's terms
+//!
+//! Given a signature, the transaction is fetched with its logs and inner
+//! instructions, so the events it emitted and the error it failed with are
+//! decoded too. Given the transaction itself, as `--propose` prints it in
+//! base64 or base58, it is read without any RPC request; nothing has run, so
+//! there are only its instructions.
+//!
+//! Instruction arguments and account names come from the program's own
+//! types and IDL. Other programs' instructions are listed by program id.
+
+use anchor_lang::prelude::Pubkey;
+use serde_json::Value;
+use solana_message::VersionedMessage;
+use solana_rpc_client::nonblocking::rpc_client::RpcClient;
+use solana_rpc_client_api::config::RpcTransactionConfig;
+use solana_signature::Signature;
+use solana_transaction_status_client_types::{
+    UiInstruction, UiLoadedAddresses, UiTransactionEncoding,
+};
+use stablecoin_client::{
+    diagnostics::ErrorDiagnostics,
+    events::parse_logs,
+    idl::IDL_JSON,
+    instructions::{self, StablecoinInstruction},
+    offline, ClientError, StableAmount, PROGRAM_ID,
+};
+
+use crate::{
+    error::{CliError, ProgramError},
+    output::{DecodeOutput, DecodedAccount, DecodedArg, DecodedEvent, DecodedInstruction},
+};
+
+/// What `decode` was given
+#[derive(Debug, Clone)]
+pub enum Input {
+    Signature(Signature),
+    /// A transaction's message, and its first signature if it has been
+    /// signed
+    Transaction(Box<VersionedMessage>, Option<Signature>),
+}
+
+/// Parse the `decode` argument: a signature, or a transaction in base64 or
+/// base58
+pub fn parse_input(input: &str) -> Result<Input, String> {
+    if let Ok(signature) = input.parse() {
+        return Ok(Input::Signature(signature));
+    }
+    let tx = offline::decode_transaction(input)
+        .or_else(|_| offline::decode_transaction_base58(input))
+        .map_err(|_| "not a signature, or a transaction in base64 or base58".to_string())?;
+    let signature = tx
+        .signatures
+        .first()
+        .copied()
+        .filter(|signature| *signature != Signature::default());
+    Ok(Input::Transaction(
+        Box::new(VersionedMessage::Legacy(tx.message)),
+        signature,
+    ))
+}
+
+pub async fn decode(rpc: &RpcClient, input: &Input) -> Result<DecodeOutput, CliError> {
+    let idl: Value = serde_json::from_str(IDL_JSON).expect("the bundled IDL is valid JSON");
+    match input {
+        Input::Transaction(message, signature) => {
+            let keys = message.static_account_keys().to_vec();
+            Ok(DecodeOutput {
+                signature: signature.map(|signature| signature.to_string()),
+                slot: None,
+                success: None,
+                error: None,
+                program_error: None,
+                instructions: top_level(&idl, message, &keys),
+                events: Vec::new(),
+            })
+        }
+        Input::Signature(signature) => fetch(rpc, &idl, signature).await,
+    }
+}
+
+async fn fetch(
+    rpc: &RpcClient,
+    idl: &Value,
+    signature: &Signature,
+) -> Result<DecodeOutput, CliError> {
+    let tx = rpc
+        .get_transaction_with_config(
+            signature,
+            RpcTransactionConfig {
+                encoding: Some(UiTransactionEncoding::Base64),
+                commitment: Some(rpc.commitment()),
+                max_supported_transaction_version: Some(0),
+            },
+        )
+        .await
+        .map_err(ClientError::from)?;
+    let versioned = tx
+        .transaction
+        .transaction
+        .decode()
+        .ok_or(CliError::UndecodableTransaction(*signature))?;
+    let meta = tx
+        .transaction
+        .meta
+        .ok_or(CliError::UndecodableTransaction(*signature))?;
+    let message = &versioned.message;
+
+    // Lookup table addresses follow the static keys, writable first
+    let loaded = Option::<UiLoadedAddresses>::from(meta.loaded_addresses).unwrap_or_default();
+    let mut keys = message.static_account_keys().to_vec();
+    for address in loaded.writable.iter().chain(&loaded.readonly) {
+        keys.push(
+            address
+                .parse()
+                .map_err(|_| CliError::UndecodableTransaction(*signature))?,
+        );
+    }
+
+    let mut inner = Vec::new();
+    for instructions in Option::<Vec<_>>::from(meta.inner_instructions).unwrap_or_default() {
+        for (position, instruction) in instructions.instructions.into_iter().enumerate() {
+            // Base64 encoding only ever yields compiled instructions
+            let UiInstruction::Compiled(instruction) = instruction else {
+                continue;
+            };
+            let data = bs58::decode(&instruction.data)
+                .into_vec()
+                .map_err(|_| CliError::UndecodableTransaction(*signature))?;
+            let decoded = decode_instruction(
+                idl,
+                message,
+                &keys,
+                format!("{}.{}", instructions.index, position + 1),
+                instruction.program_id_index,
+                &instruction.accounts,
+                &data,
+            );
+            inner.push((usize::from(instructions.index), decoded));
+        }
+    }
+    // Each top-level instruction, then the ones it invoked
+    let mut decoded = Vec::new();
+    for (index, instruction) in top_level(idl, message, &keys).into_iter().enumerate() {
+        decoded.push(instruction);
+        decoded.extend(
+            inner
+                .extract_if(.., |(parent, _)| *parent == index)
+                .map(|(_, instruction)| instruction),
+        );
+    }
+
+    let logs: Vec<String> = Option::from(meta.log_messages).unwrap_or_default();
+    let (error, program_error, events) = match meta.err {
+        // Its events were rolled back
+        Some(err) => {
+            let diagnostics = ErrorDiagnostics::new(err.into(), logs);
+            (
+                Some(diagnostics.to_string()),
+                ProgramError::from_diagnostics(&diagnostics),
+                Vec::new(),
+            )
+        }
+        None => {
+            let events = parse_logs(&PROGRAM_ID, &logs)
+                .map_err(ClientError::from)?
+                .iter()
+                .map(DecodedEvent::from)
+                .collect();
+            (None, None, events)
+        }
+    };
+
+    Ok(DecodeOutput {
+        signature: Some(signature.to_string()),
+        slot: Some(tx.slot),
+        success: Some(error.is_none()),
+        error,
+        program_error,
+        instructions: decoded,
+        events,
+    })
+}
+
+fn top_level(idl: &Value, message: &VersionedMessage, keys: &[Pubkey]) -> Vec<DecodedInstruction> {
+    message
+        .instructions()
+        .iter()
+        .enumerate()
+        .map(|(index, instruction)| {
+            decode_instruction(
+                idl,
+                message,
+                keys,
+                index.to_string(),
+                instruction.program_id_index,
+                &instruction.accounts,
+                &instruction.data,
+            )
+        })
+        .collect()
+}
+
+fn decode_instruction(
+    idl: &Value,
+    message: &VersionedMessage,
+    keys: &[Pubkey],
+    index: String,
+    program_id_index: u8,
+    accounts: &[u8],
+    data: &[u8],
+) -> DecodedInstruction {
+    let key = |index: u8| {
+        keys.get(usize::from(index))
+            .map_or_else(|| format!("unknown account {index}"), ToString::to_string)
+    };
+    let program = keys.get(usize::from(program_id_index));
+    let decoded = (program == Some(&PROGRAM_ID))
+        .then(|| instructions::decode(data))
+        .flatten();
+    let names = decoded
+        .as_ref()
+        .map(|decoded| account_names(idl, decoded.name()))
+        .unwrap_or_default();
+
+    DecodedInstruction {
+        index,
+        program: key(program_id_index),
+        name: decoded.as_ref().map(|decoded| decoded.name()),
+        args: decoded
+            .as_ref()
+            .map(args)
+            .unwrap_or_default()
+            .into_iter()
+            .map(|(name, value)| DecodedArg { name, value })
+            .collect(),
+        accounts: accounts
+            .iter()
+            .enumerate()
+            .map(|(position, index)| DecodedAccount {
+                name: names.get(position).cloned(),
+                pubkey: key(*index),
+                is_signer: message.is_signer(usize::from(*index)),
+                is_writable: message.is_maybe_writable(usize::from(*index), None),
+            })
+            .collect(),
+    }
+}
+
+/// The instruction's account names in the IDL, in order, with the accounts
+/// of a nested group named `group.account`
+fn account_names(idl: &Value, instruction: &str) -> Vec<String> {
+    fn flatten(accounts: &Value, prefix: &str, names: &mut Vec<String>) {
+        for account in accounts.as_array().into_iter().flatten() {
+            let name = format!("{prefix}{}", account["name"].as_str().unwrap_or_default());
+            match account.get("accounts") {
+                Some(group) => flatten(group, &format!("{name}."), names),
+                None => names.push(name),
+            }
+        }
+    }
+
+    let mut names = Vec::new();
+    if let Some(instruction) = idl["instructions"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .find(|candidate| candidate["name"] == instruction)
+    {
+        flatten(&instruction["accounts"], "", &mut names);
+    }
+    names
+}
+
+/// `base_units` as a person reads it
+fn tokens(base_units: u64) -> String {
+    format!("{} tokens", StableAmount::from_base_units(base_units))
+}
+
+/// The instruction's arguments, labelled, with amounts in tokens
+pub fn args(instruction: &StablecoinInstruction) -> Vec<(&'static str, String)> {
+    match instruction {
+        StablecoinInstruction::ConfigureMinter { allowance } => {
+            vec![("Allowance", tokens(*allowance))]
+        }
+        StablecoinInstruction::MintTokens { amount }
+        | StablecoinInstruction::BurnTokens { amount } => vec![("Amount", tokens(*amount))],
+        StablecoinInstruction::MintTokensIdempotent { amount, key } => {
+            vec![("Amount", tokens(*amount)), ("Key", hex(key))]
+        }
+        StablecoinInstruction::ConfigureTreasury {
+            officers,
+            threshold,
+        } => officers
+            .iter()
+            .map(|officer| ("Officer", officer.to_string()))
+            .chain([("Threshold", tokens(*threshold))])
+            .collect(),
+        StablecoinInstruction::SweepDust { threshold } => vec![("Threshold", tokens(*threshold))],
+        StablecoinInstruction::ProposeTreasuryBurn { id, amount } => {
+            vec![("Burn id", id.to_string()), ("Amount", tokens(*amount))]
+        }
+        StablecoinInstruction::ConfigureRecovery {
+            guardians,
+            quorum,
+            challenge_period,
+        } => guardians
+            .iter()
+            .map(|guardian| ("Guardian", guardian.to_string()))
+            .chain([
+                ("Quorum", quorum.to_string()),
+                ("Challenge", format!("{challenge_period}s")),
+            ])
+            .collect(),
+        StablecoinInstruction::InitiateRecovery { new_owner } => {
+            vec![("New owner", new_owner.to_string())]
+        }
+        StablecoinInstruction::ConfigureInheritance {
+            beneficiary,
+            inactivity_epochs,
+            notice_period,
+        } => vec![
+            ("Beneficiary", beneficiary.to_string()),
+            ("Inactivity", format!("{inactivity_epochs} epochs")),
+            ("Notice", format!("{notice_period}s")),
+        ],
+        StablecoinInstruction::OpenNettingWindow {
+            id,
+            institutions,
+            duration,
+        } => [("Window id", id.to_string())]
+            .into_iter()
+            .chain(
+                institutions
+                    .iter()
+                    .map(|institution| ("Institution", institution.to_string())),
+            )
+            .chain([("Duration", format!("{duration}s"))])
+            .collect(),
+        StablecoinInstruction::QueueObligation { creditor, amount } => vec![
+            ("Creditor", creditor.to_string()),
+            ("Amount", tokens(*amount)),
+        ],
+        StablecoinInstruction::RegisterPurposeCode { code } => {
+            vec![("Code", String::from_utf8_lossy(code).into_owned())]
+        }
+        StablecoinInstruction::OpenChannel {
+            recipient,
+            deposit,
+            dispute_period,
+        } => vec![
+            ("Recipient", recipient.to_string()),
+            ("Deposit", tokens(*deposit)),
+            ("Dispute", format!("{dispute_period}s")),
+        ],
+        StablecoinInstruction::CloseChannel { amount, signature } => vec![
+            ("Amount", tokens(*amount)),
+            ("Signature", Signature::from(*signature).to_string()),
+        ],
+        StablecoinInstruction::RegisterSubAccount { index } => {
+            vec![("Sub-account", index.to_string())]
+        }
+        StablecoinInstruction::OpenAirdrop {
+            id,
+            recipients,
+            amounts,
+            duration,
+        } => [("Airdrop id", id.to_string())]
+            .into_iter()
+            .chain(recipients.iter().zip(amounts).map(|(recipient, amount)| {
+                ("Recipient", format!("{recipient} {}", tokens(*amount)))
+            }))
+            .chain([("Duration", format!("{duration}s"))])
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{byte:02x}")).collect()
+}
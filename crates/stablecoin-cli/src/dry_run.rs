@@ -0,0 +1,153 @@
+//! `--dry-run`: simulate and show what would change, without sending
+//!
+//! The accounts a command touches are read as they are now and as the
+//! simulated transaction would leave them, and every field that differs is
+//! listed. The program's only roles are the admin and the minters, so role
+//! changes show up as config and minter fields.
+
+use anchor_lang::{prelude::Pubkey, AccountDeserialize};
+use anchor_spl::token::{Mint, TokenAccount};
+use solana_instruction::Instruction;
+use solana_rpc_client::nonblocking::rpc_client::RpcClient;
+use stablecoin_client::{accounts, diagnostics, instructions, pda, ClientError, TxBuilder};
+
+use crate::{
+    error::CliError,
+    output::{Change, DryRunOutput, Readback},
+};
+
+#[derive(Debug, Clone, Copy)]
+enum Kind {
+    Config,
+    Minter,
+    Mint,
+    TokenAccount,
+}
+
+impl Kind {
+    fn name(self) -> &'static str {
+        match self {
+            Self::Config => "config",
+            Self::Minter => "minter config",
+            Self::Mint => "mint",
+            Self::TokenAccount => "token account",
+        }
+    }
+
+    /// The fields shown for this kind of account, in display order
+    fn fields(
+        self,
+        address: Pubkey,
+        data: &[u8],
+    ) -> Result<Vec<(&'static str, String)>, ClientError> {
+        let decode_error = |source| ClientError::AccountDecode { address, source };
+        Ok(match self {
+            Self::Config => {
+                let config = accounts::decode_config(address, data)?;
+                vec![
+                    ("admin", config.admin.to_string()),
+                    ("mint", config.mint.to_string()),
+                    ("paused", config.paused.to_string()),
+                ]
+            }
+            Self::Minter => {
+                let account = accounts::decode_minter_config(address, data)?;
+                vec![
+                    ("minter", account.config.minter.to_string()),
+                    ("allowance", account.config.allowance.to_string()),
+                    ("amount_minted", account.config.amount_minted.to_string()),
+                    (
+                        "remaining_allowance",
+                        account.remaining_allowance().to_string(),
+                    ),
+                ]
+            }
+            Self::Mint => {
+                let mint = Mint::try_deserialize(&mut &data[..]).map_err(decode_error)?;
+                vec![("supply", mint.supply.to_string())]
+            }
+            Self::TokenAccount => {
+                let token_account =
+                    TokenAccount::try_deserialize(&mut &data[..]).map_err(decode_error)?;
+                vec![
+                    ("owner", token_account.owner.to_string()),
+                    ("amount", token_account.amount.to_string()),
+                ]
+            }
+        })
+    }
+}
+
+/// The accounts whose changes are shown, from what a command reads back
+fn watched(readback: &Readback) -> Vec<(Kind, Pubkey)> {
+    let mut watched = Vec::new();
+    if readback.config {
+        watched.push((Kind::Config, pda::config().0));
+    }
+    if let Some(minter) = &readback.minter {
+        watched.push((Kind::Minter, pda::minter_config(minter).0));
+    }
+    if let Some(owner) = &readback.token_owner {
+        watched.push((Kind::Mint, pda::mint().0));
+        watched.push((Kind::TokenAccount, pda::token_account(owner)));
+    }
+    watched
+}
+
+pub async fn dry_run(
+    rpc: &RpcClient,
+    builder: &TxBuilder,
+    instruction: &Instruction,
+    readback: &Readback,
+) -> Result<DryRunOutput, CliError> {
+    let watched = watched(readback);
+    let addresses: Vec<Pubkey> = watched.iter().map(|(_, address)| *address).collect();
+
+    let before = rpc
+        .get_multiple_accounts_with_commitment(&addresses, rpc.commitment())
+        .await
+        .map_err(ClientError::from)?
+        .value;
+    let simulation = diagnostics::simulate_with_accounts(rpc, builder, &addresses).await?;
+
+    let mut changes = Vec::new();
+    for (index, (kind, address)) in watched.iter().enumerate() {
+        let before = before
+            .get(index)
+            .and_then(Option::as_ref)
+            .filter(|account| account.lamports > 0)
+            .map(|account| kind.fields(*address, &account.data))
+            .transpose()?;
+        let after = simulation
+            .accounts
+            .get(index)
+            .and_then(Option::as_ref)
+            .map(|data| kind.fields(*address, data))
+            .transpose()?;
+
+        let names = before.as_ref().or(after.as_ref()).into_iter().flatten();
+        for (field, &(name, _)) in names.enumerate() {
+            let value = |fields: &Option<Vec<(&str, String)>>| {
+                fields.as_ref().map(|fields| fields[field].1.clone())
+            };
+            let (old, new) = (value(&before), value(&after));
+            if old != new {
+                changes.push(Change {
+                    account: kind.name(),
+                    address: address.to_string(),
+                    field: name,
+                    old,
+                    new,
+                });
+            }
+        }
+    }
+
+    Ok(DryRunOutput {
+        instruction: instructions::decode(&instruction.data)
+            .map_or("unknown", |decoded| decoded.name())
+            .to_string(),
+        units_consumed: simulation.units_consumed,
+        changes,
+    })
+}
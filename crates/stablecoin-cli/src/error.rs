@@ -0,0 +1,152 @@
+use serde::Serialize;
+use stablecoin_client::{diagnostics::ErrorDiagnostics, ClientError};
+
+#[derive(Debug, thiserror::Error)]
+pub enum CliError {
+    #[error(transparent)]
+    Client(#[from] ClientError),
+    #[error("failed to load config file {path}: {source}")]
+    Config {
+        path: String,
+        source: std::io::Error,
+    },
+    #[error("failed to read keypair {path}: {message}")]
+    Keypair { path: String, message: String },
+    #[error("failed to load profiles file {path}: {message}")]
+    Profiles { path: String, message: String },
+    #[error("profile {name:?} not found in {path}")]
+    ProfileNotFound { name: String, path: String },
+    #[error(
+        "profile {profile:?} is for program {program_id}, but this build targets {}",
+        stablecoin_client::PROGRAM_ID
+    )]
+    ProgramIdMismatch { profile: String, program_id: String },
+    #[error("failed to write {path}: {source}")]
+    Write {
+        path: String,
+        source: std::io::Error,
+    },
+    #[error("no block time for transaction {0}, so it cannot be placed in a date range")]
+    MissingBlockTime(solana_signature::Signature),
+    #[error("transaction {0} came back in a form that cannot be decoded")]
+    UndecodableTransaction(solana_signature::Signature),
+}
+
+/// Failure classes, each with its own exit code
+///
+/// The codes are stable so that scripts can branch on them. Usage errors
+/// exit with 2, from argument parsing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FailureClass {
+    /// Anything not covered below
+    Other = 1,
+    /// Bad config file, profile or keypair
+    Config = 3,
+    /// The RPC node could not be reached or refused the request
+    Rpc = 4,
+    /// The program rejected the transaction
+    Program = 5,
+    /// The transaction expired without confirming
+    Expired = 6,
+    /// An account the command needs does not exist
+    NotFound = 7,
+}
+
+impl CliError {
+    pub fn class(&self) -> FailureClass {
+        match self {
+            Self::Config { .. }
+            | Self::Keypair { .. }
+            | Self::Profiles { .. }
+            | Self::ProfileNotFound { .. }
+            | Self::ProgramIdMismatch { .. } => FailureClass::Config,
+            Self::Client(ClientError::Rpc(_) | ClientError::Pubsub(_)) => FailureClass::Rpc,
+            Self::Client(ClientError::Simulation(_) | ClientError::TransactionFailed { .. }) => {
+                FailureClass::Program
+            }
+            Self::Client(ClientError::Expired { .. }) => FailureClass::Expired,
+            Self::Client(ClientError::AccountNotFound(_)) => FailureClass::NotFound,
+            Self::Client(_)
+            | Self::Write { .. }
+            | Self::MissingBlockTime(_)
+            | Self::UndecodableTransaction(_) => FailureClass::Other,
+        }
+    }
+
+    pub fn exit_code(&self) -> u8 {
+        self.class() as u8
+    }
+
+    /// Remediation hint for a program error
+    pub fn hint(&self) -> Option<&'static str> {
+        self.diagnostics()?.hint()
+    }
+
+    /// Decoded program failure, when the program rejected the transaction
+    fn diagnostics(&self) -> Option<&ErrorDiagnostics> {
+        match self {
+            Self::Client(ClientError::Simulation(diagnostics))
+            | Self::Client(ClientError::TransactionFailed { diagnostics, .. }) => Some(diagnostics),
+            _ => None,
+        }
+    }
+}
+
+/// JSON form of a [`CliError`]
+#[derive(Debug, Serialize)]
+pub struct ErrorOutput {
+    pub class: FailureClass,
+    pub exit_code: u8,
+    pub message: String,
+    /// Custom error code and name, when the program returned one
+    pub program_error: Option<ProgramError>,
+    /// Every signature sent, when the transaction failed or expired on chain
+    pub signatures: Vec<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ProgramError {
+    pub code: u32,
+    pub name: Option<String>,
+    pub hint: Option<&'static str>,
+}
+
+impl ProgramError {
+    /// `None` unless the failing program returned a custom error code
+    pub fn from_diagnostics(diagnostics: &ErrorDiagnostics) -> Option<Self> {
+        let name = match (&diagnostics.anchor_error, &diagnostics.stablecoin_error) {
+            (Some(anchor_error), _) => Some(anchor_error.name.clone()),
+            (None, Some(error)) => Some(error.name()),
+            (None, None) => None,
+        };
+        Some(Self {
+            code: diagnostics.code?,
+            name,
+            hint: diagnostics.hint(),
+        })
+    }
+}
+
+impl From<&CliError> for ErrorOutput {
+    fn from(err: &CliError) -> Self {
+        let program_error = err.diagnostics().and_then(ProgramError::from_diagnostics);
+        let signatures = match err {
+            CliError::Client(ClientError::TransactionFailed { signature, .. }) => {
+                vec![signature.to_string()]
+            }
+            CliError::Client(ClientError::Expired { signatures }) => {
+                signatures.iter().map(ToString::to_string).collect()
+            }
+            _ => Vec::new(),
+        };
+
+        Self {
+            class: err.class(),
+            exit_code: err.exit_code(),
+            message: err.to_string(),
+            program_error,
+            signatures,
+        }
+    }
+}
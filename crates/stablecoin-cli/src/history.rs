@@ -0,0 +1,339 @@
+//! `history export`: a ledger of every mint, burn and admin action
+//!
+//! Transactions are found with `getSignaturesForAddress` on the config and
+//! the mint, and each row comes from an event the program emitted. Over the
+//! full history, minted minus burned is checked against the mint's supply;
+//! the two differ if tokens were burned through the token program directly,
+//! which this program never sees. Obligations queued with a purpose code
+//! carry it in the `purpose` column, for purpose-of-payment reporting.
+
+use std::{
+    collections::HashSet,
+    fs::File,
+    io::{self, BufWriter, Write},
+};
+
+use chrono::{DateTime, NaiveDate, SecondsFormat};
+use solana_rpc_client::nonblocking::rpc_client::RpcClient;
+use stablecoin_client::{
+    events::StablecoinEvent,
+    history::{fetch_transaction_events, signatures_since, TransactionEvents},
+    pda, ClientError, PROGRAM_ID,
+};
+
+use crate::{error::CliError, output::ExportOutput};
+
+const HEADER: &str =
+    "slot,block_time,signature,event,admin,minter,owner,destination,token_account,amount,allowance,purpose";
+
+/// Every transaction that emitted events, oldest first
+async fn transactions(rpc: &RpcClient) -> Result<Vec<TransactionEvents>, CliError> {
+    let commitment = rpc.commitment();
+    let mut signatures = signatures_since(rpc, &pda::config().0, None, commitment).await?;
+    signatures.extend(signatures_since(rpc, &pda::mint().0, None, commitment).await?);
+    let mut seen = HashSet::new();
+    signatures.retain(|(_, signature)| seen.insert(*signature));
+    signatures.sort_by_key(|(slot, _)| *slot);
+
+    let mut history = Vec::new();
+    for (_, signature) in signatures {
+        if let Some(tx) = fetch_transaction_events(rpc, &PROGRAM_ID, &signature, commitment).await?
+        {
+            if !tx.events.is_empty() {
+                history.push(tx);
+            }
+        }
+    }
+    Ok(history)
+}
+
+/// Whether `tx` falls within `[from, to)`, by UTC date
+fn in_range(
+    tx: &TransactionEvents,
+    from: Option<NaiveDate>,
+    to: Option<NaiveDate>,
+) -> Result<bool, CliError> {
+    if from.is_none() && to.is_none() {
+        return Ok(true);
+    }
+    let date = tx
+        .block_time
+        .and_then(|time| DateTime::from_timestamp(time, 0))
+        .ok_or(CliError::MissingBlockTime(tx.signature))?
+        .date_naive();
+    Ok(from.is_none_or(|from| date >= from) && to.is_none_or(|to| date < to))
+}
+
+/// The columns after `event`, blank where an event has no such field
+#[derive(Default)]
+struct Fields {
+    admin: String,
+    minter: String,
+    owner: String,
+    destination: String,
+    token_account: String,
+    amount: String,
+    allowance: String,
+    purpose: String,
+}
+
+impl From<&StablecoinEvent> for Fields {
+    fn from(event: &StablecoinEvent) -> Self {
+        match event {
+            StablecoinEvent::Initialized(event) => Self {
+                admin: event.admin.to_string(),
+                ..Self::default()
+            },
+            StablecoinEvent::MinterConfigured(event) => Self {
+                minter: event.minter.to_string(),
+                allowance: event.allowance.to_string(),
+                ..Self::default()
+            },
+            StablecoinEvent::MinterRemoved(event) => Self {
+                minter: event.minter.to_string(),
+                ..Self::default()
+            },
+            StablecoinEvent::TokensMinted(event) => Self {
+                minter: event.minter.to_string(),
+                destination: event.destination.to_string(),
+                amount: event.amount.to_string(),
+                ..Self::default()
+            },
+            StablecoinEvent::TokensBurned(event) => Self {
+                owner: event.owner.to_string(),
+                token_account: event.token_account.to_string(),
+                amount: event.amount.to_string(),
+                ..Self::default()
+            },
+            StablecoinEvent::Paused(event) => Self {
+                admin: event.admin.to_string(),
+                ..Self::default()
+            },
+            StablecoinEvent::Unpaused(event) => Self {
+                admin: event.admin.to_string(),
+                ..Self::default()
+            },
+            StablecoinEvent::MinterSetFrozen(event) => Self {
+                admin: event.admin.to_string(),
+                ..Self::default()
+            },
+            StablecoinEvent::MinterSetUnfrozen(event) => Self {
+                admin: event.admin.to_string(),
+                ..Self::default()
+            },
+            StablecoinEvent::MinterSelfPaused(event) => Self {
+                minter: event.minter.to_string(),
+                ..Self::default()
+            },
+            StablecoinEvent::MinterResumed(event) => Self {
+                minter: event.minter.to_string(),
+                admin: event.admin.to_string(),
+                ..Self::default()
+            },
+            StablecoinEvent::TreasuryConfigured(event) => Self {
+                admin: event.admin.to_string(),
+                ..Self::default()
+            },
+            StablecoinEvent::TreasuryBurnProposed(event) => Self {
+                admin: event.proposer.to_string(),
+                token_account: pda::treasury_custody().to_string(),
+                amount: event.amount.to_string(),
+                ..Self::default()
+            },
+            StablecoinEvent::TreasuryBurnApproved(event) => Self {
+                admin: event.officer.to_string(),
+                ..Self::default()
+            },
+            StablecoinEvent::TreasuryBurnCancelled(event) => Self {
+                admin: event.officer.to_string(),
+                ..Self::default()
+            },
+            StablecoinEvent::RecoveryConfigured(event) => Self {
+                owner: event.owner.to_string(),
+                ..Self::default()
+            },
+            StablecoinEvent::RecoveryInitiated(event) => Self {
+                owner: event.owner.to_string(),
+                destination: event.new_owner.to_string(),
+                ..Self::default()
+            },
+            StablecoinEvent::RecoveryCancelled(event) => Self {
+                owner: event.owner.to_string(),
+                ..Self::default()
+            },
+            StablecoinEvent::AccountRecovered(event) => Self {
+                owner: event.owner.to_string(),
+                destination: event.new_owner.to_string(),
+                amount: event.amount.to_string(),
+                ..Self::default()
+            },
+            StablecoinEvent::InheritanceConfigured(event) => Self {
+                owner: event.owner.to_string(),
+                destination: event.beneficiary.to_string(),
+                ..Self::default()
+            },
+            StablecoinEvent::InheritanceClaimStarted(event) => Self {
+                owner: event.owner.to_string(),
+                destination: event.beneficiary.to_string(),
+                ..Self::default()
+            },
+            StablecoinEvent::InheritanceClaimStopped(event) => Self {
+                owner: event.owner.to_string(),
+                ..Self::default()
+            },
+            StablecoinEvent::InheritanceClaimed(event) => Self {
+                owner: event.owner.to_string(),
+                destination: event.beneficiary.to_string(),
+                amount: event.amount.to_string(),
+                ..Self::default()
+            },
+            StablecoinEvent::ObligationQueued(event) => Self {
+                owner: event.debtor.to_string(),
+                destination: event.creditor.to_string(),
+                amount: event.amount.to_string(),
+                purpose: event
+                    .purpose
+                    .map(|code| String::from_utf8_lossy(&code).into_owned())
+                    .unwrap_or_default(),
+                ..Self::default()
+            },
+            StablecoinEvent::NetSettled(event) => Self {
+                amount: event.net.to_string(),
+                ..Self::default()
+            },
+            StablecoinEvent::ChannelClosing(event) => Self {
+                owner: event.sender.to_string(),
+                destination: event.recipient.to_string(),
+                amount: event.amount.to_string(),
+                ..Self::default()
+            },
+            StablecoinEvent::ChannelSettled(event) => Self {
+                owner: event.sender.to_string(),
+                destination: event.recipient.to_string(),
+                amount: event.amount.to_string(),
+                ..Self::default()
+            },
+            StablecoinEvent::SubAccountRegistered(event) => Self {
+                owner: event.owner.to_string(),
+                token_account: event.custody.to_string(),
+                purpose: String::from_utf8_lossy(&event.purpose).into_owned(),
+                ..Self::default()
+            },
+            StablecoinEvent::SubAccountSwept(event) => Self {
+                owner: event.owner.to_string(),
+                destination: pda::token_account(&event.owner).to_string(),
+                token_account: pda::sub_account_custody(&event.owner, event.index).to_string(),
+                amount: event.amount.to_string(),
+                ..Self::default()
+            },
+            StablecoinEvent::AirdropOpened(event) => Self {
+                owner: event.funder.to_string(),
+                token_account: pda::airdrop_custody(&event.funder, event.id).to_string(),
+                amount: event.total.to_string(),
+                ..Self::default()
+            },
+            StablecoinEvent::AirdropClaimed(event) => Self {
+                owner: event.funder.to_string(),
+                destination: pda::token_account(&event.recipient).to_string(),
+                token_account: pda::airdrop_custody(&event.funder, event.id).to_string(),
+                amount: event.amount.to_string(),
+                ..Self::default()
+            },
+            StablecoinEvent::AirdropClawedBack(event) => Self {
+                owner: event.funder.to_string(),
+                destination: pda::treasury_custody().to_string(),
+                token_account: pda::airdrop_custody(&event.funder, event.id).to_string(),
+                amount: event.amount.to_string(),
+                ..Self::default()
+            },
+            StablecoinEvent::AccountResized(event) => Self {
+                admin: event.admin.to_string(),
+                ..Self::default()
+            },
+        }
+    }
+}
+
+/// One CSV line; addresses and numbers never need quoting
+fn write_row(
+    out: &mut impl Write,
+    tx: &TransactionEvents,
+    event: &StablecoinEvent,
+) -> io::Result<()> {
+    let block_time = tx
+        .block_time
+        .and_then(|time| DateTime::from_timestamp(time, 0))
+        .map(|time| time.to_rfc3339_opts(SecondsFormat::Secs, true))
+        .unwrap_or_default();
+    let Fields {
+        admin,
+        minter,
+        owner,
+        destination,
+        token_account,
+        amount,
+        allowance,
+        purpose,
+    } = Fields::from(event);
+    writeln!(
+        out,
+        "{},{block_time},{},{},{admin},{minter},{owner},{destination},{token_account},{amount},{allowance},{purpose}",
+        tx.slot,
+        tx.signature,
+        event.name()
+    )
+}
+
+pub async fn export(
+    rpc: &RpcClient,
+    path: &str,
+    from: Option<NaiveDate>,
+    to: Option<NaiveDate>,
+) -> Result<ExportOutput, CliError> {
+    let history = transactions(rpc).await?;
+
+    let write_error = |source| CliError::Write {
+        path: path.to_string(),
+        source,
+    };
+    let mut out = BufWriter::new(File::create(path).map_err(write_error)?);
+    writeln!(out, "{HEADER}").map_err(write_error)?;
+
+    let (mut rows, mut minted, mut burned) = (0, 0u128, 0u128);
+    for tx in &history {
+        if !in_range(tx, from, to)? {
+            continue;
+        }
+        for event in &tx.events {
+            write_row(&mut out, tx, event).map_err(write_error)?;
+            rows += 1;
+            match event {
+                StablecoinEvent::TokensMinted(event) => minted += u128::from(event.amount),
+                StablecoinEvent::TokensBurned(event) => burned += u128::from(event.amount),
+                _ => {}
+            }
+        }
+    }
+    out.flush().map_err(write_error)?;
+
+    // Only the full history adds up to the supply
+    let supply = match (from, to) {
+        (None, None) => {
+            let supply = rpc
+                .get_token_supply(&pda::mint().0)
+                .await
+                .map_err(ClientError::from)?;
+            supply.amount.parse::<u64>().ok()
+        }
+        _ => None,
+    };
+
+    Ok(ExportOutput {
+        path: path.to_string(),
+        rows,
+        minted,
+        burned,
+        supply,
+        unexplained: supply.map(|supply| i128::from(supply) - (minted as i128 - burned as i128)),
+    })
+}
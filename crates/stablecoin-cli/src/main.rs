@@ -0,0 +1,219 @@
+//! `stablecoin-cli`: operate the stablecoin program from the command line
+//!
+//! Cluster URL, keypair and commitment come from the Solana CLI config
+//! unless overridden with `--url`, `--keypair` and `--commitment`.
+//!
+//! With `--output json`, results are printed as JSON on stdout and errors as
+//! JSON on stderr. Failures exit with a code per failure class, listed in
+//! `--help`.
+//!
+//! With `--propose`, transactions are printed unsigned for multisig review
+//! instead of being sent. With `--dry-run`, they are simulated and the state
+//! changes they would make are printed.
+
+mod cli;
+mod config;
+mod decode;
+mod dry_run;
+mod error;
+mod history;
+mod output;
+mod propose;
+mod signer;
+
+use std::process::ExitCode;
+
+use solana_signer::Signer;
+use stablecoin_client::{
+    accounts, instructions,
+    send::{send_and_confirm_with_retry, RetryConfig},
+    TxBuilder,
+};
+
+use crate::{
+    cli::{Cli, Command, HistoryCommand},
+    config::Settings,
+    error::CliError,
+    output::{AccountMeta, MinterState, MintersOutput, Readback, State, TransactionOutput},
+};
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    let cli = Cli::parse_args();
+    let format = cli.global.output;
+    match run(cli).await {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            output::print_error(format, &err);
+            ExitCode::from(err.exit_code())
+        }
+    }
+}
+
+async fn run(cli: Cli) -> Result<(), CliError> {
+    let settings = Settings::resolve(&cli.global)?;
+    let rpc = settings.rpc_client();
+    let format = cli.global.output;
+
+    if let Command::Minters = cli.command {
+        let minters = accounts::list_minters(&rpc).await?;
+        let output = MintersOutput {
+            minters: minters.iter().map(MinterState::from).collect(),
+        };
+        output::print(format, &output);
+        return Ok(());
+    }
+    if let Command::Decode { transaction } = &cli.command {
+        let output = decode::decode(&rpc, transaction).await?;
+        output::print(format, &output);
+        return Ok(());
+    }
+    if let Command::History {
+        command: HistoryCommand::Export { out, from, to },
+    } = &cli.command
+    {
+        let output = history::export(&rpc, out, *from, *to).await?;
+        output::print(format, &output);
+        return Ok(());
+    }
+
+    // With --authority the instruction is only proposed or simulated, and no
+    // key is needed
+    let (authority, signer) = match cli.proposal.authority {
+        Some(authority) => (authority, None),
+        None => {
+            let signer = settings.signer()?;
+            (signer.pubkey(), Some(signer))
+        }
+    };
+    let (instruction, readback) = match cli.command {
+        Command::Initialize => (
+            instructions::initialize(&authority),
+            Readback {
+                config: true,
+                ..Readback::default()
+            },
+        ),
+        Command::ConfigureMinter { minter, allowance } => (
+            instructions::configure_minter(&authority, &minter, allowance.base_units()),
+            Readback {
+                minter: Some(minter),
+                ..Readback::default()
+            },
+        ),
+        Command::RemoveMinter { minter } => (
+            instructions::remove_minter(&authority, &minter),
+            Readback {
+                minter: Some(minter),
+                ..Readback::default()
+            },
+        ),
+        Command::Mint { amount, recipient } => {
+            let recipient = recipient.unwrap_or(authority);
+            (
+                instructions::mint_tokens(&authority, &recipient, amount.base_units()),
+                Readback {
+                    minter: Some(authority),
+                    token_owner: Some(recipient),
+                    ..Readback::default()
+                },
+            )
+        }
+        Command::PauseSelf => (
+            instructions::pause_self(&authority),
+            Readback {
+                minter: Some(authority),
+                ..Readback::default()
+            },
+        ),
+        Command::ResumeMinter { minter } => (
+            instructions::resume_minter(&authority, &minter),
+            Readback {
+                minter: Some(minter),
+                ..Readback::default()
+            },
+        ),
+        Command::Burn { amount } => (
+            instructions::burn_tokens(&authority, amount.base_units()),
+            Readback {
+                token_owner: Some(authority),
+                ..Readback::default()
+            },
+        ),
+        Command::Pause => (
+            instructions::pause(&authority),
+            Readback {
+                config: true,
+                ..Readback::default()
+            },
+        ),
+        Command::Unpause => (
+            instructions::unpause(&authority),
+            Readback {
+                config: true,
+                ..Readback::default()
+            },
+        ),
+        Command::Minters | Command::Decode { .. } | Command::History { .. } => {
+            unreachable!("handled above")
+        }
+    };
+
+    if cli.proposal.propose {
+        let proposal = propose::propose(
+            &rpc,
+            &cli.proposal,
+            authority,
+            instruction,
+            settings.compute_unit_price,
+        )
+        .await?;
+        output::print(format, &proposal);
+        return Ok(());
+    }
+    let mut builder = TxBuilder::new(authority)
+        .instruction(instruction.clone())
+        .simulate_compute_units();
+    if let Some(micro_lamports) = settings.compute_unit_price {
+        builder = builder.compute_unit_price(micro_lamports);
+    }
+    if cli.proposal.dry_run {
+        let output = dry_run::dry_run(&rpc, &builder, &instruction, &readback).await?;
+        output::print(format, &output);
+        return Ok(());
+    }
+    let signer = signer.expect("--authority requires --propose or --dry-run");
+
+    let accounts = instruction
+        .accounts
+        .iter()
+        .map(|meta| AccountMeta {
+            pubkey: meta.pubkey.to_string(),
+            is_signer: meta.is_signer,
+            is_writable: meta.is_writable,
+        })
+        .collect();
+    let signer = signer::review(signer, &instruction);
+    let config = RetryConfig {
+        commitment: settings.commitment,
+        ..RetryConfig::default()
+    };
+    let signature = send_and_confirm_with_retry(&rpc, &builder, &[&signer], &config).await?;
+
+    // The transaction has landed, so a failed read must not turn into a
+    // failure exit that invites a retry
+    let state = match State::fetch(&rpc, &readback).await {
+        Ok(state) => Some(state),
+        Err(err) => {
+            eprintln!("warning: transaction confirmed, but reading state back failed: {err}");
+            None
+        }
+    };
+    let output = TransactionOutput {
+        signature: signature.to_string(),
+        accounts,
+        state,
+    };
+    output::print(format, &output);
+    Ok(())
+}
@@ -0,0 +1,466 @@
+//! Command results, as text or JSON
+//!
+//! JSON results go to stdout and errors to stderr. Token amounts are
+//! strings, so they survive parsers that read numbers as doubles.
+
+use std::fmt;
+
+use anchor_lang::prelude::Pubkey;
+use clap::ValueEnum;
+use serde::{Serialize, Serializer};
+use solana_rpc_client::nonblocking::rpc_client::RpcClient;
+use stablecoin_client::{
+    accounts::{self, MinterAccount},
+    events::StablecoinEvent,
+    pda, ClientError,
+};
+
+use crate::{
+    error::{CliError, ErrorOutput, ProgramError},
+    propose::TxEncoding,
+};
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum OutputFormat {
+    #[default]
+    Display,
+    Json,
+}
+
+pub fn print<T: Serialize + fmt::Display>(format: OutputFormat, value: &T) {
+    match format {
+        OutputFormat::Display => print!("{value}"),
+        OutputFormat::Json => println!("{}", to_json(value)),
+    }
+}
+
+pub fn print_error(format: OutputFormat, err: &CliError) {
+    match format {
+        OutputFormat::Display => {
+            eprintln!("error: {err}");
+            if let Some(hint) = err.hint() {
+                eprintln!("hint: {hint}");
+            }
+        }
+        OutputFormat::Json => eprintln!(
+            "{}",
+            to_json(&serde_json::json!({ "error": ErrorOutput::from(err) }))
+        ),
+    }
+}
+
+fn to_json(value: &impl Serialize) -> String {
+    serde_json::to_string_pretty(value).expect("output is always serializable")
+}
+
+fn as_string<T: fmt::Display, S: Serializer>(value: &T, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.collect_str(value)
+}
+
+fn option_as_string<T: fmt::Display, S: Serializer>(
+    value: &Option<T>,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    match value {
+        Some(value) => serializer.collect_str(value),
+        None => serializer.serialize_none(),
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct AccountMeta {
+    pub pubkey: String,
+    pub is_signer: bool,
+    pub is_writable: bool,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ConfigState {
+    pub address: String,
+    pub admin: String,
+    pub mint: String,
+    pub paused: bool,
+}
+
+#[derive(Debug, Serialize)]
+pub struct MinterState {
+    pub address: String,
+    pub minter: String,
+    #[serde(serialize_with = "as_string")]
+    pub allowance: u64,
+    #[serde(serialize_with = "as_string")]
+    pub amount_minted: u64,
+    #[serde(serialize_with = "as_string")]
+    pub remaining_allowance: u64,
+}
+
+impl From<&MinterAccount> for MinterState {
+    fn from(account: &MinterAccount) -> Self {
+        Self {
+            address: account.address.to_string(),
+            minter: account.config.minter.to_string(),
+            allowance: account.config.allowance,
+            amount_minted: account.config.amount_minted,
+            remaining_allowance: account.remaining_allowance(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct TokenAccountState {
+    pub address: String,
+    pub owner: String,
+    /// Balance in base units
+    pub amount: String,
+}
+
+/// Which accounts to read back once a transaction has confirmed
+#[derive(Debug, Default)]
+pub struct Readback {
+    pub config: bool,
+    pub minter: Option<Pubkey>,
+    pub token_owner: Option<Pubkey>,
+}
+
+/// Program state after a transaction; `null` where an account was not read
+/// or no longer exists
+#[derive(Debug, Default, Serialize)]
+pub struct State {
+    pub config: Option<ConfigState>,
+    pub minter: Option<MinterState>,
+    pub token_account: Option<TokenAccountState>,
+}
+
+impl State {
+    pub async fn fetch(rpc: &RpcClient, readback: &Readback) -> Result<Self, CliError> {
+        let mut state = Self::default();
+        if readback.config {
+            let config = accounts::fetch_config(rpc).await?;
+            state.config = Some(ConfigState {
+                address: pda::config().0.to_string(),
+                admin: config.admin.to_string(),
+                mint: config.mint.to_string(),
+                paused: config.paused,
+            });
+        }
+        if let Some(minter) = &readback.minter {
+            state.minter = accounts::fetch_minter(rpc, minter)
+                .await?
+                .as_ref()
+                .map(MinterState::from);
+        }
+        if let Some(owner) = &readback.token_owner {
+            let address = pda::token_account(owner);
+            let balance = rpc
+                .get_token_account_balance(&address)
+                .await
+                .map_err(ClientError::from)?;
+            state.token_account = Some(TokenAccountState {
+                address: address.to_string(),
+                owner: owner.to_string(),
+                amount: balance.amount,
+            });
+        }
+        Ok(state)
+    }
+}
+
+/// Result of a command that sends a transaction
+#[derive(Debug, Serialize)]
+pub struct TransactionOutput {
+    pub signature: String,
+    /// Accounts the program instruction touched
+    pub accounts: Vec<AccountMeta>,
+    /// `null` if the transaction confirmed but reading state back failed
+    pub state: Option<State>,
+}
+
+impl fmt::Display for TransactionOutput {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Signature: {}", self.signature)?;
+        let Some(state) = &self.state else {
+            return Ok(());
+        };
+        if let Some(config) = &state.config {
+            writeln!(
+                f,
+                "Config {}: admin {}, mint {}, paused {}",
+                config.address, config.admin, config.mint, config.paused
+            )?;
+        }
+        if let Some(minter) = &state.minter {
+            writeln!(f, "{minter}")?;
+        }
+        if let Some(token_account) = &state.token_account {
+            writeln!(
+                f,
+                "Token account {} (owner {}): {}",
+                token_account.address, token_account.owner, token_account.amount
+            )?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for MinterState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}  allowance {}  minted {}  remaining {}",
+            self.minter, self.allowance, self.amount_minted, self.remaining_allowance
+        )
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct MintersOutput {
+    pub minters: Vec<MinterState>,
+}
+
+impl fmt::Display for MintersOutput {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for minter in &self.minters {
+            writeln!(f, "{minter}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Result of `--propose`
+#[derive(Debug, Serialize)]
+pub struct ProposalOutput {
+    pub instruction: String,
+    /// The unsigned transaction
+    pub transaction: String,
+    pub encoding: TxEncoding,
+    /// Hash a hardware wallet shows when blind-signing the message
+    pub message_hash: String,
+    /// Recent blockhash, or the nonce value when built against a nonce
+    pub blockhash: String,
+    pub nonce_account: Option<String>,
+    /// Keys whose signatures the transaction needs, fee payer first
+    pub signers: Vec<String>,
+}
+
+impl fmt::Display for ProposalOutput {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{}", self.transaction)?;
+        writeln!(f)?;
+        writeln!(f, "Instruction: {}", self.instruction)?;
+        writeln!(f, "Message hash: {}", self.message_hash)?;
+        match &self.nonce_account {
+            Some(nonce_account) => writeln!(f, "Nonce: {} ({nonce_account})", self.blockhash)?,
+            None => writeln!(f, "Blockhash: {}", self.blockhash)?,
+        }
+        writeln!(f, "Signers:")?;
+        for signer in &self.signers {
+            writeln!(f, "  {signer}")?;
+        }
+        Ok(())
+    }
+}
+
+/// One field a transaction would change
+#[derive(Debug, Serialize)]
+pub struct Change {
+    pub account: &'static str,
+    pub address: String,
+    pub field: &'static str,
+    /// `null` if the account does not exist yet
+    pub old: Option<String>,
+    /// `null` if the account would be closed
+    pub new: Option<String>,
+}
+
+/// Result of `--dry-run`
+#[derive(Debug, Serialize)]
+pub struct DryRunOutput {
+    pub instruction: String,
+    pub units_consumed: Option<u64>,
+    pub changes: Vec<Change>,
+}
+
+impl fmt::Display for DryRunOutput {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Dry run of {}, nothing was sent", self.instruction)?;
+        if let Some(units) = self.units_consumed {
+            writeln!(f, "Compute units: {units}")?;
+        }
+        if self.changes.is_empty() {
+            return writeln!(f, "No changes");
+        }
+        let mut address = None;
+        for change in &self.changes {
+            if address != Some(&change.address) {
+                writeln!(f, "{} {}", change.account, change.address)?;
+                address = Some(&change.address);
+            }
+            writeln!(
+                f,
+                "  {:<20} {} -> {}",
+                change.field,
+                change.old.as_deref().unwrap_or("(none)"),
+                change.new.as_deref().unwrap_or("(none)")
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Result of `history export`
+#[derive(Debug, Serialize)]
+pub struct ExportOutput {
+    pub path: String,
+    pub rows: usize,
+    /// Total of the exported mints, in base units
+    #[serde(serialize_with = "as_string")]
+    pub minted: u128,
+    /// Total of the exported burns, in base units
+    #[serde(serialize_with = "as_string")]
+    pub burned: u128,
+    /// The mint's supply now; `null` unless the whole history was exported
+    #[serde(serialize_with = "option_as_string")]
+    pub supply: Option<u64>,
+    /// Supply not accounted for by minted minus burned, negative when tokens
+    /// were burned outside the program
+    #[serde(serialize_with = "option_as_string")]
+    pub unexplained: Option<i128>,
+}
+
+impl fmt::Display for ExportOutput {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Wrote {} rows to {}", self.rows, self.path)?;
+        writeln!(f, "Minted {}, burned {}", self.minted, self.burned)?;
+        match (self.supply, self.unexplained) {
+            (Some(supply), Some(0)) => writeln!(f, "Supply {supply}: reconciled"),
+            (Some(supply), Some(unexplained)) => writeln!(
+                f,
+                "Supply {supply}: differs from minted minus burned by {unexplained}"
+            ),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// One account an instruction names
+#[derive(Debug, Serialize)]
+pub struct DecodedAccount {
+    /// The account's name in the IDL; `null` for another program's
+    /// instruction
+    pub name: Option<String>,
+    pub pubkey: String,
+    pub is_signer: bool,
+    pub is_writable: bool,
+}
+
+#[derive(Debug, Serialize)]
+pub struct DecodedArg {
+    pub name: &'static str,
+    /// Amounts in tokens
+    pub value: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct DecodedInstruction {
+    /// Position in the transaction; `1.2` is the second instruction the
+    /// second top-level one invoked
+    pub index: String,
+    pub program: String,
+    /// `null` for another program's instruction, or data this program does
+    /// not recognize
+    pub name: Option<&'static str>,
+    pub args: Vec<DecodedArg>,
+    pub accounts: Vec<DecodedAccount>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct DecodedEvent {
+    pub name: &'static str,
+    pub fields: String,
+}
+
+impl From<&StablecoinEvent> for DecodedEvent {
+    fn from(event: &StablecoinEvent) -> Self {
+        let name = event.name();
+        // `Name(Name { field: value, .. })`, less the wrapping
+        let debug = format!("{event:?}");
+        let fields = debug
+            .strip_prefix(&format!("{name}({name} {{ "))
+            .and_then(|fields| fields.strip_suffix(" })"))
+            .map_or_else(|| debug.clone(), str::to_string);
+        Self { name, fields }
+    }
+}
+
+/// Result of `decode`
+#[derive(Debug, Serialize)]
+pub struct DecodeOutput {
+    /// `null` for an unsigned transaction
+    pub signature: Option<String>,
+    /// Where this and the fields below are `null`, the transaction was
+    /// given rather than fetched
+    pub slot: Option<u64>,
+    pub success: Option<bool>,
+    pub error: Option<String>,
+    /// Custom error code and name, when the program returned one
+    pub program_error: Option<ProgramError>,
+    /// Each top-level instruction, followed by the ones it invoked
+    pub instructions: Vec<DecodedInstruction>,
+    /// The program's events, empty unless it was fetched and succeeded
+    pub events: Vec<DecodedEvent>,
+}
+
+impl fmt::Display for DecodeOutput {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(signature) = &self.signature {
+            writeln!(f, "Signature: {signature}")?;
+        }
+        if let Some(slot) = self.slot {
+            writeln!(f, "Slot: {slot}")?;
+        }
+        match (&self.error, &self.program_error) {
+            (Some(error), program_error) => {
+                writeln!(f, "Failed: {error}")?;
+                if let Some(hint) = program_error.as_ref().and_then(|error| error.hint) {
+                    writeln!(f, "Hint: {hint}")?;
+                }
+            }
+            (None, _) if self.success == Some(true) => writeln!(f, "Succeeded")?,
+            (None, _) => {}
+        }
+        for instruction in &self.instructions {
+            writeln!(f)?;
+            match instruction.name {
+                Some(name) => writeln!(f, "#{} {name}", instruction.index)?,
+                None => writeln!(f, "#{} {}", instruction.index, instruction.program)?,
+            }
+            for arg in &instruction.args {
+                writeln!(f, "  {:<14}{}", arg.name, arg.value)?;
+            }
+            for account in &instruction.accounts {
+                let mut roles = Vec::new();
+                if account.is_signer {
+                    roles.push("signer");
+                }
+                if account.is_writable {
+                    roles.push("writable");
+                }
+                writeln!(
+                    f,
+                    "  {:<24}  {:<44}  {}",
+                    account.name.as_deref().unwrap_or("-"),
+                    account.pubkey,
+                    roles.join(", ")
+                )?;
+            }
+        }
+        if !self.events.is_empty() {
+            writeln!(f)?;
+            writeln!(f, "Events:")?;
+            for event in &self.events {
+                writeln!(f, "  {} {{ {} }}", event.name, event.fields)?;
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,75 @@
+//! `--propose`: build the transaction for multisig review instead of sending
+//!
+//! The unsigned transaction is printed for the council to sign offline, for
+//! example with the client's `offline` module, or to import into a multisig
+//! UI that accepts serialized transactions. Without `--nonce` it is built
+//! against a recent blockhash and must be signed and sent within about a
+//! minute.
+
+use anchor_lang::prelude::Pubkey;
+use clap::ValueEnum;
+use serde::Serialize;
+use solana_instruction::Instruction;
+use solana_rpc_client::nonblocking::rpc_client::RpcClient;
+use stablecoin_client::{instructions, offline, ClientError, TxBuilder};
+
+use crate::{cli::ProposalArgs, error::CliError, output::ProposalOutput};
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TxEncoding {
+    #[default]
+    Base64,
+    Base58,
+}
+
+pub async fn propose(
+    rpc: &RpcClient,
+    args: &ProposalArgs,
+    authority: Pubkey,
+    instruction: Instruction,
+    compute_unit_price: Option<u64>,
+) -> Result<ProposalOutput, CliError> {
+    let fee_payer = args.fee_payer.unwrap_or(authority);
+    let name = instructions::decode(&instruction.data).map(|decoded| decoded.name());
+    let mut builder = TxBuilder::new(fee_payer).instruction(instruction);
+    if let Some(micro_lamports) = compute_unit_price {
+        builder = builder.compute_unit_price(micro_lamports);
+    }
+
+    let blockhash = match args.nonce {
+        Some(nonce_account) => {
+            builder =
+                builder.durable_nonce(nonce_account, args.nonce_authority.unwrap_or(fee_payer));
+            offline::fetch_nonce(rpc, &nonce_account).await?
+        }
+        None => {
+            eprintln!(
+                "warning: no --nonce given, the transaction expires with its blockhash in about a minute"
+            );
+            rpc.get_latest_blockhash()
+                .await
+                .map_err(ClientError::from)?
+        }
+    };
+
+    let tx = builder.build_unsigned(blockhash);
+    let transaction = match args.encoding {
+        TxEncoding::Base64 => offline::encode_transaction(&tx),
+        TxEncoding::Base58 => offline::encode_transaction_base58(&tx),
+    }
+    .map_err(ClientError::from)?;
+
+    Ok(ProposalOutput {
+        instruction: name.unwrap_or("unknown").to_string(),
+        transaction,
+        encoding: args.encoding,
+        message_hash: solana_sha256_hasher::hash(&tx.message_data()).to_string(),
+        blockhash: blockhash.to_string(),
+        nonce_account: args.nonce.map(|nonce| nonce.to_string()),
+        signers: offline::missing_signers(&tx)
+            .iter()
+            .map(ToString::to_string)
+            .collect(),
+    })
+}
@@ -0,0 +1,211 @@
+//! Signers named by `--keypair`
+//!
+//! Either a keypair file or a URI in the Solana CLI's formats:
+//! `usb://ledger[/<wallet pubkey>][?key=<account>[/<change>]]` for a hardware
+//! wallet, or `prompt://[?key=<account>[/<change>]]` to derive the key from a
+//! BIP39 seed phrase and optional passphrase typed at a prompt. The
+//! derivation path can also be given with `--derivation-path`. Hardware
+//! wallets need a build with the `ledger` feature.
+//!
+//! The Solana Ledger app cannot decode this program's instructions, so it
+//! asks to blind-sign a message hash. Before every such request the CLI
+//! prints what is being signed together with that hash, to be compared with
+//! the one on the device.
+
+use std::{fmt::Write as _, rc::Rc};
+
+use anchor_lang::prelude::Pubkey;
+use solana_clap_utils::keypair::keypair_from_seed_phrase;
+use solana_derivation_path::DerivationPath;
+use solana_instruction::Instruction;
+use solana_keypair::read_keypair_file;
+use solana_remote_wallet::{
+    locator::Locator, remote_keypair::generate_remote_keypair, remote_wallet::RemoteWalletManager,
+};
+use solana_signature::Signature;
+use solana_signer::{Signer, SignerError};
+use stablecoin_client::{instructions, pda};
+use uriparse::URIReference;
+
+use crate::{decode, error::CliError};
+
+const USB_SCHEME: &str = "usb://";
+const PROMPT_SCHEME: &str = "prompt:";
+
+/// Parse `--derivation-path`, either `<account>[/<change>]` or a full path
+/// such as `m/44'/501'/0'/0'`
+pub fn parse_derivation_path(path: &str) -> Result<DerivationPath, String> {
+    let parsed = if path.starts_with("m/") {
+        DerivationPath::from_absolute_path_str(path)
+    } else {
+        DerivationPath::from_key_str(path)
+    };
+    parsed.map_err(|err| err.to_string())
+}
+
+pub fn signer_from_path(
+    path: &str,
+    derivation_path: Option<&DerivationPath>,
+) -> Result<Box<dyn Signer>, CliError> {
+    let signer = if path.starts_with(USB_SCHEME) {
+        remote_signer(path, derivation_path)
+    } else if path.starts_with(PROMPT_SCHEME) {
+        seed_phrase_signer(path, derivation_path)
+    } else if derivation_path.is_some() {
+        Err("--derivation-path needs a prompt:// or usb:// signer".to_string())
+    } else {
+        read_keypair_file(path)
+            .map(|keypair| Box::new(keypair) as Box<dyn Signer>)
+            .map_err(|err| err.to_string())
+    };
+    signer.map_err(|message| CliError::Keypair {
+        path: path.to_string(),
+        message,
+    })
+}
+
+/// The derivation path from the URI's query or `--derivation-path`, which
+/// must not both give one
+fn choose_derivation_path(
+    query: Option<DerivationPath>,
+    flag: Option<&DerivationPath>,
+) -> Result<Option<DerivationPath>, String> {
+    match (query, flag) {
+        (Some(_), Some(_)) => {
+            Err("derivation path given both in the URI and with --derivation-path".to_string())
+        }
+        (query, flag) => Ok(query.or_else(|| flag.cloned())),
+    }
+}
+
+fn seed_phrase_signer(
+    path: &str,
+    derivation_path: Option<&DerivationPath>,
+) -> Result<Box<dyn Signer>, String> {
+    let uri = URIReference::try_from(path).map_err(|err| err.to_string())?;
+    let query = DerivationPath::from_uri_any_query(&uri).map_err(|err| err.to_string())?;
+    let derivation_path = choose_derivation_path(query, derivation_path)?;
+
+    // Prompts for the phrase, then the passphrase, without echoing either
+    let keypair = keypair_from_seed_phrase("keypair", false, false, derivation_path, false)
+        .map_err(|err| err.to_string())?;
+    Ok(Box::new(keypair))
+}
+
+fn remote_signer(
+    path: &str,
+    derivation_path: Option<&DerivationPath>,
+) -> Result<Box<dyn Signer>, String> {
+    let uri = URIReference::try_from(path).map_err(|err| err.to_string())?;
+    let locator = Locator::new_from_uri(&uri).map_err(|err| err.to_string())?;
+    let query = DerivationPath::from_uri_key_query(&uri).map_err(|err| err.to_string())?;
+    let derivation_path = choose_derivation_path(query, derivation_path)?.unwrap_or_default();
+
+    let wallet_manager = wallet_manager()?;
+    let keypair =
+        generate_remote_keypair(locator, derivation_path, &wallet_manager, false, "keypair")
+            .map_err(|err| err.to_string())?;
+    Ok(Box::new(keypair))
+}
+
+#[cfg(feature = "ledger")]
+fn wallet_manager() -> Result<Rc<RemoteWalletManager>, String> {
+    solana_remote_wallet::remote_wallet::maybe_wallet_manager()
+        .map_err(|err| err.to_string())?
+        .ok_or_else(|| "no hardware wallet found".to_string())
+}
+
+#[cfg(not(feature = "ledger"))]
+fn wallet_manager() -> Result<Rc<RemoteWalletManager>, String> {
+    Err("hardware wallets need a build with the `ledger` feature".to_string())
+}
+
+/// Wrap `signer` so that an interactive one shows `instruction` first
+pub fn review(signer: Box<dyn Signer>, instruction: &Instruction) -> Box<dyn Signer> {
+    if !signer.is_interactive() {
+        return signer;
+    }
+    Box::new(Reviewed {
+        summary: summarize(instruction),
+        signer,
+    })
+}
+
+/// Prints the summary and message hash before every signing request
+struct Reviewed {
+    signer: Box<dyn Signer>,
+    summary: String,
+}
+
+impl Signer for Reviewed {
+    fn try_pubkey(&self) -> Result<Pubkey, SignerError> {
+        self.signer.try_pubkey()
+    }
+
+    fn try_sign_message(&self, message: &[u8]) -> Result<Signature, SignerError> {
+        eprintln!(
+            "{}  Message hash  {}\n\nApprove on the device only if it shows the same message hash.",
+            self.summary,
+            solana_sha256_hasher::hash(message)
+        );
+        self.signer.try_sign_message(message)
+    }
+
+    fn is_interactive(&self) -> bool {
+        true
+    }
+}
+
+fn summarize(instruction: &Instruction) -> String {
+    let mut summary = String::from("Transaction to sign\n");
+    let _ = writeln!(summary, "  Program       {}", instruction.program_id);
+    match instructions::decode(&instruction.data) {
+        Some(decoded) => {
+            let _ = writeln!(summary, "  Instruction   {}", decoded.name());
+            for (label, value) in decode::args(&decoded) {
+                let _ = writeln!(summary, "  {label:<14}{value}");
+            }
+        }
+        None => {
+            let _ = writeln!(summary, "  Instruction   unknown");
+        }
+    }
+
+    summary.push_str("  Accounts\n");
+    for (index, meta) in instruction.accounts.iter().enumerate() {
+        let mut roles = Vec::new();
+        if let Some(label) = label(&meta.pubkey) {
+            roles.push(label);
+        }
+        if meta.is_signer {
+            roles.push("signer");
+        }
+        if meta.is_writable {
+            roles.push("writable");
+        }
+        let _ = writeln!(
+            summary,
+            "    {index:>2}  {:<44}  {}",
+            meta.pubkey,
+            roles.join(", ")
+        );
+    }
+    summary
+}
+
+/// Names for the accounts every caller shares
+fn label(address: &Pubkey) -> Option<&'static str> {
+    if *address == pda::config().0 {
+        Some("config")
+    } else if *address == pda::mint().0 {
+        Some("mint")
+    } else if *address == anchor_spl::token::ID {
+        Some("token program")
+    } else if *address == anchor_spl::associated_token::ID {
+        Some("associated token program")
+    } else if *address == anchor_lang::system_program::ID {
+        Some("system program")
+    } else {
+        None
+    }
+}
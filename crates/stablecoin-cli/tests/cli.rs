@@ -0,0 +1,626 @@
+use std::{
+    io::{BufRead, BufReader, Read, Write},
+    net::TcpListener,
+    process::{Command, Output},
+};
+
+use anchor_lang::{prelude::Pubkey, AccountSerialize, Event};
+use base64::{engine::general_purpose::STANDARD, Engine as _};
+use serde_json::{json, Value};
+use solana_instruction::Instruction;
+use stablecoin::{Config, TokensMinted};
+use stablecoin_client::{
+    instructions,
+    offline::{decode_transaction_base58, encode_transaction, encode_transaction_base58},
+    TxBuilder,
+};
+
+fn cli(args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_stablecoin-cli"))
+        // Keep the developer's Solana CLI config out of the way
+        .env("HOME", env!("CARGO_TARGET_TMPDIR"))
+        .args(args)
+        .output()
+        .unwrap()
+}
+
+/// Answer JSON-RPC requests with `results` in turn, returning the server's URL
+fn serve(results: Vec<Value>) -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    std::thread::spawn(move || {
+        for result in results {
+            let (stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream);
+            let mut content_length = 0;
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                if line == "\r\n" {
+                    break;
+                }
+                if let Some((name, value)) = line.split_once(':') {
+                    if name.eq_ignore_ascii_case("content-length") {
+                        content_length = value.trim().parse().unwrap();
+                    }
+                }
+            }
+            let mut body = vec![0; content_length];
+            reader.read_exact(&mut body).unwrap();
+            let request: Value = serde_json::from_slice(&body).unwrap();
+
+            let response =
+                json!({ "jsonrpc": "2.0", "id": request["id"], "result": result }).to_string();
+            write!(
+            reader.get_mut(),
+            "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{response}",
+            response.len()
+        )
+        .unwrap();
+        }
+    });
+    url
+}
+
+#[test]
+fn test_help_lists_subcommands() {
+    let output = cli(&["--help"]);
+    assert!(output.status.success());
+    let help = String::from_utf8(output.stdout).unwrap();
+    for subcommand in [
+        "initialize",
+        "configure-minter",
+        "remove-minter",
+        "mint",
+        "pause-self",
+        "resume-minter",
+        "burn",
+        "pause",
+        "unpause",
+        "minters",
+    ] {
+        assert!(help.contains(subcommand), "missing {subcommand}");
+    }
+}
+
+#[test]
+fn test_invalid_address_is_a_usage_error() {
+    let output = cli(&["configure-minter", "not-an-address", "1000"]);
+    assert_eq!(output.status.code(), Some(2));
+}
+
+#[test]
+fn test_amount_beyond_mint_decimals_is_a_usage_error() {
+    let output = cli(&["mint", "0.0000001"]);
+    assert_eq!(output.status.code(), Some(2));
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.contains("more than 6 decimal places"), "{stderr}");
+}
+
+#[test]
+fn test_missing_keypair_is_reported() {
+    let output = cli(&[
+        "--url",
+        "http://127.0.0.1:1",
+        "--keypair",
+        "/nonexistent/id.json",
+        "pause",
+    ]);
+    assert_eq!(output.status.code(), Some(3));
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.contains("failed to read keypair /nonexistent/id.json"));
+}
+
+#[test]
+fn test_missing_explicit_config_is_reported() {
+    let output = cli(&["--config", "/nonexistent/config.yml", "pause"]);
+    assert_eq!(output.status.code(), Some(3));
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.contains("failed to load config file /nonexistent/config.yml"));
+}
+
+#[test]
+fn test_json_error_output() {
+    let output = cli(&[
+        "--output",
+        "json",
+        "--keypair",
+        "/nonexistent/id.json",
+        "pause",
+    ]);
+    assert_eq!(output.status.code(), Some(3));
+    assert!(output.stdout.is_empty());
+
+    let error: Value = serde_json::from_slice(&output.stderr).unwrap();
+    assert_eq!(error["error"]["class"], "config");
+    assert_eq!(error["error"]["exit_code"], 3);
+    assert!(error["error"]["program_error"].is_null());
+}
+
+#[test]
+fn test_unreachable_rpc_exit_code() {
+    let output = cli(&["--output", "json", "--url", "http://127.0.0.1:1", "minters"]);
+    assert_eq!(output.status.code(), Some(4));
+
+    let error: Value = serde_json::from_slice(&output.stderr).unwrap();
+    assert_eq!(error["error"]["class"], "rpc");
+}
+
+#[test]
+fn test_help_lists_exit_codes() {
+    let output = cli(&["--help"]);
+    let help = String::from_utf8(output.stdout).unwrap();
+    assert!(help.contains("Exit codes:"));
+    assert!(help.contains("5  transaction rejected by the program"));
+}
+
+#[test]
+fn test_usb_signer_without_ledger_support() {
+    if cfg!(feature = "ledger") {
+        return;
+    }
+    let output = cli(&["--keypair", "usb://ledger?key=0/0", "pause"]);
+    assert_eq!(output.status.code(), Some(3));
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.contains("`ledger` feature"), "{stderr}");
+}
+
+#[test]
+fn test_propose_prints_unsigned_transaction() {
+    let admin = Pubkey::new_unique();
+    let fee_payer = Pubkey::new_unique();
+    let url = serve(vec![json!({
+        "context": { "slot": 1 },
+        "value": {
+            "blockhash": "11111111111111111111111111111111",
+            "lastValidBlockHeight": 100,
+        },
+    })]);
+
+    let output = cli(&[
+        "--url",
+        &url,
+        "--output",
+        "json",
+        "--propose",
+        "--authority",
+        &admin.to_string(),
+        "--fee-payer",
+        &fee_payer.to_string(),
+        "--encoding",
+        "base58",
+        "pause",
+    ]);
+    assert!(output.status.success(), "{output:?}");
+
+    let proposal: Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(proposal["instruction"], "pause");
+    assert_eq!(proposal["encoding"], "base58");
+    assert_eq!(
+        proposal["signers"],
+        json!([fee_payer.to_string(), admin.to_string()])
+    );
+
+    let tx = decode_transaction_base58(proposal["transaction"].as_str().unwrap()).unwrap();
+    assert_eq!(tx.message.account_keys[..2], [fee_payer, admin]);
+    assert!(tx
+        .signatures
+        .iter()
+        .all(|signature| *signature == Default::default()));
+}
+
+#[test]
+fn test_authority_requires_propose() {
+    let output = cli(&["--authority", &Pubkey::new_unique().to_string(), "pause"]);
+    assert_eq!(output.status.code(), Some(2));
+}
+
+/// Write a profiles file under the test target dir, returning its path
+fn profiles_file(name: &str, contents: &str) -> String {
+    let path = format!("{}/{name}.toml", env!("CARGO_TARGET_TMPDIR"));
+    std::fs::write(&path, contents).unwrap();
+    path
+}
+
+#[test]
+fn test_profile_supplies_settings() {
+    let path = profiles_file(
+        "profiles-settings",
+        &format!(
+            r#"
+[profiles.devnet]
+url = "http://127.0.0.1:1"
+program_id = "{}"
+keypair = "/nonexistent/devnet.json"
+compute_unit_price = 1000
+"#,
+            stablecoin_client::PROGRAM_ID
+        ),
+    );
+
+    let output = cli(&["--profiles-file", &path, "--profile", "devnet", "pause"]);
+    assert_eq!(output.status.code(), Some(3));
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.contains("/nonexistent/devnet.json"), "{stderr}");
+
+    // Flags win over the profile
+    let output = cli(&[
+        "--profiles-file",
+        &path,
+        "--profile",
+        "devnet",
+        "--keypair",
+        "/nonexistent/flag.json",
+        "pause",
+    ]);
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.contains("/nonexistent/flag.json"), "{stderr}");
+}
+
+#[test]
+fn test_unknown_profile_is_reported() {
+    let path = profiles_file("profiles-unknown", "[profiles.devnet]\nurl = \"devnet\"\n");
+    let output = cli(&["--profiles-file", &path, "--profile", "mainnet", "pause"]);
+    assert_eq!(output.status.code(), Some(3));
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.contains("profile \"mainnet\" not found"), "{stderr}");
+}
+
+#[test]
+fn test_profile_for_another_program_is_rejected() {
+    let path = profiles_file(
+        "profiles-program",
+        &format!(
+            "[profiles.mainnet]\nprogram_id = \"{}\"\n",
+            Pubkey::new_unique()
+        ),
+    );
+    let output = cli(&["--profiles-file", &path, "--profile", "mainnet", "minters"]);
+    assert_eq!(output.status.code(), Some(3));
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.contains("but this build targets"), "{stderr}");
+}
+
+#[test]
+fn test_profile_rejects_unknown_keys() {
+    let path = profiles_file("profiles-typo", "[profiles.devnet]\nrpc_url = \"devnet\"\n");
+    let output = cli(&["--profiles-file", &path, "--profile", "devnet", "pause"]);
+    assert_eq!(output.status.code(), Some(3));
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.contains("unknown field `rpc_url`"), "{stderr}");
+}
+
+#[test]
+fn test_dry_run_prints_changes() {
+    let (admin, mint) = (Pubkey::new_unique(), Pubkey::new_unique());
+    let config = |paused: bool| {
+        let mut data = Vec::new();
+        Config {
+            admin,
+            mint,
+            paused,
+            bump: 255,
+            mint_bump: 254,
+            delegate_bump: 253,
+            minter_set_freeze_bump: 252,
+        }
+        .try_serialize(&mut data)
+        .unwrap();
+        json!({
+            "lamports": 1_000_000,
+            "data": [STANDARD.encode(&data), "base64"],
+            "owner": stablecoin_client::PROGRAM_ID.to_string(),
+            "executable": false,
+            "rentEpoch": 0,
+            "space": data.len(),
+        })
+    };
+    let url = serve(vec![
+        json!({ "context": { "slot": 1 }, "value": [config(false)] }),
+        json!({
+            "context": { "slot": 1 },
+            "value": { "err": null, "logs": [], "unitsConsumed": 3_000, "accounts": [config(true)] },
+        }),
+    ]);
+
+    let output = cli(&[
+        "--url",
+        &url,
+        "--output",
+        "json",
+        "--dry-run",
+        "--authority",
+        &admin.to_string(),
+        "pause",
+    ]);
+    assert!(output.status.success(), "{output:?}");
+
+    let dry_run: Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(dry_run["instruction"], "pause");
+    assert_eq!(dry_run["units_consumed"], 3_000);
+    assert_eq!(
+        dry_run["changes"],
+        json!([{
+            "account": "config",
+            "address": stablecoin_client::pda::config().0.to_string(),
+            "field": "paused",
+            "old": "false",
+            "new": "true",
+        }])
+    );
+}
+
+#[test]
+fn test_dry_run_conflicts_with_propose() {
+    let output = cli(&["--dry-run", "--propose", "pause"]);
+    assert_eq!(output.status.code(), Some(2));
+}
+
+#[test]
+fn test_invalid_derivation_path_is_a_usage_error() {
+    let output = cli(&["--derivation-path", "0/x", "pause"]);
+    assert_eq!(output.status.code(), Some(2));
+}
+
+#[test]
+fn test_derivation_path_needs_derivable_signer() {
+    let output = cli(&[
+        "--keypair",
+        "/nonexistent/id.json",
+        "--derivation-path",
+        "0/0",
+        "pause",
+    ]);
+    assert_eq!(output.status.code(), Some(3));
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.contains("prompt:// or usb://"), "{stderr}");
+}
+
+#[test]
+fn test_derivation_path_given_twice() {
+    let output = cli(&[
+        "--keypair",
+        "prompt://?key=0/0",
+        "--derivation-path",
+        "m/44'/501'/1'/0'",
+        "pause",
+    ]);
+    assert_eq!(output.status.code(), Some(3));
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.contains("given both in the URI"), "{stderr}");
+}
+
+#[test]
+fn test_history_export() {
+    let signature =
+        "5VERv8NMvzbJMEkV8xnrLkEaWRtSz9CosKDYjCJjBRnbJLgp8uirBgmQpjKhoR4tjF3ZpRzrFmBV6UjKdiSZkQUW";
+    let event = TokensMinted {
+        minter: Pubkey::new_unique(),
+        destination: Pubkey::new_unique(),
+        amount: 1_000,
+        remaining_allowance: 0,
+    };
+    let status = json!([{
+        "signature": signature,
+        "slot": 5,
+        "err": null,
+        "memo": null,
+        "blockTime": 1_767_225_600,
+    }]);
+    let program = stablecoin_client::PROGRAM_ID.to_string();
+    let url = serve(vec![
+        // The config and the mint both list the transaction
+        status.clone(),
+        status,
+        json!({
+            "slot": 5,
+            "blockTime": 1_767_225_600,
+            "transaction": ["", "base64"],
+            "meta": {
+                "err": null,
+                "status": { "Ok": null },
+                "fee": 5_000,
+                "preBalances": [],
+                "postBalances": [],
+                "logMessages": [
+                    format!("Program {program} invoke [1]"),
+                    format!("Program data: {}", STANDARD.encode(event.data())),
+                    format!("Program {program} success"),
+                ],
+            },
+        }),
+        json!({
+            "context": { "slot": 6 },
+            "value": { "amount": "900", "decimals": 6, "uiAmount": 0.0009, "uiAmountString": "0.0009" },
+        }),
+    ]);
+    let path = format!("{}/history.csv", env!("CARGO_TARGET_TMPDIR"));
+
+    let output = cli(&[
+        "--url", &url, "--output", "json", "history", "export", "--out", &path,
+    ]);
+    assert!(output.status.success(), "{output:?}");
+
+    let summary: Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(summary["rows"], 1);
+    assert_eq!(summary["minted"], "1000");
+    assert_eq!(summary["supply"], "900");
+    // 100 tokens were burned without going through the program
+    assert_eq!(summary["unexplained"], "-100");
+
+    let csv = std::fs::read_to_string(&path).unwrap();
+    let lines: Vec<&str> = csv.lines().collect();
+    assert_eq!(lines.len(), 2);
+    assert!(lines[0].starts_with("slot,block_time,signature,event"));
+    assert_eq!(
+        lines[1],
+        format!(
+            "5,2026-01-01T00:00:00Z,{signature},TokensMinted,,{},,{},,1000,,",
+            event.minter, event.destination
+        )
+    );
+}
+
+#[test]
+fn test_decode_transaction_offline() {
+    let admin = Pubkey::new_unique();
+    let minter = Pubkey::new_unique();
+    let tx = TxBuilder::new(admin)
+        .instruction(instructions::configure_minter(&admin, &minter, 1_500_000))
+        .build_unsigned(Default::default());
+    let encoded = encode_transaction_base58(&tx).unwrap();
+
+    // No --url: nothing is fetched
+    let output = cli(&["--output", "json", "decode", &encoded]);
+    assert!(output.status.success(), "{output:?}");
+
+    let decoded: Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert!(decoded["signature"].is_null());
+    assert!(decoded["success"].is_null());
+    let instruction = &decoded["instructions"][0];
+    assert_eq!(instruction["name"], "configure_minter");
+    assert_eq!(
+        instruction["args"],
+        json!([{ "name": "Allowance", "value": "1.5 tokens" }])
+    );
+    assert_eq!(
+        instruction["accounts"][0],
+        json!({
+            "name": "admin",
+            "pubkey": admin.to_string(),
+            "is_signer": true,
+            "is_writable": true,
+        })
+    );
+    assert_eq!(instruction["accounts"][2]["name"], "minter");
+    assert_eq!(instruction["accounts"][2]["pubkey"], minter.to_string());
+}
+
+#[test]
+fn test_decode_invalid_transaction_is_a_usage_error() {
+    let output = cli(&["decode", "not-a-transaction"]);
+    assert_eq!(output.status.code(), Some(2));
+}
+
+/// `getTransaction` result for `instruction`, sent alone, with `meta` merged
+/// into its status
+fn fetched(instruction: Instruction, payer: Pubkey, meta: Value) -> Value {
+    let tx = TxBuilder::new(payer)
+        .instruction(instruction)
+        .build_unsigned(Default::default());
+    let mut status = json!({
+        "err": null,
+        "status": { "Ok": null },
+        "fee": 5_000,
+        "preBalances": [],
+        "postBalances": [],
+        "logMessages": [],
+    });
+    status
+        .as_object_mut()
+        .unwrap()
+        .extend(meta.as_object().unwrap().clone());
+    json!({
+        "slot": 5,
+        "blockTime": 1_767_225_600,
+        "transaction": [encode_transaction(&tx).unwrap(), "base64"],
+        "meta": status,
+    })
+}
+
+#[test]
+fn test_decode_fetched_transaction() {
+    let signature =
+        "5VERv8NMvzbJMEkV8xnrLkEaWRtSz9CosKDYjCJjBRnbJLgp8uirBgmQpjKhoR4tjF3ZpRzrFmBV6UjKdiSZkQUW";
+    let minter = Pubkey::new_unique();
+    let instruction = instructions::mint_tokens(&minter, &minter, 2_000_000);
+    let event = TokensMinted {
+        minter,
+        destination: Pubkey::new_unique(),
+        amount: 2_000_000,
+        remaining_allowance: 0,
+    };
+    let program = stablecoin_client::PROGRAM_ID.to_string();
+    let token_program = TxBuilder::new(minter)
+        .instruction(instruction.clone())
+        .build_unsigned(Default::default())
+        .message
+        .account_keys
+        .iter()
+        .position(|key| *key == anchor_spl::token::ID)
+        .unwrap();
+    let url = serve(vec![fetched(
+        instruction,
+        minter,
+        json!({
+            "innerInstructions": [{
+                "index": 0,
+                "instructions": [{
+                    "programIdIndex": token_program,
+                    "accounts": [],
+                    "data": "",
+                    "stackHeight": 2,
+                }],
+            }],
+            "logMessages": [
+                format!("Program {program} invoke [1]"),
+                format!("Program data: {}", STANDARD.encode(event.data())),
+                format!("Program {program} success"),
+            ],
+        }),
+    )]);
+
+    let output = cli(&["--url", &url, "--output", "json", "decode", signature]);
+    assert!(output.status.success(), "{output:?}");
+
+    let decoded: Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(decoded["signature"], signature);
+    assert_eq!(decoded["slot"], 5);
+    assert_eq!(decoded["success"], true);
+    let instructions = decoded["instructions"].as_array().unwrap();
+    assert_eq!(instructions[0]["index"], "0");
+    assert_eq!(instructions[0]["name"], "mint_tokens");
+    assert_eq!(instructions[0]["args"][0]["value"], "2 tokens");
+    // The mint to the destination, made by CPI
+    assert_eq!(instructions[1]["index"], "0.1");
+    assert_eq!(
+        instructions[1]["program"],
+        anchor_spl::token::ID.to_string()
+    );
+    assert!(instructions[1]["name"].is_null());
+    assert_eq!(decoded["events"][0]["name"], "TokensMinted");
+    assert_eq!(
+        decoded["events"][0]["fields"],
+        format!(
+            "minter: {minter}, destination: {}, amount: 2000000, remaining_allowance: 0",
+            event.destination
+        )
+    );
+}
+
+#[test]
+fn test_decode_failed_transaction() {
+    let signature =
+        "5VERv8NMvzbJMEkV8xnrLkEaWRtSz9CosKDYjCJjBRnbJLgp8uirBgmQpjKhoR4tjF3ZpRzrFmBV6UjKdiSZkQUW";
+    let minter = Pubkey::new_unique();
+    let url = serve(vec![fetched(
+        instructions::mint_tokens(&minter, &minter, 1),
+        minter,
+        json!({
+            "err": { "InstructionError": [0, { "Custom": 6000 }] },
+            "status": { "Err": { "InstructionError": [0, { "Custom": 6000 }] } },
+        }),
+    )]);
+
+    let output = cli(&["--url", &url, "decode", signature]);
+    // Decoding succeeded, whatever the transaction did
+    assert!(output.status.success(), "{output:?}");
+
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(
+        stdout.contains("Failed: instruction 0: Unauthorized (6000)"),
+        "{stdout}"
+    );
+    assert!(stdout.contains("#0 mint_tokens"), "{stdout}");
+    assert!(!stdout.contains("Events:"), "{stdout}");
+}
@@ -0,0 +1,297 @@
+//! Reading the program's accounts
+//!
+//! Minter configs are enumerated with `getProgramAccounts`, filtered on the
+//! account discriminator and size. Listing is two-step so that large sets
+//! can be paged: addresses first, with no account data, then the accounts
+//! themselves in `getMultipleAccounts` batches.
+
+use anchor_lang::{prelude::Pubkey, AccountDeserialize};
+use stablecoin::{
+    Airdrop, Config, MinterConfig, MinterPause, MinterSetFreeze, MinterTombstone, PaymentChannel,
+    SubAccount, SupplyStats,
+};
+
+use crate::error::{ClientError, Result};
+
+#[cfg(feature = "rpc")]
+use {
+    solana_account_decoder_client_types::{UiAccountEncoding, UiDataSliceConfig},
+    solana_rpc_client::nonblocking::rpc_client::RpcClient,
+    solana_rpc_client_api::config::{RpcAccountInfoConfig, RpcProgramAccountsConfig},
+};
+
+/// Most accounts a single `getMultipleAccounts` request may ask for
+pub const MAX_MULTIPLE_ACCOUNTS: usize = 100;
+
+/// A minter config together with its address
+#[derive(Debug, Clone)]
+pub struct MinterAccount {
+    pub address: Pubkey,
+    pub config: MinterConfig,
+}
+
+impl MinterAccount {
+    pub fn remaining_allowance(&self) -> u64 {
+        self.config
+            .allowance
+            .saturating_sub(self.config.amount_minted)
+    }
+}
+
+/// Decode a `MinterConfig`, checking its discriminator
+pub fn decode_minter_config(address: Pubkey, data: &[u8]) -> Result<MinterAccount> {
+    let config = MinterConfig::try_deserialize(&mut &data[..])
+        .map_err(|source| ClientError::AccountDecode { address, source })?;
+    Ok(MinterAccount { address, config })
+}
+
+/// Decode the program's `Config`, checking its discriminator
+pub fn decode_config(address: Pubkey, data: &[u8]) -> Result<Config> {
+    Config::try_deserialize(&mut &data[..])
+        .map_err(|source| ClientError::AccountDecode { address, source })
+}
+
+/// Decode the program's `SupplyStats`, checking its discriminator
+pub fn decode_supply_stats(address: Pubkey, data: &[u8]) -> Result<SupplyStats> {
+    SupplyStats::try_deserialize(&mut &data[..])
+        .map_err(|source| ClientError::AccountDecode { address, source })
+}
+
+/// Decode the program's `MinterSetFreeze`, checking its discriminator
+pub fn decode_minter_set_freeze(address: Pubkey, data: &[u8]) -> Result<MinterSetFreeze> {
+    MinterSetFreeze::try_deserialize(&mut &data[..])
+        .map_err(|source| ClientError::AccountDecode { address, source })
+}
+
+/// Decode a `MinterPause`, checking its discriminator
+pub fn decode_minter_pause(address: Pubkey, data: &[u8]) -> Result<MinterPause> {
+    MinterPause::try_deserialize(&mut &data[..])
+        .map_err(|source| ClientError::AccountDecode { address, source })
+}
+
+/// Decode a `MinterTombstone`, checking its discriminator
+pub fn decode_minter_tombstone(address: Pubkey, data: &[u8]) -> Result<MinterTombstone> {
+    MinterTombstone::try_deserialize(&mut &data[..])
+        .map_err(|source| ClientError::AccountDecode { address, source })
+}
+
+/// Decode a `PaymentChannel`, checking its discriminator
+pub fn decode_payment_channel(address: Pubkey, data: &[u8]) -> Result<PaymentChannel> {
+    PaymentChannel::try_deserialize(&mut &data[..])
+        .map_err(|source| ClientError::AccountDecode { address, source })
+}
+
+/// Decode a `SubAccount`, checking its discriminator
+pub fn decode_sub_account(address: Pubkey, data: &[u8]) -> Result<SubAccount> {
+    SubAccount::try_deserialize(&mut &data[..])
+        .map_err(|source| ClientError::AccountDecode { address, source })
+}
+
+/// Decode an `Airdrop`, checking its discriminator
+pub fn decode_airdrop(address: Pubkey, data: &[u8]) -> Result<Airdrop> {
+    Airdrop::try_deserialize(&mut &data[..])
+        .map_err(|source| ClientError::AccountDecode { address, source })
+}
+
+/// One page of minters, in address order
+#[derive(Debug, Clone)]
+pub struct MinterPage {
+    pub minters: Vec<MinterAccount>,
+    /// Pass as `after` to fetch the next page; `None` on the last one
+    pub next: Option<Pubkey>,
+}
+
+/// Fetch the program's config
+#[cfg(feature = "rpc")]
+pub async fn fetch_config(rpc: &RpcClient) -> Result<Config> {
+    let (address, _) = crate::pda::config();
+    let account = rpc
+        .get_account_with_commitment(&address, rpc.commitment())
+        .await?
+        .value
+        .ok_or(ClientError::AccountNotFound(address))?;
+    decode_config(address, &account.data)
+}
+
+/// Fetch the program's supply stats
+#[cfg(feature = "rpc")]
+pub async fn fetch_supply_stats(rpc: &RpcClient) -> Result<SupplyStats> {
+    let (address, _) = crate::pda::supply_stats();
+    let account = rpc
+        .get_account_with_commitment(&address, rpc.commitment())
+        .await?
+        .value
+        .ok_or(ClientError::AccountNotFound(address))?;
+    decode_supply_stats(address, &account.data)
+}
+
+/// Fetch the minter set freeze, `None` if the set is not frozen
+#[cfg(feature = "rpc")]
+pub async fn fetch_minter_set_freeze(rpc: &RpcClient) -> Result<Option<MinterSetFreeze>> {
+    let (address, _) = crate::pda::minter_set_freeze();
+    rpc.get_account_with_commitment(&address, rpc.commitment())
+        .await?
+        .value
+        .map(|account| decode_minter_set_freeze(address, &account.data))
+        .transpose()
+}
+
+/// Fetch `minter`'s pause marker, `None` if it has not paused itself
+#[cfg(feature = "rpc")]
+pub async fn fetch_minter_pause(rpc: &RpcClient, minter: &Pubkey) -> Result<Option<MinterPause>> {
+    let (address, _) = crate::pda::minter_pause(minter);
+    rpc.get_account_with_commitment(&address, rpc.commitment())
+        .await?
+        .value
+        .map(|account| decode_minter_pause(address, &account.data))
+        .transpose()
+}
+
+/// Fetch `minter`'s tombstone, `None` if it has never been removed
+#[cfg(feature = "rpc")]
+pub async fn fetch_minter_tombstone(
+    rpc: &RpcClient,
+    minter: &Pubkey,
+) -> Result<Option<MinterTombstone>> {
+    let (address, _) = crate::pda::minter_tombstone(minter);
+    rpc.get_account_with_commitment(&address, rpc.commitment())
+        .await?
+        .value
+        .map(|account| decode_minter_tombstone(address, &account.data))
+        .transpose()
+}
+
+/// Fetch one minter's config, `None` if it is not a minter
+#[cfg(feature = "rpc")]
+pub async fn fetch_minter(rpc: &RpcClient, minter: &Pubkey) -> Result<Option<MinterAccount>> {
+    let (address, _) = crate::pda::minter_config(minter);
+    rpc.get_account_with_commitment(&address, rpc.commitment())
+        .await?
+        .value
+        .map(|account| decode_minter_config(address, &account.data))
+        .transpose()
+}
+
+/// Fetch the payment channel from `sender` to `recipient`, `None` if there
+/// is none open
+///
+/// Balance updates sign its `nonce`, so both sides read it before
+/// signing or checking one.
+#[cfg(feature = "rpc")]
+pub async fn fetch_payment_channel(
+    rpc: &RpcClient,
+    sender: &Pubkey,
+    recipient: &Pubkey,
+) -> Result<Option<PaymentChannel>> {
+    let (address, _) = crate::pda::payment_channel(sender, recipient);
+    rpc.get_account_with_commitment(&address, rpc.commitment())
+        .await?
+        .value
+        .map(|account| decode_payment_channel(address, &account.data))
+        .transpose()
+}
+
+/// Fetch `owner`'s sub-account numbered `index`, `None` if it is not
+/// registered
+#[cfg(feature = "rpc")]
+pub async fn fetch_sub_account(
+    rpc: &RpcClient,
+    owner: &Pubkey,
+    index: u64,
+) -> Result<Option<SubAccount>> {
+    let (address, _) = crate::pda::sub_account(owner, index);
+    rpc.get_account_with_commitment(&address, rpc.commitment())
+        .await?
+        .value
+        .map(|account| decode_sub_account(address, &account.data))
+        .transpose()
+}
+
+/// Fetch `funder`'s airdrop with `id`, `None` if it was never opened or
+/// has been clawed back
+#[cfg(feature = "rpc")]
+pub async fn fetch_airdrop(rpc: &RpcClient, funder: &Pubkey, id: u64) -> Result<Option<Airdrop>> {
+    let (address, _) = crate::pda::airdrop(funder, id);
+    rpc.get_account_with_commitment(&address, rpc.commitment())
+        .await?
+        .value
+        .map(|account| decode_airdrop(address, &account.data))
+        .transpose()
+}
+
+/// Addresses of every minter config, sorted
+#[cfg(feature = "rpc")]
+pub async fn minter_addresses(rpc: &RpcClient) -> Result<Vec<Pubkey>> {
+    let config = RpcProgramAccountsConfig {
+        filters: Some(crate::filters::account_type::<MinterConfig>()),
+        account_config: RpcAccountInfoConfig {
+            encoding: Some(UiAccountEncoding::Base64),
+            // Addresses only
+            data_slice: Some(UiDataSliceConfig {
+                offset: 0,
+                length: 0,
+            }),
+            commitment: Some(rpc.commitment()),
+            min_context_slot: None,
+        },
+        with_context: None,
+        sort_results: None,
+    };
+
+    let mut addresses: Vec<Pubkey> = rpc
+        .get_program_accounts_with_config(&crate::PROGRAM_ID, config)
+        .await?
+        .into_iter()
+        .map(|(address, _)| address)
+        .collect();
+    addresses.sort();
+    Ok(addresses)
+}
+
+/// Fetch and decode the given minter configs, skipping ones that were closed
+#[cfg(feature = "rpc")]
+pub async fn fetch_minters(rpc: &RpcClient, addresses: &[Pubkey]) -> Result<Vec<MinterAccount>> {
+    let mut minters = Vec::with_capacity(addresses.len());
+    for batch in addresses.chunks(MAX_MULTIPLE_ACCOUNTS) {
+        let accounts = rpc
+            .get_multiple_accounts_with_commitment(batch, rpc.commitment())
+            .await?
+            .value;
+        for (address, account) in batch.iter().zip(accounts) {
+            if let Some(account) = account {
+                minters.push(decode_minter_config(*address, &account.data)?);
+            }
+        }
+    }
+    Ok(minters)
+}
+
+/// Up to `limit` minters with addresses strictly after `after`
+///
+/// Every call re-lists addresses, so pages stay consistent with minters
+/// being added or removed in between, at the cost of one extra request.
+#[cfg(feature = "rpc")]
+pub async fn list_minters_page(
+    rpc: &RpcClient,
+    after: Option<Pubkey>,
+    limit: usize,
+) -> Result<MinterPage> {
+    let addresses = minter_addresses(rpc).await?;
+    let start = match after {
+        Some(after) => addresses.partition_point(|address| *address <= after),
+        None => 0,
+    };
+    let end = (start + limit).min(addresses.len());
+    let page = &addresses[start..end];
+
+    Ok(MinterPage {
+        minters: fetch_minters(rpc, page).await?,
+        next: page.last().copied().filter(|_| end < addresses.len()),
+    })
+}
+
+/// Every minter config
+#[cfg(feature = "rpc")]
+pub async fn list_minters(rpc: &RpcClient) -> Result<Vec<MinterAccount>> {
+    fetch_minters(rpc, &minter_addresses(rpc).await?).await
+}
@@ -0,0 +1,138 @@
+//! Token amounts, kept apart from base units
+//!
+//! The program takes and reports every amount in base units, each
+//! `10^-DECIMALS` of a token. People write amounts in tokens. Passing one
+//! where the other is expected is off by a factor of a million, and nothing
+//! on chain can tell. [`StableAmount`] holds base units and converts to and
+//! from tokens with the mint's decimals, so the conversion happens once,
+//! explicitly, where an amount enters from outside.
+//!
+//! ```
+//! use stablecoin_client::StableAmount;
+//!
+//! let amount: StableAmount = "12.5".parse().unwrap();
+//! assert_eq!(amount.base_units(), 12_500_000);
+//! assert_eq!(amount.to_string(), "12.5");
+//! ```
+
+use std::{fmt, str::FromStr};
+
+use stablecoin::DECIMALS;
+
+/// Base units in one token
+pub const BASE_UNITS_PER_TOKEN: u64 = 10u64.pow(DECIMALS as u32);
+
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum AmountError {
+    #[error("invalid amount {0:?}, expected a number of tokens such as 12.5")]
+    Invalid(String),
+    #[error("amount {0:?} has more than {DECIMALS} decimal places")]
+    TooPrecise(String),
+    #[error("amount {0:?} is too large")]
+    Overflow(String),
+}
+
+/// An amount of the stablecoin, in base units
+///
+/// Built from base units with [`StableAmount::from_base_units`], or from
+/// tokens with [`StableAmount::from_ui`] and [`StableAmount::from_tokens`].
+/// Instruction builders take base units, through
+/// [`StableAmount::base_units`]. Displays in tokens.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct StableAmount(u64);
+
+impl StableAmount {
+    pub const ZERO: Self = Self(0);
+    pub const MAX: Self = Self(u64::MAX);
+
+    pub const fn from_base_units(base_units: u64) -> Self {
+        Self(base_units)
+    }
+
+    pub const fn base_units(self) -> u64 {
+        self.0
+    }
+
+    /// A whole number of tokens, `None` if it overflows
+    pub const fn from_tokens(tokens: u64) -> Option<Self> {
+        match tokens.checked_mul(BASE_UNITS_PER_TOKEN) {
+            Some(base_units) => Some(Self(base_units)),
+            None => None,
+        }
+    }
+
+    /// Parse a number of tokens, such as `12.5`
+    ///
+    /// More decimal places than the mint has is an error rather than
+    /// rounded away.
+    pub fn from_ui(ui: &str) -> Result<Self, AmountError> {
+        let (whole, fraction) = ui.split_once('.').unwrap_or((ui, ""));
+        let digits = |part: &str| part.bytes().all(|byte| byte.is_ascii_digit());
+        if whole.is_empty()
+            || !digits(whole)
+            || !digits(fraction)
+            || (ui.contains('.') && fraction.is_empty())
+        {
+            return Err(AmountError::Invalid(ui.to_string()));
+        }
+        if fraction.len() > DECIMALS as usize {
+            return Err(AmountError::TooPrecise(ui.to_string()));
+        }
+
+        let overflow = || AmountError::Overflow(ui.to_string());
+        let whole: u64 = whole.parse().map_err(|_| overflow())?;
+        let fraction = format!("{fraction:0<width$}", width = DECIMALS as usize);
+        let fraction: u64 = fraction.parse().expect("checked digits");
+        whole
+            .checked_mul(BASE_UNITS_PER_TOKEN)
+            .and_then(|base_units| base_units.checked_add(fraction))
+            .map(Self)
+            .ok_or_else(overflow)
+    }
+
+    /// The number of tokens, without trailing zeros, such as `12.5`
+    pub fn to_ui(self) -> String {
+        let whole = self.0 / BASE_UNITS_PER_TOKEN;
+        let fraction = self.0 % BASE_UNITS_PER_TOKEN;
+        if fraction == 0 {
+            return whole.to_string();
+        }
+        let fraction = format!("{fraction:0>width$}", width = DECIMALS as usize);
+        format!("{whole}.{}", fraction.trim_end_matches('0'))
+    }
+
+    pub const fn checked_add(self, other: Self) -> Option<Self> {
+        match self.0.checked_add(other.0) {
+            Some(base_units) => Some(Self(base_units)),
+            None => None,
+        }
+    }
+
+    pub const fn checked_sub(self, other: Self) -> Option<Self> {
+        match self.0.checked_sub(other.0) {
+            Some(base_units) => Some(Self(base_units)),
+            None => None,
+        }
+    }
+}
+
+impl fmt::Display for StableAmount {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.to_ui())
+    }
+}
+
+/// Parses tokens, as [`StableAmount::from_ui`]
+impl FromStr for StableAmount {
+    type Err = AmountError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::from_ui(s)
+    }
+}
+
+impl From<StableAmount> for u64 {
+    fn from(amount: StableAmount) -> Self {
+        amount.base_units()
+    }
+}
@@ -0,0 +1,171 @@
+//! What a transaction will cost before it is sent
+//!
+//! [`estimate_cost`] simulates the transaction a [`TxBuilder`] describes for
+//! its compute units, suggests a priority fee from the fees recently paid
+//! to write the same accounts, and prices the rent of the accounts the
+//! simulation left created, grown or closed.
+//!
+//! ```ignore
+//! let estimate = estimate_cost(&rpc, &TxBuilder::new(payer).instruction(ix)).await?;
+//! let tx = TxBuilder::new(payer)
+//!     .instruction(ix)
+//!     .compute_unit_limit(estimate.compute_unit_limit)
+//!     .compute_unit_price(estimate.priority_fee_suggestion)
+//!     .build(&rpc, &[&payer_keypair])
+//!     .await?;
+//! ```
+
+use anchor_lang::prelude::Pubkey;
+use solana_hash::Hash;
+use solana_rpc_client::nonblocking::rpc_client::RpcClient;
+
+use crate::{
+    accounts::MAX_MULTIPLE_ACCOUNTS,
+    diagnostics::simulate_with_accounts,
+    error::Result,
+    tx::{apply_margin, TxBuilder, DEFAULT_SIMULATION_MARGIN_BPS, MAX_COMPUTE_UNIT_LIMIT},
+};
+
+/// Fee the runtime charges per signature, in lamports
+pub const LAMPORTS_PER_SIGNATURE: u64 = 5_000;
+
+/// Percentile of recent priority fees [`estimate_cost`] suggests
+pub const DEFAULT_FEE_PERCENTILE: u8 = 75;
+
+/// What a transaction is expected to cost its payer
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CostEstimate {
+    /// Compute units the simulation used
+    pub compute_units: u64,
+    /// Limit to request for them, with [`DEFAULT_SIMULATION_MARGIN_BPS`] on
+    /// top, as [`TxBuilder::simulate_compute_units`] would
+    pub compute_unit_limit: u32,
+    /// Priority fee, in micro-lamports per compute unit
+    pub priority_fee_suggestion: u64,
+    /// Signature fees, in lamports
+    pub base_fee: u64,
+    /// Lamports locked as rent in the accounts the transaction creates or
+    /// grows
+    pub rent: u64,
+    /// Lamports released from the accounts it closes
+    pub rent_refunded: u64,
+}
+
+impl CostEstimate {
+    /// The priority fee at the suggested price for the whole limit, in
+    /// lamports, as the runtime charges it
+    pub fn priority_fee(&self) -> u64 {
+        let micro_lamports =
+            u128::from(self.priority_fee_suggestion) * u128::from(self.compute_unit_limit);
+        micro_lamports.div_ceil(1_000_000) as u64
+    }
+
+    /// Base and priority fees, in lamports; rent is not a fee, as closing
+    /// the account returns it
+    pub fn fee(&self) -> u64 {
+        self.base_fee + self.priority_fee()
+    }
+}
+
+/// Estimate what the transaction `builder` describes will cost, suggesting
+/// the [`DEFAULT_FEE_PERCENTILE`] of recent priority fees
+///
+/// A failing simulation is returned as
+/// [`ClientError::Simulation`](crate::ClientError::Simulation), as from
+/// [`simulate`](crate::diagnostics::simulate).
+pub async fn estimate_cost(rpc: &RpcClient, builder: &TxBuilder) -> Result<CostEstimate> {
+    estimate_cost_with_percentile(rpc, builder, DEFAULT_FEE_PERCENTILE).await
+}
+
+/// [`estimate_cost`], suggesting the `percentile` of recent priority fees
+pub async fn estimate_cost_with_percentile(
+    rpc: &RpcClient,
+    builder: &TxBuilder,
+    percentile: u8,
+) -> Result<CostEstimate> {
+    let writable = writable_accounts(builder);
+    let signatures = builder
+        .build_versioned_unsigned(Hash::default())?
+        .signatures
+        .len() as u64;
+
+    let mut before = Vec::with_capacity(writable.len());
+    for batch in writable.chunks(MAX_MULTIPLE_ACCOUNTS) {
+        before.extend(
+            rpc.get_multiple_accounts_with_commitment(batch, rpc.commitment())
+                .await?
+                .value,
+        );
+    }
+    let simulation = simulate_with_accounts(rpc, builder, &writable).await?;
+    let compute_units = simulation
+        .units_consumed
+        .unwrap_or(MAX_COMPUTE_UNIT_LIMIT as u64);
+
+    let (mut rent, mut rent_refunded) = (0, 0);
+    for (before, after) in before.iter().zip(&simulation.accounts) {
+        match (before, after) {
+            (None, Some(data)) => {
+                rent += rpc
+                    .get_minimum_balance_for_rent_exemption(data.len())
+                    .await?;
+            }
+            (Some(account), Some(data)) if data.len() > account.data.len() => {
+                rent += rpc
+                    .get_minimum_balance_for_rent_exemption(data.len())
+                    .await?
+                    - rpc
+                        .get_minimum_balance_for_rent_exemption(account.data.len())
+                        .await?;
+            }
+            (Some(account), None) => rent_refunded += account.lamports,
+            _ => {}
+        }
+    }
+
+    let fees: Vec<u64> = rpc
+        .get_recent_prioritization_fees(&writable)
+        .await?
+        .into_iter()
+        .map(|fee| fee.prioritization_fee)
+        .collect();
+
+    Ok(CostEstimate {
+        compute_units,
+        compute_unit_limit: apply_margin(compute_units, DEFAULT_SIMULATION_MARGIN_BPS),
+        priority_fee_suggestion: fee_percentile(&fees, percentile),
+        base_fee: signatures * LAMPORTS_PER_SIGNATURE,
+        rent,
+        rent_refunded,
+    })
+}
+
+/// The accounts the transaction writes, the payer first
+fn writable_accounts(builder: &TxBuilder) -> Vec<Pubkey> {
+    let mut writable = vec![builder.payer()];
+    for meta in builder
+        .to_instructions()
+        .iter()
+        .flat_map(|instruction| &instruction.accounts)
+        .filter(|meta| meta.is_writable)
+    {
+        if !writable.contains(&meta.pubkey) {
+            writable.push(meta.pubkey);
+        }
+    }
+    writable
+}
+
+/// The nearest-rank `percentile` of `fees`, zero if there are none
+///
+/// Slots where no one paid to write the accounts count as zero fees, as the
+/// node reports them.
+pub fn fee_percentile(fees: &[u64], percentile: u8) -> u64 {
+    if fees.is_empty() {
+        return 0;
+    }
+    let mut fees = fees.to_vec();
+    fees.sort_unstable();
+    let rank = (fees.len() * usize::from(percentile.min(100))).div_ceil(100);
+    fees[rank.saturating_sub(1)]
+}
@@ -0,0 +1,325 @@
+//! Human-readable decoding of failed transactions
+//!
+//! A failure reaches the client as `custom program error: 0x1771`. This
+//! module maps the code back to [`StablecoinError`] and recovers what Anchor
+//! logged alongside it: the error name and message, the account or source
+//! line it came from, and the values a constraint compared.
+
+use std::fmt;
+
+use anchor_lang::error::ERROR_CODE_OFFSET;
+use solana_instruction::error::InstructionError;
+use solana_transaction_error::TransactionError;
+use stablecoin::StablecoinError;
+
+#[cfg(feature = "rpc")]
+use {
+    crate::{
+        error::{ClientError, Result},
+        events::{parse_logs, StablecoinEvent},
+        tx::TxBuilder,
+    },
+    anchor_lang::prelude::Pubkey,
+    solana_account_decoder_client_types::UiAccountEncoding,
+    solana_hash::Hash,
+    solana_rpc_client::nonblocking::rpc_client::RpcClient,
+    solana_rpc_client_api::config::{
+        RpcSimulateTransactionAccountsConfig, RpcSimulateTransactionConfig,
+    },
+};
+
+const PROGRAM_LOG: &str = "Program log: ";
+
+/// Map a custom error code back to the program's error enum
+pub fn decode_error_code(code: u32) -> Option<StablecoinError> {
+    macro_rules! try_decode {
+        ($($variant:ident),*) => {
+            $(
+                if code == u32::from(StablecoinError::$variant) {
+                    return Some(StablecoinError::$variant);
+                }
+            )*
+        };
+    }
+
+    if code < ERROR_CODE_OFFSET {
+        return None;
+    }
+    try_decode!(
+        Unauthorized,
+        Paused,
+        ExceedsAllowance,
+        NotMinter,
+        Overflow,
+        DuplicateMint,
+        IdempotencyRecordNotExpired,
+        NotOfficer,
+        InvalidOfficers,
+        AlreadyApproved,
+        InsufficientApprovals,
+        NotDust,
+        NotGuardian,
+        InvalidRecoveryConfig,
+        RecoveryInProgress,
+        NoRecoveryInProgress,
+        GuardianAlreadyApproved,
+        RecoveryNotApproved,
+        RecoveryChallengeActive,
+        NotBeneficiary,
+        InvalidInheritancePlan,
+        HolderStillActive,
+        InheritanceClaimInProgress,
+        NoInheritanceClaim,
+        InheritanceNoticeActive,
+        NotInstitution,
+        InvalidNettingWindow,
+        NettingWindowClosed,
+        NettingWindowOpen,
+        InvalidObligation,
+        InvalidSettlementAccounts,
+        InvalidPaymentChannel,
+        NotChannelParty,
+        InvalidChannelState,
+        InvalidChannelSignature,
+        ChannelDisputeOver,
+        ChannelDisputeActive,
+        InvalidPurposeCode,
+        MinterSetFrozen,
+        MinterPaused,
+        FaucetLimitExceeded,
+        InvalidAirdrop,
+        NotAirdropRecipient,
+        AirdropClaimed,
+        AirdropClosed,
+        AirdropOpen,
+        CannotResize,
+        MinterRetired,
+        NewerAccountVersion,
+        ObligationUnderfunded,
+        DelegateNotApproved
+    );
+    None
+}
+
+/// Where Anchor says an error came from
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ErrorOrigin {
+    /// An account constraint, by field name in the accounts struct
+    Account(String),
+    /// A `require!` or explicit `err!` in the program source
+    Source { file: String, line: u32 },
+}
+
+/// The `AnchorError ...` log line and the comparison that may follow it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AnchorErrorLog {
+    pub name: String,
+    pub number: u32,
+    pub message: String,
+    pub origin: Option<ErrorOrigin>,
+    /// Left and right side of a failed `require_eq!`-style or `has_one` check
+    pub compared_values: Option<(String, String)>,
+}
+
+/// Find the last Anchor error logged in `logs`
+pub fn parse_anchor_error(logs: &[String]) -> Option<AnchorErrorLog> {
+    let (index, mut error) = logs.iter().enumerate().rev().find_map(|(index, line)| {
+        Some((index, parse_error_line(line.strip_prefix(PROGRAM_LOG)?)?))
+    })?;
+
+    let rest: Vec<&str> = logs[index + 1..]
+        .iter()
+        .map_while(|line| line.strip_prefix(PROGRAM_LOG))
+        .collect();
+    error.compared_values = parse_compared_values(&rest);
+    Some(error)
+}
+
+/// `Left:`/`Right:` carry the value inline or, for pubkeys, on the next line
+fn parse_compared_values(lines: &[&str]) -> Option<(String, String)> {
+    match lines {
+        ["Left:", left, "Right:", right, ..] => Some((left.to_string(), right.to_string())),
+        [left, right, ..] => Some((
+            left.strip_prefix("Left: ")?.to_string(),
+            right.strip_prefix("Right: ")?.to_string(),
+        )),
+        _ => None,
+    }
+}
+
+fn parse_error_line(line: &str) -> Option<AnchorErrorLog> {
+    let rest = line.strip_prefix("AnchorError ")?;
+    let (origin, rest) = if let Some(rest) = rest.strip_prefix("caused by account: ") {
+        let (account, rest) = rest.split_once(". Error Code: ")?;
+        (Some(ErrorOrigin::Account(account.to_string())), rest)
+    } else if let Some(rest) = rest.strip_prefix("thrown in ") {
+        let (location, rest) = rest.split_once(". Error Code: ")?;
+        let (file, line) = location.rsplit_once(':')?;
+        let origin = ErrorOrigin::Source {
+            file: file.to_string(),
+            line: line.parse().ok()?,
+        };
+        (Some(origin), rest)
+    } else {
+        (None, rest.strip_prefix("occurred. Error Code: ")?)
+    };
+
+    let (name, rest) = rest.split_once(". Error Number: ")?;
+    let (number, message) = rest.split_once(". Error Message: ")?;
+    Some(AnchorErrorLog {
+        name: name.to_string(),
+        number: number.parse().ok()?,
+        message: message.strip_suffix('.').unwrap_or(message).to_string(),
+        origin,
+        compared_values: None,
+    })
+}
+
+/// Everything known about why a transaction failed
+#[derive(Debug, Clone)]
+pub struct ErrorDiagnostics {
+    pub error: TransactionError,
+    /// Index of the failing instruction, compute budget instructions included
+    pub instruction_index: Option<u8>,
+    /// The custom error code, when the failing program returned one
+    pub code: Option<u32>,
+    /// Set when the code is one of this program's errors
+    pub stablecoin_error: Option<StablecoinError>,
+    /// Set when the logs carry Anchor's error report
+    pub anchor_error: Option<AnchorErrorLog>,
+    pub logs: Vec<String>,
+}
+
+impl ErrorDiagnostics {
+    pub fn new(error: TransactionError, logs: Vec<String>) -> Self {
+        let (instruction_index, code) = match &error {
+            TransactionError::InstructionError(index, InstructionError::Custom(code)) => {
+                (Some(*index), Some(*code))
+            }
+            TransactionError::InstructionError(index, _) => (Some(*index), None),
+            _ => (None, None),
+        };
+        // Only trust the log when it explains this failure
+        let anchor_error =
+            parse_anchor_error(&logs).filter(|anchor_error| Some(anchor_error.number) == code);
+
+        Self {
+            error,
+            instruction_index,
+            code,
+            stablecoin_error: code.and_then(decode_error_code),
+            anchor_error,
+            logs,
+        }
+    }
+
+    /// What the caller can do about one of this program's errors
+    pub fn hint(&self) -> Option<&'static str> {
+        self.stablecoin_error.map(|error| error.info().hint)
+    }
+}
+
+impl fmt::Display for ErrorDiagnostics {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(index) = self.instruction_index {
+            write!(f, "instruction {index}: ")?;
+        }
+        match (&self.anchor_error, &self.stablecoin_error) {
+            (Some(anchor_error), _) => {
+                write!(
+                    f,
+                    "{} ({}): {}",
+                    anchor_error.name, anchor_error.number, anchor_error.message
+                )?;
+                match &anchor_error.origin {
+                    Some(ErrorOrigin::Account(account)) => write!(f, ", account `{account}`")?,
+                    Some(ErrorOrigin::Source { file, line }) => write!(f, ", at {file}:{line}")?,
+                    None => {}
+                }
+                if let Some((left, right)) = &anchor_error.compared_values {
+                    write!(f, ", left: {left}, right: {right}")?;
+                }
+                Ok(())
+            }
+            (None, Some(error)) => write!(f, "{} ({}): {error}", error.name(), u32::from(*error)),
+            (None, None) => match &self.error {
+                TransactionError::InstructionError(_, err) => write!(f, "{err}"),
+                err => write!(f, "{err}"),
+            },
+        }
+    }
+}
+
+/// A successful simulation
+#[cfg(feature = "rpc")]
+#[derive(Debug, Clone)]
+pub struct Simulation {
+    pub units_consumed: Option<u64>,
+    pub logs: Vec<String>,
+    pub events: Vec<StablecoinEvent>,
+    /// Data of the accounts asked for, as the transaction would leave them;
+    /// `None` where an account would not exist
+    pub accounts: Vec<Option<Vec<u8>>>,
+}
+
+/// Simulate the transaction `builder` describes, without signatures
+///
+/// A failure is returned as [`ClientError::Simulation`] carrying decoded
+/// [`ErrorDiagnostics`].
+#[cfg(feature = "rpc")]
+pub async fn simulate(rpc: &RpcClient, builder: &TxBuilder) -> Result<Simulation> {
+    simulate_with_accounts(rpc, builder, &[]).await
+}
+
+/// Simulate like [`simulate`], also returning `addresses` as the transaction
+/// would leave them
+#[cfg(feature = "rpc")]
+pub async fn simulate_with_accounts(
+    rpc: &RpcClient,
+    builder: &TxBuilder,
+    addresses: &[Pubkey],
+) -> Result<Simulation> {
+    // The blockhash is replaced by the node, and signatures are not checked
+    let tx = builder.build_versioned_unsigned(Hash::default())?;
+    let accounts = (!addresses.is_empty()).then(|| RpcSimulateTransactionAccountsConfig {
+        encoding: Some(UiAccountEncoding::Base64),
+        addresses: addresses.iter().map(ToString::to_string).collect(),
+    });
+    let result = rpc
+        .simulate_transaction_with_config(
+            &tx,
+            RpcSimulateTransactionConfig {
+                sig_verify: false,
+                replace_recent_blockhash: true,
+                commitment: Some(rpc.commitment()),
+                accounts,
+                ..RpcSimulateTransactionConfig::default()
+            },
+        )
+        .await?
+        .value;
+
+    let logs = result.logs.unwrap_or_default();
+    if let Some(err) = result.err {
+        return Err(ClientError::Simulation(Box::new(ErrorDiagnostics::new(
+            err.into(),
+            logs,
+        ))));
+    }
+
+    // Closed accounts come back empty and without lamports
+    let accounts = result
+        .accounts
+        .unwrap_or_default()
+        .into_iter()
+        .map(|account| account.filter(|account| account.lamports > 0))
+        .map(|account| account.and_then(|account| account.data.decode()))
+        .collect();
+
+    Ok(Simulation {
+        units_consumed: result.units_consumed,
+        events: parse_logs(&crate::PROGRAM_ID, &logs)?,
+        logs,
+        accounts,
+    })
+}
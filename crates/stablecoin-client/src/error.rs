@@ -0,0 +1,48 @@
+use crate::{events::EventError, offline::OfflineError};
+
+#[derive(Debug, thiserror::Error)]
+pub enum ClientError {
+    #[error(transparent)]
+    Event(#[from] EventError),
+    #[error(transparent)]
+    Offline(#[from] OfflineError),
+    #[cfg(feature = "rpc")]
+    #[error(transparent)]
+    Rpc(#[from] solana_rpc_client_api::client_error::Error),
+    #[cfg(feature = "rpc")]
+    #[error(transparent)]
+    Pubsub(#[from] solana_pubsub_client::nonblocking::pubsub_client::PubsubClientError),
+    #[error(transparent)]
+    Signer(#[from] solana_signer::SignerError),
+    #[error("failed to compile message: {0}")]
+    Compile(#[from] solana_message::CompileError),
+    #[error("{0} is not an address lookup table")]
+    InvalidLookupTable(anchor_lang::prelude::Pubkey),
+    #[cfg(feature = "rpc")]
+    #[error("transaction simulation failed: {0}")]
+    Simulation(Box<crate::diagnostics::ErrorDiagnostics>),
+    #[cfg(feature = "rpc")]
+    #[error("transaction {signature} failed: {diagnostics}")]
+    TransactionFailed {
+        signature: solana_signature::Signature,
+        diagnostics: Box<crate::diagnostics::ErrorDiagnostics>,
+    },
+    #[cfg(feature = "rpc")]
+    #[error("transaction expired before confirming, sent as {signatures:?}")]
+    Expired {
+        signatures: Vec<solana_signature::Signature>,
+    },
+    #[error("failed to decode account {address}: {source}")]
+    AccountDecode {
+        address: anchor_lang::prelude::Pubkey,
+        source: anchor_lang::error::Error,
+    },
+    #[error("account {0} not found")]
+    AccountNotFound(anchor_lang::prelude::Pubkey),
+    #[error("token account {0} holds a different mint")]
+    WrongMint(anchor_lang::prelude::Pubkey),
+    #[error("invalid signature returned by RPC: {0}")]
+    InvalidSignature(String),
+}
+
+pub type Result<T, E = ClientError> = std::result::Result<T, E>;
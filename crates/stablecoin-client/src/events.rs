@@ -0,0 +1,246 @@
+//! Typed decoding of the events emitted by the stablecoin program
+//!
+//! Events reach clients in two ways:
+//! - `emit!` writes `Program data: <base64>` lines into the transaction logs
+//! - `emit_cpi!` performs a self-CPI whose instruction data carries the event
+//!
+//! Both encodings are `discriminator || borsh(event)`, the CPI form prefixed
+//! with Anchor's event instruction tag.
+
+use anchor_lang::{event::EVENT_IX_TAG_LE, prelude::Pubkey, AnchorDeserialize, Discriminator};
+use base64::{engine::general_purpose::STANDARD, Engine as _};
+use stablecoin::{
+    AccountRecovered, AccountResized, AirdropClaimed, AirdropClawedBack, AirdropOpened,
+    ChannelClosing, ChannelSettled, InheritanceClaimStarted, InheritanceClaimStopped,
+    InheritanceClaimed, InheritanceConfigured, Initialized, MinterConfigured, MinterRemoved,
+    MinterResumed, MinterSelfPaused, MinterSetFrozen, MinterSetUnfrozen, NetSettled,
+    ObligationQueued, Paused, RecoveryCancelled, RecoveryConfigured, RecoveryInitiated,
+    SubAccountRegistered, SubAccountSwept, TokensBurned, TokensMinted, TreasuryBurnApproved,
+    TreasuryBurnCancelled, TreasuryBurnProposed, TreasuryConfigured, Unpaused,
+};
+
+const PROGRAM_DATA: &str = "Program data: ";
+
+#[derive(Debug, thiserror::Error)]
+pub enum EventError {
+    #[error("invalid base64 in program data: {0}")]
+    Base64(#[from] base64::DecodeError),
+    #[error("failed to deserialize {name}: {source}")]
+    Deserialize {
+        name: &'static str,
+        source: std::io::Error,
+    },
+}
+
+/// Every event the stablecoin program can emit
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StablecoinEvent {
+    Initialized(Initialized),
+    MinterConfigured(MinterConfigured),
+    MinterRemoved(MinterRemoved),
+    TokensMinted(TokensMinted),
+    TokensBurned(TokensBurned),
+    Paused(Paused),
+    Unpaused(Unpaused),
+    MinterSetFrozen(MinterSetFrozen),
+    MinterSetUnfrozen(MinterSetUnfrozen),
+    MinterSelfPaused(MinterSelfPaused),
+    MinterResumed(MinterResumed),
+    TreasuryConfigured(TreasuryConfigured),
+    TreasuryBurnProposed(TreasuryBurnProposed),
+    TreasuryBurnApproved(TreasuryBurnApproved),
+    TreasuryBurnCancelled(TreasuryBurnCancelled),
+    RecoveryConfigured(RecoveryConfigured),
+    RecoveryInitiated(RecoveryInitiated),
+    RecoveryCancelled(RecoveryCancelled),
+    AccountRecovered(AccountRecovered),
+    InheritanceConfigured(InheritanceConfigured),
+    InheritanceClaimStarted(InheritanceClaimStarted),
+    InheritanceClaimStopped(InheritanceClaimStopped),
+    InheritanceClaimed(InheritanceClaimed),
+    ObligationQueued(ObligationQueued),
+    NetSettled(NetSettled),
+    ChannelClosing(ChannelClosing),
+    ChannelSettled(ChannelSettled),
+    SubAccountRegistered(SubAccountRegistered),
+    SubAccountSwept(SubAccountSwept),
+    AirdropOpened(AirdropOpened),
+    AirdropClaimed(AirdropClaimed),
+    AirdropClawedBack(AirdropClawedBack),
+    AccountResized(AccountResized),
+}
+
+impl StablecoinEvent {
+    /// Decode `discriminator || borsh(event)`
+    ///
+    /// Returns `Ok(None)` for discriminators this client doesn't know, so that
+    /// older clients keep working when the program starts emitting new events.
+    pub fn decode(data: &[u8]) -> Result<Option<Self>, EventError> {
+        if data.len() < 8 {
+            return Ok(None);
+        }
+        let (disc, mut body) = data.split_at(8);
+
+        macro_rules! try_decode {
+            ($($variant:ident),*) => {
+                $(
+                    if disc == $variant::DISCRIMINATOR {
+                        return $variant::deserialize(&mut body)
+                            .map(|event| Some(Self::$variant(event)))
+                            .map_err(|source| EventError::Deserialize {
+                                name: stringify!($variant),
+                                source,
+                            });
+                    }
+                )*
+            };
+        }
+
+        try_decode!(
+            Initialized,
+            MinterConfigured,
+            MinterRemoved,
+            TokensMinted,
+            TokensBurned,
+            Paused,
+            Unpaused,
+            MinterSetFrozen,
+            MinterSetUnfrozen,
+            MinterSelfPaused,
+            MinterResumed,
+            TreasuryConfigured,
+            TreasuryBurnProposed,
+            TreasuryBurnApproved,
+            TreasuryBurnCancelled,
+            RecoveryConfigured,
+            RecoveryInitiated,
+            RecoveryCancelled,
+            AccountRecovered,
+            InheritanceConfigured,
+            InheritanceClaimStarted,
+            InheritanceClaimStopped,
+            InheritanceClaimed,
+            ObligationQueued,
+            NetSettled,
+            ChannelClosing,
+            ChannelSettled,
+            SubAccountRegistered,
+            SubAccountSwept,
+            AirdropOpened,
+            AirdropClaimed,
+            AirdropClawedBack,
+            AccountResized
+        );
+
+        Ok(None)
+    }
+
+    /// The event's name as it appears in the IDL
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Initialized(_) => "Initialized",
+            Self::MinterConfigured(_) => "MinterConfigured",
+            Self::MinterRemoved(_) => "MinterRemoved",
+            Self::TokensMinted(_) => "TokensMinted",
+            Self::TokensBurned(_) => "TokensBurned",
+            Self::Paused(_) => "Paused",
+            Self::Unpaused(_) => "Unpaused",
+            Self::MinterSetFrozen(_) => "MinterSetFrozen",
+            Self::MinterSetUnfrozen(_) => "MinterSetUnfrozen",
+            Self::MinterSelfPaused(_) => "MinterSelfPaused",
+            Self::MinterResumed(_) => "MinterResumed",
+            Self::TreasuryConfigured(_) => "TreasuryConfigured",
+            Self::TreasuryBurnProposed(_) => "TreasuryBurnProposed",
+            Self::TreasuryBurnApproved(_) => "TreasuryBurnApproved",
+            Self::TreasuryBurnCancelled(_) => "TreasuryBurnCancelled",
+            Self::RecoveryConfigured(_) => "RecoveryConfigured",
+            Self::RecoveryInitiated(_) => "RecoveryInitiated",
+            Self::RecoveryCancelled(_) => "RecoveryCancelled",
+            Self::AccountRecovered(_) => "AccountRecovered",
+            Self::InheritanceConfigured(_) => "InheritanceConfigured",
+            Self::InheritanceClaimStarted(_) => "InheritanceClaimStarted",
+            Self::InheritanceClaimStopped(_) => "InheritanceClaimStopped",
+            Self::InheritanceClaimed(_) => "InheritanceClaimed",
+            Self::ObligationQueued(_) => "ObligationQueued",
+            Self::NetSettled(_) => "NetSettled",
+            Self::ChannelClosing(_) => "ChannelClosing",
+            Self::ChannelSettled(_) => "ChannelSettled",
+            Self::SubAccountRegistered(_) => "SubAccountRegistered",
+            Self::SubAccountSwept(_) => "SubAccountSwept",
+            Self::AirdropOpened(_) => "AirdropOpened",
+            Self::AirdropClaimed(_) => "AirdropClaimed",
+            Self::AirdropClawedBack(_) => "AirdropClawedBack",
+            Self::AccountResized(_) => "AccountResized",
+        }
+    }
+}
+
+/// Extract the events emitted by `program_id` from a transaction's log messages
+///
+/// The invocation stack is tracked so that `Program data:` lines written by
+/// other programs (including ones CPI'd into by this program) are ignored.
+pub fn parse_logs<S: AsRef<str>>(
+    program_id: &Pubkey,
+    logs: &[S],
+) -> Result<Vec<StablecoinEvent>, EventError> {
+    let program_id = program_id.to_string();
+    let mut stack: Vec<&str> = Vec::new();
+    let mut events = Vec::new();
+
+    for log in logs {
+        let log = log.as_ref();
+
+        if let Some(data) = log.strip_prefix(PROGRAM_DATA) {
+            if stack.last() == Some(&program_id.as_str()) {
+                let bytes = STANDARD.decode(data)?;
+                if let Some(event) = StablecoinEvent::decode(&bytes)? {
+                    events.push(event);
+                }
+            }
+        } else if let Some(rest) = log.strip_prefix("Program ") {
+            let mut parts = rest.split(' ');
+            match (parts.next(), parts.next()) {
+                (Some(id), Some("invoke")) => stack.push(id),
+                (Some(_), Some("success")) | (Some(_), Some("failed:")) => {
+                    stack.pop();
+                }
+                _ => {}
+            }
+        }
+    }
+
+    Ok(events)
+}
+
+/// Decode the event carried by an `emit_cpi!` self-invocation
+///
+/// Returns `Ok(None)` if `data` is not an event instruction.
+pub fn parse_cpi_event(data: &[u8]) -> Result<Option<StablecoinEvent>, EventError> {
+    match data.strip_prefix(EVENT_IX_TAG_LE) {
+        Some(event) => StablecoinEvent::decode(event),
+        None => Ok(None),
+    }
+}
+
+/// Extract `emit_cpi!` events from a transaction's inner instructions
+///
+/// `instructions` yields the invoked program id and instruction data of each
+/// inner instruction; those not addressed to `program_id` are skipped.
+pub fn parse_cpi_events<'a, I>(
+    program_id: &Pubkey,
+    instructions: I,
+) -> Result<Vec<StablecoinEvent>, EventError>
+where
+    I: IntoIterator<Item = (&'a Pubkey, &'a [u8])>,
+{
+    let mut events = Vec::new();
+    for (id, data) in instructions {
+        if id != program_id {
+            continue;
+        }
+        if let Some(event) = parse_cpi_event(data)? {
+            events.push(event);
+        }
+    }
+    Ok(events)
+}
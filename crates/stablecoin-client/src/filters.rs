@@ -0,0 +1,128 @@
+//! Ready-made `getProgramAccounts` filters
+//!
+//! [`account_type`] matches every account of one of the program's types,
+//! by its discriminator and size. The modules below give the offset of
+//! each field an indexer is likely to filter on, discriminator included,
+//! for [`pubkey_at`] and [`bytes_at`]:
+//!
+//! ```ignore
+//! let mut filters = account_type::<PaymentChannel>();
+//! filters.push(pubkey_at(payment_channel::SENDER, &sender));
+//! ```
+//!
+//! Only fields ahead of any `Vec` have a fixed offset; the ones after it
+//! move with its length and are left out. tests/filters.rs checks every
+//! offset against the layout the program serializes.
+
+use anchor_lang::{prelude::Pubkey, Discriminator, Space};
+use solana_rpc_client_api::filter::{Memcmp, RpcFilterType};
+
+/// Where every account's discriminator starts
+pub const DISCRIMINATOR: usize = 0;
+
+/// Size of an account of type `T`, discriminator included, for a
+/// `dataSize` filter
+pub const fn data_size<T: Discriminator + Space>() -> u64 {
+    (T::DISCRIMINATOR.len() + T::INIT_SPACE) as u64
+}
+
+/// Match every account of type `T`: its size and its discriminator
+///
+/// Accounts are always allocated at their type's full size, so the size
+/// filter holds for types with a `Vec` too.
+pub fn account_type<T: Discriminator + Space>() -> Vec<RpcFilterType> {
+    vec![
+        RpcFilterType::DataSize(data_size::<T>()),
+        bytes_at(DISCRIMINATOR, T::DISCRIMINATOR),
+    ]
+}
+
+/// Match accounts holding `key` at `offset`
+pub fn pubkey_at(offset: usize, key: &Pubkey) -> RpcFilterType {
+    bytes_at(offset, key.as_ref())
+}
+
+/// Match accounts holding `bytes` at `offset`; integers are little-endian
+pub fn bytes_at(offset: usize, bytes: &[u8]) -> RpcFilterType {
+    RpcFilterType::Memcmp(Memcmp::new_base58_encoded(offset, bytes))
+}
+
+pub mod config {
+    pub const ADMIN: usize = 8;
+    pub const MINT: usize = 40;
+    pub const PAUSED: usize = 72;
+}
+
+pub mod minter_config {
+    pub const MINTER: usize = 8;
+    pub const ALLOWANCE: usize = 40;
+    pub const AMOUNT_MINTED: usize = 48;
+}
+
+pub mod minter_pause {
+    pub const MINTER: usize = 8;
+}
+
+pub mod minter_tombstone {
+    pub const MINTER: usize = 8;
+    pub const REMOVED_BY: usize = 40;
+}
+
+pub mod minter_set_freeze {
+    pub const FROZEN_BY: usize = 8;
+}
+
+pub mod idempotency_record {
+    pub const MINTER: usize = 8;
+    pub const KEY: usize = 40;
+}
+
+pub mod treasury {
+    /// The first officer; the second follows it, 32 bytes on
+    pub const OFFICERS: usize = 8;
+}
+
+pub mod pending_burn {
+    pub const ID: usize = 8;
+    pub const PROPOSER: usize = 16;
+}
+
+pub mod recovery_config {
+    pub const OWNER: usize = 8;
+}
+
+pub mod inheritance_plan {
+    pub const OWNER: usize = 8;
+    pub const BENEFICIARY: usize = 40;
+}
+
+pub mod netting_window {
+    pub const ID: usize = 8;
+    pub const OPENED_BY: usize = 16;
+}
+
+pub mod payment_channel {
+    pub const SENDER: usize = 8;
+    pub const RECIPIENT: usize = 40;
+}
+
+pub mod purpose_code {
+    pub const CODE: usize = 8;
+    pub const REGISTERED_BY: usize = 12;
+}
+
+pub mod sub_account {
+    pub const OWNER: usize = 8;
+    pub const INDEX: usize = 40;
+    pub const PURPOSE: usize = 48;
+}
+
+pub mod airdrop {
+    pub const FUNDER: usize = 8;
+    pub const ID: usize = 40;
+}
+
+#[cfg(feature = "devnet-faucet")]
+pub mod faucet_claim {
+    pub const WALLET: usize = 8;
+}
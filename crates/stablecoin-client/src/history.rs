@@ -0,0 +1,152 @@
+//! Historical event retrieval through `getSignaturesForAddress`/`getTransaction`
+
+use std::str::FromStr;
+
+use anchor_lang::prelude::Pubkey;
+use solana_commitment_config::CommitmentConfig;
+use solana_rpc_client::{
+    nonblocking::rpc_client::RpcClient, rpc_client::GetConfirmedSignaturesForAddress2Config,
+};
+use solana_rpc_client_api::config::RpcTransactionConfig;
+use solana_signature::Signature;
+use solana_transaction_status_client_types::{
+    option_serializer::OptionSerializer, UiTransactionEncoding,
+};
+
+use crate::{
+    error::{ClientError, Result},
+    events::{parse_logs, StablecoinEvent},
+};
+
+/// Maximum page size accepted by `getSignaturesForAddress`
+const SIGNATURE_PAGE_LIMIT: usize = 1_000;
+
+/// A position in the program's transaction history
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cursor {
+    pub slot: u64,
+    pub signature: Signature,
+}
+
+/// The events emitted by one successful transaction
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TransactionEvents {
+    pub slot: u64,
+    /// Unix timestamp of the block, when the node reports one
+    pub block_time: Option<i64>,
+    pub signature: Signature,
+    pub events: Vec<StablecoinEvent>,
+}
+
+impl TransactionEvents {
+    pub fn cursor(&self) -> Cursor {
+        Cursor {
+            slot: self.slot,
+            signature: self.signature,
+        }
+    }
+}
+
+/// Fetch a transaction and decode the events `program_id` emitted in it
+///
+/// Returns `None` for failed transactions (their events were rolled back) and
+/// for transactions the node no longer has metadata for.
+pub async fn fetch_transaction_events(
+    rpc: &RpcClient,
+    program_id: &Pubkey,
+    signature: &Signature,
+    commitment: CommitmentConfig,
+) -> Result<Option<TransactionEvents>> {
+    let tx = rpc
+        .get_transaction_with_config(
+            signature,
+            RpcTransactionConfig {
+                encoding: Some(UiTransactionEncoding::Base64),
+                commitment: Some(commitment),
+                max_supported_transaction_version: Some(0),
+            },
+        )
+        .await?;
+
+    let Some(meta) = tx.transaction.meta else {
+        return Ok(None);
+    };
+    if meta.err.is_some() {
+        return Ok(None);
+    }
+    let logs = match meta.log_messages {
+        OptionSerializer::Some(logs) => logs,
+        _ => Vec::new(),
+    };
+
+    Ok(Some(TransactionEvents {
+        slot: tx.slot,
+        block_time: tx.block_time,
+        signature: *signature,
+        events: parse_logs(program_id, &logs)?,
+    }))
+}
+
+/// Every successful transaction involving `program_id` after `until`, oldest first
+///
+/// With `until = None` this walks the program's entire history, so callers
+/// should normally pass the last cursor they processed.
+pub async fn signatures_since(
+    rpc: &RpcClient,
+    program_id: &Pubkey,
+    until: Option<Signature>,
+    commitment: CommitmentConfig,
+) -> Result<Vec<(u64, Signature)>> {
+    let mut signatures = Vec::new();
+    let mut before = None;
+
+    loop {
+        let page = rpc
+            .get_signatures_for_address_with_config(
+                program_id,
+                GetConfirmedSignaturesForAddress2Config {
+                    before,
+                    until,
+                    limit: Some(SIGNATURE_PAGE_LIMIT),
+                    commitment: Some(commitment),
+                },
+            )
+            .await?;
+        let page_len = page.len();
+
+        for status in page {
+            let signature = Signature::from_str(&status.signature)
+                .map_err(|_| ClientError::InvalidSignature(status.signature.clone()))?;
+            before = Some(signature);
+            if status.err.is_none() {
+                signatures.push((status.slot, signature));
+            }
+        }
+
+        if page_len < SIGNATURE_PAGE_LIMIT {
+            break;
+        }
+    }
+
+    // RPC pages newest-first
+    signatures.reverse();
+    Ok(signatures)
+}
+
+/// Decode every event `program_id` emitted after `until`, oldest first
+pub async fn events_since(
+    rpc: &RpcClient,
+    program_id: &Pubkey,
+    until: Option<Signature>,
+    commitment: CommitmentConfig,
+) -> Result<Vec<TransactionEvents>> {
+    let mut history = Vec::new();
+    for (_, signature) in signatures_since(rpc, program_id, until, commitment).await? {
+        if let Some(tx) = fetch_transaction_events(rpc, program_id, &signature, commitment).await? {
+            if !tx.events.is_empty() {
+                history.push(tx);
+            }
+        }
+    }
+    Ok(history)
+}
@@ -0,0 +1,11 @@
+//! The program's Anchor IDL
+//!
+//! `idls/stablecoin.json` is the IDL `anchor build` generates, with the
+//! program's instructions, accounts, events, errors and seed constants.
+//! Explorers and TypeScript clients read it from chain once it is published
+//! with `anchor idl init` (or `anchor idl upgrade` after a change); Anchor 1.0
+//! stores it through the Program Metadata program, so the program itself has
+//! no IDL instructions.
+
+/// The IDL as JSON
+pub const IDL_JSON: &str = include_str!("../../../idls/stablecoin.json");
@@ -0,0 +1,1232 @@
+//! Instruction builders, one per program instruction
+//!
+//! PDAs and token accounts are derived here, so callers only pass the
+//! signers and the accounts they choose. [`decode`] goes the other way, from
+//! instruction data back to the instruction and its arguments.
+//!
+//! Amounts, allowances and thresholds are in base units, as the program
+//! takes them; [`StableAmount`](crate::StableAmount) converts from tokens.
+
+use anchor_lang::{
+    prelude::Pubkey, AnchorDeserialize, Discriminator, InstructionData, ToAccountMetas,
+};
+use solana_instruction::{AccountMeta, Instruction};
+use stablecoin::{accounts, instruction};
+
+use crate::pda;
+
+fn build(accounts: impl ToAccountMetas, data: impl InstructionData) -> Instruction {
+    Instruction {
+        program_id: crate::PROGRAM_ID,
+        accounts: accounts.to_account_metas(None),
+        data: data.data(),
+    }
+}
+
+pub fn initialize(admin: &Pubkey) -> Instruction {
+    build(
+        accounts::Initialize {
+            admin: *admin,
+            config: pda::config().0,
+            mint: pda::mint().0,
+            supply_stats: pda::supply_stats().0,
+            token_program: anchor_spl::token::ID,
+            system_program: anchor_lang::system_program::ID,
+        },
+        instruction::Initialize {},
+    )
+}
+
+pub fn configure_minter(admin: &Pubkey, minter: &Pubkey, allowance: u64) -> Instruction {
+    build(
+        accounts::ConfigureMinter {
+            admin: *admin,
+            config: pda::config().0,
+            minter: *minter,
+            minter_config: pda::minter_config(minter).0,
+            minter_set_freeze: pda::minter_set_freeze().0,
+            minter_tombstone: pda::minter_tombstone(minter).0,
+            system_program: anchor_lang::system_program::ID,
+        },
+        instruction::ConfigureMinter { allowance },
+    )
+}
+
+pub fn remove_minter(admin: &Pubkey, minter: &Pubkey) -> Instruction {
+    build(
+        accounts::RemoveMinter {
+            admin: *admin,
+            config: pda::config().0,
+            minter: *minter,
+            minter_config: pda::minter_config(minter).0,
+            minter_set_freeze: pda::minter_set_freeze().0,
+            minter_tombstone: pda::minter_tombstone(minter).0,
+            system_program: anchor_lang::system_program::ID,
+        },
+        instruction::RemoveMinter {},
+    )
+}
+
+/// Freeze the minter set, paid for by `admin`
+pub fn freeze_minter_set(admin: &Pubkey) -> Instruction {
+    build(
+        accounts::FreezeMinterSet {
+            admin: *admin,
+            config: pda::config().0,
+            minter_set_freeze: pda::minter_set_freeze().0,
+            system_program: anchor_lang::system_program::ID,
+        },
+        instruction::FreezeMinterSet {},
+    )
+}
+
+/// Unfreeze the minter set, refunding the marker's rent to `frozen_by`
+pub fn unfreeze_minter_set(admin: &Pubkey, frozen_by: &Pubkey) -> Instruction {
+    build(
+        accounts::UnfreezeMinterSet {
+            admin: *admin,
+            config: pda::config().0,
+            minter_set_freeze: pda::minter_set_freeze().0,
+            frozen_by: *frozen_by,
+        },
+        instruction::UnfreezeMinterSet {},
+    )
+}
+
+fn mint_tokens_accounts(minter: &Pubkey, destination_owner: &Pubkey) -> accounts::MintTokens {
+    accounts::MintTokens {
+        minter: *minter,
+        config: pda::config().0,
+        minter_config: pda::minter_config(minter).0,
+        minter_pause: pda::minter_pause(minter).0,
+        mint: pda::mint().0,
+        supply_stats: pda::supply_stats().0,
+        destination: pda::token_account(destination_owner),
+        destination_owner: *destination_owner,
+        token_program: anchor_spl::token::ID,
+        associated_token_program: anchor_spl::associated_token::ID,
+        system_program: anchor_lang::system_program::ID,
+    }
+}
+
+/// Mint to `destination_owner`'s associated token account, creating it if needed
+pub fn mint_tokens(minter: &Pubkey, destination_owner: &Pubkey, amount: u64) -> Instruction {
+    build(
+        mint_tokens_accounts(minter, destination_owner),
+        instruction::MintTokens { amount },
+    )
+}
+
+/// [`mint_tokens`] at most once per `key`: resending it after it landed
+/// fails with `DuplicateMint` instead of minting again
+pub fn mint_tokens_idempotent(
+    minter: &Pubkey,
+    destination_owner: &Pubkey,
+    amount: u64,
+    key: [u8; 16],
+) -> Instruction {
+    build(
+        accounts::MintTokensIdempotent {
+            mint: mint_tokens_accounts(minter, destination_owner),
+            record: pda::idempotency_record(minter, &key).0,
+            system_program: anchor_lang::system_program::ID,
+        },
+        instruction::MintTokensIdempotent { amount, key },
+    )
+}
+
+/// Close `minter`'s expired record of `key`, returning its rent
+pub fn reclaim_idempotency_record(minter: &Pubkey, key: &[u8; 16]) -> Instruction {
+    build(
+        accounts::ReclaimIdempotencyRecord {
+            minter: *minter,
+            record: pda::idempotency_record(minter, key).0,
+        },
+        instruction::ReclaimIdempotencyRecord {},
+    )
+}
+
+/// Pause `minter`'s own minting until the admin resumes it
+pub fn pause_self(minter: &Pubkey) -> Instruction {
+    build(
+        accounts::PauseSelf {
+            minter: *minter,
+            minter_config: pda::minter_config(minter).0,
+            minter_pause: pda::minter_pause(minter).0,
+            system_program: anchor_lang::system_program::ID,
+        },
+        instruction::PauseSelf {},
+    )
+}
+
+/// Resume a minter that paused itself, refunding the marker's rent to it
+pub fn resume_minter(admin: &Pubkey, minter: &Pubkey) -> Instruction {
+    build(
+        accounts::ResumeMinter {
+            admin: *admin,
+            config: pda::config().0,
+            minter: *minter,
+            minter_pause: pda::minter_pause(minter).0,
+        },
+        instruction::ResumeMinter {},
+    )
+}
+
+/// Burn from the owner's associated token account
+pub fn burn_tokens(owner: &Pubkey, amount: u64) -> Instruction {
+    build(
+        accounts::BurnTokens {
+            owner: *owner,
+            config: pda::config().0,
+            mint: pda::mint().0,
+            supply_stats: pda::supply_stats().0,
+            token_account: pda::token_account(owner),
+            token_program: anchor_spl::token::ID,
+        },
+        instruction::BurnTokens { amount },
+    )
+}
+
+/// Move each of `owner`'s token accounts in `sources` into their
+/// associated token account and close it, returning the rent to `owner`
+///
+/// Fails with `NotDust` if any of them holds more than `threshold`.
+pub fn sweep_dust(owner: &Pubkey, sources: &[Pubkey], threshold: u64) -> Instruction {
+    let mut ix = build(
+        accounts::SweepDust {
+            owner: *owner,
+            config: pda::config().0,
+            mint: pda::mint().0,
+            destination: pda::token_account(owner),
+            token_program: anchor_spl::token::ID,
+        },
+        instruction::SweepDust { threshold },
+    );
+    ix.accounts.extend(
+        sources
+            .iter()
+            .map(|source| AccountMeta::new(*source, false)),
+    );
+    ix
+}
+
+pub fn configure_treasury(admin: &Pubkey, officers: [Pubkey; 2], threshold: u64) -> Instruction {
+    build(
+        accounts::ConfigureTreasury {
+            admin: *admin,
+            config: pda::config().0,
+            treasury: pda::treasury().0,
+            system_program: anchor_lang::system_program::ID,
+        },
+        instruction::ConfigureTreasury {
+            officers,
+            threshold,
+        },
+    )
+}
+
+/// Propose burning `amount` from treasury custody under `id`, which no
+/// pending burn may be using
+pub fn propose_treasury_burn(officer: &Pubkey, id: u64, amount: u64) -> Instruction {
+    build(
+        accounts::ProposeTreasuryBurn {
+            officer: *officer,
+            treasury: pda::treasury().0,
+            pending_burn: pda::pending_burn(id).0,
+            system_program: anchor_lang::system_program::ID,
+        },
+        instruction::ProposeTreasuryBurn { id, amount },
+    )
+}
+
+pub fn approve_treasury_burn(officer: &Pubkey, id: u64) -> Instruction {
+    build(
+        accounts::ApproveTreasuryBurn {
+            officer: *officer,
+            treasury: pda::treasury().0,
+            pending_burn: pda::pending_burn(id).0,
+        },
+        instruction::ApproveTreasuryBurn {},
+    )
+}
+
+/// Carry out the pending burn `id`, refunding its rent to `proposer`
+pub fn execute_treasury_burn(officer: &Pubkey, proposer: &Pubkey, id: u64) -> Instruction {
+    build(
+        accounts::ExecuteTreasuryBurn {
+            officer: *officer,
+            config: pda::config().0,
+            treasury: pda::treasury().0,
+            pending_burn: pda::pending_burn(id).0,
+            proposer: *proposer,
+            mint: pda::mint().0,
+            supply_stats: pda::supply_stats().0,
+            custody: pda::treasury_custody(),
+            token_program: anchor_spl::token::ID,
+        },
+        instruction::ExecuteTreasuryBurn {},
+    )
+}
+
+/// Drop the pending burn `id`, refunding its rent to `proposer`
+pub fn cancel_treasury_burn(officer: &Pubkey, proposer: &Pubkey, id: u64) -> Instruction {
+    build(
+        accounts::CancelTreasuryBurn {
+            officer: *officer,
+            treasury: pda::treasury().0,
+            pending_burn: pda::pending_burn(id).0,
+            proposer: *proposer,
+        },
+        instruction::CancelTreasuryBurn {},
+    )
+}
+
+/// Opt `owner` into social recovery, or replace their settings and any
+/// recovery in progress
+pub fn configure_recovery(
+    owner: &Pubkey,
+    guardians: Vec<Pubkey>,
+    quorum: u8,
+    challenge_period: i64,
+) -> Instruction {
+    build(
+        accounts::ConfigureRecovery {
+            owner: *owner,
+            config: pda::config().0,
+            mint: pda::mint().0,
+            token_account: pda::token_account(owner),
+            delegate: pda::delegate().0,
+            recovery_config: pda::recovery_config(owner).0,
+            token_program: anchor_spl::token::ID,
+            system_program: anchor_lang::system_program::ID,
+        },
+        instruction::ConfigureRecovery {
+            guardians,
+            quorum,
+            challenge_period,
+        },
+    )
+}
+
+/// Start recovering `owner`'s balance to `new_owner`
+pub fn initiate_recovery(guardian: &Pubkey, owner: &Pubkey, new_owner: &Pubkey) -> Instruction {
+    build(
+        accounts::InitiateRecovery {
+            guardian: *guardian,
+            recovery_config: pda::recovery_config(owner).0,
+            config: pda::config().0,
+            mint: pda::mint().0,
+            owner: *owner,
+            token_account: pda::token_account(owner),
+            delegate: pda::delegate().0,
+        },
+        instruction::InitiateRecovery {
+            new_owner: *new_owner,
+        },
+    )
+}
+
+pub fn approve_recovery(guardian: &Pubkey, owner: &Pubkey) -> Instruction {
+    build(
+        accounts::ApproveRecovery {
+            guardian: *guardian,
+            recovery_config: pda::recovery_config(owner).0,
+        },
+        instruction::ApproveRecovery {},
+    )
+}
+
+/// Move `owner`'s balance to `new_owner`'s associated token account,
+/// creating it if needed; `new_owner` must be the one the recovery names
+pub fn execute_recovery(guardian: &Pubkey, owner: &Pubkey, new_owner: &Pubkey) -> Instruction {
+    build(
+        accounts::ExecuteRecovery {
+            guardian: *guardian,
+            config: pda::config().0,
+            mint: pda::mint().0,
+            recovery_config: pda::recovery_config(owner).0,
+            owner: *owner,
+            token_account: pda::token_account(owner),
+            new_owner: *new_owner,
+            destination: pda::token_account(new_owner),
+            delegate: pda::delegate().0,
+            token_program: anchor_spl::token::ID,
+            associated_token_program: anchor_spl::associated_token::ID,
+            system_program: anchor_lang::system_program::ID,
+        },
+        instruction::ExecuteRecovery {},
+    )
+}
+
+pub fn cancel_recovery(owner: &Pubkey) -> Instruction {
+    build(
+        accounts::CancelRecovery {
+            owner: *owner,
+            recovery_config: pda::recovery_config(owner).0,
+        },
+        instruction::CancelRecovery {},
+    )
+}
+
+/// Opt `owner` out of social recovery, returning the settings' rent and
+/// revoking the program's delegate unless they have an inheritance plan
+pub fn close_recovery(owner: &Pubkey) -> Instruction {
+    build(
+        accounts::CloseRecovery {
+            owner: *owner,
+            config: pda::config().0,
+            mint: pda::mint().0,
+            recovery_config: pda::recovery_config(owner).0,
+            token_account: pda::token_account(owner),
+            delegate: pda::delegate().0,
+            inheritance_plan: pda::inheritance_plan(owner).0,
+            token_program: anchor_spl::token::ID,
+        },
+        instruction::CloseRecovery {},
+    )
+}
+
+/// Opt `owner` into inheritance by `beneficiary`, or replace their plan
+/// and any claim in progress
+pub fn configure_inheritance(
+    owner: &Pubkey,
+    beneficiary: &Pubkey,
+    inactivity_epochs: u64,
+    notice_period: i64,
+) -> Instruction {
+    build(
+        accounts::ConfigureInheritance {
+            owner: *owner,
+            config: pda::config().0,
+            mint: pda::mint().0,
+            token_account: pda::token_account(owner),
+            delegate: pda::delegate().0,
+            inheritance_plan: pda::inheritance_plan(owner).0,
+            token_program: anchor_spl::token::ID,
+            system_program: anchor_lang::system_program::ID,
+        },
+        instruction::ConfigureInheritance {
+            beneficiary: *beneficiary,
+            inactivity_epochs,
+            notice_period,
+        },
+    )
+}
+
+pub fn check_in(owner: &Pubkey) -> Instruction {
+    build(
+        accounts::CheckIn {
+            owner: *owner,
+            inheritance_plan: pda::inheritance_plan(owner).0,
+        },
+        instruction::CheckIn {},
+    )
+}
+
+pub fn start_inheritance_claim(beneficiary: &Pubkey, owner: &Pubkey) -> Instruction {
+    build(
+        accounts::StartInheritanceClaim {
+            beneficiary: *beneficiary,
+            inheritance_plan: pda::inheritance_plan(owner).0,
+            config: pda::config().0,
+            mint: pda::mint().0,
+            owner: *owner,
+            token_account: pda::token_account(owner),
+            delegate: pda::delegate().0,
+        },
+        instruction::StartInheritanceClaim {},
+    )
+}
+
+/// Move `owner`'s balance to `beneficiary`'s associated token account,
+/// creating it if needed
+pub fn claim_inheritance(beneficiary: &Pubkey, owner: &Pubkey) -> Instruction {
+    build(
+        accounts::ClaimInheritance {
+            beneficiary: *beneficiary,
+            config: pda::config().0,
+            mint: pda::mint().0,
+            inheritance_plan: pda::inheritance_plan(owner).0,
+            owner: *owner,
+            token_account: pda::token_account(owner),
+            destination: pda::token_account(beneficiary),
+            delegate: pda::delegate().0,
+            token_program: anchor_spl::token::ID,
+            associated_token_program: anchor_spl::associated_token::ID,
+            system_program: anchor_lang::system_program::ID,
+        },
+        instruction::ClaimInheritance {},
+    )
+}
+
+/// Opt `owner` out of inheritance, returning the plan's rent and revoking
+/// the program's delegate unless they have recovery settings
+pub fn close_inheritance(owner: &Pubkey) -> Instruction {
+    build(
+        accounts::CloseInheritance {
+            owner: *owner,
+            config: pda::config().0,
+            mint: pda::mint().0,
+            inheritance_plan: pda::inheritance_plan(owner).0,
+            token_account: pda::token_account(owner),
+            delegate: pda::delegate().0,
+            recovery_config: pda::recovery_config(owner).0,
+            token_program: anchor_spl::token::ID,
+        },
+        instruction::CloseInheritance {},
+    )
+}
+
+/// Open netting window `id` between `institutions`, closing `duration`
+/// seconds after it lands
+pub fn open_netting_window(
+    admin: &Pubkey,
+    id: u64,
+    institutions: Vec<Pubkey>,
+    duration: i64,
+) -> Instruction {
+    build(
+        accounts::OpenNettingWindow {
+            admin: *admin,
+            config: pda::config().0,
+            mint: pda::mint().0,
+            netting_window: pda::netting_window(id).0,
+            custody: pda::netting_custody(id),
+            token_program: anchor_spl::token::ID,
+            associated_token_program: anchor_spl::associated_token::ID,
+            system_program: anchor_lang::system_program::ID,
+        },
+        instruction::OpenNettingWindow {
+            id,
+            institutions,
+            duration,
+        },
+    )
+}
+
+/// Queue `debtor`'s obligation of `amount` to `creditor` in window `id`,
+/// stating its purpose if `purpose` is a registered code
+pub fn queue_obligation(
+    debtor: &Pubkey,
+    id: u64,
+    creditor: &Pubkey,
+    amount: u64,
+    purpose: Option<&[u8; 4]>,
+) -> Instruction {
+    build(
+        accounts::QueueObligation {
+            debtor: *debtor,
+            config: pda::config().0,
+            mint: pda::mint().0,
+            token_account: pda::token_account(debtor),
+            netting_window: pda::netting_window(id).0,
+            custody: pda::netting_custody(id),
+            purpose_code: purpose.map(|code| pda::purpose_code(code).0),
+            token_program: anchor_spl::token::ID,
+        },
+        instruction::QueueObligation {
+            creditor: *creditor,
+            amount,
+        },
+    )
+}
+
+/// Settle window `id`, signed and paid for by `payer`, refunding its rent
+/// to `opened_by`
+///
+/// `institutions` must be the window's, in its order; their token accounts
+/// follow the named accounts.
+pub fn settle_net(
+    payer: &Pubkey,
+    opened_by: &Pubkey,
+    id: u64,
+    institutions: &[Pubkey],
+) -> Instruction {
+    let mut ix = build(
+        accounts::SettleNet {
+            payer: *payer,
+            config: pda::config().0,
+            mint: pda::mint().0,
+            netting_window: pda::netting_window(id).0,
+            opened_by: *opened_by,
+            custody: pda::netting_custody(id),
+            token_program: anchor_spl::token::ID,
+        },
+        instruction::SettleNet {},
+    );
+    ix.accounts.extend(
+        institutions
+            .iter()
+            .map(|institution| AccountMeta::new(pda::token_account(institution), false)),
+    );
+    ix
+}
+
+/// Drop window `id` unsettled, refunding what `institutions` escrowed and
+/// its rent to `opened_by`
+///
+/// `institutions` must be the window's, in its order; their token accounts
+/// follow the named accounts.
+pub fn cancel_netting_window(
+    admin: &Pubkey,
+    opened_by: &Pubkey,
+    id: u64,
+    institutions: &[Pubkey],
+) -> Instruction {
+    let mut ix = build(
+        accounts::CancelNettingWindow {
+            admin: *admin,
+            config: pda::config().0,
+            mint: pda::mint().0,
+            netting_window: pda::netting_window(id).0,
+            opened_by: *opened_by,
+            custody: pda::netting_custody(id),
+            token_program: anchor_spl::token::ID,
+        },
+        instruction::CancelNettingWindow {},
+    );
+    ix.accounts.extend(
+        institutions
+            .iter()
+            .map(|institution| AccountMeta::new(pda::token_account(institution), false)),
+    );
+    ix
+}
+
+/// Register purpose code `code`, paid for by `admin`
+pub fn register_purpose_code(admin: &Pubkey, code: [u8; 4]) -> Instruction {
+    build(
+        accounts::RegisterPurposeCode {
+            admin: *admin,
+            config: pda::config().0,
+            purpose_code: pda::purpose_code(&code).0,
+            system_program: anchor_lang::system_program::ID,
+        },
+        instruction::RegisterPurposeCode { code },
+    )
+}
+
+/// Retire purpose code `code`, refunding its rent to `registered_by`
+pub fn retire_purpose_code(admin: &Pubkey, registered_by: &Pubkey, code: &[u8; 4]) -> Instruction {
+    build(
+        accounts::RetirePurposeCode {
+            admin: *admin,
+            config: pda::config().0,
+            purpose_code: pda::purpose_code(code).0,
+            registered_by: *registered_by,
+        },
+        instruction::RetirePurposeCode {},
+    )
+}
+
+/// Open a payment channel from `sender` to `recipient`, depositing
+/// `deposit` into its custody
+pub fn open_channel(
+    sender: &Pubkey,
+    recipient: &Pubkey,
+    deposit: u64,
+    dispute_period: i64,
+) -> Instruction {
+    build(
+        accounts::OpenChannel {
+            sender: *sender,
+            config: pda::config().0,
+            mint: pda::mint().0,
+            token_account: pda::token_account(sender),
+            payment_channel: pda::payment_channel(sender, recipient).0,
+            channel_counter: pda::channel_counter(sender, recipient).0,
+            custody: pda::channel_custody(sender, recipient),
+            token_program: anchor_spl::token::ID,
+            associated_token_program: anchor_spl::associated_token::ID,
+            system_program: anchor_lang::system_program::ID,
+        },
+        instruction::OpenChannel {
+            recipient: *recipient,
+            deposit,
+            dispute_period,
+        },
+    )
+}
+
+/// Have the Ed25519 program check `sender`'s `signature` over the balance
+/// update paying `amount` on the channel to `recipient` with nonce `nonce`
+///
+/// [`close_channel`] raising the amount must come right after it in the
+/// same transaction. The sender signs
+/// [`stablecoin::channel_state_message`] for the channel's address.
+pub fn verify_channel_state(
+    sender: &Pubkey,
+    recipient: &Pubkey,
+    nonce: u64,
+    amount: u64,
+    signature: &[u8; 64],
+) -> Instruction {
+    // The signature, key and message all follow a single offsets entry in
+    // this instruction's own data
+    const DATA_START: u16 = 16;
+    const OWN_DATA: u16 = u16::MAX;
+    let message = stablecoin::channel_state_message(
+        &pda::payment_channel(sender, recipient).0,
+        nonce,
+        amount,
+    );
+    let signature_offset = DATA_START;
+    let key_offset = signature_offset + signature.len() as u16;
+    let message_offset = key_offset + 32;
+
+    let mut data = vec![1, 0];
+    for field in [
+        signature_offset,
+        OWN_DATA,
+        key_offset,
+        OWN_DATA,
+        message_offset,
+        message.len() as u16,
+        OWN_DATA,
+    ] {
+        data.extend_from_slice(&field.to_le_bytes());
+    }
+    data.extend_from_slice(signature);
+    data.extend_from_slice(sender.as_ref());
+    data.extend_from_slice(&message);
+    Instruction {
+        program_id: solana_sdk_ids::ed25519_program::ID,
+        accounts: vec![],
+        data,
+    }
+}
+
+/// Submit the balance update paying `amount` on the channel from `sender`
+/// to `recipient`, signed by `party`, one of the two
+///
+/// Raising the amount needs [`verify_channel_state`] with the sender's
+/// `signature` right before; otherwise `signature` is ignored.
+pub fn close_channel(
+    party: &Pubkey,
+    sender: &Pubkey,
+    recipient: &Pubkey,
+    amount: u64,
+    signature: [u8; 64],
+) -> Instruction {
+    build(
+        accounts::CloseChannel {
+            party: *party,
+            payment_channel: pda::payment_channel(sender, recipient).0,
+            instructions: solana_sdk_ids::sysvar::instructions::ID,
+        },
+        instruction::CloseChannel { amount, signature },
+    )
+}
+
+/// Settle the channel from `sender` to `recipient`, signed and paid for by
+/// `payer`, creating either side's associated token account if needed
+pub fn settle_channel(payer: &Pubkey, sender: &Pubkey, recipient: &Pubkey) -> Instruction {
+    build(
+        accounts::SettleChannel {
+            payer: *payer,
+            config: pda::config().0,
+            mint: pda::mint().0,
+            payment_channel: pda::payment_channel(sender, recipient).0,
+            sender: *sender,
+            recipient: *recipient,
+            custody: pda::channel_custody(sender, recipient),
+            sender_token_account: pda::token_account(sender),
+            recipient_token_account: pda::token_account(recipient),
+            token_program: anchor_spl::token::ID,
+            associated_token_program: anchor_spl::associated_token::ID,
+            system_program: anchor_lang::system_program::ID,
+        },
+        instruction::SettleChannel {},
+    )
+}
+
+/// Register `owner`'s sub-account numbered `index` under the registered
+/// purpose code `purpose`, creating its custody
+pub fn register_sub_account(owner: &Pubkey, index: u64, purpose: &[u8; 4]) -> Instruction {
+    build(
+        accounts::RegisterSubAccount {
+            owner: *owner,
+            config: pda::config().0,
+            mint: pda::mint().0,
+            purpose_code: pda::purpose_code(purpose).0,
+            sub_account: pda::sub_account(owner, index).0,
+            custody: pda::sub_account_custody(owner, index),
+            token_program: anchor_spl::token::ID,
+            associated_token_program: anchor_spl::associated_token::ID,
+            system_program: anchor_lang::system_program::ID,
+        },
+        instruction::RegisterSubAccount { index },
+    )
+}
+
+/// Move the balance of `owner`'s sub-account numbered `index` to `owner`'s
+/// associated token account, which must exist
+pub fn sweep_sub_account(owner: &Pubkey, index: u64) -> Instruction {
+    build(
+        accounts::SweepSubAccount {
+            owner: *owner,
+            config: pda::config().0,
+            mint: pda::mint().0,
+            sub_account: pda::sub_account(owner, index).0,
+            custody: pda::sub_account_custody(owner, index),
+            destination: pda::token_account(owner),
+            token_program: anchor_spl::token::ID,
+        },
+        instruction::SweepSubAccount {},
+    )
+}
+
+/// Open treasury officer `funder`'s airdrop `id`, paying `amounts[i]` into
+/// custody for each of `recipients[i]`, claimable for `duration` seconds
+pub fn open_airdrop(
+    funder: &Pubkey,
+    id: u64,
+    recipients: Vec<Pubkey>,
+    amounts: Vec<u64>,
+    duration: i64,
+) -> Instruction {
+    build(
+        accounts::OpenAirdrop {
+            funder: *funder,
+            config: pda::config().0,
+            mint: pda::mint().0,
+            treasury: pda::treasury().0,
+            token_account: pda::token_account(funder),
+            airdrop: pda::airdrop(funder, id).0,
+            custody: pda::airdrop_custody(funder, id),
+            token_program: anchor_spl::token::ID,
+            associated_token_program: anchor_spl::associated_token::ID,
+            system_program: anchor_lang::system_program::ID,
+        },
+        instruction::OpenAirdrop {
+            id,
+            recipients,
+            amounts,
+            duration,
+        },
+    )
+}
+
+/// Claim `recipient`'s amount from `funder`'s airdrop `id` into
+/// `recipient`'s associated token account
+pub fn claim_airdrop(recipient: &Pubkey, funder: &Pubkey, id: u64) -> Instruction {
+    build(
+        accounts::ClaimAirdrop {
+            recipient: *recipient,
+            config: pda::config().0,
+            mint: pda::mint().0,
+            airdrop: pda::airdrop(funder, id).0,
+            custody: pda::airdrop_custody(funder, id),
+            destination: pda::token_account(recipient),
+            token_program: anchor_spl::token::ID,
+            associated_token_program: anchor_spl::associated_token::ID,
+            system_program: anchor_lang::system_program::ID,
+        },
+        instruction::ClaimAirdrop {},
+    )
+}
+
+/// Move what is left of `funder`'s closed airdrop `id` to treasury
+/// custody, signed by `payer`, who pays for treasury custody if it is
+/// missing
+pub fn clawback_airdrop(payer: &Pubkey, funder: &Pubkey, id: u64) -> Instruction {
+    build(
+        accounts::ClawbackAirdrop {
+            payer: *payer,
+            config: pda::config().0,
+            mint: pda::mint().0,
+            airdrop: pda::airdrop(funder, id).0,
+            funder: *funder,
+            custody: pda::airdrop_custody(funder, id),
+            treasury: pda::treasury().0,
+            treasury_custody: pda::treasury_custody(),
+            token_program: anchor_spl::token::ID,
+            associated_token_program: anchor_spl::associated_token::ID,
+            system_program: anchor_lang::system_program::ID,
+        },
+        instruction::ClawbackAirdrop {},
+    )
+}
+
+/// Grow `account` to the size the program lays its type out at, signed by
+/// the admin, `payer` topping up its rent
+pub fn resize_account(admin: &Pubkey, payer: &Pubkey, account: &Pubkey) -> Instruction {
+    build(
+        accounts::ResizeAccount {
+            admin: *admin,
+            payer: *payer,
+            config: pda::config().0,
+            account: *account,
+            system_program: anchor_lang::system_program::ID,
+        },
+        instruction::ResizeAccount {},
+    )
+}
+
+/// Mint `amount` from the devnet faucet to `wallet`'s associated token
+/// account, within the wallet's daily limit
+#[cfg(feature = "devnet-faucet")]
+pub fn faucet(wallet: &Pubkey, amount: u64) -> Instruction {
+    build(
+        accounts::Faucet {
+            wallet: *wallet,
+            config: pda::config().0,
+            mint: pda::mint().0,
+            supply_stats: pda::supply_stats().0,
+            faucet_claim: pda::faucet_claim(wallet).0,
+            destination: pda::token_account(wallet),
+            token_program: anchor_spl::token::ID,
+            associated_token_program: anchor_spl::associated_token::ID,
+            system_program: anchor_lang::system_program::ID,
+        },
+        instruction::Faucet { amount },
+    )
+}
+
+/// Record the mint's current supply in the supply stats, signed and paid
+/// for by `payer`
+pub fn sync_supply_stats(payer: &Pubkey) -> Instruction {
+    build(
+        accounts::SyncSupplyStats {
+            payer: *payer,
+            config: pda::config().0,
+            mint: pda::mint().0,
+            supply_stats: pda::supply_stats().0,
+            system_program: anchor_lang::system_program::ID,
+        },
+        instruction::SyncSupplyStats {},
+    )
+}
+
+pub fn pause(admin: &Pubkey) -> Instruction {
+    build(
+        accounts::Pause {
+            admin: *admin,
+            config: pda::config().0,
+        },
+        instruction::Pause {},
+    )
+}
+
+pub fn unpause(admin: &Pubkey) -> Instruction {
+    build(
+        accounts::Unpause {
+            admin: *admin,
+            config: pda::config().0,
+        },
+        instruction::Unpause {},
+    )
+}
+
+/// A program instruction with its arguments
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StablecoinInstruction {
+    Initialize,
+    ConfigureMinter {
+        allowance: u64,
+    },
+    RemoveMinter,
+    FreezeMinterSet,
+    UnfreezeMinterSet,
+    MintTokens {
+        amount: u64,
+    },
+    MintTokensIdempotent {
+        amount: u64,
+        key: [u8; 16],
+    },
+    ReclaimIdempotencyRecord,
+    PauseSelf,
+    ResumeMinter,
+    BurnTokens {
+        amount: u64,
+    },
+    SweepDust {
+        threshold: u64,
+    },
+    ConfigureTreasury {
+        officers: [Pubkey; 2],
+        threshold: u64,
+    },
+    ProposeTreasuryBurn {
+        id: u64,
+        amount: u64,
+    },
+    ApproveTreasuryBurn,
+    ExecuteTreasuryBurn,
+    CancelTreasuryBurn,
+    ConfigureRecovery {
+        guardians: Vec<Pubkey>,
+        quorum: u8,
+        challenge_period: i64,
+    },
+    InitiateRecovery {
+        new_owner: Pubkey,
+    },
+    ApproveRecovery,
+    ExecuteRecovery,
+    CancelRecovery,
+    CloseRecovery,
+    ConfigureInheritance {
+        beneficiary: Pubkey,
+        inactivity_epochs: u64,
+        notice_period: i64,
+    },
+    CheckIn,
+    StartInheritanceClaim,
+    ClaimInheritance,
+    CloseInheritance,
+    OpenNettingWindow {
+        id: u64,
+        institutions: Vec<Pubkey>,
+        duration: i64,
+    },
+    QueueObligation {
+        creditor: Pubkey,
+        amount: u64,
+    },
+    SettleNet,
+    CancelNettingWindow,
+    RegisterPurposeCode {
+        code: [u8; 4],
+    },
+    RetirePurposeCode,
+    OpenChannel {
+        recipient: Pubkey,
+        deposit: u64,
+        dispute_period: i64,
+    },
+    CloseChannel {
+        amount: u64,
+        signature: [u8; 64],
+    },
+    SettleChannel,
+    RegisterSubAccount {
+        index: u64,
+    },
+    SweepSubAccount,
+    OpenAirdrop {
+        id: u64,
+        recipients: Vec<Pubkey>,
+        amounts: Vec<u64>,
+        duration: i64,
+    },
+    ClaimAirdrop,
+    ClawbackAirdrop,
+    ResizeAccount,
+    SyncSupplyStats,
+    Pause,
+    Unpause,
+}
+
+impl StablecoinInstruction {
+    /// The instruction's name in the program
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Initialize => "initialize",
+            Self::ConfigureMinter { .. } => "configure_minter",
+            Self::RemoveMinter => "remove_minter",
+            Self::FreezeMinterSet => "freeze_minter_set",
+            Self::UnfreezeMinterSet => "unfreeze_minter_set",
+            Self::MintTokens { .. } => "mint_tokens",
+            Self::MintTokensIdempotent { .. } => "mint_tokens_idempotent",
+            Self::ReclaimIdempotencyRecord => "reclaim_idempotency_record",
+            Self::PauseSelf => "pause_self",
+            Self::ResumeMinter => "resume_minter",
+            Self::BurnTokens { .. } => "burn_tokens",
+            Self::SweepDust { .. } => "sweep_dust",
+            Self::ConfigureTreasury { .. } => "configure_treasury",
+            Self::ProposeTreasuryBurn { .. } => "propose_treasury_burn",
+            Self::ApproveTreasuryBurn => "approve_treasury_burn",
+            Self::ExecuteTreasuryBurn => "execute_treasury_burn",
+            Self::CancelTreasuryBurn => "cancel_treasury_burn",
+            Self::ConfigureRecovery { .. } => "configure_recovery",
+            Self::InitiateRecovery { .. } => "initiate_recovery",
+            Self::ApproveRecovery => "approve_recovery",
+            Self::ExecuteRecovery => "execute_recovery",
+            Self::CancelRecovery => "cancel_recovery",
+            Self::CloseRecovery => "close_recovery",
+            Self::ConfigureInheritance { .. } => "configure_inheritance",
+            Self::CheckIn => "check_in",
+            Self::StartInheritanceClaim => "start_inheritance_claim",
+            Self::ClaimInheritance => "claim_inheritance",
+            Self::CloseInheritance => "close_inheritance",
+            Self::OpenNettingWindow { .. } => "open_netting_window",
+            Self::QueueObligation { .. } => "queue_obligation",
+            Self::SettleNet => "settle_net",
+            Self::CancelNettingWindow => "cancel_netting_window",
+            Self::RegisterPurposeCode { .. } => "register_purpose_code",
+            Self::RetirePurposeCode => "retire_purpose_code",
+            Self::OpenChannel { .. } => "open_channel",
+            Self::CloseChannel { .. } => "close_channel",
+            Self::SettleChannel => "settle_channel",
+            Self::RegisterSubAccount { .. } => "register_sub_account",
+            Self::SweepSubAccount => "sweep_sub_account",
+            Self::OpenAirdrop { .. } => "open_airdrop",
+            Self::ClaimAirdrop => "claim_airdrop",
+            Self::ClawbackAirdrop => "clawback_airdrop",
+            Self::ResizeAccount => "resize_account",
+            Self::SyncSupplyStats => "sync_supply_stats",
+            Self::Pause => "pause",
+            Self::Unpause => "unpause",
+        }
+    }
+}
+
+/// Decode instruction data, `None` if it is not one of this program's
+/// instructions
+pub fn decode(data: &[u8]) -> Option<StablecoinInstruction> {
+    fn args<T: Discriminator + AnchorDeserialize>(data: &[u8]) -> Option<T> {
+        T::try_from_slice(data.strip_prefix(T::DISCRIMINATOR)?).ok()
+    }
+
+    if let Some(instruction::Initialize {}) = args(data) {
+        Some(StablecoinInstruction::Initialize)
+    } else if let Some(instruction::ConfigureMinter { allowance }) = args(data) {
+        Some(StablecoinInstruction::ConfigureMinter { allowance })
+    } else if let Some(instruction::RemoveMinter {}) = args(data) {
+        Some(StablecoinInstruction::RemoveMinter)
+    } else if let Some(instruction::FreezeMinterSet {}) = args(data) {
+        Some(StablecoinInstruction::FreezeMinterSet)
+    } else if let Some(instruction::UnfreezeMinterSet {}) = args(data) {
+        Some(StablecoinInstruction::UnfreezeMinterSet)
+    } else if let Some(instruction::MintTokens { amount }) = args(data) {
+        Some(StablecoinInstruction::MintTokens { amount })
+    } else if let Some(instruction::MintTokensIdempotent { amount, key }) = args(data) {
+        Some(StablecoinInstruction::MintTokensIdempotent { amount, key })
+    } else if let Some(instruction::ReclaimIdempotencyRecord {}) = args(data) {
+        Some(StablecoinInstruction::ReclaimIdempotencyRecord)
+    } else if let Some(instruction::PauseSelf {}) = args(data) {
+        Some(StablecoinInstruction::PauseSelf)
+    } else if let Some(instruction::ResumeMinter {}) = args(data) {
+        Some(StablecoinInstruction::ResumeMinter)
+    } else if let Some(instruction::BurnTokens { amount }) = args(data) {
+        Some(StablecoinInstruction::BurnTokens { amount })
+    } else if let Some(instruction::SweepDust { threshold }) = args(data) {
+        Some(StablecoinInstruction::SweepDust { threshold })
+    } else if let Some(instruction::ConfigureTreasury {
+        officers,
+        threshold,
+    }) = args(data)
+    {
+        Some(StablecoinInstruction::ConfigureTreasury {
+            officers,
+            threshold,
+        })
+    } else if let Some(instruction::ProposeTreasuryBurn { id, amount }) = args(data) {
+        Some(StablecoinInstruction::ProposeTreasuryBurn { id, amount })
+    } else if let Some(instruction::ApproveTreasuryBurn {}) = args(data) {
+        Some(StablecoinInstruction::ApproveTreasuryBurn)
+    } else if let Some(instruction::ExecuteTreasuryBurn {}) = args(data) {
+        Some(StablecoinInstruction::ExecuteTreasuryBurn)
+    } else if let Some(instruction::CancelTreasuryBurn {}) = args(data) {
+        Some(StablecoinInstruction::CancelTreasuryBurn)
+    } else if let Some(instruction::ConfigureRecovery {
+        guardians,
+        quorum,
+        challenge_period,
+    }) = args(data)
+    {
+        Some(StablecoinInstruction::ConfigureRecovery {
+            guardians,
+            quorum,
+            challenge_period,
+        })
+    } else if let Some(instruction::InitiateRecovery { new_owner }) = args(data) {
+        Some(StablecoinInstruction::InitiateRecovery { new_owner })
+    } else if let Some(instruction::ApproveRecovery {}) = args(data) {
+        Some(StablecoinInstruction::ApproveRecovery)
+    } else if let Some(instruction::ExecuteRecovery {}) = args(data) {
+        Some(StablecoinInstruction::ExecuteRecovery)
+    } else if let Some(instruction::CancelRecovery {}) = args(data) {
+        Some(StablecoinInstruction::CancelRecovery)
+    } else if let Some(instruction::CloseRecovery {}) = args(data) {
+        Some(StablecoinInstruction::CloseRecovery)
+    } else if let Some(instruction::ConfigureInheritance {
+        beneficiary,
+        inactivity_epochs,
+        notice_period,
+    }) = args(data)
+    {
+        Some(StablecoinInstruction::ConfigureInheritance {
+            beneficiary,
+            inactivity_epochs,
+            notice_period,
+        })
+    } else if let Some(instruction::CheckIn {}) = args(data) {
+        Some(StablecoinInstruction::CheckIn)
+    } else if let Some(instruction::StartInheritanceClaim {}) = args(data) {
+        Some(StablecoinInstruction::StartInheritanceClaim)
+    } else if let Some(instruction::ClaimInheritance {}) = args(data) {
+        Some(StablecoinInstruction::ClaimInheritance)
+    } else if let Some(instruction::CloseInheritance {}) = args(data) {
+        Some(StablecoinInstruction::CloseInheritance)
+    } else if let Some(instruction::OpenNettingWindow {
+        id,
+        institutions,
+        duration,
+    }) = args(data)
+    {
+        Some(StablecoinInstruction::OpenNettingWindow {
+            id,
+            institutions,
+            duration,
+        })
+    } else if let Some(instruction::QueueObligation { creditor, amount }) = args(data) {
+        Some(StablecoinInstruction::QueueObligation { creditor, amount })
+    } else if let Some(instruction::SettleNet {}) = args(data) {
+        Some(StablecoinInstruction::SettleNet)
+    } else if let Some(instruction::CancelNettingWindow {}) = args(data) {
+        Some(StablecoinInstruction::CancelNettingWindow)
+    } else if let Some(instruction::RegisterPurposeCode { code }) = args(data) {
+        Some(StablecoinInstruction::RegisterPurposeCode { code })
+    } else if let Some(instruction::RetirePurposeCode {}) = args(data) {
+        Some(StablecoinInstruction::RetirePurposeCode)
+    } else if let Some(instruction::OpenChannel {
+        recipient,
+        deposit,
+        dispute_period,
+    }) = args(data)
+    {
+        Some(StablecoinInstruction::OpenChannel {
+            recipient,
+            deposit,
+            dispute_period,
+        })
+    } else if let Some(instruction::CloseChannel { amount, signature }) = args(data) {
+        Some(StablecoinInstruction::CloseChannel { amount, signature })
+    } else if let Some(instruction::SettleChannel {}) = args(data) {
+        Some(StablecoinInstruction::SettleChannel)
+    } else if let Some(instruction::RegisterSubAccount { index }) = args(data) {
+        Some(StablecoinInstruction::RegisterSubAccount { index })
+    } else if let Some(instruction::SweepSubAccount {}) = args(data) {
+        Some(StablecoinInstruction::SweepSubAccount)
+    } else if let Some(instruction::OpenAirdrop {
+        id,
+        recipients,
+        amounts,
+        duration,
+    }) = args(data)
+    {
+        Some(StablecoinInstruction::OpenAirdrop {
+            id,
+            recipients,
+            amounts,
+            duration,
+        })
+    } else if let Some(instruction::ClaimAirdrop {}) = args(data) {
+        Some(StablecoinInstruction::ClaimAirdrop)
+    } else if let Some(instruction::ClawbackAirdrop {}) = args(data) {
+        Some(StablecoinInstruction::ClawbackAirdrop)
+    } else if let Some(instruction::ResizeAccount {}) = args(data) {
+        Some(StablecoinInstruction::ResizeAccount)
+    } else if let Some(instruction::SyncSupplyStats {}) = args(data) {
+        Some(StablecoinInstruction::SyncSupplyStats)
+    } else if let Some(instruction::Pause {}) = args(data) {
+        Some(StablecoinInstruction::Pause)
+    } else if let Some(instruction::Unpause {}) = args(data) {
+        Some(StablecoinInstruction::Unpause)
+    } else {
+        None
+    }
+}
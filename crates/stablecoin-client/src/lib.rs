@@ -0,0 +1,28 @@
+//! Rust client for the stablecoin program
+
+pub mod accounts;
+pub mod amount;
+#[cfg(feature = "rpc")]
+pub mod cost;
+pub mod diagnostics;
+pub mod error;
+pub mod events;
+#[cfg(feature = "rpc")]
+pub mod filters;
+#[cfg(feature = "rpc")]
+pub mod history;
+pub mod idl;
+pub mod instructions;
+pub mod offline;
+pub mod pda;
+#[cfg(feature = "rpc")]
+pub mod send;
+#[cfg(feature = "rpc")]
+pub mod subscribe;
+pub mod supply;
+pub mod tx;
+
+pub use amount::StableAmount;
+pub use error::ClientError;
+pub use stablecoin::ID as PROGRAM_ID;
+pub use tx::TxBuilder;
@@ -0,0 +1,230 @@
+//! Offline (air-gapped) signing
+//!
+//! The transaction is built on an online machine and handed to the signing
+//! machine as base64 text, which returns one `PUBKEY=SIGNATURE` line per key
+//! it holds. The online machine then attaches those and submits.
+//!
+//! ```ignore
+//! // online: build against a durable nonce, the signing round trip can take a while
+//! let nonce = fetch_nonce(&rpc, &nonce_account).await?;
+//! let tx = TxBuilder::new(fee_payer)
+//!     .durable_nonce(nonce_account, fee_payer)
+//!     .instruction(pause_ix)
+//!     .build_unsigned(nonce);
+//! write_transaction("pause.tx", &tx)?;
+//!
+//! // offline
+//! let tx = read_transaction("pause.tx")?;
+//! for sig in sign_partial(&tx, &[&admin])? {
+//!     println!("{sig}");
+//! }
+//!
+//! // online
+//! let mut tx = read_transaction("pause.tx")?;
+//! add_signature(&mut tx, &"<admin>=<signature>".parse()?)?;
+//! tx.partial_sign(&[&fee_payer], tx.message.recent_blockhash);
+//! submit(&rpc, tx).await?;
+//! ```
+
+use std::{fmt, path::Path, str::FromStr};
+
+use anchor_lang::prelude::Pubkey;
+use base64::{engine::general_purpose::STANDARD, Engine as _};
+use solana_hash::Hash;
+use solana_signature::Signature;
+use solana_signer::signers::Signers;
+use solana_transaction::Transaction;
+
+#[cfg(feature = "rpc")]
+use {
+    crate::error::{ClientError, Result},
+    solana_rpc_client::nonblocking::rpc_client::RpcClient,
+};
+
+#[derive(Debug, thiserror::Error)]
+pub enum OfflineError {
+    #[error("invalid base64: {0}")]
+    Base64(#[from] base64::DecodeError),
+    #[error("invalid base58: {0}")]
+    Base58(#[from] bs58::decode::Error),
+    #[error("invalid transaction encoding: {0}")]
+    Encoding(#[from] bincode::Error),
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+    #[error(transparent)]
+    Signer(#[from] solana_signer::SignerError),
+    #[error("invalid partial signature {0:?}, expected PUBKEY=SIGNATURE")]
+    InvalidPartialSignature(String),
+    #[error("{0} is not a required signer of this transaction")]
+    NotASigner(Pubkey),
+    #[error("signature for {0} does not match the transaction message")]
+    SignatureMismatch(Pubkey),
+    #[error("transaction is missing signatures from {0:?}")]
+    MissingSignatures(Vec<Pubkey>),
+    #[error("account is not an initialized nonce account")]
+    InvalidNonceAccount,
+}
+
+/// A signature produced on the offline machine, as `PUBKEY=SIGNATURE`
+///
+/// Same format as the `--signer` argument of the Solana CLI.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PartialSignature {
+    pub pubkey: Pubkey,
+    pub signature: Signature,
+}
+
+impl fmt::Display for PartialSignature {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}={}", self.pubkey, self.signature)
+    }
+}
+
+impl FromStr for PartialSignature {
+    type Err = OfflineError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || OfflineError::InvalidPartialSignature(s.to_string());
+        let (pubkey, signature) = s.trim().split_once('=').ok_or_else(invalid)?;
+        Ok(Self {
+            pubkey: pubkey.parse().map_err(|_| invalid())?,
+            signature: signature.parse().map_err(|_| invalid())?,
+        })
+    }
+}
+
+/// Base64 of the wire format; signatures already present are kept
+pub fn encode_transaction(tx: &Transaction) -> Result<String, OfflineError> {
+    Ok(STANDARD.encode(bincode::serialize(tx)?))
+}
+
+pub fn decode_transaction(encoded: &str) -> Result<Transaction, OfflineError> {
+    Ok(bincode::deserialize(&STANDARD.decode(encoded.trim())?)?)
+}
+
+/// Base58 of the wire format, as taken by multisig UIs that import
+/// transactions
+pub fn encode_transaction_base58(tx: &Transaction) -> Result<String, OfflineError> {
+    Ok(bs58::encode(bincode::serialize(tx)?).into_string())
+}
+
+pub fn decode_transaction_base58(encoded: &str) -> Result<Transaction, OfflineError> {
+    Ok(bincode::deserialize(
+        &bs58::decode(encoded.trim()).into_vec()?,
+    )?)
+}
+
+pub fn write_transaction(path: impl AsRef<Path>, tx: &Transaction) -> Result<(), OfflineError> {
+    let mut encoded = encode_transaction(tx)?;
+    encoded.push('\n');
+    Ok(std::fs::write(path, encoded)?)
+}
+
+pub fn read_transaction(path: impl AsRef<Path>) -> Result<Transaction, OfflineError> {
+    decode_transaction(&std::fs::read_to_string(path)?)
+}
+
+/// Sign the message with `signers` without modifying the transaction
+///
+/// Every signer must be one of the transaction's required signers, so a key
+/// is never used to sign something it wasn't asked for.
+pub fn sign_partial<T: Signers + ?Sized>(
+    tx: &Transaction,
+    signers: &T,
+) -> Result<Vec<PartialSignature>, OfflineError> {
+    let pubkeys = signers.try_pubkeys()?;
+    if let Some(pubkey) = pubkeys
+        .iter()
+        .find(|pubkey| signer_index(tx, pubkey).is_none())
+    {
+        return Err(OfflineError::NotASigner(*pubkey));
+    }
+
+    let signatures = signers.try_sign_message(&tx.message_data())?;
+    Ok(pubkeys
+        .into_iter()
+        .zip(signatures)
+        .map(|(pubkey, signature)| PartialSignature { pubkey, signature })
+        .collect())
+}
+
+/// Attach a signature produced by [`sign_partial`], after checking it
+pub fn add_signature(tx: &mut Transaction, partial: &PartialSignature) -> Result<(), OfflineError> {
+    let index =
+        signer_index(tx, &partial.pubkey).ok_or(OfflineError::NotASigner(partial.pubkey))?;
+    if !partial
+        .signature
+        .verify(partial.pubkey.as_ref(), &tx.message_data())
+    {
+        return Err(OfflineError::SignatureMismatch(partial.pubkey));
+    }
+    tx.signatures[index] = partial.signature;
+    Ok(())
+}
+
+/// Required signers that haven't signed yet
+pub fn missing_signers(tx: &Transaction) -> Vec<Pubkey> {
+    tx.signatures
+        .iter()
+        .zip(&tx.message.account_keys)
+        .filter(|(signature, _)| **signature == Signature::default())
+        .map(|(_, pubkey)| *pubkey)
+        .collect()
+}
+
+/// Check the transaction is fully signed and every signature verifies
+pub fn finalize(tx: Transaction) -> Result<Transaction, OfflineError> {
+    let missing = missing_signers(&tx);
+    if !missing.is_empty() {
+        return Err(OfflineError::MissingSignatures(missing));
+    }
+    let message = tx.message_data();
+    if let Some((_, pubkey)) = tx
+        .signatures
+        .iter()
+        .zip(&tx.message.account_keys)
+        .find(|(signature, pubkey)| !signature.verify(pubkey.as_ref(), &message))
+    {
+        return Err(OfflineError::SignatureMismatch(*pubkey));
+    }
+    Ok(tx)
+}
+
+/// The blockhash stored in a durable nonce account
+pub fn decode_nonce_account(data: &[u8]) -> Result<Hash, OfflineError> {
+    use solana_nonce::{state::State, versions::Versions};
+
+    let versions: Versions =
+        bincode::deserialize(data).map_err(|_| OfflineError::InvalidNonceAccount)?;
+    match versions.state() {
+        State::Initialized(data) => Ok(data.blockhash()),
+        State::Uninitialized => Err(OfflineError::InvalidNonceAccount),
+    }
+}
+
+#[cfg(feature = "rpc")]
+pub async fn fetch_nonce(rpc: &RpcClient, nonce_account: &Pubkey) -> Result<Hash> {
+    let account = rpc.get_account(nonce_account).await?;
+    if account.owner != solana_system_interface::program::ID {
+        return Err(OfflineError::InvalidNonceAccount.into());
+    }
+    Ok(decode_nonce_account(&account.data)?)
+}
+
+/// [`finalize`], then send and wait for confirmation
+#[cfg(feature = "rpc")]
+pub async fn submit(rpc: &RpcClient, tx: Transaction) -> Result<Signature> {
+    let tx = finalize(tx)?;
+    rpc.send_and_confirm_transaction(&tx)
+        .await
+        .map_err(ClientError::from)
+}
+
+fn signer_index(tx: &Transaction, pubkey: &Pubkey) -> Option<usize> {
+    let num_signers = tx.message.header.num_required_signatures as usize;
+    tx.message
+        .account_keys
+        .iter()
+        .take(num_signers)
+        .position(|key| key == pubkey)
+}
@@ -0,0 +1,166 @@
+//! Program-derived addresses
+//!
+//! Each function returns the address together with its bump.
+
+use anchor_lang::prelude::Pubkey;
+use anchor_spl::associated_token::get_associated_token_address;
+use stablecoin::seeds::{
+    AIRDROP_SEED, CHANNEL_COUNTER_SEED, CHANNEL_SEED, CONFIG_SEED, DELEGATE_SEED, IDEMPOTENCY_SEED,
+    INHERITANCE_SEED, MINTER_PAUSE_SEED, MINTER_SEED, MINTER_SET_FREEZE_SEED,
+    MINTER_TOMBSTONE_SEED, MINT_SEED, NETTING_SEED, PENDING_BURN_SEED, PURPOSE_SEED, RECOVERY_SEED,
+    SUB_ACCOUNT_SEED, SUPPLY_STATS_SEED, TREASURY_SEED,
+};
+
+pub fn config() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[CONFIG_SEED], &crate::PROGRAM_ID)
+}
+
+pub fn mint() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[MINT_SEED], &crate::PROGRAM_ID)
+}
+
+pub fn minter_config(minter: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[MINTER_SEED, minter.as_ref()], &crate::PROGRAM_ID)
+}
+
+/// The record an idempotent mint by `minter` with `key` creates
+pub fn idempotency_record(minter: &Pubkey, key: &[u8; 16]) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[IDEMPOTENCY_SEED, minter.as_ref(), key],
+        &crate::PROGRAM_ID,
+    )
+}
+
+/// The time-weighted supply stats
+pub fn supply_stats() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[SUPPLY_STATS_SEED], &crate::PROGRAM_ID)
+}
+
+/// The treasury, which owns treasury custody
+pub fn treasury() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[TREASURY_SEED], &crate::PROGRAM_ID)
+}
+
+/// The treasury burn proposed with `id`
+pub fn pending_burn(id: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[PENDING_BURN_SEED, &id.to_le_bytes()], &crate::PROGRAM_ID)
+}
+
+/// Treasury custody, the treasury's associated token account
+pub fn treasury_custody() -> Pubkey {
+    token_account(&treasury().0)
+}
+
+/// `owner`'s social recovery settings
+pub fn recovery_config(owner: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[RECOVERY_SEED, owner.as_ref()], &crate::PROGRAM_ID)
+}
+
+/// `owner`'s inheritance plan
+pub fn inheritance_plan(owner: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[INHERITANCE_SEED, owner.as_ref()], &crate::PROGRAM_ID)
+}
+
+/// The netting window opened with `id`
+pub fn netting_window(id: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[NETTING_SEED, &id.to_le_bytes()], &crate::PROGRAM_ID)
+}
+
+/// Custody of the netting window opened with `id`, the window's associated
+/// token account
+pub fn netting_custody(id: u64) -> Pubkey {
+    token_account(&netting_window(id).0)
+}
+
+/// The payment channel from `sender` to `recipient`
+pub fn payment_channel(sender: &Pubkey, recipient: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[CHANNEL_SEED, sender.as_ref(), recipient.as_ref()],
+        &crate::PROGRAM_ID,
+    )
+}
+
+/// The count of channels `sender` has opened to `recipient`
+pub fn channel_counter(sender: &Pubkey, recipient: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[CHANNEL_COUNTER_SEED, sender.as_ref(), recipient.as_ref()],
+        &crate::PROGRAM_ID,
+    )
+}
+
+/// Custody of the payment channel from `sender` to `recipient`, the
+/// channel's associated token account
+pub fn channel_custody(sender: &Pubkey, recipient: &Pubkey) -> Pubkey {
+    token_account(&payment_channel(sender, recipient).0)
+}
+
+/// `owner`'s sub-account numbered `index`
+pub fn sub_account(owner: &Pubkey, index: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[SUB_ACCOUNT_SEED, owner.as_ref(), &index.to_le_bytes()],
+        &crate::PROGRAM_ID,
+    )
+}
+
+/// Custody of `owner`'s sub-account numbered `index`, the sub-account's
+/// associated token account; the address deposits for that index go to
+pub fn sub_account_custody(owner: &Pubkey, index: u64) -> Pubkey {
+    token_account(&sub_account(owner, index).0)
+}
+
+/// `funder`'s airdrop with `id`
+pub fn airdrop(funder: &Pubkey, id: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[AIRDROP_SEED, funder.as_ref(), &id.to_le_bytes()],
+        &crate::PROGRAM_ID,
+    )
+}
+
+/// Custody of `funder`'s airdrop with `id`, the airdrop's associated token
+/// account
+pub fn airdrop_custody(funder: &Pubkey, id: u64) -> Pubkey {
+    token_account(&airdrop(funder, id).0)
+}
+
+/// `wallet`'s faucet claims, in devnet builds only
+#[cfg(feature = "devnet-faucet")]
+pub fn faucet_claim(wallet: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[stablecoin::seeds::FAUCET_SEED, wallet.as_ref()],
+        &crate::PROGRAM_ID,
+    )
+}
+
+/// The marker that exists while the minter set is frozen
+pub fn minter_set_freeze() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[MINTER_SET_FREEZE_SEED], &crate::PROGRAM_ID)
+}
+
+/// The marker that exists while `minter` has paused itself
+pub fn minter_pause(minter: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[MINTER_PAUSE_SEED, minter.as_ref()], &crate::PROGRAM_ID)
+}
+
+/// The tombstone left once `minter` has been removed
+pub fn minter_tombstone(minter: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[MINTER_TOMBSTONE_SEED, minter.as_ref()],
+        &crate::PROGRAM_ID,
+    )
+}
+
+/// The registry entry of purpose code `code`
+pub fn purpose_code(code: &[u8; 4]) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[PURPOSE_SEED, code], &crate::PROGRAM_ID)
+}
+
+/// The delegate holders opting into recovery or inheritance approve on
+/// their token account
+pub fn delegate() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[DELEGATE_SEED], &crate::PROGRAM_ID)
+}
+
+/// The owner's associated token account for the stablecoin mint
+pub fn token_account(owner: &Pubkey) -> Pubkey {
+    get_associated_token_address(owner, &mint().0)
+}
@@ -0,0 +1,163 @@
+//! Submission with retries that never lands the same intent twice
+//!
+//! Resubmitting after a timeout is only safe once the earlier transaction can
+//! no longer land. [`send_and_confirm_with_retry`] keeps every signature it
+//! has sent and only re-signs against a fresh blockhash after the previous
+//! one has expired with none of them processed. Until then it rebroadcasts
+//! the same signed bytes, which the cluster deduplicates by signature.
+//!
+//! That deduplication is by signature only, so a mint re-signed by another
+//! process, or after this one gave up, can still land twice. Minting with
+//! [`mint_tokens_idempotent`](crate::instructions::mint_tokens_idempotent)
+//! closes that gap: the program records the request's key and rejects a
+//! second mint under it with `DuplicateMint` for as long as the record
+//! exists, at least [`IDEMPOTENCY_KEY_TTL`](stablecoin::IDEMPOTENCY_KEY_TTL)
+//! seconds.
+
+use std::time::Duration;
+
+use solana_commitment_config::CommitmentConfig;
+use solana_rpc_client::nonblocking::rpc_client::RpcClient;
+use solana_rpc_client_api::config::RpcSendTransactionConfig;
+use solana_signature::Signature;
+use solana_signer::signers::Signers;
+use solana_transaction::versioned::VersionedTransaction;
+
+use crate::{
+    diagnostics::ErrorDiagnostics,
+    error::{ClientError, Result},
+    tx::TxBuilder,
+};
+
+#[derive(Debug, Clone)]
+pub struct RetryConfig {
+    /// Blockhashes to try before giving up
+    pub max_attempts: u32,
+    /// Delay before the first rebroadcast, doubled after each one
+    pub initial_backoff: Duration,
+    pub max_backoff: Duration,
+    /// How often signature statuses are polled
+    pub poll_interval: Duration,
+    pub commitment: CommitmentConfig,
+}
+
+impl Default for RetryConfig {
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            initial_backoff: Duration::from_millis(500),
+            max_backoff: Duration::from_secs(8),
+            poll_interval: Duration::from_millis(400),
+            commitment: CommitmentConfig::confirmed(),
+        }
+    }
+}
+
+/// Sign, send and wait until the transaction reaches `config.commitment`
+///
+/// The first broadcast runs preflight, so a transaction that would fail is
+/// reported as an error without being retried. Once it has been sent, errors
+/// from rebroadcasting are ignored and only signature statuses decide the
+/// outcome.
+pub async fn send_and_confirm_with_retry<T: Signers + ?Sized>(
+    rpc: &RpcClient,
+    builder: &TxBuilder,
+    signers: &T,
+    config: &RetryConfig,
+) -> Result<Signature> {
+    // Simulate once, so every attempt signs the same instructions
+    let builder = builder.clone().resolve_compute_units(rpc).await?;
+    let mut sent = Vec::new();
+
+    for attempt in 0..config.max_attempts {
+        let (blockhash, last_valid_block_height) = rpc
+            .get_latest_blockhash_with_commitment(config.commitment)
+            .await?;
+        let tx = builder.build_versioned_with_blockhash(signers, blockhash)?;
+        sent.push(tx.signatures[0]);
+
+        send(rpc, &tx, attempt > 0).await?;
+        if let Some(signature) =
+            wait_for_expiry(rpc, &tx, &sent, last_valid_block_height, config).await?
+        {
+            return Ok(signature);
+        }
+    }
+
+    Err(ClientError::Expired { signatures: sent })
+}
+
+/// Rebroadcast until one of `sent` confirms or the blockhash expires
+///
+/// Returns `None` once nothing sent so far can land anymore.
+async fn wait_for_expiry(
+    rpc: &RpcClient,
+    tx: &VersionedTransaction,
+    sent: &[Signature],
+    last_valid_block_height: u64,
+    config: &RetryConfig,
+) -> Result<Option<Signature>> {
+    let mut backoff = config.initial_backoff;
+    let mut until_resend = backoff;
+
+    loop {
+        tokio::time::sleep(config.poll_interval).await;
+
+        let statuses = rpc.get_signature_statuses(sent).await?.value;
+        let mut processed = false;
+        for (signature, status) in sent.iter().zip(statuses) {
+            let Some(status) = status else { continue };
+            if let Some(err) = status.err {
+                // Statuses don't carry logs, only the error code can be decoded
+                return Err(ClientError::TransactionFailed {
+                    signature: *signature,
+                    diagnostics: Box::new(ErrorDiagnostics::new(err, Vec::new())),
+                });
+            }
+            if status.satisfies_commitment(config.commitment) {
+                return Ok(Some(*signature));
+            }
+            processed = true;
+        }
+        // Landed but not yet at the requested commitment: keep waiting, it
+        // must not be replaced even if the blockhash has since expired
+        if processed {
+            continue;
+        }
+
+        let block_height = rpc
+            .get_block_height_with_commitment(config.commitment)
+            .await?;
+        if block_height > last_valid_block_height {
+            return Ok(None);
+        }
+
+        until_resend = until_resend.saturating_sub(config.poll_interval);
+        if until_resend.is_zero() {
+            send(rpc, tx, true).await?;
+            backoff = (backoff * 2).min(config.max_backoff);
+            until_resend = backoff;
+        }
+    }
+}
+
+async fn send(rpc: &RpcClient, tx: &VersionedTransaction, rebroadcast: bool) -> Result<()> {
+    let result = rpc
+        .send_transaction_with_config(
+            tx,
+            RpcSendTransactionConfig {
+                skip_preflight: rebroadcast,
+                // Retries are ours to manage
+                max_retries: Some(0),
+                ..RpcSendTransactionConfig::default()
+            },
+        )
+        .await;
+
+    match result {
+        Ok(_) => Ok(()),
+        // Rebroadcasts are best effort, statuses decide the outcome
+        Err(_) if rebroadcast => Ok(()),
+        Err(err) => Err(err.into()),
+    }
+}
@@ -0,0 +1,184 @@
+//! Live event streams over the RPC websocket
+//!
+//! [`subscribe_events`] wraps `logsSubscribe` with a mentions filter for the
+//! program. When the socket drops it reconnects with exponential backoff and
+//! replays whatever happened while it was away through the HTTP RPC, resuming
+//! from the last [`Cursor`] it delivered.
+
+use std::{
+    collections::HashSet,
+    pin::Pin,
+    str::FromStr,
+    task::{Context, Poll},
+    time::Duration,
+};
+
+use anchor_lang::prelude::Pubkey;
+use futures_util::{Stream, StreamExt};
+use solana_commitment_config::CommitmentConfig;
+use solana_pubsub_client::nonblocking::pubsub_client::PubsubClient;
+use solana_rpc_client::nonblocking::rpc_client::RpcClient;
+use solana_rpc_client_api::config::{RpcTransactionLogsConfig, RpcTransactionLogsFilter};
+use solana_signature::Signature;
+use tokio::{sync::mpsc, task::JoinHandle};
+
+use crate::{
+    error::{ClientError, Result},
+    events::parse_logs,
+    history::{events_since, Cursor, TransactionEvents},
+};
+
+/// Buffered items before the background task waits on the consumer
+const CHANNEL_CAPACITY: usize = 1_024;
+
+#[derive(Debug, Clone)]
+pub struct SubscribeConfig {
+    /// HTTP endpoint, used to replay gaps after a reconnect
+    pub rpc_url: String,
+    /// Websocket endpoint
+    pub ws_url: String,
+    pub program_id: Pubkey,
+    pub commitment: CommitmentConfig,
+    /// Resume after this transaction instead of starting from the live tip
+    pub cursor: Option<Cursor>,
+    /// First reconnect delay, doubled on every consecutive failure
+    pub reconnect_delay: Duration,
+    pub max_reconnect_delay: Duration,
+}
+
+impl SubscribeConfig {
+    pub fn new(rpc_url: impl Into<String>, ws_url: impl Into<String>) -> Self {
+        Self {
+            rpc_url: rpc_url.into(),
+            ws_url: ws_url.into(),
+            program_id: crate::PROGRAM_ID,
+            commitment: CommitmentConfig::confirmed(),
+            cursor: None,
+            reconnect_delay: Duration::from_millis(500),
+            max_reconnect_delay: Duration::from_secs(30),
+        }
+    }
+}
+
+/// Stream of decoded events, one item per transaction
+///
+/// `Err` items report connection problems; they are not terminal and the
+/// stream keeps reconnecting until it is dropped.
+pub struct EventStream {
+    rx: mpsc::Receiver<Result<TransactionEvents>>,
+    task: JoinHandle<()>,
+}
+
+impl Stream for EventStream {
+    type Item = Result<TransactionEvents>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.rx.poll_recv(cx)
+    }
+}
+
+impl Drop for EventStream {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+/// Subscribe to the program's events
+///
+/// Must be called from within a Tokio runtime; the subscription runs on a
+/// spawned task that stops when the returned stream is dropped.
+pub fn subscribe_events(config: SubscribeConfig) -> EventStream {
+    let (tx, rx) = mpsc::channel(CHANNEL_CAPACITY);
+    let task = tokio::spawn(run(config, tx));
+    EventStream { rx, task }
+}
+
+async fn run(config: SubscribeConfig, tx: mpsc::Sender<Result<TransactionEvents>>) {
+    let rpc = RpcClient::new_with_commitment(config.rpc_url.clone(), config.commitment);
+    let mut cursor = config.cursor;
+    let mut delay = config.reconnect_delay;
+
+    loop {
+        match stream_once(&config, &rpc, &mut cursor, &tx).await {
+            // Receiver dropped
+            Ok(false) => return,
+            // Socket closed cleanly, reconnect straight away
+            Ok(true) => delay = config.reconnect_delay,
+            Err(err) => {
+                if tx.send(Err(err)).await.is_err() {
+                    return;
+                }
+                tokio::time::sleep(delay).await;
+                delay = (delay * 2).min(config.max_reconnect_delay);
+            }
+        }
+    }
+}
+
+/// Run a single websocket session; `Ok(false)` means the consumer went away
+async fn stream_once(
+    config: &SubscribeConfig,
+    rpc: &RpcClient,
+    cursor: &mut Option<Cursor>,
+    tx: &mpsc::Sender<Result<TransactionEvents>>,
+) -> Result<bool> {
+    let pubsub = PubsubClient::new(&config.ws_url).await?;
+    let (mut logs, unsubscribe) = pubsub
+        .logs_subscribe(
+            RpcTransactionLogsFilter::Mentions(vec![config.program_id.to_string()]),
+            RpcTransactionLogsConfig {
+                commitment: Some(config.commitment),
+            },
+        )
+        .await?;
+
+    // Subscribe first, then replay: anything landing in between shows up in
+    // both and is dropped from the live side below.
+    let mut replayed = HashSet::new();
+    if let Some(last) = *cursor {
+        let missed = events_since(
+            rpc,
+            &config.program_id,
+            Some(last.signature),
+            config.commitment,
+        )
+        .await?;
+        for events in missed {
+            replayed.insert(events.signature);
+            *cursor = Some(events.cursor());
+            if tx.send(Ok(events)).await.is_err() {
+                return Ok(false);
+            }
+        }
+    }
+
+    while let Some(response) = logs.next().await {
+        if response.value.err.is_some() {
+            continue;
+        }
+        let signature = Signature::from_str(&response.value.signature)
+            .map_err(|_| ClientError::InvalidSignature(response.value.signature.clone()))?;
+        if replayed.remove(&signature) {
+            continue;
+        }
+
+        let events = parse_logs(&config.program_id, &response.value.logs)?;
+        if events.is_empty() {
+            continue;
+        }
+        let events = TransactionEvents {
+            slot: response.context.slot,
+            // Log notifications carry no block time
+            block_time: None,
+            signature,
+            events,
+        };
+        *cursor = Some(events.cursor());
+        if tx.send(Ok(events)).await.is_err() {
+            unsubscribe().await;
+            return Ok(false);
+        }
+    }
+
+    Ok(true)
+}
@@ -0,0 +1,175 @@
+//! Total and circulating supply
+//!
+//! Circulating supply is the mint's total supply minus balances that are
+//! issued but not in public hands. The program has no treasury, escrow or
+//! bridge of its own, so which token accounts those are is the caller's
+//! input, kept in one place as [`NonCirculating`].
+//!
+//! Average supply over a window comes from two reads of the program's
+//! supply stats, through [`average_supply`].
+
+use anchor_lang::prelude::Pubkey;
+use stablecoin::SupplyStats;
+
+#[cfg(feature = "rpc")]
+use {
+    crate::{
+        accounts::MAX_MULTIPLE_ACCOUNTS,
+        error::{ClientError, Result},
+    },
+    anchor_lang::AccountDeserialize,
+    anchor_spl::token::{Mint, TokenAccount},
+    solana_account_decoder_client_types::UiAccountEncoding,
+    solana_rpc_client::nonblocking::rpc_client::RpcClient,
+    solana_rpc_client_api::config::RpcAccountInfoConfig,
+    std::collections::HashSet,
+};
+
+/// Token accounts excluded from circulating supply, by category
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct NonCirculating {
+    pub treasury: Vec<Pubkey>,
+    pub escrow: Vec<Pubkey>,
+    pub bridge: Vec<Pubkey>,
+}
+
+/// Supply breakdown, in base units
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SupplyReport {
+    /// Slot the figures were read at
+    pub slot: u64,
+    pub decimals: u8,
+    pub total: u64,
+    pub treasury: u64,
+    pub escrowed: u64,
+    pub bridged: u64,
+    pub circulating: u64,
+}
+
+impl SupplyReport {
+    pub fn new(
+        slot: u64,
+        decimals: u8,
+        total: u64,
+        treasury: u64,
+        escrowed: u64,
+        bridged: u64,
+    ) -> Self {
+        let non_circulating = treasury.saturating_add(escrowed).saturating_add(bridged);
+        Self {
+            slot,
+            decimals,
+            total,
+            treasury,
+            escrowed,
+            bridged,
+            circulating: total.saturating_sub(non_circulating),
+        }
+    }
+}
+
+/// Average supply between `from` and `to`, given the supply stats read at
+/// each, in base units
+///
+/// Either read works for a time after it, as long as no supply change lands
+/// in between. `None` if `to` is not after `from`, or the reads are out of
+/// order.
+pub fn average_supply(
+    earlier: &SupplyStats,
+    from: i64,
+    later: &SupplyStats,
+    to: i64,
+) -> Option<u64> {
+    let seconds = u128::try_from(to.checked_sub(from)?)
+        .ok()
+        .filter(|&s| s > 0)?;
+    let start = earlier.cumulative_at(from).ok()?;
+    let end = later.cumulative_at(to).ok()?;
+    u64::try_from(end.checked_sub(start)? / seconds).ok()
+}
+
+/// Read the mint and every non-circulating account, and break supply down
+///
+/// Accounts listed more than once are counted once, in the first category
+/// that lists them. Closed accounts count as zero; accounts holding another
+/// mint are an error, since they would silently skew the figures.
+#[cfg(feature = "rpc")]
+pub async fn fetch_supply(
+    rpc: &RpcClient,
+    non_circulating: &NonCirculating,
+) -> Result<SupplyReport> {
+    let (mint_address, _) = crate::pda::mint();
+
+    // Mint first, then each category in order, each address once
+    let mut seen = HashSet::new();
+    let mut addresses = vec![mint_address];
+    let mut categories = Vec::new();
+    for (category, accounts) in [
+        (0, &non_circulating.treasury),
+        (1, &non_circulating.escrow),
+        (2, &non_circulating.bridge),
+    ] {
+        for address in accounts {
+            if seen.insert(*address) {
+                addresses.push(*address);
+                categories.push(category);
+            }
+        }
+    }
+
+    // Pin later batches to the first one's slot so they aren't read from
+    // an older bank
+    let mut slot = None;
+    let mut accounts = Vec::with_capacity(addresses.len());
+    for batch in addresses.chunks(MAX_MULTIPLE_ACCOUNTS) {
+        let response = rpc
+            .get_multiple_accounts_with_config(
+                batch,
+                RpcAccountInfoConfig {
+                    encoding: Some(UiAccountEncoding::Base64),
+                    commitment: Some(rpc.commitment()),
+                    min_context_slot: slot,
+                    ..Default::default()
+                },
+            )
+            .await?;
+        slot.get_or_insert(response.context.slot);
+        accounts.extend(response.value);
+    }
+
+    let mint_account = accounts[0]
+        .take()
+        .ok_or(ClientError::AccountNotFound(mint_address))?;
+    let mint = Mint::try_deserialize(&mut &mint_account.data[..]).map_err(|source| {
+        ClientError::AccountDecode {
+            address: mint_address,
+            source,
+        }
+    })?;
+
+    let mut held = [0u64; 3];
+    for ((address, account), category) in addresses[1..].iter().zip(&accounts[1..]).zip(categories)
+    {
+        let Some(account) = account else { continue };
+        let token_account =
+            TokenAccount::try_deserialize(&mut &account.data[..]).map_err(|source| {
+                ClientError::AccountDecode {
+                    address: *address,
+                    source,
+                }
+            })?;
+        if token_account.mint != mint_address {
+            return Err(ClientError::WrongMint(*address));
+        }
+        held[category] = held[category].saturating_add(token_account.amount);
+    }
+
+    Ok(SupplyReport::new(
+        slot.unwrap_or_default(),
+        mint.decimals,
+        mint.supply,
+        held[0],
+        held[1],
+        held[2],
+    ))
+}
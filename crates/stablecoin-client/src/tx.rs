@@ -0,0 +1,334 @@
+//! Transaction assembly with compute budget and priority fees
+//!
+//! Transactions are legacy unless lookup tables are attached, in which case
+//! the `*_versioned` builders produce v0 transactions resolving accounts
+//! through them.
+//!
+//! ```ignore
+//! let tx = TxBuilder::new(minter.pubkey())
+//!     .instruction(mint_ix)
+//!     .simulate_compute_units()
+//!     .compute_unit_price(50_000)
+//!     .build(&rpc, &[&minter])
+//!     .await?;
+//! ```
+
+use anchor_lang::prelude::Pubkey;
+use solana_address_lookup_table_interface::state::AddressLookupTable;
+use solana_compute_budget_interface::ComputeBudgetInstruction;
+use solana_hash::Hash;
+use solana_instruction::Instruction;
+use solana_message::{v0, AddressLookupTableAccount, CompileError, Message, VersionedMessage};
+use solana_signature::Signature;
+use solana_signer::signers::Signers;
+use solana_transaction::{versioned::VersionedTransaction, Transaction};
+
+use crate::error::{ClientError, Result};
+
+#[cfg(feature = "rpc")]
+use solana_rpc_client::nonblocking::rpc_client::RpcClient;
+
+/// Upper bound the runtime accepts for a single transaction
+pub const MAX_COMPUTE_UNIT_LIMIT: u32 = 1_400_000;
+
+/// Headroom added on top of simulated usage, in basis points
+pub const DEFAULT_SIMULATION_MARGIN_BPS: u32 = 1_000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ComputeUnitLimit {
+    /// Don't request a limit; the runtime default of 200k CU per instruction applies
+    Default,
+    Fixed(u32),
+    /// Simulate the transaction and request what it used plus a margin
+    Simulated {
+        margin_bps: u32,
+    },
+}
+
+#[derive(Debug, Clone)]
+pub struct TxBuilder {
+    payer: Pubkey,
+    instructions: Vec<Instruction>,
+    compute_unit_limit: ComputeUnitLimit,
+    compute_unit_price: Option<u64>,
+    durable_nonce: Option<(Pubkey, Pubkey)>,
+    lookup_tables: Vec<AddressLookupTableAccount>,
+}
+
+impl TxBuilder {
+    pub fn new(payer: Pubkey) -> Self {
+        Self {
+            payer,
+            instructions: Vec::new(),
+            compute_unit_limit: ComputeUnitLimit::Default,
+            compute_unit_price: None,
+            durable_nonce: None,
+            lookup_tables: Vec::new(),
+        }
+    }
+
+    pub fn instruction(mut self, instruction: Instruction) -> Self {
+        self.instructions.push(instruction);
+        self
+    }
+
+    pub fn instructions(mut self, instructions: impl IntoIterator<Item = Instruction>) -> Self {
+        self.instructions.extend(instructions);
+        self
+    }
+
+    pub fn compute_unit_limit(mut self, units: u32) -> Self {
+        self.compute_unit_limit = ComputeUnitLimit::Fixed(units);
+        self
+    }
+
+    /// Size the compute unit limit from a simulation when building
+    pub fn simulate_compute_units(self) -> Self {
+        self.simulate_compute_units_with_margin(DEFAULT_SIMULATION_MARGIN_BPS)
+    }
+
+    pub fn simulate_compute_units_with_margin(mut self, margin_bps: u32) -> Self {
+        self.compute_unit_limit = ComputeUnitLimit::Simulated { margin_bps };
+        self
+    }
+
+    /// Priority fee, in micro-lamports per compute unit
+    pub fn compute_unit_price(mut self, micro_lamports: u64) -> Self {
+        self.compute_unit_price = Some(micro_lamports);
+        self
+    }
+
+    /// Advance `nonce_account` first, so the transaction stays valid until the
+    /// nonce is used instead of expiring with the blockhash
+    ///
+    /// Pass the nonce value as the blockhash when building; see
+    /// [`crate::offline::fetch_nonce`].
+    pub fn durable_nonce(mut self, nonce_account: Pubkey, nonce_authority: Pubkey) -> Self {
+        self.durable_nonce = Some((nonce_account, nonce_authority));
+        self
+    }
+
+    /// Resolve accounts through `table` when building versioned transactions
+    pub fn address_lookup_table(mut self, table: AddressLookupTableAccount) -> Self {
+        self.lookup_tables.push(table);
+        self
+    }
+
+    pub fn address_lookup_tables(
+        mut self,
+        tables: impl IntoIterator<Item = AddressLookupTableAccount>,
+    ) -> Self {
+        self.lookup_tables.extend(tables);
+        self
+    }
+
+    /// Attach the lookup table at `address` if it exists
+    ///
+    /// A missing table is not an error: the versioned builders then fall back
+    /// to a legacy message, so the same code works before a table is created.
+    #[cfg(feature = "rpc")]
+    pub async fn resolve_lookup_table(self, rpc: &RpcClient, address: &Pubkey) -> Result<Self> {
+        Ok(match fetch_lookup_table(rpc, address).await? {
+            Some(table) => self.address_lookup_table(table),
+            None => self,
+        })
+    }
+
+    pub fn payer(&self) -> Pubkey {
+        self.payer
+    }
+
+    /// The full instruction list: nonce advance, compute budget, then the rest
+    ///
+    /// A simulated limit has no value yet at this point, so it is emitted as
+    /// [`MAX_COMPUTE_UNIT_LIMIT`], which is what the simulation itself runs with.
+    pub fn to_instructions(&self) -> Vec<Instruction> {
+        self.instructions_with_limit(self.simulation_placeholder_limit())
+    }
+
+    fn simulation_placeholder_limit(&self) -> Option<u32> {
+        match self.compute_unit_limit {
+            ComputeUnitLimit::Default => None,
+            ComputeUnitLimit::Fixed(units) => Some(units),
+            ComputeUnitLimit::Simulated { .. } => Some(MAX_COMPUTE_UNIT_LIMIT),
+        }
+    }
+
+    fn instructions_with_limit(&self, limit: Option<u32>) -> Vec<Instruction> {
+        let mut instructions = Vec::with_capacity(self.instructions.len() + 3);
+        // The runtime only recognises a nonce transaction by its first instruction
+        if let Some((nonce_account, nonce_authority)) = &self.durable_nonce {
+            instructions.push(solana_system_interface::instruction::advance_nonce_account(
+                nonce_account,
+                nonce_authority,
+            ));
+        }
+        if let Some(units) = limit {
+            instructions.push(ComputeBudgetInstruction::set_compute_unit_limit(units));
+        }
+        if let Some(price) = self.compute_unit_price {
+            instructions.push(ComputeBudgetInstruction::set_compute_unit_price(price));
+        }
+        instructions.extend(self.instructions.iter().cloned());
+        instructions
+    }
+
+    /// An unsigned transaction for the given blockhash
+    pub fn build_unsigned(&self, recent_blockhash: Hash) -> Transaction {
+        let mut tx = Transaction::new_with_payer(&self.to_instructions(), Some(&self.payer));
+        tx.message.recent_blockhash = recent_blockhash;
+        tx
+    }
+
+    /// Sign with `signers` against a known blockhash, without touching the network
+    ///
+    /// `signers` must cover every required signer.
+    pub fn build_with_blockhash<T: Signers + ?Sized>(
+        &self,
+        signers: &T,
+        recent_blockhash: Hash,
+    ) -> Result<Transaction, solana_signer::SignerError> {
+        let mut tx = self.build_unsigned(recent_blockhash);
+        tx.try_sign(signers, recent_blockhash)?;
+        Ok(tx)
+    }
+
+    /// Legacy message without lookup tables, v0 otherwise
+    fn message_with_limit(
+        &self,
+        limit: Option<u32>,
+        recent_blockhash: Hash,
+    ) -> Result<VersionedMessage, CompileError> {
+        let instructions = self.instructions_with_limit(limit);
+        if self.lookup_tables.is_empty() {
+            return Ok(VersionedMessage::Legacy(Message::new_with_blockhash(
+                &instructions,
+                Some(&self.payer),
+                &recent_blockhash,
+            )));
+        }
+        v0::Message::try_compile(
+            &self.payer,
+            &instructions,
+            &self.lookup_tables,
+            recent_blockhash,
+        )
+        .map(VersionedMessage::V0)
+    }
+
+    /// An unsigned versioned transaction for the given blockhash
+    pub fn build_versioned_unsigned(&self, recent_blockhash: Hash) -> Result<VersionedTransaction> {
+        let message =
+            self.message_with_limit(self.simulation_placeholder_limit(), recent_blockhash)?;
+        Ok(unsigned(message))
+    }
+
+    /// Versioned counterpart of [`Self::build_with_blockhash`]
+    pub fn build_versioned_with_blockhash<T: Signers + ?Sized>(
+        &self,
+        signers: &T,
+        recent_blockhash: Hash,
+    ) -> Result<VersionedTransaction> {
+        let message =
+            self.message_with_limit(self.simulation_placeholder_limit(), recent_blockhash)?;
+        Ok(VersionedTransaction::try_new(message, signers)?)
+    }
+
+    /// Versioned counterpart of [`Self::build`]
+    #[cfg(feature = "rpc")]
+    pub async fn build_versioned<T: Signers + ?Sized>(
+        &self,
+        rpc: &RpcClient,
+        signers: &T,
+    ) -> Result<VersionedTransaction> {
+        let limit = self.resolve_limit(rpc).await?;
+        let blockhash = rpc.get_latest_blockhash().await?;
+        let message = self.message_with_limit(limit, blockhash)?;
+        Ok(VersionedTransaction::try_new(message, signers)?)
+    }
+
+    /// Resolve the compute unit limit, fetch a fresh blockhash and sign
+    #[cfg(feature = "rpc")]
+    pub async fn build<T: Signers + ?Sized>(
+        &self,
+        rpc: &RpcClient,
+        signers: &T,
+    ) -> Result<Transaction> {
+        let limit = self.resolve_limit(rpc).await?;
+        let blockhash = rpc.get_latest_blockhash().await?;
+        let mut tx =
+            Transaction::new_with_payer(&self.instructions_with_limit(limit), Some(&self.payer));
+        tx.try_sign(signers, blockhash)?;
+        Ok(tx)
+    }
+
+    /// Run the compute unit simulation now and pin its result as a fixed limit
+    ///
+    /// Useful when the same builder is signed more than once, e.g. on retry.
+    #[cfg(feature = "rpc")]
+    pub async fn resolve_compute_units(mut self, rpc: &RpcClient) -> Result<Self> {
+        if let Some(units) = self.resolve_limit(rpc).await? {
+            self.compute_unit_limit = ComputeUnitLimit::Fixed(units);
+        }
+        Ok(self)
+    }
+
+    #[cfg(feature = "rpc")]
+    async fn resolve_limit(&self, rpc: &RpcClient) -> Result<Option<u32>> {
+        Ok(match self.compute_unit_limit {
+            ComputeUnitLimit::Default => None,
+            ComputeUnitLimit::Fixed(units) => Some(units),
+            ComputeUnitLimit::Simulated { margin_bps } => {
+                Some(self.simulate_limit(rpc, margin_bps).await?)
+            }
+        })
+    }
+
+    #[cfg(feature = "rpc")]
+    async fn simulate_limit(&self, rpc: &RpcClient, margin_bps: u32) -> Result<u32> {
+        let simulation = crate::diagnostics::simulate(rpc, self).await?;
+        let used = simulation
+            .units_consumed
+            .unwrap_or(MAX_COMPUTE_UNIT_LIMIT as u64);
+        Ok(apply_margin(used, margin_bps))
+    }
+}
+
+/// Decode a lookup table account as stored by the address lookup table program
+pub fn decode_lookup_table(address: Pubkey, data: &[u8]) -> Result<AddressLookupTableAccount> {
+    let table = AddressLookupTable::deserialize(data)
+        .map_err(|_| ClientError::InvalidLookupTable(address))?;
+    Ok(AddressLookupTableAccount {
+        key: address,
+        addresses: table.addresses.into_owned(),
+    })
+}
+
+/// The lookup table at `address`, or `None` if there is no account there
+#[cfg(feature = "rpc")]
+pub async fn fetch_lookup_table(
+    rpc: &RpcClient,
+    address: &Pubkey,
+) -> Result<Option<AddressLookupTableAccount>> {
+    let account = rpc
+        .get_account_with_commitment(address, rpc.commitment())
+        .await?
+        .value;
+    account
+        .map(|account| decode_lookup_table(*address, &account.data))
+        .transpose()
+}
+
+fn unsigned(message: VersionedMessage) -> VersionedTransaction {
+    let num_signers = message.header().num_required_signatures as usize;
+    VersionedTransaction {
+        signatures: vec![Signature::default(); num_signers],
+        message,
+    }
+}
+
+/// `units * (1 + margin_bps / 10_000)`, capped at [`MAX_COMPUTE_UNIT_LIMIT`]
+pub fn apply_margin(units: u64, margin_bps: u32) -> u32 {
+    let with_margin = units.saturating_mul(10_000 + margin_bps as u64) / 10_000;
+    with_margin.min(MAX_COMPUTE_UNIT_LIMIT as u64) as u32
+}
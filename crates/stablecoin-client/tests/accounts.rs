@@ -0,0 +1,165 @@
+use anchor_lang::{prelude::Pubkey, AccountSerialize};
+use stablecoin::{Config, MinterConfig};
+use stablecoin_client::{
+    accounts::{decode_config, decode_minter_config, MinterAccount},
+    ClientError,
+};
+
+fn minter_config(allowance: u64, amount_minted: u64) -> MinterConfig {
+    MinterConfig {
+        minter: Pubkey::new_unique(),
+        allowance,
+        amount_minted,
+        is_initialized: true,
+        bump: 255,
+        pause_bump: 255,
+    }
+}
+
+fn serialize(account: &impl AccountSerialize) -> Vec<u8> {
+    let mut data = Vec::new();
+    account.try_serialize(&mut data).unwrap();
+    data
+}
+
+#[test]
+fn test_decode_minter_config() {
+    let address = Pubkey::new_unique();
+    let config = minter_config(1_000, 400);
+
+    let account = decode_minter_config(address, &serialize(&config)).unwrap();
+    assert_eq!(account.address, address);
+    assert_eq!(account.config, config);
+    assert_eq!(account.remaining_allowance(), 600);
+}
+
+#[test]
+fn test_decode_rejects_other_accounts() {
+    let address = Pubkey::new_unique();
+    let mut data = serialize(&minter_config(1, 0));
+    data[0] ^= 1;
+
+    assert!(matches!(
+        decode_minter_config(address, &data),
+        Err(ClientError::AccountDecode { address: a, .. }) if a == address
+    ));
+}
+
+#[test]
+fn test_decode_config() {
+    let address = Pubkey::new_unique();
+    let config = Config {
+        admin: Pubkey::new_unique(),
+        mint: Pubkey::new_unique(),
+        paused: true,
+        bump: 254,
+        mint_bump: 253,
+        delegate_bump: 253,
+        minter_set_freeze_bump: 253,
+    };
+
+    assert_eq!(decode_config(address, &serialize(&config)).unwrap(), config);
+    assert!(matches!(
+        decode_config(address, &serialize(&minter_config(1, 0))),
+        Err(ClientError::AccountDecode { address: a, .. }) if a == address
+    ));
+}
+
+#[test]
+fn test_remaining_allowance_saturates() {
+    // An allowance lowered below what was already minted
+    let account = MinterAccount {
+        address: Pubkey::new_unique(),
+        config: minter_config(100, 250),
+    };
+    assert_eq!(account.remaining_allowance(), 0);
+}
+
+#[test]
+fn test_account_sizes() {
+    let config = Config {
+        admin: Pubkey::new_unique(),
+        mint: Pubkey::new_unique(),
+        paused: false,
+        bump: 255,
+        mint_bump: 254,
+        delegate_bump: 254,
+        minter_set_freeze_bump: 254,
+    };
+    assert_eq!(serialize(&config).len(), stablecoin::sizes::CONFIG);
+    assert_eq!(
+        serialize(&minter_config(1, 0)).len(),
+        stablecoin::sizes::MINTER_CONFIG
+    );
+}
+
+#[cfg(feature = "rpc")]
+mod rpc {
+    use std::collections::HashMap;
+
+    use base64::{engine::general_purpose::STANDARD, Engine as _};
+    use serde_json::{json, Value};
+    use solana_rpc_client::nonblocking::rpc_client::RpcClient;
+    use solana_rpc_client_api::request::RpcRequest;
+    use stablecoin_client::accounts::{fetch_minter, list_minters_page};
+
+    use super::*;
+
+    fn ui_account(data: &[u8]) -> Value {
+        json!({
+            "lamports": 1_000_000,
+            "data": [STANDARD.encode(data), "base64"],
+            "owner": stablecoin_client::PROGRAM_ID.to_string(),
+            "executable": false,
+            "rentEpoch": 0,
+            "space": data.len(),
+        })
+    }
+
+    #[tokio::test]
+    async fn test_list_minters_page() {
+        let mut addresses: Vec<Pubkey> = (0..3).map(|_| Pubkey::new_unique()).collect();
+        addresses.sort();
+        let configs: Vec<MinterConfig> = (0..3).map(|i| minter_config(100 * i, i)).collect();
+
+        let listed: Vec<Value> = addresses
+            .iter()
+            .rev()
+            .map(|address| json!({ "pubkey": address.to_string(), "account": ui_account(&[]) }))
+            .collect();
+        // Second page: the last address only
+        let fetched = json!({
+            "context": { "slot": 1 },
+            "value": [ui_account(&serialize(&configs[2]))],
+        });
+        let mocks = HashMap::from([
+            (RpcRequest::GetProgramAccounts, Value::Array(listed)),
+            (RpcRequest::GetMultipleAccounts, fetched),
+        ]);
+        let rpc = RpcClient::new_mock_with_mocks("succeeds".to_string(), mocks);
+
+        let page = list_minters_page(&rpc, Some(addresses[1]), 10)
+            .await
+            .unwrap();
+        assert_eq!(page.minters.len(), 1);
+        assert_eq!(page.minters[0].address, addresses[2]);
+        assert_eq!(page.minters[0].config, configs[2]);
+        assert_eq!(page.next, None);
+    }
+
+    #[tokio::test]
+    async fn test_fetch_minter() {
+        let minter = Pubkey::new_unique();
+        let config = minter_config(500, 20);
+        let account = json!({ "context": { "slot": 1 }, "value": ui_account(&serialize(&config)) });
+        let mocks = HashMap::from([(RpcRequest::GetAccountInfo, account)]);
+        let rpc = RpcClient::new_mock_with_mocks("succeeds".to_string(), mocks);
+
+        let fetched = fetch_minter(&rpc, &minter).await.unwrap().unwrap();
+        assert_eq!(
+            fetched.address,
+            stablecoin_client::pda::minter_config(&minter).0
+        );
+        assert_eq!(fetched.config, config);
+    }
+}
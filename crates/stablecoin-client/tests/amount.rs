@@ -0,0 +1,98 @@
+use stablecoin_client::{
+    amount::{AmountError, BASE_UNITS_PER_TOKEN},
+    instructions::{self, StablecoinInstruction},
+    StableAmount,
+};
+
+#[test]
+fn test_base_units_per_token_matches_mint_decimals() {
+    assert_eq!(BASE_UNITS_PER_TOKEN, 10u64.pow(stablecoin::DECIMALS.into()));
+    assert_eq!(BASE_UNITS_PER_TOKEN, 1_000_000);
+}
+
+#[test]
+fn test_from_ui() {
+    for (ui, base_units) in [
+        ("0", 0),
+        ("1", 1_000_000),
+        ("12.5", 12_500_000),
+        ("0.000001", 1),
+        ("007.10", 7_100_000),
+        ("18446744073709.551615", u64::MAX),
+    ] {
+        assert_eq!(
+            StableAmount::from_ui(ui).unwrap().base_units(),
+            base_units,
+            "{ui}"
+        );
+    }
+}
+
+#[test]
+fn test_from_ui_rejects() {
+    for ui in ["", ".5", "1.", "-1", "+1", "1,5", "1e6", " 1", "1.2.3"] {
+        assert_eq!(
+            StableAmount::from_ui(ui),
+            Err(AmountError::Invalid(ui.to_string())),
+            "{ui:?}"
+        );
+    }
+
+    // Never rounded away
+    assert_eq!(
+        StableAmount::from_ui("0.0000001"),
+        Err(AmountError::TooPrecise("0.0000001".to_string()))
+    );
+    for ui in ["18446744073709.551616", "99999999999999999999"] {
+        assert_eq!(
+            StableAmount::from_ui(ui),
+            Err(AmountError::Overflow(ui.to_string()))
+        );
+    }
+}
+
+#[test]
+fn test_to_ui_round_trips() {
+    for (base_units, ui) in [
+        (0, "0"),
+        (1, "0.000001"),
+        (1_000_000, "1"),
+        (12_500_000, "12.5"),
+        (u64::MAX, "18446744073709.551615"),
+    ] {
+        let amount = StableAmount::from_base_units(base_units);
+        assert_eq!(amount.to_ui(), ui);
+        assert_eq!(amount.to_string(), ui);
+        assert_eq!(ui.parse::<StableAmount>().unwrap(), amount);
+    }
+}
+
+#[test]
+fn test_from_tokens() {
+    assert_eq!(
+        StableAmount::from_tokens(3),
+        Some(StableAmount::from_base_units(3_000_000))
+    );
+    assert_eq!(StableAmount::from_tokens(u64::MAX), None);
+}
+
+#[test]
+fn test_checked_arithmetic() {
+    let one = StableAmount::from_tokens(1).unwrap();
+    let half: StableAmount = "0.5".parse().unwrap();
+    assert_eq!(one.checked_sub(half), Some(half));
+    assert_eq!(half.checked_add(half), Some(one));
+    assert_eq!(half.checked_sub(one), None);
+    assert_eq!(StableAmount::MAX.checked_add(half), None);
+}
+
+#[test]
+fn test_builders_take_base_units() {
+    let key = anchor_lang::prelude::Pubkey::new_unique();
+    let amount: StableAmount = "2.5".parse().unwrap();
+    let ix = instructions::mint_tokens(&key, &key, amount.base_units());
+    assert_eq!(
+        instructions::decode(&ix.data),
+        Some(StablecoinInstruction::MintTokens { amount: 2_500_000 })
+    );
+}
@@ -0,0 +1,131 @@
+#![cfg(feature = "rpc")]
+
+use anchor_lang::prelude::Pubkey;
+use base64::{engine::general_purpose::STANDARD, Engine as _};
+use serde_json::{json, Value};
+use solana_instruction::{AccountMeta, Instruction};
+use solana_rpc_client::{mock_sender::MocksMap, nonblocking::rpc_client::RpcClient};
+use solana_rpc_client_api::request::RpcRequest;
+use stablecoin_client::{
+    cost::{estimate_cost, fee_percentile, CostEstimate, DEFAULT_FEE_PERCENTILE},
+    TxBuilder,
+};
+
+fn account(lamports: u64, len: usize) -> Value {
+    json!({
+        "lamports": lamports,
+        "data": [STANDARD.encode(vec![0; len]), "base64"],
+        "owner": stablecoin_client::PROGRAM_ID.to_string(),
+        "executable": false,
+        "rentEpoch": 0,
+        "space": len,
+    })
+}
+
+fn response(value: Value) -> Value {
+    json!({ "context": { "slot": 1 }, "value": value })
+}
+
+#[test]
+fn test_fee_percentile() {
+    assert_eq!(fee_percentile(&[], DEFAULT_FEE_PERCENTILE), 0);
+    assert_eq!(fee_percentile(&[300, 0, 200, 100], 75), 200);
+    assert_eq!(fee_percentile(&[300, 0, 200, 100], 50), 100);
+    assert_eq!(fee_percentile(&[300, 0, 200, 100], 100), 300);
+    assert_eq!(fee_percentile(&[300, 0, 200, 100], 0), 0);
+    assert_eq!(fee_percentile(&[7], 1), 7);
+}
+
+#[test]
+fn test_priority_fee_rounds_up() {
+    let estimate = CostEstimate {
+        compute_units: 10_000,
+        compute_unit_limit: 11_000,
+        priority_fee_suggestion: 200,
+        base_fee: 5_000,
+        rent: 0,
+        rent_refunded: 0,
+    };
+    // 200 micro-lamports for each of 11k units is 2.2 lamports
+    assert_eq!(estimate.priority_fee(), 3);
+    assert_eq!(estimate.fee(), 5_003);
+}
+
+#[tokio::test]
+async fn test_estimate_cost() {
+    let payer = Pubkey::new_unique();
+    let [created, closed, grown, read] = [(); 4].map(|_| Pubkey::new_unique());
+    let builder = TxBuilder::new(payer).instruction(Instruction::new_with_bytes(
+        stablecoin_client::PROGRAM_ID,
+        &[1],
+        vec![
+            AccountMeta::new(payer, true),
+            AccountMeta::new(created, false),
+            AccountMeta::new(closed, false),
+            AccountMeta::new(grown, false),
+            AccountMeta::new_readonly(read, false),
+        ],
+    ));
+
+    let mocks = MocksMap::from_iter([
+        // The writable accounts, payer first, as they are and as the
+        // simulation leaves them
+        (
+            RpcRequest::GetMultipleAccounts,
+            response(json!([
+                account(1_000_000_000, 0),
+                null,
+                account(2_000_000, 10),
+                account(1_000_000, 40),
+            ])),
+        ),
+        (
+            RpcRequest::SimulateTransaction,
+            response(json!({
+                "err": null,
+                "logs": [],
+                "accounts": [
+                    account(999_000_000, 0),
+                    account(1_500_000, 100),
+                    account(0, 0),
+                    account(1_500_000, 70),
+                ],
+                "unitsConsumed": 10_000,
+            })),
+        ),
+        // For the created account, then the grown one after and before
+        (
+            RpcRequest::GetMinimumBalanceForRentExemption,
+            json!(1_500_000),
+        ),
+        (
+            RpcRequest::GetMinimumBalanceForRentExemption,
+            json!(1_400_000),
+        ),
+        (
+            RpcRequest::GetMinimumBalanceForRentExemption,
+            json!(1_100_000),
+        ),
+        (
+            RpcRequest::GetRecentPrioritizationFees,
+            json!([0, 300, 100, 200]
+                .iter()
+                .enumerate()
+                .map(|(slot, fee)| json!({ "slot": slot, "prioritizationFee": fee }))
+                .collect::<Vec<_>>()),
+        ),
+    ]);
+    let rpc = RpcClient::new_mock_with_mocks_map("succeeds", mocks);
+
+    assert_eq!(
+        estimate_cost(&rpc, &builder).await.unwrap(),
+        CostEstimate {
+            compute_units: 10_000,
+            compute_unit_limit: 11_000,
+            priority_fee_suggestion: 200,
+            base_fee: 5_000,
+            rent: 1_500_000 + 300_000,
+            rent_refunded: 2_000_000,
+        }
+    );
+}
@@ -0,0 +1,294 @@
+use solana_instruction::error::InstructionError;
+use solana_transaction_error::TransactionError;
+use stablecoin::{error_info, StablecoinError, ERROR_TABLE};
+use stablecoin_client::diagnostics::{
+    decode_error_code, parse_anchor_error, AnchorErrorLog, ErrorDiagnostics, ErrorOrigin,
+};
+
+fn logs(lines: &[&str]) -> Vec<String> {
+    lines.iter().map(|line| line.to_string()).collect()
+}
+
+#[test]
+fn test_decode_error_code() {
+    assert!(matches!(
+        decode_error_code(0x1771),
+        Some(StablecoinError::Paused)
+    ));
+    for error in [
+        StablecoinError::Unauthorized,
+        StablecoinError::Paused,
+        StablecoinError::ExceedsAllowance,
+        StablecoinError::NotMinter,
+        StablecoinError::Overflow,
+        StablecoinError::DuplicateMint,
+        StablecoinError::IdempotencyRecordNotExpired,
+        StablecoinError::NotOfficer,
+        StablecoinError::InvalidOfficers,
+        StablecoinError::AlreadyApproved,
+        StablecoinError::InsufficientApprovals,
+        StablecoinError::NotDust,
+        StablecoinError::NotGuardian,
+        StablecoinError::InvalidRecoveryConfig,
+        StablecoinError::RecoveryInProgress,
+        StablecoinError::NoRecoveryInProgress,
+        StablecoinError::GuardianAlreadyApproved,
+        StablecoinError::RecoveryNotApproved,
+        StablecoinError::RecoveryChallengeActive,
+        StablecoinError::NotBeneficiary,
+        StablecoinError::InvalidInheritancePlan,
+        StablecoinError::HolderStillActive,
+        StablecoinError::InheritanceClaimInProgress,
+        StablecoinError::NoInheritanceClaim,
+        StablecoinError::InheritanceNoticeActive,
+        StablecoinError::NotInstitution,
+        StablecoinError::InvalidNettingWindow,
+        StablecoinError::NettingWindowClosed,
+        StablecoinError::NettingWindowOpen,
+        StablecoinError::InvalidObligation,
+        StablecoinError::InvalidSettlementAccounts,
+        StablecoinError::InvalidPaymentChannel,
+        StablecoinError::NotChannelParty,
+        StablecoinError::InvalidChannelState,
+        StablecoinError::InvalidChannelSignature,
+        StablecoinError::ChannelDisputeOver,
+        StablecoinError::ChannelDisputeActive,
+        StablecoinError::InvalidPurposeCode,
+        StablecoinError::MinterSetFrozen,
+        StablecoinError::MinterPaused,
+        StablecoinError::FaucetLimitExceeded,
+        StablecoinError::InvalidAirdrop,
+        StablecoinError::NotAirdropRecipient,
+        StablecoinError::AirdropClaimed,
+        StablecoinError::AirdropClosed,
+        StablecoinError::AirdropOpen,
+        StablecoinError::CannotResize,
+        StablecoinError::MinterRetired,
+        StablecoinError::NewerAccountVersion,
+        StablecoinError::ObligationUnderfunded,
+        StablecoinError::DelegateNotApproved,
+    ] {
+        let decoded = decode_error_code(error.into()).unwrap();
+        assert_eq!(u32::from(decoded), u32::from(error));
+    }
+    assert!(decode_error_code(2006).is_none());
+    assert!(decode_error_code(6000 + 1_000).is_none());
+}
+
+#[test]
+fn test_error_table() {
+    let errors = [
+        StablecoinError::Unauthorized,
+        StablecoinError::Paused,
+        StablecoinError::ExceedsAllowance,
+        StablecoinError::NotMinter,
+        StablecoinError::Overflow,
+        StablecoinError::DuplicateMint,
+        StablecoinError::IdempotencyRecordNotExpired,
+        StablecoinError::NotOfficer,
+        StablecoinError::InvalidOfficers,
+        StablecoinError::AlreadyApproved,
+        StablecoinError::InsufficientApprovals,
+        StablecoinError::NotDust,
+        StablecoinError::NotGuardian,
+        StablecoinError::InvalidRecoveryConfig,
+        StablecoinError::RecoveryInProgress,
+        StablecoinError::NoRecoveryInProgress,
+        StablecoinError::GuardianAlreadyApproved,
+        StablecoinError::RecoveryNotApproved,
+        StablecoinError::RecoveryChallengeActive,
+        StablecoinError::NotBeneficiary,
+        StablecoinError::InvalidInheritancePlan,
+        StablecoinError::HolderStillActive,
+        StablecoinError::InheritanceClaimInProgress,
+        StablecoinError::NoInheritanceClaim,
+        StablecoinError::InheritanceNoticeActive,
+        StablecoinError::NotInstitution,
+        StablecoinError::InvalidNettingWindow,
+        StablecoinError::NettingWindowClosed,
+        StablecoinError::NettingWindowOpen,
+        StablecoinError::InvalidObligation,
+        StablecoinError::InvalidSettlementAccounts,
+        StablecoinError::InvalidPaymentChannel,
+        StablecoinError::NotChannelParty,
+        StablecoinError::InvalidChannelState,
+        StablecoinError::InvalidChannelSignature,
+        StablecoinError::ChannelDisputeOver,
+        StablecoinError::ChannelDisputeActive,
+        StablecoinError::InvalidPurposeCode,
+        StablecoinError::MinterSetFrozen,
+        StablecoinError::MinterPaused,
+        StablecoinError::FaucetLimitExceeded,
+        StablecoinError::InvalidAirdrop,
+        StablecoinError::NotAirdropRecipient,
+        StablecoinError::AirdropClaimed,
+        StablecoinError::AirdropClosed,
+        StablecoinError::AirdropOpen,
+        StablecoinError::CannotResize,
+        StablecoinError::MinterRetired,
+        StablecoinError::NewerAccountVersion,
+        StablecoinError::ObligationUnderfunded,
+        StablecoinError::DelegateNotApproved,
+    ];
+    assert_eq!(ERROR_TABLE.len(), errors.len());
+    for error in errors {
+        let info = error.info();
+        assert_eq!(info.code, u32::from(error));
+        assert_eq!(info.name, format!("{error:?}"));
+        assert_eq!(info.message, error.to_string());
+        assert!(!info.hint.is_empty());
+        assert_eq!(error_info(info.code), Some(info));
+    }
+    assert!(error_info(2006).is_none());
+    assert!(error_info(6051).is_none());
+
+    let diagnostics = ErrorDiagnostics::new(
+        TransactionError::InstructionError(0, InstructionError::Custom(6003)),
+        Vec::new(),
+    );
+    assert_eq!(diagnostics.hint(), Some(ERROR_TABLE[3].hint));
+}
+
+#[test]
+fn test_parse_account_error_with_pubkeys() {
+    let logs = logs(&[
+        "Program Stab1e11111111111111111111111111111111111 invoke [1]",
+        "Program log: Instruction: MintTokens",
+        "Program log: AnchorError caused by account: mint. Error Code: ConstraintSeeds. Error Number: 2006. Error Message: A seeds constraint was violated.",
+        "Program log: Left:",
+        "Program log: 11111111111111111111111111111111",
+        "Program log: Right:",
+        "Program log: SysvarRent111111111111111111111111111111111",
+        "Program Stab1e11111111111111111111111111111111111 failed: custom program error: 0x7d6",
+    ]);
+
+    assert_eq!(
+        parse_anchor_error(&logs),
+        Some(AnchorErrorLog {
+            name: "ConstraintSeeds".to_string(),
+            number: 2006,
+            message: "A seeds constraint was violated".to_string(),
+            origin: Some(ErrorOrigin::Account("mint".to_string())),
+            compared_values: Some((
+                "11111111111111111111111111111111".to_string(),
+                "SysvarRent111111111111111111111111111111111".to_string(),
+            )),
+        })
+    );
+}
+
+#[test]
+fn test_diagnostics_for_program_error() {
+    let logs = logs(&[
+        "Program log: Instruction: MintTokens",
+        "Program log: AnchorError thrown in programs/stablecoin/src/lib.rs:118. Error Code: ExceedsAllowance. Error Number: 6002. Error Message: Mint amount exceeds minter's remaining allowance.",
+    ]);
+    let diagnostics = ErrorDiagnostics::new(
+        TransactionError::InstructionError(1, InstructionError::Custom(6002)),
+        logs,
+    );
+
+    assert_eq!(diagnostics.instruction_index, Some(1));
+    assert!(matches!(
+        diagnostics.stablecoin_error,
+        Some(StablecoinError::ExceedsAllowance)
+    ));
+    assert_eq!(
+        diagnostics.anchor_error.as_ref().unwrap().origin,
+        Some(ErrorOrigin::Source {
+            file: "programs/stablecoin/src/lib.rs".to_string(),
+            line: 118,
+        })
+    );
+    assert_eq!(
+        diagnostics.to_string(),
+        "instruction 1: ExceedsAllowance (6002): Mint amount exceeds minter's remaining allowance, at programs/stablecoin/src/lib.rs:118"
+    );
+}
+
+#[test]
+fn test_diagnostics_without_logs() {
+    let diagnostics = ErrorDiagnostics::new(
+        TransactionError::InstructionError(0, InstructionError::Custom(0x1771)),
+        Vec::new(),
+    );
+    assert_eq!(
+        diagnostics.to_string(),
+        "instruction 0: Paused (6001): Minting is currently paused"
+    );
+
+    let diagnostics = ErrorDiagnostics::new(TransactionError::AccountInUse, Vec::new());
+    assert!(diagnostics.instruction_index.is_none());
+    assert_eq!(
+        diagnostics.to_string(),
+        TransactionError::AccountInUse.to_string()
+    );
+}
+
+#[test]
+fn test_unrelated_anchor_log_is_ignored() {
+    // An inner program logged an error, but the transaction failed differently
+    let logs = logs(&[
+        "Program log: AnchorError occurred. Error Code: Paused. Error Number: 6001. Error Message: Minting is currently paused.",
+    ]);
+    let diagnostics = ErrorDiagnostics::new(
+        TransactionError::InstructionError(0, InstructionError::InsufficientFunds),
+        logs,
+    );
+    assert!(diagnostics.anchor_error.is_none());
+}
+
+#[cfg(feature = "rpc")]
+mod rpc {
+    use std::collections::HashMap;
+
+    use anchor_lang::prelude::Pubkey;
+    use base64::{engine::general_purpose::STANDARD, Engine as _};
+    use serde_json::json;
+    use solana_instruction::Instruction;
+    use solana_rpc_client::nonblocking::rpc_client::RpcClient;
+    use solana_rpc_client_api::request::RpcRequest;
+    use stablecoin_client::{diagnostics::simulate_with_accounts, TxBuilder};
+
+    #[tokio::test]
+    async fn test_simulate_with_accounts() {
+        let account = |lamports: u64, data: &[u8]| {
+            json!({
+                "lamports": lamports,
+                "data": [STANDARD.encode(data), "base64"],
+                "owner": stablecoin_client::PROGRAM_ID.to_string(),
+                "executable": false,
+                "rentEpoch": 0,
+                "space": data.len(),
+            })
+        };
+        let result = json!({
+            "context": { "slot": 1 },
+            "value": {
+                "err": null,
+                "logs": [],
+                "unitsConsumed": 1_234,
+                "accounts": [account(1_000, &[1, 2, 3]), account(0, &[]), null],
+            },
+        });
+        let rpc = RpcClient::new_mock_with_mocks(
+            "succeeds".to_string(),
+            HashMap::from([(RpcRequest::SimulateTransaction, result)]),
+        );
+        let builder = TxBuilder::new(Pubkey::new_unique()).instruction(
+            Instruction::new_with_bytes(stablecoin_client::PROGRAM_ID, &[], Vec::new()),
+        );
+        let addresses = [
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+        ];
+
+        let simulation = simulate_with_accounts(&rpc, &builder, &addresses)
+            .await
+            .unwrap();
+        assert_eq!(simulation.units_consumed, Some(1_234));
+        // A closed account is reported the same as a missing one
+        assert_eq!(simulation.accounts, vec![Some(vec![1, 2, 3]), None, None]);
+    }
+}
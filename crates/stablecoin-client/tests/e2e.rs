@@ -0,0 +1,276 @@
+#![cfg(feature = "e2e")]
+
+// End-to-end tests against a live cluster running the program, through the
+// real RPC: preflight, blockhash expiry and confirmation behave as they do in
+// production, which litesvm does not model.
+//
+// Against a local validator:
+//
+//     anchor localnet
+//     cargo test -p stablecoin-client --features e2e --test e2e
+//
+// E2E_RPC_URL points elsewhere, e.g. at devnet. Every test makes its own
+// minter and holder, so the suite can run repeatedly against one deployment.
+// New accounts are airdropped, or funded by the keypair at E2E_PAYER_KEYPAIR
+// where airdrops are rationed. The config is initialized on first use by the
+// keypair at E2E_ADMIN_KEYPAIR, or a throwaway one; later runs against the
+// same deployment need E2E_ADMIN_KEYPAIR set to its admin.
+
+use std::{env, sync::OnceLock, time::Duration};
+
+use anchor_lang::prelude::Pubkey;
+use solana_commitment_config::CommitmentConfig;
+use solana_instruction::error::InstructionError;
+use solana_keypair::{read_keypair_file, Keypair};
+use solana_rpc_client::nonblocking::rpc_client::RpcClient;
+use solana_signer::Signer;
+use solana_transaction_error::TransactionError;
+use stablecoin::StablecoinError;
+use stablecoin_client::{
+    accounts, instructions, pda,
+    send::{send_and_confirm_with_retry, RetryConfig},
+    ClientError, TxBuilder,
+};
+
+const DEFAULT_RPC_URL: &str = "http://127.0.0.1:8899";
+
+/// Lamports each new account is funded with
+const FUNDING: u64 = 100_000_000;
+
+const ALLOWANCE: u64 = 1_000_000;
+
+/// Micro-lamports per compute unit, enough to exercise the priority fee path
+const PRIORITY_FEE: u64 = 1_000;
+
+fn rpc() -> RpcClient {
+    let url = env::var("E2E_RPC_URL").unwrap_or_else(|_| DEFAULT_RPC_URL.to_string());
+    RpcClient::new_with_commitment(url, CommitmentConfig::confirmed())
+}
+
+fn keypair_from_env(var: &str) -> Option<Keypair> {
+    let path = env::var(var).ok()?;
+    Some(read_keypair_file(&path).unwrap_or_else(|err| panic!("{var}={path}: {err}")))
+}
+
+/// The admin every test shares, so racing first uses initialize with the
+/// same key
+fn admin() -> &'static Keypair {
+    static ADMIN: OnceLock<Keypair> = OnceLock::new();
+    ADMIN.get_or_init(|| keypair_from_env("E2E_ADMIN_KEYPAIR").unwrap_or_else(Keypair::new))
+}
+
+fn builder(payer: &Keypair) -> TxBuilder {
+    TxBuilder::new(payer.pubkey())
+        .simulate_compute_units()
+        .compute_unit_price(PRIORITY_FEE)
+}
+
+async fn send(rpc: &RpcClient, builder: TxBuilder, signers: &[&Keypair]) {
+    send_and_confirm_with_retry(rpc, &builder, signers, &RetryConfig::default())
+        .await
+        .unwrap();
+}
+
+/// Bring `address` up to [`FUNDING`] lamports
+async fn fund(rpc: &RpcClient, address: &Pubkey) {
+    let balance = rpc.get_balance(address).await.unwrap();
+    let Some(lamports) = FUNDING.checked_sub(balance).filter(|&l| l > 0) else {
+        return;
+    };
+
+    if let Some(payer) = keypair_from_env("E2E_PAYER_KEYPAIR") {
+        let transfer =
+            solana_system_interface::instruction::transfer(&payer.pubkey(), address, lamports);
+        send(rpc, builder(&payer).instruction(transfer), &[&payer]).await;
+        return;
+    }
+    let signature = rpc.request_airdrop(address, lamports).await.unwrap();
+    while !rpc.confirm_transaction(&signature).await.unwrap() {
+        tokio::time::sleep(Duration::from_millis(500)).await;
+    }
+}
+
+async fn funded_account(rpc: &RpcClient) -> Keypair {
+    let keypair = Keypair::new();
+    fund(rpc, &keypair.pubkey()).await;
+    keypair
+}
+
+/// Initialize the config with [`admin`] unless it already is, and make sure
+/// [`admin`] can pay for its instructions
+async fn ensure_initialized(rpc: &RpcClient) {
+    let admin = admin();
+    fund(rpc, &admin.pubkey()).await;
+    if accounts::fetch_config(rpc).await.is_err() {
+        let ix = instructions::initialize(&admin.pubkey());
+        // Another test may have got there first
+        let _ = send_and_confirm_with_retry(
+            rpc,
+            &builder(admin).instruction(ix),
+            &[admin],
+            &RetryConfig::default(),
+        )
+        .await;
+    }
+
+    let config = accounts::fetch_config(rpc).await.unwrap();
+    assert_eq!(
+        config.admin,
+        admin.pubkey(),
+        "the deployment is administered by {}, set E2E_ADMIN_KEYPAIR",
+        config.admin
+    );
+    assert!(!config.paused, "the deployment is paused");
+}
+
+/// A fresh minter configured with [`ALLOWANCE`]
+async fn configured_minter(rpc: &RpcClient) -> Keypair {
+    ensure_initialized(rpc).await;
+    let admin = admin();
+    let minter = funded_account(rpc).await;
+    let ix = instructions::configure_minter(&admin.pubkey(), &minter.pubkey(), ALLOWANCE);
+    send(rpc, builder(admin).instruction(ix), &[admin]).await;
+    minter
+}
+
+async fn token_balance(rpc: &RpcClient, owner: &Pubkey) -> u64 {
+    let balance = rpc
+        .get_token_account_balance(&pda::token_account(owner))
+        .await
+        .unwrap();
+    balance.amount.parse().unwrap()
+}
+
+#[tokio::test]
+async fn test_mint_burn_lifecycle() {
+    let rpc = rpc();
+    let admin = admin();
+    let minter = configured_minter(&rpc).await;
+    let holder = funded_account(&rpc).await;
+    let holder_ata = pda::token_account(&holder.pubkey());
+    assert!(rpc.get_account(&holder_ata).await.is_err());
+
+    // The mint creates the holder's associated token account
+    let ix = instructions::mint_tokens(&minter.pubkey(), &holder.pubkey(), 600);
+    send(&rpc, builder(&minter).instruction(ix), &[&minter]).await;
+    assert_eq!(token_balance(&rpc, &holder.pubkey()).await, 600);
+    let ix = instructions::mint_tokens(&minter.pubkey(), &holder.pubkey(), 400);
+    send(&rpc, builder(&minter).instruction(ix), &[&minter]).await;
+    assert_eq!(token_balance(&rpc, &holder.pubkey()).await, 1_000);
+
+    let account = accounts::fetch_minter(&rpc, &minter.pubkey())
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(account.config.amount_minted, 1_000);
+    assert_eq!(account.remaining_allowance(), ALLOWANCE - 1_000);
+
+    let ix = instructions::burn_tokens(&holder.pubkey(), 250);
+    send(&rpc, builder(&holder).instruction(ix), &[&holder]).await;
+    assert_eq!(token_balance(&rpc, &holder.pubkey()).await, 750);
+
+    let ix = instructions::remove_minter(&admin.pubkey(), &minter.pubkey());
+    send(&rpc, builder(admin).instruction(ix), &[admin]).await;
+    assert!(accounts::fetch_minter(&rpc, &minter.pubkey())
+        .await
+        .unwrap()
+        .is_none());
+}
+
+#[tokio::test]
+async fn test_preflight_rejects_program_error() {
+    let rpc = rpc();
+    let minter = configured_minter(&rpc).await;
+    let holder = funded_account(&rpc).await;
+    let ix = instructions::mint_tokens(&minter.pubkey(), &holder.pubkey(), ALLOWANCE + 1);
+
+    // Compute unit simulation catches it first, decoded
+    let builder = builder(&minter).instruction(ix);
+    let err = send_and_confirm_with_retry(&rpc, &builder, &[&minter], &RetryConfig::default())
+        .await
+        .unwrap_err();
+    let ClientError::Simulation(diagnostics) = err else {
+        panic!("expected a simulation failure, got {err}");
+    };
+    assert!(matches!(
+        diagnostics.stablecoin_error,
+        Some(StablecoinError::ExceedsAllowance)
+    ));
+
+    // Without it, the node's preflight rejects the transaction before it
+    // is broadcast
+    let builder = builder.compute_unit_limit(200_000);
+    let err = send_and_confirm_with_retry(&rpc, &builder, &[&minter], &RetryConfig::default())
+        .await
+        .unwrap_err();
+    let ClientError::Rpc(err) = err else {
+        panic!("expected a preflight failure, got {err}");
+    };
+    assert!(matches!(
+        err.get_transaction_error(),
+        Some(TransactionError::InstructionError(_, InstructionError::Custom(code)))
+            if code == u32::from(StablecoinError::ExceedsAllowance)
+    ));
+    assert_eq!(
+        accounts::fetch_minter(&rpc, &minter.pubkey())
+            .await
+            .unwrap()
+            .unwrap()
+            .config
+            .amount_minted,
+        0
+    );
+}
+
+#[tokio::test]
+async fn test_expired_blockhash() {
+    let rpc = rpc();
+    let minter = configured_minter(&rpc).await;
+    let holder = funded_account(&rpc).await;
+    let ix = instructions::mint_tokens(&minter.pubkey(), &holder.pubkey(), 100);
+    let builder = builder(&minter).instruction(ix).compute_unit_limit(200_000);
+
+    // Signed, then held until its blockhash can no longer land; about a
+    // minute at 400ms slots
+    let (blockhash, last_valid_block_height) = rpc
+        .get_latest_blockhash_with_commitment(rpc.commitment())
+        .await
+        .unwrap();
+    let tx = builder
+        .build_versioned_with_blockhash(&[&minter], blockhash)
+        .unwrap();
+    while rpc.get_block_height().await.unwrap() <= last_valid_block_height {
+        tokio::time::sleep(Duration::from_secs(2)).await;
+    }
+    let err = rpc.send_transaction(&tx).await.unwrap_err();
+    assert_eq!(
+        err.get_transaction_error(),
+        Some(TransactionError::BlockhashNotFound)
+    );
+
+    // The retrying sender signs against a fresh blockhash
+    send(&rpc, builder, &[&minter]).await;
+    assert_eq!(token_balance(&rpc, &holder.pubkey()).await, 100);
+}
+
+#[tokio::test]
+async fn test_confirms_at_requested_commitment() {
+    let rpc = rpc();
+    let minter = configured_minter(&rpc).await;
+    let holder = funded_account(&rpc).await;
+    let ix = instructions::mint_tokens(&minter.pubkey(), &holder.pubkey(), 100);
+
+    let config = RetryConfig {
+        commitment: CommitmentConfig::finalized(),
+        ..RetryConfig::default()
+    };
+    let signature =
+        send_and_confirm_with_retry(&rpc, &builder(&minter).instruction(ix), &[&minter], &config)
+            .await
+            .unwrap();
+    let status = rpc
+        .get_signature_status_with_commitment(&signature, CommitmentConfig::finalized())
+        .await
+        .unwrap();
+    assert_eq!(status, Some(Ok(())));
+}
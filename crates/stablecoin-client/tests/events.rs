@@ -0,0 +1,134 @@
+use anchor_lang::{event::EVENT_IX_TAG_LE, prelude::Pubkey, Event};
+use base64::{engine::general_purpose::STANDARD, Engine as _};
+use stablecoin::{Paused, TokensBurned, TokensMinted};
+use stablecoin_client::{
+    events::{parse_cpi_event, parse_cpi_events, parse_logs, StablecoinEvent},
+    PROGRAM_ID,
+};
+
+const TOKEN_PROGRAM_ID: Pubkey =
+    Pubkey::from_str_const("TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA");
+
+fn data_log(event: &impl Event) -> String {
+    format!("Program data: {}", STANDARD.encode(event.data()))
+}
+
+fn minted() -> TokensMinted {
+    TokensMinted {
+        minter: Pubkey::new_unique(),
+        destination: Pubkey::new_unique(),
+        amount: 100_000_000,
+        remaining_allowance: 900_000_000,
+    }
+}
+
+#[test]
+fn test_parse_logs() {
+    let mint = minted();
+    let logs = vec![
+        format!("Program {} invoke [1]", PROGRAM_ID),
+        "Program log: Instruction: MintTokens".to_string(),
+        format!("Program {} invoke [2]", TOKEN_PROGRAM_ID),
+        "Program log: Instruction: MintTo".to_string(),
+        format!("Program {} success", TOKEN_PROGRAM_ID),
+        data_log(&mint),
+        format!("Program {} success", PROGRAM_ID),
+    ];
+
+    let events = parse_logs(&PROGRAM_ID, &logs).unwrap();
+    assert_eq!(events, vec![StablecoinEvent::TokensMinted(mint)]);
+}
+
+#[test]
+fn test_parse_logs_ignores_other_programs() {
+    let burn = TokensBurned {
+        owner: Pubkey::new_unique(),
+        token_account: Pubkey::new_unique(),
+        amount: 1,
+    };
+    let logs = vec![
+        format!("Program {} invoke [1]", PROGRAM_ID),
+        format!("Program {} invoke [2]", TOKEN_PROGRAM_ID),
+        // Emitted while the token program is executing, not ours
+        data_log(&burn),
+        format!("Program {} success", TOKEN_PROGRAM_ID),
+        format!("Program {} success", PROGRAM_ID),
+        format!("Program {} invoke [1]", TOKEN_PROGRAM_ID),
+        data_log(&burn),
+        format!("Program {} success", TOKEN_PROGRAM_ID),
+    ];
+
+    assert!(parse_logs(&PROGRAM_ID, &logs).unwrap().is_empty());
+}
+
+#[test]
+fn test_parse_logs_multiple_instructions() {
+    let mint = minted();
+    let pause = Paused {
+        admin: Pubkey::new_unique(),
+    };
+    let logs = vec![
+        format!("Program {} invoke [1]", PROGRAM_ID),
+        data_log(&mint),
+        format!("Program {} success", PROGRAM_ID),
+        format!("Program {} invoke [1]", PROGRAM_ID),
+        data_log(&pause),
+        format!("Program {} success", PROGRAM_ID),
+    ];
+
+    let events = parse_logs(&PROGRAM_ID, &logs).unwrap();
+    assert_eq!(
+        events,
+        vec![
+            StablecoinEvent::TokensMinted(mint),
+            StablecoinEvent::Paused(pause)
+        ]
+    );
+    assert_eq!(events[1].name(), "Paused");
+}
+
+#[test]
+fn test_parse_logs_skips_unknown_event() {
+    let logs = vec![
+        format!("Program {} invoke [1]", PROGRAM_ID),
+        format!("Program data: {}", STANDARD.encode([0u8; 16])),
+        format!("Program {} success", PROGRAM_ID),
+    ];
+
+    assert!(parse_logs(&PROGRAM_ID, &logs).unwrap().is_empty());
+}
+
+#[test]
+fn test_parse_logs_rejects_truncated_event() {
+    let mut data = minted().data();
+    data.truncate(20);
+    let logs = vec![
+        format!("Program {} invoke [1]", PROGRAM_ID),
+        format!("Program data: {}", STANDARD.encode(data)),
+        format!("Program {} success", PROGRAM_ID),
+    ];
+
+    assert!(parse_logs(&PROGRAM_ID, &logs).is_err());
+}
+
+#[test]
+fn test_parse_cpi_event() {
+    let mint = minted();
+    let mut ix_data = EVENT_IX_TAG_LE.to_vec();
+    ix_data.extend(mint.data());
+
+    assert_eq!(
+        parse_cpi_event(&ix_data).unwrap(),
+        Some(StablecoinEvent::TokensMinted(mint.clone()))
+    );
+    // Plain event bytes without the tag are not an event instruction
+    assert_eq!(parse_cpi_event(&mint.data()).unwrap(), None);
+
+    let other = Pubkey::new_unique();
+    let inner = [
+        (&other, ix_data.as_slice()),
+        (&PROGRAM_ID, ix_data.as_slice()),
+    ];
+    let events = parse_cpi_events(&PROGRAM_ID, inner).unwrap();
+    assert_eq!(events, vec![StablecoinEvent::TokensMinted(mint)]);
+}
@@ -0,0 +1,376 @@
+#![cfg(feature = "rpc")]
+
+use anchor_lang::{prelude::Pubkey, AccountSerialize, Discriminator};
+use solana_rpc_client_api::filter::RpcFilterType;
+use stablecoin::{
+    sizes, Airdrop, ChannelCounter, Config, IdempotencyRecord, InheritancePlan, MinterConfig,
+    MinterPause, MinterSetFreeze, MinterTombstone, NettingWindow, PaymentChannel, PendingBurn,
+    PurposeCode, RecoveryConfig, SubAccount, SupplyStats, Treasury,
+};
+use stablecoin_client::filters::{self, account_type, bytes_at, data_size, pubkey_at};
+
+fn serialize(account: &impl AccountSerialize) -> Vec<u8> {
+    let mut data = Vec::new();
+    account.try_serialize(&mut data).unwrap();
+    data
+}
+
+/// Whether every filter in `filters` matches `data`, as the node checks
+/// them
+fn matches(filters: &[RpcFilterType], data: &[u8]) -> bool {
+    filters.iter().all(|filter| match filter {
+        RpcFilterType::DataSize(size) => data.len() as u64 == *size,
+        RpcFilterType::Memcmp(memcmp) => memcmp.bytes_match(data),
+        RpcFilterType::TokenAccountState => false,
+    })
+}
+
+/// `account` serialized and padded to its allocated size, as it sits on
+/// chain
+fn on_chain<T: AccountSerialize + Discriminator + anchor_lang::Space>(account: &T) -> Vec<u8> {
+    let mut data = serialize(account);
+    data.resize(data_size::<T>() as usize, 0);
+    data
+}
+
+/// Assert the filter for each `(offset, bytes)` matches `data`, and that
+/// changing any one byte of the field breaks the match
+fn assert_fields(data: &[u8], fields: &[(usize, &[u8])]) {
+    for (offset, bytes) in fields {
+        assert!(
+            matches(&[bytes_at(*offset, bytes)], data),
+            "no match at offset {offset}"
+        );
+        for i in 0..bytes.len() {
+            let mut changed = bytes.to_vec();
+            changed[i] ^= 0xff;
+            assert!(
+                !matches(&[bytes_at(*offset, &changed)], data),
+                "byte {i} at offset {offset} ignored"
+            );
+        }
+    }
+}
+
+#[test]
+fn test_account_type_sizes() {
+    assert_eq!(data_size::<Config>(), sizes::CONFIG as u64);
+    assert_eq!(data_size::<MinterConfig>(), sizes::MINTER_CONFIG as u64);
+    assert_eq!(
+        data_size::<IdempotencyRecord>(),
+        sizes::IDEMPOTENCY_RECORD as u64
+    );
+    assert_eq!(data_size::<Treasury>(), sizes::TREASURY as u64);
+    assert_eq!(data_size::<PendingBurn>(), sizes::PENDING_BURN as u64);
+    assert_eq!(data_size::<RecoveryConfig>(), sizes::RECOVERY_CONFIG as u64);
+    assert_eq!(
+        data_size::<InheritancePlan>(),
+        sizes::INHERITANCE_PLAN as u64
+    );
+    assert_eq!(data_size::<SupplyStats>(), sizes::SUPPLY_STATS as u64);
+    assert_eq!(data_size::<NettingWindow>(), sizes::NETTING_WINDOW as u64);
+    assert_eq!(data_size::<PaymentChannel>(), sizes::PAYMENT_CHANNEL as u64);
+    assert_eq!(data_size::<ChannelCounter>(), sizes::CHANNEL_COUNTER as u64);
+    assert_eq!(data_size::<PurposeCode>(), sizes::PURPOSE_CODE as u64);
+    assert_eq!(
+        data_size::<MinterSetFreeze>(),
+        sizes::MINTER_SET_FREEZE as u64
+    );
+    assert_eq!(data_size::<MinterPause>(), sizes::MINTER_PAUSE as u64);
+    assert_eq!(
+        data_size::<MinterTombstone>(),
+        sizes::MINTER_TOMBSTONE as u64
+    );
+    assert_eq!(data_size::<SubAccount>(), sizes::SUB_ACCOUNT as u64);
+    assert_eq!(data_size::<Airdrop>(), sizes::AIRDROP as u64);
+}
+
+#[test]
+fn test_account_type_matches_only_its_type() {
+    let config = on_chain(&Config {
+        admin: Pubkey::new_unique(),
+        mint: Pubkey::new_unique(),
+        paused: false,
+        bump: 255,
+        mint_bump: 254,
+        delegate_bump: 254,
+        minter_set_freeze_bump: 254,
+    });
+    let minter_config = on_chain(&MinterConfig {
+        minter: Pubkey::new_unique(),
+        allowance: 1_000,
+        amount_minted: 400,
+        is_initialized: true,
+        bump: 255,
+        pause_bump: 255,
+    });
+    // Same size as a minter pause, different type
+    let minter_set_freeze = on_chain(&MinterSetFreeze {
+        frozen_by: Pubkey::new_unique(),
+        frozen_at: 1_700_000_000,
+        bump: 255,
+    });
+    assert_eq!(sizes::MINTER_SET_FREEZE, sizes::MINTER_PAUSE);
+
+    assert!(matches(&account_type::<Config>(), &config));
+    assert!(!matches(&account_type::<Config>(), &minter_config));
+    assert!(matches(&account_type::<MinterConfig>(), &minter_config));
+    assert!(!matches(&account_type::<MinterConfig>(), &config));
+    assert!(matches(
+        &account_type::<MinterSetFreeze>(),
+        &minter_set_freeze
+    ));
+    assert!(!matches(&account_type::<MinterPause>(), &minter_set_freeze));
+
+    // An account of the right type cut short is not matched
+    assert!(!matches(
+        &account_type::<MinterConfig>(),
+        &minter_config[..minter_config.len() - 1]
+    ));
+}
+
+#[test]
+fn test_admin_account_offsets() {
+    let (admin, mint) = (Pubkey::new_unique(), Pubkey::new_unique());
+    let data = on_chain(&Config {
+        admin,
+        mint,
+        paused: true,
+        bump: 255,
+        mint_bump: 254,
+        delegate_bump: 254,
+        minter_set_freeze_bump: 254,
+    });
+    assert_fields(
+        &data,
+        &[
+            (filters::config::ADMIN, admin.as_ref()),
+            (filters::config::MINT, mint.as_ref()),
+            (filters::config::PAUSED, &[1]),
+        ],
+    );
+
+    let frozen_by = Pubkey::new_unique();
+    let data = on_chain(&MinterSetFreeze {
+        frozen_by,
+        frozen_at: 1_700_000_000,
+        bump: 255,
+    });
+    assert_fields(
+        &data,
+        &[(filters::minter_set_freeze::FROZEN_BY, frozen_by.as_ref())],
+    );
+
+    let officers = [Pubkey::new_unique(), Pubkey::new_unique()];
+    let data = on_chain(&Treasury {
+        officers,
+        threshold: 1_000,
+        bump: 255,
+    });
+    assert_fields(
+        &data,
+        &[
+            (filters::treasury::OFFICERS, officers[0].as_ref()),
+            (filters::treasury::OFFICERS + 32, officers[1].as_ref()),
+        ],
+    );
+
+    let proposer = Pubkey::new_unique();
+    let data = on_chain(&PendingBurn {
+        id: 7,
+        proposer,
+        amount: 1_000,
+        approvals: [Pubkey::default(); 2],
+        bump: 255,
+    });
+    assert_fields(
+        &data,
+        &[
+            (filters::pending_burn::ID, &7u64.to_le_bytes()),
+            (filters::pending_burn::PROPOSER, proposer.as_ref()),
+        ],
+    );
+
+    let registered_by = Pubkey::new_unique();
+    let data = on_chain(&PurposeCode {
+        code: *b"PAYR",
+        registered_by,
+        bump: 255,
+    });
+    assert_fields(
+        &data,
+        &[
+            (filters::purpose_code::CODE, b"PAYR"),
+            (filters::purpose_code::REGISTERED_BY, registered_by.as_ref()),
+        ],
+    );
+
+    let opened_by = Pubkey::new_unique();
+    let data = on_chain(&NettingWindow {
+        id: 9,
+        opened_by,
+        institutions: vec![Pubkey::new_unique()],
+        debits: vec![1],
+        credits: vec![2],
+        escrowed: vec![3],
+        closes_at: 1_700_000_000,
+        bump: 255,
+    });
+    assert_fields(
+        &data,
+        &[
+            (filters::netting_window::ID, &9u64.to_le_bytes()),
+            (filters::netting_window::OPENED_BY, opened_by.as_ref()),
+        ],
+    );
+}
+
+#[test]
+fn test_minter_account_offsets() {
+    let minter = Pubkey::new_unique();
+    let data = on_chain(&MinterConfig {
+        minter,
+        allowance: 1_000,
+        amount_minted: 400,
+        is_initialized: true,
+        bump: 255,
+        pause_bump: 255,
+    });
+    assert_fields(
+        &data,
+        &[
+            (filters::minter_config::MINTER, minter.as_ref()),
+            (filters::minter_config::ALLOWANCE, &1_000u64.to_le_bytes()),
+            (filters::minter_config::AMOUNT_MINTED, &400u64.to_le_bytes()),
+        ],
+    );
+    assert!(matches(
+        &[
+            account_type::<MinterConfig>(),
+            vec![pubkey_at(filters::minter_config::MINTER, &minter)]
+        ]
+        .concat(),
+        &data
+    ));
+
+    let data = on_chain(&MinterPause {
+        minter,
+        paused_at: 1_700_000_000,
+        bump: 255,
+    });
+    assert_fields(&data, &[(filters::minter_pause::MINTER, minter.as_ref())]);
+
+    let removed_by = Pubkey::new_unique();
+    let data = on_chain(&MinterTombstone {
+        minter,
+        removed_by,
+        removed_at: 1_700_000_000,
+        amount_minted: 400,
+        bump: 255,
+    });
+    assert_fields(
+        &data,
+        &[
+            (filters::minter_tombstone::MINTER, minter.as_ref()),
+            (filters::minter_tombstone::REMOVED_BY, removed_by.as_ref()),
+        ],
+    );
+
+    let data = on_chain(&IdempotencyRecord {
+        minter,
+        key: [7; 16],
+        amount: 1_000,
+        expires_at: 1_700_000_000,
+        bump: 255,
+    });
+    assert_fields(
+        &data,
+        &[
+            (filters::idempotency_record::MINTER, minter.as_ref()),
+            (filters::idempotency_record::KEY, &[7; 16]),
+        ],
+    );
+}
+
+#[test]
+fn test_holder_account_offsets() {
+    let (owner, other) = (Pubkey::new_unique(), Pubkey::new_unique());
+    let data = on_chain(&RecoveryConfig {
+        owner,
+        guardians: vec![other],
+        quorum: 1,
+        challenge_period: 86_400,
+        new_owner: Pubkey::default(),
+        initiated_at: 0,
+        approvals: 0,
+        bump: 255,
+    });
+    assert_fields(&data, &[(filters::recovery_config::OWNER, owner.as_ref())]);
+
+    let data = on_chain(&InheritancePlan {
+        owner,
+        beneficiary: other,
+        inactivity_epochs: 10,
+        notice_period: 86_400,
+        last_check_in_epoch: 100,
+        claim_started_at: 0,
+        bump: 255,
+    });
+    assert_fields(
+        &data,
+        &[
+            (filters::inheritance_plan::OWNER, owner.as_ref()),
+            (filters::inheritance_plan::BENEFICIARY, other.as_ref()),
+        ],
+    );
+
+    let data = on_chain(&PaymentChannel {
+        sender: owner,
+        recipient: other,
+        deposit: 1_000,
+        dispute_period: 86_400,
+        opened_at: 1_700_000_000,
+        nonce: 2,
+        amount: 0,
+        closes_at: 0,
+        bump: 255,
+    });
+    assert_fields(
+        &data,
+        &[
+            (filters::payment_channel::SENDER, owner.as_ref()),
+            (filters::payment_channel::RECIPIENT, other.as_ref()),
+        ],
+    );
+
+    let data = on_chain(&SubAccount {
+        owner,
+        index: 3,
+        purpose: *b"PAYR",
+        bump: 255,
+    });
+    assert_fields(
+        &data,
+        &[
+            (filters::sub_account::OWNER, owner.as_ref()),
+            (filters::sub_account::INDEX, &3u64.to_le_bytes()),
+            (filters::sub_account::PURPOSE, b"PAYR"),
+        ],
+    );
+
+    let data = on_chain(&Airdrop {
+        funder: owner,
+        id: 5,
+        recipients: vec![other],
+        unclaimed: vec![100],
+        closes_at: 1_700_000_000,
+        bump: 255,
+    });
+    assert_fields(
+        &data,
+        &[
+            (filters::airdrop::FUNDER, owner.as_ref()),
+            (filters::airdrop::ID, &5u64.to_le_bytes()),
+        ],
+    );
+}
@@ -0,0 +1,449 @@
+use std::{env, fs};
+
+use anchor_lang::prelude::Pubkey;
+use solana_hash::Hash;
+use solana_instruction::{AccountMeta, Instruction};
+use solana_message::Message;
+use stablecoin_client::{
+    instructions::{self, StablecoinInstruction},
+    pda, TxBuilder,
+};
+
+/// Set to rewrite the fixtures from this run instead of checking them
+const UPDATE: &str = "UPDATE_MESSAGE_FIXTURES";
+
+/// Fixed keys and blockhash, so the messages are the same on every run
+const ADMIN: Pubkey = Pubkey::new_from_array([1; 32]);
+const MINTER: Pubkey = Pubkey::new_from_array([2; 32]);
+const HOLDER: Pubkey = Pubkey::new_from_array([3; 32]);
+const BLOCKHASH: Hash = Hash::new_from_array([7; 32]);
+
+/// Every instruction the SDK builds, with the payer that signs it and what
+/// it decodes to
+fn cases() -> Vec<(&'static str, Instruction, Pubkey, StablecoinInstruction)> {
+    vec![
+        (
+            "initialize",
+            instructions::initialize(&ADMIN),
+            ADMIN,
+            StablecoinInstruction::Initialize,
+        ),
+        (
+            "configure_minter",
+            instructions::configure_minter(&ADMIN, &MINTER, 1_000_000_000),
+            ADMIN,
+            StablecoinInstruction::ConfigureMinter {
+                allowance: 1_000_000_000,
+            },
+        ),
+        (
+            "remove_minter",
+            instructions::remove_minter(&ADMIN, &MINTER),
+            ADMIN,
+            StablecoinInstruction::RemoveMinter,
+        ),
+        (
+            "freeze_minter_set",
+            instructions::freeze_minter_set(&ADMIN),
+            ADMIN,
+            StablecoinInstruction::FreezeMinterSet,
+        ),
+        (
+            "unfreeze_minter_set",
+            instructions::unfreeze_minter_set(&ADMIN, &MINTER),
+            ADMIN,
+            StablecoinInstruction::UnfreezeMinterSet,
+        ),
+        (
+            "mint_tokens",
+            instructions::mint_tokens(&MINTER, &HOLDER, 100_000_000),
+            MINTER,
+            StablecoinInstruction::MintTokens {
+                amount: 100_000_000,
+            },
+        ),
+        (
+            "mint_tokens_idempotent",
+            instructions::mint_tokens_idempotent(&MINTER, &HOLDER, 100_000_000, [9; 16]),
+            MINTER,
+            StablecoinInstruction::MintTokensIdempotent {
+                amount: 100_000_000,
+                key: [9; 16],
+            },
+        ),
+        (
+            "reclaim_idempotency_record",
+            instructions::reclaim_idempotency_record(&MINTER, &[9; 16]),
+            MINTER,
+            StablecoinInstruction::ReclaimIdempotencyRecord,
+        ),
+        (
+            "pause_self",
+            instructions::pause_self(&MINTER),
+            MINTER,
+            StablecoinInstruction::PauseSelf,
+        ),
+        (
+            "resume_minter",
+            instructions::resume_minter(&ADMIN, &MINTER),
+            ADMIN,
+            StablecoinInstruction::ResumeMinter,
+        ),
+        (
+            "burn_tokens",
+            instructions::burn_tokens(&HOLDER, 50_000_000),
+            HOLDER,
+            StablecoinInstruction::BurnTokens { amount: 50_000_000 },
+        ),
+        (
+            "sweep_dust",
+            instructions::sweep_dust(&HOLDER, &[ADMIN, MINTER], 1_000),
+            HOLDER,
+            StablecoinInstruction::SweepDust { threshold: 1_000 },
+        ),
+        (
+            "configure_treasury",
+            instructions::configure_treasury(&ADMIN, [MINTER, HOLDER], 100_000_000),
+            ADMIN,
+            StablecoinInstruction::ConfigureTreasury {
+                officers: [MINTER, HOLDER],
+                threshold: 100_000_000,
+            },
+        ),
+        (
+            "propose_treasury_burn",
+            instructions::propose_treasury_burn(&MINTER, 1, 50_000_000),
+            MINTER,
+            StablecoinInstruction::ProposeTreasuryBurn {
+                id: 1,
+                amount: 50_000_000,
+            },
+        ),
+        (
+            "approve_treasury_burn",
+            instructions::approve_treasury_burn(&HOLDER, 1),
+            HOLDER,
+            StablecoinInstruction::ApproveTreasuryBurn,
+        ),
+        (
+            "execute_treasury_burn",
+            instructions::execute_treasury_burn(&HOLDER, &MINTER, 1),
+            HOLDER,
+            StablecoinInstruction::ExecuteTreasuryBurn,
+        ),
+        (
+            "cancel_treasury_burn",
+            instructions::cancel_treasury_burn(&HOLDER, &MINTER, 1),
+            HOLDER,
+            StablecoinInstruction::CancelTreasuryBurn,
+        ),
+        (
+            "configure_recovery",
+            instructions::configure_recovery(&HOLDER, vec![ADMIN, MINTER], 2, 86_400),
+            HOLDER,
+            StablecoinInstruction::ConfigureRecovery {
+                guardians: vec![ADMIN, MINTER],
+                quorum: 2,
+                challenge_period: 86_400,
+            },
+        ),
+        (
+            "initiate_recovery",
+            instructions::initiate_recovery(&ADMIN, &HOLDER, &MINTER),
+            ADMIN,
+            StablecoinInstruction::InitiateRecovery { new_owner: MINTER },
+        ),
+        (
+            "approve_recovery",
+            instructions::approve_recovery(&MINTER, &HOLDER),
+            MINTER,
+            StablecoinInstruction::ApproveRecovery,
+        ),
+        (
+            "execute_recovery",
+            instructions::execute_recovery(&ADMIN, &HOLDER, &MINTER),
+            ADMIN,
+            StablecoinInstruction::ExecuteRecovery,
+        ),
+        (
+            "cancel_recovery",
+            instructions::cancel_recovery(&HOLDER),
+            HOLDER,
+            StablecoinInstruction::CancelRecovery,
+        ),
+        (
+            "close_recovery",
+            instructions::close_recovery(&HOLDER),
+            HOLDER,
+            StablecoinInstruction::CloseRecovery,
+        ),
+        (
+            "configure_inheritance",
+            instructions::configure_inheritance(&HOLDER, &ADMIN, 3, 2_592_000),
+            HOLDER,
+            StablecoinInstruction::ConfigureInheritance {
+                beneficiary: ADMIN,
+                inactivity_epochs: 3,
+                notice_period: 2_592_000,
+            },
+        ),
+        (
+            "check_in",
+            instructions::check_in(&HOLDER),
+            HOLDER,
+            StablecoinInstruction::CheckIn,
+        ),
+        (
+            "start_inheritance_claim",
+            instructions::start_inheritance_claim(&ADMIN, &HOLDER),
+            ADMIN,
+            StablecoinInstruction::StartInheritanceClaim,
+        ),
+        (
+            "claim_inheritance",
+            instructions::claim_inheritance(&ADMIN, &HOLDER),
+            ADMIN,
+            StablecoinInstruction::ClaimInheritance,
+        ),
+        (
+            "close_inheritance",
+            instructions::close_inheritance(&HOLDER),
+            HOLDER,
+            StablecoinInstruction::CloseInheritance,
+        ),
+        (
+            "open_netting_window",
+            instructions::open_netting_window(&ADMIN, 1, vec![HOLDER, MINTER], 86_400),
+            ADMIN,
+            StablecoinInstruction::OpenNettingWindow {
+                id: 1,
+                institutions: vec![HOLDER, MINTER],
+                duration: 86_400,
+            },
+        ),
+        (
+            "queue_obligation",
+            instructions::queue_obligation(&HOLDER, 1, &MINTER, 50_000_000, Some(b"PAYR")),
+            HOLDER,
+            StablecoinInstruction::QueueObligation {
+                creditor: MINTER,
+                amount: 50_000_000,
+            },
+        ),
+        (
+            "settle_net",
+            instructions::settle_net(&HOLDER, &ADMIN, 1, &[HOLDER, MINTER]),
+            HOLDER,
+            StablecoinInstruction::SettleNet,
+        ),
+        (
+            "cancel_netting_window",
+            instructions::cancel_netting_window(&ADMIN, &MINTER, 1, &[HOLDER, MINTER]),
+            ADMIN,
+            StablecoinInstruction::CancelNettingWindow,
+        ),
+        (
+            "register_purpose_code",
+            instructions::register_purpose_code(&ADMIN, *b"PAYR"),
+            ADMIN,
+            StablecoinInstruction::RegisterPurposeCode { code: *b"PAYR" },
+        ),
+        (
+            "retire_purpose_code",
+            instructions::retire_purpose_code(&ADMIN, &MINTER, b"PAYR"),
+            ADMIN,
+            StablecoinInstruction::RetirePurposeCode,
+        ),
+        (
+            "open_channel",
+            instructions::open_channel(&HOLDER, &MINTER, 100_000_000, 3_600),
+            HOLDER,
+            StablecoinInstruction::OpenChannel {
+                recipient: MINTER,
+                deposit: 100_000_000,
+                dispute_period: 3_600,
+            },
+        ),
+        (
+            "close_channel",
+            instructions::close_channel(&MINTER, &HOLDER, &MINTER, 50_000_000, [7; 64]),
+            MINTER,
+            StablecoinInstruction::CloseChannel {
+                amount: 50_000_000,
+                signature: [7; 64],
+            },
+        ),
+        (
+            "settle_channel",
+            instructions::settle_channel(&ADMIN, &HOLDER, &MINTER),
+            ADMIN,
+            StablecoinInstruction::SettleChannel,
+        ),
+        (
+            "register_sub_account",
+            instructions::register_sub_account(&HOLDER, 7, b"PAYR"),
+            HOLDER,
+            StablecoinInstruction::RegisterSubAccount { index: 7 },
+        ),
+        (
+            "sweep_sub_account",
+            instructions::sweep_sub_account(&HOLDER, 7),
+            HOLDER,
+            StablecoinInstruction::SweepSubAccount,
+        ),
+        (
+            "open_airdrop",
+            instructions::open_airdrop(&HOLDER, 3, vec![MINTER, ADMIN], vec![4_000, 6_000], 86_400),
+            HOLDER,
+            StablecoinInstruction::OpenAirdrop {
+                id: 3,
+                recipients: vec![MINTER, ADMIN],
+                amounts: vec![4_000, 6_000],
+                duration: 86_400,
+            },
+        ),
+        (
+            "claim_airdrop",
+            instructions::claim_airdrop(&MINTER, &HOLDER, 3),
+            MINTER,
+            StablecoinInstruction::ClaimAirdrop,
+        ),
+        (
+            "clawback_airdrop",
+            instructions::clawback_airdrop(&ADMIN, &HOLDER, 3),
+            ADMIN,
+            StablecoinInstruction::ClawbackAirdrop,
+        ),
+        (
+            "resize_account",
+            instructions::resize_account(&ADMIN, &ADMIN, &pda::supply_stats().0),
+            ADMIN,
+            StablecoinInstruction::ResizeAccount,
+        ),
+        (
+            "sync_supply_stats",
+            instructions::sync_supply_stats(&HOLDER),
+            HOLDER,
+            StablecoinInstruction::SyncSupplyStats,
+        ),
+        (
+            "pause",
+            instructions::pause(&ADMIN),
+            ADMIN,
+            StablecoinInstruction::Pause,
+        ),
+        (
+            "unpause",
+            instructions::unpause(&ADMIN),
+            ADMIN,
+            StablecoinInstruction::Unpause,
+        ),
+    ]
+}
+
+fn fixture_path(name: &str) -> String {
+    format!(
+        "{}/tests/fixtures/messages/{name}.hex",
+        env!("CARGO_MANIFEST_DIR")
+    )
+}
+
+/// Compare `data` with the hex fixture `name`, 32 bytes to a line, and
+/// return the fixture's bytes
+fn assert_fixture(name: &str, data: &[u8]) -> Vec<u8> {
+    let path = fixture_path(name);
+    if env::var_os(UPDATE).is_some() {
+        let lines: Vec<String> = data
+            .chunks(32)
+            .map(|chunk| chunk.iter().map(|byte| format!("{byte:02x}")).collect())
+            .collect();
+        fs::write(&path, lines.join("\n") + "\n").unwrap();
+    }
+
+    let hex: String = fs::read_to_string(&path)
+        .unwrap_or_else(|err| panic!("failed to read {path}: {err}"))
+        .split_whitespace()
+        .collect();
+    let expected: Vec<u8> = (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).unwrap())
+        .collect();
+    assert_eq!(
+        data, expected,
+        "{name} message changed; the deployed program may not accept it. \
+         If the program changed with it, rerun with {UPDATE}=1"
+    );
+    expected
+}
+
+/// The instructions of `message`, with the signer and writable flags the
+/// header gives their accounts
+fn decompile(message: &Message) -> Vec<Instruction> {
+    message
+        .instructions
+        .iter()
+        .map(|compiled| Instruction {
+            program_id: message.account_keys[compiled.program_id_index as usize],
+            accounts: compiled
+                .accounts
+                .iter()
+                .map(|&index| {
+                    let index = index as usize;
+                    let pubkey = message.account_keys[index];
+                    if message.is_maybe_writable(index, None) {
+                        AccountMeta::new(pubkey, message.is_signer(index))
+                    } else {
+                        AccountMeta::new_readonly(pubkey, message.is_signer(index))
+                    }
+                })
+                .collect(),
+            data: compiled.data.clone(),
+        })
+        .collect()
+}
+
+#[test]
+fn test_instruction_messages_match_fixtures() {
+    for (name, ix, payer, expected) in cases() {
+        let message =
+            Message::new_with_blockhash(std::slice::from_ref(&ix), Some(&payer), &BLOCKHASH);
+        let golden = assert_fixture(name, &message.serialize());
+
+        // The fixture decodes back to the instruction it was built from
+        let decoded: Message = bincode::deserialize(&golden).unwrap();
+        assert_eq!(decoded.recent_blockhash, BLOCKHASH, "{name}");
+        assert_eq!(decoded.account_keys[0], payer, "{name}");
+        // The fee payer is writable in a message, whatever the instruction
+        // asked of it
+        let mut ix = ix;
+        for meta in ix.accounts.iter_mut().filter(|meta| meta.pubkey == payer) {
+            meta.is_writable = true;
+        }
+        assert_eq!(decompile(&decoded), [ix], "{name}");
+        assert_eq!(
+            instructions::decode(&decoded.instructions[0].data),
+            Some(expected),
+            "{name}"
+        );
+    }
+}
+
+#[test]
+fn test_builder_message_matches_fixture() {
+    let ix = instructions::mint_tokens(&MINTER, &HOLDER, 100_000_000);
+    let tx = TxBuilder::new(MINTER)
+        .instruction(ix.clone())
+        .compute_unit_limit(60_000)
+        .compute_unit_price(50_000)
+        .build_unsigned(BLOCKHASH);
+    let golden = assert_fixture("mint_tokens_with_priority_fee", &tx.message.serialize());
+
+    // Compute budget instructions first, then the mint untouched
+    let decoded: Message = bincode::deserialize(&golden).unwrap();
+    let instructions = decompile(&decoded);
+    assert_eq!(instructions.len(), 3);
+    assert!(instructions[..2]
+        .iter()
+        .all(|ix| ix.program_id == solana_compute_budget_interface::ID));
+    assert_eq!(instructions[2], ix);
+}
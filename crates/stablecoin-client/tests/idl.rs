@@ -0,0 +1,690 @@
+use anchor_lang::{prelude::Pubkey, AccountSerialize, Discriminator};
+use serde_json::Value;
+use stablecoin::{instruction, Config, MinterConfig, StablecoinError, SupplyStats};
+use stablecoin_client::{accounts, idl::IDL_JSON, instructions, PROGRAM_ID};
+
+fn idl() -> Value {
+    serde_json::from_str(IDL_JSON).unwrap()
+}
+
+/// The `discriminator` of the entry called `name` in `section`
+fn discriminator(idl: &Value, section: &str, name: &str) -> Vec<u8> {
+    let entry = idl[section]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|entry| entry["name"] == name)
+        .unwrap_or_else(|| panic!("{section} has no {name}"));
+    serde_json::from_value(entry["discriminator"].clone()).unwrap()
+}
+
+/// Anchor's discriminator: the first 8 bytes of `sha256("namespace:name")`
+fn get_discriminator(namespace: &str, name: &str) -> Vec<u8> {
+    let hash = solana_sha256_hasher::hash(format!("{namespace}:{name}").as_bytes());
+    hash.to_bytes()[..8].to_vec()
+}
+
+/// The names of every entry in `section`
+fn names(idl: &Value, section: &str) -> Vec<String> {
+    idl[section]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|entry| entry["name"].as_str().unwrap().to_string())
+        .collect()
+}
+
+#[test]
+fn test_idl_address() {
+    assert_eq!(idl()["address"], PROGRAM_ID.to_string());
+}
+
+#[test]
+fn test_idl_instructions() {
+    let idl = idl();
+    let instructions: [(&str, &[u8]); 46] = [
+        ("initialize", instruction::Initialize::DISCRIMINATOR),
+        (
+            "configure_minter",
+            instruction::ConfigureMinter::DISCRIMINATOR,
+        ),
+        ("remove_minter", instruction::RemoveMinter::DISCRIMINATOR),
+        (
+            "freeze_minter_set",
+            instruction::FreezeMinterSet::DISCRIMINATOR,
+        ),
+        (
+            "unfreeze_minter_set",
+            instruction::UnfreezeMinterSet::DISCRIMINATOR,
+        ),
+        ("mint_tokens", instruction::MintTokens::DISCRIMINATOR),
+        (
+            "mint_tokens_idempotent",
+            instruction::MintTokensIdempotent::DISCRIMINATOR,
+        ),
+        (
+            "reclaim_idempotency_record",
+            instruction::ReclaimIdempotencyRecord::DISCRIMINATOR,
+        ),
+        ("pause_self", instruction::PauseSelf::DISCRIMINATOR),
+        ("resume_minter", instruction::ResumeMinter::DISCRIMINATOR),
+        ("burn_tokens", instruction::BurnTokens::DISCRIMINATOR),
+        ("sweep_dust", instruction::SweepDust::DISCRIMINATOR),
+        (
+            "configure_treasury",
+            instruction::ConfigureTreasury::DISCRIMINATOR,
+        ),
+        (
+            "propose_treasury_burn",
+            instruction::ProposeTreasuryBurn::DISCRIMINATOR,
+        ),
+        (
+            "approve_treasury_burn",
+            instruction::ApproveTreasuryBurn::DISCRIMINATOR,
+        ),
+        (
+            "execute_treasury_burn",
+            instruction::ExecuteTreasuryBurn::DISCRIMINATOR,
+        ),
+        (
+            "cancel_treasury_burn",
+            instruction::CancelTreasuryBurn::DISCRIMINATOR,
+        ),
+        (
+            "configure_recovery",
+            instruction::ConfigureRecovery::DISCRIMINATOR,
+        ),
+        (
+            "initiate_recovery",
+            instruction::InitiateRecovery::DISCRIMINATOR,
+        ),
+        (
+            "approve_recovery",
+            instruction::ApproveRecovery::DISCRIMINATOR,
+        ),
+        (
+            "execute_recovery",
+            instruction::ExecuteRecovery::DISCRIMINATOR,
+        ),
+        (
+            "cancel_recovery",
+            instruction::CancelRecovery::DISCRIMINATOR,
+        ),
+        ("close_recovery", instruction::CloseRecovery::DISCRIMINATOR),
+        (
+            "configure_inheritance",
+            instruction::ConfigureInheritance::DISCRIMINATOR,
+        ),
+        ("check_in", instruction::CheckIn::DISCRIMINATOR),
+        (
+            "start_inheritance_claim",
+            instruction::StartInheritanceClaim::DISCRIMINATOR,
+        ),
+        (
+            "claim_inheritance",
+            instruction::ClaimInheritance::DISCRIMINATOR,
+        ),
+        (
+            "close_inheritance",
+            instruction::CloseInheritance::DISCRIMINATOR,
+        ),
+        (
+            "open_netting_window",
+            instruction::OpenNettingWindow::DISCRIMINATOR,
+        ),
+        (
+            "queue_obligation",
+            instruction::QueueObligation::DISCRIMINATOR,
+        ),
+        ("settle_net", instruction::SettleNet::DISCRIMINATOR),
+        (
+            "cancel_netting_window",
+            instruction::CancelNettingWindow::DISCRIMINATOR,
+        ),
+        (
+            "register_purpose_code",
+            instruction::RegisterPurposeCode::DISCRIMINATOR,
+        ),
+        (
+            "retire_purpose_code",
+            instruction::RetirePurposeCode::DISCRIMINATOR,
+        ),
+        ("open_channel", instruction::OpenChannel::DISCRIMINATOR),
+        ("close_channel", instruction::CloseChannel::DISCRIMINATOR),
+        ("settle_channel", instruction::SettleChannel::DISCRIMINATOR),
+        (
+            "register_sub_account",
+            instruction::RegisterSubAccount::DISCRIMINATOR,
+        ),
+        (
+            "sweep_sub_account",
+            instruction::SweepSubAccount::DISCRIMINATOR,
+        ),
+        ("open_airdrop", instruction::OpenAirdrop::DISCRIMINATOR),
+        ("claim_airdrop", instruction::ClaimAirdrop::DISCRIMINATOR),
+        (
+            "clawback_airdrop",
+            instruction::ClawbackAirdrop::DISCRIMINATOR,
+        ),
+        ("resize_account", instruction::ResizeAccount::DISCRIMINATOR),
+        (
+            "sync_supply_stats",
+            instruction::SyncSupplyStats::DISCRIMINATOR,
+        ),
+        ("pause", instruction::Pause::DISCRIMINATOR),
+        ("unpause", instruction::Unpause::DISCRIMINATOR),
+    ];
+    assert_eq!(
+        idl["instructions"].as_array().unwrap().len(),
+        instructions.len()
+    );
+    for (name, expected) in instructions {
+        assert_eq!(
+            discriminator(&idl, "instructions", name),
+            expected,
+            "{name}"
+        );
+    }
+}
+
+#[test]
+fn test_idl_accounts() {
+    let idl = idl();
+    assert_eq!(
+        discriminator(&idl, "accounts", "Config"),
+        stablecoin::Config::DISCRIMINATOR
+    );
+    assert_eq!(
+        discriminator(&idl, "accounts", "MinterConfig"),
+        stablecoin::MinterConfig::DISCRIMINATOR
+    );
+    assert_eq!(
+        discriminator(&idl, "accounts", "IdempotencyRecord"),
+        stablecoin::IdempotencyRecord::DISCRIMINATOR
+    );
+    assert_eq!(
+        discriminator(&idl, "accounts", "Treasury"),
+        stablecoin::Treasury::DISCRIMINATOR
+    );
+    assert_eq!(
+        discriminator(&idl, "accounts", "PendingBurn"),
+        stablecoin::PendingBurn::DISCRIMINATOR
+    );
+    assert_eq!(
+        discriminator(&idl, "accounts", "RecoveryConfig"),
+        stablecoin::RecoveryConfig::DISCRIMINATOR
+    );
+    assert_eq!(
+        discriminator(&idl, "accounts", "InheritancePlan"),
+        stablecoin::InheritancePlan::DISCRIMINATOR
+    );
+    assert_eq!(
+        discriminator(&idl, "accounts", "SupplyStats"),
+        stablecoin::SupplyStats::DISCRIMINATOR
+    );
+    assert_eq!(
+        discriminator(&idl, "accounts", "NettingWindow"),
+        stablecoin::NettingWindow::DISCRIMINATOR
+    );
+    assert_eq!(
+        discriminator(&idl, "accounts", "PaymentChannel"),
+        stablecoin::PaymentChannel::DISCRIMINATOR
+    );
+    assert_eq!(
+        discriminator(&idl, "accounts", "ChannelCounter"),
+        stablecoin::ChannelCounter::DISCRIMINATOR
+    );
+    assert_eq!(
+        discriminator(&idl, "accounts", "PurposeCode"),
+        stablecoin::PurposeCode::DISCRIMINATOR
+    );
+    assert_eq!(
+        discriminator(&idl, "accounts", "MinterSetFreeze"),
+        stablecoin::MinterSetFreeze::DISCRIMINATOR
+    );
+    assert_eq!(
+        discriminator(&idl, "accounts", "MinterPause"),
+        stablecoin::MinterPause::DISCRIMINATOR
+    );
+    assert_eq!(
+        discriminator(&idl, "accounts", "MinterTombstone"),
+        stablecoin::MinterTombstone::DISCRIMINATOR
+    );
+    assert_eq!(
+        discriminator(&idl, "accounts", "SubAccount"),
+        stablecoin::SubAccount::DISCRIMINATOR
+    );
+    assert_eq!(
+        discriminator(&idl, "accounts", "Airdrop"),
+        stablecoin::Airdrop::DISCRIMINATOR
+    );
+}
+
+#[test]
+fn test_idl_events() {
+    let idl = idl();
+    let events: [(&str, &[u8]); 33] = [
+        ("Initialized", stablecoin::Initialized::DISCRIMINATOR),
+        (
+            "MinterConfigured",
+            stablecoin::MinterConfigured::DISCRIMINATOR,
+        ),
+        ("MinterRemoved", stablecoin::MinterRemoved::DISCRIMINATOR),
+        ("TokensMinted", stablecoin::TokensMinted::DISCRIMINATOR),
+        ("TokensBurned", stablecoin::TokensBurned::DISCRIMINATOR),
+        ("Paused", stablecoin::Paused::DISCRIMINATOR),
+        ("Unpaused", stablecoin::Unpaused::DISCRIMINATOR),
+        (
+            "MinterSetFrozen",
+            stablecoin::MinterSetFrozen::DISCRIMINATOR,
+        ),
+        (
+            "MinterSetUnfrozen",
+            stablecoin::MinterSetUnfrozen::DISCRIMINATOR,
+        ),
+        (
+            "MinterSelfPaused",
+            stablecoin::MinterSelfPaused::DISCRIMINATOR,
+        ),
+        ("MinterResumed", stablecoin::MinterResumed::DISCRIMINATOR),
+        (
+            "TreasuryConfigured",
+            stablecoin::TreasuryConfigured::DISCRIMINATOR,
+        ),
+        (
+            "TreasuryBurnProposed",
+            stablecoin::TreasuryBurnProposed::DISCRIMINATOR,
+        ),
+        (
+            "TreasuryBurnApproved",
+            stablecoin::TreasuryBurnApproved::DISCRIMINATOR,
+        ),
+        (
+            "TreasuryBurnCancelled",
+            stablecoin::TreasuryBurnCancelled::DISCRIMINATOR,
+        ),
+        (
+            "RecoveryConfigured",
+            stablecoin::RecoveryConfigured::DISCRIMINATOR,
+        ),
+        (
+            "RecoveryInitiated",
+            stablecoin::RecoveryInitiated::DISCRIMINATOR,
+        ),
+        (
+            "RecoveryCancelled",
+            stablecoin::RecoveryCancelled::DISCRIMINATOR,
+        ),
+        (
+            "AccountRecovered",
+            stablecoin::AccountRecovered::DISCRIMINATOR,
+        ),
+        (
+            "InheritanceClaimStarted",
+            stablecoin::InheritanceClaimStarted::DISCRIMINATOR,
+        ),
+        (
+            "InheritanceConfigured",
+            stablecoin::InheritanceConfigured::DISCRIMINATOR,
+        ),
+        (
+            "InheritanceClaimStopped",
+            stablecoin::InheritanceClaimStopped::DISCRIMINATOR,
+        ),
+        (
+            "InheritanceClaimed",
+            stablecoin::InheritanceClaimed::DISCRIMINATOR,
+        ),
+        (
+            "ObligationQueued",
+            stablecoin::ObligationQueued::DISCRIMINATOR,
+        ),
+        ("NetSettled", stablecoin::NetSettled::DISCRIMINATOR),
+        ("ChannelClosing", stablecoin::ChannelClosing::DISCRIMINATOR),
+        ("ChannelSettled", stablecoin::ChannelSettled::DISCRIMINATOR),
+        (
+            "SubAccountRegistered",
+            stablecoin::SubAccountRegistered::DISCRIMINATOR,
+        ),
+        (
+            "SubAccountSwept",
+            stablecoin::SubAccountSwept::DISCRIMINATOR,
+        ),
+        ("AirdropOpened", stablecoin::AirdropOpened::DISCRIMINATOR),
+        ("AirdropClaimed", stablecoin::AirdropClaimed::DISCRIMINATOR),
+        (
+            "AirdropClawedBack",
+            stablecoin::AirdropClawedBack::DISCRIMINATOR,
+        ),
+        ("AccountResized", stablecoin::AccountResized::DISCRIMINATOR),
+    ];
+    assert_eq!(idl["events"].as_array().unwrap().len(), events.len());
+    for (name, expected) in events {
+        assert_eq!(discriminator(&idl, "events", name), expected, "{name}");
+    }
+}
+
+#[test]
+fn test_idl_errors() {
+    let errors = [
+        StablecoinError::Unauthorized,
+        StablecoinError::Paused,
+        StablecoinError::ExceedsAllowance,
+        StablecoinError::NotMinter,
+        StablecoinError::Overflow,
+        StablecoinError::DuplicateMint,
+        StablecoinError::IdempotencyRecordNotExpired,
+        StablecoinError::NotOfficer,
+        StablecoinError::InvalidOfficers,
+        StablecoinError::AlreadyApproved,
+        StablecoinError::InsufficientApprovals,
+        StablecoinError::NotDust,
+        StablecoinError::NotGuardian,
+        StablecoinError::InvalidRecoveryConfig,
+        StablecoinError::RecoveryInProgress,
+        StablecoinError::NoRecoveryInProgress,
+        StablecoinError::GuardianAlreadyApproved,
+        StablecoinError::RecoveryNotApproved,
+        StablecoinError::RecoveryChallengeActive,
+        StablecoinError::NotBeneficiary,
+        StablecoinError::InvalidInheritancePlan,
+        StablecoinError::HolderStillActive,
+        StablecoinError::InheritanceClaimInProgress,
+        StablecoinError::NoInheritanceClaim,
+        StablecoinError::InheritanceNoticeActive,
+        StablecoinError::NotInstitution,
+        StablecoinError::InvalidNettingWindow,
+        StablecoinError::NettingWindowClosed,
+        StablecoinError::NettingWindowOpen,
+        StablecoinError::InvalidObligation,
+        StablecoinError::InvalidSettlementAccounts,
+        StablecoinError::InvalidPaymentChannel,
+        StablecoinError::NotChannelParty,
+        StablecoinError::InvalidChannelState,
+        StablecoinError::InvalidChannelSignature,
+        StablecoinError::ChannelDisputeOver,
+        StablecoinError::ChannelDisputeActive,
+        StablecoinError::InvalidPurposeCode,
+        StablecoinError::MinterSetFrozen,
+        StablecoinError::MinterPaused,
+        StablecoinError::FaucetLimitExceeded,
+        StablecoinError::InvalidAirdrop,
+        StablecoinError::NotAirdropRecipient,
+        StablecoinError::AirdropClaimed,
+        StablecoinError::AirdropClosed,
+        StablecoinError::AirdropOpen,
+        StablecoinError::CannotResize,
+        StablecoinError::MinterRetired,
+        StablecoinError::NewerAccountVersion,
+        StablecoinError::ObligationUnderfunded,
+        StablecoinError::DelegateNotApproved,
+    ];
+    let idl = idl();
+    let entries = idl["errors"].as_array().unwrap();
+    assert_eq!(entries.len(), errors.len());
+    for (entry, error) in entries.iter().zip(errors) {
+        assert_eq!(entry["name"], format!("{error:?}"));
+        assert_eq!(entry["msg"], error.to_string());
+        assert_eq!(entry["code"], u32::from(error));
+    }
+}
+
+#[test]
+fn test_idl_seed_constants() {
+    let idl = idl();
+    let value = |name: &str| {
+        idl["constants"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|constant| constant["name"] == name)
+            .unwrap_or_else(|| panic!("no constant {name}"))["value"]
+            .clone()
+    };
+    // Anchor writes byte constants in their `Debug` form
+    assert_eq!(
+        value("CONFIG_SEED"),
+        format!("{:?}", stablecoin::seeds::CONFIG_SEED)
+    );
+    assert_eq!(
+        value("MINT_SEED"),
+        format!("{:?}", stablecoin::seeds::MINT_SEED)
+    );
+    assert_eq!(
+        value("MINTER_SEED"),
+        format!("{:?}", stablecoin::seeds::MINTER_SEED)
+    );
+    assert_eq!(
+        value("IDEMPOTENCY_SEED"),
+        format!("{:?}", stablecoin::seeds::IDEMPOTENCY_SEED)
+    );
+    assert_eq!(
+        value("TREASURY_SEED"),
+        format!("{:?}", stablecoin::seeds::TREASURY_SEED)
+    );
+    assert_eq!(
+        value("PENDING_BURN_SEED"),
+        format!("{:?}", stablecoin::seeds::PENDING_BURN_SEED)
+    );
+    assert_eq!(
+        value("RECOVERY_SEED"),
+        format!("{:?}", stablecoin::seeds::RECOVERY_SEED)
+    );
+    assert_eq!(
+        value("DELEGATE_SEED"),
+        format!("{:?}", stablecoin::seeds::DELEGATE_SEED)
+    );
+    assert_eq!(
+        value("INHERITANCE_SEED"),
+        format!("{:?}", stablecoin::seeds::INHERITANCE_SEED)
+    );
+    assert_eq!(
+        value("SUPPLY_STATS_SEED"),
+        format!("{:?}", stablecoin::seeds::SUPPLY_STATS_SEED)
+    );
+    assert_eq!(
+        value("NETTING_SEED"),
+        format!("{:?}", stablecoin::seeds::NETTING_SEED)
+    );
+    assert_eq!(
+        value("CHANNEL_SEED"),
+        format!("{:?}", stablecoin::seeds::CHANNEL_SEED)
+    );
+    assert_eq!(
+        value("CHANNEL_COUNTER_SEED"),
+        format!("{:?}", stablecoin::seeds::CHANNEL_COUNTER_SEED)
+    );
+    assert_eq!(
+        value("PURPOSE_SEED"),
+        format!("{:?}", stablecoin::seeds::PURPOSE_SEED)
+    );
+    assert_eq!(
+        value("MINTER_SET_FREEZE_SEED"),
+        format!("{:?}", stablecoin::seeds::MINTER_SET_FREEZE_SEED)
+    );
+    assert_eq!(
+        value("MINTER_PAUSE_SEED"),
+        format!("{:?}", stablecoin::seeds::MINTER_PAUSE_SEED)
+    );
+    assert_eq!(
+        value("MINTER_TOMBSTONE_SEED"),
+        format!("{:?}", stablecoin::seeds::MINTER_TOMBSTONE_SEED)
+    );
+    assert_eq!(
+        value("SUB_ACCOUNT_SEED"),
+        format!("{:?}", stablecoin::seeds::SUB_ACCOUNT_SEED)
+    );
+    assert_eq!(
+        value("AIRDROP_SEED"),
+        format!("{:?}", stablecoin::seeds::AIRDROP_SEED)
+    );
+}
+
+#[test]
+fn test_idl_decimals_constant() {
+    let idl = idl();
+    let decimals = idl["constants"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|constant| constant["name"] == "DECIMALS")
+        .unwrap();
+    assert_eq!(decimals["type"], "u8");
+    assert_eq!(decimals["value"], stablecoin::DECIMALS.to_string());
+}
+
+#[test]
+fn test_idl_leaves_out_devnet_faucet() {
+    // The published IDL is the mainnet build's, without the faucet feature
+    let idl = idl();
+    let names = |section: &str| -> Vec<String> {
+        idl[section]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|entry| entry["name"].as_str().unwrap().to_string())
+            .collect()
+    };
+    assert!(!names("instructions").contains(&"faucet".to_string()));
+    assert!(!names("accounts").contains(&"FaucetClaim".to_string()));
+    assert!(!names("constants").contains(&"FAUCET_SEED".to_string()));
+    assert!(!names("constants").contains(&"FAUCET_DAILY_LIMIT".to_string()));
+}
+
+#[test]
+fn test_idl_discriminators_are_sha256_of_names() {
+    let idl = idl();
+    // Renaming an instruction, account or event changes its discriminator,
+    // which breaks every integrator that hardcodes it
+    for (section, namespace) in [
+        ("instructions", "global"),
+        ("accounts", "account"),
+        ("events", "event"),
+    ] {
+        for name in names(&idl, section) {
+            assert_eq!(
+                discriminator(&idl, section, &name),
+                get_discriminator(namespace, &name),
+                "{section} {name}"
+            );
+        }
+    }
+}
+
+#[test]
+fn test_instruction_builders_match_idl() {
+    let idl = idl();
+    let key = Pubkey::new_unique();
+    let built = [
+        instructions::initialize(&key),
+        instructions::configure_minter(&key, &key, 1),
+        instructions::remove_minter(&key, &key),
+        instructions::freeze_minter_set(&key),
+        instructions::unfreeze_minter_set(&key, &key),
+        instructions::mint_tokens(&key, &key, 1),
+        instructions::mint_tokens_idempotent(&key, &key, 1, [1; 16]),
+        instructions::reclaim_idempotency_record(&key, &[1; 16]),
+        instructions::pause_self(&key),
+        instructions::resume_minter(&key, &key),
+        instructions::burn_tokens(&key, 1),
+        instructions::sweep_dust(&key, &[key], 1),
+        instructions::configure_treasury(&key, [key, key], 1),
+        instructions::propose_treasury_burn(&key, 1, 1),
+        instructions::approve_treasury_burn(&key, 1),
+        instructions::execute_treasury_burn(&key, &key, 1),
+        instructions::cancel_treasury_burn(&key, &key, 1),
+        instructions::configure_recovery(&key, vec![key], 1, 1),
+        instructions::initiate_recovery(&key, &key, &key),
+        instructions::approve_recovery(&key, &key),
+        instructions::execute_recovery(&key, &key, &key),
+        instructions::cancel_recovery(&key),
+        instructions::close_recovery(&key),
+        instructions::configure_inheritance(&key, &key, 1, 1),
+        instructions::check_in(&key),
+        instructions::start_inheritance_claim(&key, &key),
+        instructions::claim_inheritance(&key, &key),
+        instructions::close_inheritance(&key),
+        instructions::open_netting_window(&key, 1, vec![key, key], 1),
+        instructions::queue_obligation(&key, 1, &key, 1, None),
+        instructions::settle_net(&key, &key, 1, &[key, key]),
+        instructions::cancel_netting_window(&key, &key, 1, &[key, key]),
+        instructions::register_purpose_code(&key, *b"PAYR"),
+        instructions::retire_purpose_code(&key, &key, b"PAYR"),
+        instructions::open_channel(&key, &key, 1, 1),
+        instructions::close_channel(&key, &key, &key, 1, [1; 64]),
+        instructions::settle_channel(&key, &key, &key),
+        instructions::register_sub_account(&key, 1, b"PAYR"),
+        instructions::sweep_sub_account(&key, 1),
+        instructions::open_airdrop(&key, 1, vec![key], vec![1], 1),
+        instructions::claim_airdrop(&key, &key, 1),
+        instructions::clawback_airdrop(&key, &key, 1),
+        instructions::resize_account(&key, &key, &key),
+        instructions::sync_supply_stats(&key),
+        instructions::pause(&key),
+        instructions::unpause(&key),
+    ];
+    assert_eq!(names(&idl, "instructions").len(), built.len());
+    for ix in built {
+        let name = instructions::decode(&ix.data).unwrap().name();
+        assert_eq!(ix.program_id, PROGRAM_ID);
+        assert_eq!(
+            ix.data[..8],
+            discriminator(&idl, "instructions", name),
+            "{name}"
+        );
+    }
+}
+
+/// `account` serialized with the IDL's discriminator in place of the
+/// crate's, so decoding it fails if the two differ
+fn with_idl_discriminator(idl: &Value, name: &str, account: &impl AccountSerialize) -> Vec<u8> {
+    let mut data = Vec::new();
+    account.try_serialize(&mut data).unwrap();
+    data.splice(..8, discriminator(idl, "accounts", name));
+    data
+}
+
+#[test]
+fn test_account_decoders_accept_idl_discriminators() {
+    let idl = idl();
+    let key = Pubkey::new_unique();
+
+    let config = Config {
+        admin: key,
+        mint: key,
+        paused: false,
+        bump: 255,
+        mint_bump: 254,
+        delegate_bump: 254,
+        minter_set_freeze_bump: 254,
+    };
+    let data = with_idl_discriminator(&idl, "Config", &config);
+    assert_eq!(accounts::decode_config(key, &data).unwrap(), config);
+
+    let minter_config = MinterConfig {
+        minter: key,
+        allowance: 1,
+        amount_minted: 0,
+        is_initialized: true,
+        bump: 255,
+        pause_bump: 255,
+    };
+    let data = with_idl_discriminator(&idl, "MinterConfig", &minter_config);
+    assert_eq!(
+        accounts::decode_minter_config(key, &data).unwrap().config,
+        minter_config
+    );
+
+    let supply_stats = SupplyStats {
+        supply: 1,
+        updated_at: 2,
+        started_at: 1,
+        cumulative_supply: 3,
+        bump: 255,
+    };
+    let data = with_idl_discriminator(&idl, "SupplyStats", &supply_stats);
+    assert_eq!(
+        accounts::decode_supply_stats(key, &data).unwrap(),
+        supply_stats
+    );
+}
@@ -0,0 +1,491 @@
+use anchor_lang::{prelude::Pubkey, signature_verification::verify_ed25519_ix, Discriminator};
+use solana_keypair::Keypair;
+use solana_signer::Signer;
+use stablecoin_client::{
+    instructions::{self, StablecoinInstruction},
+    pda,
+};
+
+#[test]
+fn test_mint_tokens_accounts() {
+    let minter = Pubkey::new_unique();
+    let owner = Pubkey::new_unique();
+    let ix = instructions::mint_tokens(&minter, &owner, 42);
+
+    assert_eq!(ix.program_id, stablecoin_client::PROGRAM_ID);
+    let keys: Vec<Pubkey> = ix.accounts.iter().map(|meta| meta.pubkey).collect();
+    assert_eq!(
+        keys[..8],
+        [
+            minter,
+            pda::config().0,
+            pda::minter_config(&minter).0,
+            pda::minter_pause(&minter).0,
+            pda::mint().0,
+            pda::supply_stats().0,
+            pda::token_account(&owner),
+            owner,
+        ]
+    );
+    assert!(ix.accounts[0].is_signer && ix.accounts[0].is_writable);
+    assert!(ix.accounts[1..].iter().all(|meta| !meta.is_signer));
+
+    assert_eq!(
+        &ix.data[..8],
+        stablecoin::instruction::MintTokens::DISCRIMINATOR
+    );
+    assert_eq!(ix.data[8..], 42u64.to_le_bytes());
+}
+
+#[test]
+fn test_mint_tokens_idempotent_accounts() {
+    let minter = Pubkey::new_unique();
+    let owner = Pubkey::new_unique();
+    let key = [7; 16];
+    let ix = instructions::mint_tokens_idempotent(&minter, &owner, 42, key);
+
+    // A plain mint's accounts, in the same order, then the record
+    let plain = instructions::mint_tokens(&minter, &owner, 42);
+    assert_eq!(ix.accounts[..plain.accounts.len()], plain.accounts);
+    let record = &ix.accounts[plain.accounts.len()];
+    assert_eq!(record.pubkey, pda::idempotency_record(&minter, &key).0);
+    assert!(record.is_writable && !record.is_signer);
+
+    assert_eq!(
+        &ix.data[..8],
+        stablecoin::instruction::MintTokensIdempotent::DISCRIMINATOR
+    );
+    assert_eq!(ix.data[8..16], 42u64.to_le_bytes());
+    assert_eq!(ix.data[16..], key);
+
+    // Keys are per minter
+    assert_ne!(
+        pda::idempotency_record(&minter, &key).0,
+        pda::idempotency_record(&owner, &key).0
+    );
+}
+
+#[test]
+fn test_admin_instructions_are_signed_by_admin() {
+    let admin = Pubkey::new_unique();
+    let minter = Pubkey::new_unique();
+
+    for ix in [
+        instructions::initialize(&admin),
+        instructions::configure_minter(&admin, &minter, 1),
+        instructions::remove_minter(&admin, &minter),
+        instructions::freeze_minter_set(&admin),
+        instructions::unfreeze_minter_set(&admin, &admin),
+        instructions::resume_minter(&admin, &minter),
+        instructions::configure_treasury(&admin, [minter, minter], 1),
+        instructions::open_netting_window(&admin, 1, vec![minter], 1),
+        instructions::cancel_netting_window(&admin, &admin, 1, &[minter]),
+        instructions::pause(&admin),
+        instructions::unpause(&admin),
+    ] {
+        let signers: Vec<Pubkey> = ix
+            .accounts
+            .iter()
+            .filter(|meta| meta.is_signer)
+            .map(|meta| meta.pubkey)
+            .collect();
+        assert_eq!(signers, vec![admin]);
+    }
+}
+
+#[test]
+fn test_sweep_dust_accounts() {
+    let owner = Pubkey::new_unique();
+    let sources = [Pubkey::new_unique(), Pubkey::new_unique()];
+    let ix = instructions::sweep_dust(&owner, &sources, 42);
+
+    // The sources follow the named accounts, writable and unsigned
+    let named = ix.accounts.len() - sources.len();
+    assert_eq!(ix.accounts[3].pubkey, pda::token_account(&owner));
+    assert!(ix.accounts[named..]
+        .iter()
+        .zip(sources)
+        .all(|(meta, source)| meta.pubkey == source && meta.is_writable && !meta.is_signer));
+    assert!(ix.accounts[0].is_signer && ix.accounts[0].is_writable);
+
+    assert_eq!(
+        &ix.data[..8],
+        stablecoin::instruction::SweepDust::DISCRIMINATOR
+    );
+    assert_eq!(ix.data[8..], 42u64.to_le_bytes());
+}
+
+#[test]
+fn test_settle_net_accounts() {
+    let payer = Pubkey::new_unique();
+    let admin = Pubkey::new_unique();
+    let institutions = [Pubkey::new_unique(), Pubkey::new_unique()];
+    let ix = instructions::settle_net(&payer, &admin, 7, &institutions);
+
+    // The institutions' token accounts follow the named accounts, in order
+    let named = ix.accounts.len() - institutions.len();
+    assert_eq!(ix.accounts[3].pubkey, pda::netting_window(7).0);
+    for (meta, institution) in ix.accounts[named..].iter().zip(institutions) {
+        assert_eq!(meta.pubkey, pda::token_account(&institution));
+        assert!(meta.is_writable && !meta.is_signer);
+    }
+    let signers: Vec<Pubkey> = ix
+        .accounts
+        .iter()
+        .filter(|meta| meta.is_signer)
+        .map(|meta| meta.pubkey)
+        .collect();
+    assert_eq!(signers, vec![payer]);
+}
+
+#[test]
+fn test_queue_obligation_purpose_code() {
+    let debtor = Pubkey::new_unique();
+    let creditor = Pubkey::new_unique();
+
+    // Anchor reads the program id in an optional account's place as absent
+    let ix = instructions::queue_obligation(&debtor, 1, &creditor, 10, None);
+    assert_eq!(ix.accounts[6].pubkey, stablecoin_client::PROGRAM_ID);
+    let ix = instructions::queue_obligation(&debtor, 1, &creditor, 10, Some(b"PAYR"));
+    assert_eq!(ix.accounts[6].pubkey, pda::purpose_code(b"PAYR").0);
+    assert!(!ix.accounts[6].is_writable && !ix.accounts[6].is_signer);
+}
+
+#[test]
+fn test_verify_channel_state_matches_program_check() {
+    let sender = Keypair::new();
+    let recipient = Pubkey::new_unique();
+    let channel = pda::payment_channel(&sender.pubkey(), &recipient).0;
+    let message = stablecoin::channel_state_message(&channel, 3, 250);
+    let signature: [u8; 64] = sender.sign_message(&message).into();
+    let ix = instructions::verify_channel_state(&sender.pubkey(), &recipient, 3, 250, &signature);
+
+    // The data is what close_channel expects right before it
+    assert_eq!(ix.program_id, solana_sdk_ids::ed25519_program::ID);
+    assert!(ix.accounts.is_empty());
+    verify_ed25519_ix(&ix, &sender.pubkey().to_bytes(), &message, &signature).unwrap();
+
+    // A different amount is a different message
+    let message = stablecoin::channel_state_message(&channel, 3, 251);
+    assert!(verify_ed25519_ix(&ix, &sender.pubkey().to_bytes(), &message, &signature).is_err());
+
+    // As is the same amount on a later channel between the same wallets
+    let message = stablecoin::channel_state_message(&channel, 4, 250);
+    assert!(verify_ed25519_ix(&ix, &sender.pubkey().to_bytes(), &message, &signature).is_err());
+
+    let ix = instructions::close_channel(&recipient, &sender.pubkey(), &recipient, 250, signature);
+    assert_eq!(ix.accounts[1].pubkey, channel);
+    assert_eq!(
+        ix.accounts[2].pubkey,
+        solana_sdk_ids::sysvar::instructions::ID
+    );
+}
+
+#[test]
+fn test_execute_treasury_burn_accounts() {
+    let officer = Pubkey::new_unique();
+    let proposer = Pubkey::new_unique();
+    let ix = instructions::execute_treasury_burn(&officer, &proposer, 3);
+
+    let keys: Vec<Pubkey> = ix.accounts.iter().map(|meta| meta.pubkey).collect();
+    assert_eq!(
+        keys[..8],
+        [
+            officer,
+            pda::config().0,
+            pda::treasury().0,
+            pda::pending_burn(3).0,
+            proposer,
+            pda::mint().0,
+            pda::supply_stats().0,
+            pda::treasury_custody(),
+        ]
+    );
+    // Only the officer signs; the treasury signs the burn as a PDA
+    assert!(ix.accounts[0].is_signer && !ix.accounts[0].is_writable);
+    assert!(ix.accounts[1..].iter().all(|meta| !meta.is_signer));
+    assert_eq!(
+        pda::treasury_custody(),
+        pda::token_account(&pda::treasury().0)
+    );
+
+    // Each proposal has its own address
+    assert_ne!(pda::pending_burn(3).0, pda::pending_burn(4).0);
+}
+
+#[test]
+fn test_execute_recovery_accounts() {
+    let guardian = Pubkey::new_unique();
+    let owner = Pubkey::new_unique();
+    let new_owner = Pubkey::new_unique();
+    let ix = instructions::execute_recovery(&guardian, &owner, &new_owner);
+
+    let keys: Vec<Pubkey> = ix.accounts.iter().map(|meta| meta.pubkey).collect();
+    assert_eq!(
+        keys[..9],
+        [
+            guardian,
+            pda::config().0,
+            pda::mint().0,
+            pda::recovery_config(&owner).0,
+            owner,
+            pda::token_account(&owner),
+            new_owner,
+            pda::token_account(&new_owner),
+            pda::delegate().0,
+        ]
+    );
+    // Only the guardian signs, and pays for the new owner's token account;
+    // the delegate signs the transfer as a PDA
+    assert!(ix.accounts[0].is_signer && ix.accounts[0].is_writable);
+    assert!(ix.accounts[1..].iter().all(|meta| !meta.is_signer));
+
+    // Each holder has their own settings
+    assert_ne!(
+        pda::recovery_config(&owner).0,
+        pda::recovery_config(&new_owner).0
+    );
+}
+
+#[test]
+fn test_token_account_is_ata_of_mint() {
+    let owner = Pubkey::new_unique();
+    assert_eq!(
+        pda::token_account(&owner),
+        anchor_spl::associated_token::get_associated_token_address(&owner, &pda::mint().0)
+    );
+}
+
+#[test]
+fn test_decode_round_trips() {
+    let admin = Pubkey::new_unique();
+    let minter = Pubkey::new_unique();
+    let cases = [
+        (
+            instructions::initialize(&admin),
+            StablecoinInstruction::Initialize,
+        ),
+        (
+            instructions::configure_minter(&admin, &minter, 7),
+            StablecoinInstruction::ConfigureMinter { allowance: 7 },
+        ),
+        (
+            instructions::remove_minter(&admin, &minter),
+            StablecoinInstruction::RemoveMinter,
+        ),
+        (
+            instructions::freeze_minter_set(&admin),
+            StablecoinInstruction::FreezeMinterSet,
+        ),
+        (
+            instructions::unfreeze_minter_set(&admin, &minter),
+            StablecoinInstruction::UnfreezeMinterSet,
+        ),
+        (
+            instructions::mint_tokens(&minter, &admin, 8),
+            StablecoinInstruction::MintTokens { amount: 8 },
+        ),
+        (
+            instructions::mint_tokens_idempotent(&minter, &admin, 8, [5; 16]),
+            StablecoinInstruction::MintTokensIdempotent {
+                amount: 8,
+                key: [5; 16],
+            },
+        ),
+        (
+            instructions::reclaim_idempotency_record(&minter, &[5; 16]),
+            StablecoinInstruction::ReclaimIdempotencyRecord,
+        ),
+        (
+            instructions::pause_self(&minter),
+            StablecoinInstruction::PauseSelf,
+        ),
+        (
+            instructions::resume_minter(&admin, &minter),
+            StablecoinInstruction::ResumeMinter,
+        ),
+        (
+            instructions::burn_tokens(&admin, 9),
+            StablecoinInstruction::BurnTokens { amount: 9 },
+        ),
+        (
+            instructions::sweep_dust(&admin, &[minter], 9),
+            StablecoinInstruction::SweepDust { threshold: 9 },
+        ),
+        (
+            instructions::configure_treasury(&admin, [admin, minter], 10),
+            StablecoinInstruction::ConfigureTreasury {
+                officers: [admin, minter],
+                threshold: 10,
+            },
+        ),
+        (
+            instructions::propose_treasury_burn(&admin, 11, 12),
+            StablecoinInstruction::ProposeTreasuryBurn { id: 11, amount: 12 },
+        ),
+        (
+            instructions::approve_treasury_burn(&minter, 11),
+            StablecoinInstruction::ApproveTreasuryBurn,
+        ),
+        (
+            instructions::execute_treasury_burn(&minter, &admin, 11),
+            StablecoinInstruction::ExecuteTreasuryBurn,
+        ),
+        (
+            instructions::cancel_treasury_burn(&minter, &admin, 11),
+            StablecoinInstruction::CancelTreasuryBurn,
+        ),
+        (
+            instructions::configure_recovery(&admin, vec![minter], 1, 13),
+            StablecoinInstruction::ConfigureRecovery {
+                guardians: vec![minter],
+                quorum: 1,
+                challenge_period: 13,
+            },
+        ),
+        (
+            instructions::initiate_recovery(&minter, &admin, &minter),
+            StablecoinInstruction::InitiateRecovery { new_owner: minter },
+        ),
+        (
+            instructions::approve_recovery(&minter, &admin),
+            StablecoinInstruction::ApproveRecovery,
+        ),
+        (
+            instructions::execute_recovery(&minter, &admin, &minter),
+            StablecoinInstruction::ExecuteRecovery,
+        ),
+        (
+            instructions::cancel_recovery(&admin),
+            StablecoinInstruction::CancelRecovery,
+        ),
+        (
+            instructions::close_recovery(&admin),
+            StablecoinInstruction::CloseRecovery,
+        ),
+        (
+            instructions::configure_inheritance(&admin, &minter, 2, 17),
+            StablecoinInstruction::ConfigureInheritance {
+                beneficiary: minter,
+                inactivity_epochs: 2,
+                notice_period: 17,
+            },
+        ),
+        (
+            instructions::check_in(&admin),
+            StablecoinInstruction::CheckIn,
+        ),
+        (
+            instructions::start_inheritance_claim(&minter, &admin),
+            StablecoinInstruction::StartInheritanceClaim,
+        ),
+        (
+            instructions::claim_inheritance(&minter, &admin),
+            StablecoinInstruction::ClaimInheritance,
+        ),
+        (
+            instructions::close_inheritance(&admin),
+            StablecoinInstruction::CloseInheritance,
+        ),
+        (
+            instructions::open_netting_window(&admin, 3, vec![admin, minter], 60),
+            StablecoinInstruction::OpenNettingWindow {
+                id: 3,
+                institutions: vec![admin, minter],
+                duration: 60,
+            },
+        ),
+        (
+            instructions::queue_obligation(&admin, 3, &minter, 9, Some(b"RMIT")),
+            StablecoinInstruction::QueueObligation {
+                creditor: minter,
+                amount: 9,
+            },
+        ),
+        (
+            instructions::settle_net(&admin, &admin, 3, &[admin, minter]),
+            StablecoinInstruction::SettleNet,
+        ),
+        (
+            instructions::cancel_netting_window(&admin, &admin, 3, &[admin, minter]),
+            StablecoinInstruction::CancelNettingWindow,
+        ),
+        (
+            instructions::register_purpose_code(&admin, *b"RMIT"),
+            StablecoinInstruction::RegisterPurposeCode { code: *b"RMIT" },
+        ),
+        (
+            instructions::retire_purpose_code(&admin, &admin, b"RMIT"),
+            StablecoinInstruction::RetirePurposeCode,
+        ),
+        (
+            instructions::open_channel(&admin, &minter, 500, 3_600),
+            StablecoinInstruction::OpenChannel {
+                recipient: minter,
+                deposit: 500,
+                dispute_period: 3_600,
+            },
+        ),
+        (
+            instructions::close_channel(&minter, &admin, &minter, 200, [9; 64]),
+            StablecoinInstruction::CloseChannel {
+                amount: 200,
+                signature: [9; 64],
+            },
+        ),
+        (
+            instructions::settle_channel(&admin, &admin, &minter),
+            StablecoinInstruction::SettleChannel,
+        ),
+        (
+            instructions::register_sub_account(&admin, 19, b"RMIT"),
+            StablecoinInstruction::RegisterSubAccount { index: 19 },
+        ),
+        (
+            instructions::sweep_sub_account(&admin, 19),
+            StablecoinInstruction::SweepSubAccount,
+        ),
+        (
+            instructions::open_airdrop(&admin, 20, vec![minter, admin], vec![5, 6], 7),
+            StablecoinInstruction::OpenAirdrop {
+                id: 20,
+                recipients: vec![minter, admin],
+                amounts: vec![5, 6],
+                duration: 7,
+            },
+        ),
+        (
+            instructions::claim_airdrop(&minter, &admin, 20),
+            StablecoinInstruction::ClaimAirdrop,
+        ),
+        (
+            instructions::clawback_airdrop(&minter, &admin, 20),
+            StablecoinInstruction::ClawbackAirdrop,
+        ),
+        (
+            instructions::resize_account(&admin, &minter, &admin),
+            StablecoinInstruction::ResizeAccount,
+        ),
+        (
+            instructions::sync_supply_stats(&admin),
+            StablecoinInstruction::SyncSupplyStats,
+        ),
+        (instructions::pause(&admin), StablecoinInstruction::Pause),
+        (
+            instructions::unpause(&admin),
+            StablecoinInstruction::Unpause,
+        ),
+    ];
+    for (ix, expected) in cases {
+        assert_eq!(instructions::decode(&ix.data), Some(expected));
+    }
+}
+
+#[test]
+fn test_decode_rejects_malformed_data() {
+    let mut data = instructions::mint_tokens(&Pubkey::new_unique(), &Pubkey::new_unique(), 1).data;
+    assert_eq!(instructions::decode(&data[..12]), None);
+    data.push(0);
+    assert_eq!(instructions::decode(&data), None);
+    assert_eq!(instructions::decode(&[0; 8]), None);
+}
@@ -0,0 +1,143 @@
+use anchor_lang::prelude::Pubkey;
+use solana_hash::Hash;
+use solana_instruction::{AccountMeta, Instruction};
+use solana_keypair::Keypair;
+use solana_signer::Signer;
+use solana_transaction::Transaction;
+use stablecoin_client::{
+    offline::{
+        add_signature, decode_nonce_account, decode_transaction, decode_transaction_base58,
+        encode_transaction, encode_transaction_base58, finalize, missing_signers, read_transaction,
+        sign_partial, write_transaction, OfflineError, PartialSignature,
+    },
+    TxBuilder,
+};
+
+/// Fee payer online, admin offline
+fn unsigned_tx(fee_payer: &Keypair, admin: &Keypair) -> Transaction {
+    TxBuilder::new(fee_payer.pubkey())
+        .instruction(Instruction::new_with_bytes(
+            stablecoin_client::PROGRAM_ID,
+            &[7],
+            vec![AccountMeta::new_readonly(admin.pubkey(), true)],
+        ))
+        .build_unsigned(Hash::new_unique())
+}
+
+#[test]
+fn test_offline_round_trip() {
+    let fee_payer = Keypair::new();
+    let admin = Keypair::new();
+    let tx = unsigned_tx(&fee_payer, &admin);
+
+    // Online -> offline
+    let encoded = encode_transaction(&tx).unwrap();
+    let offline_tx = decode_transaction(&encoded).unwrap();
+    assert_eq!(offline_tx, tx);
+
+    let partials = sign_partial(&offline_tx, &[&admin]).unwrap();
+    assert_eq!(partials.len(), 1);
+
+    // Offline -> online, as text
+    let line = partials[0].to_string();
+    let partial: PartialSignature = line.parse().unwrap();
+    assert_eq!(partial, partials[0]);
+
+    let mut tx = decode_transaction(&encoded).unwrap();
+    add_signature(&mut tx, &partial).unwrap();
+    assert_eq!(missing_signers(&tx), vec![fee_payer.pubkey()]);
+    assert!(matches!(
+        finalize(tx.clone()),
+        Err(OfflineError::MissingSignatures(missing)) if missing == vec![fee_payer.pubkey()]
+    ));
+
+    let blockhash = tx.message.recent_blockhash;
+    tx.partial_sign(&[&fee_payer], blockhash);
+    let tx = finalize(tx).unwrap();
+    assert!(tx.is_signed());
+}
+
+#[test]
+fn test_file_round_trip() {
+    let tx = unsigned_tx(&Keypair::new(), &Keypair::new());
+    let path = std::env::temp_dir().join(format!("stablecoin-offline-{}.tx", Pubkey::new_unique()));
+
+    write_transaction(&path, &tx).unwrap();
+    let read = read_transaction(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+
+    assert_eq!(read, tx);
+}
+
+#[test]
+fn test_base58_round_trip() {
+    let tx = unsigned_tx(&Keypair::new(), &Keypair::new());
+    let encoded = encode_transaction_base58(&tx).unwrap();
+    assert_eq!(decode_transaction_base58(&encoded).unwrap(), tx);
+    assert!(matches!(
+        decode_transaction_base58("0OIl"),
+        Err(OfflineError::Base58(_))
+    ));
+}
+
+#[test]
+fn test_sign_partial_rejects_unrelated_signer() {
+    let tx = unsigned_tx(&Keypair::new(), &Keypair::new());
+    let stranger = Keypair::new();
+
+    assert!(matches!(
+        sign_partial(&tx, &[&stranger]),
+        Err(OfflineError::NotASigner(pubkey)) if pubkey == stranger.pubkey()
+    ));
+}
+
+#[test]
+fn test_add_signature_rejects_wrong_message() {
+    let fee_payer = Keypair::new();
+    let admin = Keypair::new();
+    let tx = unsigned_tx(&fee_payer, &admin);
+    // Signed over a different blockhash
+    let other = unsigned_tx(&fee_payer, &admin);
+    let partial = sign_partial(&other, &[&admin]).unwrap()[0];
+
+    let mut tx = tx;
+    assert!(matches!(
+        add_signature(&mut tx, &partial),
+        Err(OfflineError::SignatureMismatch(pubkey)) if pubkey == admin.pubkey()
+    ));
+    assert_eq!(missing_signers(&tx).len(), 2);
+}
+
+#[test]
+fn test_parse_partial_signature_errors() {
+    assert!("".parse::<PartialSignature>().is_err());
+    assert!("not-a-key=1111".parse::<PartialSignature>().is_err());
+    assert!(Pubkey::new_unique()
+        .to_string()
+        .parse::<PartialSignature>()
+        .is_err());
+}
+
+#[test]
+fn test_durable_nonce_instruction_first() {
+    let payer = Pubkey::new_unique();
+    let nonce_account = Pubkey::new_unique();
+    let instructions = TxBuilder::new(payer)
+        .compute_unit_price(1)
+        .durable_nonce(nonce_account, payer)
+        .to_instructions();
+
+    assert_eq!(
+        instructions[0],
+        solana_system_interface::instruction::advance_nonce_account(&nonce_account, &payer)
+    );
+    assert_eq!(instructions.len(), 2);
+}
+
+#[test]
+fn test_decode_uninitialized_nonce_account() {
+    assert!(matches!(
+        decode_nonce_account(&[0; 80]),
+        Err(OfflineError::InvalidNonceAccount)
+    ));
+}
@@ -0,0 +1,74 @@
+#![cfg(feature = "rpc")]
+
+use std::{collections::HashMap, time::Duration};
+
+use solana_instruction::{AccountMeta, Instruction};
+use solana_keypair::Keypair;
+use solana_rpc_client::nonblocking::rpc_client::RpcClient;
+use solana_rpc_client_api::request::RpcRequest;
+use solana_signer::Signer;
+use stablecoin_client::{
+    send::{send_and_confirm_with_retry, RetryConfig},
+    ClientError, TxBuilder,
+};
+
+fn config() -> RetryConfig {
+    RetryConfig {
+        max_attempts: 2,
+        initial_backoff: Duration::from_millis(1),
+        max_backoff: Duration::from_millis(2),
+        poll_interval: Duration::from_millis(1),
+        ..RetryConfig::default()
+    }
+}
+
+fn builder(payer: &Keypair) -> TxBuilder {
+    TxBuilder::new(payer.pubkey()).instruction(Instruction::new_with_bytes(
+        stablecoin_client::PROGRAM_ID,
+        &[1],
+        vec![AccountMeta::new(payer.pubkey(), true)],
+    ))
+}
+
+#[tokio::test]
+async fn test_confirms() {
+    let payer = Keypair::new();
+    let rpc = RpcClient::new_mock("succeeds".to_string());
+
+    let signature = send_and_confirm_with_retry(&rpc, &builder(&payer), &[&payer], &config())
+        .await
+        .unwrap();
+    assert_ne!(signature, Default::default());
+}
+
+#[tokio::test]
+async fn test_failed_transaction_is_not_retried() {
+    let payer = Keypair::new();
+    let rpc = RpcClient::new_mock("instruction_error".to_string());
+
+    let result = send_and_confirm_with_retry(&rpc, &builder(&payer), &[&payer], &config()).await;
+    assert!(matches!(result, Err(ClientError::TransactionFailed { .. })));
+}
+
+#[tokio::test]
+async fn test_expires_after_max_attempts() {
+    let payer = Keypair::new();
+    // Blockhashes are valid up to height 1234 in the mock
+    let mocks = HashMap::from([(RpcRequest::GetBlockHeight, 1235.into())]);
+    let rpc = RpcClient::new_mock_with_mocks("sig_not_found".to_string(), mocks);
+
+    let result = send_and_confirm_with_retry(
+        &rpc,
+        &builder(&payer),
+        &[&payer],
+        &RetryConfig {
+            max_attempts: 1,
+            ..config()
+        },
+    )
+    .await;
+    match result {
+        Err(ClientError::Expired { signatures }) => assert_eq!(signatures.len(), 1),
+        other => panic!("expected expiry, got {other:?}"),
+    }
+}
@@ -0,0 +1,152 @@
+use stablecoin::SupplyStats;
+use stablecoin_client::supply::{average_supply, SupplyReport};
+
+#[test]
+fn test_circulating_supply() {
+    let report = SupplyReport::new(10, 6, 1_000, 100, 50, 25);
+    assert_eq!(report.circulating, 825);
+}
+
+#[test]
+fn test_circulating_supply_saturates() {
+    let report = SupplyReport::new(10, 6, 100, u64::MAX, 1, 0);
+    assert_eq!(report.circulating, 0);
+}
+
+fn stats(supply: u64, updated_at: i64, cumulative_supply: u128) -> SupplyStats {
+    SupplyStats {
+        supply,
+        updated_at,
+        started_at: 0,
+        cumulative_supply,
+        bump: 255,
+    }
+}
+
+#[test]
+fn test_average_supply() {
+    // 100 for 10 seconds, then 400 from 10 on
+    let earlier = stats(100, 0, 0);
+    let later = stats(400, 10, 1_000);
+    assert_eq!(average_supply(&earlier, 0, &later, 20), Some(250));
+    assert_eq!(average_supply(&earlier, 5, &later, 10), Some(100));
+
+    // A read with no change since covers any time after it
+    assert_eq!(average_supply(&later, 10, &later, 30), Some(400));
+}
+
+#[test]
+fn test_average_supply_needs_a_window() {
+    let stats = stats(100, 0, 0);
+    assert_eq!(average_supply(&stats, 10, &stats, 10), None);
+    assert_eq!(average_supply(&stats, 10, &stats, 5), None);
+}
+
+#[cfg(feature = "rpc")]
+mod rpc {
+    use std::collections::HashMap;
+
+    use anchor_lang::{prelude::Pubkey, solana_program::program_pack::Pack};
+    use anchor_spl::token::spl_token::state::{Account, AccountState, Mint};
+    use base64::{engine::general_purpose::STANDARD, Engine as _};
+    use serde_json::{json, Value};
+    use solana_rpc_client::nonblocking::rpc_client::RpcClient;
+    use solana_rpc_client_api::request::RpcRequest;
+    use stablecoin_client::{
+        supply::{fetch_supply, NonCirculating},
+        ClientError,
+    };
+
+    fn mint_address() -> Pubkey {
+        Pubkey::find_program_address(
+            &[stablecoin::seeds::MINT_SEED],
+            &stablecoin_client::PROGRAM_ID,
+        )
+        .0
+    }
+
+    fn ui_account(data: Vec<u8>) -> Value {
+        json!({
+            "lamports": 1_000_000,
+            "data": [STANDARD.encode(&data), "base64"],
+            "owner": anchor_spl::token::ID.to_string(),
+            "executable": false,
+            "rentEpoch": 0,
+            "space": data.len(),
+        })
+    }
+
+    fn mint(supply: u64) -> Value {
+        let mut data = vec![0; Mint::LEN];
+        Mint {
+            supply,
+            decimals: 6,
+            is_initialized: true,
+            ..Mint::default()
+        }
+        .pack_into_slice(&mut data);
+        ui_account(data)
+    }
+
+    fn token_account(mint: Pubkey, amount: u64) -> Value {
+        let mut data = vec![0; Account::LEN];
+        Account {
+            mint,
+            owner: Pubkey::new_unique(),
+            amount,
+            state: AccountState::Initialized,
+            ..Account::default()
+        }
+        .pack_into_slice(&mut data);
+        ui_account(data)
+    }
+
+    fn rpc(accounts: Vec<Value>) -> RpcClient {
+        let response = json!({ "context": { "slot": 42 }, "value": accounts });
+        let mocks = HashMap::from([(RpcRequest::GetMultipleAccounts, response)]);
+        RpcClient::new_mock_with_mocks("succeeds".to_string(), mocks)
+    }
+
+    #[tokio::test]
+    async fn test_fetch_supply() {
+        let treasury = Pubkey::new_unique();
+        let escrow = Pubkey::new_unique();
+        let closed = Pubkey::new_unique();
+        // The treasury is listed twice and only counted once
+        let non_circulating = NonCirculating {
+            treasury: vec![treasury, closed],
+            escrow: vec![escrow, treasury],
+            bridge: vec![],
+        };
+        let rpc = rpc(vec![
+            mint(1_000_000),
+            token_account(mint_address(), 300_000),
+            Value::Null,
+            token_account(mint_address(), 200_000),
+        ]);
+
+        let report = fetch_supply(&rpc, &non_circulating).await.unwrap();
+        assert_eq!(report.slot, 42);
+        assert_eq!(report.decimals, 6);
+        assert_eq!(report.total, 1_000_000);
+        assert_eq!(report.treasury, 300_000);
+        assert_eq!(report.escrowed, 200_000);
+        assert_eq!(report.bridged, 0);
+        assert_eq!(report.circulating, 500_000);
+    }
+
+    #[tokio::test]
+    async fn test_fetch_supply_rejects_foreign_mint() {
+        let bridge = Pubkey::new_unique();
+        let non_circulating = NonCirculating {
+            bridge: vec![bridge],
+            ..NonCirculating::default()
+        };
+        let rpc = rpc(vec![mint(1_000), token_account(Pubkey::new_unique(), 10)]);
+
+        assert!(matches!(
+            fetch_supply(&rpc, &non_circulating).await,
+            Err(ClientError::WrongMint(address)) if address == bridge
+        ));
+    }
+}
@@ -0,0 +1,157 @@
+use anchor_lang::prelude::Pubkey;
+use solana_address_lookup_table_interface::state::{AddressLookupTable, LookupTableMeta};
+use solana_compute_budget_interface::ComputeBudgetInstruction;
+use solana_hash::Hash;
+use solana_instruction::{AccountMeta, Instruction};
+use solana_keypair::Keypair;
+use solana_message::{AddressLookupTableAccount, VersionedMessage};
+use solana_signer::Signer;
+use stablecoin_client::{
+    tx::{apply_margin, decode_lookup_table, TxBuilder, MAX_COMPUTE_UNIT_LIMIT},
+    ClientError,
+};
+
+fn program_ix(payer: Pubkey) -> Instruction {
+    Instruction::new_with_bytes(
+        stablecoin_client::PROGRAM_ID,
+        &[1, 2, 3],
+        vec![AccountMeta::new(payer, true)],
+    )
+}
+
+#[test]
+fn test_no_compute_budget_by_default() {
+    let payer = Pubkey::new_unique();
+    let instructions = TxBuilder::new(payer)
+        .instruction(program_ix(payer))
+        .to_instructions();
+
+    assert_eq!(instructions, vec![program_ix(payer)]);
+}
+
+#[test]
+fn test_compute_budget_instructions_come_first() {
+    let payer = Pubkey::new_unique();
+    let instructions = TxBuilder::new(payer)
+        .instructions([program_ix(payer), program_ix(payer)])
+        .compute_unit_limit(50_000)
+        .compute_unit_price(10_000)
+        .to_instructions();
+
+    assert_eq!(
+        instructions,
+        vec![
+            ComputeBudgetInstruction::set_compute_unit_limit(50_000),
+            ComputeBudgetInstruction::set_compute_unit_price(10_000),
+            program_ix(payer),
+            program_ix(payer),
+        ]
+    );
+}
+
+#[test]
+fn test_simulated_limit_placeholder() {
+    let payer = Pubkey::new_unique();
+    let instructions = TxBuilder::new(payer)
+        .instruction(program_ix(payer))
+        .simulate_compute_units()
+        .to_instructions();
+
+    assert_eq!(
+        instructions[0],
+        ComputeBudgetInstruction::set_compute_unit_limit(MAX_COMPUTE_UNIT_LIMIT)
+    );
+}
+
+#[test]
+fn test_build_unsigned() {
+    let payer = Pubkey::new_unique();
+    let blockhash = Hash::new_unique();
+    let tx = TxBuilder::new(payer)
+        .instruction(program_ix(payer))
+        .compute_unit_price(1)
+        .build_unsigned(blockhash);
+
+    assert_eq!(tx.message.account_keys[0], payer);
+    assert_eq!(tx.message.recent_blockhash, blockhash);
+    assert_eq!(tx.message.instructions.len(), 2);
+    assert!(!tx.is_signed());
+}
+
+#[test]
+fn test_apply_margin() {
+    assert_eq!(apply_margin(10_000, 1_000), 11_000);
+    assert_eq!(apply_margin(10_000, 0), 10_000);
+    assert_eq!(apply_margin(1_300_000, 5_000), MAX_COMPUTE_UNIT_LIMIT);
+    assert_eq!(apply_margin(u64::MAX, 1_000), MAX_COMPUTE_UNIT_LIMIT);
+}
+
+#[test]
+fn test_versioned_falls_back_to_legacy() {
+    let payer = Keypair::new();
+    let tx = TxBuilder::new(payer.pubkey())
+        .instruction(program_ix(payer.pubkey()))
+        .build_versioned_with_blockhash(&[&payer], Hash::new_unique())
+        .unwrap();
+
+    assert!(matches!(tx.message, VersionedMessage::Legacy(_)));
+    assert!(tx.signatures[0].verify(payer.pubkey().as_ref(), &tx.message.serialize()));
+}
+
+#[test]
+fn test_versioned_uses_lookup_table() {
+    let payer = Keypair::new();
+    let mint = Pubkey::new_unique();
+    let config = Pubkey::new_unique();
+    let table = AddressLookupTableAccount {
+        key: Pubkey::new_unique(),
+        addresses: vec![config, mint],
+    };
+    let ix = Instruction::new_with_bytes(
+        stablecoin_client::PROGRAM_ID,
+        &[1],
+        vec![
+            AccountMeta::new(payer.pubkey(), true),
+            AccountMeta::new_readonly(config, false),
+            AccountMeta::new(mint, false),
+        ],
+    );
+
+    let tx = TxBuilder::new(payer.pubkey())
+        .instruction(ix)
+        .address_lookup_table(table.clone())
+        .build_versioned_with_blockhash(&[&payer], Hash::new_unique())
+        .unwrap();
+
+    let VersionedMessage::V0(message) = &tx.message else {
+        panic!("expected a v0 message");
+    };
+    assert!(!message.account_keys.contains(&mint));
+    assert!(!message.account_keys.contains(&config));
+    assert_eq!(message.address_table_lookups.len(), 1);
+    assert_eq!(message.address_table_lookups[0].account_key, table.key);
+    assert_eq!(message.address_table_lookups[0].writable_indexes, vec![1]);
+    assert_eq!(message.address_table_lookups[0].readonly_indexes, vec![0]);
+    assert!(tx.signatures[0].verify(payer.pubkey().as_ref(), &tx.message.serialize()));
+}
+
+#[test]
+fn test_decode_lookup_table() {
+    let address = Pubkey::new_unique();
+    let addresses = vec![Pubkey::new_unique(), Pubkey::new_unique()];
+    let data = AddressLookupTable {
+        meta: LookupTableMeta::default(),
+        addresses: addresses.clone().into(),
+    }
+    .serialize_for_tests()
+    .unwrap();
+
+    let table = decode_lookup_table(address, &data).unwrap();
+    assert_eq!(table.key, address);
+    assert_eq!(table.addresses, addresses);
+
+    assert!(matches!(
+        decode_lookup_table(address, &[0; 4]),
+        Err(ClientError::InvalidLookupTable(key)) if key == address
+    ));
+}
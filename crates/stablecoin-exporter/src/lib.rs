@@ -0,0 +1,229 @@
+//! Prometheus metrics for the stablecoin program
+//!
+//! [`poll`] reads the program's state into a [`Snapshot`], and [`Metrics`]
+//! renders the latest one in the Prometheus text format. A failed poll keeps
+//! the previous snapshot and sets `stablecoin_up` to 0, so alerts on stale
+//! values can be gated on it.
+//!
+//! The program has no blacklist, so there is no blacklist count. Treasury
+//! balance is the sum of the token accounts passed as treasury, the same
+//! input the client's supply report takes.
+
+use std::fmt::Write as _;
+
+use anchor_lang::prelude::Pubkey;
+use solana_rpc_client::{
+    nonblocking::rpc_client::RpcClient, rpc_client::GetConfirmedSignaturesForAddress2Config,
+};
+use stablecoin_client::{
+    accounts::{self, MinterAccount},
+    supply::{fetch_supply, NonCirculating, SupplyReport},
+    ClientError, PROGRAM_ID,
+};
+
+/// Recent signatures looked through for the last successful transaction
+const RECENT_SIGNATURES: usize = 25;
+
+/// Program state read by one poll
+#[derive(Debug, Clone)]
+pub struct Snapshot {
+    pub supply: SupplyReport,
+    pub paused: bool,
+    pub minters: Vec<MinterAccount>,
+    /// Slot of the newest successful transaction calling the program, if
+    /// one is among the most recent; not every instruction emits an event,
+    /// so this is when the program last did something
+    pub last_event_slot: Option<u64>,
+}
+
+/// Read the program's state
+pub async fn poll(rpc: &RpcClient, treasury: &[Pubkey]) -> Result<Snapshot, ClientError> {
+    let non_circulating = NonCirculating {
+        treasury: treasury.to_vec(),
+        ..NonCirculating::default()
+    };
+    let supply = fetch_supply(rpc, &non_circulating).await?;
+    let config = accounts::fetch_config(rpc).await?;
+    let minters = accounts::list_minters(rpc).await?;
+
+    // Not every instruction takes the config, but every transaction calling
+    // the program lists its id
+    let recent = rpc
+        .get_signatures_for_address_with_config(
+            &PROGRAM_ID,
+            GetConfirmedSignaturesForAddress2Config {
+                limit: Some(RECENT_SIGNATURES),
+                commitment: Some(rpc.commitment()),
+                ..GetConfirmedSignaturesForAddress2Config::default()
+            },
+        )
+        .await?;
+    let last_event_slot = recent
+        .iter()
+        .find(|status| status.err.is_none())
+        .map(|status| status.slot);
+
+    Ok(Snapshot {
+        supply,
+        paused: config.paused,
+        minters,
+        last_event_slot,
+    })
+}
+
+/// The latest snapshot and the health of polling
+#[derive(Debug, Clone, Default)]
+pub struct Metrics {
+    pub snapshot: Option<Snapshot>,
+    /// Whether the last poll succeeded
+    pub up: bool,
+    pub poll_errors: u64,
+    /// Unix time of the last successful poll
+    pub last_success: Option<u64>,
+}
+
+impl Metrics {
+    /// Keep the result of a poll made at `now`, in Unix seconds
+    pub fn record(&mut self, result: Result<Snapshot, ClientError>, now: u64) {
+        match result {
+            Ok(snapshot) => {
+                self.snapshot = Some(snapshot);
+                self.up = true;
+                self.last_success = Some(now);
+            }
+            Err(_) => {
+                self.up = false;
+                self.poll_errors += 1;
+            }
+        }
+    }
+
+    /// The Prometheus text exposition format
+    pub fn render(&self) -> String {
+        let mut out = String::new();
+        gauge(
+            &mut out,
+            "stablecoin_up",
+            "Whether the last poll of the program succeeded",
+            &[("", u64::from(self.up))],
+        );
+        metric(
+            &mut out,
+            "counter",
+            "stablecoin_poll_errors_total",
+            "Polls that failed",
+            &[("", self.poll_errors)],
+        );
+        if let Some(last_success) = self.last_success {
+            gauge(
+                &mut out,
+                "stablecoin_last_success_timestamp_seconds",
+                "Unix time of the last successful poll",
+                &[("", last_success)],
+            );
+        }
+
+        let Some(snapshot) = &self.snapshot else {
+            return out;
+        };
+        let supply = &snapshot.supply;
+        gauge(
+            &mut out,
+            "stablecoin_slot",
+            "Slot the figures were read at",
+            &[("", supply.slot)],
+        );
+        gauge(
+            &mut out,
+            "stablecoin_total_supply",
+            "Total supply, in base units",
+            &[("", supply.total)],
+        );
+        gauge(
+            &mut out,
+            "stablecoin_circulating_supply",
+            "Supply outside the treasury accounts, in base units",
+            &[("", supply.circulating)],
+        );
+        gauge(
+            &mut out,
+            "stablecoin_treasury_balance",
+            "Balance of the treasury accounts, in base units",
+            &[("", supply.treasury)],
+        );
+        gauge(
+            &mut out,
+            "stablecoin_paused",
+            "Whether minting is paused",
+            &[("", u64::from(snapshot.paused))],
+        );
+        gauge(
+            &mut out,
+            "stablecoin_minters",
+            "Number of minters",
+            &[("", snapshot.minters.len() as u64)],
+        );
+        if let Some(slot) = snapshot.last_event_slot {
+            gauge(
+                &mut out,
+                "stablecoin_last_event_slot",
+                "Slot of the newest successful transaction calling the program",
+                &[("", slot)],
+            );
+        }
+
+        let per_minter = |value: fn(&MinterAccount) -> u64| -> Vec<(String, u64)> {
+            snapshot
+                .minters
+                .iter()
+                .map(|minter| {
+                    (
+                        format!("minter=\"{}\"", minter.config.minter),
+                        value(minter),
+                    )
+                })
+                .collect()
+        };
+        for (name, help, values) in [
+            (
+                "stablecoin_minter_allowance",
+                "Total the minter may mint, in base units",
+                per_minter(|minter| minter.config.allowance),
+            ),
+            (
+                "stablecoin_minter_minted",
+                "Amount the minter has minted, in base units",
+                per_minter(|minter| minter.config.amount_minted),
+            ),
+            (
+                "stablecoin_minter_remaining_allowance",
+                "Amount the minter may still mint, in base units",
+                per_minter(MinterAccount::remaining_allowance),
+            ),
+        ] {
+            let values: Vec<(&str, u64)> = values
+                .iter()
+                .map(|(labels, value)| (labels.as_str(), *value))
+                .collect();
+            gauge(&mut out, name, help, &values);
+        }
+        out
+    }
+}
+
+fn gauge(out: &mut String, name: &str, help: &str, values: &[(&str, u64)]) {
+    metric(out, "gauge", name, help, values);
+}
+
+/// One metric family; each value comes with its labels, `""` for none
+fn metric(out: &mut String, kind: &str, name: &str, help: &str, values: &[(&str, u64)]) {
+    let _ = writeln!(out, "# HELP {name} {help}");
+    let _ = writeln!(out, "# TYPE {name} {kind}");
+    for (labels, value) in values {
+        if labels.is_empty() {
+            let _ = writeln!(out, "{name} {value}");
+        } else {
+            let _ = writeln!(out, "{name}{{{labels}}} {value}");
+        }
+    }
+}
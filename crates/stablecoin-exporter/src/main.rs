@@ -0,0 +1,107 @@
+//! `stablecoin-exporter`: serve the program's state as Prometheus metrics
+//!
+//! Polls the cluster every `--interval` seconds and serves the latest
+//! figures on `http://<listen>/metrics`. Useful alerts are
+//! `stablecoin_minter_remaining_allowance == 0`, `stablecoin_paused == 1` and
+//! `stablecoin_up == 0`.
+
+use std::{
+    convert::Infallible,
+    net::SocketAddr,
+    sync::Arc,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use anchor_lang::prelude::Pubkey;
+use clap::Parser;
+use http_body_util::Full;
+use hyper::{
+    body::{Bytes, Incoming},
+    header::CONTENT_TYPE,
+    server::conn::http1,
+    service::service_fn,
+    Request, Response, StatusCode,
+};
+use hyper_util::rt::TokioIo;
+use solana_commitment_config::CommitmentConfig;
+use solana_rpc_client::nonblocking::rpc_client::RpcClient;
+use stablecoin_exporter::{poll, Metrics};
+use tokio::{net::TcpListener, sync::RwLock};
+
+const CONTENT_TYPE_TEXT: &str = "text/plain; version=0.0.4";
+
+#[derive(Debug, Parser)]
+#[command(name = "stablecoin-exporter", version, about)]
+struct Args {
+    /// RPC URL
+    #[arg(short = 'u', long, default_value = "http://127.0.0.1:8899")]
+    url: String,
+    /// Address to serve metrics on
+    #[arg(long, default_value = "127.0.0.1:9464")]
+    listen: SocketAddr,
+    /// Seconds between polls
+    #[arg(long, default_value_t = 30)]
+    interval: u64,
+    /// Treasury token account, for `stablecoin_treasury_balance`; repeatable
+    #[arg(long, value_name = "PUBKEY")]
+    treasury: Vec<Pubkey>,
+    /// Commitment to read at
+    #[arg(long, default_value = "confirmed")]
+    commitment: CommitmentConfig,
+}
+
+#[tokio::main]
+async fn main() -> std::io::Result<()> {
+    let args = Args::parse();
+    let metrics = Arc::new(RwLock::new(Metrics::default()));
+
+    let rpc = RpcClient::new_with_commitment(args.url.clone(), args.commitment);
+    let polled = metrics.clone();
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(Duration::from_secs(args.interval));
+        loop {
+            interval.tick().await;
+            let result = poll(&rpc, &args.treasury).await;
+            if let Err(err) = &result {
+                eprintln!("poll failed: {err}");
+            }
+            let now = SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_secs();
+            polled.write().await.record(result, now);
+        }
+    });
+
+    let listener = TcpListener::bind(args.listen).await?;
+    eprintln!("serving metrics on http://{}/metrics", args.listen);
+    loop {
+        let (stream, _) = listener.accept().await?;
+        let metrics = metrics.clone();
+        tokio::spawn(async move {
+            let service = service_fn(move |request| serve(request, metrics.clone()));
+            if let Err(err) = http1::Builder::new()
+                .serve_connection(TokioIo::new(stream), service)
+                .await
+            {
+                eprintln!("connection failed: {err}");
+            }
+        });
+    }
+}
+
+async fn serve(
+    request: Request<Incoming>,
+    metrics: Arc<RwLock<Metrics>>,
+) -> Result<Response<Full<Bytes>>, Infallible> {
+    let response = if request.uri().path() == "/metrics" {
+        Response::builder()
+            .header(CONTENT_TYPE, CONTENT_TYPE_TEXT)
+            .body(Full::from(metrics.read().await.render()))
+    } else {
+        Response::builder()
+            .status(StatusCode::NOT_FOUND)
+            .body(Full::from("not found\n"))
+    };
+    Ok(response.expect("static response parts are valid"))
+}
@@ -0,0 +1,76 @@
+use anchor_lang::prelude::Pubkey;
+use stablecoin::MinterConfig;
+use stablecoin_client::{accounts::MinterAccount, supply::SupplyReport, ClientError};
+use stablecoin_exporter::{Metrics, Snapshot};
+
+fn snapshot(minter: Pubkey) -> Snapshot {
+    Snapshot {
+        supply: SupplyReport::new(42, 6, 1_000, 300, 0, 0),
+        paused: true,
+        minters: vec![MinterAccount {
+            address: Pubkey::new_unique(),
+            config: MinterConfig {
+                minter,
+                allowance: 500,
+                amount_minted: 500,
+                is_initialized: true,
+                bump: 255,
+                pause_bump: 255,
+            },
+        }],
+        last_event_slot: Some(40),
+    }
+}
+
+#[test]
+fn test_render_snapshot() {
+    let minter = Pubkey::new_unique();
+    let mut metrics = Metrics::default();
+    metrics.record(Ok(snapshot(minter)), 1_700_000_000);
+
+    let text = metrics.render();
+    for line in [
+        "# TYPE stablecoin_up gauge",
+        "stablecoin_up 1",
+        "stablecoin_poll_errors_total 0",
+        "stablecoin_last_success_timestamp_seconds 1700000000",
+        "stablecoin_slot 42",
+        "stablecoin_total_supply 1000",
+        "stablecoin_circulating_supply 700",
+        "stablecoin_treasury_balance 300",
+        "# HELP stablecoin_paused Whether minting is paused",
+        "stablecoin_paused 1",
+        "stablecoin_minters 1",
+        "stablecoin_last_event_slot 40",
+        &format!("stablecoin_minter_allowance{{minter=\"{minter}\"}} 500"),
+        &format!("stablecoin_minter_remaining_allowance{{minter=\"{minter}\"}} 0"),
+    ] {
+        assert!(
+            text.lines().any(|l| l == line),
+            "missing {line:?} in\n{text}"
+        );
+    }
+}
+
+#[test]
+fn test_failed_poll_keeps_last_snapshot() {
+    let mut metrics = Metrics::default();
+    metrics.record(
+        Err(ClientError::AccountNotFound(Pubkey::new_unique())),
+        1_700_000_000,
+    );
+    let text = metrics.render();
+    assert!(text.contains("stablecoin_up 0"));
+    assert!(!text.contains("stablecoin_total_supply"));
+
+    metrics.record(Ok(snapshot(Pubkey::new_unique())), 1_700_000_030);
+    metrics.record(
+        Err(ClientError::AccountNotFound(Pubkey::new_unique())),
+        1_700_000_060,
+    );
+    let text = metrics.render();
+    assert!(text.contains("stablecoin_up 0"));
+    assert!(text.contains("stablecoin_poll_errors_total 2"));
+    assert!(text.contains("stablecoin_last_success_timestamp_seconds 1700000030"));
+    assert!(text.contains("stablecoin_total_supply 1000"));
+}
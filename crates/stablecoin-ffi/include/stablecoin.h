@@ -0,0 +1,103 @@
+/*
+ * C interface to the stablecoin client (crates/stablecoin-ffi)
+ *
+ * - Addresses are passed as pointers to 32 bytes.
+ * - Every function returns a StablecoinStatus; results are written through
+ *   the out pointers, which are left untouched on failure.
+ * - Buffers returned by the library must be released with
+ *   stablecoin_buffer_free.
+ *
+ * Instructions are returned serialized as:
+ *
+ *   program_id      uint8_t[32]
+ *   account_count   uint16_t, little endian
+ *   accounts        account_count * { pubkey uint8_t[32], is_signer uint8_t, is_writable uint8_t }
+ *   data_len        uint32_t, little endian
+ *   data            uint8_t[data_len]
+ */
+
+#ifndef STABLECOIN_H
+#define STABLECOIN_H
+
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+typedef enum {
+    STABLECOIN_OK = 0,
+    STABLECOIN_NULL_POINTER = 1,
+    /* The account data is not the expected account type */
+    STABLECOIN_INVALID_ACCOUNT = 2,
+} StablecoinStatus;
+
+typedef struct {
+    uint8_t *data;
+    size_t len;
+} StablecoinBuffer;
+
+typedef struct {
+    uint8_t admin[32];
+    uint8_t mint[32];
+    bool paused;
+    uint8_t bump;
+    uint8_t mint_bump;
+    uint8_t delegate_bump;
+    uint8_t minter_set_freeze_bump;
+} StablecoinConfig;
+
+typedef struct {
+    uint8_t minter[32];
+    uint64_t allowance;
+    uint64_t amount_minted;
+    bool is_initialized;
+    uint8_t bump;
+    uint8_t pause_bump;
+} StablecoinMinterConfig;
+
+StablecoinStatus stablecoin_program_id(uint8_t *out);
+
+/* Program-derived addresses */
+StablecoinStatus stablecoin_config_pda(uint8_t *out_address, uint8_t *out_bump);
+StablecoinStatus stablecoin_mint_pda(uint8_t *out_address, uint8_t *out_bump);
+StablecoinStatus stablecoin_minter_config_pda(const uint8_t *minter,
+                                              uint8_t *out_address,
+                                              uint8_t *out_bump);
+/* The owner's associated token account for the stablecoin mint */
+StablecoinStatus stablecoin_token_account(const uint8_t *owner, uint8_t *out_address);
+
+/* Instructions */
+StablecoinStatus stablecoin_ix_initialize(const uint8_t *admin, StablecoinBuffer *out);
+StablecoinStatus stablecoin_ix_configure_minter(const uint8_t *admin,
+                                                const uint8_t *minter,
+                                                uint64_t allowance,
+                                                StablecoinBuffer *out);
+StablecoinStatus stablecoin_ix_remove_minter(const uint8_t *admin,
+                                             const uint8_t *minter,
+                                             StablecoinBuffer *out);
+StablecoinStatus stablecoin_ix_mint_tokens(const uint8_t *minter,
+                                           const uint8_t *destination_owner,
+                                           uint64_t amount,
+                                           StablecoinBuffer *out);
+StablecoinStatus stablecoin_ix_burn_tokens(const uint8_t *owner,
+                                           uint64_t amount,
+                                           StablecoinBuffer *out);
+StablecoinStatus stablecoin_ix_pause(const uint8_t *admin, StablecoinBuffer *out);
+StablecoinStatus stablecoin_ix_unpause(const uint8_t *admin, StablecoinBuffer *out);
+
+void stablecoin_buffer_free(StablecoinBuffer buffer);
+
+/* Account decoding; the data includes the 8-byte discriminator */
+StablecoinStatus stablecoin_decode_config(const uint8_t *data, size_t len, StablecoinConfig *out);
+StablecoinStatus stablecoin_decode_minter_config(const uint8_t *data,
+                                                 size_t len,
+                                                 StablecoinMinterConfig *out);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* STABLECOIN_H */
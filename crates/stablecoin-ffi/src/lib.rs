@@ -0,0 +1,372 @@
+//! C ABI over the stablecoin client
+//!
+//! Conventions shared by every function:
+//! - addresses are 32-byte arrays
+//! - the return value is a [`StablecoinStatus`]; results are written through
+//!   out pointers, which are left untouched on failure
+//! - buffers handed out by the library are released with
+//!   [`stablecoin_buffer_free`]
+//!
+//! The matching C declarations are in `include/stablecoin.h`.
+
+use std::{ptr, slice};
+
+use anchor_lang::{prelude::Pubkey, AccountDeserialize};
+use solana_instruction::Instruction;
+use stablecoin::{Config, MinterConfig};
+use stablecoin_client::{instructions, pda};
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StablecoinStatus {
+    Ok = 0,
+    NullPointer = 1,
+    /// The account data is not the expected account type
+    InvalidAccount = 2,
+}
+
+/// Bytes owned by the library
+#[repr(C)]
+#[derive(Debug)]
+pub struct StablecoinBuffer {
+    pub data: *mut u8,
+    pub len: usize,
+}
+
+impl StablecoinBuffer {
+    fn from_vec(bytes: Vec<u8>) -> Self {
+        let bytes = Box::into_raw(bytes.into_boxed_slice());
+        Self {
+            data: bytes as *mut u8,
+            len: bytes.len(),
+        }
+    }
+}
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StablecoinConfig {
+    pub admin: [u8; 32],
+    pub mint: [u8; 32],
+    pub paused: bool,
+    pub bump: u8,
+    pub mint_bump: u8,
+    pub delegate_bump: u8,
+    pub minter_set_freeze_bump: u8,
+}
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StablecoinMinterConfig {
+    pub minter: [u8; 32],
+    pub allowance: u64,
+    pub amount_minted: u64,
+    pub is_initialized: bool,
+    pub bump: u8,
+    pub pause_bump: u8,
+}
+
+/// Serialize an instruction as
+///
+/// ```text
+/// program_id      [u8; 32]
+/// account_count   u16 LE
+/// accounts        account_count * { pubkey [u8; 32], is_signer u8, is_writable u8 }
+/// data_len        u32 LE
+/// data            [u8; data_len]
+/// ```
+pub fn encode_instruction(instruction: &Instruction) -> Vec<u8> {
+    let mut out =
+        Vec::with_capacity(32 + 2 + instruction.accounts.len() * 34 + 4 + instruction.data.len());
+    out.extend_from_slice(instruction.program_id.as_ref());
+    out.extend_from_slice(&(instruction.accounts.len() as u16).to_le_bytes());
+    for meta in &instruction.accounts {
+        out.extend_from_slice(meta.pubkey.as_ref());
+        out.push(meta.is_signer as u8);
+        out.push(meta.is_writable as u8);
+    }
+    out.extend_from_slice(&(instruction.data.len() as u32).to_le_bytes());
+    out.extend_from_slice(&instruction.data);
+    out
+}
+
+unsafe fn read_pubkey(address: *const [u8; 32]) -> Option<Pubkey> {
+    address.as_ref().map(|bytes| Pubkey::new_from_array(*bytes))
+}
+
+unsafe fn write_pda(
+    (address, bump): (Pubkey, u8),
+    out_address: *mut [u8; 32],
+    out_bump: *mut u8,
+) -> StablecoinStatus {
+    if out_address.is_null() || out_bump.is_null() {
+        return StablecoinStatus::NullPointer;
+    }
+    *out_address = address.to_bytes();
+    *out_bump = bump;
+    StablecoinStatus::Ok
+}
+
+unsafe fn write_instruction(
+    instruction: Instruction,
+    out: *mut StablecoinBuffer,
+) -> StablecoinStatus {
+    if out.is_null() {
+        return StablecoinStatus::NullPointer;
+    }
+    ptr::write(
+        out,
+        StablecoinBuffer::from_vec(encode_instruction(&instruction)),
+    );
+    StablecoinStatus::Ok
+}
+
+/// # Safety
+///
+/// `out` must be valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn stablecoin_program_id(out: *mut [u8; 32]) -> StablecoinStatus {
+    if out.is_null() {
+        return StablecoinStatus::NullPointer;
+    }
+    *out = stablecoin_client::PROGRAM_ID.to_bytes();
+    StablecoinStatus::Ok
+}
+
+/// # Safety
+///
+/// `out_address` and `out_bump` must be valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn stablecoin_config_pda(
+    out_address: *mut [u8; 32],
+    out_bump: *mut u8,
+) -> StablecoinStatus {
+    write_pda(pda::config(), out_address, out_bump)
+}
+
+/// # Safety
+///
+/// `out_address` and `out_bump` must be valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn stablecoin_mint_pda(
+    out_address: *mut [u8; 32],
+    out_bump: *mut u8,
+) -> StablecoinStatus {
+    write_pda(pda::mint(), out_address, out_bump)
+}
+
+/// # Safety
+///
+/// `minter` must be valid for reads; `out_address` and `out_bump` for writes.
+#[no_mangle]
+pub unsafe extern "C" fn stablecoin_minter_config_pda(
+    minter: *const [u8; 32],
+    out_address: *mut [u8; 32],
+    out_bump: *mut u8,
+) -> StablecoinStatus {
+    let Some(minter) = read_pubkey(minter) else {
+        return StablecoinStatus::NullPointer;
+    };
+    write_pda(pda::minter_config(&minter), out_address, out_bump)
+}
+
+/// The owner's associated token account for the stablecoin mint
+///
+/// # Safety
+///
+/// `owner` must be valid for reads and `out_address` for writes.
+#[no_mangle]
+pub unsafe extern "C" fn stablecoin_token_account(
+    owner: *const [u8; 32],
+    out_address: *mut [u8; 32],
+) -> StablecoinStatus {
+    let Some(owner) = read_pubkey(owner) else {
+        return StablecoinStatus::NullPointer;
+    };
+    if out_address.is_null() {
+        return StablecoinStatus::NullPointer;
+    }
+    *out_address = pda::token_account(&owner).to_bytes();
+    StablecoinStatus::Ok
+}
+
+/// # Safety
+///
+/// `admin` must be valid for reads and `out` for writes.
+#[no_mangle]
+pub unsafe extern "C" fn stablecoin_ix_initialize(
+    admin: *const [u8; 32],
+    out: *mut StablecoinBuffer,
+) -> StablecoinStatus {
+    let Some(admin) = read_pubkey(admin) else {
+        return StablecoinStatus::NullPointer;
+    };
+    write_instruction(instructions::initialize(&admin), out)
+}
+
+/// # Safety
+///
+/// `admin` and `minter` must be valid for reads and `out` for writes.
+#[no_mangle]
+pub unsafe extern "C" fn stablecoin_ix_configure_minter(
+    admin: *const [u8; 32],
+    minter: *const [u8; 32],
+    allowance: u64,
+    out: *mut StablecoinBuffer,
+) -> StablecoinStatus {
+    let (Some(admin), Some(minter)) = (read_pubkey(admin), read_pubkey(minter)) else {
+        return StablecoinStatus::NullPointer;
+    };
+    write_instruction(
+        instructions::configure_minter(&admin, &minter, allowance),
+        out,
+    )
+}
+
+/// # Safety
+///
+/// `admin` and `minter` must be valid for reads and `out` for writes.
+#[no_mangle]
+pub unsafe extern "C" fn stablecoin_ix_remove_minter(
+    admin: *const [u8; 32],
+    minter: *const [u8; 32],
+    out: *mut StablecoinBuffer,
+) -> StablecoinStatus {
+    let (Some(admin), Some(minter)) = (read_pubkey(admin), read_pubkey(minter)) else {
+        return StablecoinStatus::NullPointer;
+    };
+    write_instruction(instructions::remove_minter(&admin, &minter), out)
+}
+
+/// # Safety
+///
+/// `minter` and `destination_owner` must be valid for reads and `out` for
+/// writes.
+#[no_mangle]
+pub unsafe extern "C" fn stablecoin_ix_mint_tokens(
+    minter: *const [u8; 32],
+    destination_owner: *const [u8; 32],
+    amount: u64,
+    out: *mut StablecoinBuffer,
+) -> StablecoinStatus {
+    let (Some(minter), Some(destination_owner)) =
+        (read_pubkey(minter), read_pubkey(destination_owner))
+    else {
+        return StablecoinStatus::NullPointer;
+    };
+    write_instruction(
+        instructions::mint_tokens(&minter, &destination_owner, amount),
+        out,
+    )
+}
+
+/// # Safety
+///
+/// `owner` must be valid for reads and `out` for writes.
+#[no_mangle]
+pub unsafe extern "C" fn stablecoin_ix_burn_tokens(
+    owner: *const [u8; 32],
+    amount: u64,
+    out: *mut StablecoinBuffer,
+) -> StablecoinStatus {
+    let Some(owner) = read_pubkey(owner) else {
+        return StablecoinStatus::NullPointer;
+    };
+    write_instruction(instructions::burn_tokens(&owner, amount), out)
+}
+
+/// # Safety
+///
+/// `admin` must be valid for reads and `out` for writes.
+#[no_mangle]
+pub unsafe extern "C" fn stablecoin_ix_pause(
+    admin: *const [u8; 32],
+    out: *mut StablecoinBuffer,
+) -> StablecoinStatus {
+    let Some(admin) = read_pubkey(admin) else {
+        return StablecoinStatus::NullPointer;
+    };
+    write_instruction(instructions::pause(&admin), out)
+}
+
+/// # Safety
+///
+/// `admin` must be valid for reads and `out` for writes.
+#[no_mangle]
+pub unsafe extern "C" fn stablecoin_ix_unpause(
+    admin: *const [u8; 32],
+    out: *mut StablecoinBuffer,
+) -> StablecoinStatus {
+    let Some(admin) = read_pubkey(admin) else {
+        return StablecoinStatus::NullPointer;
+    };
+    write_instruction(instructions::unpause(&admin), out)
+}
+
+/// Release a buffer returned by the library; null buffers are ignored
+///
+/// # Safety
+///
+/// `buffer` must come from this library and not have been freed already.
+#[no_mangle]
+pub unsafe extern "C" fn stablecoin_buffer_free(buffer: StablecoinBuffer) {
+    if !buffer.data.is_null() {
+        drop(Box::from_raw(ptr::slice_from_raw_parts_mut(
+            buffer.data,
+            buffer.len,
+        )));
+    }
+}
+
+/// # Safety
+///
+/// `data` must be valid for `len` bytes of reads and `out` for writes.
+#[no_mangle]
+pub unsafe extern "C" fn stablecoin_decode_config(
+    data: *const u8,
+    len: usize,
+    out: *mut StablecoinConfig,
+) -> StablecoinStatus {
+    if data.is_null() || out.is_null() {
+        return StablecoinStatus::NullPointer;
+    }
+    let Ok(config) = Config::try_deserialize(&mut slice::from_raw_parts(data, len)) else {
+        return StablecoinStatus::InvalidAccount;
+    };
+    *out = StablecoinConfig {
+        admin: config.admin.to_bytes(),
+        mint: config.mint.to_bytes(),
+        paused: config.paused,
+        bump: config.bump,
+        mint_bump: config.mint_bump,
+        delegate_bump: config.delegate_bump,
+        minter_set_freeze_bump: config.minter_set_freeze_bump,
+    };
+    StablecoinStatus::Ok
+}
+
+/// # Safety
+///
+/// `data` must be valid for `len` bytes of reads and `out` for writes.
+#[no_mangle]
+pub unsafe extern "C" fn stablecoin_decode_minter_config(
+    data: *const u8,
+    len: usize,
+    out: *mut StablecoinMinterConfig,
+) -> StablecoinStatus {
+    if data.is_null() || out.is_null() {
+        return StablecoinStatus::NullPointer;
+    }
+    let Ok(config) = MinterConfig::try_deserialize(&mut slice::from_raw_parts(data, len)) else {
+        return StablecoinStatus::InvalidAccount;
+    };
+    *out = StablecoinMinterConfig {
+        minter: config.minter.to_bytes(),
+        allowance: config.allowance,
+        amount_minted: config.amount_minted,
+        is_initialized: config.is_initialized,
+        bump: config.bump,
+        pause_bump: config.pause_bump,
+    };
+    StablecoinStatus::Ok
+}
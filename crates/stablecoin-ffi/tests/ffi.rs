@@ -0,0 +1,197 @@
+use std::ptr;
+
+use anchor_lang::{prelude::Pubkey, AccountSerialize};
+use stablecoin::{Config, MinterConfig};
+use stablecoin_client::{instructions, pda};
+use stablecoin_ffi::*;
+
+fn key(pubkey: &Pubkey) -> [u8; 32] {
+    pubkey.to_bytes()
+}
+
+fn empty_buffer() -> StablecoinBuffer {
+    StablecoinBuffer {
+        data: ptr::null_mut(),
+        len: 0,
+    }
+}
+
+unsafe fn take(buffer: StablecoinBuffer) -> Vec<u8> {
+    let bytes = std::slice::from_raw_parts(buffer.data, buffer.len).to_vec();
+    stablecoin_buffer_free(buffer);
+    bytes
+}
+
+#[test]
+fn test_pdas_match_client() {
+    let minter = Pubkey::new_unique();
+    let mut address = [0u8; 32];
+    let mut bump = 0u8;
+
+    unsafe {
+        assert_eq!(
+            stablecoin_config_pda(&mut address, &mut bump),
+            StablecoinStatus::Ok
+        );
+        assert_eq!((Pubkey::new_from_array(address), bump), pda::config());
+
+        assert_eq!(
+            stablecoin_mint_pda(&mut address, &mut bump),
+            StablecoinStatus::Ok
+        );
+        assert_eq!((Pubkey::new_from_array(address), bump), pda::mint());
+
+        assert_eq!(
+            stablecoin_minter_config_pda(&key(&minter), &mut address, &mut bump),
+            StablecoinStatus::Ok
+        );
+        assert_eq!(
+            (Pubkey::new_from_array(address), bump),
+            pda::minter_config(&minter)
+        );
+
+        assert_eq!(
+            stablecoin_token_account(&key(&minter), &mut address),
+            StablecoinStatus::Ok
+        );
+        assert_eq!(Pubkey::new_from_array(address), pda::token_account(&minter));
+    }
+}
+
+#[test]
+fn test_instruction_encoding() {
+    let minter = Pubkey::new_unique();
+    let owner = Pubkey::new_unique();
+    let mut buffer = empty_buffer();
+
+    let bytes = unsafe {
+        assert_eq!(
+            stablecoin_ix_mint_tokens(&key(&minter), &key(&owner), 1_000_000, &mut buffer),
+            StablecoinStatus::Ok
+        );
+        take(buffer)
+    };
+
+    let expected = instructions::mint_tokens(&minter, &owner, 1_000_000);
+    assert_eq!(bytes, encode_instruction(&expected));
+
+    // Walk the documented layout
+    assert_eq!(&bytes[..32], stablecoin_client::PROGRAM_ID.as_ref());
+    let count = u16::from_le_bytes([bytes[32], bytes[33]]) as usize;
+    assert_eq!(count, expected.accounts.len());
+    let first = &bytes[34..34 + 34];
+    assert_eq!(&first[..32], minter.as_ref());
+    assert_eq!(first[32..], [1, 1]);
+    let data_at = 34 + count * 34;
+    let data_len = u32::from_le_bytes(bytes[data_at..data_at + 4].try_into().unwrap()) as usize;
+    assert_eq!(&bytes[data_at + 4..], expected.data.as_slice());
+    assert_eq!(data_len, expected.data.len());
+}
+
+#[test]
+fn test_null_pointers() {
+    let mut address = [0u8; 32];
+    let mut buffer = empty_buffer();
+
+    unsafe {
+        assert_eq!(
+            stablecoin_config_pda(ptr::null_mut(), ptr::null_mut()),
+            StablecoinStatus::NullPointer
+        );
+        assert_eq!(
+            stablecoin_token_account(ptr::null(), &mut address),
+            StablecoinStatus::NullPointer
+        );
+        assert_eq!(
+            stablecoin_ix_pause(ptr::null(), &mut buffer),
+            StablecoinStatus::NullPointer
+        );
+        assert_eq!(
+            stablecoin_ix_pause(&address, ptr::null_mut()),
+            StablecoinStatus::NullPointer
+        );
+        // Freeing an empty buffer is a no-op
+        stablecoin_buffer_free(empty_buffer());
+    }
+    assert!(buffer.data.is_null());
+}
+
+#[test]
+fn test_decode_accounts() {
+    let config = Config {
+        admin: Pubkey::new_unique(),
+        mint: pda::mint().0,
+        paused: true,
+        bump: 254,
+        mint_bump: 253,
+        delegate_bump: 251,
+        minter_set_freeze_bump: 250,
+    };
+    let mut data = Vec::new();
+    config.try_serialize(&mut data).unwrap();
+
+    let mut decoded = StablecoinConfig {
+        admin: [0; 32],
+        mint: [0; 32],
+        paused: false,
+        bump: 0,
+        mint_bump: 0,
+        delegate_bump: 0,
+        minter_set_freeze_bump: 0,
+    };
+    unsafe {
+        assert_eq!(
+            stablecoin_decode_config(data.as_ptr(), data.len(), &mut decoded),
+            StablecoinStatus::Ok
+        );
+    }
+    assert_eq!(decoded.admin, key(&config.admin));
+    assert_eq!(decoded.mint, key(&config.mint));
+    assert!(decoded.paused);
+    assert_eq!(
+        (
+            decoded.bump,
+            decoded.mint_bump,
+            decoded.delegate_bump,
+            decoded.minter_set_freeze_bump
+        ),
+        (254, 253, 251, 250)
+    );
+
+    // A config is not a minter config
+    let mut minter = StablecoinMinterConfig {
+        minter: [0; 32],
+        allowance: 0,
+        amount_minted: 0,
+        is_initialized: false,
+        bump: 0,
+        pause_bump: 0,
+    };
+    unsafe {
+        assert_eq!(
+            stablecoin_decode_minter_config(data.as_ptr(), data.len(), &mut minter),
+            StablecoinStatus::InvalidAccount
+        );
+    }
+
+    let minter_config = MinterConfig {
+        minter: Pubkey::new_unique(),
+        allowance: 500,
+        amount_minted: 200,
+        is_initialized: true,
+        bump: 252,
+        pause_bump: 249,
+    };
+    let mut data = Vec::new();
+    minter_config.try_serialize(&mut data).unwrap();
+    unsafe {
+        assert_eq!(
+            stablecoin_decode_minter_config(data.as_ptr(), data.len(), &mut minter),
+            StablecoinStatus::Ok
+        );
+    }
+    assert_eq!(minter.minter, key(&minter_config.minter));
+    assert_eq!((minter.allowance, minter.amount_minted), (500, 200));
+    assert!(minter.is_initialized);
+    assert_eq!((minter.bump, minter.pause_bump), (252, 249));
+}
@@ -0,0 +1,303 @@
+//! The history from before the follower ran, read back over RPC
+//!
+//! A backfill walks `getSignaturesForAddress` for the program and for the
+//! mint, newest first, a page at a time, down to a starting slot. Each
+//! transaction is fetched and put in the form the stream delivers, so
+//! [`TransactionUpdate`] reads it exactly as it reads live ones, and the
+//! history tables' keys drop whatever the stream already wrote. After every
+//! page the walk's [`Cursor`] is saved, so an interrupted backfill picks up
+//! where it stopped.
+//!
+//! [`Pacer`] spaces the requests out and, when the node answers 429 Too Many
+//! Requests anyway, backs off and retries.
+
+use std::{future::Future, time::Duration};
+
+use anchor_lang::prelude::Pubkey;
+use solana_commitment_config::CommitmentConfig;
+use solana_rpc_client::{
+    nonblocking::rpc_client::RpcClient, rpc_client::GetConfirmedSignaturesForAddress2Config,
+};
+use solana_rpc_client_api::{
+    client_error::{Error as RpcError, ErrorKind},
+    config::RpcTransactionConfig,
+};
+use solana_signature::Signature;
+use solana_transaction_status_client_types::{
+    EncodedConfirmedTransactionWithStatusMeta, UiInstruction, UiLoadedAddresses,
+    UiTransactionEncoding,
+};
+use stablecoin_client::{pda, ClientError, PROGRAM_ID};
+use tokio::time::Instant;
+use yellowstone_grpc_proto::prelude::{
+    CompiledInstruction, InnerInstruction, InnerInstructions, Message, SubscribeUpdateTransaction,
+    SubscribeUpdateTransactionInfo, TokenBalance, Transaction, TransactionError,
+    TransactionStatusMeta,
+};
+
+use crate::{transactions::TransactionUpdate, ViewError};
+
+/// Maximum page size accepted by `getSignaturesForAddress`
+pub const PAGE_LIMIT: usize = 1_000;
+
+const MIN_BACKOFF: Duration = Duration::from_secs(1);
+/// Past this, a rate-limited request fails; the saved cursor resumes it
+const MAX_BACKOFF: Duration = Duration::from_secs(64);
+
+/// The addresses walked: the transactions [`crate::transactions::with_transactions`]
+/// streams are the ones naming either
+pub fn addresses() -> [Pubkey; 2] {
+    [PROGRAM_ID, pda::mint().0]
+}
+
+/// How far a backfill of one address has got
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cursor {
+    /// Oldest slot the walk goes back to
+    pub from_slot: u64,
+    /// Last signature walked past; `None` before the first page
+    pub before: Option<Signature>,
+    /// Whether the walk has reached `from_slot`
+    pub done: bool,
+}
+
+impl Cursor {
+    /// Where a walk back to `from_slot` starts, given the cursor an earlier
+    /// backfill left
+    ///
+    /// A finished walk going back at least as far has nothing left to do.
+    /// Otherwise the walk carries on from the last signature, so reaching
+    /// further back than an earlier backfill only fetches the difference.
+    pub fn resume(saved: Option<Cursor>, from_slot: u64) -> Cursor {
+        match saved {
+            Some(saved) if saved.done && saved.from_slot <= from_slot => saved,
+            saved => Cursor {
+                from_slot,
+                before: saved.and_then(|saved| saved.before),
+                done: false,
+            },
+        }
+    }
+}
+
+/// Spaces RPC requests at most `per_second` a second, and retries the
+/// ones the node rate limits
+#[derive(Debug)]
+pub struct Pacer {
+    interval: Duration,
+    next: Instant,
+}
+
+impl Pacer {
+    pub fn new(per_second: u32) -> Self {
+        Self {
+            interval: Duration::from_secs(1) / per_second.max(1),
+            next: Instant::now(),
+        }
+    }
+
+    /// Make `request` once its turn comes, again after a growing delay for
+    /// as long as it is rate limited
+    pub async fn call<T, F, Fut>(&mut self, mut request: F) -> Result<T, ClientError>
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = Result<T, RpcError>>,
+    {
+        let mut backoff = MIN_BACKOFF;
+        loop {
+            tokio::time::sleep_until(self.next).await;
+            self.next = Instant::now() + self.interval;
+            match request().await {
+                Err(err) if is_rate_limited(&err) && backoff <= MAX_BACKOFF => {
+                    tokio::time::sleep(backoff).await;
+                    backoff *= 2;
+                }
+                result => return result.map_err(ClientError::from),
+            }
+        }
+    }
+}
+
+/// Whether the node turned the request away as over its rate limit
+///
+/// The RPC client already retries a 429 a few times on its own; this is
+/// the one it gave up on.
+pub fn is_rate_limited(err: &RpcError) -> bool {
+    matches!(
+        err.kind(),
+        ErrorKind::Reqwest(err) if err.status().is_some_and(|status| status.as_u16() == 429)
+    )
+}
+
+/// The next page of the walk `cursor` describes: its successful
+/// transactions, newest first, and the cursor after them
+pub async fn page(
+    rpc: &RpcClient,
+    pacer: &mut Pacer,
+    address: &Pubkey,
+    cursor: Cursor,
+    commitment: CommitmentConfig,
+) -> Result<(Vec<Signature>, Cursor), ViewError> {
+    let statuses = pacer
+        .call(|| {
+            rpc.get_signatures_for_address_with_config(
+                address,
+                GetConfirmedSignaturesForAddress2Config {
+                    before: cursor.before,
+                    until: None,
+                    limit: Some(PAGE_LIMIT),
+                    commitment: Some(commitment),
+                },
+            )
+        })
+        .await?;
+
+    let mut next = Cursor {
+        done: statuses.len() < PAGE_LIMIT,
+        ..cursor
+    };
+    let mut signatures = Vec::new();
+    for status in statuses {
+        if status.slot < cursor.from_slot {
+            next.done = true;
+            break;
+        }
+        let signature = status
+            .signature
+            .parse()
+            .map_err(|_| ViewError::InvalidSignature)?;
+        next.before = Some(signature);
+        if status.err.is_none() {
+            signatures.push(signature);
+        }
+    }
+    Ok((signatures, next))
+}
+
+/// Fetch one transaction and read it as the stream would have delivered it
+pub async fn fetch(
+    rpc: &RpcClient,
+    pacer: &mut Pacer,
+    signature: &Signature,
+    commitment: CommitmentConfig,
+) -> Result<TransactionUpdate, ViewError> {
+    let transaction = pacer
+        .call(|| {
+            rpc.get_transaction_with_config(
+                signature,
+                RpcTransactionConfig {
+                    encoding: Some(UiTransactionEncoding::Base64),
+                    commitment: Some(commitment),
+                    max_supported_transaction_version: Some(0),
+                },
+            )
+        })
+        .await?;
+    TransactionUpdate::try_from(from_rpc(signature, transaction)?)
+}
+
+/// `transaction`, fetched over RPC in base64, in the form the stream
+/// delivers it; only what [`TransactionUpdate`] reads is filled in
+pub fn from_rpc(
+    signature: &Signature,
+    transaction: EncodedConfirmedTransactionWithStatusMeta,
+) -> Result<SubscribeUpdateTransaction, ViewError> {
+    let versioned = transaction
+        .transaction
+        .transaction
+        .decode()
+        .ok_or(ViewError::MissingTransaction)?;
+    let meta = transaction
+        .transaction
+        .meta
+        .ok_or(ViewError::MissingTransaction)?;
+    let address = |address: String| {
+        address
+            .parse::<Pubkey>()
+            .map(|address| address.to_bytes().to_vec())
+            .map_err(|_| ViewError::InvalidAddress)
+    };
+    let loaded = Option::<UiLoadedAddresses>::from(meta.loaded_addresses).unwrap_or_default();
+
+    let message = Message {
+        account_keys: versioned
+            .message
+            .static_account_keys()
+            .iter()
+            .map(|key| key.to_bytes().to_vec())
+            .collect(),
+        instructions: versioned
+            .message
+            .instructions()
+            .iter()
+            .map(|instruction| CompiledInstruction {
+                program_id_index: instruction.program_id_index.into(),
+                accounts: instruction.accounts.clone(),
+                data: instruction.data.clone(),
+            })
+            .collect(),
+        ..Message::default()
+    };
+    let mut inner_instructions = Vec::new();
+    for inner in Option::<Vec<_>>::from(meta.inner_instructions).unwrap_or_default() {
+        let mut instructions = Vec::new();
+        for instruction in inner.instructions {
+            // Base64 encoding only ever yields compiled instructions
+            let UiInstruction::Compiled(instruction) = instruction else {
+                continue;
+            };
+            instructions.push(InnerInstruction {
+                program_id_index: instruction.program_id_index.into(),
+                accounts: instruction.accounts,
+                data: bs58::decode(&instruction.data)
+                    .into_vec()
+                    .map_err(|_| ViewError::MissingTransaction)?,
+                stack_height: instruction.stack_height,
+            });
+        }
+        inner_instructions.push(InnerInstructions {
+            index: inner.index.into(),
+            instructions,
+        });
+    }
+    let meta = TransactionStatusMeta {
+        // Only whether there was one is read
+        err: meta.err.map(|_| TransactionError::default()),
+        log_messages: Option::from(meta.log_messages).unwrap_or_default(),
+        inner_instructions,
+        pre_token_balances: Option::<Vec<_>>::from(meta.pre_token_balances)
+            .unwrap_or_default()
+            .into_iter()
+            .map(|balance| TokenBalance {
+                account_index: balance.account_index.into(),
+                mint: balance.mint,
+                ..TokenBalance::default()
+            })
+            .collect(),
+        loaded_writable_addresses: loaded
+            .writable
+            .into_iter()
+            .map(address)
+            .collect::<Result<_, _>>()?,
+        loaded_readonly_addresses: loaded
+            .readonly
+            .into_iter()
+            .map(address)
+            .collect::<Result<_, _>>()?,
+        ..TransactionStatusMeta::default()
+    };
+
+    Ok(SubscribeUpdateTransaction {
+        transaction: Some(SubscribeUpdateTransactionInfo {
+            signature: signature.as_ref().to_vec(),
+            transaction: Some(Transaction {
+                signatures: vec![signature.as_ref().to_vec()],
+                message: Some(message),
+            }),
+            meta: Some(meta),
+            ..SubscribeUpdateTransactionInfo::default()
+        }),
+        slot: transaction.slot,
+        ..SubscribeUpdateTransaction::default()
+    })
+}
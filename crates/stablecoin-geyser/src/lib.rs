@@ -0,0 +1,271 @@
+//! Live supply and minter view fed by a Yellowstone gRPC account stream
+//!
+//! [`subscribe_request`] asks for the config and mint PDAs and for every
+//! account the program owns, which covers new minter configs. A closed
+//! account belongs to the system program, so an owner filter never sees a
+//! minter being removed; known minter configs are listed by address too, and
+//! the request is resent as minters appear. [`View::apply`] folds each update
+//! into memory; an update older than what the view already holds for that
+//! account is dropped, so a view loaded over RPC can be fed a stream that
+//! overlaps it.
+//!
+//! The account stream only gives the current state. [`transactions`] follows
+//! the program's transactions too, for the history of mints, burns,
+//! transfers and admin actions the `postgres` store keeps. [`backfill`]
+//! reads the same history back over RPC, for the stretch before the
+//! follower started.
+
+pub mod backfill;
+#[cfg(feature = "postgres")]
+pub mod postgres;
+pub mod transactions;
+
+use std::collections::{BTreeMap, HashMap};
+
+use anchor_lang::{prelude::Pubkey, AccountDeserialize, Discriminator};
+use anchor_spl::token::Mint;
+use serde_json::{json, Value};
+use solana_commitment_config::{CommitmentConfig, CommitmentLevel as Commitment};
+use solana_rpc_client::nonblocking::rpc_client::RpcClient;
+use stablecoin::{Config, MinterConfig};
+use stablecoin_client::{
+    accounts::{self, MinterAccount, MAX_MULTIPLE_ACCOUNTS},
+    events::EventError,
+    pda, ClientError, PROGRAM_ID,
+};
+use yellowstone_grpc_proto::prelude::{
+    CommitmentLevel, SubscribeRequest, SubscribeRequestFilterAccounts, SubscribeUpdateAccount,
+};
+
+/// Name of the accounts filter in [`subscribe_request`], and of the
+/// transactions filter [`transactions::with_transactions`] adds
+const FILTER: &str = "stablecoin";
+
+#[derive(Debug, thiserror::Error)]
+pub enum ViewError {
+    #[error("update has a malformed address")]
+    InvalidAddress,
+    #[error("account update carries no account")]
+    MissingAccount,
+    #[error("transaction update carries no transaction")]
+    MissingTransaction,
+    #[error("transaction update has a malformed signature")]
+    InvalidSignature,
+    #[error(transparent)]
+    Event(#[from] EventError),
+    #[error(transparent)]
+    Client(#[from] ClientError),
+}
+
+/// The subscription for the program's accounts at `commitment`, listing
+/// `minters` so that their closing is seen
+pub fn subscribe_request<'a>(
+    commitment: CommitmentConfig,
+    minters: impl IntoIterator<Item = &'a Pubkey>,
+) -> SubscribeRequest {
+    let commitment = match commitment.commitment {
+        Commitment::Processed => CommitmentLevel::Processed,
+        Commitment::Confirmed => CommitmentLevel::Confirmed,
+        Commitment::Finalized => CommitmentLevel::Finalized,
+    };
+    SubscribeRequest {
+        accounts: HashMap::from([(
+            FILTER.to_string(),
+            SubscribeRequestFilterAccounts {
+                // The mint is owned by the token program, so it is listed
+                account: [pda::config().0, pda::mint().0]
+                    .into_iter()
+                    .chain(minters.into_iter().copied())
+                    .map(|address| address.to_string())
+                    .collect(),
+                owner: vec![PROGRAM_ID.to_string()],
+                ..SubscribeRequestFilterAccounts::default()
+            },
+        )]),
+        commitment: Some(commitment as i32),
+        ..SubscribeRequest::default()
+    }
+}
+
+/// One account's state at a slot
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AccountUpdate {
+    pub address: Pubkey,
+    pub owner: Pubkey,
+    /// Zero once the account is closed
+    pub lamports: u64,
+    pub data: Vec<u8>,
+    pub slot: u64,
+}
+
+impl TryFrom<SubscribeUpdateAccount> for AccountUpdate {
+    type Error = ViewError;
+
+    fn try_from(update: SubscribeUpdateAccount) -> Result<Self, ViewError> {
+        let account = update.account.ok_or(ViewError::MissingAccount)?;
+        let pubkey = |bytes: &[u8]| Pubkey::try_from(bytes).map_err(|_| ViewError::InvalidAddress);
+        Ok(Self {
+            address: pubkey(&account.pubkey)?,
+            owner: pubkey(&account.owner)?,
+            lamports: account.lamports,
+            data: account.data,
+            slot: update.slot,
+        })
+    }
+}
+
+/// What an applied update changed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Change {
+    Config,
+    Mint,
+    /// A minter config was created or updated, by config address
+    Minter(Pubkey),
+    /// A minter config was closed, by config address
+    MinterRemoved(Pubkey),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MintState {
+    pub supply: u64,
+    pub decimals: u8,
+}
+
+/// The program's state as of the updates applied so far
+#[derive(Debug, Clone, Default)]
+pub struct View {
+    /// Newest slot applied
+    pub slot: u64,
+    pub config: Option<Config>,
+    pub mint: Option<MintState>,
+    /// Minter configs by address
+    pub minters: BTreeMap<Pubkey, MinterAccount>,
+    /// Slot each account was last updated at
+    slots: HashMap<Pubkey, u64>,
+}
+
+impl View {
+    /// Fold in one account update; `None` if it changed nothing
+    pub fn apply(&mut self, update: &AccountUpdate) -> Result<Option<Change>, ViewError> {
+        let address = update.address;
+        if self
+            .slots
+            .get(&address)
+            .is_some_and(|slot| *slot > update.slot)
+        {
+            return Ok(None);
+        }
+        let closed = update.lamports == 0;
+
+        let change = if address == pda::config().0 {
+            self.config = if closed {
+                None
+            } else {
+                Some(accounts::decode_config(address, &update.data)?)
+            };
+            Change::Config
+        } else if address == pda::mint().0 {
+            self.mint = if closed {
+                None
+            } else {
+                let mint = Mint::try_deserialize(&mut &update.data[..])
+                    .map_err(|source| ClientError::AccountDecode { address, source })?;
+                Some(MintState {
+                    supply: mint.supply,
+                    decimals: mint.decimals,
+                })
+            };
+            Change::Mint
+        } else if closed {
+            if self.minters.remove(&address).is_none() {
+                return Ok(None);
+            }
+            Change::MinterRemoved(address)
+        } else if update.owner == PROGRAM_ID && update.data.starts_with(MinterConfig::DISCRIMINATOR)
+        {
+            let minter = accounts::decode_minter_config(address, &update.data)?;
+            self.minters.insert(address, minter);
+            Change::Minter(address)
+        } else {
+            return Ok(None);
+        };
+
+        self.slots.insert(address, update.slot);
+        self.slot = self.slot.max(update.slot);
+        Ok(Some(change))
+    }
+
+    /// Supply and config, for `GET /supply`
+    pub fn supply_json(&self) -> Value {
+        json!({
+            "slot": self.slot,
+            "supply": self.mint.map(|mint| mint.supply.to_string()),
+            "decimals": self.mint.map(|mint| mint.decimals),
+            "paused": self.config.as_ref().map(|config| config.paused),
+            "admin": self.config.as_ref().map(|config| config.admin.to_string()),
+            "minters": self.minters.len(),
+        })
+    }
+
+    /// Every minter, for `GET /minters`
+    pub fn minters_json(&self) -> Value {
+        json!({
+            "slot": self.slot,
+            "minters": self.minters.values().map(minter_json).collect::<Vec<_>>(),
+        })
+    }
+
+    /// One minter by wallet, for `GET /minters/<pubkey>`
+    pub fn minter_json(&self, minter: &Pubkey) -> Option<Value> {
+        self.minters
+            .get(&pda::minter_config(minter).0)
+            .map(minter_json)
+    }
+}
+
+/// Amounts are strings, as JSON numbers lose precision above 2^53
+fn minter_json(minter: &MinterAccount) -> Value {
+    json!({
+        "address": minter.address.to_string(),
+        "minter": minter.config.minter.to_string(),
+        "allowance": minter.config.allowance.to_string(),
+        "amount_minted": minter.config.amount_minted.to_string(),
+        "remaining_allowance": minter.remaining_allowance().to_string(),
+    })
+}
+
+/// Read the whole view over RPC
+///
+/// Every account is stamped with the slot read before any of them, which
+/// keeps later stream updates for the same account winning over it.
+pub async fn load(rpc: &RpcClient) -> Result<View, ViewError> {
+    let slot = rpc
+        .get_slot_with_commitment(rpc.commitment())
+        .await
+        .map_err(ClientError::from)?;
+    let mut addresses = vec![pda::config().0, pda::mint().0];
+    addresses.extend(accounts::minter_addresses(rpc).await?);
+
+    let mut view = View::default();
+    for batch in addresses.chunks(MAX_MULTIPLE_ACCOUNTS) {
+        let accounts = rpc
+            .get_multiple_accounts_with_commitment(batch, rpc.commitment())
+            .await
+            .map_err(ClientError::from)?
+            .value;
+        for (address, account) in batch.iter().zip(accounts) {
+            let Some(account) = account else {
+                continue;
+            };
+            view.apply(&AccountUpdate {
+                address: *address,
+                owner: account.owner,
+                lamports: account.lamports,
+                data: account.data,
+                slot,
+            })?;
+        }
+    }
+    view.slot = slot;
+    Ok(view)
+}
@@ -0,0 +1,437 @@
+//! `stablecoin-geyser`: serve a live supply and minter view over HTTP
+//!
+//! Follows the program's accounts over a Yellowstone gRPC stream and serves
+//! `GET /supply`, `GET /minters` and `GET /minters/<pubkey>` as JSON from
+//! memory. On every (re)connect the view is reloaded over RPC after the
+//! subscription is open, so nothing that lands in between is missed. Built
+//! with the `postgres` feature, `--postgres` mirrors the view into a database
+//! and follows the program's transactions too, keeping the history of
+//! mints, burns, transfers and admin actions there. That history is served
+//! as `GET /holders/<pubkey>/history`, a wallet's or token account's mints,
+//! burns and transfers, and `GET /events?since=<slot>`, everything recorded
+//! after a slot, a page at a time, each page's `next` passed as the next
+//! `since`. `backfill --from-slot <slot>` fills that history in over RPC
+//! for the stretch before the follower started.
+
+use std::{convert::Infallible, net::SocketAddr, str::FromStr, sync::Arc, time::Duration};
+
+use anchor_lang::prelude::Pubkey;
+use clap::Parser;
+use futures_util::{SinkExt, StreamExt};
+use http_body_util::Full;
+use hyper::{
+    body::{Bytes, Incoming},
+    header::CONTENT_TYPE,
+    server::conn::http1,
+    service::service_fn,
+    Request, Response, StatusCode,
+};
+use hyper_util::rt::TokioIo;
+use serde_json::Value;
+use solana_commitment_config::CommitmentConfig;
+use solana_rpc_client::nonblocking::rpc_client::RpcClient;
+use stablecoin_geyser::{
+    load, subscribe_request, transactions::with_transactions, AccountUpdate, Change, View,
+    ViewError,
+};
+use tokio::{net::TcpListener, sync::RwLock};
+use yellowstone_grpc_client::{
+    ClientTlsConfig, GeyserGrpcBuilderError, GeyserGrpcClient, GeyserGrpcClientError,
+    SubscribeRequestSinkError,
+};
+use yellowstone_grpc_proto::prelude::{
+    subscribe_update::UpdateOneof, SubscribeRequest, SubscribeRequestPing,
+};
+
+#[cfg(feature = "postgres")]
+use clap::Subcommand;
+#[cfg(feature = "postgres")]
+use stablecoin_geyser::{
+    backfill::{self, Cursor, Pacer},
+    postgres::{EventCursor, Store},
+    transactions::TransactionUpdate,
+};
+#[cfg(feature = "postgres")]
+use std::collections::HashSet;
+
+const RECONNECT_DELAY: Duration = Duration::from_millis(500);
+const MAX_RECONNECT_DELAY: Duration = Duration::from_secs(30);
+
+type Shared = Arc<RwLock<Option<View>>>;
+
+#[derive(Debug, Parser)]
+#[command(
+    name = "stablecoin-geyser",
+    version,
+    about,
+    subcommand_negates_reqs = true
+)]
+struct Args {
+    /// RPC URL, to load the view on (re)connect and to backfill from
+    #[arg(
+        short = 'u',
+        long,
+        default_value = "http://127.0.0.1:8899",
+        global = true
+    )]
+    url: String,
+    /// Yellowstone gRPC endpoint
+    #[arg(long, value_name = "URL", required = true)]
+    grpc: Option<String>,
+    /// Yellowstone access token
+    #[arg(long)]
+    x_token: Option<String>,
+    /// Address to serve the HTTP API on
+    #[arg(long, default_value = "127.0.0.1:8080")]
+    listen: SocketAddr,
+    /// Commitment to follow at
+    #[arg(long, default_value = "confirmed", global = true)]
+    commitment: CommitmentConfig,
+    /// Postgres connection string to mirror the view into
+    #[cfg(feature = "postgres")]
+    #[arg(long, value_name = "URL", global = true)]
+    postgres: Option<String>,
+    #[cfg(feature = "postgres")]
+    #[command(subcommand)]
+    command: Option<Command>,
+}
+
+#[cfg(feature = "postgres")]
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// Fill the history tables over RPC back to a slot, then exit
+    ///
+    /// Run it alongside the follower, which covers everything from its own
+    /// start; what both see is written once. An interrupted backfill
+    /// resumes where it stopped.
+    Backfill {
+        /// Oldest slot to read back to
+        #[arg(long)]
+        from_slot: u64,
+        /// Most RPC requests a second
+        #[arg(long, default_value_t = 10)]
+        rate: u32,
+    },
+}
+
+#[derive(Debug, thiserror::Error)]
+enum FollowError {
+    #[error(transparent)]
+    Connect(#[from] GeyserGrpcBuilderError),
+    #[error(transparent)]
+    Stream(#[from] GeyserGrpcClientError),
+    #[error(transparent)]
+    Send(#[from] SubscribeRequestSinkError),
+    #[error(transparent)]
+    View(#[from] ViewError),
+    #[cfg(feature = "postgres")]
+    #[error("postgres: {0}")]
+    Postgres(#[from] tokio_postgres::Error),
+}
+
+impl From<tonic::Status> for FollowError {
+    fn from(status: tonic::Status) -> Self {
+        Self::Stream(status.into())
+    }
+}
+
+#[tokio::main]
+async fn main() -> std::io::Result<()> {
+    let args = Args::parse();
+
+    #[cfg(feature = "postgres")]
+    if let Some(Command::Backfill { from_slot, rate }) = args.command {
+        let Some(url) = &args.postgres else {
+            eprintln!("error: backfill needs --postgres");
+            std::process::exit(2);
+        };
+        let result = match Store::connect(url).await {
+            Ok(mut store) => backfill(&args, &mut store, from_slot, rate).await,
+            Err(err) => Err(err.into()),
+        };
+        if let Err(err) = result {
+            eprintln!("error: backfill: {err}");
+            std::process::exit(1);
+        }
+        return Ok(());
+    }
+
+    let view: Shared = Arc::new(RwLock::new(None));
+
+    #[cfg(feature = "postgres")]
+    let mut store = match &args.postgres {
+        Some(url) => match Store::connect(url).await {
+            Ok(store) => Some(store),
+            Err(err) => {
+                eprintln!("error: postgres: {err}");
+                std::process::exit(1);
+            }
+        },
+        None => None,
+    };
+
+    // The follower holds its own connection for writing
+    #[cfg(feature = "postgres")]
+    let history = match &args.postgres {
+        Some(url) => match Store::connect(url).await {
+            Ok(store) => Some(Arc::new(store)),
+            Err(err) => {
+                eprintln!("error: postgres: {err}");
+                std::process::exit(1);
+            }
+        },
+        None => None,
+    };
+
+    let listen = args.listen;
+    let rpc = RpcClient::new_with_commitment(args.url.clone(), args.commitment);
+    let followed = view.clone();
+    tokio::spawn(async move {
+        let mut delay = RECONNECT_DELAY;
+        loop {
+            let result = follow(
+                &args,
+                &rpc,
+                &followed,
+                #[cfg(feature = "postgres")]
+                &mut store,
+            )
+            .await;
+            match result {
+                Ok(()) => delay = RECONNECT_DELAY,
+                Err(err) => {
+                    eprintln!("stream failed: {err}");
+                    tokio::time::sleep(delay).await;
+                    delay = (delay * 2).min(MAX_RECONNECT_DELAY);
+                }
+            }
+        }
+    });
+
+    let listener = TcpListener::bind(listen).await?;
+    eprintln!("serving on http://{listen}");
+    loop {
+        let (stream, _) = listener.accept().await?;
+        let view = view.clone();
+        #[cfg(feature = "postgres")]
+        let history = history.clone();
+        tokio::spawn(async move {
+            let service = service_fn(move |request| {
+                serve(
+                    request,
+                    view.clone(),
+                    #[cfg(feature = "postgres")]
+                    history.clone(),
+                )
+            });
+            if let Err(err) = http1::Builder::new()
+                .serve_connection(TokioIo::new(stream), service)
+                .await
+            {
+                eprintln!("connection failed: {err}");
+            }
+        });
+    }
+}
+
+/// One gRPC session, from connect until the stream ends or fails
+async fn follow(
+    args: &Args,
+    rpc: &RpcClient,
+    view: &Shared,
+    #[cfg(feature = "postgres")] store: &mut Option<Store>,
+) -> Result<(), FollowError> {
+    let grpc = args.grpc.clone().expect("required without a subcommand");
+    let tls = grpc.starts_with("https://");
+    let mut builder = GeyserGrpcClient::build_from_shared(grpc)?.x_token(args.x_token.clone())?;
+    if tls {
+        builder = builder.tls_config(ClientTlsConfig::new().with_native_roots())?;
+    }
+    let mut client = builder.connect().await?;
+
+    // Subscribe, then load: updates landing in between queue on the stream
+    #[cfg(feature = "postgres")]
+    let history = store.is_some();
+    #[cfg(not(feature = "postgres"))]
+    let history = false;
+    let request = |known: &[Pubkey]| {
+        let request = subscribe_request(args.commitment, known);
+        if history {
+            with_transactions(request)
+        } else {
+            request
+        }
+    };
+    let (mut sink, mut stream) = client.subscribe_with_request(Some(request(&[]))).await?;
+    let loaded = load(rpc).await?;
+    let mut known: Vec<Pubkey> = loaded.minters.keys().copied().collect();
+    sink.send(request(&known)).await?;
+    #[cfg(feature = "postgres")]
+    if let Some(store) = store {
+        store.sync(&loaded).await?;
+    }
+    *view.write().await = Some(loaded);
+
+    while let Some(update) = stream.next().await {
+        match update?.update_oneof {
+            Some(UpdateOneof::Account(account)) => {
+                let update = AccountUpdate::try_from(account)?;
+                let mut guard = view.write().await;
+                let view = guard.as_mut().expect("loaded before streaming");
+                let Some(change) = view.apply(&update)? else {
+                    continue;
+                };
+                if let Change::Minter(address) = change {
+                    if !known.contains(&address) {
+                        known.push(address);
+                        sink.send(request(&known)).await?;
+                    }
+                }
+                #[cfg(feature = "postgres")]
+                if let Some(store) = store {
+                    store.write(view, change).await?;
+                }
+            }
+            #[cfg(feature = "postgres")]
+            Some(UpdateOneof::Transaction(transaction)) => {
+                if let Some(store) = store {
+                    store
+                        .write_transaction(&TransactionUpdate::try_from(transaction)?)
+                        .await?;
+                }
+            }
+            // Keeps load balancers from dropping an idle stream
+            Some(UpdateOneof::Ping(_)) => {
+                sink.send(SubscribeRequest {
+                    ping: Some(SubscribeRequestPing { id: 1 }),
+                    ..SubscribeRequest::default()
+                })
+                .await?;
+            }
+            _ => {}
+        }
+    }
+    Ok(())
+}
+
+/// Walk each address back to `from_slot`, writing what every transaction
+/// did and saving the cursor after each page
+#[cfg(feature = "postgres")]
+async fn backfill(
+    args: &Args,
+    store: &mut Store,
+    from_slot: u64,
+    rate: u32,
+) -> Result<(), FollowError> {
+    let rpc = RpcClient::new_with_commitment(args.url.clone(), args.commitment);
+    let mut pacer = Pacer::new(rate);
+    // Transactions naming both addresses are fetched once
+    let mut fetched = HashSet::new();
+    for address in backfill::addresses() {
+        let mut cursor = Cursor::resume(store.backfill_cursor(&address).await?, from_slot);
+        while !cursor.done {
+            let (signatures, next) =
+                backfill::page(&rpc, &mut pacer, &address, cursor, args.commitment).await?;
+            for signature in signatures {
+                if fetched.insert(signature) {
+                    let update =
+                        backfill::fetch(&rpc, &mut pacer, &signature, args.commitment).await?;
+                    store.write_transaction(&update).await?;
+                }
+            }
+            store.save_backfill_cursor(&address, &next).await?;
+            if let Some(before) = next.before {
+                eprintln!("{address}: back to {before}");
+            }
+            cursor = next;
+        }
+        eprintln!("{address}: back to slot {from_slot}");
+    }
+    Ok(())
+}
+
+async fn serve(
+    request: Request<Incoming>,
+    view: Shared,
+    #[cfg(feature = "postgres")] history: Option<Arc<Store>>,
+) -> Result<Response<Full<Bytes>>, Infallible> {
+    #[cfg(feature = "postgres")]
+    if let Some(response) = serve_history(&request, history.as_deref()).await {
+        return Ok(response);
+    }
+    let guard = view.read().await;
+    let response = match (guard.as_ref(), request.uri().path()) {
+        (None, _) => text(StatusCode::SERVICE_UNAVAILABLE, "loading\n"),
+        (Some(view), "/supply") => json(&view.supply_json()),
+        (Some(view), "/minters") => json(&view.minters_json()),
+        (Some(view), path) => match path.strip_prefix("/minters/") {
+            Some(minter) => match Pubkey::from_str(minter) {
+                Ok(minter) => match view.minter_json(&minter) {
+                    Some(body) => json(&body),
+                    None => text(StatusCode::NOT_FOUND, "not a minter\n"),
+                },
+                Err(_) => text(StatusCode::BAD_REQUEST, "invalid pubkey\n"),
+            },
+            None => text(StatusCode::NOT_FOUND, "not found\n"),
+        },
+    };
+    Ok(response)
+}
+
+/// The history routes, read from Postgres; `None` for any other path
+#[cfg(feature = "postgres")]
+async fn serve_history(
+    request: &Request<Incoming>,
+    store: Option<&Store>,
+) -> Option<Response<Full<Bytes>>> {
+    let path = request.uri().path();
+    let holder = path
+        .strip_prefix("/holders/")
+        .and_then(|rest| rest.strip_suffix("/history"));
+    if holder.is_none() && path != "/events" {
+        return None;
+    }
+    let Some(store) = store else {
+        return Some(text(StatusCode::NOT_FOUND, "history needs --postgres\n"));
+    };
+    let body = match holder {
+        Some(holder) => match Pubkey::from_str(holder) {
+            Ok(holder) => store.holder_history(&holder).await,
+            Err(_) => return Some(text(StatusCode::BAD_REQUEST, "invalid pubkey\n")),
+        },
+        None => {
+            let since = request
+                .uri()
+                .query()
+                .unwrap_or_default()
+                .split('&')
+                .find_map(|pair| pair.strip_prefix("since="))
+                .map_or(Ok(EventCursor::slot(0)), str::parse);
+            match since {
+                Ok(since) => store.events_since(&since).await,
+                Err(_) => return Some(text(StatusCode::BAD_REQUEST, "invalid since\n")),
+            }
+        }
+    };
+    Some(match body {
+        Ok(body) => json(&body),
+        Err(err) => {
+            eprintln!("postgres: {err}");
+            text(StatusCode::INTERNAL_SERVER_ERROR, "history unavailable\n")
+        }
+    })
+}
+
+fn json(body: &Value) -> Response<Full<Bytes>> {
+    Response::builder()
+        .header(CONTENT_TYPE, "application/json")
+        .body(Full::from(body.to_string()))
+        .expect("static response parts are valid")
+}
+
+fn text(status: StatusCode, body: &'static str) -> Response<Full<Bytes>> {
+    Response::builder()
+        .status(status)
+        .body(Full::from(body))
+        .expect("static response parts are valid")
+}
@@ -0,0 +1,579 @@
+//! Mirror of the view in Postgres, and the history behind it
+//!
+//! Two tables mirror the view: `stablecoin_state`, a single row of config
+//! and supply, and `stablecoin_minters`, one row per minter config.
+//! [`Store::write_transaction`] appends to the history tables, one per
+//! [`Record`] kind: `stablecoin_mints`, `stablecoin_burns`,
+//! `stablecoin_transfers`, `stablecoin_admin_actions` and
+//! `stablecoin_minter_history`. History rows are keyed by signature and
+//! index, so a transaction streamed twice, say across a reconnect, is
+//! written once. Amounts are `NUMERIC`, since `BIGINT` is signed and a
+//! `u64` may not fit.
+//!
+//! [`Store::holder_history`] and [`Store::events_since`] read the history
+//! back for the HTTP API. `stablecoin_backfill` keeps each backfill walk's
+//! [`Cursor`], one row per address walked.
+
+use std::{fmt, str::FromStr};
+
+use anchor_lang::prelude::Pubkey;
+use serde_json::{json, Map, Value};
+use tokio_postgres::{types::ToSql, Client, NoTls, Row};
+
+use crate::{
+    backfill::Cursor,
+    transactions::{Record, TransactionUpdate},
+    Change, View,
+};
+
+const SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS stablecoin_state (
+    id BOOLEAN PRIMARY KEY DEFAULT TRUE CHECK (id),
+    slot BIGINT NOT NULL,
+    admin TEXT,
+    paused BOOLEAN,
+    supply NUMERIC,
+    decimals SMALLINT
+);
+CREATE TABLE IF NOT EXISTS stablecoin_minters (
+    address TEXT PRIMARY KEY,
+    minter TEXT NOT NULL,
+    allowance NUMERIC NOT NULL,
+    amount_minted NUMERIC NOT NULL,
+    slot BIGINT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS stablecoin_mints (
+    signature TEXT NOT NULL,
+    index INTEGER NOT NULL,
+    slot BIGINT NOT NULL,
+    minter TEXT NOT NULL,
+    destination TEXT NOT NULL,
+    amount NUMERIC NOT NULL,
+    remaining_allowance NUMERIC NOT NULL,
+    PRIMARY KEY (signature, index)
+);
+CREATE TABLE IF NOT EXISTS stablecoin_burns (
+    signature TEXT NOT NULL,
+    index INTEGER NOT NULL,
+    slot BIGINT NOT NULL,
+    owner TEXT NOT NULL,
+    token_account TEXT NOT NULL,
+    amount NUMERIC NOT NULL,
+    PRIMARY KEY (signature, index)
+);
+CREATE TABLE IF NOT EXISTS stablecoin_transfers (
+    signature TEXT NOT NULL,
+    index INTEGER NOT NULL,
+    slot BIGINT NOT NULL,
+    source TEXT NOT NULL,
+    destination TEXT NOT NULL,
+    authority TEXT NOT NULL,
+    amount NUMERIC NOT NULL,
+    PRIMARY KEY (signature, index)
+);
+CREATE TABLE IF NOT EXISTS stablecoin_admin_actions (
+    signature TEXT NOT NULL,
+    index INTEGER NOT NULL,
+    slot BIGINT NOT NULL,
+    action TEXT NOT NULL,
+    admin TEXT,
+    subject TEXT,
+    PRIMARY KEY (signature, index)
+);
+CREATE TABLE IF NOT EXISTS stablecoin_minter_history (
+    signature TEXT NOT NULL,
+    index INTEGER NOT NULL,
+    slot BIGINT NOT NULL,
+    minter TEXT NOT NULL,
+    change TEXT NOT NULL,
+    allowance NUMERIC,
+    remaining_allowance NUMERIC,
+    PRIMARY KEY (signature, index)
+);
+CREATE INDEX IF NOT EXISTS stablecoin_mints_slot ON stablecoin_mints (slot);
+CREATE INDEX IF NOT EXISTS stablecoin_burns_slot ON stablecoin_burns (slot);
+CREATE INDEX IF NOT EXISTS stablecoin_transfers_slot ON stablecoin_transfers (slot);
+CREATE INDEX IF NOT EXISTS stablecoin_minter_history_minter
+    ON stablecoin_minter_history (minter, slot);
+CREATE INDEX IF NOT EXISTS stablecoin_admin_actions_slot ON stablecoin_admin_actions (slot);
+CREATE TABLE IF NOT EXISTS stablecoin_backfill (
+    address TEXT PRIMARY KEY,
+    from_slot BIGINT NOT NULL,
+    before TEXT,
+    done BOOLEAN NOT NULL
+);
+";
+
+/// Most rows one history request returns
+pub const MAX_ROWS: usize = 1_000;
+
+/// A history row as `(slot, (signature, index), json)`
+pub type HistoryRow = (i64, (String, i32), Value);
+
+/// Where a page of `GET /events` starts: after slot `slot`, or partway
+/// through it, after the row `after`
+///
+/// Written `<slot>` or `<slot>:<signature>:<index>`. A page's `next` is its
+/// last row, so a slot holding more rows than fit a page is read across
+/// pages.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EventCursor {
+    pub slot: u64,
+    pub after: Option<(String, i32)>,
+}
+
+impl EventCursor {
+    pub fn slot(slot: u64) -> Self {
+        Self { slot, after: None }
+    }
+
+    /// Whether the row at `slot`, `signature` and `index` comes after the
+    /// cursor, and so belongs on the page it starts
+    pub fn precedes(&self, slot: u64, signature: &str, index: i32) -> bool {
+        match &self.after {
+            None => slot > self.slot,
+            Some((after, after_index)) => {
+                (slot, signature, index) > (self.slot, after.as_str(), *after_index)
+            }
+        }
+    }
+}
+
+impl FromStr for EventCursor {
+    type Err = String;
+
+    fn from_str(cursor: &str) -> Result<Self, Self::Err> {
+        let invalid = || format!("not a slot, or <slot>:<signature>:<index>: {cursor}");
+        let mut parts = cursor.splitn(3, ':');
+        let slot = parts
+            .next()
+            .unwrap_or_default()
+            .parse()
+            .map_err(|_| invalid())?;
+        let after = match (parts.next(), parts.next()) {
+            (None, _) => None,
+            (Some(signature), Some(index)) if !signature.is_empty() => {
+                Some((signature.to_string(), index.parse().map_err(|_| invalid())?))
+            }
+            _ => return Err(invalid()),
+        };
+        Ok(Self { slot, after })
+    }
+}
+
+impl fmt::Display for EventCursor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.after {
+            None => write!(f, "{}", self.slot),
+            Some((signature, index)) => write!(f, "{}:{signature}:{index}", self.slot),
+        }
+    }
+}
+
+/// The first [`MAX_ROWS`] of `rows`, all after `since`, oldest first, with
+/// the cursor the next page starts at; `since` itself if there are none
+pub fn page(mut rows: Vec<HistoryRow>, since: &EventCursor) -> (Vec<Value>, EventCursor) {
+    rows.sort_by(|a, b| a.0.cmp(&b.0).then_with(|| a.1.cmp(&b.1)));
+    rows.truncate(MAX_ROWS);
+    let next = rows.last().map_or_else(
+        || since.clone(),
+        |(slot, key, _)| EventCursor {
+            slot: *slot as u64,
+            after: Some(key.clone()),
+        },
+    );
+    (rows.into_iter().map(|(.., row)| row).collect(), next)
+}
+
+/// Each history table, by the kind its rows are served as, with the
+/// columns served from it
+const HISTORY: [(&str, &str, &str); 5] = [
+    (
+        "mint",
+        "stablecoin_mints",
+        "minter, destination, amount::TEXT AS amount,
+         remaining_allowance::TEXT AS remaining_allowance",
+    ),
+    (
+        "burn",
+        "stablecoin_burns",
+        "owner, token_account, amount::TEXT AS amount",
+    ),
+    (
+        "transfer",
+        "stablecoin_transfers",
+        "source, destination, authority, amount::TEXT AS amount",
+    ),
+    (
+        "admin_action",
+        "stablecoin_admin_actions",
+        "action, admin, subject",
+    ),
+    (
+        "minter_change",
+        "stablecoin_minter_history",
+        "minter, change, allowance::TEXT AS allowance,
+         remaining_allowance::TEXT AS remaining_allowance",
+    ),
+];
+
+/// Where a holder's wallet or token account appears, by history table
+const HOLDER_COLUMNS: [(&str, &str); 3] = [
+    ("mint", "destination = $1"),
+    ("burn", "owner = $1 OR token_account = $1"),
+    (
+        "transfer",
+        "source = $1 OR destination = $1 OR authority = $1",
+    ),
+];
+
+pub struct Store {
+    client: Client,
+}
+
+impl Store {
+    /// Connect and create the tables if they don't exist
+    pub async fn connect(url: &str) -> Result<Self, tokio_postgres::Error> {
+        let (client, connection) = tokio_postgres::connect(url, NoTls).await?;
+        tokio::spawn(async move {
+            if let Err(err) = connection.await {
+                eprintln!("postgres connection failed: {err}");
+            }
+        });
+        client.batch_execute(SCHEMA).await?;
+        Ok(Self { client })
+    }
+
+    /// Replace everything with `view`
+    pub async fn sync(&mut self, view: &View) -> Result<(), tokio_postgres::Error> {
+        let tx = self.client.transaction().await?;
+        tx.execute("DELETE FROM stablecoin_minters", &[]).await?;
+        write_state(&tx, view).await?;
+        for address in view.minters.keys() {
+            write_minter(&tx, view, address).await?;
+        }
+        tx.commit().await
+    }
+
+    /// Write what `change` touched
+    pub async fn write(&self, view: &View, change: Change) -> Result<(), tokio_postgres::Error> {
+        match change {
+            Change::Config | Change::Mint => write_state(&self.client, view).await,
+            Change::Minter(address) => write_minter(&self.client, view, &address).await,
+            Change::MinterRemoved(address) => self
+                .client
+                .execute(
+                    "DELETE FROM stablecoin_minters WHERE address = $1",
+                    &[&address.to_string()],
+                )
+                .await
+                .map(drop),
+        }
+    }
+
+    /// Append what `update` did to the history tables, all or nothing
+    pub async fn write_transaction(
+        &mut self,
+        update: &TransactionUpdate,
+    ) -> Result<(), tokio_postgres::Error> {
+        let tx = self.client.transaction().await?;
+        let signature = update.signature.to_string();
+        let slot = update.slot as i64;
+        for (index, record) in update.records() {
+            let index = index as i32;
+            match record {
+                Record::Mint {
+                    minter,
+                    destination,
+                    amount,
+                    remaining_allowance,
+                } => {
+                    tx.execute(
+                        "INSERT INTO stablecoin_mints
+                             (signature, index, slot, minter, destination, amount,
+                              remaining_allowance)
+                         VALUES ($1, $2, $3, $4, $5, CAST($6::TEXT AS NUMERIC),
+                             CAST($7::TEXT AS NUMERIC))
+                         ON CONFLICT (signature, index) DO NOTHING",
+                        &[
+                            &signature,
+                            &index,
+                            &slot,
+                            &minter.to_string(),
+                            &destination.to_string(),
+                            &amount.to_string(),
+                            &remaining_allowance.to_string(),
+                        ],
+                    )
+                    .await?
+                }
+                Record::Burn {
+                    owner,
+                    token_account,
+                    amount,
+                } => {
+                    tx.execute(
+                        "INSERT INTO stablecoin_burns
+                             (signature, index, slot, owner, token_account, amount)
+                         VALUES ($1, $2, $3, $4, $5, CAST($6::TEXT AS NUMERIC))
+                         ON CONFLICT (signature, index) DO NOTHING",
+                        &[
+                            &signature,
+                            &index,
+                            &slot,
+                            &owner.to_string(),
+                            &token_account.to_string(),
+                            &amount.to_string(),
+                        ],
+                    )
+                    .await?
+                }
+                Record::Transfer(transfer) => {
+                    tx.execute(
+                        "INSERT INTO stablecoin_transfers
+                             (signature, index, slot, source, destination, authority, amount)
+                         VALUES ($1, $2, $3, $4, $5, $6, CAST($7::TEXT AS NUMERIC))
+                         ON CONFLICT (signature, index) DO NOTHING",
+                        &[
+                            &signature,
+                            &index,
+                            &slot,
+                            &transfer.source.to_string(),
+                            &transfer.destination.to_string(),
+                            &transfer.authority.to_string(),
+                            &transfer.amount.to_string(),
+                        ],
+                    )
+                    .await?
+                }
+                Record::AdminAction {
+                    action,
+                    admin,
+                    subject,
+                } => {
+                    tx.execute(
+                        "INSERT INTO stablecoin_admin_actions
+                             (signature, index, slot, action, admin, subject)
+                         VALUES ($1, $2, $3, $4, $5, $6)
+                         ON CONFLICT (signature, index) DO NOTHING",
+                        &[
+                            &signature,
+                            &index,
+                            &slot,
+                            &action,
+                            &admin.map(|admin| admin.to_string()),
+                            &subject.map(|subject| subject.to_string()),
+                        ],
+                    )
+                    .await?
+                }
+                Record::MinterState {
+                    minter,
+                    change,
+                    allowance,
+                    remaining_allowance,
+                } => {
+                    tx.execute(
+                        "INSERT INTO stablecoin_minter_history
+                             (signature, index, slot, minter, change, allowance,
+                              remaining_allowance)
+                         VALUES ($1, $2, $3, $4, $5, CAST($6::TEXT AS NUMERIC),
+                             CAST($7::TEXT AS NUMERIC))
+                         ON CONFLICT (signature, index) DO NOTHING",
+                        &[
+                            &signature,
+                            &index,
+                            &slot,
+                            &minter.to_string(),
+                            &change.as_str(),
+                            &allowance.map(|allowance| allowance.to_string()),
+                            &remaining_allowance.map(|remaining| remaining.to_string()),
+                        ],
+                    )
+                    .await?
+                }
+            };
+        }
+        tx.commit().await
+    }
+
+    /// The cursor the last backfill of `address` saved
+    pub async fn backfill_cursor(
+        &self,
+        address: &Pubkey,
+    ) -> Result<Option<Cursor>, tokio_postgres::Error> {
+        let row = self
+            .client
+            .query_opt(
+                "SELECT from_slot, before, done FROM stablecoin_backfill WHERE address = $1",
+                &[&address.to_string()],
+            )
+            .await?;
+        Ok(row.map(|row| Cursor {
+            from_slot: row.get::<_, i64>("from_slot") as u64,
+            // Only ever written from a signature
+            before: row
+                .get::<_, Option<String>>("before")
+                .and_then(|before| before.parse().ok()),
+            done: row.get("done"),
+        }))
+    }
+
+    pub async fn save_backfill_cursor(
+        &self,
+        address: &Pubkey,
+        cursor: &Cursor,
+    ) -> Result<(), tokio_postgres::Error> {
+        self.client
+            .execute(
+                "INSERT INTO stablecoin_backfill (address, from_slot, before, done)
+                 VALUES ($1, $2, $3, $4)
+                 ON CONFLICT (address) DO UPDATE SET from_slot = EXCLUDED.from_slot,
+                     before = EXCLUDED.before, done = EXCLUDED.done",
+                &[
+                    &address.to_string(),
+                    &(cursor.from_slot as i64),
+                    &cursor.before.map(|before| before.to_string()),
+                    &cursor.done,
+                ],
+            )
+            .await
+            .map(drop)
+    }
+
+    /// The mints, burns and transfers naming `address`, as a wallet or a
+    /// token account, newest first, for `GET /holders/<address>/history`
+    pub async fn holder_history(&self, address: &Pubkey) -> Result<Value, tokio_postgres::Error> {
+        let address = address.to_string();
+        let mut rows = Vec::new();
+        for (kind, filter) in HOLDER_COLUMNS {
+            let (_, table, columns) = HISTORY
+                .iter()
+                .find(|(history, ..)| *history == kind)
+                .expect("every holder kind has a table");
+            let sql = format!(
+                "SELECT signature, index, slot, {columns} FROM {table} WHERE {filter}
+                 ORDER BY slot DESC, signature, index LIMIT {MAX_ROWS}"
+            );
+            rows.extend(history_rows(&self.client, kind, &sql, &[&address]).await?);
+        }
+        rows.sort_by(|a, b| b.0.cmp(&a.0).then_with(|| a.1.cmp(&b.1)));
+        rows.truncate(MAX_ROWS);
+        Ok(json!({
+            "address": address,
+            "history": rows.into_iter().map(|(.., row)| row).collect::<Vec<_>>(),
+        }))
+    }
+
+    /// Everything recorded after `since`, oldest first, for
+    /// `GET /events?since=<cursor>`; `next` is the `since` of the next page
+    pub async fn events_since(&self, since: &EventCursor) -> Result<Value, tokio_postgres::Error> {
+        // Signatures compared byte by byte, as `page` sorts them
+        let slot = since.slot as i64;
+        let mut rows = Vec::new();
+        for (kind, table, columns) in HISTORY {
+            let rows_after = match &since.after {
+                None => {
+                    let sql = format!(
+                        "SELECT signature, index, slot, {columns} FROM {table} WHERE slot > $1
+                         ORDER BY slot, signature COLLATE \"C\", index LIMIT {MAX_ROWS}"
+                    );
+                    history_rows(&self.client, kind, &sql, &[&slot]).await?
+                }
+                Some((signature, index)) => {
+                    let sql = format!(
+                        "SELECT signature, index, slot, {columns} FROM {table}
+                         WHERE (slot, signature COLLATE \"C\", index) > ($1, $2, $3)
+                         ORDER BY slot, signature COLLATE \"C\", index LIMIT {MAX_ROWS}"
+                    );
+                    history_rows(&self.client, kind, &sql, &[&slot, signature, index]).await?
+                }
+            };
+            rows.extend(rows_after);
+        }
+        let (events, next) = page(rows, since);
+        Ok(json!({
+            "since": since.to_string(),
+            "next": next.to_string(),
+            "events": events,
+        }))
+    }
+}
+
+/// Rows of one history table, every column after the key read as text
+async fn history_rows(
+    client: &Client,
+    kind: &str,
+    sql: &str,
+    params: &[&(dyn ToSql + Sync)],
+) -> Result<Vec<HistoryRow>, tokio_postgres::Error> {
+    let rows = client.query(sql, params).await?;
+    Ok(rows.iter().map(|row| history_row(kind, row)).collect())
+}
+
+fn history_row(kind: &str, row: &Row) -> HistoryRow {
+    let signature: String = row.get("signature");
+    let index: i32 = row.get("index");
+    let slot: i64 = row.get("slot");
+    let mut value = Map::new();
+    value.insert("kind".into(), kind.into());
+    value.insert("signature".into(), signature.clone().into());
+    value.insert("index".into(), index.into());
+    value.insert("slot".into(), slot.into());
+    for (position, column) in row.columns().iter().enumerate().skip(3) {
+        let field: Option<String> = row.get(position);
+        value.insert(column.name().into(), field.into());
+    }
+    (slot, (signature, index), Value::Object(value))
+}
+
+async fn write_state(
+    client: &impl tokio_postgres::GenericClient,
+    view: &View,
+) -> Result<(), tokio_postgres::Error> {
+    let config = view.config.as_ref();
+    client
+        .execute(
+            "INSERT INTO stablecoin_state (slot, admin, paused, supply, decimals)
+             VALUES ($1, $2, $3, CAST($4::TEXT AS NUMERIC), $5)
+             ON CONFLICT (id) DO UPDATE SET slot = EXCLUDED.slot, admin = EXCLUDED.admin,
+                 paused = EXCLUDED.paused, supply = EXCLUDED.supply,
+                 decimals = EXCLUDED.decimals",
+            &[
+                &(view.slot as i64),
+                &config.map(|config| config.admin.to_string()),
+                &config.map(|config| config.paused),
+                &view.mint.map(|mint| mint.supply.to_string()),
+                &view.mint.map(|mint| i16::from(mint.decimals)),
+            ],
+        )
+        .await
+        .map(drop)
+}
+
+async fn write_minter(
+    client: &impl tokio_postgres::GenericClient,
+    view: &View,
+    address: &Pubkey,
+) -> Result<(), tokio_postgres::Error> {
+    let Some(minter) = view.minters.get(address) else {
+        return Ok(());
+    };
+    client
+        .execute(
+            "INSERT INTO stablecoin_minters (address, minter, allowance, amount_minted, slot)
+             VALUES ($1, $2, CAST($3::TEXT AS NUMERIC), CAST($4::TEXT AS NUMERIC), $5)
+             ON CONFLICT (address) DO UPDATE SET minter = EXCLUDED.minter,
+                 allowance = EXCLUDED.allowance, amount_minted = EXCLUDED.amount_minted,
+                 slot = EXCLUDED.slot",
+            &[
+                &address.to_string(),
+                &minter.config.minter.to_string(),
+                &minter.config.allowance.to_string(),
+                &minter.config.amount_minted.to_string(),
+                &(view.slot as i64),
+            ],
+        )
+        .await
+        .map(drop)
+}
@@ -0,0 +1,324 @@
+//! The program's history from the Yellowstone transaction stream
+//!
+//! [`with_transactions`] adds the transactions naming the program or the
+//! mint to a subscription. [`TransactionUpdate`] decodes one: the events
+//! the program emitted, from its logs, and the transfers of the stablecoin
+//! SPL Token ran, at the top level or by CPI. [`TransactionUpdate::records`]
+//! normalizes both into the rows of the history tables.
+//!
+//! A plain `Transfer` between two holders names neither the program nor
+//! the mint, so the stream only carries the ones sent as `TransferChecked`,
+//! which names the mint, or made inside one of the program's instructions.
+
+use anchor_lang::prelude::Pubkey;
+use anchor_spl::token::spl_token;
+use solana_signature::Signature;
+use stablecoin_client::{
+    events::{parse_logs, StablecoinEvent},
+    pda, PROGRAM_ID,
+};
+use yellowstone_grpc_proto::prelude::{
+    SubscribeRequest, SubscribeRequestFilterTransactions, SubscribeUpdateTransaction, TokenBalance,
+};
+
+use crate::{ViewError, FILTER};
+
+/// SPL Token instruction tags
+const TRANSFER: u8 = 3;
+const TRANSFER_CHECKED: u8 = 12;
+
+/// `request`, also asking for every successful transaction that names the
+/// program or the mint
+pub fn with_transactions(mut request: SubscribeRequest) -> SubscribeRequest {
+    request.transactions.insert(
+        FILTER.to_string(),
+        SubscribeRequestFilterTransactions {
+            vote: Some(false),
+            failed: Some(false),
+            account_include: vec![PROGRAM_ID.to_string(), pda::mint().0.to_string()],
+            ..SubscribeRequestFilterTransactions::default()
+        },
+    );
+    request
+}
+
+/// A transfer of the stablecoin between two token accounts
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Transfer {
+    pub source: Pubkey,
+    pub destination: Pubkey,
+    /// The source's owner or delegate, who signed the transfer
+    pub authority: Pubkey,
+    pub amount: u64,
+}
+
+/// What one successful transaction did to the stablecoin
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TransactionUpdate {
+    pub slot: u64,
+    pub signature: Signature,
+    /// The program's events, in the order it emitted them
+    pub events: Vec<StablecoinEvent>,
+    /// Transfers of the stablecoin, in the order they ran
+    pub transfers: Vec<Transfer>,
+}
+
+impl TryFrom<SubscribeUpdateTransaction> for TransactionUpdate {
+    type Error = ViewError;
+
+    fn try_from(update: SubscribeUpdateTransaction) -> Result<Self, ViewError> {
+        let info = update.transaction.ok_or(ViewError::MissingTransaction)?;
+        let signature =
+            Signature::try_from(&info.signature[..]).map_err(|_| ViewError::InvalidSignature)?;
+        let message = info
+            .transaction
+            .and_then(|transaction| transaction.message)
+            .ok_or(ViewError::MissingTransaction)?;
+        let meta = info.meta.ok_or(ViewError::MissingTransaction)?;
+
+        let mut update = Self {
+            slot: update.slot,
+            signature,
+            events: Vec::new(),
+            transfers: Vec::new(),
+        };
+        // Its events and transfers were rolled back
+        if meta.err.is_some() {
+            return Ok(update);
+        }
+        update.events = parse_logs(&PROGRAM_ID, &meta.log_messages)?;
+
+        // Lookup table addresses follow the static keys, writable first
+        let keys = message
+            .account_keys
+            .iter()
+            .chain(&meta.loaded_writable_addresses)
+            .chain(&meta.loaded_readonly_addresses)
+            .map(|key| Pubkey::try_from(&key[..]).map_err(|_| ViewError::InvalidAddress))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        // Each top-level instruction, then the ones it invoked
+        for (index, instruction) in message.instructions.iter().enumerate() {
+            let inner = meta
+                .inner_instructions
+                .iter()
+                .filter(|inner| inner.index as usize == index)
+                .flat_map(|inner| &inner.instructions)
+                .map(|inner| (inner.program_id_index, &inner.accounts, &inner.data));
+            for (program, accounts, data) in std::iter::once((
+                instruction.program_id_index,
+                &instruction.accounts,
+                &instruction.data,
+            ))
+            .chain(inner)
+            {
+                if keys.get(program as usize) != Some(&spl_token::ID) {
+                    continue;
+                }
+                if let Some(transfer) = transfer(&keys, accounts, data, &meta.pre_token_balances) {
+                    update.transfers.push(transfer);
+                }
+            }
+        }
+        Ok(update)
+    }
+}
+
+/// The stablecoin transfer an SPL Token instruction makes, if it is one
+///
+/// `TransferChecked` names its mint; a plain `Transfer` does not, so its
+/// source's mint is looked up in the balances the node reports.
+fn transfer(
+    keys: &[Pubkey],
+    accounts: &[u8],
+    data: &[u8],
+    balances: &[TokenBalance],
+) -> Option<Transfer> {
+    let key = |position: usize| keys.get(usize::from(*accounts.get(position)?)).copied();
+    let amount = u64::from_le_bytes(data.get(1..9)?.try_into().ok()?);
+    let mint = pda::mint().0;
+    let (source, destination, authority) = match *data.first()? {
+        TRANSFER => {
+            let source = *accounts.first()?;
+            let holds_mint = balances.iter().any(|balance| {
+                balance.account_index == u32::from(source) && balance.mint == mint.to_string()
+            });
+            if !holds_mint {
+                return None;
+            }
+            (key(0)?, key(1)?, key(2)?)
+        }
+        TRANSFER_CHECKED if key(1)? == mint => (key(0)?, key(2)?, key(3)?),
+        _ => return None,
+    };
+    Some(Transfer {
+        source,
+        destination,
+        authority,
+        amount,
+    })
+}
+
+/// One row of the history tables
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Record {
+    Mint {
+        minter: Pubkey,
+        destination: Pubkey,
+        amount: u64,
+        remaining_allowance: u64,
+    },
+    Burn {
+        owner: Pubkey,
+        token_account: Pubkey,
+        amount: u64,
+    },
+    Transfer(Transfer),
+    /// An event only the admin, or a minter about its own key, can cause;
+    /// `admin` is `None` where the event does not name them
+    AdminAction {
+        action: &'static str,
+        admin: Option<Pubkey>,
+        /// The minter or account acted on
+        subject: Option<Pubkey>,
+    },
+    /// A change to a minter's allowance or status
+    MinterState {
+        minter: Pubkey,
+        change: MinterChange,
+        /// Set where the change sets it
+        allowance: Option<u64>,
+        remaining_allowance: Option<u64>,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MinterChange {
+    Configured,
+    Minted,
+    Paused,
+    Resumed,
+    Removed,
+}
+
+impl MinterChange {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Configured => "configured",
+            Self::Minted => "minted",
+            Self::Paused => "paused",
+            Self::Resumed => "resumed",
+            Self::Removed => "removed",
+        }
+    }
+}
+
+impl TransactionUpdate {
+    /// The rows this transaction adds, each with its index in the
+    /// transaction
+    ///
+    /// Rows from events take the event's index among the transaction's
+    /// events, so one event's rows share it across tables; transfers
+    /// take their index among its transfers. Together with the signature
+    /// that keys every row, so writing a transaction twice adds nothing.
+    pub fn records(&self) -> Vec<(u32, Record)> {
+        let mut records = Vec::new();
+        for (index, event) in self.events.iter().enumerate() {
+            let index = index as u32;
+            let mut push = |record| records.push((index, record));
+            let admin_action = |admin, subject| Record::AdminAction {
+                action: event.name(),
+                admin,
+                subject,
+            };
+            match event {
+                StablecoinEvent::TokensMinted(event) => {
+                    push(Record::Mint {
+                        minter: event.minter,
+                        destination: event.destination,
+                        amount: event.amount,
+                        remaining_allowance: event.remaining_allowance,
+                    });
+                    push(Record::MinterState {
+                        minter: event.minter,
+                        change: MinterChange::Minted,
+                        allowance: None,
+                        remaining_allowance: Some(event.remaining_allowance),
+                    });
+                }
+                StablecoinEvent::TokensBurned(event) => push(Record::Burn {
+                    owner: event.owner,
+                    token_account: event.token_account,
+                    amount: event.amount,
+                }),
+                StablecoinEvent::Initialized(event) => {
+                    push(admin_action(Some(event.admin), Some(event.mint)))
+                }
+                StablecoinEvent::MinterConfigured(event) => {
+                    push(admin_action(None, Some(event.minter)));
+                    push(Record::MinterState {
+                        minter: event.minter,
+                        change: MinterChange::Configured,
+                        allowance: Some(event.allowance),
+                        remaining_allowance: None,
+                    });
+                }
+                StablecoinEvent::MinterRemoved(event) => {
+                    push(admin_action(None, Some(event.minter)));
+                    push(minter_state(event.minter, MinterChange::Removed));
+                }
+                StablecoinEvent::MinterSelfPaused(event) => {
+                    push(admin_action(None, Some(event.minter)));
+                    push(minter_state(event.minter, MinterChange::Paused));
+                }
+                StablecoinEvent::MinterResumed(event) => {
+                    push(admin_action(Some(event.admin), Some(event.minter)));
+                    push(minter_state(event.minter, MinterChange::Resumed));
+                }
+                StablecoinEvent::Paused(event) => push(admin_action(Some(event.admin), None)),
+                StablecoinEvent::Unpaused(event) => push(admin_action(Some(event.admin), None)),
+                StablecoinEvent::MinterSetFrozen(event) => {
+                    push(admin_action(Some(event.admin), None))
+                }
+                StablecoinEvent::MinterSetUnfrozen(event) => {
+                    push(admin_action(Some(event.admin), None))
+                }
+                StablecoinEvent::TreasuryConfigured(event) => {
+                    push(admin_action(Some(event.admin), None))
+                }
+                // Officers act for the treasury, as the admin does for the
+                // minters
+                StablecoinEvent::TreasuryBurnProposed(event) => {
+                    push(admin_action(Some(event.proposer), None))
+                }
+                StablecoinEvent::TreasuryBurnApproved(event) => {
+                    push(admin_action(Some(event.officer), None))
+                }
+                StablecoinEvent::TreasuryBurnCancelled(event) => {
+                    push(admin_action(Some(event.officer), None))
+                }
+                StablecoinEvent::AccountResized(event) => {
+                    push(admin_action(Some(event.admin), Some(event.account)))
+                }
+                // Holder-side features, with their own accounts to read
+                _ => {}
+            }
+        }
+        records.extend(
+            self.transfers
+                .iter()
+                .enumerate()
+                .map(|(index, transfer)| (index as u32, Record::Transfer(*transfer))),
+        );
+        records
+    }
+}
+
+fn minter_state(minter: Pubkey, change: MinterChange) -> Record {
+    Record::MinterState {
+        minter,
+        change,
+        allowance: None,
+        remaining_allowance: None,
+    }
+}
@@ -0,0 +1,203 @@
+use anchor_lang::{prelude::Pubkey, Event};
+use anchor_spl::token::spl_token;
+use base64::{engine::general_purpose::STANDARD, Engine as _};
+use serde_json::json;
+use solana_message::{
+    compiled_instruction::CompiledInstruction, v0, MessageHeader, VersionedMessage,
+};
+use solana_signature::Signature;
+use solana_transaction::versioned::VersionedTransaction;
+use solana_transaction_status_client_types::EncodedConfirmedTransactionWithStatusMeta;
+use stablecoin::TokensMinted;
+use stablecoin_client::{events::StablecoinEvent, pda, PROGRAM_ID};
+use stablecoin_geyser::{
+    backfill::{addresses, from_rpc, Cursor},
+    transactions::{TransactionUpdate, Transfer},
+};
+
+fn signature(byte: u8) -> Signature {
+    Signature::from([byte; 64])
+}
+
+/// A v0 transaction paying from `account_keys[0]`, in base64 as RPC returns
+/// it
+fn encode(
+    account_keys: Vec<Pubkey>,
+    instructions: Vec<CompiledInstruction>,
+    address_table_lookups: Vec<v0::MessageAddressTableLookup>,
+) -> String {
+    let transaction = VersionedTransaction {
+        signatures: vec![Signature::default()],
+        message: VersionedMessage::V0(v0::Message {
+            header: MessageHeader {
+                num_required_signatures: 1,
+                num_readonly_signed_accounts: 0,
+                num_readonly_unsigned_accounts: 0,
+            },
+            account_keys,
+            recent_blockhash: Default::default(),
+            instructions,
+            address_table_lookups,
+        }),
+    };
+    STANDARD.encode(bincode::serialize(&transaction).unwrap())
+}
+
+#[test]
+fn test_addresses_match_the_stream() {
+    assert_eq!(addresses(), [PROGRAM_ID, pda::mint().0]);
+}
+
+#[test]
+fn test_cursor_resume() {
+    // A first backfill starts at the newest transaction
+    assert_eq!(
+        Cursor::resume(None, 100),
+        Cursor {
+            from_slot: 100,
+            before: None,
+            done: false,
+        }
+    );
+
+    // An interrupted one carries on from where it stopped
+    let interrupted = Cursor {
+        from_slot: 100,
+        before: Some(signature(1)),
+        done: false,
+    };
+    assert_eq!(Cursor::resume(Some(interrupted), 100), interrupted);
+
+    // A finished one is done for any slot it already reached
+    let finished = Cursor {
+        done: true,
+        ..interrupted
+    };
+    assert_eq!(Cursor::resume(Some(finished), 100), finished);
+    assert_eq!(Cursor::resume(Some(finished), 150), finished);
+
+    // Reaching further back goes on from its last signature
+    assert_eq!(
+        Cursor::resume(Some(finished), 50),
+        Cursor {
+            from_slot: 50,
+            before: Some(signature(1)),
+            done: false,
+        }
+    );
+}
+
+#[test]
+fn test_from_rpc_reads_as_the_stream() {
+    let (payer, source, destination) = (
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+    );
+    let minted = TokensMinted {
+        minter: payer,
+        destination: source,
+        amount: 1_000,
+        remaining_allowance: 0,
+    };
+    // The destination comes from a lookup table, after the static keys
+    let transaction = encode(
+        vec![payer, source, spl_token::ID, PROGRAM_ID],
+        vec![CompiledInstruction::new_from_raw_parts(
+            3,
+            vec![1],
+            vec![0, 1, 4, 2],
+        )],
+        vec![v0::MessageAddressTableLookup {
+            account_key: Pubkey::new_unique(),
+            writable_indexes: vec![0],
+            readonly_indexes: vec![],
+        }],
+    );
+    let transfer = [&[3][..], &400u64.to_le_bytes()].concat();
+    let rpc: EncodedConfirmedTransactionWithStatusMeta = serde_json::from_value(json!({
+        "slot": 42,
+        "blockTime": 1_700_000_000,
+        "version": 0,
+        "transaction": [
+            transaction,
+            "base64"
+        ],
+        "meta": {
+            "err": null,
+            "status": { "Ok": null },
+            "fee": 5_000,
+            "preBalances": [],
+            "postBalances": [],
+            "innerInstructions": [{
+                "index": 0,
+                "instructions": [{
+                    "programIdIndex": 2,
+                    "accounts": [1, 4, 0],
+                    "data": bs58::encode(&transfer).into_string(),
+                    "stackHeight": 2
+                }]
+            }],
+            "logMessages": [
+                format!("Program {PROGRAM_ID} invoke [1]"),
+                format!("Program data: {}", STANDARD.encode(minted.data())),
+                format!("Program {PROGRAM_ID} success"),
+            ],
+            "preTokenBalances": [{
+                "accountIndex": 1,
+                "mint": pda::mint().0.to_string(),
+                "uiTokenAmount": {
+                    "uiAmount": 0.001,
+                    "decimals": 6,
+                    "amount": "1000",
+                    "uiAmountString": "0.001"
+                }
+            }],
+            "postTokenBalances": [],
+            "loadedAddresses": {
+                "writable": [destination.to_string()],
+                "readonly": []
+            }
+        }
+    }))
+    .unwrap();
+
+    let update = TransactionUpdate::try_from(from_rpc(&signature(7), rpc).unwrap()).unwrap();
+    assert_eq!(update.slot, 42);
+    assert_eq!(update.signature, signature(7));
+    assert_eq!(update.events, [StablecoinEvent::TokensMinted(minted)]);
+    assert_eq!(
+        update.transfers,
+        [Transfer {
+            source,
+            destination,
+            authority: payer,
+            amount: 400,
+        }]
+    );
+}
+
+#[test]
+fn test_from_rpc_keeps_failure() {
+    let transaction = encode(vec![Pubkey::new_unique()], vec![], vec![]);
+    let rpc: EncodedConfirmedTransactionWithStatusMeta = serde_json::from_value(json!({
+        "slot": 42,
+        "blockTime": null,
+        "transaction": [
+            transaction,
+            "base64"
+        ],
+        "meta": {
+            "err": { "InstructionError": [0, { "Custom": 6000 }] },
+            "status": { "Err": { "InstructionError": [0, { "Custom": 6000 }] } },
+            "fee": 5_000,
+            "preBalances": [],
+            "postBalances": [],
+            "logMessages": []
+        }
+    }))
+    .unwrap();
+
+    let update = TransactionUpdate::try_from(from_rpc(&signature(7), rpc).unwrap()).unwrap();
+    assert!(update.records().is_empty());
+}
@@ -0,0 +1,81 @@
+#![cfg(feature = "postgres")]
+
+use serde_json::json;
+use stablecoin_geyser::postgres::{page, EventCursor, HistoryRow, MAX_ROWS};
+
+fn row(slot: i64, signature: String, index: i32) -> HistoryRow {
+    let value = json!({ "slot": slot, "signature": signature, "index": index });
+    (slot, (signature, index), value)
+}
+
+/// What `events_since` reads from each table: its first [`MAX_ROWS`] rows
+/// after `since`
+fn query(tables: &[Vec<HistoryRow>], since: &EventCursor) -> Vec<HistoryRow> {
+    tables
+        .iter()
+        .flat_map(|table| {
+            let mut rows: Vec<_> = table
+                .iter()
+                .filter(|(slot, (signature, index), _)| {
+                    since.precedes(*slot as u64, signature, *index)
+                })
+                .cloned()
+                .collect();
+            rows.sort_by(|a, b| a.0.cmp(&b.0).then_with(|| a.1.cmp(&b.1)));
+            rows.truncate(MAX_ROWS);
+            rows
+        })
+        .collect()
+}
+
+#[test]
+fn test_events_page_through_a_crowded_slot() {
+    // Slot 10 alone holds more than two pages, split across two tables
+    let crowded = 2 * MAX_ROWS + 500;
+    let mut mints = Vec::new();
+    let mut burns = Vec::new();
+    for n in 0..crowded {
+        let table = if n % 3 == 0 { &mut burns } else { &mut mints };
+        table.push(row(10, format!("sig{n:05}"), 0));
+    }
+    mints.push(row(5, "before".into(), 0));
+    burns.push(row(11, "after".into(), 1));
+    let tables = [mints, burns];
+
+    let mut since = EventCursor::slot(5);
+    let mut seen = Vec::new();
+    loop {
+        let (events, next) = page(query(&tables, &since), &since);
+        assert!(events.len() <= MAX_ROWS);
+        if events.is_empty() {
+            assert_eq!(next, since);
+            break;
+        }
+        // The cursor survives the query string
+        since = next.to_string().parse().unwrap();
+        seen.extend(events);
+    }
+
+    let mut expected: Vec<_> = (0..crowded)
+        .map(|n| json!({ "slot": 10, "signature": format!("sig{n:05}"), "index": 0 }))
+        .collect();
+    expected.push(json!({ "slot": 11, "signature": "after", "index": 1 }));
+    assert_eq!(seen, expected);
+}
+
+#[test]
+fn test_event_cursor_parse() {
+    assert_eq!("42".parse(), Ok(EventCursor::slot(42)));
+    let cursor: EventCursor = "42:abc:3".parse().unwrap();
+    assert_eq!(
+        cursor,
+        EventCursor {
+            slot: 42,
+            after: Some(("abc".into(), 3)),
+        }
+    );
+    assert_eq!(cursor.to_string(), "42:abc:3");
+    for invalid in ["", "x", "42:", "42:abc", "42::3", "42:abc:x"] {
+        assert!(invalid.parse::<EventCursor>().is_err(), "{invalid}");
+    }
+}
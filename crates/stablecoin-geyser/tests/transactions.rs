@@ -0,0 +1,305 @@
+use anchor_lang::{prelude::Pubkey, Event};
+use anchor_spl::token::spl_token;
+use base64::{engine::general_purpose::STANDARD, Engine as _};
+use solana_signature::Signature;
+use stablecoin::{MinterRemoved, TokensBurned, TokensMinted};
+use stablecoin_client::{events::StablecoinEvent, pda, PROGRAM_ID};
+use stablecoin_geyser::{
+    subscribe_request,
+    transactions::{with_transactions, MinterChange, Record, TransactionUpdate, Transfer},
+};
+use yellowstone_grpc_proto::prelude::{
+    CompiledInstruction, InnerInstruction, InnerInstructions, Message, SubscribeUpdateTransaction,
+    SubscribeUpdateTransactionInfo, TokenBalance, Transaction, TransactionError,
+    TransactionStatusMeta,
+};
+
+const SIGNATURE: [u8; 64] = [9; 64];
+
+/// Static keys of the test transactions, by index
+struct Keys {
+    payer: Pubkey,
+    source: Pubkey,
+    destination: Pubkey,
+}
+
+impl Keys {
+    const PAYER: u8 = 0;
+    const SOURCE: u8 = 1;
+    const DESTINATION: u8 = 2;
+    const MINT: u8 = 3;
+    const TOKEN_PROGRAM: u8 = 4;
+    const PROGRAM: u8 = 5;
+
+    fn new() -> Self {
+        Self {
+            payer: Pubkey::new_unique(),
+            source: Pubkey::new_unique(),
+            destination: Pubkey::new_unique(),
+        }
+    }
+
+    fn all(&self) -> Vec<Vec<u8>> {
+        [
+            self.payer,
+            self.source,
+            self.destination,
+            pda::mint().0,
+            spl_token::ID,
+            PROGRAM_ID,
+        ]
+        .iter()
+        .map(|key| key.to_bytes().to_vec())
+        .collect()
+    }
+}
+
+fn data_log(event: &impl Event) -> String {
+    format!("Program data: {}", STANDARD.encode(event.data()))
+}
+
+fn transfer_checked(amount: u64) -> Vec<u8> {
+    [&[12][..], &amount.to_le_bytes(), &[6]].concat()
+}
+
+fn transaction(
+    keys: &Keys,
+    instructions: Vec<CompiledInstruction>,
+    meta: TransactionStatusMeta,
+) -> SubscribeUpdateTransaction {
+    SubscribeUpdateTransaction {
+        transaction: Some(SubscribeUpdateTransactionInfo {
+            signature: SIGNATURE.to_vec(),
+            transaction: Some(Transaction {
+                signatures: vec![SIGNATURE.to_vec()],
+                message: Some(Message {
+                    account_keys: keys.all(),
+                    instructions,
+                    ..Message::default()
+                }),
+            }),
+            meta: Some(meta),
+            ..SubscribeUpdateTransactionInfo::default()
+        }),
+        slot: 42,
+        ..SubscribeUpdateTransaction::default()
+    }
+}
+
+#[test]
+fn test_with_transactions() {
+    let request = with_transactions(subscribe_request(Default::default(), []));
+    let filter = &request.transactions["stablecoin"];
+    assert_eq!(filter.vote, Some(false));
+    assert_eq!(filter.failed, Some(false));
+    assert_eq!(
+        filter.account_include,
+        [PROGRAM_ID.to_string(), pda::mint().0.to_string()]
+    );
+    // The accounts filter is left as it was
+    assert_eq!(request.accounts.len(), 1);
+}
+
+#[test]
+fn test_events_and_transfers() {
+    let keys = Keys::new();
+    let minter = Pubkey::new_unique();
+    let minted = TokensMinted {
+        minter,
+        destination: keys.destination,
+        amount: 500,
+        remaining_allowance: 1_500,
+    };
+    let removed = MinterRemoved { minter };
+    let update = TransactionUpdate::try_from(transaction(
+        &keys,
+        vec![
+            CompiledInstruction {
+                program_id_index: Keys::PROGRAM.into(),
+                ..CompiledInstruction::default()
+            },
+            CompiledInstruction {
+                program_id_index: Keys::TOKEN_PROGRAM.into(),
+                accounts: vec![Keys::SOURCE, Keys::MINT, Keys::DESTINATION, Keys::PAYER],
+                data: transfer_checked(250),
+            },
+        ],
+        TransactionStatusMeta {
+            log_messages: vec![
+                format!("Program {PROGRAM_ID} invoke [1]"),
+                data_log(&minted),
+                data_log(&removed),
+                format!("Program {PROGRAM_ID} success"),
+            ],
+            ..TransactionStatusMeta::default()
+        },
+    ))
+    .unwrap();
+
+    assert_eq!(update.slot, 42);
+    assert_eq!(update.signature, Signature::from(SIGNATURE));
+    assert_eq!(
+        update.events,
+        [
+            StablecoinEvent::TokensMinted(minted),
+            StablecoinEvent::MinterRemoved(removed)
+        ]
+    );
+    let transfer = Transfer {
+        source: keys.source,
+        destination: keys.destination,
+        authority: keys.payer,
+        amount: 250,
+    };
+    assert_eq!(update.transfers, [transfer]);
+
+    assert_eq!(
+        update.records(),
+        [
+            (
+                0,
+                Record::Mint {
+                    minter,
+                    destination: keys.destination,
+                    amount: 500,
+                    remaining_allowance: 1_500,
+                }
+            ),
+            (
+                0,
+                Record::MinterState {
+                    minter,
+                    change: MinterChange::Minted,
+                    allowance: None,
+                    remaining_allowance: Some(1_500),
+                }
+            ),
+            (
+                1,
+                Record::AdminAction {
+                    action: "MinterRemoved",
+                    admin: None,
+                    subject: Some(minter),
+                }
+            ),
+            (
+                1,
+                Record::MinterState {
+                    minter,
+                    change: MinterChange::Removed,
+                    allowance: None,
+                    remaining_allowance: None,
+                }
+            ),
+            (0, Record::Transfer(transfer)),
+        ]
+    );
+}
+
+#[test]
+fn test_inner_transfer_is_matched_by_balance() {
+    let keys = Keys::new();
+    let transfer = [&[3][..], &700u64.to_le_bytes()].concat();
+    let inner = |source| InnerInstruction {
+        program_id_index: Keys::TOKEN_PROGRAM.into(),
+        accounts: vec![source, Keys::DESTINATION, Keys::PAYER],
+        data: transfer.clone(),
+        stack_height: Some(2),
+    };
+    let update = TransactionUpdate::try_from(transaction(
+        &keys,
+        vec![CompiledInstruction {
+            program_id_index: Keys::PROGRAM.into(),
+            ..CompiledInstruction::default()
+        }],
+        TransactionStatusMeta {
+            inner_instructions: vec![InnerInstructions {
+                index: 0,
+                instructions: vec![inner(Keys::SOURCE), inner(Keys::PAYER)],
+            }],
+            // The payer's account holds some other token
+            pre_token_balances: vec![
+                TokenBalance {
+                    account_index: Keys::SOURCE.into(),
+                    mint: pda::mint().0.to_string(),
+                    ..TokenBalance::default()
+                },
+                TokenBalance {
+                    account_index: Keys::PAYER.into(),
+                    mint: Pubkey::new_unique().to_string(),
+                    ..TokenBalance::default()
+                },
+            ],
+            ..TransactionStatusMeta::default()
+        },
+    ))
+    .unwrap();
+
+    assert!(update.events.is_empty());
+    assert_eq!(
+        update.transfers,
+        [Transfer {
+            source: keys.source,
+            destination: keys.destination,
+            authority: keys.payer,
+            amount: 700,
+        }]
+    );
+}
+
+#[test]
+fn test_other_mint_transfer_checked_is_skipped() {
+    let keys = Keys::new();
+    let update = TransactionUpdate::try_from(transaction(
+        &keys,
+        vec![CompiledInstruction {
+            program_id_index: Keys::TOKEN_PROGRAM.into(),
+            // Names the payer where the mint goes
+            accounts: vec![Keys::SOURCE, Keys::PAYER, Keys::DESTINATION, Keys::PAYER],
+            data: transfer_checked(250),
+        }],
+        TransactionStatusMeta::default(),
+    ))
+    .unwrap();
+    assert!(update.transfers.is_empty());
+}
+
+#[test]
+fn test_failed_transaction_has_no_records() {
+    let keys = Keys::new();
+    let burned = TokensBurned {
+        owner: keys.payer,
+        token_account: keys.source,
+        amount: 100,
+    };
+    let update = TransactionUpdate::try_from(transaction(
+        &keys,
+        vec![CompiledInstruction {
+            program_id_index: Keys::TOKEN_PROGRAM.into(),
+            accounts: vec![Keys::SOURCE, Keys::MINT, Keys::DESTINATION, Keys::PAYER],
+            data: transfer_checked(250),
+        }],
+        TransactionStatusMeta {
+            err: Some(TransactionError { err: vec![1] }),
+            log_messages: vec![
+                format!("Program {PROGRAM_ID} invoke [1]"),
+                data_log(&burned),
+                format!("Program {PROGRAM_ID} failed: custom program error: 0x1770"),
+            ],
+            ..TransactionStatusMeta::default()
+        },
+    ))
+    .unwrap();
+    assert_eq!(update.signature, Signature::from(SIGNATURE));
+    assert!(update.records().is_empty());
+}
+
+#[test]
+fn test_transaction_update_rejects_malformed() {
+    assert!(TransactionUpdate::try_from(SubscribeUpdateTransaction::default()).is_err());
+
+    let keys = Keys::new();
+    let mut update = transaction(&keys, vec![], TransactionStatusMeta::default());
+    update.transaction.as_mut().unwrap().signature = vec![1; 10];
+    assert!(TransactionUpdate::try_from(update).is_err());
+}
@@ -0,0 +1,213 @@
+use anchor_lang::{prelude::Pubkey, solana_program::program_pack::Pack, AccountSerialize};
+use anchor_spl::token::spl_token::state::Mint;
+use serde_json::json;
+use solana_commitment_config::CommitmentConfig;
+use stablecoin::{Config, MinterConfig};
+use stablecoin_client::{pda, PROGRAM_ID};
+use stablecoin_geyser::{subscribe_request, AccountUpdate, Change, View, ViewError};
+use yellowstone_grpc_proto::prelude::{SubscribeUpdateAccount, SubscribeUpdateAccountInfo};
+
+fn serialize(account: &impl AccountSerialize) -> Vec<u8> {
+    let mut data = Vec::new();
+    account.try_serialize(&mut data).unwrap();
+    data
+}
+
+fn update(address: Pubkey, owner: Pubkey, data: Vec<u8>, slot: u64) -> AccountUpdate {
+    AccountUpdate {
+        address,
+        owner,
+        lamports: 1_000_000,
+        data,
+        slot,
+    }
+}
+
+fn config_update(paused: bool, slot: u64) -> AccountUpdate {
+    let config = Config {
+        admin: Pubkey::new_from_array([7; 32]),
+        mint: pda::mint().0,
+        paused,
+        bump: 255,
+        mint_bump: 254,
+        delegate_bump: 254,
+        minter_set_freeze_bump: 254,
+    };
+    update(pda::config().0, PROGRAM_ID, serialize(&config), slot)
+}
+
+fn mint_update(supply: u64, slot: u64) -> AccountUpdate {
+    let mut data = vec![0; Mint::LEN];
+    Mint {
+        supply,
+        decimals: 6,
+        is_initialized: true,
+        ..Mint::default()
+    }
+    .pack_into_slice(&mut data);
+    update(pda::mint().0, anchor_spl::token::ID, data, slot)
+}
+
+fn minter_update(minter: Pubkey, amount_minted: u64, slot: u64) -> AccountUpdate {
+    let config = MinterConfig {
+        minter,
+        allowance: 1_000,
+        amount_minted,
+        is_initialized: true,
+        bump: 255,
+        pause_bump: 255,
+    };
+    update(
+        pda::minter_config(&minter).0,
+        PROGRAM_ID,
+        serialize(&config),
+        slot,
+    )
+}
+
+#[test]
+fn test_apply_builds_view() {
+    let minter = Pubkey::new_unique();
+    let address = pda::minter_config(&minter).0;
+    let mut view = View::default();
+
+    assert_eq!(
+        view.apply(&config_update(true, 10)).unwrap(),
+        Some(Change::Config)
+    );
+    assert_eq!(
+        view.apply(&mint_update(5_000, 11)).unwrap(),
+        Some(Change::Mint)
+    );
+    assert_eq!(
+        view.apply(&minter_update(minter, 400, 12)).unwrap(),
+        Some(Change::Minter(address))
+    );
+
+    assert_eq!(
+        view.supply_json(),
+        json!({
+            "slot": 12,
+            "supply": "5000",
+            "decimals": 6,
+            "paused": true,
+            "admin": Pubkey::new_from_array([7; 32]).to_string(),
+            "minters": 1,
+        })
+    );
+    assert_eq!(
+        view.minter_json(&minter),
+        Some(json!({
+            "address": address.to_string(),
+            "minter": minter.to_string(),
+            "allowance": "1000",
+            "amount_minted": "400",
+            "remaining_allowance": "600",
+        }))
+    );
+    assert_eq!(view.minter_json(&Pubkey::new_unique()), None);
+    assert_eq!(view.minters_json()["minters"].as_array().unwrap().len(), 1);
+}
+
+#[test]
+fn test_apply_drops_stale_updates() {
+    let mut view = View::default();
+    view.apply(&mint_update(5_000, 20)).unwrap();
+
+    assert_eq!(view.apply(&mint_update(4_000, 19)).unwrap(), None);
+    assert_eq!(view.mint.unwrap().supply, 5_000);
+
+    // Same slot wins, so a stream overlapping a load still applies
+    assert_eq!(
+        view.apply(&mint_update(6_000, 20)).unwrap(),
+        Some(Change::Mint)
+    );
+    assert_eq!(view.mint.unwrap().supply, 6_000);
+}
+
+#[test]
+fn test_apply_minter_closed() {
+    let minter = Pubkey::new_unique();
+    let address = pda::minter_config(&minter).0;
+    let mut view = View::default();
+    view.apply(&minter_update(minter, 0, 30)).unwrap();
+
+    // A closed account is handed to the system program
+    let closed = AccountUpdate {
+        lamports: 0,
+        ..update(address, Pubkey::default(), Vec::new(), 31)
+    };
+    assert_eq!(
+        view.apply(&closed).unwrap(),
+        Some(Change::MinterRemoved(address))
+    );
+    assert!(view.minters.is_empty());
+    assert_eq!(view.apply(&closed).unwrap(), None);
+}
+
+#[test]
+fn test_apply_ignores_other_accounts() {
+    let mut view = View::default();
+    let other = update(Pubkey::new_unique(), PROGRAM_ID, vec![1, 2, 3], 40);
+    assert_eq!(view.apply(&other).unwrap(), None);
+    assert_eq!(view.slot, 0);
+
+    let garbled = update(pda::config().0, PROGRAM_ID, vec![1, 2, 3], 40);
+    assert!(matches!(view.apply(&garbled), Err(ViewError::Client(_))));
+}
+
+#[test]
+fn test_subscribe_request() {
+    let minter_config = Pubkey::new_unique();
+    let request = subscribe_request(CommitmentConfig::finalized(), [&minter_config]);
+
+    assert_eq!(request.commitment, Some(2));
+    let filter = &request.accounts["stablecoin"];
+    assert_eq!(
+        filter.account,
+        vec![
+            pda::config().0.to_string(),
+            pda::mint().0.to_string(),
+            minter_config.to_string(),
+        ]
+    );
+    assert_eq!(filter.owner, vec![PROGRAM_ID.to_string()]);
+}
+
+#[test]
+fn test_account_update_from_grpc() {
+    let address = Pubkey::new_unique();
+    let grpc = SubscribeUpdateAccount {
+        account: Some(SubscribeUpdateAccountInfo {
+            pubkey: address.to_bytes().to_vec(),
+            lamports: 5,
+            owner: PROGRAM_ID.to_bytes().to_vec(),
+            data: vec![9],
+            ..SubscribeUpdateAccountInfo::default()
+        }),
+        slot: 50,
+        ..SubscribeUpdateAccount::default()
+    };
+    assert_eq!(
+        AccountUpdate::try_from(grpc).unwrap(),
+        AccountUpdate {
+            address,
+            owner: PROGRAM_ID,
+            lamports: 5,
+            data: vec![9],
+            slot: 50,
+        }
+    );
+
+    let malformed = SubscribeUpdateAccount {
+        account: Some(SubscribeUpdateAccountInfo {
+            pubkey: vec![1, 2],
+            ..SubscribeUpdateAccountInfo::default()
+        }),
+        ..SubscribeUpdateAccount::default()
+    };
+    assert!(matches!(
+        AccountUpdate::try_from(malformed),
+        Err(ViewError::InvalidAddress)
+    ));
+}
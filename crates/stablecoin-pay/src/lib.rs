@@ -0,0 +1,170 @@
+//! Solana Pay transaction requests for the stablecoin
+//!
+//! A wallet that scans `solana:<link>` fetches a label from the link, then
+//! POSTs its account and gets back an unsigned transaction to sign. Here that
+//! transaction is an SPL `transfer_checked` of the invoice amount from the
+//! payer's associated token account to the recipient's, with the invoice's
+//! reference key attached so the payment can be found by
+//! `getSignaturesForAddress`. The program has no payment instruction or
+//! on-chain invoices, so invoices live with whoever serves them.
+//!
+//! The `payments-server` feature builds `stablecoin-payments-server`, an axum
+//! service around this module.
+
+use anchor_lang::prelude::Pubkey;
+use anchor_spl::token::spl_token;
+use base64::{engine::general_purpose::STANDARD, Engine as _};
+use serde::{Deserialize, Serialize};
+use solana_hash::Hash;
+use solana_instruction::{AccountMeta, Instruction};
+use solana_transaction_status_client_types::{
+    option_serializer::OptionSerializer, UiTransactionStatusMeta, UiTransactionTokenBalance,
+};
+use stablecoin_client::{pda, TxBuilder};
+
+/// SPL Memo v2
+pub const MEMO_PROGRAM_ID: Pubkey =
+    Pubkey::from_str_const("MemoSq4gqABAXKb96qnH8TysNcWxMyWCqXgDLGmfcHr");
+
+#[derive(Debug, thiserror::Error)]
+pub enum PayError {
+    #[error("failed to build transfer: {0}")]
+    Transfer(#[from] anchor_lang::prelude::ProgramError),
+    #[error("failed to serialize transaction: {0}")]
+    Serialize(#[from] bincode::Error),
+}
+
+/// A payment the server is waiting for
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Invoice {
+    /// Unique per invoice; identifies the payment on chain
+    #[serde(with = "display")]
+    pub reference: Pubkey,
+    /// Wallet paid; tokens go to its associated token account
+    #[serde(with = "display")]
+    pub recipient: Pubkey,
+    /// In base units
+    pub amount: u64,
+    /// Shown by the wallet next to the transaction
+    pub message: Option<String>,
+    /// Recorded on chain with the payment
+    pub memo: Option<String>,
+}
+
+/// Response to the transaction request `GET`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Label {
+    pub label: String,
+    pub icon: String,
+}
+
+/// Body of the transaction request `POST`
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct TransactionRequest {
+    pub account: String,
+}
+
+/// Response to the transaction request `POST`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct TransactionResponse {
+    /// Base64 of the bincode-serialized, unsigned transaction
+    pub transaction: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+}
+
+/// The memo, if any, then the transfer carrying the reference
+pub fn payment_instructions(
+    payer: &Pubkey,
+    invoice: &Invoice,
+    decimals: u8,
+) -> Result<Vec<Instruction>, PayError> {
+    let mut instructions = Vec::new();
+    if let Some(memo) = &invoice.memo {
+        instructions.push(Instruction {
+            program_id: MEMO_PROGRAM_ID,
+            accounts: Vec::new(),
+            data: memo.as_bytes().to_vec(),
+        });
+    }
+    let mut transfer = spl_token::instruction::transfer_checked(
+        &spl_token::ID,
+        &pda::token_account(payer),
+        &pda::mint().0,
+        &pda::token_account(&invoice.recipient),
+        payer,
+        &[],
+        invoice.amount,
+        decimals,
+    )?;
+    transfer
+        .accounts
+        .push(AccountMeta::new_readonly(invoice.reference, false));
+    instructions.push(transfer);
+    Ok(instructions)
+}
+
+/// The unsigned payment transaction for `payer`, who also pays the fee
+pub fn transaction_response(
+    payer: &Pubkey,
+    invoice: &Invoice,
+    decimals: u8,
+    recent_blockhash: Hash,
+) -> Result<TransactionResponse, PayError> {
+    let tx = TxBuilder::new(*payer)
+        .instructions(payment_instructions(payer, invoice, decimals)?)
+        .build_unsigned(recent_blockhash);
+    Ok(TransactionResponse {
+        transaction: STANDARD.encode(bincode::serialize(&tx)?),
+        message: invoice.message.clone(),
+    })
+}
+
+/// The `solana:` URL wallets scan for the transaction request at `link`
+///
+/// Links with a query string are percent-encoded, as the spec requires.
+pub fn transaction_request_url(link: &str) -> String {
+    if !link.contains('?') {
+        return format!("solana:{link}");
+    }
+    let mut url = String::from("solana:");
+    for byte in link.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
+                url.push(byte as char)
+            }
+            byte => url.push_str(&format!("%{byte:02X}")),
+        }
+    }
+    url
+}
+
+/// Stablecoin base units `recipient` gained in a transaction, from its
+/// token balances
+pub fn amount_received(meta: &UiTransactionStatusMeta, recipient: &Pubkey) -> u64 {
+    let (recipient, mint) = (recipient.to_string(), pda::mint().0.to_string());
+    let balance = |balances: &OptionSerializer<Vec<UiTransactionTokenBalance>>| -> u64 {
+        let OptionSerializer::Some(balances) = balances else {
+            return 0;
+        };
+        balances
+            .iter()
+            .filter(|balance| {
+                balance.mint == mint
+                    && matches!(&balance.owner, OptionSerializer::Some(owner) if *owner == recipient)
+            })
+            .filter_map(|balance| balance.ui_token_amount.amount.parse::<u64>().ok())
+            .sum()
+    };
+    balance(&meta.post_token_balances).saturating_sub(balance(&meta.pre_token_balances))
+}
+
+/// Serialize a pubkey as its base58 string
+mod display {
+    use anchor_lang::prelude::Pubkey;
+    use serde::Serializer;
+
+    pub fn serialize<S: Serializer>(pubkey: &Pubkey, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(pubkey)
+    }
+}
@@ -0,0 +1,294 @@
+//! `stablecoin-payments-server`: a Solana Pay transaction-request server
+//!
+//! Merchant API, guarded by `--api-token` when set:
+//! - `POST /invoices` with `{"amount", "message"?, "memo"?}` creates an
+//!   invoice and returns it with its `solana:` URL
+//! - `GET /invoices/{reference}` reports whether it has been paid
+//!
+//! Wallet API, per the Solana Pay spec:
+//! - `GET /pay/{reference}` returns the label and icon
+//! - `POST /pay/{reference}` with `{"account"}` returns the transaction
+//!
+//! Invoices are held in memory and are lost on restart.
+
+use std::{collections::HashMap, net::SocketAddr, str::FromStr, sync::Arc};
+
+use anchor_lang::{prelude::Pubkey, AccountDeserialize};
+use anchor_spl::token::Mint;
+use axum::{
+    extract::{Path, State},
+    http::{header, HeaderMap, HeaderValue, StatusCode},
+    response::{IntoResponse, Response},
+    routing::{get, post},
+    Json, Router,
+};
+use clap::Parser;
+use serde::Deserialize;
+use serde_json::json;
+use solana_keypair::Keypair;
+use solana_rpc_client::nonblocking::rpc_client::RpcClient;
+use solana_rpc_client_api::config::RpcTransactionConfig;
+use solana_signer::Signer;
+use stablecoin_client::pda;
+use stablecoin_pay::{
+    amount_received, transaction_request_url, transaction_response, Invoice, Label,
+    TransactionRequest,
+};
+use tokio::{net::TcpListener, sync::RwLock};
+
+#[derive(Debug, Parser)]
+#[command(name = "stablecoin-payments-server", version, about)]
+struct Args {
+    /// RPC URL
+    #[arg(short = 'u', long, default_value = "http://127.0.0.1:8899")]
+    url: String,
+    /// Address to listen on
+    #[arg(long, default_value = "127.0.0.1:8787")]
+    listen: SocketAddr,
+    /// Public base URL wallets reach this server at, e.g. https://pay.example.com
+    #[arg(long, value_name = "URL")]
+    public_url: String,
+    /// Wallet that receives payments
+    #[arg(long, value_name = "PUBKEY")]
+    recipient: Pubkey,
+    /// Merchant name shown by wallets
+    #[arg(long, default_value = "Stablecoin payment")]
+    label: String,
+    /// Square icon shown by wallets (SVG, PNG or WebP URL)
+    #[arg(long, value_name = "URL")]
+    icon: String,
+    /// Bearer token required by the merchant API
+    #[arg(long, env = "STABLECOIN_PAY_API_TOKEN")]
+    api_token: Option<String>,
+}
+
+struct AppState {
+    rpc: RpcClient,
+    args: Args,
+    decimals: u8,
+    invoices: RwLock<HashMap<Pubkey, Invoice>>,
+}
+
+type Shared = Arc<AppState>;
+
+#[tokio::main]
+async fn main() -> std::io::Result<()> {
+    let args = Args::parse();
+    let rpc = RpcClient::new(args.url.clone());
+    let decimals = match rpc.get_account_data(&pda::mint().0).await {
+        Ok(data) => match Mint::try_deserialize(&mut &data[..]) {
+            Ok(mint) => mint.decimals,
+            Err(err) => {
+                eprintln!("error: invalid mint account: {err}");
+                std::process::exit(1);
+            }
+        },
+        Err(err) => {
+            eprintln!("error: failed to read the mint: {err}");
+            std::process::exit(1);
+        }
+    };
+
+    let listen = args.listen;
+    let state = Arc::new(AppState {
+        rpc,
+        args,
+        decimals,
+        invoices: RwLock::new(HashMap::new()),
+    });
+    let app = Router::new()
+        .route("/invoices", post(create_invoice))
+        .route("/invoices/{reference}", get(invoice_status))
+        .route(
+            "/pay/{reference}",
+            get(label).post(transaction).options(preflight),
+        )
+        .with_state(state);
+
+    let listener = TcpListener::bind(listen).await?;
+    eprintln!("serving on http://{listen}");
+    axum::serve(listener, app).await
+}
+
+fn error(status: StatusCode, message: &str) -> Response {
+    (status, Json(json!({ "error": message }))).into_response()
+}
+
+/// Wallets may call from a browser, so the wallet API allows any origin
+fn cors(response: impl IntoResponse) -> Response {
+    let mut response = response.into_response();
+    let headers = response.headers_mut();
+    headers.insert(
+        header::ACCESS_CONTROL_ALLOW_ORIGIN,
+        HeaderValue::from_static("*"),
+    );
+    headers.insert(
+        header::ACCESS_CONTROL_ALLOW_METHODS,
+        HeaderValue::from_static("GET, POST, OPTIONS"),
+    );
+    headers.insert(
+        header::ACCESS_CONTROL_ALLOW_HEADERS,
+        HeaderValue::from_static("Content-Type"),
+    );
+    response
+}
+
+fn authorized(state: &AppState, headers: &HeaderMap) -> bool {
+    let Some(token) = &state.args.api_token else {
+        return true;
+    };
+    headers
+        .get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+        == Some(token.as_str())
+}
+
+async fn invoice(state: &AppState, reference: &str) -> Result<Invoice, Response> {
+    let reference = Pubkey::from_str(reference)
+        .map_err(|_| error(StatusCode::BAD_REQUEST, "invalid reference"))?;
+    state
+        .invoices
+        .read()
+        .await
+        .get(&reference)
+        .cloned()
+        .ok_or_else(|| error(StatusCode::NOT_FOUND, "no such invoice"))
+}
+
+#[derive(Debug, Deserialize)]
+struct NewInvoice {
+    amount: u64,
+    message: Option<String>,
+    memo: Option<String>,
+}
+
+async fn create_invoice(
+    State(state): State<Shared>,
+    headers: HeaderMap,
+    Json(new): Json<NewInvoice>,
+) -> Response {
+    if !authorized(&state, &headers) {
+        return error(StatusCode::UNAUTHORIZED, "missing or wrong API token");
+    }
+    if new.amount == 0 {
+        return error(StatusCode::BAD_REQUEST, "amount must be positive");
+    }
+    let invoice = Invoice {
+        reference: Keypair::new().pubkey(),
+        recipient: state.args.recipient,
+        amount: new.amount,
+        message: new.message,
+        memo: new.memo,
+    };
+    state
+        .invoices
+        .write()
+        .await
+        .insert(invoice.reference, invoice.clone());
+
+    let link = format!(
+        "{}/pay/{}",
+        state.args.public_url.trim_end_matches('/'),
+        invoice.reference
+    );
+    (
+        StatusCode::CREATED,
+        Json(json!({
+            "invoice": invoice,
+            "url": transaction_request_url(&link),
+        })),
+    )
+        .into_response()
+}
+
+/// Paid once a successful transaction carrying the reference credited the
+/// recipient with at least the amount
+async fn invoice_status(
+    State(state): State<Shared>,
+    headers: HeaderMap,
+    Path(reference): Path<String>,
+) -> Response {
+    if !authorized(&state, &headers) {
+        return error(StatusCode::UNAUTHORIZED, "missing or wrong API token");
+    }
+    let invoice = match invoice(&state, &reference).await {
+        Ok(invoice) => invoice,
+        Err(response) => return response,
+    };
+    let signatures = match state
+        .rpc
+        .get_signatures_for_address(&invoice.reference)
+        .await
+    {
+        Ok(signatures) => signatures,
+        Err(err) => return error(StatusCode::BAD_GATEWAY, &err.to_string()),
+    };
+
+    for status in signatures.iter().filter(|status| status.err.is_none()) {
+        let Ok(signature) = status.signature.parse() else {
+            continue;
+        };
+        let tx = match state
+            .rpc
+            .get_transaction_with_config(
+                &signature,
+                RpcTransactionConfig {
+                    max_supported_transaction_version: Some(0),
+                    ..RpcTransactionConfig::default()
+                },
+            )
+            .await
+        {
+            Ok(tx) => tx,
+            Err(err) => return error(StatusCode::BAD_GATEWAY, &err.to_string()),
+        };
+        let paid = tx
+            .transaction
+            .meta
+            .as_ref()
+            .is_some_and(|meta| amount_received(meta, &invoice.recipient) >= invoice.amount);
+        if paid {
+            return Json(json!({
+                "invoice": invoice,
+                "status": "paid",
+                "signature": status.signature,
+            }))
+            .into_response();
+        }
+    }
+    Json(json!({ "invoice": invoice, "status": "pending" })).into_response()
+}
+
+async fn label(State(state): State<Shared>) -> Response {
+    cors(Json(Label {
+        label: state.args.label.clone(),
+        icon: state.args.icon.clone(),
+    }))
+}
+
+async fn transaction(
+    State(state): State<Shared>,
+    Path(reference): Path<String>,
+    Json(request): Json<TransactionRequest>,
+) -> Response {
+    let invoice = match invoice(&state, &reference).await {
+        Ok(invoice) => invoice,
+        Err(response) => return cors(response),
+    };
+    let Ok(payer) = Pubkey::from_str(&request.account) else {
+        return cors(error(StatusCode::BAD_REQUEST, "invalid account"));
+    };
+    let blockhash = match state.rpc.get_latest_blockhash().await {
+        Ok(blockhash) => blockhash,
+        Err(err) => return cors(error(StatusCode::BAD_GATEWAY, &err.to_string())),
+    };
+    match transaction_response(&payer, &invoice, state.decimals, blockhash) {
+        Ok(response) => cors(Json(response)),
+        Err(err) => cors(error(StatusCode::INTERNAL_SERVER_ERROR, &err.to_string())),
+    }
+}
+
+async fn preflight() -> Response {
+    cors(StatusCode::NO_CONTENT)
+}
@@ -0,0 +1,122 @@
+use anchor_lang::prelude::Pubkey;
+use anchor_spl::token::spl_token;
+use base64::{engine::general_purpose::STANDARD, Engine as _};
+use serde_json::json;
+use solana_hash::Hash;
+use solana_transaction::Transaction;
+use solana_transaction_status_client_types::UiTransactionStatusMeta;
+use stablecoin_client::pda;
+use stablecoin_pay::{
+    amount_received, payment_instructions, transaction_request_url, transaction_response, Invoice,
+    MEMO_PROGRAM_ID,
+};
+
+fn invoice(memo: Option<&str>) -> Invoice {
+    Invoice {
+        reference: Pubkey::new_unique(),
+        recipient: Pubkey::new_unique(),
+        amount: 2_500_000,
+        message: Some("Order 42".to_string()),
+        memo: memo.map(str::to_string),
+    }
+}
+
+#[test]
+fn test_payment_instructions() {
+    let payer = Pubkey::new_unique();
+    let invoice = invoice(Some("INV-42"));
+    let instructions = payment_instructions(&payer, &invoice, 6).unwrap();
+    assert_eq!(instructions.len(), 2);
+
+    assert_eq!(instructions[0].program_id, MEMO_PROGRAM_ID);
+    assert_eq!(instructions[0].data, b"INV-42");
+
+    let transfer = &instructions[1];
+    assert_eq!(transfer.program_id, spl_token::ID);
+    let accounts: Vec<_> = transfer
+        .accounts
+        .iter()
+        .map(|meta| (meta.pubkey, meta.is_signer, meta.is_writable))
+        .collect();
+    assert_eq!(
+        accounts,
+        [
+            (pda::token_account(&payer), false, true),
+            (pda::mint().0, false, false),
+            (pda::token_account(&invoice.recipient), false, true),
+            (payer, true, false),
+            (invoice.reference, false, false),
+        ]
+    );
+
+    let without_memo = payment_instructions(
+        &payer,
+        &Invoice {
+            memo: None,
+            ..invoice
+        },
+        6,
+    );
+    assert_eq!(without_memo.unwrap().len(), 1);
+}
+
+#[test]
+fn test_transaction_response() {
+    let payer = Pubkey::new_unique();
+    let invoice = invoice(None);
+    let blockhash = Hash::new_from_array([7; 32]);
+    let response = transaction_response(&payer, &invoice, 6, blockhash).unwrap();
+    assert_eq!(response.message.as_deref(), Some("Order 42"));
+
+    let tx: Transaction =
+        bincode::deserialize(&STANDARD.decode(&response.transaction).unwrap()).unwrap();
+    assert_eq!(tx.message.account_keys[0], payer);
+    assert_eq!(tx.message.recent_blockhash, blockhash);
+    assert_eq!(tx.signatures.len(), 1);
+    assert!(tx.message.account_keys.contains(&invoice.reference));
+}
+
+#[test]
+fn test_transaction_request_url() {
+    assert_eq!(
+        transaction_request_url("https://pay.example.com/pay/abc"),
+        "solana:https://pay.example.com/pay/abc"
+    );
+    assert_eq!(
+        transaction_request_url("https://pay.example.com/pay?id=a b"),
+        "solana:https%3A%2F%2Fpay.example.com%2Fpay%3Fid%3Da%20b"
+    );
+}
+
+#[test]
+fn test_amount_received() {
+    let recipient = Pubkey::new_unique();
+    let balance = |index: u8, owner: &Pubkey, amount: &str| {
+        json!({
+            "accountIndex": index,
+            "mint": pda::mint().0.to_string(),
+            "owner": owner.to_string(),
+            "programId": spl_token::ID.to_string(),
+            "uiTokenAmount": {
+                "amount": amount,
+                "decimals": 6,
+                "uiAmount": null,
+                "uiAmountString": "0",
+            },
+        })
+    };
+    let payer = Pubkey::new_unique();
+    let meta: UiTransactionStatusMeta = serde_json::from_value(json!({
+        "err": null,
+        "status": { "Ok": null },
+        "fee": 5000,
+        "preBalances": [],
+        "postBalances": [],
+        "preTokenBalances": [balance(1, &payer, "9000000"), balance(2, &recipient, "500")],
+        "postTokenBalances": [balance(1, &payer, "6500000"), balance(2, &recipient, "2500500")],
+    }))
+    .unwrap();
+
+    assert_eq!(amount_received(&meta, &recipient), 2_500_000);
+    assert_eq!(amount_received(&meta, &payer), 0);
+}
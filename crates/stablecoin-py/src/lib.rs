@@ -0,0 +1,244 @@
+//! Python bindings for the stablecoin client
+//!
+//! Exposes the client's PDA derivation, instruction builders and account
+//! decoders as the `stablecoin` Python module, so scripts share the Rust
+//! implementation instead of re-deriving addresses and layouts by hand.
+//! Addresses cross the boundary as base58 strings.
+//!
+//! Build a wheel with `maturin build --release` from this directory.
+
+use std::borrow::Cow;
+
+use anchor_lang::{prelude::Pubkey, AccountDeserialize};
+use pyo3::{exceptions::PyValueError, prelude::*};
+use stablecoin_client::{instructions, pda};
+
+fn parse_pubkey(address: &str) -> PyResult<Pubkey> {
+    address
+        .parse()
+        .map_err(|_| PyValueError::new_err(format!("invalid address: {address}")))
+}
+
+fn decode<T: AccountDeserialize>(name: &str, mut data: &[u8]) -> PyResult<T> {
+    T::try_deserialize(&mut data)
+        .map_err(|err| PyValueError::new_err(format!("not a {name} account: {err}")))
+}
+
+#[pyclass(module = "stablecoin", get_all, frozen)]
+#[derive(Clone)]
+pub struct AccountMeta {
+    pub pubkey: String,
+    pub is_signer: bool,
+    pub is_writable: bool,
+}
+
+#[pymethods]
+impl AccountMeta {
+    fn __repr__(&self) -> String {
+        format!(
+            "AccountMeta(pubkey={:?}, is_signer={}, is_writable={})",
+            self.pubkey,
+            if self.is_signer { "True" } else { "False" },
+            if self.is_writable { "True" } else { "False" },
+        )
+    }
+}
+
+#[pyclass(module = "stablecoin", frozen)]
+pub struct Instruction {
+    #[pyo3(get)]
+    pub program_id: String,
+    #[pyo3(get)]
+    pub accounts: Vec<AccountMeta>,
+    data: Vec<u8>,
+}
+
+#[pymethods]
+impl Instruction {
+    #[getter]
+    fn data(&self) -> Cow<'_, [u8]> {
+        Cow::Borrowed(&self.data)
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "Instruction(program_id={:?}, accounts=<{} accounts>, data=<{} bytes>)",
+            self.program_id,
+            self.accounts.len(),
+            self.data.len()
+        )
+    }
+}
+
+impl From<solana_instruction::Instruction> for Instruction {
+    fn from(instruction: solana_instruction::Instruction) -> Self {
+        Self {
+            program_id: instruction.program_id.to_string(),
+            accounts: instruction
+                .accounts
+                .into_iter()
+                .map(|meta| AccountMeta {
+                    pubkey: meta.pubkey.to_string(),
+                    is_signer: meta.is_signer,
+                    is_writable: meta.is_writable,
+                })
+                .collect(),
+            data: instruction.data,
+        }
+    }
+}
+
+#[pyclass(module = "stablecoin", get_all, frozen)]
+pub struct Config {
+    pub admin: String,
+    pub mint: String,
+    pub paused: bool,
+    pub bump: u8,
+    pub mint_bump: u8,
+    pub delegate_bump: u8,
+    pub minter_set_freeze_bump: u8,
+}
+
+#[pyclass(module = "stablecoin", get_all, frozen)]
+pub struct MinterConfig {
+    pub minter: String,
+    pub allowance: u64,
+    pub amount_minted: u64,
+    pub is_initialized: bool,
+    pub bump: u8,
+    pub pause_bump: u8,
+}
+
+#[pymethods]
+impl MinterConfig {
+    #[getter]
+    fn remaining_allowance(&self) -> u64 {
+        self.allowance.saturating_sub(self.amount_minted)
+    }
+}
+
+/// Config PDA as `(address, bump)`
+#[pyfunction]
+fn config_pda() -> (String, u8) {
+    let (address, bump) = pda::config();
+    (address.to_string(), bump)
+}
+
+/// Mint PDA as `(address, bump)`
+#[pyfunction]
+fn mint_pda() -> (String, u8) {
+    let (address, bump) = pda::mint();
+    (address.to_string(), bump)
+}
+
+/// Minter config PDA as `(address, bump)`
+#[pyfunction]
+fn minter_config_pda(minter: &str) -> PyResult<(String, u8)> {
+    let (address, bump) = pda::minter_config(&parse_pubkey(minter)?);
+    Ok((address.to_string(), bump))
+}
+
+/// The owner's associated token account for the stablecoin mint
+#[pyfunction]
+fn token_account(owner: &str) -> PyResult<String> {
+    Ok(pda::token_account(&parse_pubkey(owner)?).to_string())
+}
+
+#[pyfunction]
+fn initialize(admin: &str) -> PyResult<Instruction> {
+    Ok(instructions::initialize(&parse_pubkey(admin)?).into())
+}
+
+#[pyfunction]
+fn configure_minter(admin: &str, minter: &str, allowance: u64) -> PyResult<Instruction> {
+    Ok(
+        instructions::configure_minter(&parse_pubkey(admin)?, &parse_pubkey(minter)?, allowance)
+            .into(),
+    )
+}
+
+#[pyfunction]
+fn remove_minter(admin: &str, minter: &str) -> PyResult<Instruction> {
+    Ok(instructions::remove_minter(&parse_pubkey(admin)?, &parse_pubkey(minter)?).into())
+}
+
+#[pyfunction]
+fn mint_tokens(minter: &str, destination_owner: &str, amount: u64) -> PyResult<Instruction> {
+    Ok(instructions::mint_tokens(
+        &parse_pubkey(minter)?,
+        &parse_pubkey(destination_owner)?,
+        amount,
+    )
+    .into())
+}
+
+#[pyfunction]
+fn burn_tokens(owner: &str, amount: u64) -> PyResult<Instruction> {
+    Ok(instructions::burn_tokens(&parse_pubkey(owner)?, amount).into())
+}
+
+#[pyfunction]
+fn pause(admin: &str) -> PyResult<Instruction> {
+    Ok(instructions::pause(&parse_pubkey(admin)?).into())
+}
+
+#[pyfunction]
+fn unpause(admin: &str) -> PyResult<Instruction> {
+    Ok(instructions::unpause(&parse_pubkey(admin)?).into())
+}
+
+/// Decode raw `Config` account data, discriminator included
+#[pyfunction]
+fn decode_config(data: &[u8]) -> PyResult<Config> {
+    let config: stablecoin::Config = decode("Config", data)?;
+    Ok(Config {
+        admin: config.admin.to_string(),
+        mint: config.mint.to_string(),
+        paused: config.paused,
+        bump: config.bump,
+        mint_bump: config.mint_bump,
+        delegate_bump: config.delegate_bump,
+        minter_set_freeze_bump: config.minter_set_freeze_bump,
+    })
+}
+
+/// Decode raw `MinterConfig` account data, discriminator included
+#[pyfunction]
+fn decode_minter_config(data: &[u8]) -> PyResult<MinterConfig> {
+    let config: stablecoin::MinterConfig = decode("MinterConfig", data)?;
+    Ok(MinterConfig {
+        minter: config.minter.to_string(),
+        allowance: config.allowance,
+        amount_minted: config.amount_minted,
+        is_initialized: config.is_initialized,
+        bump: config.bump,
+        pause_bump: config.pause_bump,
+    })
+}
+
+#[pymodule(name = "stablecoin")]
+pub fn stablecoin_module(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add("PROGRAM_ID", stablecoin_client::PROGRAM_ID.to_string())?;
+
+    m.add_class::<AccountMeta>()?;
+    m.add_class::<Instruction>()?;
+    m.add_class::<Config>()?;
+    m.add_class::<MinterConfig>()?;
+
+    m.add_function(wrap_pyfunction!(config_pda, m)?)?;
+    m.add_function(wrap_pyfunction!(mint_pda, m)?)?;
+    m.add_function(wrap_pyfunction!(minter_config_pda, m)?)?;
+    m.add_function(wrap_pyfunction!(token_account, m)?)?;
+
+    m.add_function(wrap_pyfunction!(initialize, m)?)?;
+    m.add_function(wrap_pyfunction!(configure_minter, m)?)?;
+    m.add_function(wrap_pyfunction!(remove_minter, m)?)?;
+    m.add_function(wrap_pyfunction!(mint_tokens, m)?)?;
+    m.add_function(wrap_pyfunction!(burn_tokens, m)?)?;
+    m.add_function(wrap_pyfunction!(pause, m)?)?;
+    m.add_function(wrap_pyfunction!(unpause, m)?)?;
+
+    m.add_function(wrap_pyfunction!(decode_config, m)?)?;
+    m.add_function(wrap_pyfunction!(decode_minter_config, m)?)?;
+    Ok(())
+}
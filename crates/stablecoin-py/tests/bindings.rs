@@ -0,0 +1,186 @@
+use anchor_lang::{prelude::Pubkey, AccountSerialize};
+use pyo3::{ffi::c_str, prelude::*, types::PyDict, BoundObject};
+use stablecoin::{Config, MinterConfig};
+use stablecoin_client::{instructions, pda};
+
+/// Run `code` with the module bound as `stablecoin` and `vars` as globals
+fn run(code: &std::ffi::CStr, vars: &[(&str, PyObject)]) {
+    Python::with_gil(|py| {
+        let globals = PyDict::new(py);
+        let module = PyModule::new(py, "stablecoin").unwrap();
+        stablecoin_py::stablecoin_module(&module).unwrap();
+        globals.set_item("stablecoin", module).unwrap();
+        for (name, value) in vars {
+            globals.set_item(name, value).unwrap();
+        }
+        if let Err(err) = py.run(code, Some(&globals), None) {
+            err.print(py);
+            panic!("python assertion failed");
+        }
+    });
+}
+
+fn obj<T: for<'py> IntoPyObject<'py>>(value: T) -> PyObject {
+    Python::with_gil(|py| match value.into_pyobject(py) {
+        Ok(value) => value.into_any().unbind(),
+        Err(_) => panic!("not convertible to a python object"),
+    })
+}
+
+fn str_obj(value: impl ToString) -> PyObject {
+    obj(value.to_string())
+}
+
+#[test]
+fn test_pdas_match_client() {
+    let minter = Pubkey::new_unique();
+    let (config, config_bump) = pda::config();
+    let (mint, mint_bump) = pda::mint();
+    let (minter_config, minter_bump) = pda::minter_config(&minter);
+
+    run(
+        c_str!(
+            r#"
+assert stablecoin.PROGRAM_ID == program_id
+assert stablecoin.config_pda() == (config, config_bump)
+assert stablecoin.mint_pda() == (mint, mint_bump)
+assert stablecoin.minter_config_pda(minter) == (minter_config, minter_bump)
+assert stablecoin.token_account(minter) == token_account
+"#
+        ),
+        &[
+            ("program_id", str_obj(stablecoin_client::PROGRAM_ID)),
+            ("minter", str_obj(minter)),
+            ("config", str_obj(config)),
+            ("config_bump", obj(config_bump)),
+            ("mint", str_obj(mint)),
+            ("mint_bump", obj(mint_bump)),
+            ("minter_config", str_obj(minter_config)),
+            ("minter_bump", obj(minter_bump)),
+            ("token_account", str_obj(pda::token_account(&minter))),
+        ],
+    );
+}
+
+#[test]
+fn test_instruction_matches_client() {
+    let minter = Pubkey::new_unique();
+    let owner = Pubkey::new_unique();
+    let expected = instructions::mint_tokens(&minter, &owner, 1_000);
+
+    Python::with_gil(|py| {
+        let module = PyModule::new(py, "stablecoin").unwrap();
+        stablecoin_py::stablecoin_module(&module).unwrap();
+        let ix = module
+            .getattr("mint_tokens")
+            .unwrap()
+            .call1((minter.to_string(), owner.to_string(), 1_000u64))
+            .unwrap();
+
+        let program_id: String = ix.getattr("program_id").unwrap().extract().unwrap();
+        assert_eq!(program_id, expected.program_id.to_string());
+        let data: Vec<u8> = ix.getattr("data").unwrap().extract().unwrap();
+        assert_eq!(data, expected.data);
+
+        let accounts = ix.getattr("accounts").unwrap();
+        assert_eq!(accounts.len().unwrap(), expected.accounts.len());
+        for (index, meta) in expected.accounts.iter().enumerate() {
+            let account = accounts.get_item(index).unwrap();
+            let pubkey: String = account.getattr("pubkey").unwrap().extract().unwrap();
+            let is_signer: bool = account.getattr("is_signer").unwrap().extract().unwrap();
+            let is_writable: bool = account.getattr("is_writable").unwrap().extract().unwrap();
+            assert_eq!(pubkey, meta.pubkey.to_string());
+            assert_eq!(is_signer, meta.is_signer);
+            assert_eq!(is_writable, meta.is_writable);
+        }
+    });
+}
+
+#[test]
+fn test_decode_accounts() {
+    let config = Config {
+        admin: Pubkey::new_unique(),
+        mint: pda::mint().0,
+        paused: true,
+        bump: 254,
+        mint_bump: 253,
+        delegate_bump: 251,
+        minter_set_freeze_bump: 250,
+    };
+    let mut config_data = Vec::new();
+    config.try_serialize(&mut config_data).unwrap();
+
+    let minter_config = MinterConfig {
+        minter: Pubkey::new_unique(),
+        allowance: 1_000,
+        amount_minted: 400,
+        is_initialized: true,
+        bump: 252,
+        pause_bump: 249,
+    };
+    let mut minter_data = Vec::new();
+    minter_config.try_serialize(&mut minter_data).unwrap();
+
+    run(
+        c_str!(
+            r#"
+config = stablecoin.decode_config(bytes(config_data))
+assert config.admin == admin
+assert config.mint == stablecoin.mint_pda()[0]
+assert config.paused is True
+assert (config.bump, config.mint_bump) == (254, 253)
+assert (config.delegate_bump, config.minter_set_freeze_bump) == (251, 250)
+
+minter = stablecoin.decode_minter_config(bytes(minter_data))
+assert minter.minter == minter_key
+assert (minter.allowance, minter.amount_minted) == (1000, 400)
+assert minter.remaining_allowance == 600
+assert minter.is_initialized is True
+assert (minter.bump, minter.pause_bump) == (252, 249)
+"#
+        ),
+        &[
+            ("config_data", obj(config_data)),
+            ("minter_data", obj(minter_data)),
+            ("admin", str_obj(config.admin)),
+            ("minter_key", str_obj(minter_config.minter)),
+        ],
+    );
+}
+
+#[test]
+fn test_invalid_input_raises_value_error() {
+    let admin = Pubkey::new_unique();
+    let mut config_data = Vec::new();
+    Config {
+        admin,
+        mint: pda::mint().0,
+        paused: false,
+        bump: 255,
+        mint_bump: 255,
+        delegate_bump: 255,
+        minter_set_freeze_bump: 255,
+    }
+    .try_serialize(&mut config_data)
+    .unwrap();
+
+    run(
+        c_str!(
+            r#"
+for call in (
+    lambda: stablecoin.pause("not an address"),
+    lambda: stablecoin.minter_config_pda(""),
+    lambda: stablecoin.decode_minter_config(bytes(config_data)),
+    lambda: stablecoin.decode_config(b"short"),
+):
+    try:
+        call()
+    except ValueError:
+        pass
+    else:
+        raise AssertionError("expected ValueError")
+"#
+        ),
+        &[("config_data", obj(config_data))],
+    );
+}
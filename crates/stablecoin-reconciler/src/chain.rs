@@ -0,0 +1,158 @@
+//! Mints and burns as recorded on chain
+//!
+//! The program's events carry no reference, so the reference is the SPL
+//! Memo attached to the transaction. A transaction with several mints or
+//! burns numbers them after the first, `<memo>#1`, `<memo>#2` and so on; one
+//! without a memo is referenced by its signature.
+
+use anchor_lang::prelude::Pubkey;
+use chrono::{DateTime, NaiveDate};
+use solana_commitment_config::CommitmentConfig;
+use solana_rpc_client::nonblocking::rpc_client::RpcClient;
+use solana_rpc_client_api::config::RpcTransactionConfig;
+use solana_signature::Signature;
+use solana_transaction_status_client_types::{
+    option_serializer::OptionSerializer, UiTransactionEncoding,
+};
+use stablecoin_client::{
+    events::{parse_logs, StablecoinEvent},
+    history::signatures_since,
+    pda, ClientError, PROGRAM_ID,
+};
+
+use crate::ledger::Kind;
+
+/// SPL Memo v1 and v2
+const MEMO_PROGRAMS: [&str; 2] = [
+    "Memo1UhkJRfHyvLMcVucJwxXeuD6YKHAndJ4S5NPAiN",
+    "MemoSq4gqABAXKb96qnH8TysNcWxMyWCqXgDLGmfcHr",
+];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChainEntry {
+    pub reference: String,
+    pub kind: Kind,
+    pub amount: u64,
+    /// Destination of a mint, source of a burn
+    pub account: Pubkey,
+    pub signature: Signature,
+    pub slot: u64,
+    pub block_time: Option<i64>,
+}
+
+impl ChainEntry {
+    /// UTC date of the block, when the node reports a block time
+    pub fn date(&self) -> Option<NaiveDate> {
+        self.block_time
+            .and_then(|time| DateTime::from_timestamp(time, 0))
+            .map(|time| time.date_naive())
+    }
+}
+
+/// The memo a memo program logged, if any
+///
+/// The memo program logs `Memo (len N): "<memo>"`, the memo quoted as a
+/// Rust debug string.
+pub fn parse_memo<S: AsRef<str>>(logs: &[S]) -> Option<String> {
+    let mut invoked = "";
+    for log in logs {
+        let log = log.as_ref();
+        if let Some(rest) = log.strip_prefix("Program ") {
+            if let Some(id) = rest.strip_suffix(" invoke [1]") {
+                invoked = id;
+            }
+        }
+        if !MEMO_PROGRAMS.contains(&invoked) {
+            continue;
+        }
+        let Some(quoted) = log
+            .strip_prefix("Program log: Memo (len ")
+            .and_then(|rest| rest.split_once("): "))
+            .map(|(_, quoted)| quoted)
+        else {
+            continue;
+        };
+        // Debug escapes are JSON's for everything a reference should hold
+        return Some(
+            serde_json::from_str(quoted).unwrap_or_else(|_| quoted.trim_matches('"').to_string()),
+        );
+    }
+    None
+}
+
+/// The mints and burns in one transaction's events
+pub fn chain_entries(
+    signature: Signature,
+    slot: u64,
+    block_time: Option<i64>,
+    memo: Option<&str>,
+    events: &[StablecoinEvent],
+) -> Vec<ChainEntry> {
+    let base = memo.map_or_else(|| signature.to_string(), str::to_string);
+    events
+        .iter()
+        .filter_map(|event| match event {
+            StablecoinEvent::TokensMinted(event) => {
+                Some((Kind::Mint, event.amount, event.destination))
+            }
+            StablecoinEvent::TokensBurned(event) => {
+                Some((Kind::Burn, event.amount, event.token_account))
+            }
+            _ => None,
+        })
+        .enumerate()
+        .map(|(i, (kind, amount, account))| ChainEntry {
+            reference: match i {
+                0 => base.clone(),
+                i => format!("{base}#{i}"),
+            },
+            kind,
+            amount,
+            account,
+            signature,
+            slot,
+            block_time,
+        })
+        .collect()
+}
+
+/// Every mint and burn in the program's history, oldest first
+///
+/// Both touch the mint, so its signatures cover them.
+pub async fn fetch_chain(
+    rpc: &RpcClient,
+    commitment: CommitmentConfig,
+) -> Result<Vec<ChainEntry>, ClientError> {
+    let mut entries = Vec::new();
+    for (_, signature) in signatures_since(rpc, &pda::mint().0, None, commitment).await? {
+        let tx = rpc
+            .get_transaction_with_config(
+                &signature,
+                RpcTransactionConfig {
+                    encoding: Some(UiTransactionEncoding::Base64),
+                    commitment: Some(commitment),
+                    max_supported_transaction_version: Some(0),
+                },
+            )
+            .await?;
+        let Some(meta) = tx.transaction.meta else {
+            continue;
+        };
+        if meta.err.is_some() {
+            continue;
+        }
+        let logs = match meta.log_messages {
+            OptionSerializer::Some(logs) => logs,
+            _ => Vec::new(),
+        };
+        let events = parse_logs(&PROGRAM_ID, &logs)?;
+        entries.extend(chain_entries(
+            signature,
+            tx.slot,
+            tx.block_time,
+            parse_memo(&logs).as_deref(),
+            &events,
+        ));
+    }
+    Ok(entries)
+}
@@ -0,0 +1,132 @@
+//! The issuer's issuance ledger
+//!
+//! A CSV file with a header row and the columns `reference`, `kind` (`mint`
+//! or `burn`), `amount` in base units, `account` and `date` (`YYYY-MM-DD`,
+//! UTC). `account` is the destination token account of a mint or the source
+//! of a burn; left blank, it is not compared. Other columns are ignored.
+
+use std::{fmt, str::FromStr};
+
+use anchor_lang::prelude::Pubkey;
+use chrono::NaiveDate;
+use csv::StringRecord;
+
+use crate::ReconcileError;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Kind {
+    Mint,
+    Burn,
+}
+
+impl fmt::Display for Kind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Mint => "mint",
+            Self::Burn => "burn",
+        })
+    }
+}
+
+impl FromStr for Kind {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, String> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "mint" => Ok(Self::Mint),
+            "burn" => Ok(Self::Burn),
+            other => Err(format!("kind must be mint or burn, not {other:?}")),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LedgerEntry {
+    pub reference: String,
+    pub kind: Kind,
+    pub amount: u64,
+    pub account: Option<Pubkey>,
+    pub date: NaiveDate,
+}
+
+/// Read the ledger from a path or an http(s):// URL, sending `token` as a
+/// bearer token to the latter
+pub async fn read_ledger(source: &str, token: Option<&str>) -> Result<String, ReconcileError> {
+    if !(source.starts_with("http://") || source.starts_with("https://")) {
+        return std::fs::read_to_string(source).map_err(|source_err| ReconcileError::Io {
+            path: source.to_string(),
+            source: source_err,
+        });
+    }
+    let mut request = reqwest::Client::new().get(source);
+    if let Some(token) = token {
+        request = request.bearer_auth(token);
+    }
+    let response = request
+        .send()
+        .await
+        .and_then(|response| response.error_for_status())
+        // The URL may carry credentials
+        .map_err(|err| ReconcileError::Fetch(err.without_url()))?;
+    response
+        .text()
+        .await
+        .map_err(|err| ReconcileError::Fetch(err.without_url()))
+}
+
+/// Parse the ledger CSV
+pub fn parse_ledger(csv: &str) -> Result<Vec<LedgerEntry>, ReconcileError> {
+    let mut reader = csv::ReaderBuilder::new()
+        .trim(csv::Trim::All)
+        .from_reader(csv.as_bytes());
+    let headers = reader.headers()?.clone();
+    let column = |name: &str| {
+        headers
+            .iter()
+            .position(|header| header == name)
+            .ok_or_else(|| ReconcileError::Ledger {
+                line: 1,
+                message: format!("missing column {name:?}"),
+            })
+    };
+    let columns = [
+        column("reference")?,
+        column("kind")?,
+        column("amount")?,
+        column("account")?,
+        column("date")?,
+    ];
+
+    let mut entries = Vec::new();
+    for record in reader.records() {
+        let record = record?;
+        let line = record.position().map_or(0, |position| position.line());
+        entries.push(
+            parse_entry(&record, columns)
+                .map_err(|message| ReconcileError::Ledger { line, message })?,
+        );
+    }
+    Ok(entries)
+}
+
+fn parse_entry(record: &StringRecord, columns: [usize; 5]) -> Result<LedgerEntry, String> {
+    let [reference, kind, amount, account, date] = columns.map(|i| record.get(i).unwrap_or(""));
+    if reference.is_empty() {
+        return Err("empty reference".to_string());
+    }
+    Ok(LedgerEntry {
+        reference: reference.to_string(),
+        kind: kind.parse()?,
+        amount: amount
+            .parse()
+            .map_err(|_| format!("invalid amount {amount:?}"))?,
+        account: match account {
+            "" => None,
+            account => Some(
+                Pubkey::from_str(account).map_err(|_| format!("invalid account {account:?}"))?,
+            ),
+        },
+        date: NaiveDate::parse_from_str(date, "%Y-%m-%d")
+            .map_err(|_| format!("invalid date {date:?}"))?,
+    })
+}
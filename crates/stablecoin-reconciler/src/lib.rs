@@ -0,0 +1,201 @@
+//! Two-way reconciliation of the issuance ledger against the chain
+//!
+//! Entries are matched by reference (see [`chain`] for what that is on
+//! chain). Every reference with a ledger entry or an on-chain entry in the
+//! date window is checked, against the other side's entry from any date, so
+//! a mint booked just before midnight and landing just after still matches.
+//! The ledger must record amounts in base units.
+
+pub mod chain;
+pub mod ledger;
+
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    io,
+};
+
+use chrono::{DateTime, NaiveDate, SecondsFormat};
+
+use crate::{chain::ChainEntry, ledger::LedgerEntry};
+
+#[derive(Debug, thiserror::Error)]
+pub enum ReconcileError {
+    #[error("ledger line {line}: {message}")]
+    Ledger { line: u64, message: String },
+    #[error("{path}: {source}")]
+    Io { path: String, source: io::Error },
+    #[error("fetching ledger: {0}")]
+    Fetch(reqwest::Error),
+    #[error(transparent)]
+    Csv(#[from] csv::Error),
+    #[error(transparent)]
+    Client(#[from] stablecoin_client::ClientError),
+}
+
+/// Dates to reconcile, `[from, to)` in UTC; unbounded where `None`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Window {
+    pub from: Option<NaiveDate>,
+    pub to: Option<NaiveDate>,
+}
+
+impl Window {
+    pub fn contains(&self, date: NaiveDate) -> bool {
+        self.from.is_none_or(|from| date >= from) && self.to.is_none_or(|to| date < to)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Issue {
+    MissingOnChain,
+    MissingFromLedger,
+    KindMismatch,
+    AmountMismatch,
+    AccountMismatch,
+    DuplicateInLedger,
+    DuplicateOnChain,
+}
+
+impl Issue {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::MissingOnChain => "missing_on_chain",
+            Self::MissingFromLedger => "missing_from_ledger",
+            Self::KindMismatch => "kind_mismatch",
+            Self::AmountMismatch => "amount_mismatch",
+            Self::AccountMismatch => "account_mismatch",
+            Self::DuplicateInLedger => "duplicate_in_ledger",
+            Self::DuplicateOnChain => "duplicate_on_chain",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Discrepancy {
+    pub reference: String,
+    pub issue: Issue,
+    pub ledger: Option<LedgerEntry>,
+    pub chain: Option<ChainEntry>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Report {
+    pub matched: usize,
+    pub discrepancies: Vec<Discrepancy>,
+}
+
+/// Match `ledger` against `chain` for the references active in `window`
+pub fn reconcile(ledger: &[LedgerEntry], chain: &[ChainEntry], window: Window) -> Report {
+    let mut by_ledger: BTreeMap<&str, Vec<&LedgerEntry>> = BTreeMap::new();
+    for entry in ledger {
+        by_ledger.entry(&entry.reference).or_default().push(entry);
+    }
+    let mut by_chain: BTreeMap<&str, Vec<&ChainEntry>> = BTreeMap::new();
+    for entry in chain {
+        by_chain.entry(&entry.reference).or_default().push(entry);
+    }
+    let references: BTreeSet<&str> = by_ledger.keys().chain(by_chain.keys()).copied().collect();
+
+    let mut report = Report::default();
+    for reference in references {
+        let ledger = by_ledger.get(reference).map_or(&[][..], Vec::as_slice);
+        let chain = by_chain.get(reference).map_or(&[][..], Vec::as_slice);
+        let active = ledger.iter().any(|entry| window.contains(entry.date))
+            // An unknown block time can't be placed, so it is always checked
+            || chain
+                .iter()
+                .any(|entry| entry.date().is_none_or(|date| window.contains(date)));
+        if !active {
+            continue;
+        }
+
+        let mut flag = |issue, ledger: Option<&LedgerEntry>, chain: Option<&ChainEntry>| {
+            report.discrepancies.push(Discrepancy {
+                reference: reference.to_string(),
+                issue,
+                ledger: ledger.cloned(),
+                chain: chain.cloned(),
+            })
+        };
+        for duplicate in ledger.iter().skip(1) {
+            flag(Issue::DuplicateInLedger, Some(duplicate), None);
+        }
+        for duplicate in chain.iter().skip(1) {
+            flag(Issue::DuplicateOnChain, None, Some(duplicate));
+        }
+
+        match (ledger.first(), chain.first()) {
+            (Some(&l), None) => flag(Issue::MissingOnChain, Some(l), None),
+            (None, Some(&c)) => flag(Issue::MissingFromLedger, None, Some(c)),
+            (Some(&l), Some(&c)) => {
+                let mut matched = true;
+                for (issue, differs) in [
+                    (Issue::KindMismatch, l.kind != c.kind),
+                    (Issue::AmountMismatch, l.amount != c.amount),
+                    (
+                        Issue::AccountMismatch,
+                        l.account.is_some_and(|account| account != c.account),
+                    ),
+                ] {
+                    if differs {
+                        flag(issue, Some(l), Some(c));
+                        matched = false;
+                    }
+                }
+                if matched {
+                    report.matched += 1;
+                }
+            }
+            (None, None) => unreachable!("every reference comes from one side"),
+        }
+    }
+    report
+}
+
+const REPORT_HEADER: [&str; 11] = [
+    "reference",
+    "issue",
+    "ledger_kind",
+    "ledger_amount",
+    "ledger_account",
+    "ledger_date",
+    "chain_kind",
+    "chain_amount",
+    "chain_account",
+    "block_time",
+    "signature",
+];
+
+/// The discrepancies as CSV, one row each
+pub fn write_report(out: impl io::Write, report: &Report) -> csv::Result<()> {
+    fn field<T: ToString>(value: Option<T>) -> String {
+        value.map(|value| value.to_string()).unwrap_or_default()
+    }
+
+    let mut writer = csv::Writer::from_writer(out);
+    writer.write_record(REPORT_HEADER)?;
+    for discrepancy in &report.discrepancies {
+        let ledger = discrepancy.ledger.as_ref();
+        let chain = discrepancy.chain.as_ref();
+        writer.write_record([
+            discrepancy.reference.clone(),
+            discrepancy.issue.as_str().to_string(),
+            field(ledger.map(|entry| entry.kind)),
+            field(ledger.map(|entry| entry.amount)),
+            field(ledger.and_then(|entry| entry.account)),
+            field(ledger.map(|entry| entry.date)),
+            field(chain.map(|entry| entry.kind)),
+            field(chain.map(|entry| entry.amount)),
+            field(chain.map(|entry| entry.account)),
+            field(
+                chain
+                    .and_then(|entry| entry.block_time)
+                    .and_then(|time| DateTime::from_timestamp(time, 0))
+                    .map(|time| time.to_rfc3339_opts(SecondsFormat::Secs, true)),
+            ),
+            field(chain.map(|entry| entry.signature)),
+        ])?;
+    }
+    writer.flush()?;
+    Ok(())
+}
@@ -0,0 +1,90 @@
+//! `stablecoin-reconciler`: reconcile the issuance ledger against the chain
+//!
+//! Reads the ledger from a file or an HTTP(S) URL, walks the program's mints
+//! and burns, and writes every discrepancy as CSV. Exits 0 when the two
+//! sides agree, 1 when they don't, and 2 when reconciliation could not run,
+//! so a daily job can alert on anything but 0.
+
+use std::{
+    fs::File,
+    io::{self, Write},
+    process::ExitCode,
+};
+
+use chrono::NaiveDate;
+use clap::Parser;
+use solana_commitment_config::CommitmentConfig;
+use solana_rpc_client::nonblocking::rpc_client::RpcClient;
+use stablecoin_reconciler::{
+    chain::fetch_chain,
+    ledger::{parse_ledger, read_ledger},
+    reconcile, write_report, ReconcileError, Window,
+};
+
+#[derive(Debug, Parser)]
+#[command(name = "stablecoin-reconciler", version, about)]
+struct Args {
+    /// RPC URL
+    #[arg(short = 'u', long, default_value = "http://127.0.0.1:8899")]
+    url: String,
+    /// Ledger CSV, a path or an http(s):// URL
+    #[arg(long, value_name = "PATH|URL")]
+    ledger: String,
+    /// Bearer token sent when fetching the ledger from a URL
+    #[arg(long, value_name = "TOKEN", env = "STABLECOIN_LEDGER_TOKEN")]
+    ledger_token: Option<String>,
+    /// First day to reconcile, YYYY-MM-DD (UTC)
+    #[arg(long)]
+    from: Option<NaiveDate>,
+    /// Day after the last one to reconcile, YYYY-MM-DD (UTC)
+    #[arg(long)]
+    to: Option<NaiveDate>,
+    /// Write the report here instead of stdout
+    #[arg(long, value_name = "PATH")]
+    out: Option<String>,
+    /// Commitment to read at
+    #[arg(long, default_value = "finalized")]
+    commitment: CommitmentConfig,
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    match run(Args::parse()).await {
+        Ok(true) => ExitCode::SUCCESS,
+        Ok(false) => ExitCode::from(1),
+        Err(err) => {
+            eprintln!("error: {err}");
+            ExitCode::from(2)
+        }
+    }
+}
+
+/// Whether the two sides agree
+async fn run(args: Args) -> Result<bool, ReconcileError> {
+    let ledger = read_ledger(&args.ledger, args.ledger_token.as_deref()).await?;
+    let ledger = parse_ledger(&ledger)?;
+
+    let rpc = RpcClient::new_with_commitment(args.url, args.commitment);
+    let chain = fetch_chain(&rpc, args.commitment).await?;
+
+    let window = Window {
+        from: args.from,
+        to: args.to,
+    };
+    let report = reconcile(&ledger, &chain, window);
+
+    let out: Box<dyn Write> = match &args.out {
+        Some(path) => Box::new(File::create(path).map_err(|source| ReconcileError::Io {
+            path: path.clone(),
+            source,
+        })?),
+        None => Box::new(io::stdout().lock()),
+    };
+    write_report(out, &report)?;
+    eprintln!(
+        "{} matched, {} discrepancies",
+        report.matched,
+        report.discrepancies.len()
+    );
+    Ok(report.discrepancies.is_empty())
+}
@@ -0,0 +1,211 @@
+use anchor_lang::prelude::Pubkey;
+use chrono::NaiveDate;
+use solana_signature::Signature;
+use stablecoin::{TokensBurned, TokensMinted};
+use stablecoin_client::events::StablecoinEvent;
+use stablecoin_reconciler::{
+    chain::{chain_entries, parse_memo, ChainEntry},
+    ledger::{parse_ledger, Kind, LedgerEntry},
+    reconcile, write_report, Issue, ReconcileError, Window,
+};
+
+fn date(s: &str) -> NaiveDate {
+    NaiveDate::parse_from_str(s, "%Y-%m-%d").unwrap()
+}
+
+/// Midnight UTC of `s`, plus `seconds`
+fn time(s: &str, seconds: i64) -> i64 {
+    date(s).and_hms_opt(0, 0, 0).unwrap().and_utc().timestamp() + seconds
+}
+
+fn ledger(reference: &str, kind: Kind, amount: u64, day: &str) -> LedgerEntry {
+    LedgerEntry {
+        reference: reference.to_string(),
+        kind,
+        amount,
+        account: None,
+        date: date(day),
+    }
+}
+
+fn chain(reference: &str, kind: Kind, amount: u64, block_time: i64) -> ChainEntry {
+    ChainEntry {
+        reference: reference.to_string(),
+        kind,
+        amount,
+        account: Pubkey::new_from_array([3; 32]),
+        signature: Signature::default(),
+        slot: 1,
+        block_time: Some(block_time),
+    }
+}
+
+#[test]
+fn test_parse_ledger() {
+    let account = Pubkey::new_unique();
+    let csv = format!(
+        "date,reference,kind,amount,account,note\n\
+         2024-03-01,INV-1,mint,1000,{account},first\n\
+         2024-03-02, INV-2 ,BURN,250,,\n"
+    );
+    assert_eq!(
+        parse_ledger(&csv).unwrap(),
+        vec![
+            LedgerEntry {
+                account: Some(account),
+                ..ledger("INV-1", Kind::Mint, 1_000, "2024-03-01")
+            },
+            ledger("INV-2", Kind::Burn, 250, "2024-03-02"),
+        ]
+    );
+}
+
+#[test]
+fn test_parse_ledger_errors() {
+    let missing = parse_ledger("reference,kind,amount,date\n").unwrap_err();
+    assert_eq!(
+        missing.to_string(),
+        "ledger line 1: missing column \"account\""
+    );
+
+    let bad = parse_ledger(
+        "reference,kind,amount,account,date\nINV-1,mint,1000,,2024-03-01\nINV-2,mint,1.5,,2024-03-01\n",
+    )
+    .unwrap_err();
+    assert!(matches!(bad, ReconcileError::Ledger { line: 3, .. }));
+    assert_eq!(bad.to_string(), "ledger line 3: invalid amount \"1.5\"");
+}
+
+#[test]
+fn test_parse_memo() {
+    let logs = [
+        "Program ComputeBudget111111111111111111111111111111 invoke [1]",
+        "Program ComputeBudget111111111111111111111111111111 success",
+        "Program MemoSq4gqABAXKb96qnH8TysNcWxMyWCqXgDLGmfcHr invoke [1]",
+        "Program log: Memo (len 12): \"INV-\\\"7\\\" \\\\ x\"",
+        "Program MemoSq4gqABAXKb96qnH8TysNcWxMyWCqXgDLGmfcHr success",
+    ];
+    assert_eq!(parse_memo(&logs), Some("INV-\"7\" \\ x".to_string()));
+
+    // Only a memo program's log counts
+    let spoofed = [
+        "Program 11111111111111111111111111111111 invoke [1]",
+        "Program log: Memo (len 5): \"INV-7\"",
+        "Program 11111111111111111111111111111111 success",
+    ];
+    assert_eq!(parse_memo(&spoofed), None);
+}
+
+#[test]
+fn test_chain_entries() {
+    let destination = Pubkey::new_unique();
+    let token_account = Pubkey::new_unique();
+    let mint = |amount| {
+        StablecoinEvent::TokensMinted(TokensMinted {
+            minter: Pubkey::new_unique(),
+            destination,
+            amount,
+            remaining_allowance: 0,
+        })
+    };
+    let burn = StablecoinEvent::TokensBurned(TokensBurned {
+        owner: Pubkey::new_unique(),
+        token_account,
+        amount: 5,
+    });
+    let signature = Signature::from([4; 64]);
+
+    let entries = chain_entries(signature, 9, None, Some("INV-1"), &[mint(10), mint(20)]);
+    let references: Vec<_> = entries
+        .iter()
+        .map(|entry| entry.reference.as_str())
+        .collect();
+    assert_eq!(references, ["INV-1", "INV-1#1"]);
+    assert_eq!(entries[1].amount, 20);
+    assert_eq!(entries[1].account, destination);
+
+    let entries = chain_entries(signature, 9, None, None, &[burn]);
+    assert_eq!(entries.len(), 1);
+    assert_eq!(entries[0].reference, signature.to_string());
+    assert_eq!(entries[0].kind, Kind::Burn);
+    assert_eq!(entries[0].account, token_account);
+}
+
+#[test]
+fn test_reconcile() {
+    let day = "2024-03-01";
+    let ledger = [
+        ledger("matched", Kind::Mint, 100, day),
+        ledger("short", Kind::Mint, 100, day),
+        ledger("unminted", Kind::Mint, 100, day),
+        ledger("twice", Kind::Burn, 5, day),
+        ledger("twice", Kind::Burn, 5, day),
+        LedgerEntry {
+            account: Some(Pubkey::new_from_array([9; 32])),
+            ..ledger("elsewhere", Kind::Mint, 1, day)
+        },
+    ];
+    let chain = [
+        chain("matched", Kind::Mint, 100, time(day, 60)),
+        chain("short", Kind::Mint, 90, time(day, 60)),
+        chain("twice", Kind::Burn, 5, time(day, 60)),
+        chain("elsewhere", Kind::Mint, 1, time(day, 60)),
+        chain("unbooked", Kind::Burn, 7, time(day, 60)),
+    ];
+
+    let report = reconcile(&ledger, &chain, Window::default());
+    assert_eq!(report.matched, 2);
+    let issues: Vec<_> = report
+        .discrepancies
+        .iter()
+        .map(|discrepancy| (discrepancy.reference.as_str(), discrepancy.issue))
+        .collect();
+    assert_eq!(
+        issues,
+        [
+            ("elsewhere", Issue::AccountMismatch),
+            ("short", Issue::AmountMismatch),
+            ("twice", Issue::DuplicateInLedger),
+            ("unbooked", Issue::MissingFromLedger),
+            ("unminted", Issue::MissingOnChain),
+        ]
+    );
+}
+
+#[test]
+fn test_reconcile_window() {
+    let window = Window {
+        from: Some(date("2024-03-02")),
+        to: Some(date("2024-03-03")),
+    };
+    let ledger = [
+        // Booked the day before, landed in the window: still matches
+        ledger("midnight", Kind::Mint, 1, "2024-03-01"),
+        ledger("old", Kind::Mint, 1, "2024-03-01"),
+        ledger("later", Kind::Mint, 1, "2024-03-03"),
+    ];
+    let chain = [
+        chain("midnight", Kind::Mint, 1, time("2024-03-02", 5)),
+        chain("later", Kind::Mint, 2, time("2024-03-03", 5)),
+    ];
+
+    let report = reconcile(&ledger, &chain, window);
+    assert_eq!(report.matched, 1);
+    assert!(report.discrepancies.is_empty());
+}
+
+#[test]
+fn test_write_report() {
+    let report = reconcile(
+        &[ledger("INV,1", Kind::Mint, 100, "2024-03-01")],
+        &[],
+        Window::default(),
+    );
+    let mut out = Vec::new();
+    write_report(&mut out, &report).unwrap();
+    assert_eq!(
+        String::from_utf8(out).unwrap(),
+        "reference,issue,ledger_kind,ledger_amount,ledger_account,ledger_date,chain_kind,chain_amount,chain_account,block_time,signature\n\
+         \"INV,1\",missing_on_chain,mint,100,,2024-03-01,,,,,\n"
+    );
+}
@@ -0,0 +1,42 @@
+//! `gen-fixtures`: dump the canonical scenario's accounts as JSON fixtures
+//!
+//! ```text
+//! cargo run -p stablecoin-test-utils --bin gen-fixtures [OUT_DIR]
+//! ```
+//!
+//! Runs [`scenario::run`] in litesvm and writes each of
+//! [`scenario::accounts`] to `OUT_DIR/<name>.json`, in the format
+//! `solana account --output json` uses, so `dump::read` and
+//! `TestContext::load_accounts` take them as they are. `OUT_DIR` defaults to
+//! `fixtures/scenario` at the workspace root. Rerun after changing the
+//! program or the scenario and commit the result.
+
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+};
+
+use stablecoin_test_utils::{dump, scenario};
+
+fn main() {
+    let out_dir = env::args_os().nth(1).map(PathBuf::from).unwrap_or_else(|| {
+        let workspace = Path::new(env!("CARGO_MANIFEST_DIR"))
+            .ancestors()
+            .nth(2)
+            .unwrap();
+        workspace.join("fixtures/scenario")
+    });
+    fs::create_dir_all(&out_dir)
+        .unwrap_or_else(|err| panic!("failed to create {}: {err}", out_dir.display()));
+
+    let ctx = scenario::run();
+    for (name, address) in scenario::accounts() {
+        let account = ctx
+            .svm
+            .get_account(&address)
+            .unwrap_or_else(|| panic!("the scenario did not create {name} at {address}"));
+        let path = out_dir.join(format!("{name}.json"));
+        dump::write(&path, &address, &account);
+        println!("{}", path.display());
+    }
+}
@@ -0,0 +1,383 @@
+//! What a transaction did to the program's accounts
+//!
+//! A [`Snapshot`] copies every account the program owns, with the
+//! stablecoin mint and its token accounts, out of litesvm.
+//! [`Snapshot::diff`] compares two of them: which accounts were created or
+//! closed, how their lamports moved, and which fields changed, decoded as
+//! the program's and SPL Token's own types. [`TestContext::execute_with_diff`]
+//! takes the snapshots around a single instruction.
+//!
+//! Fields are compared by their `Debug` output, so values read as they do in
+//! a failed `assert_eq!`. The diff's `Display` lists every change, for
+//! printing when a test needs to see what happened:
+//!
+//! ```text
+//! created Airdrop <address> +2004480 lamports
+//!     funder: <funder>
+//!     id: 1
+//!     ...
+//! modified TokenAccount <address>
+//!     amount: 1000 -> 500
+//! ```
+//!
+//! [`TestContext::execute_with_diff`]: crate::TestContext::execute_with_diff
+
+use std::{collections::BTreeMap, fmt};
+
+use anchor_lang::{prelude::Pubkey, AccountDeserialize, Discriminator};
+use anchor_litesvm::LiteSVM;
+use anchor_spl::token::{spl_token, Mint, TokenAccount};
+use solana_account::Account;
+use stablecoin::{
+    Airdrop, ChannelCounter, Config, IdempotencyRecord, InheritancePlan, MinterConfig, MinterPause,
+    MinterSetFreeze, MinterTombstone, NettingWindow, PaymentChannel, PendingBurn, PurposeCode,
+    RecoveryConfig, SubAccount, SupplyStats, Treasury,
+};
+
+use crate::{pda, PROGRAM_ID};
+
+/// The program's accounts and the stablecoin's token accounts at one
+/// moment
+#[derive(Debug, Clone, Default)]
+pub struct Snapshot {
+    accounts: BTreeMap<Pubkey, Account>,
+}
+
+impl Snapshot {
+    /// Copy the accounts out of `svm`
+    pub fn take(svm: &LiteSVM) -> Self {
+        let mint = pda::mint().0;
+        let accounts = svm
+            .accounts_db()
+            .inner
+            .iter()
+            .map(|(address, account)| (*address, Account::from(account.clone())))
+            .filter(|(address, account)| {
+                account.owner == PROGRAM_ID
+                    || account.owner == spl_token::ID
+                        && (*address == mint || is_token_account_of(&mint, &account.data))
+            })
+            .collect();
+        Self { accounts }
+    }
+
+    /// The account at `address`, if it existed and is one the snapshot
+    /// keeps
+    pub fn get(&self, address: &Pubkey) -> Option<&Account> {
+        self.accounts.get(address)
+    }
+
+    /// What changed from `self` to `after`, in address order
+    pub fn diff(&self, after: &Snapshot) -> AccountDiff {
+        let mut addresses: Vec<_> = self.accounts.keys().chain(after.accounts.keys()).collect();
+        addresses.sort();
+        addresses.dedup();
+        let changes = addresses
+            .into_iter()
+            .filter_map(|address| {
+                AccountChange::new(*address, self.get(address), after.get(address))
+            })
+            .collect();
+        AccountDiff { changes }
+    }
+}
+
+/// Whether `data` is an SPL Token account of `mint`
+fn is_token_account_of(mint: &Pubkey, data: &[u8]) -> bool {
+    data.len() == TokenAccount::LEN && data[..32] == mint.to_bytes()
+}
+
+/// Every account a transaction created, closed or modified
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AccountDiff {
+    pub changes: Vec<AccountChange>,
+}
+
+impl AccountDiff {
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+
+    /// The change to the account at `address`, if it changed
+    pub fn get(&self, address: &Pubkey) -> Option<&AccountChange> {
+        self.changes
+            .iter()
+            .find(|change| change.address == *address)
+    }
+
+    pub fn created(&self) -> impl Iterator<Item = &AccountChange> {
+        self.with_status(Status::Created)
+    }
+
+    pub fn closed(&self) -> impl Iterator<Item = &AccountChange> {
+        self.with_status(Status::Closed)
+    }
+
+    pub fn modified(&self) -> impl Iterator<Item = &AccountChange> {
+        self.with_status(Status::Modified)
+    }
+
+    fn with_status(&self, status: Status) -> impl Iterator<Item = &AccountChange> {
+        self.changes
+            .iter()
+            .filter(move |change| change.status == status)
+    }
+}
+
+impl fmt::Display for AccountDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_empty() {
+            return writeln!(f, "no accounts changed");
+        }
+        for change in &self.changes {
+            write!(f, "{change}")?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Status {
+    Created,
+    Closed,
+    Modified,
+}
+
+/// One account's change
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AccountChange {
+    pub address: Pubkey,
+    pub status: Status,
+    /// The type the account decodes as, `"Unknown"` if none; a closed
+    /// account's type before it closed
+    pub kind: &'static str,
+    /// Zero for an account that did not exist
+    pub lamports_before: u64,
+    /// Zero for a closed account
+    pub lamports_after: u64,
+    /// Every field of a created or closed account, and the fields that
+    /// changed in a modified one, in declaration order
+    pub fields: Vec<FieldChange>,
+}
+
+impl AccountChange {
+    fn new(address: Pubkey, before: Option<&Account>, after: Option<&Account>) -> Option<Self> {
+        let status = match (before, after) {
+            (None, None) => return None,
+            (None, Some(_)) => Status::Created,
+            (Some(_), None) => Status::Closed,
+            (Some(before), Some(after)) if before == after => return None,
+            (Some(_), Some(_)) => Status::Modified,
+        };
+        let (kind, before_fields) = before.map(decode).unwrap_or_default();
+        let (after_kind, after_fields) = after.map(decode).unwrap_or_default();
+        let fields = match status {
+            Status::Created => after_fields
+                .into_iter()
+                .map(|(name, value)| FieldChange {
+                    name,
+                    before: None,
+                    after: Some(value),
+                })
+                .collect(),
+            Status::Closed => before_fields
+                .into_iter()
+                .map(|(name, value)| FieldChange {
+                    name,
+                    before: Some(value),
+                    after: None,
+                })
+                .collect(),
+            Status::Modified => {
+                let mut before_fields: BTreeMap<_, _> = before_fields.into_iter().collect();
+                let mut fields: Vec<_> = after_fields
+                    .into_iter()
+                    .filter_map(|(name, value)| {
+                        let before = before_fields.remove(&name);
+                        (before.as_ref() != Some(&value)).then_some(FieldChange {
+                            name,
+                            before,
+                            after: Some(value),
+                        })
+                    })
+                    .collect();
+                // Fields only the old type had, if the account changed type
+                fields.extend(before_fields.into_iter().map(|(name, value)| FieldChange {
+                    name,
+                    before: Some(value),
+                    after: None,
+                }));
+                fields
+            }
+        };
+        Some(Self {
+            address,
+            status,
+            kind: if status == Status::Closed {
+                kind
+            } else {
+                after_kind
+            },
+            lamports_before: before.map_or(0, |account| account.lamports),
+            lamports_after: after.map_or(0, |account| account.lamports),
+            fields,
+        })
+    }
+
+    /// How far the account's lamports moved
+    pub fn lamport_delta(&self) -> i128 {
+        i128::from(self.lamports_after) - i128::from(self.lamports_before)
+    }
+
+    /// The change to the field `name`, if it changed
+    pub fn field(&self, name: &str) -> Option<&FieldChange> {
+        self.fields.iter().find(|field| field.name == name)
+    }
+}
+
+impl fmt::Display for AccountChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let status = match self.status {
+            Status::Created => "created",
+            Status::Closed => "closed",
+            Status::Modified => "modified",
+        };
+        write!(f, "{status} {} {}", self.kind, self.address)?;
+        match self.lamport_delta() {
+            0 => writeln!(f)?,
+            delta => writeln!(f, " {delta:+} lamports")?,
+        }
+        for field in &self.fields {
+            writeln!(f, "    {field}")?;
+        }
+        Ok(())
+    }
+}
+
+/// One field's value before and after; `None` where the account, or the
+/// field, did not exist
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FieldChange {
+    pub name: String,
+    pub before: Option<String>,
+    pub after: Option<String>,
+}
+
+impl fmt::Display for FieldChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (&self.before, &self.after) {
+            (Some(before), Some(after)) => write!(f, "{}: {before} -> {after}", self.name),
+            (Some(value), None) | (None, Some(value)) => write!(f, "{}: {value}", self.name),
+            (None, None) => write!(f, "{}", self.name),
+        }
+    }
+}
+
+/// The type `account` decodes as and its fields
+///
+/// An account no type decodes gets a single `data` field, in hex.
+fn decode(account: &Account) -> (&'static str, Vec<(String, String)>) {
+    fn fields_of<T: AccountDeserialize + fmt::Debug>(data: &[u8]) -> Option<Vec<(String, String)>> {
+        T::try_deserialize(&mut &data[..])
+            .ok()
+            .map(|state| debug_fields(&format!("{state:?}")))
+    }
+
+    let data = &account.data[..];
+    let decoded = if account.owner == spl_token::ID {
+        if data.len() == Mint::LEN {
+            Mint::try_deserialize(&mut &data[..])
+                .ok()
+                .map(|mint| ("Mint", debug_fields(&format!("{:?}", *mint))))
+        } else {
+            TokenAccount::try_deserialize(&mut &data[..])
+                .ok()
+                .map(|token| ("TokenAccount", debug_fields(&format!("{:?}", *token))))
+        }
+    } else {
+        macro_rules! program_accounts {
+            ($($ty:ident),* $(,)?) => {
+                match data.get(..8) {
+                    $(Some(discriminator) if discriminator == $ty::DISCRIMINATOR => {
+                        fields_of::<$ty>(data).map(|fields| (stringify!($ty), fields))
+                    })*
+                    _ => None,
+                }
+            };
+        }
+        program_accounts!(
+            Config,
+            MinterConfig,
+            IdempotencyRecord,
+            Treasury,
+            PendingBurn,
+            RecoveryConfig,
+            InheritancePlan,
+            NettingWindow,
+            PurposeCode,
+            MinterSetFreeze,
+            MinterPause,
+            MinterTombstone,
+            PaymentChannel,
+            ChannelCounter,
+            SubAccount,
+            Airdrop,
+            SupplyStats,
+        )
+    };
+    #[cfg(feature = "devnet-faucet")]
+    let decoded = decoded.or_else(|| {
+        (data.get(..8) == Some(stablecoin::FaucetClaim::DISCRIMINATOR))
+            .then(|| fields_of::<stablecoin::FaucetClaim>(data))
+            .flatten()
+            .map(|fields| ("FaucetClaim", fields))
+    });
+    decoded.unwrap_or_else(|| {
+        let hex = data.iter().map(|byte| format!("{byte:02x}")).collect();
+        ("Unknown", vec![("data".to_string(), hex)])
+    })
+}
+
+/// The top-level fields of a struct's `Debug` output,
+/// `Name { a: 1, b: [2, 3] }`, as `[("a", "1"), ("b", "[2, 3]")]`
+fn debug_fields(debug: &str) -> Vec<(String, String)> {
+    let Some(body) = debug
+        .split_once(" { ")
+        .and_then(|(_, body)| body.strip_suffix(" }"))
+    else {
+        return Vec::new();
+    };
+    let mut fields = Vec::new();
+    let mut depth = 0usize;
+    let mut in_string = false;
+    let mut escaped = false;
+    let mut start = 0;
+    let mut push = |field: &str| {
+        if let Some((name, value)) = field.split_once(": ") {
+            fields.push((name.to_string(), value.to_string()));
+        }
+    };
+    for (i, c) in body.char_indices() {
+        if in_string {
+            match c {
+                _ if escaped => escaped = false,
+                '\\' => escaped = true,
+                '"' => in_string = false,
+                _ => {}
+            }
+            continue;
+        }
+        match c {
+            '"' => in_string = true,
+            '(' | '[' | '{' => depth += 1,
+            ')' | ']' | '}' => depth -= 1,
+            ',' if depth == 0 => {
+                push(&body[start..i]);
+                start = i + 2;
+            }
+            _ => {}
+        }
+    }
+    push(&body[start..]);
+    fields
+}
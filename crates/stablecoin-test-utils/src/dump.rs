@@ -0,0 +1,129 @@
+//! Accounts dumped from a live cluster
+//!
+//! Two formats are read, told apart by extension:
+//!
+//! - `.json`, as written by
+//!   `solana account <ADDRESS> --output json --output-file <ADDRESS>.json`,
+//!   the same files `solana-test-validator --account` takes. The address is
+//!   read from the file.
+//! - `.bin`, a bincode-serialized [`Account`], named `<ADDRESS>.bin`.
+//!
+//! Anything malformed panics with the path, as the rest of the harness does.
+//! [`write`] produces the `.json` format.
+
+use std::{fs, path::Path};
+
+use anchor_lang::prelude::Pubkey;
+use base64::{engine::general_purpose::STANDARD, Engine as _};
+use serde::{Deserialize, Serialize};
+use solana_account::Account;
+
+/// `solana account --output json`
+#[derive(Serialize, Deserialize)]
+struct CliAccount {
+    pubkey: String,
+    account: UiAccount,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct UiAccount {
+    lamports: u64,
+    /// `[data, encoding]`
+    data: (String, String),
+    owner: String,
+    executable: bool,
+    rent_epoch: u64,
+    /// Only informational; the data's length is what counts
+    #[serde(default)]
+    space: u64,
+}
+
+/// The address and account in the dump at `path`
+pub fn read(path: impl AsRef<Path>) -> (Pubkey, Account) {
+    let path = path.as_ref();
+    let bytes =
+        fs::read(path).unwrap_or_else(|err| panic!("failed to read {}: {err}", path.display()));
+    let fail = |what: &dyn std::fmt::Display| -> ! {
+        panic!("invalid account dump {}: {what}", path.display())
+    };
+
+    match path.extension().and_then(|ext| ext.to_str()) {
+        Some("json") => {
+            let dump: CliAccount = serde_json::from_slice(&bytes).unwrap_or_else(|err| fail(&err));
+            let UiAccount {
+                lamports,
+                data: (data, encoding),
+                owner,
+                executable,
+                rent_epoch,
+                space: _,
+            } = dump.account;
+            let data = match encoding.as_str() {
+                "base64" => STANDARD.decode(data).unwrap_or_else(|err| fail(&err)),
+                "base58" => bs58::decode(data)
+                    .into_vec()
+                    .unwrap_or_else(|err| fail(&err)),
+                other => fail(&format!("unsupported encoding {other:?}, dump as base64")),
+            };
+            let account = Account {
+                lamports,
+                data,
+                owner: owner.parse().ok().unwrap_or_else(|| fail(&"invalid owner")),
+                executable,
+                rent_epoch,
+            };
+            let address = dump
+                .pubkey
+                .parse()
+                .ok()
+                .unwrap_or_else(|| fail(&"invalid pubkey"));
+            (address, account)
+        }
+        Some("bin") => {
+            let address = path
+                .file_stem()
+                .and_then(|stem| stem.to_str()?.parse().ok())
+                .unwrap_or_else(|| fail(&"file name is not an address"));
+            let account = bincode::deserialize(&bytes).unwrap_or_else(|err| fail(&err));
+            (address, account)
+        }
+        _ => fail(&"expected a .json or .bin file"),
+    }
+}
+
+/// Write `account` at `address` to `path` as `solana account --output json`
+/// does, base64-encoded
+pub fn write(path: impl AsRef<Path>, address: &Pubkey, account: &Account) {
+    let path = path.as_ref();
+    let dump = CliAccount {
+        pubkey: address.to_string(),
+        account: UiAccount {
+            lamports: account.lamports,
+            data: (STANDARD.encode(&account.data), "base64".to_string()),
+            owner: account.owner.to_string(),
+            executable: account.executable,
+            rent_epoch: account.rent_epoch,
+            space: account.data.len() as u64,
+        },
+    };
+    let json = serde_json::to_string_pretty(&dump).unwrap() + "\n";
+    fs::write(path, json).unwrap_or_else(|err| panic!("failed to write {}: {err}", path.display()));
+}
+
+/// Every dump in `dir`, in file name order
+pub fn read_dir(dir: impl AsRef<Path>) -> Vec<(Pubkey, Account)> {
+    let dir = dir.as_ref();
+    let mut paths: Vec<_> = fs::read_dir(dir)
+        .unwrap_or_else(|err| panic!("failed to read {}: {err}", dir.display()))
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| {
+            matches!(
+                path.extension().and_then(|ext| ext.to_str()),
+                Some("json" | "bin")
+            )
+        })
+        .collect();
+    paths.sort();
+    paths.into_iter().map(read).collect()
+}
@@ -0,0 +1,718 @@
+//! litesvm harness for the stablecoin program's tests
+//!
+//! [`TestContext`] loads the built program, building it on first use if
+//! there is none (see [`program`]), and sends the client's instructions to it.
+//! The chained steps sign as the right party and assert success:
+//!
+//! ```no_run
+//! # use stablecoin_test_utils::TestContext;
+//! let mut ctx = TestContext::new();
+//! let minter = ctx.funded_account();
+//! let user = ctx.funded_account();
+//! ctx.initialize()
+//!     .configure_minter(&minter, 1_000)
+//!     .mint(&minter, &user, 100);
+//! ```
+//!
+//! Scenarios that should fail build their instruction with
+//! [`instructions`], swapping in accounts of the attacker's choosing with
+//! [`substitute`], and check the result of [`TestContext::execute`].
+//! [`TestContext::config`] and its siblings read state back as the program's
+//! and SPL Token's own types, for asserting on fields.
+//! [`TestContext::advance_time`] and the `warp_to_*` methods move the Clock
+//! sysvar for behavior that depends on time.
+//! [`TestContext::execute_with_diff`] reports what an instruction did to
+//! the program's accounts, through [`diff`].
+//! [`TestContext::load_accounts`] brings in accounts [`dump`]ed from a live
+//! cluster, for testing upgrades and migrations against deployed state.
+//! [`scenario`] is the canonical known-good state, shared with other crates'
+//! tests through the fixtures the `gen-fixtures` binary dumps from it.
+//! [`paths`] runs every instruction, for checks that must cover the whole
+//! program.
+
+use std::{
+    ops::{Deref, DerefMut},
+    path::Path,
+};
+
+use anchor_lang::{
+    prelude::{Clock, Pubkey},
+    solana_program::system_instruction,
+    AccountDeserialize, AccountSerialize,
+};
+use anchor_litesvm::{
+    AnchorContext, AnchorLiteSVM, Instruction, Keypair, Signer, TransactionResult,
+};
+use anchor_spl::token::{spl_token, Mint, TokenAccount};
+use litesvm_utils::TestHelpers;
+use solana_account::Account;
+use stablecoin::{
+    channel_state_message, Airdrop, Config, IdempotencyRecord, InheritancePlan, MinterConfig,
+    NettingWindow, PaymentChannel, PendingBurn, RecoveryConfig, SubAccount, SupplyStats, Treasury,
+    MIN_CHANNEL_DISPUTE_PERIOD, MIN_INHERITANCE_NOTICE_PERIOD, MIN_RECOVERY_CHALLENGE_PERIOD,
+};
+pub use stablecoin_client::{instructions, pda, PROGRAM_ID};
+
+use crate::diff::{AccountDiff, Snapshot};
+
+pub mod diff;
+pub mod dump;
+pub mod paths;
+pub mod program;
+pub mod scenario;
+
+/// Lamports given to every account the harness funds
+pub const FUNDING: u64 = 10_000_000_000;
+
+/// Slot time the clock helpers assume, as on mainnet
+pub const SLOT_DURATION_MS: u64 = 400;
+
+/// `instruction` with every account at `from` replaced by `to`, for
+/// passing the program accounts it did not ask for
+///
+/// Panics if `from` is not one of the instruction's accounts.
+pub fn substitute(mut instruction: Instruction, from: &Pubkey, to: &Pubkey) -> Instruction {
+    let mut found = false;
+    for meta in instruction
+        .accounts
+        .iter_mut()
+        .filter(|meta| meta.pubkey == *from)
+    {
+        meta.pubkey = *to;
+        found = true;
+    }
+    assert!(found, "{from} is not an account of the instruction");
+    instruction
+}
+
+/// A litesvm instance with the program deployed and a funded admin
+///
+/// Derefs to the underlying [`AnchorContext`], so `ctx.svm` and the
+/// litesvm-utils assertions stay at hand.
+pub struct TestContext {
+    inner: AnchorContext,
+    pub admin: Keypair,
+}
+
+impl TestContext {
+    /// A fresh program deployment; nothing is initialized yet
+    pub fn new() -> Self {
+        Self::with_admin(Keypair::new())
+    }
+
+    /// A fresh deployment whose admin is `admin`, funded with [`FUNDING`],
+    /// for state that must come out the same on every run
+    pub fn with_admin(admin: Keypair) -> Self {
+        let mut inner = AnchorLiteSVM::build_with_program(PROGRAM_ID, program::elf());
+        inner.svm.airdrop(&admin.pubkey(), FUNDING).unwrap();
+        Self { inner, admin }
+    }
+
+    /// A new keypair holding [`FUNDING`] lamports
+    pub fn funded_account(&mut self) -> Keypair {
+        self.inner.svm.create_funded_account(FUNDING).unwrap()
+    }
+
+    /// Send `instruction`, the first signer paying, without checking the outcome
+    pub fn execute(
+        &mut self,
+        instruction: anchor_litesvm::Instruction,
+        signers: &[&Keypair],
+    ) -> TransactionResult {
+        self.inner
+            .execute_instruction(instruction, signers)
+            .unwrap()
+    }
+
+    /// Send `instructions` as one transaction, the first signer paying,
+    /// without checking the outcome
+    pub fn execute_all(
+        &mut self,
+        instructions: Vec<anchor_litesvm::Instruction>,
+        signers: &[&Keypair],
+    ) -> TransactionResult {
+        self.inner
+            .execute_instructions(instructions, signers)
+            .unwrap()
+    }
+
+    /// [`execute`](Self::execute), also returning what the transaction did
+    /// to the program's accounts
+    pub fn execute_with_diff(
+        &mut self,
+        instruction: anchor_litesvm::Instruction,
+        signers: &[&Keypair],
+    ) -> (TransactionResult, AccountDiff) {
+        let before = self.snapshot();
+        let result = self.execute(instruction, signers);
+        (result, before.diff(&self.snapshot()))
+    }
+
+    /// Send `instruction` and assert it succeeded
+    pub fn expect_success(
+        &mut self,
+        instruction: anchor_litesvm::Instruction,
+        signers: &[&Keypair],
+    ) -> TransactionResult {
+        let result = self.execute(instruction, signers);
+        result.assert_success();
+        result
+    }
+
+    /// Send `instruction` and assert it failed
+    pub fn expect_failure(
+        &mut self,
+        instruction: anchor_litesvm::Instruction,
+        signers: &[&Keypair],
+    ) -> TransactionResult {
+        let result = self.execute(instruction, signers);
+        assert!(!result.is_success(), "expected the transaction to fail");
+        result
+    }
+
+    pub fn initialize(&mut self) -> &mut Self {
+        let admin = self.admin.insecure_clone();
+        self.expect_success(instructions::initialize(&admin.pubkey()), &[&admin]);
+        self
+    }
+
+    pub fn configure_minter(&mut self, minter: &Keypair, allowance: u64) -> &mut Self {
+        let admin = self.admin.insecure_clone();
+        self.expect_success(
+            instructions::configure_minter(&admin.pubkey(), &minter.pubkey(), allowance),
+            &[&admin],
+        );
+        self
+    }
+
+    pub fn remove_minter(&mut self, minter: &Keypair) -> &mut Self {
+        let admin = self.admin.insecure_clone();
+        self.expect_success(
+            instructions::remove_minter(&admin.pubkey(), &minter.pubkey()),
+            &[&admin],
+        );
+        self
+    }
+
+    pub fn freeze_minter_set(&mut self) -> &mut Self {
+        let admin = self.admin.insecure_clone();
+        self.expect_success(instructions::freeze_minter_set(&admin.pubkey()), &[&admin]);
+        self
+    }
+
+    pub fn unfreeze_minter_set(&mut self) -> &mut Self {
+        let admin = self.admin.insecure_clone();
+        self.expect_success(
+            instructions::unfreeze_minter_set(&admin.pubkey(), &admin.pubkey()),
+            &[&admin],
+        );
+        self
+    }
+
+    pub fn pause_self(&mut self, minter: &Keypair) -> &mut Self {
+        self.expect_success(instructions::pause_self(&minter.pubkey()), &[minter]);
+        self
+    }
+
+    pub fn resume_minter(&mut self, minter: &Keypair) -> &mut Self {
+        let admin = self.admin.insecure_clone();
+        self.expect_success(
+            instructions::resume_minter(&admin.pubkey(), &minter.pubkey()),
+            &[&admin],
+        );
+        self
+    }
+
+    /// Mint to `owner`'s associated token account, creating it if needed
+    pub fn mint(&mut self, minter: &Keypair, owner: &Keypair, amount: u64) -> &mut Self {
+        self.expect_success(
+            instructions::mint_tokens(&minter.pubkey(), &owner.pubkey(), amount),
+            &[minter],
+        );
+        self
+    }
+
+    /// Burn from `owner`'s associated token account
+    pub fn burn(&mut self, owner: &Keypair, amount: u64) -> &mut Self {
+        self.expect_success(instructions::burn_tokens(&owner.pubkey(), amount), &[owner]);
+        self
+    }
+
+    pub fn configure_treasury(&mut self, officers: [&Keypair; 2], threshold: u64) -> &mut Self {
+        let admin = self.admin.insecure_clone();
+        self.expect_success(
+            instructions::configure_treasury(
+                &admin.pubkey(),
+                officers.map(|officer| officer.pubkey()),
+                threshold,
+            ),
+            &[&admin],
+        );
+        self
+    }
+
+    /// Opt `owner` into social recovery with the shortest challenge period
+    pub fn configure_recovery(
+        &mut self,
+        owner: &Keypair,
+        guardians: &[&Keypair],
+        quorum: u8,
+    ) -> &mut Self {
+        self.expect_success(
+            instructions::configure_recovery(
+                &owner.pubkey(),
+                guardians.iter().map(|guardian| guardian.pubkey()).collect(),
+                quorum,
+                MIN_RECOVERY_CHALLENGE_PERIOD,
+            ),
+            &[owner],
+        );
+        self
+    }
+
+    /// Opt `owner` into inheritance by `beneficiary` with the shortest
+    /// notice period
+    pub fn configure_inheritance(
+        &mut self,
+        owner: &Keypair,
+        beneficiary: &Keypair,
+        inactivity_epochs: u64,
+    ) -> &mut Self {
+        self.expect_success(
+            instructions::configure_inheritance(
+                &owner.pubkey(),
+                &beneficiary.pubkey(),
+                inactivity_epochs,
+                MIN_INHERITANCE_NOTICE_PERIOD,
+            ),
+            &[owner],
+        );
+        self
+    }
+
+    /// Open netting window `id` between `institutions` as the admin
+    pub fn open_netting_window(
+        &mut self,
+        id: u64,
+        institutions: &[&Keypair],
+        duration: i64,
+    ) -> &mut Self {
+        let admin = self.admin.insecure_clone();
+        self.expect_success(
+            instructions::open_netting_window(
+                &admin.pubkey(),
+                id,
+                institutions
+                    .iter()
+                    .map(|institution| institution.pubkey())
+                    .collect(),
+                duration,
+            ),
+            &[&admin],
+        );
+        self
+    }
+
+    /// Queue `debtor`'s obligation of `amount` to `creditor` in window `id`
+    pub fn queue_obligation(
+        &mut self,
+        debtor: &Keypair,
+        id: u64,
+        creditor: &Keypair,
+        amount: u64,
+    ) -> &mut Self {
+        self.expect_success(
+            instructions::queue_obligation(&debtor.pubkey(), id, &creditor.pubkey(), amount, None),
+            &[debtor],
+        );
+        self
+    }
+
+    /// Register purpose code `code` as the admin
+    pub fn register_purpose_code(&mut self, code: [u8; 4]) -> &mut Self {
+        let admin = self.admin.insecure_clone();
+        self.expect_success(
+            instructions::register_purpose_code(&admin.pubkey(), code),
+            &[&admin],
+        );
+        self
+    }
+
+    /// Open a payment channel from `sender` to `recipient` with the
+    /// shortest dispute period
+    pub fn open_channel(
+        &mut self,
+        sender: &Keypair,
+        recipient: &Keypair,
+        deposit: u64,
+    ) -> &mut Self {
+        self.expect_success(
+            instructions::open_channel(
+                &sender.pubkey(),
+                &recipient.pubkey(),
+                deposit,
+                MIN_CHANNEL_DISPUTE_PERIOD,
+            ),
+            &[sender],
+        );
+        self
+    }
+
+    pub fn register_sub_account(
+        &mut self,
+        owner: &Keypair,
+        index: u64,
+        purpose: [u8; 4],
+    ) -> &mut Self {
+        self.expect_success(
+            instructions::register_sub_account(&owner.pubkey(), index, &purpose),
+            &[owner],
+        );
+        self
+    }
+
+    pub fn sweep_sub_account(&mut self, owner: &Keypair, index: u64) -> &mut Self {
+        self.expect_success(
+            instructions::sweep_sub_account(&owner.pubkey(), index),
+            &[owner],
+        );
+        self
+    }
+
+    /// Open `funder`'s airdrop `id`, paying each recipient its amount
+    pub fn open_airdrop(
+        &mut self,
+        funder: &Keypair,
+        id: u64,
+        recipients: &[(&Keypair, u64)],
+        duration: i64,
+    ) -> &mut Self {
+        self.expect_success(
+            instructions::open_airdrop(
+                &funder.pubkey(),
+                id,
+                recipients
+                    .iter()
+                    .map(|(recipient, _)| recipient.pubkey())
+                    .collect(),
+                recipients.iter().map(|(_, amount)| *amount).collect(),
+                duration,
+            ),
+            &[funder],
+        );
+        self
+    }
+
+    pub fn claim_airdrop(&mut self, recipient: &Keypair, funder: &Keypair, id: u64) -> &mut Self {
+        self.expect_success(
+            instructions::claim_airdrop(&recipient.pubkey(), &funder.pubkey(), id),
+            &[recipient],
+        );
+        self
+    }
+
+    /// `sender`'s signature over the balance update paying `amount` on
+    /// their open channel to `recipient`
+    pub fn sign_channel_state(
+        &self,
+        sender: &Keypair,
+        recipient: &Keypair,
+        amount: u64,
+    ) -> [u8; 64] {
+        let channel = pda::payment_channel(&sender.pubkey(), &recipient.pubkey()).0;
+        let nonce = self.payment_channel(sender, recipient).nonce;
+        sender
+            .sign_message(&channel_state_message(&channel, nonce, amount))
+            .into()
+    }
+
+    /// The Ed25519 check and the close submitting the balance update paying
+    /// `amount`, signed by `sender`, as `party` sends them
+    pub fn close_channel_instructions(
+        &self,
+        party: &Keypair,
+        sender: &Keypair,
+        recipient: &Keypair,
+        amount: u64,
+    ) -> Vec<anchor_litesvm::Instruction> {
+        let signature = self.sign_channel_state(sender, recipient, amount);
+        let nonce = self.payment_channel(sender, recipient).nonce;
+        vec![
+            instructions::verify_channel_state(
+                &sender.pubkey(),
+                &recipient.pubkey(),
+                nonce,
+                amount,
+                &signature,
+            ),
+            instructions::close_channel(
+                &party.pubkey(),
+                &sender.pubkey(),
+                &recipient.pubkey(),
+                amount,
+                signature,
+            ),
+        ]
+    }
+
+    /// Open a token account at `account` for `owner`, besides its
+    /// associated one, and move `amount` into it from the associated one
+    pub fn open_token_account(
+        &mut self,
+        account: &Keypair,
+        owner: &Keypair,
+        amount: u64,
+    ) -> &mut Self {
+        let rent = self
+            .inner
+            .svm
+            .minimum_balance_for_rent_exemption(TokenAccount::LEN);
+        let mut instructions = vec![
+            system_instruction::create_account(
+                &owner.pubkey(),
+                &account.pubkey(),
+                rent,
+                TokenAccount::LEN as u64,
+                &spl_token::ID,
+            ),
+            spl_token::instruction::initialize_account3(
+                &spl_token::ID,
+                &account.pubkey(),
+                &pda::mint().0,
+                &owner.pubkey(),
+            )
+            .unwrap(),
+        ];
+        if amount > 0 {
+            instructions.push(
+                spl_token::instruction::transfer(
+                    &spl_token::ID,
+                    &self.token_account(owner),
+                    &account.pubkey(),
+                    &owner.pubkey(),
+                    &[],
+                    amount,
+                )
+                .unwrap(),
+            );
+        }
+        self.execute_all(instructions, &[owner, account])
+            .assert_success();
+        self
+    }
+
+    pub fn pause(&mut self) -> &mut Self {
+        let admin = self.admin.insecure_clone();
+        self.expect_success(instructions::pause(&admin.pubkey()), &[&admin]);
+        self
+    }
+
+    pub fn unpause(&mut self) -> &mut Self {
+        let admin = self.admin.insecure_clone();
+        self.expect_success(instructions::unpause(&admin.pubkey()), &[&admin]);
+        self
+    }
+
+    /// The Clock sysvar the program reads
+    pub fn clock(&self) -> Clock {
+        self.inner.svm.get_sysvar()
+    }
+
+    /// Replace the Clock sysvar
+    ///
+    /// The blockhash moves on too, so a transaction repeated after the
+    /// change is not rejected as already processed.
+    pub fn set_clock(&mut self, clock: &Clock) -> &mut Self {
+        self.inner.svm.set_sysvar(clock);
+        self.inner.svm.expire_blockhash();
+        self
+    }
+
+    /// Jump to `slot`, leaving the timestamp as it is
+    pub fn warp_to_slot(&mut self, slot: u64) -> &mut Self {
+        let clock = Clock {
+            slot,
+            ..self.clock()
+        };
+        self.set_clock(&clock)
+    }
+
+    /// Jump to `unix_timestamp`, leaving the slot as it is
+    pub fn warp_to_timestamp(&mut self, unix_timestamp: i64) -> &mut Self {
+        let clock = Clock {
+            unix_timestamp,
+            ..self.clock()
+        };
+        self.set_clock(&clock)
+    }
+
+    /// Let `seconds` pass: the timestamp moves by that much and the slot by
+    /// the slots that fit in it
+    pub fn advance_time(&mut self, seconds: u64) -> &mut Self {
+        let clock = self.clock();
+        let clock = Clock {
+            slot: clock.slot + seconds * 1000 / SLOT_DURATION_MS,
+            unix_timestamp: clock.unix_timestamp + seconds as i64,
+            ..clock
+        };
+        self.set_clock(&clock)
+    }
+
+    /// Move the clock `epochs` epochs forward, leaving the slot and
+    /// timestamp alone
+    pub fn advance_epochs(&mut self, epochs: u64) -> &mut Self {
+        let clock = self.clock();
+        let clock = Clock {
+            epoch: clock.epoch + epochs,
+            ..clock
+        };
+        self.set_clock(&clock)
+    }
+
+    /// `owner`'s associated token account for the stablecoin
+    pub fn token_account(&self, owner: &Keypair) -> Pubkey {
+        pda::token_account(&owner.pubkey())
+    }
+
+    /// The program's accounts and the stablecoin's token accounts as they
+    /// stand, for [`Snapshot::diff`]
+    pub fn snapshot(&self) -> Snapshot {
+        Snapshot::take(&self.inner.svm)
+    }
+
+    /// Deserialize the account at `address`, panicking if it is missing or
+    /// not a `T`
+    pub fn account<T: AccountDeserialize>(&self, address: &Pubkey) -> T {
+        self.inner
+            .get_account(address)
+            .unwrap_or_else(|err| panic!("failed to read account {address}: {err}"))
+    }
+
+    pub fn config(&self) -> Config {
+        self.account(&pda::config().0)
+    }
+
+    pub fn minter_config(&self, minter: &Keypair) -> MinterConfig {
+        self.account(&pda::minter_config(&minter.pubkey()).0)
+    }
+
+    pub fn idempotency_record(&self, minter: &Keypair, key: &[u8; 16]) -> IdempotencyRecord {
+        self.account(&pda::idempotency_record(&minter.pubkey(), key).0)
+    }
+
+    pub fn treasury(&self) -> Treasury {
+        self.account(&pda::treasury().0)
+    }
+
+    pub fn pending_burn(&self, id: u64) -> PendingBurn {
+        self.account(&pda::pending_burn(id).0)
+    }
+
+    pub fn recovery_config(&self, owner: &Keypair) -> RecoveryConfig {
+        self.account(&pda::recovery_config(&owner.pubkey()).0)
+    }
+
+    pub fn inheritance_plan(&self, owner: &Keypair) -> InheritancePlan {
+        self.account(&pda::inheritance_plan(&owner.pubkey()).0)
+    }
+
+    pub fn netting_window(&self, id: u64) -> NettingWindow {
+        self.account(&pda::netting_window(id).0)
+    }
+
+    pub fn payment_channel(&self, sender: &Keypair, recipient: &Keypair) -> PaymentChannel {
+        self.account(&pda::payment_channel(&sender.pubkey(), &recipient.pubkey()).0)
+    }
+
+    pub fn sub_account(&self, owner: &Keypair, index: u64) -> SubAccount {
+        self.account(&pda::sub_account(&owner.pubkey(), index).0)
+    }
+
+    pub fn airdrop(&self, funder: &Keypair, id: u64) -> Airdrop {
+        self.account(&pda::airdrop(&funder.pubkey(), id).0)
+    }
+
+    #[cfg(feature = "devnet-faucet")]
+    pub fn faucet_claim(&self, wallet: &Keypair) -> stablecoin::FaucetClaim {
+        self.account(&pda::faucet_claim(&wallet.pubkey()).0)
+    }
+
+    pub fn supply_stats(&self) -> SupplyStats {
+        self.account(&pda::supply_stats().0)
+    }
+
+    /// The stablecoin mint
+    pub fn mint_state(&self) -> Mint {
+        self.account(&pda::mint().0)
+    }
+
+    /// `owner`'s associated token account
+    pub fn token_state(&self, owner: &Keypair) -> TokenAccount {
+        self.account(&self.token_account(owner))
+    }
+
+    /// Write `state` to `address` as a rent-exempt account owned by the
+    /// program, as if the program had created it; for forged accounts
+    pub fn forge_account<T: AccountSerialize>(&mut self, address: &Pubkey, state: &T) {
+        let mut data = Vec::new();
+        state.try_serialize(&mut data).unwrap();
+        let account = Account {
+            lamports: self
+                .inner
+                .svm
+                .minimum_balance_for_rent_exemption(data.len()),
+            data,
+            owner: PROGRAM_ID,
+            executable: false,
+            rent_epoch: 0,
+        };
+        self.inner.svm.set_account(*address, account).unwrap();
+    }
+
+    /// Write the account dumped at `path` to its address, returning the
+    /// address
+    pub fn load_account(&mut self, path: impl AsRef<Path>) -> Pubkey {
+        let (address, account) = dump::read(path);
+        self.inner.svm.set_account(address, account).unwrap();
+        address
+    }
+
+    /// [`load_account`](Self::load_account) for every dump in `dir`
+    pub fn load_accounts(&mut self, dir: impl AsRef<Path>) -> Vec<Pubkey> {
+        dump::read_dir(dir)
+            .into_iter()
+            .map(|(address, account)| {
+                self.inner.svm.set_account(address, account).unwrap();
+                address
+            })
+            .collect()
+    }
+
+    /// `owner`'s stablecoin balance, zero without a token account
+    pub fn balance(&self, owner: &Keypair) -> u64 {
+        if !self.inner.account_exists(&self.token_account(owner)) {
+            return 0;
+        }
+        self.token_state(owner).amount
+    }
+}
+
+impl Default for TestContext {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Deref for TestContext {
+    type Target = AnchorContext;
+
+    fn deref(&self) -> &AnchorContext {
+        &self.inner
+    }
+}
+
+impl DerefMut for TestContext {
+    fn deref_mut(&mut self) -> &mut AnchorContext {
+        &mut self.inner
+    }
+}
@@ -0,0 +1,239 @@
+//! Every instruction by the path that costs it the most
+//!
+//! For tests that must cover the whole program, such as the stack frame
+//! and compute unit checks: [`run`] sends each of [`deepest_paths`] from a
+//! fresh deployment and fails if one of the program's instructions has
+//! none.
+
+use anchor_litesvm::{Instruction, Keypair, Signer, TransactionResult};
+use stablecoin::{
+    IDEMPOTENCY_KEY_TTL, MIN_CHANNEL_DISPUTE_PERIOD, MIN_INHERITANCE_NOTICE_PERIOD,
+    MIN_RECOVERY_CHALLENGE_PERIOD,
+};
+
+use crate::{
+    instructions::{self, StablecoinInstruction},
+    pda, TestContext,
+};
+
+/// How many instructions the program has
+pub const INSTRUCTIONS: usize = 46;
+
+/// Where `instruction` goes in a list of every instruction
+///
+/// Deliberately without a wildcard arm: a new instruction does not compile
+/// until it has a path in [`deepest_paths`].
+pub fn position(instruction: &StablecoinInstruction) -> usize {
+    match instruction {
+        StablecoinInstruction::Initialize => 0,
+        StablecoinInstruction::ConfigureMinter { .. } => 1,
+        StablecoinInstruction::RemoveMinter => 2,
+        StablecoinInstruction::FreezeMinterSet => 3,
+        StablecoinInstruction::UnfreezeMinterSet => 4,
+        StablecoinInstruction::MintTokens { .. } => 5,
+        StablecoinInstruction::MintTokensIdempotent { .. } => 6,
+        StablecoinInstruction::ReclaimIdempotencyRecord => 7,
+        StablecoinInstruction::PauseSelf => 8,
+        StablecoinInstruction::ResumeMinter => 9,
+        StablecoinInstruction::BurnTokens { .. } => 10,
+        StablecoinInstruction::SweepDust { .. } => 11,
+        StablecoinInstruction::ConfigureTreasury { .. } => 12,
+        StablecoinInstruction::ProposeTreasuryBurn { .. } => 13,
+        StablecoinInstruction::ApproveTreasuryBurn => 14,
+        StablecoinInstruction::ExecuteTreasuryBurn => 15,
+        StablecoinInstruction::CancelTreasuryBurn => 16,
+        StablecoinInstruction::ConfigureRecovery { .. } => 17,
+        StablecoinInstruction::InitiateRecovery { .. } => 18,
+        StablecoinInstruction::ApproveRecovery => 19,
+        StablecoinInstruction::ExecuteRecovery => 20,
+        StablecoinInstruction::CancelRecovery => 21,
+        StablecoinInstruction::CloseRecovery => 22,
+        StablecoinInstruction::ConfigureInheritance { .. } => 23,
+        StablecoinInstruction::CheckIn => 24,
+        StablecoinInstruction::StartInheritanceClaim => 25,
+        StablecoinInstruction::ClaimInheritance => 26,
+        StablecoinInstruction::CloseInheritance => 27,
+        StablecoinInstruction::OpenNettingWindow { .. } => 28,
+        StablecoinInstruction::QueueObligation { .. } => 29,
+        StablecoinInstruction::SettleNet => 30,
+        StablecoinInstruction::CancelNettingWindow => 31,
+        StablecoinInstruction::RegisterPurposeCode { .. } => 32,
+        StablecoinInstruction::RetirePurposeCode => 33,
+        StablecoinInstruction::OpenChannel { .. } => 34,
+        StablecoinInstruction::CloseChannel { .. } => 35,
+        StablecoinInstruction::SettleChannel => 36,
+        StablecoinInstruction::RegisterSubAccount { .. } => 37,
+        StablecoinInstruction::SweepSubAccount => 38,
+        StablecoinInstruction::OpenAirdrop { .. } => 39,
+        StablecoinInstruction::ClaimAirdrop => 40,
+        StablecoinInstruction::ClawbackAirdrop => 41,
+        StablecoinInstruction::ResizeAccount => 42,
+        StablecoinInstruction::SyncSupplyStats => 43,
+        StablecoinInstruction::Pause => 44,
+        StablecoinInstruction::Unpause => 45,
+    }
+}
+
+/// Each instruction by the path that deserializes and creates the most,
+/// in an order that runs from a fresh deployment
+///
+/// `dust` is a second token account of the holder's, opened just before
+/// it is swept.
+pub fn deepest_paths(
+    admin: &Keypair,
+    minter: &Keypair,
+    holder: &Keypair,
+    dust: &Keypair,
+) -> Vec<Instruction> {
+    let (admin, minter, holder) = (admin.pubkey(), minter.pubkey(), holder.pubkey());
+    vec![
+        instructions::initialize(&admin),
+        // Creates the minter config
+        instructions::configure_minter(&admin, &minter, 1_000_000_000),
+        // Creates the holder's token account
+        instructions::mint_tokens(&minter, &holder, 100_000_000),
+        // Creates the admin's token account and the record
+        instructions::mint_tokens_idempotent(&minter, &admin, 100_000_000, [1; 16]),
+        instructions::reclaim_idempotency_record(&minter, &[1; 16]),
+        // Creates the pause marker, then closes it
+        instructions::pause_self(&minter),
+        instructions::resume_minter(&admin, &minter),
+        instructions::burn_tokens(&holder, 50_000_000),
+        // Moves tokens and closes the account
+        instructions::sweep_dust(&holder, &[dust.pubkey()], 1),
+        // Creates the treasury, then its custody account
+        instructions::configure_treasury(&admin, [admin, minter], 0),
+        instructions::mint_tokens(&minter, &pda::treasury().0, 100_000_000),
+        // Creates the pending burn
+        instructions::propose_treasury_burn(&admin, 1, 50_000_000),
+        instructions::approve_treasury_burn(&minter, 1),
+        instructions::execute_treasury_burn(&admin, &admin, 1),
+        instructions::propose_treasury_burn(&admin, 2, 50_000_000),
+        instructions::cancel_treasury_burn(&minter, &admin, 2),
+        // Creates the recovery config
+        instructions::configure_recovery(
+            &holder,
+            vec![admin, minter],
+            1,
+            MIN_RECOVERY_CHALLENGE_PERIOD,
+        ),
+        instructions::initiate_recovery(&admin, &holder, &minter),
+        instructions::approve_recovery(&minter, &holder),
+        // Creates the minter's token account
+        instructions::execute_recovery(&admin, &holder, &minter),
+        instructions::initiate_recovery(&admin, &holder, &minter),
+        instructions::cancel_recovery(&holder),
+        instructions::close_recovery(&holder),
+        // Creates the inheritance plan
+        instructions::configure_inheritance(&holder, &minter, 1, MIN_INHERITANCE_NOTICE_PERIOD),
+        instructions::start_inheritance_claim(&minter, &holder),
+        instructions::claim_inheritance(&minter, &holder),
+        instructions::start_inheritance_claim(&minter, &holder),
+        // Stops the claim
+        instructions::check_in(&holder),
+        instructions::close_inheritance(&holder),
+        // Creates the purpose code, then the netting window and its custody
+        instructions::register_purpose_code(&admin, *b"PAYR"),
+        instructions::open_netting_window(&admin, 1, vec![holder, minter], 1),
+        // Reads the purpose code and escrows the debit
+        instructions::queue_obligation(&minter, 1, &holder, 10_000_000, Some(b"PAYR")),
+        // Pays out custody and closes it with the window
+        instructions::settle_net(&admin, &admin, 1, &[holder, minter]),
+        instructions::open_netting_window(&admin, 2, vec![holder, minter], 1),
+        instructions::cancel_netting_window(&admin, &admin, 2, &[holder, minter]),
+        // Creates the sub-account and its custody, reading the purpose code
+        instructions::register_sub_account(&holder, 0, b"PAYR"),
+        instructions::mint_tokens(&minter, &pda::sub_account(&holder, 0).0, 1_000_000),
+        // Moves tokens out of custody
+        instructions::sweep_sub_account(&holder, 0),
+        // Creates the airdrop and its custody, funded by an officer
+        instructions::open_airdrop(&admin, 0, vec![minter, holder], vec![400_000, 600_000], 1),
+        instructions::claim_airdrop(&minter, &admin, 0),
+        // Moves the rest to treasury custody and closes custody
+        instructions::clawback_airdrop(&admin, &admin, 0),
+        // Tops up nothing, the config already being this version's size
+        instructions::resize_account(&admin, &admin, &pda::config().0),
+        instructions::retire_purpose_code(&admin, &admin, b"PAYR"),
+        // Creates the channel and its custody
+        instructions::open_channel(&minter, &holder, 1_000_000, MIN_CHANNEL_DISPUTE_PERIOD),
+        // Checks the minter's signature on a nonzero amount
+        instructions::close_channel(&holder, &minter, &holder, 400_000, [0; 64]),
+        // Pays out, refunds and closes custody
+        instructions::settle_channel(&admin, &minter, &holder),
+        instructions::sync_supply_stats(&holder),
+        instructions::pause(&admin),
+        instructions::unpause(&admin),
+        // Creates the freeze marker, then closes it
+        instructions::freeze_minter_set(&admin),
+        instructions::unfreeze_minter_set(&admin, &admin),
+        // Closes the minter config and creates its tombstone
+        instructions::remove_minter(&admin, &minter),
+    ]
+}
+
+/// Send each of [`deepest_paths`] in `ctx`, fresh from [`TestContext::new`],
+/// as the admin, minter or holder it needs, passing each instruction and
+/// its transaction's result to `check`
+///
+/// Moves the clock and sets up accounts between instructions as their paths
+/// need. Panics if an instruction has no path.
+pub fn run(
+    ctx: &mut TestContext,
+    minter: &Keypair,
+    holder: &Keypair,
+    dust: &Keypair,
+    mut check: impl FnMut(&StablecoinInstruction, TransactionResult),
+) {
+    let admin = ctx.admin.insecure_clone();
+    let mut covered = [false; INSTRUCTIONS];
+    for ix in deepest_paths(&admin, minter, holder, dust) {
+        let instruction = instructions::decode(&ix.data).unwrap();
+        covered[position(&instruction)] = true;
+        let mut transaction = vec![ix];
+        match instruction {
+            StablecoinInstruction::ReclaimIdempotencyRecord => {
+                ctx.advance_time(IDEMPOTENCY_KEY_TTL as u64);
+            }
+            StablecoinInstruction::SweepDust { .. } => {
+                ctx.open_token_account(dust, holder, 1);
+            }
+            StablecoinInstruction::ExecuteRecovery => {
+                ctx.advance_time(MIN_RECOVERY_CHALLENGE_PERIOD as u64);
+            }
+            StablecoinInstruction::StartInheritanceClaim => {
+                ctx.advance_epochs(1);
+            }
+            StablecoinInstruction::ClaimInheritance => {
+                ctx.advance_time(MIN_INHERITANCE_NOTICE_PERIOD as u64);
+            }
+            StablecoinInstruction::SettleNet | StablecoinInstruction::ClawbackAirdrop => {
+                ctx.advance_time(1);
+            }
+            StablecoinInstruction::CloseChannel { amount, .. } => {
+                // The signature covers the channel's nonce, which is only
+                // known once it opens
+                transaction = ctx.close_channel_instructions(holder, minter, holder, amount);
+            }
+            StablecoinInstruction::SettleChannel => {
+                ctx.advance_time(MIN_CHANNEL_DISPUTE_PERIOD as u64);
+            }
+            _ => {}
+        }
+
+        let signer = [&admin, minter, holder]
+            .into_iter()
+            .find(|signer| {
+                transaction
+                    .iter()
+                    .flat_map(|ix| &ix.accounts)
+                    .any(|meta| meta.is_signer && meta.pubkey == signer.pubkey())
+            })
+            .unwrap();
+        let result = ctx.execute_all(transaction, &[signer]);
+        check(&instruction, result);
+    }
+    assert!(
+        covered.iter().all(|&covered| covered),
+        "an instruction has no path in deepest_paths"
+    );
+}
@@ -0,0 +1,100 @@
+//! The built program, found or built on first use
+//!
+//! The artifact is looked for in `$SBF_OUT_DIR`, which `cargo test-sbf`
+//! sets, and otherwise in the workspace's `target/deploy`, where
+//! `anchor build` and `cargo build-sbf` put it. When it is missing, the first
+//! test to ask builds it with `cargo build-sbf`, falling back to
+//! `anchor build`; later tests, in the same process or the next run, reuse
+//! the file. An artifact that exists is never rebuilt, so rebuild after
+//! changing the program.
+//!
+//! With the `devnet-faucet` feature the program is built with its faucet,
+//! and the default directory is `target/deploy-faucet` instead, so the
+//! mainnet artifact is never mistaken for it.
+
+use std::{
+    env,
+    path::{Path, PathBuf},
+    process::Command,
+    sync::OnceLock,
+};
+
+const ARTIFACT: &str = "stablecoin.so";
+
+#[cfg(not(feature = "devnet-faucet"))]
+const DEPLOY_DIR: &str = "target/deploy";
+#[cfg(feature = "devnet-faucet")]
+const DEPLOY_DIR: &str = "target/deploy-faucet";
+
+fn workspace() -> &'static Path {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .ancestors()
+        .nth(2)
+        .unwrap()
+}
+
+/// Where the program is, or will be once built
+pub fn path() -> PathBuf {
+    match env::var_os("SBF_OUT_DIR") {
+        Some(dir) => Path::new(&dir).join(ARTIFACT),
+        None => workspace().join(DEPLOY_DIR).join(ARTIFACT),
+    }
+}
+
+/// The program's ELF, built first if there is none
+///
+/// Panics with what was tried when it can be neither found nor built.
+pub fn elf() -> &'static [u8] {
+    static ELF: OnceLock<Vec<u8>> = OnceLock::new();
+    ELF.get_or_init(|| {
+        let path = path();
+        if !path.exists() {
+            build(&path);
+        }
+        std::fs::read(&path)
+            .unwrap_or_else(|err| panic!("failed to read {}: {err}", path.display()))
+    })
+}
+
+fn build(path: &Path) {
+    let out_dir = path.parent().unwrap();
+    let manifest = workspace().join("programs/stablecoin/Cargo.toml");
+    let mut cargo = Command::new("cargo");
+    cargo
+        .arg("build-sbf")
+        .arg("--manifest-path")
+        .arg(&manifest)
+        .arg("--sbf-out-dir")
+        .arg(out_dir);
+    if cfg!(feature = "devnet-faucet") {
+        cargo.args(["--features", "devnet-faucet"]);
+    }
+    let mut commands = vec![("cargo build-sbf", cargo)];
+    // anchor build only writes the default build, to target/deploy
+    if !cfg!(feature = "devnet-faucet") {
+        let mut anchor = Command::new("anchor");
+        anchor
+            .args(["build", "--program-name", "stablecoin"])
+            .current_dir(workspace());
+        commands.push(("anchor build", anchor));
+    }
+
+    let mut failures = Vec::new();
+    for (name, mut command) in commands {
+        match command.status() {
+            Ok(status) if status.success() && path.exists() => return,
+            Ok(status) if status.success() => {
+                failures.push(format!("`{name}` did not write {}", path.display()))
+            }
+            Ok(status) => failures.push(format!("`{name}` failed: {status}")),
+            Err(err) => failures.push(format!("`{name}` could not run: {err}")),
+        }
+    }
+    panic!(
+        "{} is missing and building it failed:\n  {}\n\
+         Install the Solana CLI (for `cargo build-sbf`) or Anchor, or build the \
+         program yourself and rerun the tests.",
+        path.display(),
+        failures.join("\n  ")
+    );
+}
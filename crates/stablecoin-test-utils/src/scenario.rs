@@ -0,0 +1,81 @@
+//! The canonical end-to-end scenario
+//!
+//! One known-good state for every test layer: tests here run [`run`]
+//! directly, while other crates load the accounts `gen-fixtures` dumps from
+//! it. Keys and amounts are fixed, so the accounts come out byte for byte
+//! the same on every run against the same program build. The admin and
+//! first minter are the keys the layout fixtures use.
+
+use anchor_lang::prelude::Pubkey;
+use anchor_litesvm::{Keypair, Signer};
+
+use crate::{pda, TestContext, FUNDING};
+
+pub const ALLOWANCE: u64 = 1_000_000_000;
+pub const SECOND_ALLOWANCE: u64 = 500_000_000;
+
+pub const MINTED: u64 = 300_000_000;
+pub const SECOND_MINTED: u64 = 100_000_000;
+pub const BURNED: u64 = 50_000_000;
+
+pub fn admin() -> Keypair {
+    Keypair::new_from_array([1; 32])
+}
+
+pub fn minter() -> Keypair {
+    Keypair::new_from_array([2; 32])
+}
+
+pub fn holder() -> Keypair {
+    Keypair::new_from_array([3; 32])
+}
+
+pub fn second_minter() -> Keypair {
+    Keypair::new_from_array([4; 32])
+}
+
+pub fn second_holder() -> Keypair {
+    Keypair::new_from_array([5; 32])
+}
+
+/// Initialize with [`admin`], configure both minters, have each mint to its
+/// holder, and have [`holder`] burn part of what it got
+///
+/// Leaves a supply of `MINTED + SECOND_MINTED - BURNED`, unpaused.
+pub fn run() -> TestContext {
+    let mut ctx = TestContext::with_admin(admin());
+    let (minter, holder) = (minter(), holder());
+    let (second_minter, second_holder) = (second_minter(), second_holder());
+    for wallet in [&minter, &holder, &second_minter, &second_holder] {
+        ctx.svm.airdrop(&wallet.pubkey(), FUNDING).unwrap();
+    }
+
+    ctx.initialize()
+        .configure_minter(&minter, ALLOWANCE)
+        .configure_minter(&second_minter, SECOND_ALLOWANCE)
+        .mint(&minter, &holder, MINTED)
+        .mint(&second_minter, &second_holder, SECOND_MINTED)
+        .burn(&holder, BURNED);
+    ctx
+}
+
+/// Every account [`run`] creates, by the name its fixture is written under
+pub fn accounts() -> Vec<(&'static str, Pubkey)> {
+    vec![
+        ("config", pda::config().0),
+        ("mint", pda::mint().0),
+        ("minter_config", pda::minter_config(&minter().pubkey()).0),
+        (
+            "second_minter_config",
+            pda::minter_config(&second_minter().pubkey()).0,
+        ),
+        (
+            "holder_token_account",
+            pda::token_account(&holder().pubkey()),
+        ),
+        (
+            "second_holder_token_account",
+            pda::token_account(&second_holder().pubkey()),
+        ),
+    ]
+}
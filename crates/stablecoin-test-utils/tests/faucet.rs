@@ -0,0 +1,119 @@
+#![cfg(feature = "devnet-faucet")]
+
+// Tests of the devnet faucet, which only builds with the `devnet-faucet`
+// feature. They live with the harness rather than the program's tests so
+// that only they turn on its `devnet-faucet` feature, which loads, or
+// builds, the program from target/deploy-faucet rather than the mainnet
+// artifact in target/deploy:
+//
+//     cargo build-sbf --manifest-path programs/stablecoin/Cargo.toml \
+//         --features devnet-faucet --sbf-out-dir target/deploy-faucet
+//     cargo test -p stablecoin-test-utils --features devnet-faucet --test faucet
+
+use anchor_litesvm::{EventHelpers, Keypair, Signer};
+use litesvm_utils::AssertionHelpers;
+use stablecoin::{FaucetClaim, StablecoinError, TokensMinted, FAUCET_DAILY_LIMIT};
+use stablecoin_test_utils::{instructions, pda, substitute, TestContext};
+
+const DAY: i64 = 24 * 60 * 60;
+
+#[test]
+fn test_faucet() {
+    let mut ctx = TestContext::new();
+    let wallet = ctx.funded_account();
+    ctx.initialize().warp_to_timestamp(100 * DAY + 5);
+
+    let result = ctx.expect_success(
+        instructions::faucet(&wallet.pubkey(), 300_000_000),
+        &[&wallet],
+    );
+    let destination = ctx.token_account(&wallet);
+    ctx.svm.assert_token_balance(&destination, 300_000_000);
+    assert_eq!(ctx.mint_state().supply, 300_000_000);
+    assert_eq!(ctx.supply_stats().supply, 300_000_000);
+    assert_eq!(
+        ctx.faucet_claim(&wallet),
+        FaucetClaim {
+            wallet: wallet.pubkey(),
+            day: 100,
+            claimed: 300_000_000,
+            bump: pda::faucet_claim(&wallet.pubkey()).1,
+        }
+    );
+
+    // Reported like any other mint, the wallet standing in for the minter
+    let event: TokensMinted = result.parse_event().unwrap();
+    assert_eq!(
+        event,
+        TokensMinted {
+            minter: wallet.pubkey(),
+            destination,
+            amount: 300_000_000,
+            remaining_allowance: FAUCET_DAILY_LIMIT - 300_000_000,
+        }
+    );
+}
+
+#[test]
+fn test_faucet_daily_limit() {
+    let mut ctx = TestContext::new();
+    let wallet = ctx.funded_account();
+    let other = ctx.funded_account();
+    ctx.initialize().warp_to_timestamp(100 * DAY);
+
+    ctx.expect_success(
+        instructions::faucet(&wallet.pubkey(), FAUCET_DAILY_LIMIT - 1),
+        &[&wallet],
+    );
+    let result = ctx.execute(instructions::faucet(&wallet.pubkey(), 2), &[&wallet]);
+    result.assert_error_code(StablecoinError::FaucetLimitExceeded.into());
+    ctx.expect_success(instructions::faucet(&wallet.pubkey(), 1), &[&wallet]);
+
+    // Each wallet has its own limit
+    ctx.expect_success(
+        instructions::faucet(&other.pubkey(), FAUCET_DAILY_LIMIT),
+        &[&other],
+    );
+
+    // The limit starts over at the next UTC day, not a day after the claim
+    ctx.warp_to_timestamp(101 * DAY - 1);
+    let result = ctx.execute(instructions::faucet(&wallet.pubkey(), 1), &[&wallet]);
+    result.assert_error_code(StablecoinError::FaucetLimitExceeded.into());
+    ctx.warp_to_timestamp(101 * DAY);
+    ctx.expect_success(
+        instructions::faucet(&wallet.pubkey(), FAUCET_DAILY_LIMIT),
+        &[&wallet],
+    );
+    let claim = ctx.faucet_claim(&wallet);
+    assert_eq!((claim.day, claim.claimed), (101, FAUCET_DAILY_LIMIT));
+    assert_eq!(ctx.token_state(&wallet).amount, 2 * FAUCET_DAILY_LIMIT);
+}
+
+#[test]
+fn test_faucet_when_paused() {
+    let mut ctx = TestContext::new();
+    let wallet = ctx.funded_account();
+    ctx.initialize().pause();
+
+    let result = ctx.execute(instructions::faucet(&wallet.pubkey(), 1), &[&wallet]);
+    result.assert_error_code(StablecoinError::Paused.into());
+
+    ctx.unpause();
+    ctx.expect_success(instructions::faucet(&wallet.pubkey(), 1), &[&wallet]);
+}
+
+#[test]
+fn test_faucet_for_another_wallet() {
+    let mut ctx = TestContext::new();
+    let wallet = ctx.funded_account();
+    let victim = Keypair::new();
+    ctx.initialize();
+
+    // A wallet cannot spend another's limit through its claim record
+    let ix = substitute(
+        instructions::faucet(&wallet.pubkey(), 1),
+        &pda::faucet_claim(&wallet.pubkey()).0,
+        &pda::faucet_claim(&victim.pubkey()).0,
+    );
+    ctx.expect_failure(ix, &[&wallet]);
+}
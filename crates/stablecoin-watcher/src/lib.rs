@@ -0,0 +1,332 @@
+//! Alerts on sensitive stablecoin activity, and the webhook payloads that
+//! carry them
+//!
+//! Alerts come from two places: events the program emitted (pauses, admin
+//! and minter changes, treasury officers and burns, mints over a threshold
+//! or leaving little allowance, social recoveries, inheritance claims, net
+//! settlements and payment channels closing) and transactions it rejected
+//! because the signer lacked the role the instruction needs, or because
+//! they configured a removed minter. The program has no blacklist, so
+//! there are no blacklist alerts.
+
+use serde_json::{json, Value};
+use stablecoin::StablecoinError;
+use stablecoin_client::{
+    diagnostics::{ErrorDiagnostics, ErrorOrigin},
+    events::StablecoinEvent,
+    history::TransactionEvents,
+};
+
+/// How urgently someone should look
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Severity {
+    Warning,
+    Critical,
+}
+
+impl Severity {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Warning => "warning",
+            Self::Critical => "critical",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Alert {
+    pub severity: Severity,
+    pub summary: String,
+    pub signature: String,
+    pub slot: u64,
+}
+
+/// Which events are worth an alert
+#[derive(Debug, Clone, Default)]
+pub struct Rules {
+    /// Alert on mints of at least this many base units
+    pub large_mint: Option<u64>,
+    /// Alert on mints that leave the minter this many base units of
+    /// allowance or fewer
+    pub low_allowance: Option<u64>,
+}
+
+/// Alerts for the events of one successful transaction
+pub fn event_alerts(tx: &TransactionEvents, rules: &Rules) -> Vec<Alert> {
+    tx.events
+        .iter()
+        .filter_map(|event| {
+            let (severity, summary) = match event {
+                StablecoinEvent::Initialized(event) => (
+                    Severity::Critical,
+                    format!("Stablecoin initialized with admin {}", event.admin),
+                ),
+                StablecoinEvent::Paused(event) => (
+                    Severity::Critical,
+                    format!("Minting paused by {}", event.admin),
+                ),
+                StablecoinEvent::Unpaused(event) => (
+                    Severity::Warning,
+                    format!("Minting unpaused by {}", event.admin),
+                ),
+                StablecoinEvent::MinterSetFrozen(event) => (
+                    Severity::Warning,
+                    format!("Minter set frozen by {}", event.admin),
+                ),
+                StablecoinEvent::MinterSetUnfrozen(event) => (
+                    Severity::Warning,
+                    format!("Minter set unfrozen by {}", event.admin),
+                ),
+                // A minter only pauses itself when it fears its key is out
+                StablecoinEvent::MinterSelfPaused(event) => (
+                    Severity::Critical,
+                    format!("Minter {} paused itself", event.minter),
+                ),
+                StablecoinEvent::MinterResumed(event) => (
+                    Severity::Warning,
+                    format!("Minter {} resumed by {}", event.minter, event.admin),
+                ),
+                StablecoinEvent::MinterConfigured(event) => (
+                    Severity::Warning,
+                    format!(
+                        "Minter {} configured with allowance {}",
+                        event.minter, event.allowance
+                    ),
+                ),
+                StablecoinEvent::MinterRemoved(event) => (
+                    Severity::Warning,
+                    format!("Minter {} removed", event.minter),
+                ),
+                StablecoinEvent::TokensMinted(event)
+                    if rules
+                        .large_mint
+                        .is_some_and(|threshold| event.amount >= threshold) =>
+                {
+                    (
+                        Severity::Critical,
+                        format!(
+                            "Large mint of {} by {} to {}",
+                            event.amount, event.minter, event.destination
+                        ),
+                    )
+                }
+                StablecoinEvent::TokensMinted(event)
+                    if rules
+                        .low_allowance
+                        .is_some_and(|threshold| event.remaining_allowance <= threshold) =>
+                {
+                    (
+                        Severity::Warning,
+                        format!(
+                            "Minter {} has {} allowance left",
+                            event.minter, event.remaining_allowance
+                        ),
+                    )
+                }
+                StablecoinEvent::TreasuryConfigured(event) => (
+                    Severity::Warning,
+                    format!(
+                        "Treasury officers set to {} and {} with threshold {} by {}",
+                        event.officers[0], event.officers[1], event.threshold, event.admin
+                    ),
+                ),
+                StablecoinEvent::TreasuryBurnProposed(event) => (
+                    Severity::Warning,
+                    format!(
+                        "Treasury burn {} of {} proposed by {}",
+                        event.id, event.amount, event.proposer
+                    ),
+                ),
+                StablecoinEvent::TreasuryBurnApproved(event) => (
+                    Severity::Warning,
+                    format!("Treasury burn {} approved by {}", event.id, event.officer),
+                ),
+                StablecoinEvent::TreasuryBurnCancelled(event) => (
+                    Severity::Warning,
+                    format!("Treasury burn {} cancelled by {}", event.id, event.officer),
+                ),
+                StablecoinEvent::RecoveryInitiated(event) => (
+                    Severity::Warning,
+                    format!(
+                        "Recovery of {} to {} initiated by guardian {}",
+                        event.owner, event.new_owner, event.guardian
+                    ),
+                ),
+                StablecoinEvent::RecoveryCancelled(event) => (
+                    Severity::Warning,
+                    format!("Recovery of {} cancelled", event.owner),
+                ),
+                StablecoinEvent::AccountRecovered(event) => (
+                    Severity::Critical,
+                    format!(
+                        "Recovered {} from {} to {}",
+                        event.amount, event.owner, event.new_owner
+                    ),
+                ),
+                StablecoinEvent::InheritanceClaimStarted(event) => (
+                    Severity::Warning,
+                    format!(
+                        "Inheritance claim on {} started by {}, claimable at {}",
+                        event.owner, event.beneficiary, event.claimable_at
+                    ),
+                ),
+                StablecoinEvent::InheritanceClaimStopped(event) => (
+                    Severity::Warning,
+                    format!("Inheritance claim on {} stopped by a check-in", event.owner),
+                ),
+                StablecoinEvent::InheritanceClaimed(event) => (
+                    Severity::Critical,
+                    format!(
+                        "Inherited {} from {} by {}",
+                        event.amount, event.owner, event.beneficiary
+                    ),
+                ),
+                StablecoinEvent::NetSettled(event) => (
+                    Severity::Warning,
+                    format!(
+                        "Netting window {} settled, {} gross as {} net",
+                        event.id, event.gross, event.net
+                    ),
+                ),
+                StablecoinEvent::ChannelClosing(event) => (
+                    Severity::Warning,
+                    format!(
+                        "Payment channel from {} to {} closing at {} with {} paid",
+                        event.sender, event.recipient, event.closes_at, event.amount
+                    ),
+                ),
+                StablecoinEvent::AirdropClawedBack(event) => (
+                    Severity::Warning,
+                    format!(
+                        "Airdrop {} of {} clawed back, {} to treasury custody",
+                        event.id, event.funder, event.amount
+                    ),
+                ),
+                StablecoinEvent::AccountResized(event) => (
+                    Severity::Warning,
+                    format!(
+                        "Account {} resized from {} to {} bytes by {}",
+                        event.account, event.old_len, event.new_len, event.admin
+                    ),
+                ),
+                StablecoinEvent::TokensMinted(_)
+                | StablecoinEvent::TokensBurned(_)
+                | StablecoinEvent::RecoveryConfigured(_)
+                | StablecoinEvent::InheritanceConfigured(_)
+                | StablecoinEvent::ObligationQueued(_)
+                | StablecoinEvent::ChannelSettled(_)
+                | StablecoinEvent::SubAccountRegistered(_)
+                | StablecoinEvent::SubAccountSwept(_)
+                | StablecoinEvent::AirdropOpened(_)
+                | StablecoinEvent::AirdropClaimed(_) => return None,
+            };
+            Some(Alert {
+                severity,
+                summary,
+                signature: tx.signature.to_string(),
+                slot: tx.slot,
+            })
+        })
+        .collect()
+}
+
+/// An alert if a failed transaction was a privileged call by the wrong
+/// signer, or configured a removed minter
+pub fn failure_alert(signature: &str, slot: u64, diagnostics: &ErrorDiagnostics) -> Option<Alert> {
+    let instruction = diagnostics
+        .logs
+        .iter()
+        .find_map(|line| line.strip_prefix("Program log: Instruction: "))
+        .unwrap_or("unknown instruction");
+    let reason = match (&diagnostics.stablecoin_error, &diagnostics.anchor_error) {
+        (Some(StablecoinError::Unauthorized), _) => "signer is not the admin",
+        (Some(StablecoinError::NotMinter), _) => "signer is not a minter",
+        (Some(StablecoinError::NotOfficer), _) => "signer is not a treasury officer",
+        (Some(StablecoinError::NotGuardian), _) => "signer is not a guardian of the holder",
+        (Some(StablecoinError::NotBeneficiary), _) => "signer is not the beneficiary of the holder",
+        (Some(StablecoinError::NotInstitution), _) => {
+            "signer is not an institution in the netting window"
+        }
+        (Some(StablecoinError::NotChannelParty), _) => {
+            "signer is not a party to the payment channel"
+        }
+        (Some(StablecoinError::NotAirdropRecipient), _) => {
+            "signer is not a recipient of the airdrop"
+        }
+        // Configuring a removed minter again, by a replayed or stale
+        // transaction or otherwise
+        (Some(StablecoinError::MinterRetired), _) => "minter was removed",
+        // A signer that never was a minter has no minter config at all
+        (_, Some(error))
+            if error.name == "AccountNotInitialized"
+                && error.origin == Some(ErrorOrigin::Account("minter_config".to_string())) =>
+        {
+            "signer is not a minter"
+        }
+        _ => return None,
+    };
+    Some(Alert {
+        severity: Severity::Warning,
+        summary: format!("Rejected {instruction}: {reason}"),
+        signature: signature.to_string(),
+        slot,
+    })
+}
+
+/// Where alerts are posted
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Webhook {
+    /// Slack incoming webhook URL
+    Slack(String),
+    /// PagerDuty Events API v2 routing key
+    PagerDuty(String),
+    /// Any URL, sent the alert as JSON
+    Generic(String),
+}
+
+const PAGERDUTY_EVENTS_URL: &str = "https://events.pagerduty.com/v2/enqueue";
+
+impl Webhook {
+    pub fn url(&self) -> &str {
+        match self {
+            Self::Slack(url) | Self::Generic(url) => url,
+            Self::PagerDuty(_) => PAGERDUTY_EVENTS_URL,
+        }
+    }
+
+    /// The JSON body to post for `alert`
+    pub fn payload(&self, alert: &Alert) -> Value {
+        match self {
+            Self::Slack(_) => json!({
+                "text": format!(
+                    "[{}] {}\nSignature: {} (slot {})",
+                    alert.severity.as_str(),
+                    alert.summary,
+                    alert.signature,
+                    alert.slot
+                ),
+            }),
+            Self::PagerDuty(routing_key) => json!({
+                "routing_key": routing_key,
+                "event_action": "trigger",
+                // One incident per transaction, however often it is seen
+                "dedup_key": alert.signature,
+                "payload": {
+                    "summary": alert.summary,
+                    "source": "stablecoin-watcher",
+                    "severity": alert.severity.as_str(),
+                    "custom_details": {
+                        "signature": alert.signature,
+                        "slot": alert.slot,
+                    },
+                },
+            }),
+            Self::Generic(_) => json!({
+                "severity": alert.severity.as_str(),
+                "summary": alert.summary,
+                "signature": alert.signature,
+                "slot": alert.slot,
+            }),
+        }
+    }
+}
@@ -0,0 +1,214 @@
+//! `stablecoin-watcher`: post alerts on sensitive program activity to webhooks
+//!
+//! Events arrive over the websocket as soon as the transaction reaches the
+//! requested commitment, and gaps after a reconnect are replayed. Rejected
+//! privileged calls come from a second logs subscription; failed transactions
+//! are not in the HTTP history the replay reads, so those missed while the
+//! socket was down are not recovered.
+
+use std::time::Duration;
+
+use clap::Parser;
+use futures_util::StreamExt;
+use solana_commitment_config::CommitmentConfig;
+use solana_pubsub_client::nonblocking::pubsub_client::PubsubClient;
+use solana_rpc_client_api::config::{RpcTransactionLogsConfig, RpcTransactionLogsFilter};
+use stablecoin_client::{
+    diagnostics::ErrorDiagnostics,
+    subscribe::{subscribe_events, SubscribeConfig},
+    PROGRAM_ID,
+};
+use stablecoin_watcher::{event_alerts, failure_alert, Alert, Rules, Webhook};
+
+/// Attempts per webhook before an alert is dropped
+const POST_ATTEMPTS: u32 = 3;
+const POST_TIMEOUT: Duration = Duration::from_secs(10);
+const RECONNECT_DELAY: Duration = Duration::from_millis(500);
+const MAX_RECONNECT_DELAY: Duration = Duration::from_secs(30);
+
+#[derive(Debug, Parser)]
+#[command(name = "stablecoin-watcher", version, about)]
+struct Args {
+    /// RPC URL
+    #[arg(short = 'u', long, default_value = "http://127.0.0.1:8899")]
+    url: String,
+    /// Websocket URL [default: derived from --url]
+    #[arg(long)]
+    ws_url: Option<String>,
+    /// Slack incoming webhook URL; repeatable
+    #[arg(long, value_name = "URL")]
+    slack: Vec<String>,
+    /// PagerDuty Events API v2 routing key; repeatable
+    #[arg(long, value_name = "ROUTING_KEY")]
+    pagerduty: Vec<String>,
+    /// URL sent each alert as plain JSON; repeatable
+    #[arg(long, value_name = "URL")]
+    webhook: Vec<String>,
+    /// Alert on mints of at least this many base units
+    #[arg(long, value_name = "AMOUNT")]
+    large_mint: Option<u64>,
+    /// Alert on mints that leave the minter this many base units of
+    /// allowance or fewer
+    #[arg(long, value_name = "AMOUNT")]
+    low_allowance: Option<u64>,
+    /// Commitment to watch at
+    #[arg(long, default_value = "confirmed")]
+    commitment: CommitmentConfig,
+}
+
+impl Args {
+    fn webhooks(&self) -> Vec<Webhook> {
+        let slack = self.slack.iter().cloned().map(Webhook::Slack);
+        let pagerduty = self.pagerduty.iter().cloned().map(Webhook::PagerDuty);
+        let generic = self.webhook.iter().cloned().map(Webhook::Generic);
+        slack.chain(pagerduty).chain(generic).collect()
+    }
+}
+
+/// The websocket URL a validator serves next to `url`: same host, the
+/// scheme switched to ws(s) and an explicit port moved up by one
+fn ws_url(url: &str) -> String {
+    let (scheme, rest) = match url.split_once("://") {
+        Some(("https", rest)) => ("wss", rest),
+        Some((_, rest)) => ("ws", rest),
+        None => ("ws", url),
+    };
+    let (authority, path) = rest.split_at(rest.find('/').unwrap_or(rest.len()));
+    let authority = match authority.rsplit_once(':') {
+        Some((host, port)) => match port.parse::<u16>() {
+            Ok(port) => format!("{host}:{}", port.saturating_add(1)),
+            Err(_) => authority.to_string(),
+        },
+        None => authority.to_string(),
+    };
+    format!("{scheme}://{authority}{path}")
+}
+
+#[tokio::main]
+async fn main() {
+    let args = Args::parse();
+    let webhooks = args.webhooks();
+    if webhooks.is_empty() {
+        eprintln!("error: pass at least one of --slack, --pagerduty or --webhook");
+        std::process::exit(2);
+    }
+    let ws_url = args.ws_url.clone().unwrap_or_else(|| ws_url(&args.url));
+    let rules = Rules {
+        large_mint: args.large_mint,
+        low_allowance: args.low_allowance,
+    };
+    let http = reqwest::Client::builder()
+        .timeout(POST_TIMEOUT)
+        .build()
+        .expect("the HTTP client has no custom TLS configuration");
+
+    let (tx, mut alerts) = tokio::sync::mpsc::unbounded_channel();
+    tokio::spawn(watch_failures(ws_url.clone(), args.commitment, tx.clone()));
+
+    let mut config = SubscribeConfig::new(args.url.clone(), ws_url.clone());
+    config.commitment = args.commitment;
+    tokio::spawn(async move {
+        let mut events = subscribe_events(config);
+        while let Some(item) = events.next().await {
+            match item {
+                Ok(events) => {
+                    for alert in event_alerts(&events, &rules) {
+                        let _ = tx.send(alert);
+                    }
+                }
+                Err(err) => eprintln!("event subscription failed: {err}"),
+            }
+        }
+    });
+
+    eprintln!("watching {PROGRAM_ID} on {ws_url}");
+    while let Some(alert) = alerts.recv().await {
+        eprintln!(
+            "[{}] {} ({})",
+            alert.severity.as_str(),
+            alert.summary,
+            alert.signature
+        );
+        for webhook in &webhooks {
+            tokio::spawn(post(http.clone(), webhook.clone(), alert.clone()));
+        }
+    }
+}
+
+/// Alert on failed transactions, reconnecting with backoff
+async fn watch_failures(
+    ws_url: String,
+    commitment: CommitmentConfig,
+    tx: tokio::sync::mpsc::UnboundedSender<Alert>,
+) {
+    let mut delay = RECONNECT_DELAY;
+    loop {
+        match watch_failures_once(&ws_url, commitment, &tx).await {
+            Ok(()) => delay = RECONNECT_DELAY,
+            Err(err) => {
+                eprintln!("failure subscription failed: {err}");
+                tokio::time::sleep(delay).await;
+                delay = (delay * 2).min(MAX_RECONNECT_DELAY);
+            }
+        }
+    }
+}
+
+async fn watch_failures_once(
+    ws_url: &str,
+    commitment: CommitmentConfig,
+    tx: &tokio::sync::mpsc::UnboundedSender<Alert>,
+) -> Result<(), solana_pubsub_client::nonblocking::pubsub_client::PubsubClientError> {
+    let pubsub = PubsubClient::new(ws_url).await?;
+    let (mut logs, unsubscribe) = pubsub
+        .logs_subscribe(
+            RpcTransactionLogsFilter::Mentions(vec![PROGRAM_ID.to_string()]),
+            RpcTransactionLogsConfig {
+                commitment: Some(commitment),
+            },
+        )
+        .await?;
+    while let Some(response) = logs.next().await {
+        let Some(err) = response.value.err else {
+            continue;
+        };
+        let diagnostics = ErrorDiagnostics::new(err.into(), response.value.logs);
+        if let Some(alert) = failure_alert(
+            &response.value.signature,
+            response.context.slot,
+            &diagnostics,
+        ) {
+            let _ = tx.send(alert);
+        }
+    }
+    unsubscribe().await;
+    Ok(())
+}
+
+async fn post(http: reqwest::Client, webhook: Webhook, alert: Alert) {
+    let payload = webhook.payload(&alert);
+    let mut delay = RECONNECT_DELAY;
+    for attempt in 1..=POST_ATTEMPTS {
+        let result = http
+            .post(webhook.url())
+            .json(&payload)
+            .send()
+            .await
+            .and_then(|response| response.error_for_status());
+        match result {
+            Ok(_) => return,
+            Err(err) if attempt == POST_ATTEMPTS => {
+                // Webhook URLs are secrets, keep them out of the log
+                eprintln!(
+                    "dropping alert for {}: {}",
+                    alert.signature,
+                    err.without_url()
+                );
+            }
+            Err(_) => {
+                tokio::time::sleep(delay).await;
+                delay *= 2;
+            }
+        }
+    }
+}
@@ -0,0 +1,257 @@
+use anchor_lang::prelude::Pubkey;
+use solana_instruction::error::InstructionError;
+use solana_signature::Signature;
+use solana_transaction_error::TransactionError;
+use stablecoin::{
+    MinterRemoved, Paused, RecoveryConfigured, StablecoinError, TokensBurned, TokensMinted,
+    TreasuryBurnProposed,
+};
+use stablecoin_client::{
+    diagnostics::ErrorDiagnostics, events::StablecoinEvent, history::TransactionEvents,
+};
+use stablecoin_watcher::{event_alerts, failure_alert, Alert, Rules, Severity, Webhook};
+
+fn transaction(events: Vec<StablecoinEvent>) -> TransactionEvents {
+    TransactionEvents {
+        slot: 42,
+        block_time: None,
+        signature: Signature::default(),
+        events,
+    }
+}
+
+fn minted(amount: u64, remaining_allowance: u64) -> StablecoinEvent {
+    StablecoinEvent::TokensMinted(TokensMinted {
+        minter: Pubkey::new_unique(),
+        destination: Pubkey::new_unique(),
+        amount,
+        remaining_allowance,
+    })
+}
+
+fn failure(code: u32, logs: &[&str]) -> ErrorDiagnostics {
+    ErrorDiagnostics::new(
+        TransactionError::InstructionError(0, InstructionError::Custom(code)),
+        logs.iter().map(|line| line.to_string()).collect(),
+    )
+}
+
+#[test]
+fn test_event_alerts() {
+    let admin = Pubkey::new_unique();
+    let minter = Pubkey::new_unique();
+    let tx = transaction(vec![
+        StablecoinEvent::Paused(Paused { admin }),
+        StablecoinEvent::MinterRemoved(MinterRemoved { minter }),
+        StablecoinEvent::TokensBurned(TokensBurned {
+            owner: Pubkey::new_unique(),
+            token_account: Pubkey::new_unique(),
+            amount: u64::MAX,
+        }),
+    ]);
+
+    let alerts = event_alerts(&tx, &Rules::default());
+    assert_eq!(
+        alerts,
+        vec![
+            Alert {
+                severity: Severity::Critical,
+                summary: format!("Minting paused by {admin}"),
+                signature: Signature::default().to_string(),
+                slot: 42,
+            },
+            Alert {
+                severity: Severity::Warning,
+                summary: format!("Minter {minter} removed"),
+                signature: Signature::default().to_string(),
+                slot: 42,
+            },
+        ]
+    );
+}
+
+#[test]
+fn test_treasury_alerts() {
+    let proposer = Pubkey::new_unique();
+    let tx = transaction(vec![
+        StablecoinEvent::TreasuryBurnProposed(TreasuryBurnProposed {
+            id: 7,
+            proposer,
+            amount: 500,
+        }),
+        // A holder's own recovery settings are not the issuer's concern
+        StablecoinEvent::RecoveryConfigured(RecoveryConfigured {
+            owner: Pubkey::new_unique(),
+            guardians: vec![Pubkey::new_unique()],
+            quorum: 1,
+            challenge_period: 86_400,
+        }),
+    ]);
+
+    let alerts = event_alerts(&tx, &Rules::default());
+    assert_eq!(
+        alerts,
+        vec![Alert {
+            severity: Severity::Warning,
+            summary: format!("Treasury burn 7 of 500 proposed by {proposer}"),
+            signature: Signature::default().to_string(),
+            slot: 42,
+        }]
+    );
+}
+
+#[test]
+fn test_large_mint_threshold() {
+    let tx = transaction(vec![minted(999, 0), minted(1_000, 0)]);
+
+    assert!(event_alerts(&tx, &Rules::default()).is_empty());
+
+    let alerts = event_alerts(
+        &tx,
+        &Rules {
+            large_mint: Some(1_000),
+            ..Rules::default()
+        },
+    );
+    assert_eq!(alerts.len(), 1);
+    assert_eq!(alerts[0].severity, Severity::Critical);
+    assert!(alerts[0].summary.starts_with("Large mint of 1000 by "));
+}
+
+#[test]
+fn test_low_allowance_threshold() {
+    let tx = transaction(vec![minted(1, 501), minted(1, 500)]);
+
+    assert!(event_alerts(&tx, &Rules::default()).is_empty());
+
+    let alerts = event_alerts(
+        &tx,
+        &Rules {
+            low_allowance: Some(500),
+            ..Rules::default()
+        },
+    );
+    assert_eq!(alerts.len(), 1);
+    assert_eq!(alerts[0].severity, Severity::Warning);
+    assert!(alerts[0].summary.ends_with(" has 500 allowance left"));
+}
+
+#[test]
+fn test_failure_alert() {
+    let unauthorized = failure(
+        6000,
+        &[
+            "Program log: Instruction: Pause",
+            "Program log: AnchorError caused by account: admin. Error Code: Unauthorized. Error Number: 6000. Error Message: Unauthorized.",
+        ],
+    );
+    assert_eq!(
+        failure_alert("sig", 7, &unauthorized),
+        Some(Alert {
+            severity: Severity::Warning,
+            summary: "Rejected Pause: signer is not the admin".to_string(),
+            signature: "sig".to_string(),
+            slot: 7,
+        })
+    );
+
+    // Never a minter: the minter config PDA doesn't exist
+    let stranger = failure(
+        3012,
+        &[
+            "Program log: Instruction: MintTokens",
+            "Program log: AnchorError caused by account: minter_config. Error Code: AccountNotInitialized. Error Number: 3012. Error Message: The program expected this account to be already initialized.",
+        ],
+    );
+    assert_eq!(
+        failure_alert("sig", 7, &stranger).map(|alert| alert.summary),
+        Some("Rejected MintTokens: signer is not a minter".to_string())
+    );
+
+    for (error, instruction, reason) in [
+        (
+            StablecoinError::NotOfficer,
+            "ApproveTreasuryBurn",
+            "signer is not a treasury officer",
+        ),
+        (
+            StablecoinError::NotGuardian,
+            "InitiateRecovery",
+            "signer is not a guardian of the holder",
+        ),
+        (
+            StablecoinError::NotBeneficiary,
+            "StartInheritanceClaim",
+            "signer is not the beneficiary of the holder",
+        ),
+        (
+            StablecoinError::NotInstitution,
+            "QueueObligation",
+            "signer is not an institution in the netting window",
+        ),
+        (
+            StablecoinError::NotChannelParty,
+            "CloseChannel",
+            "signer is not a party to the payment channel",
+        ),
+        (
+            StablecoinError::NotAirdropRecipient,
+            "ClaimAirdrop",
+            "signer is not a recipient of the airdrop",
+        ),
+        (
+            StablecoinError::MinterRetired,
+            "ConfigureMinter",
+            "minter was removed",
+        ),
+    ] {
+        let log = format!("Program log: Instruction: {instruction}");
+        let diagnostics = failure(error.into(), &[&log]);
+        assert_eq!(
+            failure_alert("sig", 7, &diagnostics).map(|alert| alert.summary),
+            Some(format!("Rejected {instruction}: {reason}")),
+            "{error:?}"
+        );
+    }
+
+    // Ordinary failures are not security events
+    let exceeds = failure(6002, &["Program log: Instruction: MintTokens"]);
+    assert_eq!(failure_alert("sig", 7, &exceeds), None);
+}
+
+#[test]
+fn test_webhook_payloads() {
+    let alert = Alert {
+        severity: Severity::Critical,
+        summary: "Minting paused".to_string(),
+        signature: "sig".to_string(),
+        slot: 7,
+    };
+
+    let slack = Webhook::Slack("https://hooks.slack.com/services/x".to_string());
+    assert_eq!(slack.url(), "https://hooks.slack.com/services/x");
+    assert_eq!(
+        slack.payload(&alert)["text"],
+        "[critical] Minting paused\nSignature: sig (slot 7)"
+    );
+
+    let pagerduty = Webhook::PagerDuty("key".to_string());
+    assert_eq!(pagerduty.url(), "https://events.pagerduty.com/v2/enqueue");
+    let payload = pagerduty.payload(&alert);
+    assert_eq!(payload["routing_key"], "key");
+    assert_eq!(payload["event_action"], "trigger");
+    assert_eq!(payload["dedup_key"], "sig");
+    assert_eq!(payload["payload"]["severity"], "critical");
+    assert_eq!(payload["payload"]["summary"], "Minting paused");
+
+    let generic = Webhook::Generic("http://localhost/alerts".to_string());
+    assert_eq!(
+        generic.payload(&alert),
+        serde_json::json!({
+            "severity": "critical",
+            "summary": "Minting paused",
+            "signature": "sig",
+            "slot": 7,
+        })
+    );
+}
@@ -0,0 +1,127 @@
+//! Throughput and latency of mints and transfers through litesvm
+//!
+//! Drives thousands of transactions through the built program and reports,
+//! per instruction, transactions per second, wall-time latency percentiles
+//! and mean compute units. Latency covers litesvm executing the whole
+//! transaction, SPL Token CPIs included, so the numbers compare runs on one
+//! machine (say, before and after adding a check), not clusters.
+//!
+//! ```text
+//! anchor build
+//! cargo bench -p stablecoin --bench throughput
+//! BENCH_TRANSACTIONS=20000 cargo bench -p stablecoin --bench throughput
+//! ```
+
+use std::{
+    env,
+    time::{Duration, Instant},
+};
+
+use anchor_litesvm::{Instruction, Keypair, Signer};
+use anchor_spl::token::spl_token;
+use stablecoin_test_utils::{instructions, TestContext};
+
+/// Transactions per instruction, unless `BENCH_TRANSACTIONS` says otherwise
+const DEFAULT_TRANSACTIONS: usize = 5_000;
+
+/// Holders the load is spread over
+const HOLDERS: usize = 16;
+
+const MINT_AMOUNT: u64 = 1_000;
+const TRANSFER_AMOUNT: u64 = 10;
+
+/// Latency and compute units of every transaction of one kind
+struct Samples {
+    name: &'static str,
+    latencies: Vec<Duration>,
+    compute_units: Vec<u64>,
+}
+
+impl Samples {
+    fn new(name: &'static str) -> Self {
+        Self {
+            name,
+            latencies: Vec::new(),
+            compute_units: Vec::new(),
+        }
+    }
+
+    /// Send `ix` and record how long it took, asserting it succeeded
+    ///
+    /// The blockhash is expired first, outside the timing, so repeating an
+    /// identical transaction is not rejected as already processed.
+    fn run(&mut self, ctx: &mut TestContext, ix: Instruction, signers: &[&Keypair]) {
+        ctx.svm.expire_blockhash();
+        let start = Instant::now();
+        let result = ctx.execute(ix, signers);
+        self.latencies.push(start.elapsed());
+        result.assert_success();
+        self.compute_units.push(result.compute_units());
+    }
+
+    fn report(&mut self) {
+        self.latencies.sort();
+        let total: Duration = self.latencies.iter().sum();
+        let percentile = |p: usize| {
+            let index = (self.latencies.len() * p / 100).min(self.latencies.len() - 1);
+            self.latencies[index].as_micros()
+        };
+        println!(
+            "{:<16} {:>8} {:>10.0} {:>8} {:>8} {:>8} {:>8} {:>8}",
+            self.name,
+            self.latencies.len(),
+            self.latencies.len() as f64 / total.as_secs_f64(),
+            percentile(50),
+            percentile(90),
+            percentile(99),
+            self.latencies.last().unwrap().as_micros(),
+            self.compute_units.iter().sum::<u64>() / self.compute_units.len() as u64,
+        );
+    }
+}
+
+fn main() {
+    let transactions = env::var("BENCH_TRANSACTIONS")
+        .map(|n| n.parse().expect("BENCH_TRANSACTIONS must be a number"))
+        .unwrap_or(DEFAULT_TRANSACTIONS);
+
+    let mut ctx = TestContext::new();
+    let minter = ctx.funded_account();
+    let holders: Vec<Keypair> = (0..HOLDERS).map(|_| ctx.funded_account()).collect();
+    ctx.initialize().configure_minter(&minter, u64::MAX);
+    // Create every token account up front, so the runs measure the steady
+    // state rather than account creation
+    for holder in &holders {
+        ctx.mint(&minter, holder, MINT_AMOUNT);
+    }
+
+    let mut mints = Samples::new("mint_tokens");
+    for i in 0..transactions {
+        let holder = &holders[i % HOLDERS];
+        let ix = instructions::mint_tokens(&minter.pubkey(), &holder.pubkey(), MINT_AMOUNT);
+        mints.run(&mut ctx, ix, &[&minter]);
+    }
+
+    let mut transfers = Samples::new("transfer");
+    for i in 0..transactions {
+        let from = &holders[i % HOLDERS];
+        let to = &holders[(i + 1) % HOLDERS];
+        let ix = spl_token::instruction::transfer(
+            &spl_token::ID,
+            &ctx.token_account(from),
+            &ctx.token_account(to),
+            &from.pubkey(),
+            &[],
+            TRANSFER_AMOUNT,
+        )
+        .unwrap();
+        transfers.run(&mut ctx, ix, &[from]);
+    }
+
+    println!(
+        "{:<16} {:>8} {:>10} {:>8} {:>8} {:>8} {:>8} {:>8}",
+        "instruction", "txs", "tx/s", "p50 µs", "p90 µs", "p99 µs", "max µs", "CU"
+    );
+    mints.report();
+    transfers.report();
+}
@@ -0,0 +1,64 @@
+//! Well-formed instruction data with arbitrary accounts
+//!
+//! Each slot picks one of the fixture's accounts, or a key it does not know,
+//! and its signer and writable flags. Besides never panicking, the program
+//! must not succeed unless the instruction's authority signed.
+
+#![no_main]
+
+use anchor_litesvm::{AccountMeta, Pubkey};
+use arbitrary::Arbitrary;
+use libfuzzer_sys::fuzz_target;
+use stablecoin_fuzz::Fixture;
+
+/// Slots past this are dropped, as a transaction could not lock them all
+const MAX_ACCOUNTS: usize = 64;
+
+thread_local! {
+    static FIXTURE: Fixture = Fixture::new();
+}
+
+#[derive(Debug, Arbitrary)]
+struct Input {
+    instruction: u8,
+    amount: u64,
+    accounts: Vec<Slot>,
+}
+
+#[derive(Debug, Arbitrary)]
+struct Slot {
+    account: u8,
+    is_signer: bool,
+    is_writable: bool,
+}
+
+fuzz_target!(|input: Input| {
+    FIXTURE.with(|fixture| {
+        let keys = fixture.keys();
+        let metas: Vec<AccountMeta> = input
+            .accounts
+            .iter()
+            .take(MAX_ACCOUNTS)
+            .map(|slot| AccountMeta {
+                pubkey: keys
+                    .get(slot.account as usize)
+                    .copied()
+                    .unwrap_or_else(|| Pubkey::new_from_array([slot.account; 32])),
+                is_signer: slot.is_signer,
+                is_writable: slot.is_writable,
+            })
+            .collect();
+
+        let ix = fixture.instruction(input.instruction, input.amount);
+        let result = fixture.process(&metas, &ix.data);
+
+        if let (Ok(()), Some(authority)) = (result, fixture.authority(input.instruction)) {
+            assert!(
+                metas
+                    .iter()
+                    .any(|meta| meta.pubkey == authority && meta.is_signer),
+                "succeeded without {authority} signing: {input:?}"
+            );
+        }
+    });
+});
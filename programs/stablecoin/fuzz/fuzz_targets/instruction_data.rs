@@ -0,0 +1,26 @@
+//! Arbitrary instruction data against each instruction's accounts
+//!
+//! The bytes are tried as they come and again behind the instruction's
+//! discriminator, so argument decoding is reached without the fuzzer first
+//! having to find one of the seven discriminators.
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use stablecoin_fuzz::Fixture;
+
+thread_local! {
+    static FIXTURE: Fixture = Fixture::new();
+}
+
+fuzz_target!(|input: (u8, Vec<u8>)| {
+    let (index, data) = input;
+    FIXTURE.with(|fixture| {
+        let ix = fixture.instruction(index, 0);
+        let _ = fixture.process(&ix.accounts, &data);
+
+        let mut prefixed = ix.data[..8].to_vec();
+        prefixed.extend_from_slice(&data);
+        let _ = fixture.process(&ix.accounts, &prefixed);
+    });
+});
@@ -0,0 +1,174 @@
+//! litesvm fixture for fuzzing the stablecoin program
+//!
+//! Instructions run against the built program, as on chain: a panic or
+//! memory fault inside it fails the transaction with
+//! `ProgramFailedToComplete` rather than unwinding here, so
+//! [`Fixture::process`] turns that into a Rust panic for libFuzzer to
+//! report. Everything else the program returns is an ordinary error.
+//!
+//! The fixture is an initialized, unpaused stablecoin with one minter that
+//! has minted to one holder, built once and cloned for every run, so inputs
+//! start from accounts that pass deserialization and reach the handlers.
+//! Like the tests, it loads the built program, building it first if there
+//! is none:
+//!
+//! ```text
+//! cd programs/stablecoin
+//! cargo +nightly fuzz run account_permutations
+//! ```
+
+use anchor_litesvm::{AccountMeta, Instruction, Keypair, LiteSVM, Pubkey, Signer};
+use solana_instruction::error::InstructionError;
+use solana_transaction::Transaction;
+use solana_transaction_error::TransactionError;
+use stablecoin_test_utils::{instructions, pda, TestContext, PROGRAM_ID};
+
+/// Allowance and amount minted the fixture starts with
+pub const ALLOWANCE: u64 = 1_000;
+pub const MINTED: u64 = 100;
+
+/// Number of instructions [`Fixture::instruction`] picks from
+pub const INSTRUCTIONS: u8 = 7;
+
+pub struct Fixture {
+    svm: LiteSVM,
+    payer: Keypair,
+    pub admin: Keypair,
+    pub minter: Keypair,
+    pub holder: Keypair,
+    /// Funded, but holds no role
+    pub outsider: Keypair,
+}
+
+impl Default for Fixture {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Fixture {
+    pub fn new() -> Self {
+        let mut ctx = TestContext::new();
+        let payer = ctx.funded_account();
+        let minter = ctx.funded_account();
+        let holder = ctx.funded_account();
+        let outsider = ctx.funded_account();
+        ctx.initialize()
+            .configure_minter(&minter, ALLOWANCE)
+            .mint(&minter, &holder, MINTED);
+
+        Self {
+            svm: ctx.svm.clone(),
+            payer,
+            admin: ctx.admin.insecure_clone(),
+            minter,
+            holder,
+            outsider,
+        }
+    }
+
+    /// The `index`th program instruction, with its fixture authority
+    pub fn instruction(&self, index: u8, amount: u64) -> Instruction {
+        let admin = self.admin.pubkey();
+        match index % INSTRUCTIONS {
+            0 => instructions::initialize(&admin),
+            1 => instructions::configure_minter(&admin, &self.minter.pubkey(), amount),
+            2 => instructions::remove_minter(&admin, &self.minter.pubkey()),
+            3 => instructions::mint_tokens(&self.minter.pubkey(), &self.holder.pubkey(), amount),
+            4 => instructions::burn_tokens(&self.holder.pubkey(), amount),
+            5 => instructions::pause(&admin),
+            _ => instructions::unpause(&admin),
+        }
+    }
+
+    /// Who has to sign the `index`th instruction for it to succeed
+    ///
+    /// `None` for `initialize`, which anyone may call first.
+    pub fn authority(&self, index: u8) -> Option<Pubkey> {
+        match index % INSTRUCTIONS {
+            0 => None,
+            3 => Some(self.minter.pubkey()),
+            4 => Some(self.holder.pubkey()),
+            _ => Some(self.admin.pubkey()),
+        }
+    }
+
+    fn wallets(&self) -> [&Keypair; 4] {
+        [&self.admin, &self.minter, &self.holder, &self.outsider]
+    }
+
+    /// Every account the fixture knows of: wallets, PDAs, token accounts
+    /// and programs
+    pub fn keys(&self) -> Vec<Pubkey> {
+        let mut keys: Vec<Pubkey> = self
+            .wallets()
+            .iter()
+            .map(|wallet| wallet.pubkey())
+            .collect();
+        keys.extend([
+            pda::config().0,
+            pda::mint().0,
+            pda::minter_config(&self.minter.pubkey()).0,
+            pda::minter_config(&self.outsider.pubkey()).0,
+            pda::token_account(&self.holder.pubkey()),
+            pda::token_account(&self.outsider.pubkey()),
+            PROGRAM_ID,
+            anchor_spl::token::ID,
+            anchor_spl::associated_token::ID,
+            anchor_lang::system_program::ID,
+        ]);
+        keys
+    }
+
+    /// Run the program on `data` with the accounts `metas` name, on a copy
+    /// of the fixture
+    ///
+    /// Only the fixture's wallets can sign; other metas marked as signers
+    /// are sent unsigned. Panics if the program panicked or faulted.
+    pub fn process(&self, metas: &[AccountMeta], data: &[u8]) -> Result<(), TransactionError> {
+        let wallets = self.wallets();
+        let mut signers = vec![&self.payer];
+        let accounts = metas
+            .iter()
+            .map(|meta| {
+                let wallet = wallets
+                    .into_iter()
+                    .find(|wallet| wallet.pubkey() == meta.pubkey);
+                if let Some(wallet) = wallet.filter(|_| meta.is_signer) {
+                    if !signers.iter().any(|signer| signer.pubkey() == meta.pubkey) {
+                        signers.push(wallet);
+                    }
+                }
+                AccountMeta {
+                    is_signer: meta.is_signer && wallet.is_some(),
+                    ..meta.clone()
+                }
+            })
+            .collect();
+        let ix = Instruction {
+            program_id: PROGRAM_ID,
+            accounts,
+            data: data.to_vec(),
+        };
+
+        let mut svm = self.svm.clone();
+        let tx = Transaction::new_signed_with_payer(
+            &[ix],
+            Some(&self.payer.pubkey()),
+            &signers,
+            svm.latest_blockhash(),
+        );
+        let Err(failed) = svm.send_transaction(tx) else {
+            return Ok(());
+        };
+        if let TransactionError::InstructionError(_, InstructionError::ProgramFailedToComplete) =
+            failed.err
+        {
+            panic!(
+                "program failed to complete:\n{}",
+                failed.meta.logs.join("\n")
+            );
+        }
+        Err(failed.err)
+    }
+}
@@ -0,0 +1,442 @@
+use anchor_lang::prelude::*;
+
+#[error_code]
+pub enum StablecoinError {
+    #[msg("You are not authorized to perform this action")]
+    Unauthorized,
+    #[msg("Minting is currently paused")]
+    Paused,
+    #[msg("Mint amount exceeds minter's remaining allowance")]
+    ExceedsAllowance,
+    #[msg("Account is not an authorized minter")]
+    NotMinter,
+    #[msg("Arithmetic overflow")]
+    Overflow,
+    #[msg("A mint with this idempotency key was already made")]
+    DuplicateMint,
+    #[msg("Idempotency record has not expired yet")]
+    IdempotencyRecordNotExpired,
+    #[msg("Account is not a treasury officer")]
+    NotOfficer,
+    #[msg("Treasury officers must be two distinct keys")]
+    InvalidOfficers,
+    #[msg("Officer has already approved this burn")]
+    AlreadyApproved,
+    #[msg("Treasury burn does not have enough approvals")]
+    InsufficientApprovals,
+    #[msg("Token account balance is above the dust threshold")]
+    NotDust,
+    #[msg("Account is not a guardian of this holder")]
+    NotGuardian,
+    #[msg("Recovery settings are invalid")]
+    InvalidRecoveryConfig,
+    #[msg("A recovery is already in progress")]
+    RecoveryInProgress,
+    #[msg("No recovery is in progress")]
+    NoRecoveryInProgress,
+    #[msg("Guardian has already approved this recovery")]
+    GuardianAlreadyApproved,
+    #[msg("Recovery does not have a quorum of guardian approvals")]
+    RecoveryNotApproved,
+    #[msg("Recovery challenge period has not elapsed yet")]
+    RecoveryChallengeActive,
+    #[msg("Account is not the beneficiary of this holder")]
+    NotBeneficiary,
+    #[msg("Inheritance settings are invalid")]
+    InvalidInheritancePlan,
+    #[msg("Holder has checked in too recently")]
+    HolderStillActive,
+    #[msg("An inheritance claim is already in progress")]
+    InheritanceClaimInProgress,
+    #[msg("No inheritance claim is in progress")]
+    NoInheritanceClaim,
+    #[msg("Inheritance notice period has not elapsed yet")]
+    InheritanceNoticeActive,
+    #[msg("Account is not an institution in this netting window")]
+    NotInstitution,
+    #[msg("Netting window settings are invalid")]
+    InvalidNettingWindow,
+    #[msg("Netting window has closed")]
+    NettingWindowClosed,
+    #[msg("Netting window is still open")]
+    NettingWindowOpen,
+    #[msg("Obligation is invalid")]
+    InvalidObligation,
+    #[msg("Settlement accounts do not match the netting window")]
+    InvalidSettlementAccounts,
+    #[msg("Payment channel settings are invalid")]
+    InvalidPaymentChannel,
+    #[msg("Account is neither the sender nor the recipient of this payment channel")]
+    NotChannelParty,
+    #[msg("Balance update is below one already submitted or above the deposit")]
+    InvalidChannelState,
+    #[msg("Balance update is not signed by the channel's sender")]
+    InvalidChannelSignature,
+    #[msg("Payment channel dispute period has ended")]
+    ChannelDisputeOver,
+    #[msg("Payment channel is open or still in its dispute period")]
+    ChannelDisputeActive,
+    #[msg("Purpose code is invalid")]
+    InvalidPurposeCode,
+    #[msg("Minter set is frozen")]
+    MinterSetFrozen,
+    #[msg("Minter has paused itself")]
+    MinterPaused,
+    #[msg("Faucet daily limit exceeded")]
+    FaucetLimitExceeded,
+    #[msg("Airdrop settings are invalid")]
+    InvalidAirdrop,
+    #[msg("Account is not a recipient of this airdrop")]
+    NotAirdropRecipient,
+    #[msg("Airdrop already claimed")]
+    AirdropClaimed,
+    #[msg("Airdrop has closed")]
+    AirdropClosed,
+    #[msg("Airdrop is still open")]
+    AirdropOpen,
+    #[msg("Account cannot be resized")]
+    CannotResize,
+    #[msg("Minter was removed")]
+    MinterRetired,
+    #[msg("Account was laid out by a newer version of the program")]
+    NewerAccountVersion,
+    #[msg("Debtor cannot escrow its net debit in the window")]
+    ObligationUnderfunded,
+    #[msg("Token account does not delegate its balance to the program")]
+    DelegateNotApproved,
+}
+
+/// A custom error as clients and support tooling present it
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ErrorInfo {
+    pub code: u32,
+    /// The variant name; stable across releases
+    pub name: &'static str,
+    /// Same as the error's `#[msg]`
+    pub message: &'static str,
+    /// What the caller can do about it
+    pub hint: &'static str,
+}
+
+/// Every custom error, in code order
+pub const ERROR_TABLE: &[ErrorInfo] = &[
+    ErrorInfo {
+        code: 6000,
+        name: "Unauthorized",
+        message: "You are not authorized to perform this action",
+        hint: "Sign with the admin keypair recorded in the config account",
+    },
+    ErrorInfo {
+        code: 6001,
+        name: "Paused",
+        message: "Minting is currently paused",
+        hint: "Wait for the admin to unpause the stablecoin",
+    },
+    ErrorInfo {
+        code: 6002,
+        name: "ExceedsAllowance",
+        message: "Mint amount exceeds minter's remaining allowance",
+        hint: "Mint at most the remaining allowance, or have the admin raise it with configure_minter",
+    },
+    ErrorInfo {
+        code: 6003,
+        name: "NotMinter",
+        message: "Account is not an authorized minter",
+        hint: "Have the admin configure this signer as a minter with configure_minter",
+    },
+    ErrorInfo {
+        code: 6004,
+        name: "Overflow",
+        message: "Arithmetic overflow",
+        hint: "Mint a smaller amount; the minter's running total would not fit in a u64",
+    },
+    ErrorInfo {
+        code: 6005,
+        name: "DuplicateMint",
+        message: "A mint with this idempotency key was already made",
+        hint: "Nothing to do: the earlier mint with this key went through",
+    },
+    ErrorInfo {
+        code: 6006,
+        name: "IdempotencyRecordNotExpired",
+        message: "Idempotency record has not expired yet",
+        hint: "Wait until the record's expires_at before reclaiming it",
+    },
+    ErrorInfo {
+        code: 6007,
+        name: "NotOfficer",
+        message: "Account is not a treasury officer",
+        hint: "Sign with one of the officers recorded in the treasury account",
+    },
+    ErrorInfo {
+        code: 6008,
+        name: "InvalidOfficers",
+        message: "Treasury officers must be two distinct keys",
+        hint: "Pass two different, non-default officer keys to configure_treasury",
+    },
+    ErrorInfo {
+        code: 6009,
+        name: "AlreadyApproved",
+        message: "Officer has already approved this burn",
+        hint: "Have the other officer approve the burn",
+    },
+    ErrorInfo {
+        code: 6010,
+        name: "InsufficientApprovals",
+        message: "Treasury burn does not have enough approvals",
+        hint: "Burns above the treasury threshold need both officers; have the other officer approve first",
+    },
+    ErrorInfo {
+        code: 6011,
+        name: "NotDust",
+        message: "Token account balance is above the dust threshold",
+        hint: "Leave that account out of the sweep, or transfer from it directly",
+    },
+    ErrorInfo {
+        code: 6012,
+        name: "NotGuardian",
+        message: "Account is not a guardian of this holder",
+        hint: "Sign with one of the guardians recorded in the holder's recovery config",
+    },
+    ErrorInfo {
+        code: 6013,
+        name: "InvalidRecoveryConfig",
+        message: "Recovery settings are invalid",
+        hint: "Use 1 to 5 distinct guardians other than the holder, a quorum no larger than that, a challenge period of at least a day, and a new owner other than the holder",
+    },
+    ErrorInfo {
+        code: 6014,
+        name: "RecoveryInProgress",
+        message: "A recovery is already in progress",
+        hint: "Approve the recovery in progress, or have the holder cancel it first",
+    },
+    ErrorInfo {
+        code: 6015,
+        name: "NoRecoveryInProgress",
+        message: "No recovery is in progress",
+        hint: "Have a guardian initiate a recovery first",
+    },
+    ErrorInfo {
+        code: 6016,
+        name: "GuardianAlreadyApproved",
+        message: "Guardian has already approved this recovery",
+        hint: "Have another guardian approve the recovery",
+    },
+    ErrorInfo {
+        code: 6017,
+        name: "RecoveryNotApproved",
+        message: "Recovery does not have a quorum of guardian approvals",
+        hint: "Have more guardians approve the recovery",
+    },
+    ErrorInfo {
+        code: 6018,
+        name: "RecoveryChallengeActive",
+        message: "Recovery challenge period has not elapsed yet",
+        hint: "Wait until the challenge period after initiation has passed",
+    },
+    ErrorInfo {
+        code: 6019,
+        name: "NotBeneficiary",
+        message: "Account is not the beneficiary of this holder",
+        hint: "Sign with the beneficiary recorded in the holder's inheritance plan",
+    },
+    ErrorInfo {
+        code: 6020,
+        name: "InvalidInheritancePlan",
+        message: "Inheritance settings are invalid",
+        hint: "Name a beneficiary other than the holder, at least one epoch of inactivity and a notice period of at least 30 days",
+    },
+    ErrorInfo {
+        code: 6021,
+        name: "HolderStillActive",
+        message: "Holder has checked in too recently",
+        hint: "Wait until the holder has gone the plan's inactivity epochs without checking in",
+    },
+    ErrorInfo {
+        code: 6022,
+        name: "InheritanceClaimInProgress",
+        message: "An inheritance claim is already in progress",
+        hint: "Wait out the notice period and claim, or have the holder check in to stop it",
+    },
+    ErrorInfo {
+        code: 6023,
+        name: "NoInheritanceClaim",
+        message: "No inheritance claim is in progress",
+        hint: "Have the beneficiary start a claim first",
+    },
+    ErrorInfo {
+        code: 6024,
+        name: "InheritanceNoticeActive",
+        message: "Inheritance notice period has not elapsed yet",
+        hint: "Wait until the notice period after the claim started has passed",
+    },
+    ErrorInfo {
+        code: 6025,
+        name: "NotInstitution",
+        message: "Account is not an institution in this netting window",
+        hint: "Sign with one of the institutions the admin registered for the window",
+    },
+    ErrorInfo {
+        code: 6026,
+        name: "InvalidNettingWindow",
+        message: "Netting window settings are invalid",
+        hint: "Register between 2 and 8 distinct institutions, none the default key, for a positive duration",
+    },
+    ErrorInfo {
+        code: 6027,
+        name: "NettingWindowClosed",
+        message: "Netting window has closed",
+        hint: "Queue the obligation in a window that is still open",
+    },
+    ErrorInfo {
+        code: 6028,
+        name: "NettingWindowOpen",
+        message: "Netting window is still open",
+        hint: "Wait until the window's closing time has passed",
+    },
+    ErrorInfo {
+        code: 6029,
+        name: "InvalidObligation",
+        message: "Obligation is invalid",
+        hint: "Name another institution in the window as creditor and a nonzero amount",
+    },
+    ErrorInfo {
+        code: 6030,
+        name: "InvalidSettlementAccounts",
+        message: "Settlement accounts do not match the netting window",
+        hint: "Pass each institution's associated token account, in the window's order",
+    },
+    ErrorInfo {
+        code: 6031,
+        name: "InvalidPaymentChannel",
+        message: "Payment channel settings are invalid",
+        hint: "Pay someone other than yourself, deposit a nonzero amount, and allow at least MIN_CHANNEL_DISPUTE_PERIOD seconds for disputes",
+    },
+    ErrorInfo {
+        code: 6032,
+        name: "NotChannelParty",
+        message: "Account is neither the sender nor the recipient of this payment channel",
+        hint: "Sign with the channel's sender or recipient",
+    },
+    ErrorInfo {
+        code: 6033,
+        name: "InvalidChannelState",
+        message: "Balance update is below one already submitted or above the deposit",
+        hint: "Submit the latest balance update the sender signed",
+    },
+    ErrorInfo {
+        code: 6034,
+        name: "InvalidChannelSignature",
+        message: "Balance update is not signed by the channel's sender",
+        hint: "Put an Ed25519 program instruction verifying the sender's signature right before this one",
+    },
+    ErrorInfo {
+        code: 6035,
+        name: "ChannelDisputeOver",
+        message: "Payment channel dispute period has ended",
+        hint: "Settle the channel with the balance update already submitted",
+    },
+    ErrorInfo {
+        code: 6036,
+        name: "ChannelDisputeActive",
+        message: "Payment channel is open or still in its dispute period",
+        hint: "Close the channel first, then wait until its dispute period has ended",
+    },
+    ErrorInfo {
+        code: 6037,
+        name: "InvalidPurposeCode",
+        message: "Purpose code is invalid",
+        hint: "Use four uppercase ASCII letters or digits, such as PAYR",
+    },
+    ErrorInfo {
+        code: 6038,
+        name: "MinterSetFrozen",
+        message: "Minter set is frozen",
+        hint: "Wait for the admin to unfreeze the minter set; minting continues meanwhile",
+    },
+    ErrorInfo {
+        code: 6039,
+        name: "MinterPaused",
+        message: "Minter has paused itself",
+        hint: "Ask the admin to resume the minter once its key is known to be safe",
+    },
+    ErrorInfo {
+        code: 6040,
+        name: "FaucetLimitExceeded",
+        message: "Faucet daily limit exceeded",
+        hint: "Claim a smaller amount, or wait for the next UTC day",
+    },
+    ErrorInfo {
+        code: 6041,
+        name: "InvalidAirdrop",
+        message: "Airdrop settings are invalid",
+        hint: "Pass between 1 and 16 distinct recipients, none the default key, each with a nonzero amount, for a positive duration",
+    },
+    ErrorInfo {
+        code: 6042,
+        name: "NotAirdropRecipient",
+        message: "Account is not a recipient of this airdrop",
+        hint: "Sign with one of the wallets the airdrop was opened for",
+    },
+    ErrorInfo {
+        code: 6043,
+        name: "AirdropClaimed",
+        message: "Airdrop already claimed",
+        hint: "Each recipient claims their amount once",
+    },
+    ErrorInfo {
+        code: 6044,
+        name: "AirdropClosed",
+        message: "Airdrop has closed",
+        hint: "Claims end when the airdrop closes; what is left goes to the treasury",
+    },
+    ErrorInfo {
+        code: 6045,
+        name: "AirdropOpen",
+        message: "Airdrop is still open",
+        hint: "Wait until the airdrop closes before clawing it back",
+    },
+    ErrorInfo {
+        code: 6046,
+        name: "CannotResize",
+        message: "Account cannot be resized",
+        hint: "Pass one of the program's accounts",
+    },
+    ErrorInfo {
+        code: 6047,
+        name: "MinterRetired",
+        message: "Minter was removed",
+        hint: "Configure the minter under a new key; a removed minter's key cannot be configured again",
+    },
+    ErrorInfo {
+        code: 6048,
+        name: "NewerAccountVersion",
+        message: "Account was laid out by a newer version of the program",
+        hint: "Upgrade the program first; this version would drop the fields it does not know",
+    },
+    ErrorInfo {
+        code: 6049,
+        name: "ObligationUnderfunded",
+        message: "Debtor cannot escrow its net debit in the window",
+        hint: "Fund the debtor's token account to cover its net debit before queueing",
+    },
+    ErrorInfo {
+        code: 6050,
+        name: "DelegateNotApproved",
+        message: "Token account does not delegate its balance to the program",
+        hint: "The holder revoked or replaced the program's delegate; configuring recovery or inheritance again approves it",
+    },
+];
+
+impl StablecoinError {
+    /// This error's entry in [`ERROR_TABLE`]
+    pub fn info(self) -> &'static ErrorInfo {
+        &ERROR_TABLE[self as usize]
+    }
+}
+
+/// The [`ERROR_TABLE`] entry for a custom error code
+pub fn error_info(code: u32) -> Option<&'static ErrorInfo> {
+    let index = code.checked_sub(anchor_lang::error::ERROR_CODE_OFFSET)?;
+    ERROR_TABLE.get(index as usize)
+}
@@ -0,0 +1,297 @@
+use anchor_lang::prelude::*;
+
+/// Emitted once when the stablecoin is initialized
+#[event]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Initialized {
+    pub admin: Pubkey,
+    pub mint: Pubkey,
+}
+
+/// Emitted when a minter is added or its allowance is updated
+#[event]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MinterConfigured {
+    pub minter: Pubkey,
+    pub allowance: u64,
+}
+
+/// Emitted when a minter's config is closed
+#[event]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MinterRemoved {
+    pub minter: Pubkey,
+}
+
+/// Emitted on every successful mint, with what the minter's allowance has
+/// left after it
+#[event]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TokensMinted {
+    pub minter: Pubkey,
+    pub destination: Pubkey,
+    pub amount: u64,
+    pub remaining_allowance: u64,
+}
+
+/// Emitted on every successful burn
+#[event]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TokensBurned {
+    pub owner: Pubkey,
+    pub token_account: Pubkey,
+    pub amount: u64,
+}
+
+/// Emitted when minting is paused
+#[event]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Paused {
+    pub admin: Pubkey,
+}
+
+/// Emitted when minting is unpaused
+#[event]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Unpaused {
+    pub admin: Pubkey,
+}
+
+/// Emitted when the admin freezes the minter set
+#[event]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MinterSetFrozen {
+    pub admin: Pubkey,
+}
+
+/// Emitted when the admin unfreezes the minter set
+#[event]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MinterSetUnfrozen {
+    pub admin: Pubkey,
+}
+
+/// Emitted when a minter pauses itself
+#[event]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MinterSelfPaused {
+    pub minter: Pubkey,
+}
+
+/// Emitted when the admin resumes a minter that paused itself
+#[event]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MinterResumed {
+    pub minter: Pubkey,
+    pub admin: Pubkey,
+}
+
+/// Emitted when the admin appoints the treasury officers
+#[event]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TreasuryConfigured {
+    pub admin: Pubkey,
+    pub officers: [Pubkey; 2],
+    pub threshold: u64,
+}
+
+/// Emitted when an officer proposes a treasury burn
+#[event]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TreasuryBurnProposed {
+    pub id: u64,
+    pub proposer: Pubkey,
+    pub amount: u64,
+}
+
+/// Emitted when an officer approves a proposed treasury burn
+#[event]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TreasuryBurnApproved {
+    pub id: u64,
+    pub officer: Pubkey,
+}
+
+/// Emitted when an officer drops a proposed treasury burn
+#[event]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TreasuryBurnCancelled {
+    pub id: u64,
+    pub officer: Pubkey,
+}
+
+/// Emitted when a holder sets or replaces their recovery guardians
+#[event]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RecoveryConfigured {
+    pub owner: Pubkey,
+    pub guardians: Vec<Pubkey>,
+    pub quorum: u8,
+    pub challenge_period: i64,
+}
+
+/// Emitted when a guardian starts recovering a holder's balance
+#[event]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RecoveryInitiated {
+    pub owner: Pubkey,
+    pub new_owner: Pubkey,
+    pub guardian: Pubkey,
+}
+
+/// Emitted when a holder cancels a recovery of their balance
+#[event]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RecoveryCancelled {
+    pub owner: Pubkey,
+}
+
+/// Emitted when a recovery moves a holder's balance to the new owner
+#[event]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AccountRecovered {
+    pub owner: Pubkey,
+    pub new_owner: Pubkey,
+    pub amount: u64,
+}
+
+/// Emitted when a holder sets or replaces their inheritance plan
+#[event]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InheritanceConfigured {
+    pub owner: Pubkey,
+    pub beneficiary: Pubkey,
+    pub inactivity_epochs: u64,
+    pub notice_period: i64,
+}
+
+/// Emitted when a beneficiary starts a claim on the balance of a holder who
+/// stopped checking in
+#[event]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InheritanceClaimStarted {
+    pub owner: Pubkey,
+    pub beneficiary: Pubkey,
+    pub claimable_at: i64,
+}
+
+/// Emitted when a holder checks in while a claim on their balance is in
+/// progress, stopping it
+#[event]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InheritanceClaimStopped {
+    pub owner: Pubkey,
+}
+
+/// Emitted when a claim moves a holder's balance to the beneficiary
+#[event]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InheritanceClaimed {
+    pub owner: Pubkey,
+    pub beneficiary: Pubkey,
+    pub amount: u64,
+}
+
+/// Emitted when an institution queues an obligation in a netting window
+#[event]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ObligationQueued {
+    pub id: u64,
+    pub debtor: Pubkey,
+    pub creditor: Pubkey,
+    pub amount: u64,
+    pub purpose: Option<[u8; 4]>,
+}
+
+/// Emitted when a netting window settles, moving `net` for `gross` queued
+#[event]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NetSettled {
+    pub id: u64,
+    pub gross: u64,
+    pub net: u64,
+}
+
+/// Emitted when a payment channel starts closing, and again for each later
+/// balance update submitted before it settles
+#[event]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChannelClosing {
+    pub sender: Pubkey,
+    pub recipient: Pubkey,
+    pub amount: u64,
+    pub closes_at: i64,
+}
+
+/// Emitted when a payment channel settles, paying `amount` and refunding the
+/// sender `refund`
+#[event]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChannelSettled {
+    pub sender: Pubkey,
+    pub recipient: Pubkey,
+    pub amount: u64,
+    pub refund: u64,
+}
+
+/// Emitted when an owner registers a sub-account, so indexers can attribute
+/// deposits to `custody` by index
+#[event]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SubAccountRegistered {
+    pub owner: Pubkey,
+    pub index: u64,
+    pub purpose: [u8; 4],
+    pub custody: Pubkey,
+}
+
+/// Emitted when an owner sweeps `amount` out of a sub-account
+#[event]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SubAccountSwept {
+    pub owner: Pubkey,
+    pub index: u64,
+    pub amount: u64,
+}
+
+/// Emitted when a funder opens an airdrop, paying `total` into its custody
+#[event]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AirdropOpened {
+    pub funder: Pubkey,
+    pub id: u64,
+    pub total: u64,
+    pub closes_at: i64,
+}
+
+/// Emitted when a recipient claims `amount` from an airdrop
+#[event]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AirdropClaimed {
+    pub funder: Pubkey,
+    pub id: u64,
+    pub recipient: Pubkey,
+    pub amount: u64,
+}
+
+/// Emitted when a closed airdrop's remaining `amount` moves to treasury
+/// custody
+#[event]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AirdropClawedBack {
+    pub funder: Pubkey,
+    pub id: u64,
+    pub amount: u64,
+}
+
+/// Emitted when the admin resizes one of the program's accounts, `top_up`
+/// being the rent the payer added
+#[event]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AccountResized {
+    pub admin: Pubkey,
+    pub account: Pubkey,
+    pub old_len: u32,
+    pub new_len: u32,
+    pub top_up: u64,
+}
@@ -0,0 +1,59 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    seeds::RECOVERY_SEED,
+    validation::{self, Checked, Policy, Role},
+    Config, RecoveryConfig, StablecoinError,
+};
+
+#[derive(Accounts)]
+pub struct ApproveRecovery<'info> {
+    /// The guardian approving the recovery
+    #[account(
+        constraint = validation::is_guardian(&guardian.key(), &recovery_config) @ StablecoinError::NotGuardian
+    )]
+    pub guardian: Signer<'info>,
+
+    /// The settings of the holder being recovered
+    #[account(
+        mut,
+        seeds = [RECOVERY_SEED, recovery_config.owner.as_ref()],
+        bump = recovery_config.bump
+    )]
+    pub recovery_config: Account<'info, RecoveryConfig>,
+}
+
+impl ApproveRecovery<'_> {
+    pub fn approve_recovery(&mut self, _: Checked<Self>) -> Result<()> {
+        let recovery_config = &mut self.recovery_config;
+        require!(recovery_config.in_progress(), StablecoinError::NoRecoveryInProgress);
+
+        let index = recovery_config.guardian_index(&self.guardian.key()).ok_or(StablecoinError::NotGuardian)?;
+        require!(
+            recovery_config.approvals & (1 << index) == 0,
+            StablecoinError::GuardianAlreadyApproved
+        );
+        recovery_config.approvals |= 1 << index;
+
+        msg!("Recovery of {} approved by {}", recovery_config.owner, self.guardian.key());
+
+        Ok(())
+    }
+}
+
+impl Policy for ApproveRecovery<'_> {
+    const ROLE: Role = Role::Guardian;
+    const PAUSABLE: bool = false;
+
+    fn config(&self) -> Option<&Config> {
+        None
+    }
+
+    fn signer(&self) -> Pubkey {
+        self.guardian.key()
+    }
+
+    fn recovery_config(&self) -> Option<&RecoveryConfig> {
+        Some(&self.recovery_config)
+    }
+}
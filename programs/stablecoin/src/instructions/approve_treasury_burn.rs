@@ -0,0 +1,77 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    seeds::{PENDING_BURN_SEED, TREASURY_SEED},
+    validation::{self, Checked, Policy, Role},
+    Config, PendingBurn, StablecoinError, Treasury, TreasuryBurnApproved,
+};
+
+#[derive(Accounts)]
+pub struct ApproveTreasuryBurn<'info> {
+    /// The officer approving the burn
+    #[account(
+        constraint = validation::is_officer(&officer.key(), &treasury) @ StablecoinError::NotOfficer
+    )]
+    pub officer: Signer<'info>,
+
+    #[account(
+        seeds = [TREASURY_SEED],
+        bump = treasury.bump
+    )]
+    pub treasury: Account<'info, Treasury>,
+
+    /// The proposal being approved
+    #[account(
+        mut,
+        seeds = [PENDING_BURN_SEED, pending_burn.id.to_le_bytes().as_ref()],
+        bump = pending_burn.bump
+    )]
+    pub pending_burn: Account<'info, PendingBurn>,
+}
+
+impl ApproveTreasuryBurn<'_> {
+    pub fn approve_treasury_burn(&mut self, _: Checked<Self>) -> Result<()> {
+        let officer = self.officer.key();
+        let treasury = &self.treasury;
+        let pending_burn = &mut self.pending_burn;
+        require!(
+            !pending_burn.approvals.contains(&officer),
+            StablecoinError::AlreadyApproved
+        );
+
+        // Take a slot that holds no current officer's approval; there is
+        // none once every officer has approved
+        let slot = pending_burn
+            .approvals
+            .iter()
+            .position(|approval| !treasury.is_officer(approval))
+            .ok_or(StablecoinError::AlreadyApproved)?;
+        pending_burn.approvals[slot] = officer;
+
+        msg!("Approved treasury burn {}", pending_burn.id);
+
+        emit!(TreasuryBurnApproved {
+            id: pending_burn.id,
+            officer,
+        });
+
+        Ok(())
+    }
+}
+
+impl Policy for ApproveTreasuryBurn<'_> {
+    const ROLE: Role = Role::Officer;
+    const PAUSABLE: bool = false;
+
+    fn config(&self) -> Option<&Config> {
+        None
+    }
+
+    fn signer(&self) -> Pubkey {
+        self.officer.key()
+    }
+
+    fn treasury(&self) -> Option<&Treasury> {
+        Some(&self.treasury)
+    }
+}
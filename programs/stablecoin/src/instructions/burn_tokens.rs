@@ -0,0 +1,90 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Mint, Token, TokenAccount, burn, Burn};
+
+use crate::{
+    seeds::{CONFIG_SEED, MINT_SEED, SUPPLY_STATS_SEED},
+    validation::{Checked, Policy, Role},
+    Config, SupplyStats, TokensBurned,
+};
+
+#[derive(Accounts)]
+pub struct BurnTokens<'info> {
+    /// The owner of the token account burning tokens
+    pub owner: Signer<'info>,
+
+    /// The config account
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump
+    )]
+    pub config: Box<Account<'info, Config>>,
+
+    /// The stablecoin mint
+    #[account(
+        mut,
+        seeds = [MINT_SEED],
+        bump = config.mint_bump
+    )]
+    pub mint: Box<Account<'info, Mint>>,
+
+    /// The time-weighted supply, updated with the new supply
+    #[account(
+        mut,
+        seeds = [SUPPLY_STATS_SEED],
+        bump = supply_stats.bump
+    )]
+    pub supply_stats: Account<'info, SupplyStats>,
+
+    /// The token account to burn from
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = owner,
+    )]
+    pub token_account: Box<Account<'info, TokenAccount>>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+impl BurnTokens<'_> {
+    pub fn burn_tokens(&mut self, _: Checked<Self>, amount: u64) -> Result<()> {
+        burn(
+            CpiContext::new(
+                self.token_program.key(),
+                Burn {
+                    mint: self.mint.to_account_info(),
+                    from: self.token_account.to_account_info(),
+                    authority: self.owner.to_account_info(),
+                },
+            ),
+            amount,
+        )?;
+
+        self.mint.reload()?;
+        self.supply_stats.record(self.mint.supply, Clock::get()?.unix_timestamp)?;
+
+        msg!("Burned {} tokens from {}", amount, self.token_account.key());
+
+        emit!(TokensBurned {
+            owner: self.owner.key(),
+            token_account: self.token_account.key(),
+            amount,
+        });
+
+        Ok(())
+    }
+}
+
+/// Holders can always burn what they own; SPL Token checks the owner signed
+impl Policy for BurnTokens<'_> {
+    const ROLE: Role = Role::Anyone;
+    const PAUSABLE: bool = false;
+
+    fn config(&self) -> Option<&Config> {
+        Some(&self.config)
+    }
+
+    fn signer(&self) -> Pubkey {
+        self.owner.key()
+    }
+}
@@ -0,0 +1,95 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Mint, Token, TokenAccount};
+
+use super::settle_net::pay_out;
+use crate::{
+    seeds::{CONFIG_SEED, MINT_SEED, NETTING_SEED},
+    validation::{self, Checked, Policy, Role},
+    Config, NettingWindow, StablecoinError,
+};
+
+#[derive(Accounts)]
+pub struct CancelNettingWindow<'info> {
+    /// Only the admin can drop a window
+    #[account(
+        constraint = validation::is_admin(&admin.key(), &config) @ StablecoinError::Unauthorized
+    )]
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump
+    )]
+    pub config: Box<Account<'info, Config>>,
+
+    /// The stablecoin mint
+    #[account(
+        seeds = [MINT_SEED],
+        bump = config.mint_bump
+    )]
+    pub mint: Box<Account<'info, Mint>>,
+
+    /// The window to drop, with everything queued in it
+    #[account(
+        mut,
+        close = opened_by,
+        has_one = opened_by,
+        seeds = [NETTING_SEED, netting_window.id.to_le_bytes().as_ref()],
+        bump = netting_window.bump
+    )]
+    pub netting_window: Account<'info, NettingWindow>,
+
+    /// CHECK: The admin that opened the window, who gets its rent back
+    #[account(mut)]
+    pub opened_by: UncheckedAccount<'info>,
+
+    /// Window custody, which holds the escrowed net debits
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = netting_window,
+    )]
+    pub custody: Box<Account<'info, TokenAccount>>,
+
+    pub token_program: Program<'info, Token>,
+    // Remaining accounts: each institution's associated token account, in
+    // the window's order, writable
+}
+
+impl<'info> CancelNettingWindow<'info> {
+    /// Refund each institution's escrow; the window itself is closed by its
+    /// `close` constraint once this returns
+    pub fn cancel_netting_window(
+        &mut self,
+        _: Checked<Self>,
+        token_accounts: &[AccountInfo<'info>],
+    ) -> Result<()> {
+        let netting_window = &self.netting_window;
+        pay_out(
+            netting_window,
+            &self.mint.key(),
+            &self.custody,
+            &self.opened_by,
+            &self.token_program,
+            token_accounts,
+            &netting_window.escrowed,
+        )?;
+
+        msg!("Cancelled netting window {}", netting_window.id);
+
+        Ok(())
+    }
+}
+
+impl Policy for CancelNettingWindow<'_> {
+    const ROLE: Role = Role::Admin;
+    const PAUSABLE: bool = false;
+
+    fn config(&self) -> Option<&Config> {
+        Some(&self.config)
+    }
+
+    fn signer(&self) -> Pubkey {
+        self.admin.key()
+    }
+}
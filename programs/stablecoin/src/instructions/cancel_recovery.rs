@@ -0,0 +1,52 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    seeds::RECOVERY_SEED,
+    validation::{Checked, Policy, Role},
+    Config, RecoveryCancelled, RecoveryConfig, StablecoinError,
+};
+
+#[derive(Accounts)]
+pub struct CancelRecovery<'info> {
+    /// The holder, who still has their key
+    pub owner: Signer<'info>,
+
+    /// The holder's recovery settings
+    #[account(
+        mut,
+        seeds = [RECOVERY_SEED, owner.key().as_ref()],
+        bump = recovery_config.bump
+    )]
+    pub recovery_config: Account<'info, RecoveryConfig>,
+}
+
+impl CancelRecovery<'_> {
+    pub fn cancel_recovery(&mut self, _: Checked<Self>) -> Result<()> {
+        let recovery_config = &mut self.recovery_config;
+        require!(recovery_config.in_progress(), StablecoinError::NoRecoveryInProgress);
+        recovery_config.clear();
+
+        msg!("Recovery of {} cancelled", self.owner.key());
+
+        emit!(RecoveryCancelled {
+            owner: self.owner.key(),
+        });
+
+        Ok(())
+    }
+}
+
+/// Holders cancel recoveries of their own account; the config's seeds bind
+/// it to the signer
+impl Policy for CancelRecovery<'_> {
+    const ROLE: Role = Role::Anyone;
+    const PAUSABLE: bool = false;
+
+    fn config(&self) -> Option<&Config> {
+        None
+    }
+
+    fn signer(&self) -> Pubkey {
+        self.owner.key()
+    }
+}
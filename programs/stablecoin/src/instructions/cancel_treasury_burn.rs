@@ -0,0 +1,67 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    seeds::{PENDING_BURN_SEED, TREASURY_SEED},
+    validation::{self, Checked, Policy, Role},
+    Config, PendingBurn, StablecoinError, Treasury, TreasuryBurnCancelled,
+};
+
+#[derive(Accounts)]
+pub struct CancelTreasuryBurn<'info> {
+    /// Either officer can drop a proposal
+    #[account(
+        constraint = validation::is_officer(&officer.key(), &treasury) @ StablecoinError::NotOfficer
+    )]
+    pub officer: Signer<'info>,
+
+    #[account(
+        seeds = [TREASURY_SEED],
+        bump = treasury.bump
+    )]
+    pub treasury: Account<'info, Treasury>,
+
+    /// The proposal to drop
+    #[account(
+        mut,
+        close = proposer,
+        has_one = proposer,
+        seeds = [PENDING_BURN_SEED, pending_burn.id.to_le_bytes().as_ref()],
+        bump = pending_burn.bump
+    )]
+    pub pending_burn: Account<'info, PendingBurn>,
+
+    /// CHECK: The proposer recorded in the proposal, who gets its rent back
+    #[account(mut)]
+    pub proposer: UncheckedAccount<'info>,
+}
+
+impl CancelTreasuryBurn<'_> {
+    /// The proposal itself is closed by its `close` constraint once this returns
+    pub fn cancel_treasury_burn(&mut self, _: Checked<Self>) -> Result<()> {
+        msg!("Cancelled treasury burn {}", self.pending_burn.id);
+
+        emit!(TreasuryBurnCancelled {
+            id: self.pending_burn.id,
+            officer: self.officer.key(),
+        });
+
+        Ok(())
+    }
+}
+
+impl Policy for CancelTreasuryBurn<'_> {
+    const ROLE: Role = Role::Officer;
+    const PAUSABLE: bool = false;
+
+    fn config(&self) -> Option<&Config> {
+        None
+    }
+
+    fn signer(&self) -> Pubkey {
+        self.officer.key()
+    }
+
+    fn treasury(&self) -> Option<&Treasury> {
+        Some(&self.treasury)
+    }
+}
@@ -0,0 +1,55 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    seeds::INHERITANCE_SEED,
+    validation::{Checked, Policy, Role},
+    Config, InheritanceClaimStopped, InheritancePlan,
+};
+
+#[derive(Accounts)]
+pub struct CheckIn<'info> {
+    /// The holder, showing they still have their key
+    pub owner: Signer<'info>,
+
+    /// The holder's inheritance plan
+    #[account(
+        mut,
+        seeds = [INHERITANCE_SEED, owner.key().as_ref()],
+        bump = inheritance_plan.bump
+    )]
+    pub inheritance_plan: Account<'info, InheritancePlan>,
+}
+
+impl CheckIn<'_> {
+    pub fn check_in(&mut self, _: Checked<Self>) -> Result<()> {
+        let inheritance_plan = &mut self.inheritance_plan;
+        inheritance_plan.last_check_in_epoch = Clock::get()?.epoch;
+
+        msg!("{} checked in", self.owner.key());
+
+        if inheritance_plan.claim_in_progress() {
+            inheritance_plan.claim_started_at = 0;
+
+            emit!(InheritanceClaimStopped {
+                owner: self.owner.key(),
+            });
+        }
+
+        Ok(())
+    }
+}
+
+/// Holders check in on their own plan; the plan's seeds bind it to the
+/// signer
+impl Policy for CheckIn<'_> {
+    const ROLE: Role = Role::Anyone;
+    const PAUSABLE: bool = false;
+
+    fn config(&self) -> Option<&Config> {
+        None
+    }
+
+    fn signer(&self) -> Pubkey {
+        self.owner.key()
+    }
+}
@@ -0,0 +1,124 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token::{Mint, Token, TokenAccount, transfer, Transfer},
+};
+
+use crate::{
+    seeds::{AIRDROP_SEED, CONFIG_SEED, MINT_SEED},
+    validation::{self, Checked, Policy, Role},
+    Airdrop, AirdropClaimed, Config, StablecoinError,
+};
+
+#[derive(Accounts)]
+pub struct ClaimAirdrop<'info> {
+    /// One of the airdrop's recipients, who pays for their token account if
+    /// they have none
+    #[account(
+        mut,
+        constraint = validation::is_airdrop_recipient(&recipient.key(), &airdrop) @ StablecoinError::NotAirdropRecipient
+    )]
+    pub recipient: Signer<'info>,
+
+    /// The config account
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump
+    )]
+    pub config: Box<Account<'info, Config>>,
+
+    /// The stablecoin mint
+    #[account(
+        seeds = [MINT_SEED],
+        bump = config.mint_bump
+    )]
+    pub mint: Box<Account<'info, Mint>>,
+
+    /// The airdrop claimed from
+    #[account(
+        mut,
+        seeds = [AIRDROP_SEED, airdrop.funder.as_ref(), airdrop.id.to_le_bytes().as_ref()],
+        bump = airdrop.bump
+    )]
+    pub airdrop: Account<'info, Airdrop>,
+
+    /// Airdrop custody
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = airdrop,
+    )]
+    pub custody: Box<Account<'info, TokenAccount>>,
+
+    /// The recipient's token account
+    #[account(
+        init_if_needed,
+        payer = recipient,
+        associated_token::mint = mint,
+        associated_token::authority = recipient,
+    )]
+    pub destination: Box<Account<'info, TokenAccount>>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+impl ClaimAirdrop<'_> {
+    pub fn claim_airdrop(&mut self, _: Checked<Self>) -> Result<()> {
+        let airdrop = &self.airdrop;
+        require!(Clock::get()?.unix_timestamp < airdrop.closes_at, StablecoinError::AirdropClosed);
+        let recipient = self.recipient.key();
+        let index = airdrop.recipient_index(&recipient).ok_or(StablecoinError::NotAirdropRecipient)?;
+        let amount = airdrop.unclaimed[index];
+        require!(amount > 0, StablecoinError::AirdropClaimed);
+
+        // Create the signer seeds for the custody owner PDA
+        let id = airdrop.id.to_le_bytes();
+        let signer_seeds: &[&[&[u8]]] = &[&[AIRDROP_SEED, airdrop.funder.as_ref(), &id, &[airdrop.bump]]];
+
+        transfer(
+            CpiContext::new_with_signer(
+                self.token_program.key(),
+                Transfer {
+                    from: self.custody.to_account_info(),
+                    to: self.destination.to_account_info(),
+                    authority: self.airdrop.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            amount,
+        )?;
+
+        let airdrop = &mut self.airdrop;
+        airdrop.unclaimed[index] = 0;
+
+        msg!("{} claimed {} tokens from airdrop {} of {}", recipient, amount, airdrop.id, airdrop.funder);
+
+        emit!(AirdropClaimed {
+            funder: airdrop.funder,
+            id: airdrop.id,
+            recipient,
+            amount,
+        });
+
+        Ok(())
+    }
+}
+
+impl Policy for ClaimAirdrop<'_> {
+    const ROLE: Role = Role::AirdropRecipient;
+    const PAUSABLE: bool = false;
+
+    fn config(&self) -> Option<&Config> {
+        Some(&self.config)
+    }
+
+    fn signer(&self) -> Pubkey {
+        self.recipient.key()
+    }
+
+    fn airdrop(&self) -> Option<&Airdrop> {
+        Some(&self.airdrop)
+    }
+}
@@ -0,0 +1,144 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token::{Mint, Token, TokenAccount, transfer, Transfer},
+};
+
+use crate::{
+    seeds::{CONFIG_SEED, DELEGATE_SEED, INHERITANCE_SEED, MINT_SEED},
+    validation::{self, Checked, Policy, Role},
+    Config, InheritanceClaimed, InheritancePlan, StablecoinError,
+};
+
+#[derive(Accounts)]
+pub struct ClaimInheritance<'info> {
+    /// The holder's beneficiary, who pays for their token account if they
+    /// have none
+    #[account(
+        mut,
+        constraint = validation::is_beneficiary(&beneficiary.key(), &inheritance_plan) @ StablecoinError::NotBeneficiary
+    )]
+    pub beneficiary: Signer<'info>,
+
+    /// The config account
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump
+    )]
+    pub config: Box<Account<'info, Config>>,
+
+    /// The stablecoin mint
+    #[account(
+        seeds = [MINT_SEED],
+        bump = config.mint_bump
+    )]
+    pub mint: Box<Account<'info, Mint>>,
+
+    /// The plan of the holder whose balance is claimed
+    #[account(
+        mut,
+        seeds = [INHERITANCE_SEED, inheritance_plan.owner.as_ref()],
+        bump = inheritance_plan.bump
+    )]
+    pub inheritance_plan: Account<'info, InheritancePlan>,
+
+    /// CHECK: The holder, as the plan records
+    #[account(address = inheritance_plan.owner)]
+    pub owner: UncheckedAccount<'info>,
+
+    /// The holder's token account
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = owner,
+    )]
+    pub token_account: Box<Account<'info, TokenAccount>>,
+
+    /// The beneficiary's token account
+    #[account(
+        init_if_needed,
+        payer = beneficiary,
+        associated_token::mint = mint,
+        associated_token::authority = beneficiary,
+    )]
+    pub destination: Box<Account<'info, TokenAccount>>,
+
+    /// CHECK: The program's transfer delegate, a PDA without data
+    #[account(
+        seeds = [DELEGATE_SEED],
+        bump = config.delegate_bump
+    )]
+    pub delegate: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+impl ClaimInheritance<'_> {
+    pub fn claim_inheritance(&mut self, _: Checked<Self>) -> Result<()> {
+        let inheritance_plan = &self.inheritance_plan;
+        require!(inheritance_plan.claim_in_progress(), StablecoinError::NoInheritanceClaim);
+        let claimable_at = inheritance_plan.claim_started_at
+            .checked_add(inheritance_plan.notice_period)
+            .ok_or(StablecoinError::Overflow)?;
+        require!(
+            Clock::get()?.unix_timestamp >= claimable_at,
+            StablecoinError::InheritanceNoticeActive
+        );
+        require!(
+            validation::delegates_balance(&self.token_account, &self.delegate.key()),
+            StablecoinError::DelegateNotApproved
+        );
+
+        // Create the signer seeds for the delegate PDA
+        let signer_seeds: &[&[&[u8]]] = &[&[DELEGATE_SEED, &[self.config.delegate_bump]]];
+
+        let amount = self.token_account.amount;
+        transfer(
+            CpiContext::new_with_signer(
+                self.token_program.key(),
+                Transfer {
+                    from: self.token_account.to_account_info(),
+                    to: self.destination.to_account_info(),
+                    authority: self.delegate.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            amount,
+        )?;
+
+        // The plan stays, so tokens that arrive later can be claimed the
+        // same way
+        let owner = inheritance_plan.owner;
+        let beneficiary = inheritance_plan.beneficiary;
+        self.inheritance_plan.claim_started_at = 0;
+
+        msg!("Inherited {} tokens from {} by {}", amount, owner, beneficiary);
+
+        emit!(InheritanceClaimed {
+            owner,
+            beneficiary,
+            amount,
+        });
+
+        Ok(())
+    }
+}
+
+impl Policy for ClaimInheritance<'_> {
+    const ROLE: Role = Role::Beneficiary;
+    const PAUSABLE: bool = false;
+
+    fn config(&self) -> Option<&Config> {
+        Some(&self.config)
+    }
+
+    fn signer(&self) -> Pubkey {
+        self.beneficiary.key()
+    }
+
+    fn inheritance_plan(&self) -> Option<&InheritancePlan> {
+        Some(&self.inheritance_plan)
+    }
+}
@@ -0,0 +1,139 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token::{Mint, Token, TokenAccount, close_account, transfer, CloseAccount, Transfer},
+};
+
+use crate::{
+    seeds::{AIRDROP_SEED, CONFIG_SEED, MINT_SEED, TREASURY_SEED},
+    validation::{Checked, Policy, Role},
+    Airdrop, AirdropClawedBack, Config, StablecoinError, Treasury,
+};
+
+#[derive(Accounts)]
+pub struct ClawbackAirdrop<'info> {
+    /// Whoever claws the airdrop back, paying for treasury custody if there
+    /// is none yet
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// The config account
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump
+    )]
+    pub config: Box<Account<'info, Config>>,
+
+    /// The stablecoin mint
+    #[account(
+        seeds = [MINT_SEED],
+        bump = config.mint_bump
+    )]
+    pub mint: Box<Account<'info, Mint>>,
+
+    /// The closed airdrop, itself closed once clawed back
+    #[account(
+        mut,
+        close = funder,
+        has_one = funder,
+        seeds = [AIRDROP_SEED, funder.key().as_ref(), airdrop.id.to_le_bytes().as_ref()],
+        bump = airdrop.bump
+    )]
+    pub airdrop: Account<'info, Airdrop>,
+
+    /// CHECK: The funder recorded in the airdrop, who gets the rent back
+    #[account(mut)]
+    pub funder: UncheckedAccount<'info>,
+
+    /// Airdrop custody, closed once emptied
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = airdrop,
+    )]
+    pub custody: Box<Account<'info, TokenAccount>>,
+
+    /// The treasury, which owns treasury custody
+    #[account(
+        seeds = [TREASURY_SEED],
+        bump = treasury.bump
+    )]
+    pub treasury: Box<Account<'info, Treasury>>,
+
+    /// Treasury custody, which receives what was not claimed
+    #[account(
+        init_if_needed,
+        payer = payer,
+        associated_token::mint = mint,
+        associated_token::authority = treasury,
+    )]
+    pub treasury_custody: Box<Account<'info, TokenAccount>>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+impl ClawbackAirdrop<'_> {
+    /// Move custody's whole balance to treasury custody and close it; the
+    /// airdrop is closed by its `close` constraint once this returns
+    pub fn clawback_airdrop(&mut self, _: Checked<Self>) -> Result<()> {
+        let airdrop = &self.airdrop;
+        require!(Clock::get()?.unix_timestamp >= airdrop.closes_at, StablecoinError::AirdropOpen);
+
+        // Create the signer seeds for the custody owner PDA
+        let id = airdrop.id.to_le_bytes();
+        let signer_seeds: &[&[&[u8]]] = &[&[AIRDROP_SEED, airdrop.funder.as_ref(), &id, &[airdrop.bump]]];
+
+        // Anything sent to custody besides the funding goes too
+        let amount = self.custody.amount;
+        if amount > 0 {
+            transfer(
+                CpiContext::new_with_signer(
+                    self.token_program.key(),
+                    Transfer {
+                        from: self.custody.to_account_info(),
+                        to: self.treasury_custody.to_account_info(),
+                        authority: self.airdrop.to_account_info(),
+                    },
+                    signer_seeds,
+                ),
+                amount,
+            )?;
+        }
+        close_account(CpiContext::new_with_signer(
+            self.token_program.key(),
+            CloseAccount {
+                account: self.custody.to_account_info(),
+                destination: self.funder.to_account_info(),
+                authority: self.airdrop.to_account_info(),
+            },
+            signer_seeds,
+        ))?;
+
+        msg!("Clawed back {} tokens from airdrop {} of {}", amount, airdrop.id, airdrop.funder);
+
+        emit!(AirdropClawedBack {
+            funder: airdrop.funder,
+            id: airdrop.id,
+            amount,
+        });
+
+        Ok(())
+    }
+}
+
+/// The clawback only moves an officer's airdrop to treasury custody, so
+/// anyone can crank it once the airdrop closes
+impl Policy for ClawbackAirdrop<'_> {
+    const ROLE: Role = Role::Anyone;
+    const PAUSABLE: bool = false;
+
+    fn config(&self) -> Option<&Config> {
+        Some(&self.config)
+    }
+
+    fn signer(&self) -> Pubkey {
+        self.payer.key()
+    }
+}
@@ -0,0 +1,100 @@
+use anchor_lang::{prelude::*, signature_verification::verify_ed25519_ix};
+use solana_instructions_sysvar::{load_current_index_checked, load_instruction_at_checked};
+
+use crate::{
+    channel_state_message,
+    seeds::CHANNEL_SEED,
+    validation::{self, Checked, Policy, Role},
+    ChannelClosing, Config, PaymentChannel, StablecoinError,
+};
+
+#[derive(Accounts)]
+pub struct CloseChannel<'info> {
+    /// The channel's sender or recipient
+    #[account(
+        constraint = validation::is_channel_party(&party.key(), &payment_channel) @ StablecoinError::NotChannelParty
+    )]
+    pub party: Signer<'info>,
+
+    /// The channel to close
+    #[account(
+        mut,
+        seeds = [CHANNEL_SEED, payment_channel.sender.as_ref(), payment_channel.recipient.as_ref()],
+        bump = payment_channel.bump
+    )]
+    pub payment_channel: Account<'info, PaymentChannel>,
+
+    /// CHECK: The instructions sysvar, to find the sender's signature
+    #[account(address = solana_instructions_sysvar::ID)]
+    pub instructions: UncheckedAccount<'info>,
+}
+
+impl CloseChannel<'_> {
+    /// Submit the balance update paying `amount`, starting the dispute
+    /// period if this is the first
+    ///
+    /// Raising the amount takes the sender's `signature` over
+    /// [`channel_state_message`], verified by an Ed25519 program
+    /// instruction right before this one; `signature` is ignored otherwise.
+    pub fn close_channel(&mut self, _: Checked<Self>, amount: u64, signature: [u8; 64]) -> Result<()> {
+        let payment_channel = &self.payment_channel;
+        let now = Clock::get()?.unix_timestamp;
+        require!(
+            !payment_channel.closing() || now < payment_channel.closes_at,
+            StablecoinError::ChannelDisputeOver
+        );
+        require!(
+            amount >= payment_channel.amount && amount <= payment_channel.deposit,
+            StablecoinError::InvalidChannelState
+        );
+        if amount > payment_channel.amount {
+            let message = channel_state_message(&payment_channel.key(), payment_channel.nonce, amount);
+            let index = load_current_index_checked(&self.instructions)?;
+            let verify_ix = index
+                .checked_sub(1)
+                .ok_or(StablecoinError::InvalidChannelSignature)
+                .and_then(|index| {
+                    load_instruction_at_checked(index as usize, &self.instructions)
+                        .map_err(|_| StablecoinError::InvalidChannelSignature)
+                })?;
+            verify_ed25519_ix(&verify_ix, &payment_channel.sender.to_bytes(), &message, &signature)
+                .map_err(|_| StablecoinError::InvalidChannelSignature)?;
+        }
+
+        let payment_channel = &mut self.payment_channel;
+        payment_channel.amount = amount;
+        if !payment_channel.closing() {
+            payment_channel.closes_at = now
+                .checked_add(payment_channel.dispute_period)
+                .ok_or(StablecoinError::Overflow)?;
+        }
+
+        msg!("Payment channel from {} to {} closing with {} paid", payment_channel.sender, payment_channel.recipient, amount);
+
+        emit!(ChannelClosing {
+            sender: payment_channel.sender,
+            recipient: payment_channel.recipient,
+            amount,
+            closes_at: payment_channel.closes_at,
+        });
+
+        Ok(())
+    }
+}
+
+impl Policy for CloseChannel<'_> {
+    const ROLE: Role = Role::ChannelParty;
+    const PAUSABLE: bool = false;
+
+    fn config(&self) -> Option<&Config> {
+        None
+    }
+
+    fn signer(&self) -> Pubkey {
+        self.party.key()
+    }
+
+    fn payment_channel(&self) -> Option<&PaymentChannel> {
+        Some(&self.payment_channel)
+    }
+}
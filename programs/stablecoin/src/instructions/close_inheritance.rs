@@ -0,0 +1,102 @@
+use anchor_lang::{prelude::*, solana_program::program_option::COption};
+use anchor_spl::token::{Mint, Token, TokenAccount, revoke, Revoke};
+
+use crate::{
+    seeds::{CONFIG_SEED, DELEGATE_SEED, INHERITANCE_SEED, MINT_SEED, RECOVERY_SEED},
+    validation::{Checked, Policy, Role},
+    Config, InheritancePlan,
+};
+
+#[derive(Accounts)]
+pub struct CloseInheritance<'info> {
+    /// The holder opting out, who gets the rent back
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    /// The config account
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump
+    )]
+    pub config: Box<Account<'info, Config>>,
+
+    /// The stablecoin mint
+    #[account(
+        seeds = [MINT_SEED],
+        bump = config.mint_bump
+    )]
+    pub mint: Box<Account<'info, Mint>>,
+
+    /// The holder's inheritance plan to close
+    #[account(
+        mut,
+        close = owner,
+        seeds = [INHERITANCE_SEED, owner.key().as_ref()],
+        bump = inheritance_plan.bump
+    )]
+    pub inheritance_plan: Account<'info, InheritancePlan>,
+
+    /// The holder's token account, whose delegate configuring the plan
+    /// approved
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = owner,
+    )]
+    pub token_account: Box<Account<'info, TokenAccount>>,
+
+    /// CHECK: The program's transfer delegate, a PDA without data
+    #[account(
+        seeds = [DELEGATE_SEED],
+        bump = config.delegate_bump
+    )]
+    pub delegate: UncheckedAccount<'info>,
+
+    /// CHECK: The holder's recovery settings, read only for whether they
+    /// exist
+    #[account(
+        seeds = [RECOVERY_SEED, owner.key().as_ref()],
+        bump
+    )]
+    pub recovery_config: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+impl CloseInheritance<'_> {
+    /// Revoke the program's delegate unless recovery still needs it; the
+    /// plan is closed by its `close` constraint once this returns, along
+    /// with any claim in progress
+    pub fn close_inheritance(&mut self, _: Checked<Self>) -> Result<()> {
+        // The same rule as closing recovery, from the other side
+        let delegated = self.token_account.delegate == COption::Some(self.delegate.key());
+        if delegated && self.recovery_config.data_is_empty() {
+            revoke(CpiContext::new(
+                self.token_program.key(),
+                Revoke {
+                    source: self.token_account.to_account_info(),
+                    authority: self.owner.to_account_info(),
+                },
+            ))?;
+        }
+
+        msg!("Inheritance for {} closed", self.owner.key());
+
+        Ok(())
+    }
+}
+
+/// Holders opt out of their own plan; the plan's seeds bind it to the
+/// signer
+impl Policy for CloseInheritance<'_> {
+    const ROLE: Role = Role::Anyone;
+    const PAUSABLE: bool = false;
+
+    fn config(&self) -> Option<&Config> {
+        Some(&self.config)
+    }
+
+    fn signer(&self) -> Pubkey {
+        self.owner.key()
+    }
+}
@@ -0,0 +1,104 @@
+use anchor_lang::{prelude::*, solana_program::program_option::COption};
+use anchor_spl::token::{Mint, Token, TokenAccount, revoke, Revoke};
+
+use crate::{
+    seeds::{CONFIG_SEED, DELEGATE_SEED, INHERITANCE_SEED, MINT_SEED, RECOVERY_SEED},
+    validation::{Checked, Policy, Role},
+    Config, RecoveryConfig,
+};
+
+#[derive(Accounts)]
+pub struct CloseRecovery<'info> {
+    /// The holder opting out, who gets the rent back
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    /// The config account
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump
+    )]
+    pub config: Box<Account<'info, Config>>,
+
+    /// The stablecoin mint
+    #[account(
+        seeds = [MINT_SEED],
+        bump = config.mint_bump
+    )]
+    pub mint: Box<Account<'info, Mint>>,
+
+    /// The holder's recovery settings to close
+    #[account(
+        mut,
+        close = owner,
+        seeds = [RECOVERY_SEED, owner.key().as_ref()],
+        bump = recovery_config.bump
+    )]
+    pub recovery_config: Account<'info, RecoveryConfig>,
+
+    /// The holder's token account, whose delegate configuring recovery
+    /// approved
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = owner,
+    )]
+    pub token_account: Box<Account<'info, TokenAccount>>,
+
+    /// CHECK: The program's transfer delegate, a PDA without data
+    #[account(
+        seeds = [DELEGATE_SEED],
+        bump = config.delegate_bump
+    )]
+    pub delegate: UncheckedAccount<'info>,
+
+    /// CHECK: The holder's inheritance plan, read only for whether it
+    /// exists
+    #[account(
+        seeds = [INHERITANCE_SEED, owner.key().as_ref()],
+        bump
+    )]
+    pub inheritance_plan: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+impl CloseRecovery<'_> {
+    /// Revoke the program's delegate unless an inheritance plan still needs
+    /// it; the settings are closed by their `close` constraint once this
+    /// returns, along with any recovery in progress
+    pub fn close_recovery(&mut self, _: Checked<Self>) -> Result<()> {
+        // The delegate is shared with inheritance, so it stays while the
+        // holder has a plan, and a delegate the holder approved themselves
+        // is not the program's to revoke
+        let delegated = self.token_account.delegate == COption::Some(self.delegate.key());
+        if delegated && self.inheritance_plan.data_is_empty() {
+            revoke(CpiContext::new(
+                self.token_program.key(),
+                Revoke {
+                    source: self.token_account.to_account_info(),
+                    authority: self.owner.to_account_info(),
+                },
+            ))?;
+        }
+
+        msg!("Recovery for {} closed", self.owner.key());
+
+        Ok(())
+    }
+}
+
+/// Holders opt out of their own recovery; the config's seeds bind it to the
+/// signer
+impl Policy for CloseRecovery<'_> {
+    const ROLE: Role = Role::Anyone;
+    const PAUSABLE: bool = false;
+
+    fn config(&self) -> Option<&Config> {
+        Some(&self.config)
+    }
+
+    fn signer(&self) -> Pubkey {
+        self.owner.key()
+    }
+}
@@ -0,0 +1,129 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Mint, Token, TokenAccount, approve, Approve};
+
+use crate::{
+    seeds::{CONFIG_SEED, DELEGATE_SEED, INHERITANCE_SEED, MINT_SEED},
+    sizes,
+    validation::{Checked, Policy, Role},
+    Config, InheritanceConfigured, InheritancePlan, StablecoinError, MIN_INHERITANCE_NOTICE_PERIOD,
+};
+
+#[derive(Accounts)]
+pub struct ConfigureInheritance<'info> {
+    /// The holder opting in, or changing their plan
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    /// The config account
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump
+    )]
+    pub config: Box<Account<'info, Config>>,
+
+    /// The stablecoin mint
+    #[account(
+        seeds = [MINT_SEED],
+        bump = config.mint_bump
+    )]
+    pub mint: Box<Account<'info, Mint>>,
+
+    /// The holder's token account, whose balance a claim moves
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = owner,
+    )]
+    pub token_account: Box<Account<'info, TokenAccount>>,
+
+    /// CHECK: The program's transfer delegate, a PDA without data
+    #[account(
+        seeds = [DELEGATE_SEED],
+        bump = config.delegate_bump
+    )]
+    pub delegate: UncheckedAccount<'info>,
+
+    /// The holder's inheritance plan, created on first use
+    #[account(
+        init_if_needed,
+        payer = owner,
+        space = sizes::INHERITANCE_PLAN,
+        seeds = [INHERITANCE_SEED, owner.key().as_ref()],
+        bump
+    )]
+    pub inheritance_plan: Account<'info, InheritancePlan>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+impl ConfigureInheritance<'_> {
+    pub fn configure_inheritance(
+        &mut self,
+        _: Checked<Self>,
+        beneficiary: Pubkey,
+        inactivity_epochs: u64,
+        notice_period: i64,
+        bumps: &ConfigureInheritanceBumps,
+    ) -> Result<()> {
+        let owner = self.owner.key();
+        require!(
+            beneficiary != Pubkey::default()
+                && beneficiary != owner
+                && inactivity_epochs >= 1
+                && notice_period >= MIN_INHERITANCE_NOTICE_PERIOD,
+            StablecoinError::InvalidInheritancePlan
+        );
+
+        // A new plan replaces any claim on the old one, and configuring it
+        // counts as a check-in
+        let inheritance_plan = &mut self.inheritance_plan;
+        inheritance_plan.owner = owner;
+        inheritance_plan.beneficiary = beneficiary;
+        inheritance_plan.inactivity_epochs = inactivity_epochs;
+        inheritance_plan.notice_period = notice_period;
+        inheritance_plan.last_check_in_epoch = Clock::get()?.epoch;
+        inheritance_plan.claim_started_at = 0;
+        inheritance_plan.bump = bumps.inheritance_plan;
+
+        // Let the program move the balance once a claim completes; the
+        // delegate is shared with recovery, and closing the plan revokes it
+        // unless recovery settings still need it
+        approve(
+            CpiContext::new(
+                self.token_program.key(),
+                Approve {
+                    to: self.token_account.to_account_info(),
+                    delegate: self.delegate.to_account_info(),
+                    authority: self.owner.to_account_info(),
+                },
+            ),
+            u64::MAX,
+        )?;
+
+        msg!("Configured inheritance for {} to {} after {} epochs without a check-in", owner, beneficiary, inactivity_epochs);
+
+        emit!(InheritanceConfigured {
+            owner,
+            beneficiary,
+            inactivity_epochs,
+            notice_period,
+        });
+
+        Ok(())
+    }
+}
+
+/// Holders configure their own plan; the plan's seeds bind it to the signer
+impl Policy for ConfigureInheritance<'_> {
+    const ROLE: Role = Role::Anyone;
+    const PAUSABLE: bool = false;
+
+    fn config(&self) -> Option<&Config> {
+        Some(&self.config)
+    }
+
+    fn signer(&self) -> Pubkey {
+        self.owner.key()
+    }
+}
@@ -0,0 +1,105 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    seeds::{CONFIG_SEED, MINTER_SEED, MINTER_SET_FREEZE_SEED, MINTER_TOMBSTONE_SEED},
+    sizes,
+    validation::{self, Checked, Policy, Role},
+    Config, MinterConfig, MinterConfigured, StablecoinError,
+};
+
+#[derive(Accounts)]
+pub struct ConfigureMinter<'info> {
+    /// Only the admin can configure minters
+    #[account(
+        mut,
+        constraint = validation::is_admin(&admin.key(), &config) @ StablecoinError::Unauthorized
+    )]
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump
+    )]
+    pub config: Box<Account<'info, Config>>,
+
+    /// The minter being configured
+    /// CHECK: This can be any account that will be authorized to mint
+    pub minter: UncheckedAccount<'info>,
+
+    /// The minter's configuration account
+    #[account(
+        init_if_needed,
+        payer = admin,
+        space = sizes::MINTER_CONFIG,
+        seeds = [MINTER_SEED, minter.key().as_ref()],
+        bump
+    )]
+    pub minter_config: Account<'info, MinterConfig>,
+
+    /// CHECK: The minter set freeze marker, which must not exist
+    #[account(
+        seeds = [MINTER_SET_FREEZE_SEED],
+        bump = config.minter_set_freeze_bump,
+        constraint = minter_set_freeze.data_is_empty() @ StablecoinError::MinterSetFrozen
+    )]
+    pub minter_set_freeze: UncheckedAccount<'info>,
+
+    /// CHECK: The minter's tombstone, which must not exist; checked by the
+    /// handler when the config is created
+    pub minter_tombstone: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl ConfigureMinter<'_> {
+    pub fn configure_minter(
+        &mut self,
+        _: Checked<Self>,
+        allowance: u64,
+        bumps: &ConfigureMinterBumps,
+    ) -> Result<()> {
+        let minter_config = &mut self.minter_config;
+
+        // If not initialized, set the minter address
+        if !minter_config.is_initialized {
+            // A config and its tombstone never exist together, so only a
+            // new config needs the check
+            let (tombstone, _) = Pubkey::find_program_address(
+                &[MINTER_TOMBSTONE_SEED, self.minter.key().as_ref()],
+                &crate::ID,
+            );
+            require_keys_eq!(self.minter_tombstone.key(), tombstone, ErrorCode::ConstraintSeeds);
+            require!(self.minter_tombstone.data_is_empty(), StablecoinError::MinterRetired);
+
+            minter_config.minter = self.minter.key();
+            minter_config.amount_minted = 0;
+            minter_config.is_initialized = true;
+            minter_config.bump = bumps.minter_config;
+            minter_config.derive_bumps();
+        }
+
+        minter_config.allowance = allowance;
+
+        msg!("Configured minter {} with allowance {}", self.minter.key(), allowance);
+
+        emit!(MinterConfigured {
+            minter: self.minter.key(),
+            allowance,
+        });
+
+        Ok(())
+    }
+}
+
+impl Policy for ConfigureMinter<'_> {
+    const ROLE: Role = Role::Admin;
+    const PAUSABLE: bool = false;
+
+    fn config(&self) -> Option<&Config> {
+        Some(&self.config)
+    }
+
+    fn signer(&self) -> Pubkey {
+        self.admin.key()
+    }
+}
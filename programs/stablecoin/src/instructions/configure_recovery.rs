@@ -0,0 +1,135 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Mint, Token, TokenAccount, approve, Approve};
+
+use crate::{
+    seeds::{CONFIG_SEED, DELEGATE_SEED, MINT_SEED, RECOVERY_SEED},
+    sizes,
+    validation::{Checked, Policy, Role},
+    Config, RecoveryConfig, RecoveryConfigured, StablecoinError, MAX_GUARDIANS,
+    MIN_RECOVERY_CHALLENGE_PERIOD,
+};
+
+#[derive(Accounts)]
+pub struct ConfigureRecovery<'info> {
+    /// The holder opting in, or changing their settings
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    /// The config account
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump
+    )]
+    pub config: Box<Account<'info, Config>>,
+
+    /// The stablecoin mint
+    #[account(
+        seeds = [MINT_SEED],
+        bump = config.mint_bump
+    )]
+    pub mint: Box<Account<'info, Mint>>,
+
+    /// The holder's token account, whose balance a recovery moves
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = owner,
+    )]
+    pub token_account: Box<Account<'info, TokenAccount>>,
+
+    /// CHECK: The program's transfer delegate, a PDA without data
+    #[account(
+        seeds = [DELEGATE_SEED],
+        bump = config.delegate_bump
+    )]
+    pub delegate: UncheckedAccount<'info>,
+
+    /// The holder's recovery settings, created on first use
+    #[account(
+        init_if_needed,
+        payer = owner,
+        space = sizes::RECOVERY_CONFIG,
+        seeds = [RECOVERY_SEED, owner.key().as_ref()],
+        bump
+    )]
+    pub recovery_config: Account<'info, RecoveryConfig>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+impl ConfigureRecovery<'_> {
+    pub fn configure_recovery(
+        &mut self,
+        _: Checked<Self>,
+        guardians: Vec<Pubkey>,
+        quorum: u8,
+        challenge_period: i64,
+        bumps: &ConfigureRecoveryBumps,
+    ) -> Result<()> {
+        let owner = self.owner.key();
+        let distinct = guardians
+            .iter()
+            .enumerate()
+            .all(|(i, guardian)| !guardians[..i].contains(guardian));
+        require!(
+            (1..=MAX_GUARDIANS as usize).contains(&guardians.len())
+                && distinct
+                && !guardians.contains(&Pubkey::default())
+                && !guardians.contains(&owner)
+                && quorum >= 1
+                && quorum as usize <= guardians.len()
+                && challenge_period >= MIN_RECOVERY_CHALLENGE_PERIOD,
+            StablecoinError::InvalidRecoveryConfig
+        );
+
+        // New settings replace any recovery the old guardians started
+        let recovery_config = &mut self.recovery_config;
+        recovery_config.owner = owner;
+        recovery_config.guardians = guardians;
+        recovery_config.quorum = quorum;
+        recovery_config.challenge_period = challenge_period;
+        recovery_config.clear();
+        recovery_config.bump = bumps.recovery_config;
+
+        // Let the program move the balance once a recovery is approved;
+        // the delegate is shared with inheritance, and closing the settings
+        // revokes it unless an inheritance plan still needs it
+        approve(
+            CpiContext::new(
+                self.token_program.key(),
+                Approve {
+                    to: self.token_account.to_account_info(),
+                    delegate: self.delegate.to_account_info(),
+                    authority: self.owner.to_account_info(),
+                },
+            ),
+            u64::MAX,
+        )?;
+
+        msg!("Configured recovery for {} with quorum {} of {}", owner, quorum, recovery_config.guardians.len());
+
+        emit!(RecoveryConfigured {
+            owner,
+            guardians: recovery_config.guardians.clone(),
+            quorum,
+            challenge_period,
+        });
+
+        Ok(())
+    }
+}
+
+/// Holders configure their own recovery; the config's seeds bind it to the signer
+impl Policy for ConfigureRecovery<'_> {
+    const ROLE: Role = Role::Anyone;
+    const PAUSABLE: bool = false;
+
+    fn config(&self) -> Option<&Config> {
+        Some(&self.config)
+    }
+
+    fn signer(&self) -> Pubkey {
+        self.owner.key()
+    }
+}
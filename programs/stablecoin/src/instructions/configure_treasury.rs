@@ -0,0 +1,80 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    seeds::{CONFIG_SEED, TREASURY_SEED},
+    sizes,
+    validation::{self, Checked, Policy, Role},
+    Config, StablecoinError, Treasury, TreasuryConfigured,
+};
+
+#[derive(Accounts)]
+pub struct ConfigureTreasury<'info> {
+    /// Only the admin can appoint treasury officers
+    #[account(
+        mut,
+        constraint = validation::is_admin(&admin.key(), &config) @ StablecoinError::Unauthorized
+    )]
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump
+    )]
+    pub config: Box<Account<'info, Config>>,
+
+    /// The treasury account, created on first use
+    #[account(
+        init_if_needed,
+        payer = admin,
+        space = sizes::TREASURY,
+        seeds = [TREASURY_SEED],
+        bump
+    )]
+    pub treasury: Account<'info, Treasury>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl ConfigureTreasury<'_> {
+    pub fn configure_treasury(
+        &mut self,
+        _: Checked<Self>,
+        officers: [Pubkey; 2],
+        threshold: u64,
+        bumps: &ConfigureTreasuryBumps,
+    ) -> Result<()> {
+        // Two approvals must come from two people
+        require!(
+            officers[0] != officers[1] && !officers.contains(&Pubkey::default()),
+            StablecoinError::InvalidOfficers
+        );
+
+        let treasury = &mut self.treasury;
+        treasury.officers = officers;
+        treasury.threshold = threshold;
+        treasury.bump = bumps.treasury;
+
+        msg!("Configured treasury officers {} and {} with threshold {}", officers[0], officers[1], threshold);
+
+        emit!(TreasuryConfigured {
+            admin: self.admin.key(),
+            officers,
+            threshold,
+        });
+
+        Ok(())
+    }
+}
+
+impl Policy for ConfigureTreasury<'_> {
+    const ROLE: Role = Role::Admin;
+    const PAUSABLE: bool = false;
+
+    fn config(&self) -> Option<&Config> {
+        Some(&self.config)
+    }
+
+    fn signer(&self) -> Pubkey {
+        self.admin.key()
+    }
+}
@@ -0,0 +1,150 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token::{Mint, Token, TokenAccount, transfer, Transfer},
+};
+
+use crate::{
+    seeds::{CONFIG_SEED, DELEGATE_SEED, MINT_SEED, RECOVERY_SEED},
+    validation::{self, Checked, Policy, Role},
+    AccountRecovered, Config, RecoveryConfig, StablecoinError,
+};
+
+#[derive(Accounts)]
+pub struct ExecuteRecovery<'info> {
+    /// Any guardian can execute an approved recovery, and pays for the new
+    /// owner's token account if it has none
+    #[account(
+        mut,
+        constraint = validation::is_guardian(&guardian.key(), &recovery_config) @ StablecoinError::NotGuardian
+    )]
+    pub guardian: Signer<'info>,
+
+    /// The config account
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump
+    )]
+    pub config: Box<Account<'info, Config>>,
+
+    /// The stablecoin mint
+    #[account(
+        seeds = [MINT_SEED],
+        bump = config.mint_bump
+    )]
+    pub mint: Box<Account<'info, Mint>>,
+
+    /// The settings of the holder being recovered
+    #[account(
+        mut,
+        seeds = [RECOVERY_SEED, recovery_config.owner.as_ref()],
+        bump = recovery_config.bump
+    )]
+    pub recovery_config: Account<'info, RecoveryConfig>,
+
+    /// CHECK: The holder being recovered, as the settings record
+    #[account(address = recovery_config.owner)]
+    pub owner: UncheckedAccount<'info>,
+
+    /// The holder's token account
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = owner,
+    )]
+    pub token_account: Box<Account<'info, TokenAccount>>,
+
+    /// CHECK: The wallet the recovery in progress names
+    #[account(address = recovery_config.new_owner)]
+    pub new_owner: UncheckedAccount<'info>,
+
+    /// The new owner's token account
+    #[account(
+        init_if_needed,
+        payer = guardian,
+        associated_token::mint = mint,
+        associated_token::authority = new_owner,
+    )]
+    pub destination: Box<Account<'info, TokenAccount>>,
+
+    /// CHECK: The program's transfer delegate, a PDA without data
+    #[account(
+        seeds = [DELEGATE_SEED],
+        bump = config.delegate_bump
+    )]
+    pub delegate: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+impl ExecuteRecovery<'_> {
+    pub fn execute_recovery(&mut self, _: Checked<Self>) -> Result<()> {
+        let recovery_config = &self.recovery_config;
+        require!(recovery_config.in_progress(), StablecoinError::NoRecoveryInProgress);
+        require!(
+            recovery_config.approvals.count_ones() >= u32::from(recovery_config.quorum),
+            StablecoinError::RecoveryNotApproved
+        );
+        let executable_at = recovery_config.initiated_at
+            .checked_add(recovery_config.challenge_period)
+            .ok_or(StablecoinError::Overflow)?;
+        require!(
+            Clock::get()?.unix_timestamp >= executable_at,
+            StablecoinError::RecoveryChallengeActive
+        );
+        require!(
+            validation::delegates_balance(&self.token_account, &self.delegate.key()),
+            StablecoinError::DelegateNotApproved
+        );
+
+        // Create the signer seeds for the delegate PDA
+        let signer_seeds: &[&[&[u8]]] = &[&[DELEGATE_SEED, &[self.config.delegate_bump]]];
+
+        let amount = self.token_account.amount;
+        transfer(
+            CpiContext::new_with_signer(
+                self.token_program.key(),
+                Transfer {
+                    from: self.token_account.to_account_info(),
+                    to: self.destination.to_account_info(),
+                    authority: self.delegate.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            amount,
+        )?;
+
+        let owner = recovery_config.owner;
+        let new_owner = recovery_config.new_owner;
+        self.recovery_config.clear();
+
+        msg!("Recovered {} tokens from {} to {}", amount, owner, new_owner);
+
+        emit!(AccountRecovered {
+            owner,
+            new_owner,
+            amount,
+        });
+
+        Ok(())
+    }
+}
+
+impl Policy for ExecuteRecovery<'_> {
+    const ROLE: Role = Role::Guardian;
+    const PAUSABLE: bool = false;
+
+    fn config(&self) -> Option<&Config> {
+        Some(&self.config)
+    }
+
+    fn signer(&self) -> Pubkey {
+        self.guardian.key()
+    }
+
+    fn recovery_config(&self) -> Option<&RecoveryConfig> {
+        Some(&self.recovery_config)
+    }
+}
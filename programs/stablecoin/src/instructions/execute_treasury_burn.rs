@@ -0,0 +1,130 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Mint, Token, TokenAccount, burn, Burn};
+
+use crate::{
+    seeds::{CONFIG_SEED, MINT_SEED, PENDING_BURN_SEED, SUPPLY_STATS_SEED, TREASURY_SEED},
+    validation::{self, Checked, Policy, Role},
+    Config, PendingBurn, StablecoinError, SupplyStats, TokensBurned, Treasury,
+};
+
+#[derive(Accounts)]
+pub struct ExecuteTreasuryBurn<'info> {
+    /// Any officer can execute a burn with enough approvals
+    #[account(
+        constraint = validation::is_officer(&officer.key(), &treasury) @ StablecoinError::NotOfficer
+    )]
+    pub officer: Signer<'info>,
+
+    /// The config account
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump
+    )]
+    pub config: Box<Account<'info, Config>>,
+
+    /// The treasury, which owns custody and signs the burn
+    #[account(
+        seeds = [TREASURY_SEED],
+        bump = treasury.bump
+    )]
+    pub treasury: Account<'info, Treasury>,
+
+    /// The approved proposal, closed once it is carried out
+    #[account(
+        mut,
+        close = proposer,
+        has_one = proposer,
+        seeds = [PENDING_BURN_SEED, pending_burn.id.to_le_bytes().as_ref()],
+        bump = pending_burn.bump
+    )]
+    pub pending_burn: Account<'info, PendingBurn>,
+
+    /// CHECK: The proposer recorded in the proposal, who gets its rent back
+    #[account(mut)]
+    pub proposer: UncheckedAccount<'info>,
+
+    /// The stablecoin mint
+    #[account(
+        mut,
+        seeds = [MINT_SEED],
+        bump = config.mint_bump
+    )]
+    pub mint: Box<Account<'info, Mint>>,
+
+    /// The time-weighted supply, updated with the new supply
+    #[account(
+        mut,
+        seeds = [SUPPLY_STATS_SEED],
+        bump = supply_stats.bump
+    )]
+    pub supply_stats: Account<'info, SupplyStats>,
+
+    /// Treasury custody, the token account to burn from
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = treasury,
+    )]
+    pub custody: Box<Account<'info, TokenAccount>>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+impl ExecuteTreasuryBurn<'_> {
+    /// The proposal itself is closed by its `close` constraint once this returns
+    pub fn execute_treasury_burn(&mut self, _: Checked<Self>) -> Result<()> {
+        let treasury = &self.treasury;
+        let pending_burn = &self.pending_burn;
+        require!(
+            pending_burn.approvals(treasury) >= treasury.required_approvals(pending_burn.amount),
+            StablecoinError::InsufficientApprovals
+        );
+
+        // Create the signer seeds for the custody owner PDA
+        let signer_seeds: &[&[&[u8]]] = &[&[TREASURY_SEED, &[treasury.bump]]];
+
+        burn(
+            CpiContext::new_with_signer(
+                self.token_program.key(),
+                Burn {
+                    mint: self.mint.to_account_info(),
+                    from: self.custody.to_account_info(),
+                    authority: self.treasury.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            pending_burn.amount,
+        )?;
+
+        self.mint.reload()?;
+        self.supply_stats.record(self.mint.supply, Clock::get()?.unix_timestamp)?;
+
+        msg!("Executed treasury burn {} of {} tokens", pending_burn.id, pending_burn.amount);
+
+        emit!(TokensBurned {
+            owner: self.treasury.key(),
+            token_account: self.custody.key(),
+            amount: pending_burn.amount,
+        });
+
+        Ok(())
+    }
+}
+
+/// Burns are never paused, from custody or anywhere else
+impl Policy for ExecuteTreasuryBurn<'_> {
+    const ROLE: Role = Role::Officer;
+    const PAUSABLE: bool = false;
+
+    fn config(&self) -> Option<&Config> {
+        Some(&self.config)
+    }
+
+    fn signer(&self) -> Pubkey {
+        self.officer.key()
+    }
+
+    fn treasury(&self) -> Option<&Treasury> {
+        Some(&self.treasury)
+    }
+}
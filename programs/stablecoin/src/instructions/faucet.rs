@@ -0,0 +1,124 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token::{Mint, Token, TokenAccount, mint_to, MintTo},
+};
+
+use crate::{
+    seeds::{CONFIG_SEED, FAUCET_SEED, MINT_SEED, SUPPLY_STATS_SEED},
+    sizes,
+    validation::{Checked, Policy, Role},
+    Config, FaucetClaim, SupplyStats, TokensMinted,
+};
+
+#[derive(Accounts)]
+pub struct Faucet<'info> {
+    /// The wallet claiming tokens, who pays for its claim record and token
+    /// account if it has none
+    #[account(mut)]
+    pub wallet: Signer<'info>,
+
+    /// The config account
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump
+    )]
+    pub config: Box<Account<'info, Config>>,
+
+    /// The stablecoin mint
+    #[account(
+        mut,
+        seeds = [MINT_SEED],
+        bump = config.mint_bump
+    )]
+    pub mint: Box<Account<'info, Mint>>,
+
+    /// The time-weighted supply, updated with the new supply
+    #[account(
+        mut,
+        seeds = [SUPPLY_STATS_SEED],
+        bump = supply_stats.bump
+    )]
+    pub supply_stats: Account<'info, SupplyStats>,
+
+    /// The wallet's claims today, created on its first claim
+    #[account(
+        init_if_needed,
+        payer = wallet,
+        space = sizes::FAUCET_CLAIM,
+        seeds = [FAUCET_SEED, wallet.key().as_ref()],
+        bump
+    )]
+    pub faucet_claim: Account<'info, FaucetClaim>,
+
+    /// The wallet's token account
+    #[account(
+        init_if_needed,
+        payer = wallet,
+        associated_token::mint = mint,
+        associated_token::authority = wallet,
+    )]
+    pub destination: Box<Account<'info, TokenAccount>>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+impl Faucet<'_> {
+    pub fn faucet(&mut self, _: Checked<Self>, amount: u64, bumps: &FaucetBumps) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+
+        let faucet_claim = &mut self.faucet_claim;
+        faucet_claim.wallet = self.wallet.key();
+        faucet_claim.bump = bumps.faucet_claim;
+        let remaining = faucet_claim.claim(amount, now)?;
+
+        // Create the signer seeds for the mint authority PDA
+        let signer_seeds: &[&[&[u8]]] = &[&[CONFIG_SEED, &[self.config.bump]]];
+
+        mint_to(
+            CpiContext::new_with_signer(
+                self.token_program.key(),
+                MintTo {
+                    mint: self.mint.to_account_info(),
+                    to: self.destination.to_account_info(),
+                    authority: self.config.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            amount,
+        )?;
+
+        self.mint.reload()?;
+        self.supply_stats.record(self.mint.supply, now)?;
+
+        msg!("Faucet minted {} tokens to {}", amount, self.destination.key());
+
+        // Reported like any other mint, with the wallet as the minter and
+        // what is left of its daily limit as the allowance
+        emit!(TokensMinted {
+            minter: self.wallet.key(),
+            destination: self.destination.key(),
+            amount,
+            remaining_allowance: remaining,
+        });
+
+        Ok(())
+    }
+}
+
+/// Anyone can claim within the daily limit, which the claim record's seeds
+/// bind to the signer; pausing stops the faucet like any other mint
+impl Policy for Faucet<'_> {
+    const ROLE: Role = Role::Anyone;
+    const PAUSABLE: bool = true;
+
+    fn config(&self) -> Option<&Config> {
+        Some(&self.config)
+    }
+
+    fn signer(&self) -> Pubkey {
+        self.wallet.key()
+    }
+}
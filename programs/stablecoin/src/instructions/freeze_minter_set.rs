@@ -0,0 +1,66 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    seeds::{CONFIG_SEED, MINTER_SET_FREEZE_SEED},
+    sizes,
+    validation::{self, Checked, Policy, Role},
+    Config, MinterSetFreeze, MinterSetFrozen, StablecoinError,
+};
+
+#[derive(Accounts)]
+pub struct FreezeMinterSet<'info> {
+    /// Only the admin can freeze the minter set
+    #[account(
+        mut,
+        constraint = validation::is_admin(&admin.key(), &config) @ StablecoinError::Unauthorized
+    )]
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump
+    )]
+    pub config: Box<Account<'info, Config>>,
+
+    /// The freeze marker, which exists only while the set is frozen
+    #[account(
+        init,
+        payer = admin,
+        space = sizes::MINTER_SET_FREEZE,
+        seeds = [MINTER_SET_FREEZE_SEED],
+        bump
+    )]
+    pub minter_set_freeze: Account<'info, MinterSetFreeze>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl FreezeMinterSet<'_> {
+    pub fn freeze_minter_set(&mut self, _: Checked<Self>, bumps: &FreezeMinterSetBumps) -> Result<()> {
+        let minter_set_freeze = &mut self.minter_set_freeze;
+        minter_set_freeze.frozen_by = self.admin.key();
+        minter_set_freeze.frozen_at = Clock::get()?.unix_timestamp;
+        minter_set_freeze.bump = bumps.minter_set_freeze;
+
+        msg!("Minter set frozen");
+
+        emit!(MinterSetFrozen {
+            admin: self.admin.key(),
+        });
+
+        Ok(())
+    }
+}
+
+impl Policy for FreezeMinterSet<'_> {
+    const ROLE: Role = Role::Admin;
+    const PAUSABLE: bool = false;
+
+    fn config(&self) -> Option<&Config> {
+        Some(&self.config)
+    }
+
+    fn signer(&self) -> Pubkey {
+        self.admin.key()
+    }
+}
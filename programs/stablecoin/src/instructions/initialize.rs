@@ -0,0 +1,90 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Mint, Token};
+
+use crate::{
+    seeds::{CONFIG_SEED, MINT_SEED, SUPPLY_STATS_SEED},
+    sizes,
+    validation::{Checked, Policy, Role},
+    Config, Initialized, SupplyStats, DECIMALS,
+};
+
+#[derive(Accounts)]
+pub struct Initialize<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    /// The config account that stores stablecoin settings
+    #[account(
+        init,
+        payer = admin,
+        space = sizes::CONFIG,
+        seeds = [CONFIG_SEED],
+        bump
+    )]
+    pub config: Box<Account<'info, Config>>,
+
+    /// The stablecoin mint
+    /// The config PDA is set as both mint authority and freeze authority
+    #[account(
+        init,
+        payer = admin,
+        mint::decimals = DECIMALS,
+        mint::authority = config,
+        mint::freeze_authority = config,
+        seeds = [MINT_SEED],
+        bump
+    )]
+    pub mint: Box<Account<'info, Mint>>,
+
+    /// The time-weighted supply, accumulated from now on
+    #[account(
+        init,
+        payer = admin,
+        space = sizes::SUPPLY_STATS,
+        seeds = [SUPPLY_STATS_SEED],
+        bump
+    )]
+    pub supply_stats: Account<'info, SupplyStats>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+impl Initialize<'_> {
+    pub fn initialize(&mut self, _: Checked<Self>, bumps: &InitializeBumps) -> Result<()> {
+        let config = &mut self.config;
+        config.admin = self.admin.key();
+        config.mint = self.mint.key();
+        config.paused = false;
+        config.bump = bumps.config;
+        config.mint_bump = bumps.mint;
+        config.derive_bumps();
+
+        let now = Clock::get()?.unix_timestamp;
+        let supply_stats = &mut self.supply_stats;
+        supply_stats.updated_at = now;
+        supply_stats.started_at = now;
+        supply_stats.bump = bumps.supply_stats;
+
+        emit!(Initialized {
+            admin: config.admin,
+            mint: config.mint,
+        });
+
+        Ok(())
+    }
+}
+
+/// Whoever initializes first becomes the admin
+impl Policy for Initialize<'_> {
+    const ROLE: Role = Role::Anyone;
+    const PAUSABLE: bool = false;
+
+    fn config(&self) -> Option<&Config> {
+        Some(&self.config)
+    }
+
+    fn signer(&self) -> Pubkey {
+        self.admin.key()
+    }
+}
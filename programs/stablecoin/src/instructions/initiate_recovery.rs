@@ -0,0 +1,106 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Mint, TokenAccount};
+
+use crate::{
+    seeds::{CONFIG_SEED, DELEGATE_SEED, MINT_SEED, RECOVERY_SEED},
+    validation::{self, Checked, Policy, Role},
+    Config, RecoveryConfig, RecoveryInitiated, StablecoinError,
+};
+
+#[derive(Accounts)]
+pub struct InitiateRecovery<'info> {
+    /// The guardian starting the recovery, who approves it by starting it
+    #[account(
+        constraint = validation::is_guardian(&guardian.key(), &recovery_config) @ StablecoinError::NotGuardian
+    )]
+    pub guardian: Signer<'info>,
+
+    /// The settings of the holder being recovered
+    #[account(
+        mut,
+        seeds = [RECOVERY_SEED, recovery_config.owner.as_ref()],
+        bump = recovery_config.bump
+    )]
+    pub recovery_config: Account<'info, RecoveryConfig>,
+
+    /// The config account
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump
+    )]
+    pub config: Box<Account<'info, Config>>,
+
+    /// The stablecoin mint
+    #[account(
+        seeds = [MINT_SEED],
+        bump = config.mint_bump
+    )]
+    pub mint: Box<Account<'info, Mint>>,
+
+    /// CHECK: The holder being recovered, as the settings record
+    #[account(address = recovery_config.owner)]
+    pub owner: UncheckedAccount<'info>,
+
+    /// The holder's token account, which must still delegate its balance
+    #[account(
+        associated_token::mint = mint,
+        associated_token::authority = owner,
+    )]
+    pub token_account: Box<Account<'info, TokenAccount>>,
+
+    /// CHECK: The program's transfer delegate, a PDA without data
+    #[account(
+        seeds = [DELEGATE_SEED],
+        bump = config.delegate_bump
+    )]
+    pub delegate: UncheckedAccount<'info>,
+}
+
+impl InitiateRecovery<'_> {
+    pub fn initiate_recovery(&mut self, _: Checked<Self>, new_owner: Pubkey) -> Result<()> {
+        let recovery_config = &mut self.recovery_config;
+        require!(!recovery_config.in_progress(), StablecoinError::RecoveryInProgress);
+        require!(
+            new_owner != Pubkey::default() && new_owner != recovery_config.owner,
+            StablecoinError::InvalidRecoveryConfig
+        );
+        // Don't start a recovery that could never be executed
+        require!(
+            validation::delegates_balance(&self.token_account, &self.delegate.key()),
+            StablecoinError::DelegateNotApproved
+        );
+
+        let guardian = self.guardian.key();
+        let index = recovery_config.guardian_index(&guardian).ok_or(StablecoinError::NotGuardian)?;
+        recovery_config.new_owner = new_owner;
+        recovery_config.initiated_at = Clock::get()?.unix_timestamp;
+        recovery_config.approvals = 1 << index;
+
+        msg!("Recovery of {} to {} initiated by {}", recovery_config.owner, new_owner, guardian);
+
+        emit!(RecoveryInitiated {
+            owner: recovery_config.owner,
+            new_owner,
+            guardian,
+        });
+
+        Ok(())
+    }
+}
+
+impl Policy for InitiateRecovery<'_> {
+    const ROLE: Role = Role::Guardian;
+    const PAUSABLE: bool = false;
+
+    fn config(&self) -> Option<&Config> {
+        Some(&self.config)
+    }
+
+    fn signer(&self) -> Pubkey {
+        self.guardian.key()
+    }
+
+    fn recovery_config(&self) -> Option<&RecoveryConfig> {
+        Some(&self.recovery_config)
+    }
+}
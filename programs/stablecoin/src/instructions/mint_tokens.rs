@@ -0,0 +1,143 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token::{Mint, Token, TokenAccount, mint_to, MintTo},
+};
+
+use crate::{
+    seeds::{CONFIG_SEED, MINTER_PAUSE_SEED, MINTER_SEED, MINT_SEED, SUPPLY_STATS_SEED},
+    validation::{self, Checked, Policy, Role},
+    Config, MinterConfig, StablecoinError, SupplyStats, TokensMinted,
+};
+
+#[derive(Accounts)]
+pub struct MintTokens<'info> {
+    /// The minter calling this instruction
+    #[account(mut)]
+    pub minter: Signer<'info>,
+
+    /// The config account
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump
+    )]
+    pub config: Box<Account<'info, Config>>,
+
+    /// The minter's configuration - verifies they are authorized
+    #[account(
+        mut,
+        seeds = [MINTER_SEED, minter.key().as_ref()],
+        bump = minter_config.bump,
+        constraint = validation::is_minter(&minter_config) @ StablecoinError::NotMinter
+    )]
+    pub minter_config: Account<'info, MinterConfig>,
+
+    /// CHECK: The minter's pause marker, which must not exist
+    #[account(
+        seeds = [MINTER_PAUSE_SEED, minter.key().as_ref()],
+        bump = minter_config.pause_bump,
+        constraint = minter_pause.data_is_empty() @ StablecoinError::MinterPaused
+    )]
+    pub minter_pause: UncheckedAccount<'info>,
+
+    /// The stablecoin mint
+    #[account(
+        mut,
+        seeds = [MINT_SEED],
+        bump = config.mint_bump
+    )]
+    pub mint: Box<Account<'info, Mint>>,
+
+    /// The time-weighted supply, updated with the new supply
+    #[account(
+        mut,
+        seeds = [SUPPLY_STATS_SEED],
+        bump = supply_stats.bump
+    )]
+    pub supply_stats: Account<'info, SupplyStats>,
+
+    /// The destination token account to mint to
+    #[account(
+        init_if_needed,
+        payer = minter,
+        associated_token::mint = mint,
+        associated_token::authority = destination_owner,
+    )]
+    pub destination: Box<Account<'info, TokenAccount>>,
+
+    /// CHECK: The owner of the destination token account
+    pub destination_owner: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+impl MintTokens<'_> {
+    pub fn mint_tokens(&mut self, _: Checked<Self>, amount: u64) -> Result<()> {
+        self.issue(amount)
+    }
+
+    /// Mint within the minter's allowance; for handlers whose checks
+    /// include this instruction's
+    pub(crate) fn issue(&mut self, amount: u64) -> Result<()> {
+        let config = &self.config;
+
+        // Check and update minter allowance
+        let minter_config = &mut self.minter_config;
+        let remaining = minter_config.allowance.checked_sub(minter_config.amount_minted)
+            .ok_or(StablecoinError::ExceedsAllowance)?;
+        require!(amount <= remaining, StablecoinError::ExceedsAllowance);
+
+        minter_config.amount_minted = minter_config.amount_minted.checked_add(amount)
+            .ok_or(StablecoinError::Overflow)?;
+
+        // Create the signer seeds for the mint authority PDA
+        let signer_seeds: &[&[&[u8]]] = &[&[CONFIG_SEED, &[config.bump]]];
+
+        // Mint tokens to the destination account
+        mint_to(
+            CpiContext::new_with_signer(
+                self.token_program.key(),
+                MintTo {
+                    mint: self.mint.to_account_info(),
+                    to: self.destination.to_account_info(),
+                    authority: self.config.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            amount,
+        )?;
+
+        self.mint.reload()?;
+        self.supply_stats.record(self.mint.supply, Clock::get()?.unix_timestamp)?;
+
+        msg!("Minted {} tokens to {}", amount, self.destination.key());
+
+        emit!(TokensMinted {
+            minter: self.minter.key(),
+            destination: self.destination.key(),
+            amount,
+            remaining_allowance: remaining - amount,
+        });
+
+        Ok(())
+    }
+}
+
+impl Policy for MintTokens<'_> {
+    const ROLE: Role = Role::Minter;
+    const PAUSABLE: bool = true;
+
+    fn config(&self) -> Option<&Config> {
+        Some(&self.config)
+    }
+
+    fn signer(&self) -> Pubkey {
+        self.minter.key()
+    }
+
+    fn minter_config(&self) -> Option<&MinterConfig> {
+        Some(&self.minter_config)
+    }
+}
@@ -0,0 +1,73 @@
+use anchor_lang::prelude::*;
+
+// The composite derive refers to MintTokens' generated modules by name
+use super::mint_tokens::*;
+use crate::{
+    seeds::IDEMPOTENCY_SEED,
+    sizes,
+    validation::{Checked, Policy, Role},
+    Config, IdempotencyRecord, MinterConfig, StablecoinError, IDEMPOTENCY_KEY_TTL,
+};
+
+#[derive(Accounts)]
+#[instruction(amount: u64, key: [u8; 16])]
+pub struct MintTokensIdempotent<'info> {
+    /// The accounts of a plain mint, checked the same way
+    pub mint: MintTokens<'info>,
+
+    /// The record of this key; exists already if the key was used
+    #[account(
+        init_if_needed,
+        payer = mint.minter,
+        space = sizes::IDEMPOTENCY_RECORD,
+        seeds = [IDEMPOTENCY_SEED, mint.minter.key().as_ref(), key.as_ref()],
+        bump
+    )]
+    pub record: Account<'info, IdempotencyRecord>,
+
+    /// Anchor creates the record through its own field, even though the
+    /// mint's accounts pass the same program
+    pub system_program: Program<'info, System>,
+}
+
+impl MintTokensIdempotent<'_> {
+    pub fn mint_tokens_idempotent(
+        &mut self,
+        _: Checked<Self>,
+        amount: u64,
+        key: [u8; 16],
+        bumps: &MintTokensIdempotentBumps,
+    ) -> Result<()> {
+        // A record that was only just created has no minter yet
+        let record = &mut self.record;
+        require_keys_eq!(record.minter, Pubkey::default(), StablecoinError::DuplicateMint);
+
+        record.minter = self.mint.minter.key();
+        record.key = key;
+        record.amount = amount;
+        record.expires_at = Clock::get()?
+            .unix_timestamp
+            .checked_add(IDEMPOTENCY_KEY_TTL)
+            .ok_or(StablecoinError::Overflow)?;
+        record.bump = bumps.record;
+
+        self.mint.issue(amount)
+    }
+}
+
+impl Policy for MintTokensIdempotent<'_> {
+    const ROLE: Role = MintTokens::ROLE;
+    const PAUSABLE: bool = MintTokens::PAUSABLE;
+
+    fn config(&self) -> Option<&Config> {
+        self.mint.config()
+    }
+
+    fn signer(&self) -> Pubkey {
+        self.mint.signer()
+    }
+
+    fn minter_config(&self) -> Option<&MinterConfig> {
+        self.mint.minter_config()
+    }
+}
@@ -0,0 +1,97 @@
+pub mod approve_recovery;
+pub mod approve_treasury_burn;
+pub mod burn_tokens;
+pub mod cancel_netting_window;
+pub mod cancel_recovery;
+pub mod cancel_treasury_burn;
+pub mod check_in;
+pub mod claim_airdrop;
+pub mod claim_inheritance;
+pub mod clawback_airdrop;
+pub mod close_channel;
+pub mod close_inheritance;
+pub mod close_recovery;
+pub mod configure_inheritance;
+pub mod configure_minter;
+pub mod configure_recovery;
+pub mod configure_treasury;
+pub mod execute_recovery;
+pub mod execute_treasury_burn;
+#[cfg(feature = "devnet-faucet")]
+pub mod faucet;
+pub mod freeze_minter_set;
+pub mod initialize;
+pub mod initiate_recovery;
+pub mod mint_tokens;
+pub mod mint_tokens_idempotent;
+pub mod open_airdrop;
+pub mod open_channel;
+pub mod open_netting_window;
+pub mod pause;
+pub mod pause_self;
+pub mod propose_treasury_burn;
+pub mod queue_obligation;
+pub mod reclaim_idempotency_record;
+pub mod register_purpose_code;
+pub mod register_sub_account;
+pub mod remove_minter;
+pub mod resize_account;
+pub mod resume_minter;
+pub mod retire_purpose_code;
+pub mod settle_channel;
+pub mod settle_net;
+pub mod start_inheritance_claim;
+pub mod sweep_dust;
+pub mod sweep_sub_account;
+pub mod sync_supply_stats;
+pub mod unfreeze_minter_set;
+pub mod unpause;
+
+pub use approve_recovery::*;
+pub use approve_treasury_burn::*;
+pub use burn_tokens::*;
+pub use cancel_netting_window::*;
+pub use cancel_recovery::*;
+pub use cancel_treasury_burn::*;
+pub use check_in::*;
+pub use claim_airdrop::*;
+pub use claim_inheritance::*;
+pub use clawback_airdrop::*;
+pub use close_channel::*;
+pub use close_inheritance::*;
+pub use close_recovery::*;
+pub use configure_inheritance::*;
+pub use configure_minter::*;
+pub use configure_recovery::*;
+pub use configure_treasury::*;
+pub use execute_recovery::*;
+pub use execute_treasury_burn::*;
+#[cfg(feature = "devnet-faucet")]
+pub use faucet::*;
+pub use freeze_minter_set::*;
+pub use initialize::*;
+pub use initiate_recovery::*;
+pub use mint_tokens::*;
+pub use mint_tokens_idempotent::*;
+pub use open_airdrop::*;
+pub use open_channel::*;
+pub use open_netting_window::*;
+pub use pause::*;
+pub use pause_self::*;
+pub use propose_treasury_burn::*;
+pub use queue_obligation::*;
+pub use reclaim_idempotency_record::*;
+pub use register_purpose_code::*;
+pub use register_sub_account::*;
+pub use remove_minter::*;
+pub use resize_account::*;
+pub use resume_minter::*;
+pub use retire_purpose_code::*;
+pub use settle_channel::*;
+pub use settle_net::*;
+pub use start_inheritance_claim::*;
+pub use sweep_dust::*;
+pub use sweep_sub_account::*;
+pub use sync_supply_stats::*;
+pub use unfreeze_minter_set::*;
+pub use unpause::*;
@@ -0,0 +1,161 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token::{Mint, Token, TokenAccount, transfer, Transfer},
+};
+
+use crate::{
+    seeds::{AIRDROP_SEED, CONFIG_SEED, MINT_SEED, TREASURY_SEED},
+    sizes,
+    validation::{self, Checked, Policy, Role},
+    Airdrop, AirdropOpened, Config, StablecoinError, Treasury, MAX_AIRDROP_RECIPIENTS,
+};
+
+#[derive(Accounts)]
+#[instruction(id: u64)]
+pub struct OpenAirdrop<'info> {
+    /// The treasury officer funding the airdrop
+    #[account(
+        mut,
+        constraint = validation::is_officer(&funder.key(), &treasury) @ StablecoinError::NotOfficer
+    )]
+    pub funder: Signer<'info>,
+
+    /// The config account
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump
+    )]
+    pub config: Box<Account<'info, Config>>,
+
+    /// The stablecoin mint
+    #[account(
+        seeds = [MINT_SEED],
+        bump = config.mint_bump
+    )]
+    pub mint: Box<Account<'info, Mint>>,
+
+    /// The treasury, which records the officers and gets back what is not
+    /// claimed
+    #[account(
+        seeds = [TREASURY_SEED],
+        bump = treasury.bump
+    )]
+    pub treasury: Box<Account<'info, Treasury>>,
+
+    /// The funder's token account, which pays for the airdrop
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = funder,
+    )]
+    pub token_account: Box<Account<'info, TokenAccount>>,
+
+    /// The airdrop, at an id the funder picks
+    #[account(
+        init,
+        payer = funder,
+        space = sizes::AIRDROP,
+        seeds = [AIRDROP_SEED, funder.key().as_ref(), id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub airdrop: Account<'info, Airdrop>,
+
+    /// Airdrop custody, which holds what is yet to be claimed; anyone can
+    /// create it beforehand, by sending to the airdrop's address
+    #[account(
+        init_if_needed,
+        payer = funder,
+        associated_token::mint = mint,
+        associated_token::authority = airdrop,
+    )]
+    pub custody: Box<Account<'info, TokenAccount>>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+impl OpenAirdrop<'_> {
+    pub fn open_airdrop(
+        &mut self,
+        _: Checked<Self>,
+        id: u64,
+        recipients: Vec<Pubkey>,
+        amounts: Vec<u64>,
+        duration: i64,
+        bumps: &OpenAirdropBumps,
+    ) -> Result<()> {
+        let distinct = recipients
+            .iter()
+            .enumerate()
+            .all(|(i, recipient)| !recipients[..i].contains(recipient));
+        require!(
+            (1..=MAX_AIRDROP_RECIPIENTS as usize).contains(&recipients.len())
+                && amounts.len() == recipients.len()
+                && distinct
+                && !recipients.contains(&Pubkey::default())
+                && !amounts.contains(&0)
+                && duration > 0,
+            StablecoinError::InvalidAirdrop
+        );
+        let total = amounts
+            .iter()
+            .try_fold(0u64, |total, amount| total.checked_add(*amount))
+            .ok_or(StablecoinError::Overflow)?;
+        let closes_at = Clock::get()?
+            .unix_timestamp
+            .checked_add(duration)
+            .ok_or(StablecoinError::Overflow)?;
+
+        transfer(
+            CpiContext::new(
+                self.token_program.key(),
+                Transfer {
+                    from: self.token_account.to_account_info(),
+                    to: self.custody.to_account_info(),
+                    authority: self.funder.to_account_info(),
+                },
+            ),
+            total,
+        )?;
+
+        let airdrop = &mut self.airdrop;
+        airdrop.funder = self.funder.key();
+        airdrop.id = id;
+        airdrop.recipients = recipients;
+        airdrop.unclaimed = amounts;
+        airdrop.closes_at = closes_at;
+        airdrop.bump = bumps.airdrop;
+
+        msg!("Opened airdrop {} of {} tokens to {} recipients until {}", id, total, airdrop.recipients.len(), closes_at);
+
+        emit!(AirdropOpened {
+            funder: airdrop.funder,
+            id,
+            total,
+            closes_at,
+        });
+
+        Ok(())
+    }
+}
+
+/// Officers open airdrops with their own tokens; what is not claimed goes
+/// to treasury custody, so only the treasury's people can fund one
+impl Policy for OpenAirdrop<'_> {
+    const ROLE: Role = Role::Officer;
+    const PAUSABLE: bool = false;
+
+    fn config(&self) -> Option<&Config> {
+        Some(&self.config)
+    }
+
+    fn signer(&self) -> Pubkey {
+        self.funder.key()
+    }
+
+    fn treasury(&self) -> Option<&Treasury> {
+        Some(&self.treasury)
+    }
+}
@@ -0,0 +1,144 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token::{Mint, Token, TokenAccount, transfer, Transfer},
+};
+
+use crate::{
+    seeds::{CHANNEL_COUNTER_SEED, CHANNEL_SEED, CONFIG_SEED, MINT_SEED},
+    sizes,
+    validation::{Checked, Policy, Role},
+    ChannelCounter, Config, PaymentChannel, StablecoinError, MIN_CHANNEL_DISPUTE_PERIOD,
+};
+
+#[derive(Accounts)]
+#[instruction(recipient: Pubkey)]
+pub struct OpenChannel<'info> {
+    /// The wallet opening the channel and depositing into it
+    #[account(mut)]
+    pub sender: Signer<'info>,
+
+    /// The config account
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump
+    )]
+    pub config: Box<Account<'info, Config>>,
+
+    /// The stablecoin mint
+    #[account(
+        seeds = [MINT_SEED],
+        bump = config.mint_bump
+    )]
+    pub mint: Box<Account<'info, Mint>>,
+
+    /// The sender's token account, which funds the deposit
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = sender,
+    )]
+    pub token_account: Box<Account<'info, TokenAccount>>,
+
+    /// The channel, one at a time between the same sender and recipient
+    #[account(
+        init,
+        payer = sender,
+        space = sizes::PAYMENT_CHANNEL,
+        seeds = [CHANNEL_SEED, sender.key().as_ref(), recipient.as_ref()],
+        bump
+    )]
+    pub payment_channel: Account<'info, PaymentChannel>,
+
+    /// Counts the channels between the same sender and recipient, created
+    /// with the first
+    #[account(
+        init_if_needed,
+        payer = sender,
+        space = sizes::CHANNEL_COUNTER,
+        seeds = [CHANNEL_COUNTER_SEED, sender.key().as_ref(), recipient.as_ref()],
+        bump
+    )]
+    pub channel_counter: Account<'info, ChannelCounter>,
+
+    /// Channel custody, which holds the deposit until settlement; anyone
+    /// can create it beforehand, by sending to the channel's address
+    #[account(
+        init_if_needed,
+        payer = sender,
+        associated_token::mint = mint,
+        associated_token::authority = payment_channel,
+    )]
+    pub custody: Box<Account<'info, TokenAccount>>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+impl OpenChannel<'_> {
+    pub fn open_channel(
+        &mut self,
+        _: Checked<Self>,
+        recipient: Pubkey,
+        deposit: u64,
+        dispute_period: i64,
+        bumps: &OpenChannelBumps,
+    ) -> Result<()> {
+        let sender = self.sender.key();
+        require!(
+            recipient != Pubkey::default()
+                && recipient != sender
+                && deposit > 0
+                && dispute_period >= MIN_CHANNEL_DISPUTE_PERIOD,
+            StablecoinError::InvalidPaymentChannel
+        );
+
+        transfer(
+            CpiContext::new(
+                self.token_program.key(),
+                Transfer {
+                    from: self.token_account.to_account_info(),
+                    to: self.custody.to_account_info(),
+                    authority: self.sender.to_account_info(),
+                },
+            ),
+            deposit,
+        )?;
+
+        let channel_counter = &mut self.channel_counter;
+        let nonce = channel_counter.opened;
+        channel_counter.opened = nonce.checked_add(1).ok_or(StablecoinError::Overflow)?;
+        channel_counter.bump = bumps.channel_counter;
+
+        let payment_channel = &mut self.payment_channel;
+        payment_channel.sender = sender;
+        payment_channel.recipient = recipient;
+        payment_channel.deposit = deposit;
+        payment_channel.dispute_period = dispute_period;
+        payment_channel.opened_at = Clock::get()?.unix_timestamp;
+        payment_channel.nonce = nonce;
+        payment_channel.amount = 0;
+        payment_channel.closes_at = 0;
+        payment_channel.bump = bumps.payment_channel;
+
+        msg!("Opened payment channel from {} to {} with {} tokens", sender, recipient, deposit);
+
+        Ok(())
+    }
+}
+
+/// Senders open channels with their own deposit; the channel's seeds bind
+/// it to the signer
+impl Policy for OpenChannel<'_> {
+    const ROLE: Role = Role::Anyone;
+    const PAUSABLE: bool = false;
+
+    fn config(&self) -> Option<&Config> {
+        Some(&self.config)
+    }
+
+    fn signer(&self) -> Pubkey {
+        self.sender.key()
+    }
+}
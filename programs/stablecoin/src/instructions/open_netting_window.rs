@@ -0,0 +1,114 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token::{Mint, Token, TokenAccount},
+};
+
+use crate::{
+    seeds::{CONFIG_SEED, MINT_SEED, NETTING_SEED},
+    sizes,
+    validation::{self, Checked, Policy, Role},
+    Config, NettingWindow, StablecoinError, MAX_NETTING_INSTITUTIONS,
+};
+
+#[derive(Accounts)]
+#[instruction(id: u64)]
+pub struct OpenNettingWindow<'info> {
+    /// Only the admin can register institutions for netting
+    #[account(
+        mut,
+        constraint = validation::is_admin(&admin.key(), &config) @ StablecoinError::Unauthorized
+    )]
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump
+    )]
+    pub config: Box<Account<'info, Config>>,
+
+    /// The stablecoin mint
+    #[account(
+        seeds = [MINT_SEED],
+        bump = config.mint_bump
+    )]
+    pub mint: Box<Account<'info, Mint>>,
+
+    /// The window, at an id the admin picks
+    #[account(
+        init,
+        payer = admin,
+        space = sizes::NETTING_WINDOW,
+        seeds = [NETTING_SEED, id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub netting_window: Account<'info, NettingWindow>,
+
+    /// Window custody, which holds what institutions escrow; anyone can
+    /// create it beforehand, by sending to the window's address
+    #[account(
+        init_if_needed,
+        payer = admin,
+        associated_token::mint = mint,
+        associated_token::authority = netting_window,
+    )]
+    pub custody: Box<Account<'info, TokenAccount>>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+impl OpenNettingWindow<'_> {
+    pub fn open_netting_window(
+        &mut self,
+        _: Checked<Self>,
+        id: u64,
+        institutions: Vec<Pubkey>,
+        duration: i64,
+        bumps: &OpenNettingWindowBumps,
+    ) -> Result<()> {
+        let distinct = institutions
+            .iter()
+            .enumerate()
+            .all(|(i, institution)| !institutions[..i].contains(institution));
+        require!(
+            (2..=MAX_NETTING_INSTITUTIONS as usize).contains(&institutions.len())
+                && distinct
+                && !institutions.contains(&Pubkey::default())
+                && duration > 0,
+            StablecoinError::InvalidNettingWindow
+        );
+        let closes_at = Clock::get()?
+            .unix_timestamp
+            .checked_add(duration)
+            .ok_or(StablecoinError::Overflow)?;
+
+        let netting_window = &mut self.netting_window;
+        netting_window.id = id;
+        netting_window.opened_by = self.admin.key();
+        netting_window.debits = vec![0; institutions.len()];
+        netting_window.credits = vec![0; institutions.len()];
+        netting_window.escrowed = vec![0; institutions.len()];
+        netting_window.institutions = institutions;
+        netting_window.closes_at = closes_at;
+        netting_window.bump = bumps.netting_window;
+
+        msg!("Opened netting window {} for {} institutions until {}", id, netting_window.institutions.len(), closes_at);
+
+        Ok(())
+    }
+}
+
+impl Policy for OpenNettingWindow<'_> {
+    const ROLE: Role = Role::Admin;
+    const PAUSABLE: bool = false;
+
+    fn config(&self) -> Option<&Config> {
+        Some(&self.config)
+    }
+
+    fn signer(&self) -> Pubkey {
+        self.admin.key()
+    }
+}
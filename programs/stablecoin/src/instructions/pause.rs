@@ -0,0 +1,48 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    seeds::CONFIG_SEED,
+    validation::{self, Checked, Policy, Role},
+    Config, StablecoinError, Paused,
+};
+
+#[derive(Accounts)]
+pub struct Pause<'info> {
+    /// Only the admin can pause
+    #[account(
+        constraint = validation::is_admin(&admin.key(), &config) @ StablecoinError::Unauthorized
+    )]
+    pub admin: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [CONFIG_SEED],
+        bump = config.bump
+    )]
+    pub config: Box<Account<'info, Config>>,
+}
+
+impl Pause<'_> {
+    pub fn pause(&mut self, _: Checked<Self>) -> Result<()> {
+        self.config.paused = true;
+        msg!("Stablecoin paused");
+        emit!(Paused {
+            admin: self.admin.key(),
+        });
+        Ok(())
+    }
+}
+
+/// Pausing never stops the admin from undoing or repeating a pause
+impl Policy for Pause<'_> {
+    const ROLE: Role = Role::Admin;
+    const PAUSABLE: bool = false;
+
+    fn config(&self) -> Option<&Config> {
+        Some(&self.config)
+    }
+
+    fn signer(&self) -> Pubkey {
+        self.admin.key()
+    }
+}
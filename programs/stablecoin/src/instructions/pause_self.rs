@@ -0,0 +1,71 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    seeds::{MINTER_PAUSE_SEED, MINTER_SEED},
+    sizes,
+    validation::{self, Checked, Policy, Role},
+    Config, MinterConfig, MinterPause, MinterSelfPaused, StablecoinError,
+};
+
+#[derive(Accounts)]
+pub struct PauseSelf<'info> {
+    /// The minter pausing itself, who pays for the marker
+    #[account(mut)]
+    pub minter: Signer<'info>,
+
+    /// The minter's configuration - verifies they are a minter
+    #[account(
+        seeds = [MINTER_SEED, minter.key().as_ref()],
+        bump = minter_config.bump,
+        constraint = validation::is_minter(&minter_config) @ StablecoinError::NotMinter
+    )]
+    pub minter_config: Account<'info, MinterConfig>,
+
+    /// The pause marker, which exists only while the minter is paused
+    #[account(
+        init,
+        payer = minter,
+        space = sizes::MINTER_PAUSE,
+        seeds = [MINTER_PAUSE_SEED, minter.key().as_ref()],
+        bump
+    )]
+    pub minter_pause: Account<'info, MinterPause>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl PauseSelf<'_> {
+    pub fn pause_self(&mut self, _: Checked<Self>, bumps: &PauseSelfBumps) -> Result<()> {
+        let minter_pause = &mut self.minter_pause;
+        minter_pause.minter = self.minter.key();
+        minter_pause.paused_at = Clock::get()?.unix_timestamp;
+        minter_pause.bump = bumps.minter_pause;
+
+        msg!("Minter {} paused itself", self.minter.key());
+
+        emit!(MinterSelfPaused {
+            minter: self.minter.key(),
+        });
+
+        Ok(())
+    }
+}
+
+/// A kill switch, so neither a global pause nor a frozen minter set stops
+/// it
+impl Policy for PauseSelf<'_> {
+    const ROLE: Role = Role::Minter;
+    const PAUSABLE: bool = false;
+
+    fn config(&self) -> Option<&Config> {
+        None
+    }
+
+    fn signer(&self) -> Pubkey {
+        self.minter.key()
+    }
+
+    fn minter_config(&self) -> Option<&MinterConfig> {
+        Some(&self.minter_config)
+    }
+}
@@ -0,0 +1,81 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    seeds::{PENDING_BURN_SEED, TREASURY_SEED},
+    sizes,
+    validation::{self, Checked, Policy, Role},
+    Config, PendingBurn, StablecoinError, Treasury, TreasuryBurnProposed,
+};
+
+#[derive(Accounts)]
+#[instruction(id: u64)]
+pub struct ProposeTreasuryBurn<'info> {
+    /// The officer proposing the burn, who approves it by proposing
+    #[account(
+        mut,
+        constraint = validation::is_officer(&officer.key(), &treasury) @ StablecoinError::NotOfficer
+    )]
+    pub officer: Signer<'info>,
+
+    #[account(
+        seeds = [TREASURY_SEED],
+        bump = treasury.bump
+    )]
+    pub treasury: Account<'info, Treasury>,
+
+    /// The proposal, at an id the proposer picks
+    #[account(
+        init,
+        payer = officer,
+        space = sizes::PENDING_BURN,
+        seeds = [PENDING_BURN_SEED, id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub pending_burn: Account<'info, PendingBurn>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl ProposeTreasuryBurn<'_> {
+    pub fn propose_treasury_burn(
+        &mut self,
+        _: Checked<Self>,
+        id: u64,
+        amount: u64,
+        bumps: &ProposeTreasuryBurnBumps,
+    ) -> Result<()> {
+        let pending_burn = &mut self.pending_burn;
+        pending_burn.id = id;
+        pending_burn.proposer = self.officer.key();
+        pending_burn.amount = amount;
+        pending_burn.approvals = [self.officer.key(), Pubkey::default()];
+        pending_burn.bump = bumps.pending_burn;
+
+        msg!("Proposed treasury burn {} of {} tokens", id, amount);
+
+        emit!(TreasuryBurnProposed {
+            id,
+            proposer: self.officer.key(),
+            amount,
+        });
+
+        Ok(())
+    }
+}
+
+impl Policy for ProposeTreasuryBurn<'_> {
+    const ROLE: Role = Role::Officer;
+    const PAUSABLE: bool = false;
+
+    fn config(&self) -> Option<&Config> {
+        None
+    }
+
+    fn signer(&self) -> Pubkey {
+        self.officer.key()
+    }
+
+    fn treasury(&self) -> Option<&Treasury> {
+        Some(&self.treasury)
+    }
+}
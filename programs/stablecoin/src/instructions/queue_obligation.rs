@@ -0,0 +1,138 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Mint, Token, TokenAccount, transfer, Transfer};
+
+use crate::{
+    seeds::{CONFIG_SEED, MINT_SEED, NETTING_SEED, PURPOSE_SEED},
+    validation::{self, Checked, Policy, Role},
+    Config, NettingWindow, ObligationQueued, PurposeCode, StablecoinError,
+};
+
+#[derive(Accounts)]
+pub struct QueueObligation<'info> {
+    /// The institution that owes the amount
+    #[account(
+        constraint = validation::is_institution(&debtor.key(), &netting_window) @ StablecoinError::NotInstitution
+    )]
+    pub debtor: Signer<'info>,
+
+    /// The config account
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump
+    )]
+    pub config: Box<Account<'info, Config>>,
+
+    /// The stablecoin mint
+    #[account(
+        seeds = [MINT_SEED],
+        bump = config.mint_bump
+    )]
+    pub mint: Box<Account<'info, Mint>>,
+
+    /// The debtor's token account, which escrows its net debit
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = debtor,
+    )]
+    pub token_account: Box<Account<'info, TokenAccount>>,
+
+    /// The window to queue the obligation in
+    #[account(
+        mut,
+        seeds = [NETTING_SEED, netting_window.id.to_le_bytes().as_ref()],
+        bump = netting_window.bump
+    )]
+    pub netting_window: Account<'info, NettingWindow>,
+
+    /// Window custody, which holds the escrowed net debits
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = netting_window,
+    )]
+    pub custody: Box<Account<'info, TokenAccount>>,
+
+    /// The registered purpose of the payment, if the debtor states one
+    #[account(
+        seeds = [PURPOSE_SEED, purpose_code.code.as_ref()],
+        bump = purpose_code.bump
+    )]
+    pub purpose_code: Option<Account<'info, PurposeCode>>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+impl QueueObligation<'_> {
+    pub fn queue_obligation(&mut self, _: Checked<Self>, creditor: Pubkey, amount: u64) -> Result<()> {
+        let debtor = self.debtor.key();
+        let netting_window = &mut self.netting_window;
+        require!(
+            Clock::get()?.unix_timestamp < netting_window.closes_at,
+            StablecoinError::NettingWindowClosed
+        );
+        let creditor_index = netting_window
+            .institution_index(&creditor)
+            .filter(|_| creditor != debtor && amount > 0)
+            .ok_or(StablecoinError::InvalidObligation)?;
+        let debtor_index = netting_window
+            .institution_index(&debtor)
+            .ok_or(StablecoinError::NotInstitution)?;
+
+        let debit = &mut netting_window.debits[debtor_index];
+        *debit = debit.checked_add(amount).ok_or(StablecoinError::Overflow)?;
+        let credit = &mut netting_window.credits[creditor_index];
+        *credit = credit.checked_add(amount).ok_or(StablecoinError::Overflow)?;
+
+        // Escrow what the debtor's net debit grew by, so settlement has it
+        // whatever the debtor holds once the window closes
+        let shortfall = netting_window.shortfall(debtor_index);
+        if shortfall > 0 {
+            require!(
+                self.token_account.amount >= shortfall,
+                StablecoinError::ObligationUnderfunded
+            );
+            transfer(
+                CpiContext::new(
+                    self.token_program.key(),
+                    Transfer {
+                        from: self.token_account.to_account_info(),
+                        to: self.custody.to_account_info(),
+                        authority: self.debtor.to_account_info(),
+                    },
+                ),
+                shortfall,
+            )?;
+            netting_window.escrowed[debtor_index] += shortfall;
+        }
+
+        msg!("Queued obligation of {} tokens from {} to {}", amount, debtor, creditor);
+
+        emit!(ObligationQueued {
+            id: self.netting_window.id,
+            debtor,
+            creditor,
+            amount,
+            purpose: self.purpose_code.as_ref().map(|purpose_code| purpose_code.code),
+        });
+
+        Ok(())
+    }
+}
+
+impl Policy for QueueObligation<'_> {
+    const ROLE: Role = Role::Institution;
+    const PAUSABLE: bool = false;
+
+    fn config(&self) -> Option<&Config> {
+        Some(&self.config)
+    }
+
+    fn signer(&self) -> Pubkey {
+        self.debtor.key()
+    }
+
+    fn netting_window(&self) -> Option<&NettingWindow> {
+        Some(&self.netting_window)
+    }
+}
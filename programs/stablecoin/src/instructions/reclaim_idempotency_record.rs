@@ -0,0 +1,52 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    seeds::IDEMPOTENCY_SEED,
+    validation::{Checked, Policy, Role},
+    Config, IdempotencyRecord, StablecoinError,
+};
+
+#[derive(Accounts)]
+pub struct ReclaimIdempotencyRecord<'info> {
+    /// The minter that created the record, and gets its rent back
+    #[account(mut)]
+    pub minter: Signer<'info>,
+
+    /// The expired record to close
+    #[account(
+        mut,
+        close = minter,
+        has_one = minter,
+        seeds = [IDEMPOTENCY_SEED, minter.key().as_ref(), record.key.as_ref()],
+        bump = record.bump
+    )]
+    pub record: Account<'info, IdempotencyRecord>,
+}
+
+impl ReclaimIdempotencyRecord<'_> {
+    /// The record itself is closed by its `close` constraint once this returns
+    pub fn reclaim_idempotency_record(&mut self, _: Checked<Self>) -> Result<()> {
+        require!(
+            Clock::get()?.unix_timestamp >= self.record.expires_at,
+            StablecoinError::IdempotencyRecordNotExpired
+        );
+
+        msg!("Reclaimed idempotency record {}", self.record.key());
+
+        Ok(())
+    }
+}
+
+/// A minter that has since been removed can still reclaim its records
+impl Policy for ReclaimIdempotencyRecord<'_> {
+    const ROLE: Role = Role::Anyone;
+    const PAUSABLE: bool = false;
+
+    fn config(&self) -> Option<&Config> {
+        None
+    }
+
+    fn signer(&self) -> Pubkey {
+        self.minter.key()
+    }
+}
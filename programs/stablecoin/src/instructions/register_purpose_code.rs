@@ -0,0 +1,65 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    seeds::{CONFIG_SEED, PURPOSE_SEED},
+    sizes,
+    validation::{self, Checked, Policy, Role},
+    Config, PurposeCode, StablecoinError,
+};
+
+#[derive(Accounts)]
+#[instruction(code: [u8; 4])]
+pub struct RegisterPurposeCode<'info> {
+    /// Only the admin manages the code registry
+    #[account(
+        mut,
+        constraint = validation::is_admin(&admin.key(), &config) @ StablecoinError::Unauthorized
+    )]
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump
+    )]
+    pub config: Box<Account<'info, Config>>,
+
+    /// The code's registry entry
+    #[account(
+        init,
+        payer = admin,
+        space = sizes::PURPOSE_CODE,
+        seeds = [PURPOSE_SEED, code.as_ref()],
+        bump
+    )]
+    pub purpose_code: Account<'info, PurposeCode>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl RegisterPurposeCode<'_> {
+    pub fn register_purpose_code(&mut self, _: Checked<Self>, code: [u8; 4], bumps: &RegisterPurposeCodeBumps) -> Result<()> {
+        require!(PurposeCode::is_valid(&code), StablecoinError::InvalidPurposeCode);
+
+        let purpose_code = &mut self.purpose_code;
+        purpose_code.code = code;
+        purpose_code.registered_by = self.admin.key();
+        purpose_code.bump = bumps.purpose_code;
+
+        msg!("Registered purpose code {}", String::from_utf8_lossy(&code));
+
+        Ok(())
+    }
+}
+
+impl Policy for RegisterPurposeCode<'_> {
+    const ROLE: Role = Role::Admin;
+    const PAUSABLE: bool = false;
+
+    fn config(&self) -> Option<&Config> {
+        Some(&self.config)
+    }
+
+    fn signer(&self) -> Pubkey {
+        self.admin.key()
+    }
+}
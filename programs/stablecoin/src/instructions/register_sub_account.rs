@@ -0,0 +1,101 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token::{Mint, Token, TokenAccount},
+};
+
+use crate::{
+    seeds::{CONFIG_SEED, MINT_SEED, PURPOSE_SEED, SUB_ACCOUNT_SEED},
+    sizes,
+    validation::{Checked, Policy, Role},
+    Config, PurposeCode, SubAccount, SubAccountRegistered,
+};
+
+#[derive(Accounts)]
+#[instruction(index: u64)]
+pub struct RegisterSubAccount<'info> {
+    /// The wallet registering the sub-account, who pays for it
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    /// The config account
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump
+    )]
+    pub config: Box<Account<'info, Config>>,
+
+    /// The stablecoin mint
+    #[account(
+        seeds = [MINT_SEED],
+        bump = config.mint_bump
+    )]
+    pub mint: Box<Account<'info, Mint>>,
+
+    /// The registered purpose the sub-account is filed under
+    #[account(
+        seeds = [PURPOSE_SEED, purpose_code.code.as_ref()],
+        bump = purpose_code.bump
+    )]
+    pub purpose_code: Account<'info, PurposeCode>,
+
+    /// The sub-account's registry entry, one per owner and index
+    #[account(
+        init,
+        payer = owner,
+        space = sizes::SUB_ACCOUNT,
+        seeds = [SUB_ACCOUNT_SEED, owner.key().as_ref(), &index.to_le_bytes()],
+        bump
+    )]
+    pub sub_account: Account<'info, SubAccount>,
+
+    /// Sub-account custody, where tokens sent to the sub-account arrive;
+    /// anyone can create it before registration, by sending to it
+    #[account(
+        init_if_needed,
+        payer = owner,
+        associated_token::mint = mint,
+        associated_token::authority = sub_account,
+    )]
+    pub custody: Box<Account<'info, TokenAccount>>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+impl RegisterSubAccount<'_> {
+    pub fn register_sub_account(&mut self, _: Checked<Self>, index: u64, bumps: &RegisterSubAccountBumps) -> Result<()> {
+        let sub_account = &mut self.sub_account;
+        sub_account.owner = self.owner.key();
+        sub_account.index = index;
+        sub_account.purpose = self.purpose_code.code;
+        sub_account.bump = bumps.sub_account;
+
+        msg!("Registered sub-account {} of {}", index, sub_account.owner);
+
+        emit!(SubAccountRegistered {
+            owner: sub_account.owner,
+            index,
+            purpose: sub_account.purpose,
+            custody: self.custody.key(),
+        });
+
+        Ok(())
+    }
+}
+
+/// Owners number their own sub-accounts; the sub-account's seeds bind it
+/// to the signer
+impl Policy for RegisterSubAccount<'_> {
+    const ROLE: Role = Role::Anyone;
+    const PAUSABLE: bool = false;
+
+    fn config(&self) -> Option<&Config> {
+        Some(&self.config)
+    }
+
+    fn signer(&self) -> Pubkey {
+        self.owner.key()
+    }
+}
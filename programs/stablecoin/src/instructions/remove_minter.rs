@@ -0,0 +1,90 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    seeds::{CONFIG_SEED, MINTER_SEED, MINTER_SET_FREEZE_SEED, MINTER_TOMBSTONE_SEED},
+    sizes,
+    validation::{self, Checked, Policy, Role},
+    Config, MinterConfig, MinterRemoved, MinterTombstone, StablecoinError,
+};
+
+#[derive(Accounts)]
+pub struct RemoveMinter<'info> {
+    /// Only the admin can remove minters
+    #[account(
+        mut,
+        constraint = validation::is_admin(&admin.key(), &config) @ StablecoinError::Unauthorized
+    )]
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump
+    )]
+    pub config: Box<Account<'info, Config>>,
+
+    /// The minter being removed
+    /// CHECK: This is the minter whose config is being closed
+    pub minter: UncheckedAccount<'info>,
+
+    /// The minter's configuration account to close
+    #[account(
+        mut,
+        close = admin,
+        seeds = [MINTER_SEED, minter.key().as_ref()],
+        bump = minter_config.bump
+    )]
+    pub minter_config: Account<'info, MinterConfig>,
+
+    /// CHECK: The minter set freeze marker, which must not exist
+    #[account(
+        seeds = [MINTER_SET_FREEZE_SEED],
+        bump = config.minter_set_freeze_bump,
+        constraint = minter_set_freeze.data_is_empty() @ StablecoinError::MinterSetFrozen
+    )]
+    pub minter_set_freeze: UncheckedAccount<'info>,
+
+    /// The tombstone left in the config's place, paid for by the admin
+    #[account(
+        init,
+        payer = admin,
+        space = sizes::MINTER_TOMBSTONE,
+        seeds = [MINTER_TOMBSTONE_SEED, minter.key().as_ref()],
+        bump
+    )]
+    pub minter_tombstone: Account<'info, MinterTombstone>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl RemoveMinter<'_> {
+    /// The config itself is closed by its `close` constraint once this returns
+    pub fn remove_minter(&mut self, _: Checked<Self>, bumps: &RemoveMinterBumps) -> Result<()> {
+        let minter_tombstone = &mut self.minter_tombstone;
+        minter_tombstone.minter = self.minter.key();
+        minter_tombstone.removed_by = self.admin.key();
+        minter_tombstone.removed_at = Clock::get()?.unix_timestamp;
+        minter_tombstone.amount_minted = self.minter_config.amount_minted;
+        minter_tombstone.bump = bumps.minter_tombstone;
+
+        msg!("Minter removed");
+
+        emit!(MinterRemoved {
+            minter: self.minter.key(),
+        });
+
+        Ok(())
+    }
+}
+
+impl Policy for RemoveMinter<'_> {
+    const ROLE: Role = Role::Admin;
+    const PAUSABLE: bool = false;
+
+    fn config(&self) -> Option<&Config> {
+        Some(&self.config)
+    }
+
+    fn signer(&self) -> Pubkey {
+        self.admin.key()
+    }
+}
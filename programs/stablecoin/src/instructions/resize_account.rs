@@ -0,0 +1,117 @@
+use anchor_lang::{
+    prelude::*,
+    system_program::{self, Transfer},
+};
+
+use crate::{
+    seeds::CONFIG_SEED,
+    sizes,
+    validation::{Checked, Policy, Role},
+    AccountResized, Config, MinterConfig, StablecoinError,
+};
+
+#[derive(Accounts)]
+pub struct ResizeAccount<'info> {
+    /// Only the admin resizes accounts
+    pub admin: Signer<'info>,
+
+    /// Pays for any rent the new size needs
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// CHECK: the config, read only for its admin, since a config laid out
+    /// by an older version of the program may not deserialize until it is
+    /// resized itself
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump,
+        owner = crate::ID
+    )]
+    pub config: UncheckedAccount<'info>,
+
+    /// CHECK: any of the program's accounts; the handler finds its type by
+    /// its discriminator
+    #[account(
+        mut,
+        owner = crate::ID
+    )]
+    pub account: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl ResizeAccount<'_> {
+    pub fn resize_account(&mut self, _: Checked<Self>) -> Result<()> {
+        let account = self.account.to_account_info();
+        let old_len = account.data_len();
+        let new_len = sizes::of(&account.try_borrow_data()?).ok_or(StablecoinError::CannotResize)?;
+        // Accounts carry no version field: every field is fixed size and
+        // versions only ever append fields, so the length is the layout
+        // version. Longer than this version lays the type out means a newer
+        // version wrote it, and shrinking it would drop fields
+        require!(old_len <= new_len, StablecoinError::NewerAccountVersion);
+
+        if new_len > old_len {
+            // Zero-extends, so new fields start out zeroed
+            account.resize(new_len)?;
+        }
+
+        // Bumps a newer layout added start out zeroed, which no canonical
+        // bump is
+        {
+            let mut data = account.try_borrow_mut_data()?;
+            if data.starts_with(Config::DISCRIMINATOR) {
+                let mut config = Config::try_deserialize(&mut &data[..])?;
+                config.derive_bumps();
+                config.try_serialize(&mut &mut data[..])?;
+            } else if data.starts_with(MinterConfig::DISCRIMINATOR) {
+                let mut minter_config = MinterConfig::try_deserialize(&mut &data[..])?;
+                minter_config.derive_bumps();
+                minter_config.try_serialize(&mut &mut data[..])?;
+            }
+        }
+
+        let top_up = Rent::get()?.minimum_balance(new_len).saturating_sub(account.lamports());
+        if top_up > 0 {
+            system_program::transfer(
+                CpiContext::new(
+                    self.system_program.key(),
+                    Transfer {
+                        from: self.payer.to_account_info(),
+                        to: account,
+                    },
+                ),
+                top_up,
+            )?;
+        }
+
+        emit!(AccountResized {
+            admin: self.admin.key(),
+            account: self.account.key(),
+            old_len: old_len as u32,
+            new_len: new_len as u32,
+            top_up,
+        });
+
+        msg!("Resized {} from {} to {} bytes", self.account.key(), old_len, new_len);
+
+        Ok(())
+    }
+}
+
+impl Policy for ResizeAccount<'_> {
+    const ROLE: Role = Role::Admin;
+    const PAUSABLE: bool = false;
+
+    fn config(&self) -> Option<&Config> {
+        None
+    }
+
+    fn admin(&self) -> Option<Pubkey> {
+        Config::admin_of(&self.config.try_borrow_data().ok()?)
+    }
+
+    fn signer(&self) -> Pubkey {
+        self.admin.key()
+    }
+}
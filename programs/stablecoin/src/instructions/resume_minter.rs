@@ -0,0 +1,63 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    seeds::{CONFIG_SEED, MINTER_PAUSE_SEED},
+    validation::{self, Checked, Policy, Role},
+    Config, MinterPause, MinterResumed, StablecoinError,
+};
+
+#[derive(Accounts)]
+pub struct ResumeMinter<'info> {
+    /// Only the admin can resume a minter that paused itself
+    #[account(
+        constraint = validation::is_admin(&admin.key(), &config) @ StablecoinError::Unauthorized
+    )]
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump
+    )]
+    pub config: Box<Account<'info, Config>>,
+
+    /// CHECK: The minter being resumed, who gets the marker's rent back
+    #[account(mut)]
+    pub minter: UncheckedAccount<'info>,
+
+    /// The pause marker to close
+    #[account(
+        mut,
+        close = minter,
+        has_one = minter,
+        seeds = [MINTER_PAUSE_SEED, minter.key().as_ref()],
+        bump = minter_pause.bump
+    )]
+    pub minter_pause: Account<'info, MinterPause>,
+}
+
+impl ResumeMinter<'_> {
+    /// The marker is closed by its `close` constraint once this returns
+    pub fn resume_minter(&mut self, _: Checked<Self>) -> Result<()> {
+        msg!("Minter {} resumed", self.minter.key());
+
+        emit!(MinterResumed {
+            minter: self.minter.key(),
+            admin: self.admin.key(),
+        });
+
+        Ok(())
+    }
+}
+
+impl Policy for ResumeMinter<'_> {
+    const ROLE: Role = Role::Admin;
+    const PAUSABLE: bool = false;
+
+    fn config(&self) -> Option<&Config> {
+        Some(&self.config)
+    }
+
+    fn signer(&self) -> Pubkey {
+        self.admin.key()
+    }
+}
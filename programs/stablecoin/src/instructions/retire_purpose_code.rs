@@ -0,0 +1,59 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    seeds::{CONFIG_SEED, PURPOSE_SEED},
+    validation::{self, Checked, Policy, Role},
+    Config, PurposeCode, StablecoinError,
+};
+
+#[derive(Accounts)]
+pub struct RetirePurposeCode<'info> {
+    /// Only the admin manages the code registry
+    #[account(
+        constraint = validation::is_admin(&admin.key(), &config) @ StablecoinError::Unauthorized
+    )]
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump
+    )]
+    pub config: Box<Account<'info, Config>>,
+
+    /// The code's registry entry
+    #[account(
+        mut,
+        close = registered_by,
+        has_one = registered_by,
+        seeds = [PURPOSE_SEED, purpose_code.code.as_ref()],
+        bump = purpose_code.bump
+    )]
+    pub purpose_code: Account<'info, PurposeCode>,
+
+    /// CHECK: The admin that registered the code, who gets its rent back
+    #[account(mut)]
+    pub registered_by: UncheckedAccount<'info>,
+}
+
+impl RetirePurposeCode<'_> {
+    /// The entry is closed by its `close` constraint once this returns;
+    /// obligations already queued keep the code in their events
+    pub fn retire_purpose_code(&mut self, _: Checked<Self>) -> Result<()> {
+        msg!("Retired purpose code {}", String::from_utf8_lossy(&self.purpose_code.code));
+
+        Ok(())
+    }
+}
+
+impl Policy for RetirePurposeCode<'_> {
+    const ROLE: Role = Role::Admin;
+    const PAUSABLE: bool = false;
+
+    fn config(&self) -> Option<&Config> {
+        Some(&self.config)
+    }
+
+    fn signer(&self) -> Pubkey {
+        self.admin.key()
+    }
+}
@@ -0,0 +1,159 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token::{Mint, Token, TokenAccount, close_account, transfer, CloseAccount, Transfer},
+};
+
+use crate::{
+    seeds::{CHANNEL_SEED, CONFIG_SEED, MINT_SEED},
+    validation::{Checked, Policy, Role},
+    ChannelSettled, Config, PaymentChannel, StablecoinError,
+};
+
+#[derive(Accounts)]
+pub struct SettleChannel<'info> {
+    /// Whoever settles the channel, paying for token accounts either side
+    /// lacks
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// The config account
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump
+    )]
+    pub config: Box<Account<'info, Config>>,
+
+    /// The stablecoin mint
+    #[account(
+        seeds = [MINT_SEED],
+        bump = config.mint_bump
+    )]
+    pub mint: Box<Account<'info, Mint>>,
+
+    /// The closed channel, itself closed once settled
+    #[account(
+        mut,
+        close = sender,
+        has_one = sender,
+        has_one = recipient,
+        seeds = [CHANNEL_SEED, sender.key().as_ref(), recipient.key().as_ref()],
+        bump = payment_channel.bump
+    )]
+    pub payment_channel: Account<'info, PaymentChannel>,
+
+    /// CHECK: The sender recorded in the channel, who gets the rest of the
+    /// deposit and the rent back
+    #[account(mut)]
+    pub sender: UncheckedAccount<'info>,
+
+    /// CHECK: The recipient recorded in the channel
+    pub recipient: UncheckedAccount<'info>,
+
+    /// Channel custody, closed once emptied
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = payment_channel,
+    )]
+    pub custody: Box<Account<'info, TokenAccount>>,
+
+    /// The sender's token account
+    #[account(
+        init_if_needed,
+        payer = payer,
+        associated_token::mint = mint,
+        associated_token::authority = sender,
+    )]
+    pub sender_token_account: Box<Account<'info, TokenAccount>>,
+
+    /// The recipient's token account
+    #[account(
+        init_if_needed,
+        payer = payer,
+        associated_token::mint = mint,
+        associated_token::authority = recipient,
+    )]
+    pub recipient_token_account: Box<Account<'info, TokenAccount>>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+impl SettleChannel<'_> {
+    /// Pay the recipient the highest balance update and refund the sender
+    /// the rest; the channel is closed by its `close` constraint once this
+    /// returns
+    pub fn settle_channel(&mut self, _: Checked<Self>) -> Result<()> {
+        let payment_channel = &self.payment_channel;
+        require!(
+            payment_channel.closing() && Clock::get()?.unix_timestamp >= payment_channel.closes_at,
+            StablecoinError::ChannelDisputeActive
+        );
+
+        // Create the signer seeds for the custody owner PDA
+        let signer_seeds: &[&[&[u8]]] = &[&[
+            CHANNEL_SEED,
+            payment_channel.sender.as_ref(),
+            payment_channel.recipient.as_ref(),
+            &[payment_channel.bump],
+        ]];
+
+        // Anything sent to custody besides the deposit goes back too
+        let amount = payment_channel.amount;
+        let refund = self.custody.amount.checked_sub(amount).ok_or(StablecoinError::Overflow)?;
+        for (to, amount) in [(&self.recipient_token_account, amount), (&self.sender_token_account, refund)] {
+            if amount == 0 {
+                continue;
+            }
+            transfer(
+                CpiContext::new_with_signer(
+                    self.token_program.key(),
+                    Transfer {
+                        from: self.custody.to_account_info(),
+                        to: to.to_account_info(),
+                        authority: self.payment_channel.to_account_info(),
+                    },
+                    signer_seeds,
+                ),
+                amount,
+            )?;
+        }
+        close_account(CpiContext::new_with_signer(
+            self.token_program.key(),
+            CloseAccount {
+                account: self.custody.to_account_info(),
+                destination: self.sender.to_account_info(),
+                authority: self.payment_channel.to_account_info(),
+            },
+            signer_seeds,
+        ))?;
+
+        msg!("Settled payment channel from {} to {}: {} paid, {} refunded", payment_channel.sender, payment_channel.recipient, amount, refund);
+
+        emit!(ChannelSettled {
+            sender: payment_channel.sender,
+            recipient: payment_channel.recipient,
+            amount,
+            refund,
+        });
+
+        Ok(())
+    }
+}
+
+/// Settlement only pays out what was submitted, so anyone can run it once
+/// the dispute period ends
+impl Policy for SettleChannel<'_> {
+    const ROLE: Role = Role::Anyone;
+    const PAUSABLE: bool = false;
+
+    fn config(&self) -> Option<&Config> {
+        Some(&self.config)
+    }
+
+    fn signer(&self) -> Pubkey {
+        self.payer.key()
+    }
+}
@@ -0,0 +1,177 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::get_associated_token_address,
+    token::{Mint, Token, TokenAccount, close_account, transfer, CloseAccount, Transfer},
+};
+
+use crate::{
+    seeds::{CONFIG_SEED, MINT_SEED, NETTING_SEED},
+    validation::{Checked, Policy, Role},
+    Config, NetSettled, NettingWindow, StablecoinError,
+};
+
+#[derive(Accounts)]
+pub struct SettleNet<'info> {
+    /// Whoever settles the window; pays only the fee
+    pub payer: Signer<'info>,
+
+    /// The config account
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump
+    )]
+    pub config: Box<Account<'info, Config>>,
+
+    /// The stablecoin mint
+    #[account(
+        seeds = [MINT_SEED],
+        bump = config.mint_bump
+    )]
+    pub mint: Box<Account<'info, Mint>>,
+
+    /// The closed window, itself closed once settled
+    #[account(
+        mut,
+        close = opened_by,
+        has_one = opened_by,
+        seeds = [NETTING_SEED, netting_window.id.to_le_bytes().as_ref()],
+        bump = netting_window.bump
+    )]
+    pub netting_window: Account<'info, NettingWindow>,
+
+    /// CHECK: The admin that opened the window, who gets its rent back
+    #[account(mut)]
+    pub opened_by: UncheckedAccount<'info>,
+
+    /// Window custody, which holds the escrowed net debits
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = netting_window,
+    )]
+    pub custody: Box<Account<'info, TokenAccount>>,
+
+    pub token_program: Program<'info, Token>,
+    // Remaining accounts: each institution's associated token account, in
+    // the window's order, writable
+}
+
+impl<'info> SettleNet<'info> {
+    /// Pay each institution its escrow adjusted by its net position; the
+    /// window is closed by its `close` constraint once this returns
+    pub fn settle_net(
+        &mut self,
+        _: Checked<Self>,
+        token_accounts: &[AccountInfo<'info>],
+    ) -> Result<()> {
+        let netting_window = &self.netting_window;
+        require!(
+            Clock::get()?.unix_timestamp >= netting_window.closes_at,
+            StablecoinError::NettingWindowOpen
+        );
+
+        pay_out(
+            netting_window,
+            &self.mint.key(),
+            &self.custody,
+            &self.opened_by,
+            &self.token_program,
+            token_accounts,
+            &netting_window.payouts(),
+        )?;
+
+        let gross = netting_window.gross();
+        let net = netting_window.net();
+        msg!("Settled netting window {}: {} gross as {} net", netting_window.id, gross, net);
+
+        emit!(NetSettled {
+            id: netting_window.id,
+            gross,
+            net,
+        });
+
+        Ok(())
+    }
+}
+
+/// Pay `amounts[i]` from window custody to `token_accounts[i]`, which must
+/// be the institutions' associated token accounts in the window's order,
+/// then close custody to `opened_by` if nothing is left in it
+///
+/// Settling and cancelling a window both end this way.
+pub(crate) fn pay_out<'info>(
+    netting_window: &Account<'info, NettingWindow>,
+    mint: &Pubkey,
+    custody: &Account<'info, TokenAccount>,
+    opened_by: &AccountInfo<'info>,
+    token_program: &Program<'info, Token>,
+    token_accounts: &[AccountInfo<'info>],
+    amounts: &[u64],
+) -> Result<()> {
+    require!(
+        token_accounts.len() == netting_window.institutions.len()
+            && netting_window
+                .institutions
+                .iter()
+                .zip(token_accounts)
+                .all(|(institution, token_account)| {
+                    token_account.key() == get_associated_token_address(institution, mint)
+                }),
+        StablecoinError::InvalidSettlementAccounts
+    );
+
+    // Create the signer seeds for the custody owner PDA
+    let id = netting_window.id.to_le_bytes();
+    let signer_seeds: &[&[&[u8]]] = &[&[NETTING_SEED, &id, &[netting_window.bump]]];
+
+    let mut paid: u64 = 0;
+    for (token_account, amount) in token_accounts.iter().zip(amounts) {
+        if *amount == 0 {
+            continue;
+        }
+        transfer(
+            CpiContext::new_with_signer(
+                token_program.key(),
+                Transfer {
+                    from: custody.to_account_info(),
+                    to: token_account.clone(),
+                    authority: netting_window.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            *amount,
+        )?;
+        paid = paid.checked_add(*amount).ok_or(StablecoinError::Overflow)?;
+    }
+
+    // Tokens sent to custody from outside the window stay there, and so
+    // does its rent
+    if custody.amount == paid {
+        close_account(CpiContext::new_with_signer(
+            token_program.key(),
+            CloseAccount {
+                account: custody.to_account_info(),
+                destination: opened_by.clone(),
+                authority: netting_window.to_account_info(),
+            },
+            signer_seeds,
+        ))?;
+    }
+
+    Ok(())
+}
+
+/// Settlement only moves what institutions escrowed, so anyone can run it
+/// once the window closes
+impl Policy for SettleNet<'_> {
+    const ROLE: Role = Role::Anyone;
+    const PAUSABLE: bool = false;
+
+    fn config(&self) -> Option<&Config> {
+        Some(&self.config)
+    }
+
+    fn signer(&self) -> Pubkey {
+        self.payer.key()
+    }
+}
@@ -0,0 +1,103 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Mint, TokenAccount};
+
+use crate::{
+    seeds::{CONFIG_SEED, DELEGATE_SEED, INHERITANCE_SEED, MINT_SEED},
+    validation::{self, Checked, Policy, Role},
+    Config, InheritanceClaimStarted, InheritancePlan, StablecoinError,
+};
+
+#[derive(Accounts)]
+pub struct StartInheritanceClaim<'info> {
+    /// The holder's beneficiary
+    #[account(
+        constraint = validation::is_beneficiary(&beneficiary.key(), &inheritance_plan) @ StablecoinError::NotBeneficiary
+    )]
+    pub beneficiary: Signer<'info>,
+
+    /// The plan of the holder whose balance is claimed
+    #[account(
+        mut,
+        seeds = [INHERITANCE_SEED, inheritance_plan.owner.as_ref()],
+        bump = inheritance_plan.bump
+    )]
+    pub inheritance_plan: Account<'info, InheritancePlan>,
+
+    /// The config account
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump
+    )]
+    pub config: Box<Account<'info, Config>>,
+
+    /// The stablecoin mint
+    #[account(
+        seeds = [MINT_SEED],
+        bump = config.mint_bump
+    )]
+    pub mint: Box<Account<'info, Mint>>,
+
+    /// CHECK: The holder, as the plan records
+    #[account(address = inheritance_plan.owner)]
+    pub owner: UncheckedAccount<'info>,
+
+    /// The holder's token account, which must still delegate its balance
+    #[account(
+        associated_token::mint = mint,
+        associated_token::authority = owner,
+    )]
+    pub token_account: Box<Account<'info, TokenAccount>>,
+
+    /// CHECK: The program's transfer delegate, a PDA without data
+    #[account(
+        seeds = [DELEGATE_SEED],
+        bump = config.delegate_bump
+    )]
+    pub delegate: UncheckedAccount<'info>,
+}
+
+impl StartInheritanceClaim<'_> {
+    pub fn start_inheritance_claim(&mut self, _: Checked<Self>) -> Result<()> {
+        let inheritance_plan = &mut self.inheritance_plan;
+        require!(!inheritance_plan.claim_in_progress(), StablecoinError::InheritanceClaimInProgress);
+
+        let clock = Clock::get()?;
+        require!(inheritance_plan.check_in_lapsed_at(clock.epoch), StablecoinError::HolderStillActive);
+        // Don't open a notice period on a claim that could never complete
+        require!(
+            validation::delegates_balance(&self.token_account, &self.delegate.key()),
+            StablecoinError::DelegateNotApproved
+        );
+        let claimable_at = clock.unix_timestamp
+            .checked_add(inheritance_plan.notice_period)
+            .ok_or(StablecoinError::Overflow)?;
+        inheritance_plan.claim_started_at = clock.unix_timestamp;
+
+        msg!("Inheritance claim on {} started by {}", inheritance_plan.owner, inheritance_plan.beneficiary);
+
+        emit!(InheritanceClaimStarted {
+            owner: inheritance_plan.owner,
+            beneficiary: inheritance_plan.beneficiary,
+            claimable_at,
+        });
+
+        Ok(())
+    }
+}
+
+impl Policy for StartInheritanceClaim<'_> {
+    const ROLE: Role = Role::Beneficiary;
+    const PAUSABLE: bool = false;
+
+    fn config(&self) -> Option<&Config> {
+        Some(&self.config)
+    }
+
+    fn signer(&self) -> Pubkey {
+        self.beneficiary.key()
+    }
+
+    fn inheritance_plan(&self) -> Option<&InheritancePlan> {
+        Some(&self.inheritance_plan)
+    }
+}
@@ -0,0 +1,104 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Mint, Token, TokenAccount, close_account, transfer, CloseAccount, Transfer};
+
+use crate::{
+    seeds::{CONFIG_SEED, MINT_SEED},
+    validation::{Checked, Policy, Role},
+    Config, StablecoinError,
+};
+
+#[derive(Accounts)]
+pub struct SweepDust<'info> {
+    /// The owner of every account swept, who gets their rent back
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    /// The config account
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump
+    )]
+    pub config: Box<Account<'info, Config>>,
+
+    /// The stablecoin mint
+    #[account(
+        seeds = [MINT_SEED],
+        bump = config.mint_bump
+    )]
+    pub mint: Box<Account<'info, Mint>>,
+
+    /// The owner's associated token account, which receives the dust
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = owner,
+    )]
+    pub destination: Box<Account<'info, TokenAccount>>,
+
+    pub token_program: Program<'info, Token>,
+    // Remaining accounts: the owner's token accounts to sweep, writable
+}
+
+impl<'info> SweepDust<'info> {
+    /// Move each of `sources` into the destination and close it
+    pub fn sweep_dust(
+        &mut self,
+        _: Checked<Self>,
+        threshold: u64,
+        sources: &[AccountInfo<'info>],
+    ) -> Result<()> {
+        let mut swept: u64 = 0;
+        for source in sources {
+            // Fails for anything but a token account, including one an
+            // earlier entry already closed
+            require_keys_eq!(*source.owner, Token::id(), ErrorCode::AccountOwnedByWrongProgram);
+            let account = TokenAccount::try_deserialize(&mut &source.try_borrow_data()?[..])?;
+            require_keys_eq!(account.mint, self.mint.key(), ErrorCode::ConstraintTokenMint);
+            require_keys_eq!(account.owner, self.owner.key(), ErrorCode::ConstraintTokenOwner);
+            require_keys_neq!(source.key(), self.destination.key(), ErrorCode::ConstraintDuplicateMutableAccount);
+            require!(account.amount <= threshold, StablecoinError::NotDust);
+
+            if account.amount > 0 {
+                transfer(
+                    CpiContext::new(
+                        self.token_program.key(),
+                        Transfer {
+                            from: source.clone(),
+                            to: self.destination.to_account_info(),
+                            authority: self.owner.to_account_info(),
+                        },
+                    ),
+                    account.amount,
+                )?;
+            }
+            close_account(CpiContext::new(
+                self.token_program.key(),
+                CloseAccount {
+                    account: source.clone(),
+                    destination: self.owner.to_account_info(),
+                    authority: self.owner.to_account_info(),
+                },
+            ))?;
+
+            swept = swept.checked_add(account.amount).ok_or(StablecoinError::Overflow)?;
+        }
+
+        msg!("Swept {} tokens from {} accounts into {}", swept, sources.len(), self.destination.key());
+
+        Ok(())
+    }
+}
+
+/// Holders can always move what they own; SPL Token checks the owner signed
+impl Policy for SweepDust<'_> {
+    const ROLE: Role = Role::Anyone;
+    const PAUSABLE: bool = false;
+
+    fn config(&self) -> Option<&Config> {
+        Some(&self.config)
+    }
+
+    fn signer(&self) -> Pubkey {
+        self.owner.key()
+    }
+}
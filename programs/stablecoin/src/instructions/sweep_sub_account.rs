@@ -0,0 +1,107 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Mint, Token, TokenAccount, transfer, Transfer};
+
+use crate::{
+    seeds::{CONFIG_SEED, MINT_SEED, SUB_ACCOUNT_SEED},
+    validation::{Checked, Policy, Role},
+    Config, SubAccount, SubAccountSwept,
+};
+
+#[derive(Accounts)]
+pub struct SweepSubAccount<'info> {
+    /// The sub-account's owner
+    pub owner: Signer<'info>,
+
+    /// The config account
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump
+    )]
+    pub config: Box<Account<'info, Config>>,
+
+    /// The stablecoin mint
+    #[account(
+        seeds = [MINT_SEED],
+        bump = config.mint_bump
+    )]
+    pub mint: Box<Account<'info, Mint>>,
+
+    /// The sub-account to sweep
+    #[account(
+        has_one = owner,
+        seeds = [SUB_ACCOUNT_SEED, owner.key().as_ref(), &sub_account.index.to_le_bytes()],
+        bump = sub_account.bump
+    )]
+    pub sub_account: Account<'info, SubAccount>,
+
+    /// Sub-account custody
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = sub_account,
+    )]
+    pub custody: Box<Account<'info, TokenAccount>>,
+
+    /// The owner's associated token account, which receives the balance
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = owner,
+    )]
+    pub destination: Box<Account<'info, TokenAccount>>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+impl SweepSubAccount<'_> {
+    /// Move custody's whole balance to the owner; custody stays open for
+    /// later deposits
+    pub fn sweep_sub_account(&mut self, _: Checked<Self>) -> Result<()> {
+        let sub_account = &self.sub_account;
+        let owner = sub_account.owner;
+        let index_bytes = sub_account.index.to_le_bytes();
+        // Create the signer seeds for the sub-account PDA
+        let signer_seeds: &[&[&[u8]]] = &[&[SUB_ACCOUNT_SEED, owner.as_ref(), &index_bytes, &[sub_account.bump]]];
+
+        let amount = self.custody.amount;
+        if amount > 0 {
+            transfer(
+                CpiContext::new_with_signer(
+                    self.token_program.key(),
+                    Transfer {
+                        from: self.custody.to_account_info(),
+                        to: self.destination.to_account_info(),
+                        authority: self.sub_account.to_account_info(),
+                    },
+                    signer_seeds,
+                ),
+                amount,
+            )?;
+        }
+
+        msg!("Swept {} tokens from sub-account {} of {}", amount, sub_account.index, owner);
+
+        emit!(SubAccountSwept {
+            owner,
+            index: sub_account.index,
+            amount,
+        });
+
+        Ok(())
+    }
+}
+
+/// Owners sweep their own sub-accounts; the sub-account's seeds bind it to
+/// the signer
+impl Policy for SweepSubAccount<'_> {
+    const ROLE: Role = Role::Anyone;
+    const PAUSABLE: bool = false;
+
+    fn config(&self) -> Option<&Config> {
+        Some(&self.config)
+    }
+
+    fn signer(&self) -> Pubkey {
+        self.owner.key()
+    }
+}
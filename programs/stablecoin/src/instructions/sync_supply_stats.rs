@@ -0,0 +1,77 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::Mint;
+
+use crate::{
+    seeds::{CONFIG_SEED, MINT_SEED, SUPPLY_STATS_SEED},
+    sizes,
+    validation::{Checked, Policy, Role},
+    Config, SupplyStats,
+};
+
+#[derive(Accounts)]
+pub struct SyncSupplyStats<'info> {
+    /// Whoever cranks the stats; pays the fee, and the rent when the stats
+    /// are created
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// The config account
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump
+    )]
+    pub config: Box<Account<'info, Config>>,
+
+    /// The stablecoin mint
+    #[account(
+        seeds = [MINT_SEED],
+        bump = config.mint_bump
+    )]
+    pub mint: Box<Account<'info, Mint>>,
+
+    /// The time-weighted supply, created here on a program deployed before
+    /// `initialize` created it
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = sizes::SUPPLY_STATS,
+        seeds = [SUPPLY_STATS_SEED],
+        bump
+    )]
+    pub supply_stats: Account<'info, SupplyStats>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl SyncSupplyStats<'_> {
+    pub fn sync_supply_stats(&mut self, _: Checked<Self>, bumps: &SyncSupplyStatsBumps) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let supply_stats = &mut self.supply_stats;
+        // Just created: accumulation starts now
+        if supply_stats.started_at == 0 {
+            supply_stats.updated_at = now;
+            supply_stats.started_at = now;
+            supply_stats.bump = bumps.supply_stats;
+        }
+        supply_stats.record(self.mint.supply, now)?;
+
+        msg!("Synced supply stats at supply {}", self.mint.supply);
+
+        Ok(())
+    }
+}
+
+/// Recording the mint's own supply can only make the stats more accurate,
+/// so anyone can do it, paused or not
+impl Policy for SyncSupplyStats<'_> {
+    const ROLE: Role = Role::Anyone;
+    const PAUSABLE: bool = false;
+
+    fn config(&self) -> Option<&Config> {
+        Some(&self.config)
+    }
+
+    fn signer(&self) -> Pubkey {
+        self.payer.key()
+    }
+}
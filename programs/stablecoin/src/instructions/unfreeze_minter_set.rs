@@ -0,0 +1,62 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    seeds::{CONFIG_SEED, MINTER_SET_FREEZE_SEED},
+    validation::{self, Checked, Policy, Role},
+    Config, MinterSetFreeze, MinterSetUnfrozen, StablecoinError,
+};
+
+#[derive(Accounts)]
+pub struct UnfreezeMinterSet<'info> {
+    /// Only the admin can unfreeze the minter set
+    #[account(
+        constraint = validation::is_admin(&admin.key(), &config) @ StablecoinError::Unauthorized
+    )]
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [CONFIG_SEED],
+        bump = config.bump
+    )]
+    pub config: Box<Account<'info, Config>>,
+
+    /// The freeze marker to close
+    #[account(
+        mut,
+        close = frozen_by,
+        has_one = frozen_by,
+        seeds = [MINTER_SET_FREEZE_SEED],
+        bump = minter_set_freeze.bump
+    )]
+    pub minter_set_freeze: Account<'info, MinterSetFreeze>,
+
+    /// CHECK: The admin that froze the set, who gets the marker's rent back
+    #[account(mut)]
+    pub frozen_by: UncheckedAccount<'info>,
+}
+
+impl UnfreezeMinterSet<'_> {
+    /// The marker is closed by its `close` constraint once this returns
+    pub fn unfreeze_minter_set(&mut self, _: Checked<Self>) -> Result<()> {
+        msg!("Minter set unfrozen");
+
+        emit!(MinterSetUnfrozen {
+            admin: self.admin.key(),
+        });
+
+        Ok(())
+    }
+}
+
+impl Policy for UnfreezeMinterSet<'_> {
+    const ROLE: Role = Role::Admin;
+    const PAUSABLE: bool = false;
+
+    fn config(&self) -> Option<&Config> {
+        Some(&self.config)
+    }
+
+    fn signer(&self) -> Pubkey {
+        self.admin.key()
+    }
+}
@@ -0,0 +1,48 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    seeds::CONFIG_SEED,
+    validation::{self, Checked, Policy, Role},
+    Config, StablecoinError, Unpaused,
+};
+
+#[derive(Accounts)]
+pub struct Unpause<'info> {
+    /// Only the admin can unpause
+    #[account(
+        constraint = validation::is_admin(&admin.key(), &config) @ StablecoinError::Unauthorized
+    )]
+    pub admin: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [CONFIG_SEED],
+        bump = config.bump
+    )]
+    pub config: Box<Account<'info, Config>>,
+}
+
+impl Unpause<'_> {
+    pub fn unpause(&mut self, _: Checked<Self>) -> Result<()> {
+        self.config.paused = false;
+        msg!("Stablecoin unpaused");
+        emit!(Unpaused {
+            admin: self.admin.key(),
+        });
+        Ok(())
+    }
+}
+
+/// Pausing never stops the admin from undoing or repeating a pause
+impl Policy for Unpause<'_> {
+    const ROLE: Role = Role::Admin;
+    const PAUSABLE: bool = false;
+
+    fn config(&self) -> Option<&Config> {
+        Some(&self.config)
+    }
+
+    fn signer(&self) -> Pubkey {
+        self.admin.key()
+    }
+}
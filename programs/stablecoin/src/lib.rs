@@ -1,394 +1,579 @@
 use anchor_lang::prelude::*;
-use anchor_spl::{
-    associated_token::AssociatedToken,
-    token::{Mint, Token, TokenAccount, mint_to, burn, MintTo, Burn},
-};
 
 declare_id!("2hFkP8rkdPzyMsjsp5AddPyfpu1aY69qkjXf1Xd97b6K");
 
+pub mod errors;
+pub mod events;
+pub mod instructions;
+pub mod state;
+pub mod validation;
+
+pub use errors::*;
+pub use events::*;
+pub use instructions::*;
+pub use state::*;
+
+/// PDA seeds, for integrators deriving the program's addresses
+pub mod seeds {
+    use super::*;
+
+    /// Seed of the config PDA, which is also the mint authority
+    #[constant]
+    pub const CONFIG_SEED: &[u8] = b"config";
+
+    /// Seed of the mint PDA
+    #[constant]
+    pub const MINT_SEED: &[u8] = b"mint";
+
+    /// Seed prefix of a minter's config PDA, followed by the minter's address
+    #[constant]
+    pub const MINTER_SEED: &[u8] = b"minter";
+
+    /// Seed prefix of an idempotency record PDA, followed by the minter's
+    /// address and the 16-byte key
+    #[constant]
+    pub const IDEMPOTENCY_SEED: &[u8] = b"idempotency";
+
+    /// Seed of the treasury PDA, which also owns treasury custody
+    #[constant]
+    pub const TREASURY_SEED: &[u8] = b"treasury";
+
+    /// Seed prefix of a pending treasury burn PDA, followed by its id as
+    /// little-endian bytes
+    #[constant]
+    pub const PENDING_BURN_SEED: &[u8] = b"pending_burn";
+
+    /// Seed prefix of a holder's recovery config PDA, followed by the
+    /// holder's address
+    #[constant]
+    pub const RECOVERY_SEED: &[u8] = b"recovery";
+
+    /// Seed of the delegate PDA, which holders opting into recovery or
+    /// inheritance approve on their token account
+    #[constant]
+    pub const DELEGATE_SEED: &[u8] = b"delegate";
+
+    /// Seed prefix of a holder's inheritance plan PDA, followed by the
+    /// holder's address
+    #[constant]
+    pub const INHERITANCE_SEED: &[u8] = b"inheritance";
+
+    /// Seed of the supply stats PDA
+    #[constant]
+    pub const SUPPLY_STATS_SEED: &[u8] = b"supply_stats";
+
+    /// Seed prefix of a netting window PDA, followed by its id as
+    /// little-endian bytes
+    #[constant]
+    pub const NETTING_SEED: &[u8] = b"netting";
+
+    /// Seed prefix of a payment channel PDA, followed by the sender's and
+    /// the recipient's addresses; the PDA also owns the channel's custody
+    #[constant]
+    pub const CHANNEL_SEED: &[u8] = b"channel";
+
+    /// Seed prefix of a channel counter PDA, followed by the sender's and
+    /// the recipient's addresses
+    #[constant]
+    pub const CHANNEL_COUNTER_SEED: &[u8] = b"channel_counter";
+
+    /// Seed prefix of a registered purpose code PDA, followed by the code's
+    /// four bytes
+    #[constant]
+    pub const PURPOSE_SEED: &[u8] = b"purpose";
+
+    /// Seed of the minter set freeze PDA, which exists only while the set
+    /// is frozen
+    #[constant]
+    pub const MINTER_SET_FREEZE_SEED: &[u8] = b"minter_set_freeze";
+
+    /// Seed prefix of a minter's pause PDA, followed by the minter's
+    /// address; the PDA exists only while the minter has paused itself
+    #[constant]
+    pub const MINTER_PAUSE_SEED: &[u8] = b"minter_pause";
+
+    /// Seed prefix of a removed minter's tombstone PDA, followed by the
+    /// minter's address
+    #[constant]
+    pub const MINTER_TOMBSTONE_SEED: &[u8] = b"minter_tombstone";
+
+    /// Seed prefix of a sub-account PDA, followed by the owner's address and
+    /// the index as little-endian bytes; the PDA also owns the sub-account's
+    /// custody
+    #[constant]
+    pub const SUB_ACCOUNT_SEED: &[u8] = b"sub_account";
+
+    /// Seed prefix of an airdrop PDA, followed by the funder's address and
+    /// the id as little-endian bytes; the PDA also owns the airdrop's
+    /// custody
+    #[constant]
+    pub const AIRDROP_SEED: &[u8] = b"airdrop";
+
+    /// Seed prefix of a wallet's faucet claim PDA, followed by the wallet's
+    /// address; devnet builds only
+    #[cfg(feature = "devnet-faucet")]
+    #[constant]
+    pub const FAUCET_SEED: &[u8] = b"faucet";
+}
+
+/// Account sizes in bytes, discriminator included
+pub mod sizes {
+    use super::*;
+
+    pub const CONFIG: usize = Config::DISCRIMINATOR.len() + Config::INIT_SPACE;
+
+    pub const MINTER_CONFIG: usize = MinterConfig::DISCRIMINATOR.len() + MinterConfig::INIT_SPACE;
+
+    pub const IDEMPOTENCY_RECORD: usize = IdempotencyRecord::DISCRIMINATOR.len() + IdempotencyRecord::INIT_SPACE;
+
+    pub const TREASURY: usize = Treasury::DISCRIMINATOR.len() + Treasury::INIT_SPACE;
+
+    pub const PENDING_BURN: usize = PendingBurn::DISCRIMINATOR.len() + PendingBurn::INIT_SPACE;
+
+    pub const RECOVERY_CONFIG: usize = RecoveryConfig::DISCRIMINATOR.len() + RecoveryConfig::INIT_SPACE;
+
+    pub const INHERITANCE_PLAN: usize = InheritancePlan::DISCRIMINATOR.len() + InheritancePlan::INIT_SPACE;
+
+    pub const SUPPLY_STATS: usize = SupplyStats::DISCRIMINATOR.len() + SupplyStats::INIT_SPACE;
+
+    pub const NETTING_WINDOW: usize = NettingWindow::DISCRIMINATOR.len() + NettingWindow::INIT_SPACE;
+
+    pub const PAYMENT_CHANNEL: usize = PaymentChannel::DISCRIMINATOR.len() + PaymentChannel::INIT_SPACE;
+
+    pub const CHANNEL_COUNTER: usize = ChannelCounter::DISCRIMINATOR.len() + ChannelCounter::INIT_SPACE;
+
+    pub const PURPOSE_CODE: usize = PurposeCode::DISCRIMINATOR.len() + PurposeCode::INIT_SPACE;
+
+    pub const MINTER_SET_FREEZE: usize = MinterSetFreeze::DISCRIMINATOR.len() + MinterSetFreeze::INIT_SPACE;
+
+    pub const MINTER_PAUSE: usize = MinterPause::DISCRIMINATOR.len() + MinterPause::INIT_SPACE;
+
+    pub const MINTER_TOMBSTONE: usize = MinterTombstone::DISCRIMINATOR.len() + MinterTombstone::INIT_SPACE;
+
+    pub const SUB_ACCOUNT: usize = SubAccount::DISCRIMINATOR.len() + SubAccount::INIT_SPACE;
+
+    pub const AIRDROP: usize = Airdrop::DISCRIMINATOR.len() + Airdrop::INIT_SPACE;
+
+    #[cfg(feature = "devnet-faucet")]
+    pub const FAUCET_CLAIM: usize = FaucetClaim::DISCRIMINATOR.len() + FaucetClaim::INIT_SPACE;
+
+    /// The size of the account whose `data` this is, as this version of the
+    /// program lays out its type; `None` if the discriminator is not one of
+    /// the program's
+    pub fn of(data: &[u8]) -> Option<usize> {
+        let discriminator = data.get(..8)?;
+        #[cfg(feature = "devnet-faucet")]
+        if discriminator == FaucetClaim::DISCRIMINATOR {
+            return Some(FAUCET_CLAIM);
+        }
+        [
+            (Config::DISCRIMINATOR, CONFIG),
+            (MinterConfig::DISCRIMINATOR, MINTER_CONFIG),
+            (IdempotencyRecord::DISCRIMINATOR, IDEMPOTENCY_RECORD),
+            (Treasury::DISCRIMINATOR, TREASURY),
+            (PendingBurn::DISCRIMINATOR, PENDING_BURN),
+            (RecoveryConfig::DISCRIMINATOR, RECOVERY_CONFIG),
+            (InheritancePlan::DISCRIMINATOR, INHERITANCE_PLAN),
+            (SupplyStats::DISCRIMINATOR, SUPPLY_STATS),
+            (NettingWindow::DISCRIMINATOR, NETTING_WINDOW),
+            (PaymentChannel::DISCRIMINATOR, PAYMENT_CHANNEL),
+            (ChannelCounter::DISCRIMINATOR, CHANNEL_COUNTER),
+            (PurposeCode::DISCRIMINATOR, PURPOSE_CODE),
+            (MinterSetFreeze::DISCRIMINATOR, MINTER_SET_FREEZE),
+            (MinterPause::DISCRIMINATOR, MINTER_PAUSE),
+            (MinterTombstone::DISCRIMINATOR, MINTER_TOMBSTONE),
+            (SubAccount::DISCRIMINATOR, SUB_ACCOUNT),
+            (Airdrop::DISCRIMINATOR, AIRDROP),
+        ]
+        .into_iter()
+        .find(|(known, _)| *known == discriminator)
+        .map(|(_, size)| size)
+    }
+}
+
 #[program]
 pub mod stablecoin {
     use super::*;
 
-    /// Initialize the stablecoin mint and config
+    /// Initialize the stablecoin mint, config and supply stats
     /// This creates a new token mint with the program PDA as the mint authority
     pub fn initialize(ctx: Context<Initialize>) -> Result<()> {
-        let config = &mut ctx.accounts.config;
-        config.admin = ctx.accounts.admin.key();
-        config.mint = ctx.accounts.mint.key();
-        config.paused = false;
-        config.bump = ctx.bumps.config;
-        config.mint_bump = ctx.bumps.mint;
-
-        Ok(())
+        let checked = validation::check(&*ctx.accounts)?;
+        ctx.accounts.initialize(checked, &ctx.bumps)
     }
 
     /// Configure a minter with a specific allowance
     /// Only the admin can call this instruction
-    /// If the minter already exists, this updates their allowance
+    /// If the minter already exists, this updates their allowance; a removed
+    /// minter cannot be configured again
     pub fn configure_minter(ctx: Context<ConfigureMinter>, allowance: u64) -> Result<()> {
-        let minter_config = &mut ctx.accounts.minter_config;
-
-        // If not initialized, set the minter address
-        if !minter_config.is_initialized {
-            minter_config.minter = ctx.accounts.minter.key();
-            minter_config.amount_minted = 0;
-            minter_config.is_initialized = true;
-            minter_config.bump = ctx.bumps.minter_config;
-        }
-
-        minter_config.allowance = allowance;
+        let checked = validation::check(&*ctx.accounts)?;
+        ctx.accounts.configure_minter(checked, allowance, &ctx.bumps)
+    }
 
-        msg!("Configured minter {} with allowance {}", ctx.accounts.minter.key(), allowance);
+    /// Remove a minter's authorization
+    /// Only the admin can call this instruction
+    /// This closes the minter config account and returns rent to admin,
+    /// leaving a tombstone so the minter cannot be configured again
+    pub fn remove_minter(ctx: Context<RemoveMinter>) -> Result<()> {
+        let checked = validation::check(&*ctx.accounts)?;
+        ctx.accounts.remove_minter(checked, &ctx.bumps)
+    }
 
-        Ok(())
+    /// Freeze the minter set, so no minter can be added, removed or changed
+    /// until it is unfrozen; minting continues
+    /// Only the admin can call this instruction
+    pub fn freeze_minter_set(ctx: Context<FreezeMinterSet>) -> Result<()> {
+        let checked = validation::check(&*ctx.accounts)?;
+        ctx.accounts.freeze_minter_set(checked, &ctx.bumps)
     }
 
-    /// Remove a minter's authorization
+    /// Unfreeze the minter set, returning rent to the admin that froze it
     /// Only the admin can call this instruction
-    /// This closes the minter config account and returns rent to admin
-    pub fn remove_minter(_ctx: Context<RemoveMinter>) -> Result<()> {
-        msg!("Minter removed");
-        Ok(())
+    pub fn unfreeze_minter_set(ctx: Context<UnfreezeMinterSet>) -> Result<()> {
+        let checked = validation::check(&*ctx.accounts)?;
+        ctx.accounts.unfreeze_minter_set(checked)
     }
 
     /// Mint new stablecoins to a user
     /// Only authorized minters can call this instruction
     /// The minter must have sufficient allowance remaining
     pub fn mint_tokens(ctx: Context<MintTokens>, amount: u64) -> Result<()> {
-        let config = &ctx.accounts.config;
-
-        // Check not paused
-        require!(!config.paused, StablecoinError::Paused);
-
-        // Check and update minter allowance
-        let minter_config = &mut ctx.accounts.minter_config;
-        let remaining = minter_config.allowance.checked_sub(minter_config.amount_minted)
-            .ok_or(StablecoinError::ExceedsAllowance)?;
-        require!(amount <= remaining, StablecoinError::ExceedsAllowance);
-
-        minter_config.amount_minted = minter_config.amount_minted.checked_add(amount)
-            .ok_or(StablecoinError::Overflow)?;
+        let checked = validation::check(&*ctx.accounts)?;
+        ctx.accounts.mint_tokens(checked, amount)
+    }
 
-        // Create the signer seeds for the mint authority PDA
-        let signer_seeds: &[&[&[u8]]] = &[&[b"config", &[config.bump]]];
+    /// Mint like mint_tokens, at most once per client-supplied key
+    /// Creates an idempotency record for the minter and key; a second mint
+    /// with the same key fails while the record exists
+    pub fn mint_tokens_idempotent(ctx: Context<MintTokensIdempotent>, amount: u64, key: [u8; 16]) -> Result<()> {
+        let checked = validation::check(&*ctx.accounts)?;
+        ctx.accounts.mint_tokens_idempotent(checked, amount, key, &ctx.bumps)
+    }
 
-        // Mint tokens to the destination account
-        mint_to(
-            CpiContext::new_with_signer(
-                ctx.accounts.token_program.key(),
-                MintTo {
-                    mint: ctx.accounts.mint.to_account_info(),
-                    to: ctx.accounts.destination.to_account_info(),
-                    authority: ctx.accounts.config.to_account_info(),
-                },
-                signer_seeds,
-            ),
-            amount,
-        )?;
+    /// Close an expired idempotency record, returning its rent to the minter
+    /// Only the minter that created it can call this instruction
+    pub fn reclaim_idempotency_record(ctx: Context<ReclaimIdempotencyRecord>) -> Result<()> {
+        let checked = validation::check(&*ctx.accounts)?;
+        ctx.accounts.reclaim_idempotency_record(checked)
+    }
 
-        msg!("Minted {} tokens to {}", amount, ctx.accounts.destination.key());
+    /// Stop the caller's own minting at once, for a minter that suspects
+    /// its key is compromised
+    /// Only a minter can call this instruction, and only the admin can undo it
+    pub fn pause_self(ctx: Context<PauseSelf>) -> Result<()> {
+        let checked = validation::check(&*ctx.accounts)?;
+        ctx.accounts.pause_self(checked, &ctx.bumps)
+    }
 
-        Ok(())
+    /// Resume a minter that paused itself, returning rent to the minter
+    /// Only the admin can call this instruction
+    pub fn resume_minter(ctx: Context<ResumeMinter>) -> Result<()> {
+        let checked = validation::check(&*ctx.accounts)?;
+        ctx.accounts.resume_minter(checked)
     }
 
     /// Burn stablecoins from the caller's account
     /// Anyone can burn their own tokens
     /// In a real stablecoin, this would be called when users redeem for fiat
     pub fn burn_tokens(ctx: Context<BurnTokens>, amount: u64) -> Result<()> {
-        burn(
-            CpiContext::new(
-                ctx.accounts.token_program.key(),
-                Burn {
-                    mint: ctx.accounts.mint.to_account_info(),
-                    from: ctx.accounts.token_account.to_account_info(),
-                    authority: ctx.accounts.owner.to_account_info(),
-                },
-            ),
-            amount,
-        )?;
+        let checked = validation::check(&*ctx.accounts)?;
+        ctx.accounts.burn_tokens(checked, amount)
+    }
 
-        msg!("Burned {} tokens from {}", amount, ctx.accounts.token_account.key());
+    /// Move the caller's token accounts passed as remaining accounts into
+    /// their associated token account, closing each
+    /// Anyone can sweep their own accounts, each holding at most `threshold`
+    pub fn sweep_dust<'info>(ctx: Context<'_, '_, '_, 'info, SweepDust<'info>>, threshold: u64) -> Result<()> {
+        let checked = validation::check(&*ctx.accounts)?;
+        ctx.accounts.sweep_dust(checked, threshold, ctx.remaining_accounts)
+    }
 
-        Ok(())
+    /// Appoint the two treasury officers and the threshold above which a
+    /// treasury burn needs both
+    /// Only the admin can call this instruction
+    pub fn configure_treasury(ctx: Context<ConfigureTreasury>, officers: [Pubkey; 2], threshold: u64) -> Result<()> {
+        let checked = validation::check(&*ctx.accounts)?;
+        ctx.accounts.configure_treasury(checked, officers, threshold, &ctx.bumps)
     }
 
-    /// Pause all minting operations
+    /// Propose burning from treasury custody, approving it as the proposer
+    /// Only a treasury officer can call this instruction
+    pub fn propose_treasury_burn(ctx: Context<ProposeTreasuryBurn>, id: u64, amount: u64) -> Result<()> {
+        let checked = validation::check(&*ctx.accounts)?;
+        ctx.accounts.propose_treasury_burn(checked, id, amount, &ctx.bumps)
+    }
+
+    /// Approve a pending treasury burn
+    /// Only a treasury officer that has not approved it yet can call this instruction
+    pub fn approve_treasury_burn(ctx: Context<ApproveTreasuryBurn>) -> Result<()> {
+        let checked = validation::check(&*ctx.accounts)?;
+        ctx.accounts.approve_treasury_burn(checked)
+    }
+
+    /// Burn a pending treasury burn's amount from custody
+    /// Needs both officers' approvals above the threshold, one up to it
+    /// Closes the proposal and returns rent to the proposer
+    pub fn execute_treasury_burn(ctx: Context<ExecuteTreasuryBurn>) -> Result<()> {
+        let checked = validation::check(&*ctx.accounts)?;
+        ctx.accounts.execute_treasury_burn(checked)
+    }
+
+    /// Drop a pending treasury burn, returning rent to the proposer
+    /// Only a treasury officer can call this instruction
+    pub fn cancel_treasury_burn(ctx: Context<CancelTreasuryBurn>) -> Result<()> {
+        let checked = validation::check(&*ctx.accounts)?;
+        ctx.accounts.cancel_treasury_burn(checked)
+    }
+
+    /// Opt into social recovery, naming the guardians, how many must approve
+    /// and how long the holder has to cancel; approves the delegate PDA on
+    /// the caller's token account. Replaces earlier settings
+    pub fn configure_recovery(ctx: Context<ConfigureRecovery>, guardians: Vec<Pubkey>, quorum: u8, challenge_period: i64) -> Result<()> {
+        let checked = validation::check(&*ctx.accounts)?;
+        ctx.accounts.configure_recovery(checked, guardians, quorum, challenge_period, &ctx.bumps)
+    }
+
+    /// Start recovering a holder's balance to `new_owner`, approving it as
+    /// the initiator; the holder's token account must still delegate its
+    /// balance to the delegate PDA
+    /// Only one of the holder's guardians can call this instruction
+    pub fn initiate_recovery(ctx: Context<InitiateRecovery>, new_owner: Pubkey) -> Result<()> {
+        let checked = validation::check(&*ctx.accounts)?;
+        ctx.accounts.initiate_recovery(checked, new_owner)
+    }
+
+    /// Approve the recovery in progress
+    /// Only a guardian that has not approved it yet can call this instruction
+    pub fn approve_recovery(ctx: Context<ApproveRecovery>) -> Result<()> {
+        let checked = validation::check(&*ctx.accounts)?;
+        ctx.accounts.approve_recovery(checked)
+    }
+
+    /// Move the holder's whole balance to the new owner
+    /// Needs a quorum of guardians and the challenge period to have passed
+    pub fn execute_recovery(ctx: Context<ExecuteRecovery>) -> Result<()> {
+        let checked = validation::check(&*ctx.accounts)?;
+        ctx.accounts.execute_recovery(checked)
+    }
+
+    /// Drop the recovery in progress
+    /// Only the holder can call this instruction
+    pub fn cancel_recovery(ctx: Context<CancelRecovery>) -> Result<()> {
+        let checked = validation::check(&*ctx.accounts)?;
+        ctx.accounts.cancel_recovery(checked)
+    }
+
+    /// Opt out of social recovery, returning rent to the holder; revokes
+    /// the delegate PDA unless the holder has an inheritance plan
+    /// Only the holder can call this instruction
+    pub fn close_recovery(ctx: Context<CloseRecovery>) -> Result<()> {
+        let checked = validation::check(&*ctx.accounts)?;
+        ctx.accounts.close_recovery(checked)
+    }
+
+    /// Opt into inheritance, naming the beneficiary, how many epochs
+    /// without a check-in let them start a claim and how long the holder
+    /// then has to stop it; approves the delegate PDA on the caller's token
+    /// account and counts as a check-in. Replaces earlier settings
+    pub fn configure_inheritance(ctx: Context<ConfigureInheritance>, beneficiary: Pubkey, inactivity_epochs: u64, notice_period: i64) -> Result<()> {
+        let checked = validation::check(&*ctx.accounts)?;
+        ctx.accounts.configure_inheritance(checked, beneficiary, inactivity_epochs, notice_period, &ctx.bumps)
+    }
+
+    /// Check in, restarting the inactivity count and stopping any claim in
+    /// progress; transfers do not count, so wallets send this alongside
+    /// them
+    /// Only the holder can call this instruction
+    pub fn check_in(ctx: Context<CheckIn>) -> Result<()> {
+        let checked = validation::check(&*ctx.accounts)?;
+        ctx.accounts.check_in(checked)
+    }
+
+    /// Start a claim on the balance of a holder who has not checked in for
+    /// the plan's inactivity epochs, opening the notice period; the holder's token account must still delegate its balance
+    /// to the delegate PDA
+    /// Only the holder's beneficiary can call this instruction
+    pub fn start_inheritance_claim(ctx: Context<StartInheritanceClaim>) -> Result<()> {
+        let checked = validation::check(&*ctx.accounts)?;
+        ctx.accounts.start_inheritance_claim(checked)
+    }
+
+    /// Move the holder's whole balance to the beneficiary once the notice
+    /// period has passed
+    /// Only the holder's beneficiary can call this instruction
+    pub fn claim_inheritance(ctx: Context<ClaimInheritance>) -> Result<()> {
+        let checked = validation::check(&*ctx.accounts)?;
+        ctx.accounts.claim_inheritance(checked)
+    }
+
+    /// Opt out of inheritance, returning rent to the holder; revokes the
+    /// delegate PDA unless the holder has recovery settings
+    /// Only the holder can call this instruction
+    pub fn close_inheritance(ctx: Context<CloseInheritance>) -> Result<()> {
+        let checked = validation::check(&*ctx.accounts)?;
+        ctx.accounts.close_inheritance(checked)
+    }
+
+    /// Open a netting window between the given institutions, closing
+    /// `duration` seconds from now
     /// Only the admin can call this instruction
-    pub fn pause(ctx: Context<Pause>) -> Result<()> {
-        ctx.accounts.config.paused = true;
-        msg!("Stablecoin paused");
-        Ok(())
+    pub fn open_netting_window(ctx: Context<OpenNettingWindow>, id: u64, institutions: Vec<Pubkey>, duration: i64) -> Result<()> {
+        let checked = validation::check(&*ctx.accounts)?;
+        ctx.accounts.open_netting_window(checked, id, institutions, duration, &ctx.bumps)
     }
 
-    /// Unpause minting operations
+    /// Queue an obligation to `creditor` in an open netting window, with
+    /// the purpose of the payment if a registered purpose code is passed;
+    /// escrows whatever the caller's net debit grows by
+    /// Only an institution in the window can call this instruction
+    pub fn queue_obligation(ctx: Context<QueueObligation>, creditor: Pubkey, amount: u64) -> Result<()> {
+        let checked = validation::check(&*ctx.accounts)?;
+        ctx.accounts.queue_obligation(checked, creditor, amount)
+    }
+
+    /// Settle a closed netting window from what institutions escrowed,
+    /// passing their token accounts as remaining accounts
+    /// Anyone can call this instruction; closes the window and returns rent
+    /// to the admin that opened it
+    pub fn settle_net<'info>(ctx: Context<'_, '_, '_, 'info, SettleNet<'info>>) -> Result<()> {
+        let checked = validation::check(&*ctx.accounts)?;
+        ctx.accounts.settle_net(checked, ctx.remaining_accounts)
+    }
+
+    /// Drop a netting window without settling it, refunding what each
+    /// institution escrowed and passing their token accounts as remaining
+    /// accounts
+    /// Only the admin can call this instruction; returns rent to the admin
+    /// that opened the window
+    pub fn cancel_netting_window<'info>(ctx: Context<'_, '_, '_, 'info, CancelNettingWindow<'info>>) -> Result<()> {
+        let checked = validation::check(&*ctx.accounts)?;
+        ctx.accounts.cancel_netting_window(checked, ctx.remaining_accounts)
+    }
+
+    /// Register a purpose-of-payment code obligations can carry
     /// Only the admin can call this instruction
-    pub fn unpause(ctx: Context<Unpause>) -> Result<()> {
-        ctx.accounts.config.paused = false;
-        msg!("Stablecoin unpaused");
-        Ok(())
+    pub fn register_purpose_code(ctx: Context<RegisterPurposeCode>, code: [u8; 4]) -> Result<()> {
+        let checked = validation::check(&*ctx.accounts)?;
+        ctx.accounts.register_purpose_code(checked, code, &ctx.bumps)
     }
-}
 
-// ============================================================================
-// Account Structures
-// ============================================================================
-
-/// Config account that stores the stablecoin configuration
-#[account]
-#[derive(InitSpace)]
-pub struct Config {
-    /// The admin who can configure minters and blacklist accounts
-    pub admin: Pubkey,
-    /// The mint address of the stablecoin
-    pub mint: Pubkey,
-    /// Whether minting is paused
-    pub paused: bool,
-    /// Bump seed for the config PDA
-    pub bump: u8,
-    /// Bump seed for the mint PDA
-    pub mint_bump: u8,
-}
+    /// Retire a purpose code, returning rent to the admin that registered
+    /// it
+    /// Only the admin can call this instruction
+    pub fn retire_purpose_code(ctx: Context<RetirePurposeCode>) -> Result<()> {
+        let checked = validation::check(&*ctx.accounts)?;
+        ctx.accounts.retire_purpose_code(checked)
+    }
 
-/// Minter configuration account
-/// Each authorized minter has their own config with an allowance
-#[account]
-#[derive(InitSpace)]
-pub struct MinterConfig {
-    /// The minter's public key
-    pub minter: Pubkey,
-    /// Maximum amount the minter can mint (total)
-    pub allowance: u64,
-    /// Amount already minted by this minter
-    pub amount_minted: u64,
-    /// Whether this account has been initialized
-    pub is_initialized: bool,
-    /// Bump seed for this PDA
-    pub bump: u8,
-}
+    /// Open a payment channel to `recipient`, moving `deposit` into its
+    /// custody
+    /// The caller is the sender, who signs balance updates off-chain
+    pub fn open_channel(ctx: Context<OpenChannel>, recipient: Pubkey, deposit: u64, dispute_period: i64) -> Result<()> {
+        let checked = validation::check(&*ctx.accounts)?;
+        ctx.accounts.open_channel(checked, recipient, deposit, dispute_period, &ctx.bumps)
+    }
 
-// ============================================================================
-// Instruction Contexts
-// ============================================================================
-
-#[derive(Accounts)]
-pub struct Initialize<'info> {
-    #[account(mut)]
-    pub admin: Signer<'info>,
-
-    /// The config account that stores stablecoin settings
-    #[account(
-        init,
-        payer = admin,
-        space = 8 + Config::INIT_SPACE,
-        seeds = [b"config"],
-        bump
-    )]
-    pub config: Account<'info, Config>,
-
-    /// The stablecoin mint
-    /// The config PDA is set as both mint authority and freeze authority
-    #[account(
-        init,
-        payer = admin,
-        mint::decimals = 6,
-        mint::authority = config,
-        mint::freeze_authority = config,
-        seeds = [b"mint"],
-        bump
-    )]
-    pub mint: Account<'info, Mint>,
-
-    pub token_program: Program<'info, Token>,
-    pub system_program: Program<'info, System>,
-}
+    /// Submit a balance update paying `amount` in total, starting the
+    /// dispute period on the first; a higher amount needs the sender's
+    /// signature, verified by an Ed25519 program instruction right before
+    /// Only the channel's sender or recipient can call this instruction
+    pub fn close_channel(ctx: Context<CloseChannel>, amount: u64, signature: [u8; 64]) -> Result<()> {
+        let checked = validation::check(&*ctx.accounts)?;
+        ctx.accounts.close_channel(checked, amount, signature)
+    }
 
-#[derive(Accounts)]
-pub struct ConfigureMinter<'info> {
-    /// Only the admin can configure minters
-    #[account(
-        mut,
-        constraint = admin.key() == config.admin @ StablecoinError::Unauthorized
-    )]
-    pub admin: Signer<'info>,
-
-    #[account(
-        seeds = [b"config"],
-        bump = config.bump
-    )]
-    pub config: Account<'info, Config>,
-
-    /// The minter being configured
-    /// CHECK: This can be any account that will be authorized to mint
-    pub minter: UncheckedAccount<'info>,
-
-    /// The minter's configuration account
-    #[account(
-        init_if_needed,
-        payer = admin,
-        space = 8 + MinterConfig::INIT_SPACE,
-        seeds = [b"minter", minter.key().as_ref()],
-        bump
-    )]
-    pub minter_config: Account<'info, MinterConfig>,
-
-    pub system_program: Program<'info, System>,
-}
+    /// Pay the recipient the highest balance update submitted and refund
+    /// the sender the rest once the dispute period ends
+    /// Anyone can call this instruction; closes the channel and its custody
+    /// and returns rent to the sender
+    pub fn settle_channel(ctx: Context<SettleChannel>) -> Result<()> {
+        let checked = validation::check(&*ctx.accounts)?;
+        ctx.accounts.settle_channel(checked)
+    }
 
-#[derive(Accounts)]
-pub struct RemoveMinter<'info> {
-    /// Only the admin can remove minters
-    #[account(
-        mut,
-        constraint = admin.key() == config.admin @ StablecoinError::Unauthorized
-    )]
-    pub admin: Signer<'info>,
-
-    #[account(
-        seeds = [b"config"],
-        bump = config.bump
-    )]
-    pub config: Account<'info, Config>,
-
-    /// The minter being removed
-    /// CHECK: This is the minter whose config is being closed
-    pub minter: UncheckedAccount<'info>,
-
-    /// The minter's configuration account to close
-    #[account(
-        mut,
-        close = admin,
-        seeds = [b"minter", minter.key().as_ref()],
-        bump = minter_config.bump
-    )]
-    pub minter_config: Account<'info, MinterConfig>,
-}
+    /// Register sub-account `index` under a registered purpose code,
+    /// creating its custody for deposits
+    /// The caller owns the sub-account and pays its rent
+    pub fn register_sub_account(ctx: Context<RegisterSubAccount>, index: u64) -> Result<()> {
+        let checked = validation::check(&*ctx.accounts)?;
+        ctx.accounts.register_sub_account(checked, index, &ctx.bumps)
+    }
 
-#[derive(Accounts)]
-pub struct MintTokens<'info> {
-    /// The minter calling this instruction
-    #[account(mut)]
-    pub minter: Signer<'info>,
-
-    /// The config account
-    #[account(
-        seeds = [b"config"],
-        bump = config.bump
-    )]
-    pub config: Account<'info, Config>,
-
-    /// The minter's configuration - verifies they are authorized
-    #[account(
-        mut,
-        seeds = [b"minter", minter.key().as_ref()],
-        bump = minter_config.bump,
-        constraint = minter_config.is_initialized @ StablecoinError::NotMinter
-    )]
-    pub minter_config: Account<'info, MinterConfig>,
-
-    /// The stablecoin mint
-    #[account(
-        mut,
-        seeds = [b"mint"],
-        bump = config.mint_bump
-    )]
-    pub mint: Account<'info, Mint>,
-
-    /// The destination token account to mint to
-    #[account(
-        init_if_needed,
-        payer = minter,
-        associated_token::mint = mint,
-        associated_token::authority = destination_owner,
-    )]
-    pub destination: Account<'info, TokenAccount>,
-
-    /// CHECK: The owner of the destination token account
-    pub destination_owner: UncheckedAccount<'info>,
-
-    pub token_program: Program<'info, Token>,
-    pub associated_token_program: Program<'info, AssociatedToken>,
-    pub system_program: Program<'info, System>,
-}
+    /// Move a sub-account's whole balance to its owner's token account
+    /// Only the sub-account's owner can call this instruction
+    pub fn sweep_sub_account(ctx: Context<SweepSubAccount>) -> Result<()> {
+        let checked = validation::check(&*ctx.accounts)?;
+        ctx.accounts.sweep_sub_account(checked)
+    }
 
-#[derive(Accounts)]
-pub struct BurnTokens<'info> {
-    /// The owner of the token account burning tokens
-    pub owner: Signer<'info>,
-
-    /// The config account
-    #[account(
-        seeds = [b"config"],
-        bump = config.bump
-    )]
-    pub config: Account<'info, Config>,
-
-    /// The stablecoin mint
-    #[account(
-        mut,
-        seeds = [b"mint"],
-        bump = config.mint_bump
-    )]
-    pub mint: Account<'info, Mint>,
-
-    /// The token account to burn from
-    #[account(
-        mut,
-        associated_token::mint = mint,
-        associated_token::authority = owner,
-    )]
-    pub token_account: Account<'info, TokenAccount>,
-
-    pub token_program: Program<'info, Token>,
-}
+    /// Fund airdrop `id` with `amounts[i]` for each of `recipients[i]`,
+    /// claimable for `duration` seconds
+    /// Only a treasury officer can call this instruction; the caller funds
+    /// the airdrop and pays its rent
+    pub fn open_airdrop(
+        ctx: Context<OpenAirdrop>,
+        id: u64,
+        recipients: Vec<Pubkey>,
+        amounts: Vec<u64>,
+        duration: i64,
+    ) -> Result<()> {
+        let checked = validation::check(&*ctx.accounts)?;
+        ctx.accounts.open_airdrop(checked, id, recipients, amounts, duration, &ctx.bumps)
+    }
 
+    /// Claim the caller's amount from an airdrop before it closes
+    /// Only the airdrop's recipients can call this instruction
+    pub fn claim_airdrop(ctx: Context<ClaimAirdrop>) -> Result<()> {
+        let checked = validation::check(&*ctx.accounts)?;
+        ctx.accounts.claim_airdrop(checked)
+    }
 
-#[derive(Accounts)]
-pub struct Pause<'info> {
-    /// Only the admin can pause
-    #[account(
-        constraint = admin.key() == config.admin @ StablecoinError::Unauthorized
-    )]
-    pub admin: Signer<'info>,
-
-    #[account(
-        mut,
-        seeds = [b"config"],
-        bump = config.bump
-    )]
-    pub config: Account<'info, Config>,
-}
+    /// Move what is left of a closed airdrop to treasury custody
+    /// Anyone can call this instruction; closes the airdrop and its custody
+    /// and returns rent to the funder
+    pub fn clawback_airdrop(ctx: Context<ClawbackAirdrop>) -> Result<()> {
+        let checked = validation::check(&*ctx.accounts)?;
+        ctx.accounts.clawback_airdrop(checked)
+    }
 
-#[derive(Accounts)]
-pub struct Unpause<'info> {
-    /// Only the admin can unpause
-    #[account(
-        constraint = admin.key() == config.admin @ StablecoinError::Unauthorized
-    )]
-    pub admin: Signer<'info>,
-
-    #[account(
-        mut,
-        seeds = [b"config"],
-        bump = config.bump
-    )]
-    pub config: Account<'info, Config>,
-}
+    /// Grow one of the program's accounts to the size this version of the
+    /// program lays its type out at, topping up its rent from the payer
+    /// Only the admin can call this instruction
+    pub fn resize_account(ctx: Context<ResizeAccount>) -> Result<()> {
+        let checked = validation::check(&*ctx.accounts)?;
+        ctx.accounts.resize_account(checked)
+    }
+
+    /// Mint `amount` to the caller, up to a daily limit per wallet; only
+    /// built with the `devnet-faucet` feature, never for mainnet
+    /// Anyone can call this instruction
+    #[cfg(feature = "devnet-faucet")]
+    pub fn faucet(ctx: Context<Faucet>, amount: u64) -> Result<()> {
+        let checked = validation::check(&*ctx.accounts)?;
+        ctx.accounts.faucet(checked, amount, &ctx.bumps)
+    }
 
-// ============================================================================
-// Error Codes
-// ============================================================================
-
-#[error_code]
-pub enum StablecoinError {
-    #[msg("You are not authorized to perform this action")]
-    Unauthorized,
-    #[msg("Minting is currently paused")]
-    Paused,
-    #[msg("Mint amount exceeds minter's remaining allowance")]
-    ExceedsAllowance,
-    #[msg("Account is not an authorized minter")]
-    NotMinter,
-    #[msg("Arithmetic overflow")]
-    Overflow,
+    /// Record the mint's current supply in the supply stats, counting burns
+    /// made directly through SPL Token from now on; creates the stats on a
+    /// program upgraded from a version without them
+    /// Anyone can call this instruction
+    pub fn sync_supply_stats(ctx: Context<SyncSupplyStats>) -> Result<()> {
+        let checked = validation::check(&*ctx.accounts)?;
+        ctx.accounts.sync_supply_stats(checked, &ctx.bumps)
+    }
+
+    /// Pause all minting operations
+    /// Only the admin can call this instruction
+    pub fn pause(ctx: Context<Pause>) -> Result<()> {
+        let checked = validation::check(&*ctx.accounts)?;
+        ctx.accounts.pause(checked)
+    }
+
+    /// Unpause minting operations
+    /// Only the admin can call this instruction
+    pub fn unpause(ctx: Context<Unpause>) -> Result<()> {
+        let checked = validation::check(&*ctx.accounts)?;
+        ctx.accounts.unpause(checked)
+    }
 }
@@ -0,0 +1,40 @@
+use anchor_lang::prelude::*;
+
+/// Most recipients one airdrop can pay
+#[constant]
+pub const MAX_AIRDROP_RECIPIENTS: u8 = 16;
+
+/// An airdrop its funder has paid into custody, claimable by each recipient
+/// until it closes
+///
+/// The PDA owns the airdrop's custody, its associated token account. Once
+/// the airdrop closes, anyone can claw what is left back to treasury
+/// custody, so unclaimed tokens never stay stranded.
+#[account]
+#[derive(InitSpace, Debug, PartialEq, Eq)]
+pub struct Airdrop {
+    /// The treasury officer that funded the airdrop, and paid for this
+    /// account
+    pub funder: Pubkey,
+    /// The funder's id for the airdrop; part of this PDA's seeds
+    pub id: u64,
+    /// Wallets that can claim from the airdrop
+    #[max_len(MAX_AIRDROP_RECIPIENTS)]
+    pub recipients: Vec<Pubkey>,
+    /// What each recipient has yet to claim, `unclaimed[i]` for
+    /// `recipients[i]`; zero once claimed
+    #[max_len(MAX_AIRDROP_RECIPIENTS)]
+    pub unclaimed: Vec<u64>,
+    /// Unix timestamp from which nothing more can be claimed and the rest
+    /// can be clawed back
+    pub closes_at: i64,
+    /// Bump seed for this PDA
+    pub bump: u8,
+}
+
+impl Airdrop {
+    /// Where `key` is among the recipients
+    pub fn recipient_index(&self, key: &Pubkey) -> Option<usize> {
+        self.recipients.iter().position(|recipient| recipient == key)
+    }
+}
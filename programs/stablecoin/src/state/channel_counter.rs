@@ -0,0 +1,15 @@
+use anchor_lang::prelude::*;
+
+/// Counts the channels a sender has opened to a recipient
+///
+/// Outlives each channel, which settlement closes, so every channel between
+/// the same wallets gets a nonce none before it had, and a balance update
+/// signed for one cannot be submitted to another.
+#[account]
+#[derive(InitSpace, Debug, PartialEq, Eq)]
+pub struct ChannelCounter {
+    /// Channels opened so far, the nonce of the next
+    pub opened: u64,
+    /// Bump seed for this PDA
+    pub bump: u8,
+}
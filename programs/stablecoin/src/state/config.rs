@@ -0,0 +1,48 @@
+use anchor_lang::prelude::*;
+
+use crate::seeds::{DELEGATE_SEED, MINTER_SET_FREEZE_SEED};
+
+/// Decimals of the stablecoin mint; a token is `10^DECIMALS` base units,
+/// and every amount the program takes or reports is in base units
+#[constant]
+pub const DECIMALS: u8 = 6;
+
+/// Config account that stores the stablecoin configuration
+#[account]
+#[derive(InitSpace, Debug, PartialEq, Eq)]
+pub struct Config {
+    /// The admin who can configure minters and blacklist accounts
+    pub admin: Pubkey,
+    /// The mint address of the stablecoin
+    pub mint: Pubkey,
+    /// Whether minting is paused
+    pub paused: bool,
+    /// Bump seed for the config PDA
+    pub bump: u8,
+    /// Bump seed for the mint PDA
+    pub mint_bump: u8,
+    /// Bump seed for the delegate PDA
+    pub delegate_bump: u8,
+    /// Bump seed for the minter set freeze marker
+    pub minter_set_freeze_bump: u8,
+}
+
+impl Config {
+    /// Derive the bumps of the PDAs with fixed seeds, once, so the
+    /// instructions that check or sign for them don't have to
+    pub fn derive_bumps(&mut self) {
+        self.delegate_bump = Pubkey::find_program_address(&[DELEGATE_SEED], &crate::ID).1;
+        self.minter_set_freeze_bump = Pubkey::find_program_address(&[MINTER_SET_FREEZE_SEED], &crate::ID).1;
+    }
+
+    /// The admin recorded in config account `data`, read without
+    /// deserializing the rest, so a config laid out by any version of the
+    /// program will do
+    pub fn admin_of(data: &[u8]) -> Option<Pubkey> {
+        if data.get(..Self::DISCRIMINATOR.len())? != Self::DISCRIMINATOR {
+            return None;
+        }
+        let admin = data.get(Self::DISCRIMINATOR.len()..Self::DISCRIMINATOR.len() + 32)?;
+        Some(Pubkey::try_from(admin).unwrap())
+    }
+}
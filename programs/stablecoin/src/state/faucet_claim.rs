@@ -0,0 +1,39 @@
+use anchor_lang::prelude::*;
+
+use crate::{StablecoinError, DECIMALS};
+
+/// Base units a wallet can take from the devnet faucet per UTC day
+#[constant]
+pub const FAUCET_DAILY_LIMIT: u64 = 1_000 * 10u64.pow(DECIMALS as u32);
+
+/// A wallet's faucet claims on its latest claim day, in devnet builds only
+#[account]
+#[derive(InitSpace, Debug, PartialEq, Eq)]
+pub struct FaucetClaim {
+    /// The wallet the faucet mints to
+    pub wallet: Pubkey,
+    /// The UTC day of the latest claim, in days since the Unix epoch
+    pub day: i64,
+    /// Base units claimed on `day`
+    pub claimed: u64,
+    /// Bump seed for this PDA
+    pub bump: u8,
+}
+
+impl FaucetClaim {
+    /// Record a claim of `amount` at `now`, starting over on a new UTC day,
+    /// and return what is left of the day's limit
+    pub fn claim(&mut self, amount: u64, now: i64) -> Result<u64> {
+        let day = now.div_euclid(24 * 60 * 60);
+        if day != self.day {
+            self.day = day;
+            self.claimed = 0;
+        }
+
+        let claimed = self.claimed.checked_add(amount).ok_or(StablecoinError::Overflow)?;
+        require!(claimed <= FAUCET_DAILY_LIMIT, StablecoinError::FaucetLimitExceeded);
+        self.claimed = claimed;
+
+        Ok(FAUCET_DAILY_LIMIT - claimed)
+    }
+}
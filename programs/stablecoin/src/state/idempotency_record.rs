@@ -0,0 +1,24 @@
+use anchor_lang::prelude::*;
+
+/// How long an idempotency record blocks its key before the minter can
+/// reclaim it, in seconds
+#[constant]
+pub const IDEMPOTENCY_KEY_TTL: i64 = 24 * 60 * 60;
+
+/// Record of an idempotent mint
+/// Its address is derived from the minter and the client's key, so a second
+/// mint with the same key finds it and fails
+#[account]
+#[derive(InitSpace, Debug, PartialEq, Eq)]
+pub struct IdempotencyRecord {
+    /// The minter that minted under the key, and paid for this account
+    pub minter: Pubkey,
+    /// The client-supplied key
+    pub key: [u8; 16],
+    /// Amount minted under the key
+    pub amount: u64,
+    /// Unix timestamp from which the record can be reclaimed
+    pub expires_at: i64,
+    /// Bump seed for this PDA
+    pub bump: u8,
+}
@@ -0,0 +1,49 @@
+use anchor_lang::prelude::*;
+
+/// Shortest notice period a holder can choose, in seconds; the window in
+/// which they can check in and stop a claim on their balance
+#[constant]
+pub const MIN_INHERITANCE_NOTICE_PERIOD: i64 = 30 * 24 * 60 * 60;
+
+/// A holder's opt-in inheritance plan, and the beneficiary's claim in
+/// progress if there is one
+///
+/// The plan is a check-in switch, not an activity monitor. Transfers go
+/// straight through SPL Token without calling this program, so it cannot
+/// see them, and a holder who spends every day but never checks in lapses
+/// all the same. Wallets keep an active holder covered by adding a
+/// `check_in` to the holder's transactions; the notice period is the last
+/// chance to check in once a claim starts.
+#[account]
+#[derive(InitSpace, Debug, PartialEq, Eq)]
+pub struct InheritancePlan {
+    /// The holder whose balance can be inherited
+    pub owner: Pubkey,
+    /// The wallet that can claim the balance
+    pub beneficiary: Pubkey,
+    /// Epochs without a check-in after which the beneficiary can start a
+    /// claim
+    pub inactivity_epochs: u64,
+    /// Seconds between starting a claim and completing it
+    pub notice_period: i64,
+    /// The epoch the holder last checked in, or configured the plan
+    pub last_check_in_epoch: u64,
+    /// Unix timestamp the claim in progress was started at; 0 when none is
+    /// in progress
+    pub claim_started_at: i64,
+    /// Bump seed for this PDA
+    pub bump: u8,
+}
+
+impl InheritancePlan {
+    /// Whether a claim is in progress
+    pub fn claim_in_progress(&self) -> bool {
+        self.claim_started_at != 0
+    }
+
+    /// Whether the holder has gone long enough without checking in by
+    /// `epoch` for the beneficiary to start a claim
+    pub fn check_in_lapsed_at(&self, epoch: u64) -> bool {
+        epoch.saturating_sub(self.last_check_in_epoch) >= self.inactivity_epochs
+    }
+}
@@ -0,0 +1,30 @@
+use anchor_lang::prelude::*;
+
+use crate::seeds::MINTER_PAUSE_SEED;
+
+/// Minter configuration account
+/// Each authorized minter has their own config with an allowance
+#[account]
+#[derive(InitSpace, Debug, PartialEq, Eq)]
+pub struct MinterConfig {
+    /// The minter's public key
+    pub minter: Pubkey,
+    /// Maximum amount the minter can mint (total)
+    pub allowance: u64,
+    /// Amount already minted by this minter
+    pub amount_minted: u64,
+    /// Whether this account has been initialized
+    pub is_initialized: bool,
+    /// Bump seed for this PDA
+    pub bump: u8,
+    /// Bump seed for the minter's pause marker
+    pub pause_bump: u8,
+}
+
+impl MinterConfig {
+    /// Derive the bump of the minter's pause marker, once, so minting
+    /// doesn't have to
+    pub fn derive_bumps(&mut self) {
+        self.pause_bump = Pubkey::find_program_address(&[MINTER_PAUSE_SEED, self.minter.as_ref()], &crate::ID).1;
+    }
+}
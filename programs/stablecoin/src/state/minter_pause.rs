@@ -0,0 +1,17 @@
+use anchor_lang::prelude::*;
+
+/// Marks a minter as having paused itself for as long as it exists
+///
+/// A minter that suspects its key is compromised creates this to stop its
+/// own minting at once. Only the admin can close it, so a stolen key cannot
+/// undo the pause.
+#[account]
+#[derive(InitSpace, Debug, PartialEq, Eq)]
+pub struct MinterPause {
+    /// The minter that paused itself, and paid for this account
+    pub minter: Pubkey,
+    /// Unix timestamp the minter paused at
+    pub paused_at: i64,
+    /// Bump seed for this PDA
+    pub bump: u8,
+}
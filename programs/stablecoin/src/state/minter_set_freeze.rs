@@ -0,0 +1,17 @@
+use anchor_lang::prelude::*;
+
+/// Marks the minter set as frozen for as long as it exists
+///
+/// While it does, minters cannot be added, removed or have their allowance
+/// changed, so auditors can rely on a static issuer set during an
+/// examination. Minters keep minting within the allowances they have.
+#[account]
+#[derive(InitSpace, Debug, PartialEq, Eq)]
+pub struct MinterSetFreeze {
+    /// The admin that froze the set, and paid for this account
+    pub frozen_by: Pubkey,
+    /// Unix timestamp the set was frozen at
+    pub frozen_at: i64,
+    /// Bump seed for this PDA
+    pub bump: u8,
+}
@@ -0,0 +1,22 @@
+use anchor_lang::prelude::*;
+
+/// Marks a minter as removed, for good
+///
+/// Removing a minter closes its config and leaves this in its place, so the
+/// config cannot be created again under the same key, by a replayed or
+/// long-signed `configure_minter` or otherwise. A minter the admin wants
+/// back gets a new key.
+#[account]
+#[derive(InitSpace, Debug, PartialEq, Eq)]
+pub struct MinterTombstone {
+    /// The removed minter
+    pub minter: Pubkey,
+    /// The admin that removed it, and paid for this account
+    pub removed_by: Pubkey,
+    /// Unix timestamp the minter was removed at
+    pub removed_at: i64,
+    /// What the minter had minted when it was removed
+    pub amount_minted: u64,
+    /// Bump seed for this PDA
+    pub bump: u8,
+}
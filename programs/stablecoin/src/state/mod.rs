@@ -0,0 +1,39 @@
+pub mod airdrop;
+pub mod channel_counter;
+pub mod config;
+#[cfg(feature = "devnet-faucet")]
+pub mod faucet_claim;
+pub mod idempotency_record;
+pub mod inheritance_plan;
+pub mod minter_config;
+pub mod minter_pause;
+pub mod minter_set_freeze;
+pub mod minter_tombstone;
+pub mod netting_window;
+pub mod payment_channel;
+pub mod pending_burn;
+pub mod purpose_code;
+pub mod recovery_config;
+pub mod sub_account;
+pub mod supply_stats;
+pub mod treasury;
+
+pub use airdrop::*;
+pub use channel_counter::*;
+pub use config::*;
+#[cfg(feature = "devnet-faucet")]
+pub use faucet_claim::*;
+pub use idempotency_record::*;
+pub use inheritance_plan::*;
+pub use minter_config::*;
+pub use minter_pause::*;
+pub use minter_set_freeze::*;
+pub use minter_tombstone::*;
+pub use netting_window::*;
+pub use payment_channel::*;
+pub use pending_burn::*;
+pub use purpose_code::*;
+pub use recovery_config::*;
+pub use sub_account::*;
+pub use supply_stats::*;
+pub use treasury::*;
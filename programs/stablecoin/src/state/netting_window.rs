@@ -0,0 +1,82 @@
+use anchor_lang::prelude::*;
+
+/// Most institutions a netting window can settle between
+#[constant]
+pub const MAX_NETTING_INSTITUTIONS: u8 = 8;
+
+/// A window in which registered institutions queue obligations to each
+/// other, settled by moving only each institution's net position
+///
+/// Queueing escrows the debtor's net debit in the window's custody, so
+/// settlement never depends on what institutions hold once the window
+/// closes.
+#[account]
+#[derive(InitSpace, Debug, PartialEq, Eq)]
+pub struct NettingWindow {
+    /// The admin's id for the window, part of this PDA's seeds
+    pub id: u64,
+    /// The admin that opened the window, and paid for this account
+    pub opened_by: Pubkey,
+    /// Institutions that can queue obligations in this window
+    #[max_len(MAX_NETTING_INSTITUTIONS)]
+    pub institutions: Vec<Pubkey>,
+    /// What each institution owes in total, `debits[i]` for
+    /// `institutions[i]`
+    #[max_len(MAX_NETTING_INSTITUTIONS)]
+    pub debits: Vec<u64>,
+    /// What each institution is owed in total, `credits[i]` for
+    /// `institutions[i]`
+    #[max_len(MAX_NETTING_INSTITUTIONS)]
+    pub credits: Vec<u64>,
+    /// What each institution has escrowed in the window's custody,
+    /// `escrowed[i]` for `institutions[i]`
+    #[max_len(MAX_NETTING_INSTITUTIONS)]
+    pub escrowed: Vec<u64>,
+    /// Unix timestamp from which no more obligations can be queued and the
+    /// window can be settled
+    pub closes_at: i64,
+    /// Bump seed for this PDA
+    pub bump: u8,
+}
+
+impl NettingWindow {
+    /// Where `key` is among the institutions
+    pub fn institution_index(&self, key: &Pubkey) -> Option<usize> {
+        self.institutions.iter().position(|institution| institution == key)
+    }
+
+    /// Sum of every obligation queued
+    pub fn gross(&self) -> u64 {
+        self.debits.iter().fold(0, |gross, debit| gross.saturating_add(*debit))
+    }
+
+    /// Sum of every net credit, what settlement moves between
+    /// institutions
+    pub fn net(&self) -> u64 {
+        self.credits
+            .iter()
+            .zip(&self.debits)
+            .fold(0, |net, (credit, debit)| net.saturating_add(credit.saturating_sub(*debit)))
+    }
+
+    /// What `institutions[index]` has yet to escrow to cover its net debit
+    pub fn shortfall(&self, index: usize) -> u64 {
+        self.debits[index]
+            .saturating_sub(self.credits[index])
+            .saturating_sub(self.escrowed[index])
+    }
+
+    /// What custody pays each institution to settle the window: its escrow,
+    /// less its net debit or plus its net credit
+    pub fn payouts(&self) -> Vec<u64> {
+        self.escrowed
+            .iter()
+            .zip(self.credits.iter().zip(&self.debits))
+            .map(|(escrowed, (credit, debit))| {
+                // Escrow covers every net debit, and the payouts add up to
+                // the escrow, so each fits a u64
+                (*escrowed as u128 + *credit as u128 - *debit as u128) as u64
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,56 @@
+use anchor_lang::prelude::*;
+
+/// Shortest dispute period a sender can choose, in seconds; the window in
+/// which the other party can answer a close with a later balance update
+#[constant]
+pub const MIN_CHANNEL_DISPUTE_PERIOD: i64 = 60 * 60;
+
+/// A unidirectional payment channel from a sender to a recipient
+///
+/// The sender's deposit sits in custody owned by this PDA. Payments happen
+/// off-chain: the sender signs balance updates, each the total paid so far,
+/// and the recipient keeps the latest. Either side closes the channel by
+/// submitting one, and the highest submitted before the dispute period ends
+/// is what the recipient is paid.
+#[account]
+#[derive(InitSpace, Debug, PartialEq, Eq)]
+pub struct PaymentChannel {
+    /// The wallet that deposited and signs balance updates
+    pub sender: Pubkey,
+    /// The wallet balance updates pay
+    pub recipient: Pubkey,
+    /// Tokens deposited, the most a balance update can pay
+    pub deposit: u64,
+    /// Seconds between the first submitted balance update and settlement
+    pub dispute_period: i64,
+    /// Unix timestamp the channel was opened at
+    pub opened_at: i64,
+    /// How many channels the sender had opened to the recipient before this
+    /// one; balance updates sign it so none carries over to a later channel
+    /// between the same wallets
+    pub nonce: u64,
+    /// Highest balance update submitted so far
+    pub amount: u64,
+    /// Unix timestamp the dispute period ends at; 0 while the channel is
+    /// open
+    pub closes_at: i64,
+    /// Bump seed for this PDA
+    pub bump: u8,
+}
+
+impl PaymentChannel {
+    /// Whether either side has started closing the channel
+    pub fn closing(&self) -> bool {
+        self.closes_at != 0
+    }
+}
+
+/// The message a sender signs to have paid `amount` in total over the
+/// channel at `channel` with nonce `nonce`
+pub fn channel_state_message(channel: &Pubkey, nonce: u64, amount: u64) -> [u8; 48] {
+    let mut message = [0; 48];
+    message[..32].copy_from_slice(channel.as_ref());
+    message[32..40].copy_from_slice(&nonce.to_le_bytes());
+    message[40..].copy_from_slice(&amount.to_le_bytes());
+    message
+}
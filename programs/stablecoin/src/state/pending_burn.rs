@@ -0,0 +1,30 @@
+use anchor_lang::prelude::*;
+
+use crate::Treasury;
+
+/// A proposed burn from treasury custody, waiting for approvals
+#[account]
+#[derive(InitSpace, Debug, PartialEq, Eq)]
+pub struct PendingBurn {
+    /// The proposer's id for the burn, part of this PDA's seeds
+    pub id: u64,
+    /// The officer that proposed the burn, and paid for this account
+    pub proposer: Pubkey,
+    /// Amount to burn from custody
+    pub amount: u64,
+    /// Officers that approved, the proposer first; unused slots are the
+    /// default key
+    pub approvals: [Pubkey; 2],
+    /// Bump seed for this PDA
+    pub bump: u8,
+}
+
+impl PendingBurn {
+    /// Approvals from keys that are still officers of `treasury`
+    pub fn approvals(&self, treasury: &Treasury) -> usize {
+        self.approvals
+            .iter()
+            .filter(|approval| treasury.is_officer(approval))
+            .count()
+    }
+}
@@ -0,0 +1,26 @@
+use anchor_lang::prelude::*;
+
+/// A purpose-of-payment code the admin registered, such as `b"PAYR"` for
+/// payroll or `b"RMIT"` for remittance
+///
+/// Obligations can carry a registered code, which their event records for
+/// purpose-of-payment reporting.
+#[account]
+#[derive(InitSpace, Debug, PartialEq, Eq)]
+pub struct PurposeCode {
+    /// The code, four uppercase ASCII letters or digits; part of this PDA's
+    /// seeds
+    pub code: [u8; 4],
+    /// The admin that registered the code, and paid for this account
+    pub registered_by: Pubkey,
+    /// Bump seed for this PDA
+    pub bump: u8,
+}
+
+impl PurposeCode {
+    /// Whether `code` can be registered: uppercase ASCII letters and digits,
+    /// so reports show it as written
+    pub fn is_valid(code: &[u8; 4]) -> bool {
+        code.iter().all(|byte| byte.is_ascii_uppercase() || byte.is_ascii_digit())
+    }
+}
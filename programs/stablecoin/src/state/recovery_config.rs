@@ -0,0 +1,55 @@
+use anchor_lang::prelude::*;
+
+/// Most guardians a holder can designate
+#[constant]
+pub const MAX_GUARDIANS: u8 = 5;
+
+/// Shortest challenge period a holder can choose, in seconds; the window
+/// in which they can cancel a recovery they did not ask for
+#[constant]
+pub const MIN_RECOVERY_CHALLENGE_PERIOD: i64 = 24 * 60 * 60;
+
+/// A holder's opt-in social recovery settings, and the recovery in
+/// progress if there is one
+#[account]
+#[derive(InitSpace, Debug, PartialEq, Eq)]
+pub struct RecoveryConfig {
+    /// The holder whose balance can be recovered
+    pub owner: Pubkey,
+    /// Wallets that can start and approve a recovery
+    #[max_len(MAX_GUARDIANS)]
+    pub guardians: Vec<Pubkey>,
+    /// How many guardians must approve a recovery
+    pub quorum: u8,
+    /// Seconds between starting a recovery and executing it
+    pub challenge_period: i64,
+    /// Wallet the recovery in progress moves the balance to; the default
+    /// key when none is in progress
+    pub new_owner: Pubkey,
+    /// Unix timestamp the recovery in progress was started at
+    pub initiated_at: i64,
+    /// Guardians that approved the recovery in progress, bit `i` for
+    /// `guardians[i]`
+    pub approvals: u8,
+    /// Bump seed for this PDA
+    pub bump: u8,
+}
+
+impl RecoveryConfig {
+    /// Where `key` is among the guardians
+    pub fn guardian_index(&self, key: &Pubkey) -> Option<usize> {
+        self.guardians.iter().position(|guardian| guardian == key)
+    }
+
+    /// Whether a recovery is in progress
+    pub fn in_progress(&self) -> bool {
+        self.new_owner != Pubkey::default()
+    }
+
+    /// Forget the recovery in progress
+    pub fn clear(&mut self) {
+        self.new_owner = Pubkey::default();
+        self.initiated_at = 0;
+        self.approvals = 0;
+    }
+}
@@ -0,0 +1,20 @@
+use anchor_lang::prelude::*;
+
+/// A numbered sub-account of an owner, such as an exchange's deposit
+/// address for one of its customers
+///
+/// The PDA owns the sub-account's custody, its associated token account, so
+/// tokens sent there stay attributable to the index and only leave through
+/// a sweep by the owner.
+#[account]
+#[derive(InitSpace, Debug, PartialEq, Eq)]
+pub struct SubAccount {
+    /// The wallet that registered the sub-account and sweeps it
+    pub owner: Pubkey,
+    /// The owner's number for the sub-account; part of this PDA's seeds
+    pub index: u64,
+    /// The registered purpose code the owner filed the sub-account under
+    pub purpose: [u8; 4],
+    /// Bump seed for this PDA
+    pub bump: u8,
+}
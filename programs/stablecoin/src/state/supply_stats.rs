@@ -0,0 +1,45 @@
+use anchor_lang::prelude::*;
+
+use crate::StablecoinError;
+
+/// Time-weighted supply, accumulated whenever the program changes supply
+///
+/// The average supply over a window is the difference between two
+/// snapshots' `cumulative_supply` divided by the seconds between them.
+/// Holders can also burn through SPL Token without calling this program;
+/// such burns count from the next time the supply is recorded.
+#[account]
+#[derive(InitSpace, Debug, PartialEq, Eq)]
+pub struct SupplyStats {
+    /// The mint's supply when last recorded
+    pub supply: u64,
+    /// Unix timestamp the supply was last recorded at
+    pub updated_at: i64,
+    /// Unix timestamp accumulation started at, when the program was
+    /// initialized
+    pub started_at: i64,
+    /// Sum of supply times the seconds it was held, since `started_at`
+    pub cumulative_supply: u128,
+    /// Bump seed for this PDA
+    pub bump: u8,
+}
+
+impl SupplyStats {
+    /// `cumulative_supply` as of `now`, holding the recorded supply since
+    /// it was recorded
+    pub fn cumulative_at(&self, now: i64) -> Result<u128> {
+        let elapsed = now.saturating_sub(self.updated_at).max(0) as u128;
+        self.cumulative_supply
+            .checked_add((self.supply as u128) * elapsed)
+            .ok_or_else(|| StablecoinError::Overflow.into())
+    }
+
+    /// Accumulate up to `now`, then record `supply` from `now` on
+    pub fn record(&mut self, supply: u64, now: i64) -> Result<()> {
+        self.cumulative_supply = self.cumulative_at(now)?;
+        self.supply = supply;
+        self.updated_at = self.updated_at.max(now);
+
+        Ok(())
+    }
+}
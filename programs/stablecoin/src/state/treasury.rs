@@ -0,0 +1,31 @@
+use anchor_lang::prelude::*;
+
+/// Treasury account that records who may burn from treasury custody
+/// The treasury PDA owns the custody token account, its associated token
+/// account, so tokens there only leave through an approved treasury burn
+#[account]
+#[derive(InitSpace, Debug, PartialEq, Eq)]
+pub struct Treasury {
+    /// The officers who propose and approve treasury burns
+    pub officers: [Pubkey; 2],
+    /// Largest burn one officer can approve alone; larger burns need both
+    pub threshold: u64,
+    /// Bump seed for the treasury PDA
+    pub bump: u8,
+}
+
+impl Treasury {
+    /// Whether `key` is one of the officers
+    pub fn is_officer(&self, key: &Pubkey) -> bool {
+        self.officers.contains(key)
+    }
+
+    /// How many officers must approve a burn of `amount`
+    pub fn required_approvals(&self, amount: u64) -> usize {
+        if amount > self.threshold {
+            self.officers.len()
+        } else {
+            1
+        }
+    }
+}
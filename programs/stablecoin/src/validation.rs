@@ -0,0 +1,281 @@
+//! Checks shared by every instruction
+//!
+//! Each instruction context declares its [`Policy`]: who must sign and
+//! whether pausing stops it. The program runs [`check`] before every
+//! handler, and handlers take the [`Checked`] it returns, so none can be
+//! called without the checks. Role checks also run as account constraints
+//! through [`is_admin`], [`is_minter`], [`is_officer`], [`is_guardian`],
+//! [`is_beneficiary`], [`is_institution`], [`is_channel_party`] and
+//! [`is_airdrop_recipient`], failing at the account as they always have;
+//! [`check`] repeats them in case a context leaves one out.
+
+use std::marker::PhantomData;
+
+use anchor_lang::{prelude::*, solana_program::program_option::COption};
+use anchor_spl::token::TokenAccount;
+
+use crate::{
+    Airdrop, ApproveRecovery, ApproveTreasuryBurn, BurnTokens, CancelNettingWindow, CancelRecovery,
+    CancelTreasuryBurn, CheckIn, ClaimAirdrop, ClaimInheritance, ClawbackAirdrop, CloseChannel,
+    CloseInheritance, CloseRecovery, Config, ConfigureInheritance, ConfigureMinter,
+    ConfigureRecovery, ConfigureTreasury, ExecuteRecovery, ExecuteTreasuryBurn, FreezeMinterSet,
+    InheritancePlan, Initialize, InitiateRecovery, MintTokens, MintTokensIdempotent, MinterConfig,
+    NettingWindow, OpenAirdrop, OpenChannel, OpenNettingWindow, Pause, PauseSelf, PaymentChannel,
+    ProposeTreasuryBurn, QueueObligation, ReclaimIdempotencyRecord, RecoveryConfig,
+    RegisterPurposeCode, RegisterSubAccount, RemoveMinter, ResizeAccount, ResumeMinter,
+    RetirePurposeCode, SettleChannel, SettleNet, StablecoinError, StartInheritanceClaim, SweepDust,
+    SweepSubAccount, SyncSupplyStats, Treasury, UnfreezeMinterSet, Unpause,
+};
+
+/// Who must sign an instruction
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Role {
+    /// Any signer; SPL Token or the accounts themselves decide the rest
+    Anyone,
+    /// The admin recorded in the config
+    Admin,
+    /// A minter with an initialized minter config
+    Minter,
+    /// One of the officers recorded in the treasury
+    Officer,
+    /// One of the guardians recorded in a holder's recovery config
+    Guardian,
+    /// The beneficiary recorded in a holder's inheritance plan
+    Beneficiary,
+    /// One of the institutions registered in a netting window
+    Institution,
+    /// The sender or recipient recorded in a payment channel
+    ChannelParty,
+    /// One of the recipients recorded in an airdrop
+    AirdropRecipient,
+}
+
+/// What an instruction requires before its handler runs
+pub trait Policy {
+    const ROLE: Role;
+    /// Whether pausing stops the instruction
+    const PAUSABLE: bool;
+
+    /// The config, for instructions that read it
+    fn config(&self) -> Option<&Config>;
+
+    /// The signer [`Policy::ROLE`] applies to
+    fn signer(&self) -> Pubkey;
+
+    /// The admin, for [`Role::Admin`]; the one [`Policy::config`] records,
+    /// unless the context reads the config without deserializing it
+    fn admin(&self) -> Option<Pubkey> {
+        self.config().map(|config| config.admin)
+    }
+
+    /// The signer's minter config, for [`Role::Minter`]
+    fn minter_config(&self) -> Option<&MinterConfig> {
+        None
+    }
+
+    /// The treasury, for [`Role::Officer`]
+    fn treasury(&self) -> Option<&Treasury> {
+        None
+    }
+
+    /// The holder's recovery config, for [`Role::Guardian`]
+    fn recovery_config(&self) -> Option<&RecoveryConfig> {
+        None
+    }
+
+    /// The holder's inheritance plan, for [`Role::Beneficiary`]
+    fn inheritance_plan(&self) -> Option<&InheritancePlan> {
+        None
+    }
+
+    /// The netting window, for [`Role::Institution`]
+    fn netting_window(&self) -> Option<&NettingWindow> {
+        None
+    }
+
+    /// The payment channel, for [`Role::ChannelParty`]
+    fn payment_channel(&self) -> Option<&PaymentChannel> {
+        None
+    }
+
+    /// The airdrop, for [`Role::AirdropRecipient`]
+    fn airdrop(&self) -> Option<&Airdrop> {
+        None
+    }
+}
+
+/// Proof that [`check`] passed for `T`
+///
+/// Only [`check`] makes one, and every handler takes one.
+pub struct Checked<T>(PhantomData<fn() -> T>);
+
+/// Run the checks `accounts` declares through [`Policy`]
+pub fn check<T: Policy>(accounts: &T) -> Result<Checked<T>> {
+    let config = accounts.config();
+    match T::ROLE {
+        Role::Anyone => {}
+        Role::Admin => require!(
+            accounts.admin() == Some(accounts.signer()),
+            StablecoinError::Unauthorized
+        ),
+        Role::Minter => require!(
+            accounts.minter_config().is_some_and(is_minter),
+            StablecoinError::NotMinter
+        ),
+        Role::Officer => require!(
+            accounts
+                .treasury()
+                .is_some_and(|treasury| is_officer(&accounts.signer(), treasury)),
+            StablecoinError::NotOfficer
+        ),
+        Role::Guardian => require!(
+            accounts
+                .recovery_config()
+                .is_some_and(|recovery_config| is_guardian(&accounts.signer(), recovery_config)),
+            StablecoinError::NotGuardian
+        ),
+        Role::Beneficiary => require!(
+            accounts
+                .inheritance_plan()
+                .is_some_and(|inheritance_plan| is_beneficiary(&accounts.signer(), inheritance_plan)),
+            StablecoinError::NotBeneficiary
+        ),
+        Role::Institution => require!(
+            accounts
+                .netting_window()
+                .is_some_and(|netting_window| is_institution(&accounts.signer(), netting_window)),
+            StablecoinError::NotInstitution
+        ),
+        Role::ChannelParty => require!(
+            accounts
+                .payment_channel()
+                .is_some_and(|payment_channel| is_channel_party(&accounts.signer(), payment_channel)),
+            StablecoinError::NotChannelParty
+        ),
+        Role::AirdropRecipient => require!(
+            accounts
+                .airdrop()
+                .is_some_and(|airdrop| is_airdrop_recipient(&accounts.signer(), airdrop)),
+            StablecoinError::NotAirdropRecipient
+        ),
+    }
+    if T::PAUSABLE {
+        // An instruction that can be paused reads the pause flag
+        require_not_paused(config.expect("pausable instruction without the config"))?;
+    }
+    Ok(Checked(PhantomData))
+}
+
+/// Whether `signer` is the admin `config` records
+pub fn is_admin(signer: &Pubkey, config: &Config) -> bool {
+    *signer == config.admin
+}
+
+/// Whether `minter_config` authorizes its minter
+pub fn is_minter(minter_config: &MinterConfig) -> bool {
+    minter_config.is_initialized
+}
+
+/// Whether `signer` is one of the officers `treasury` records
+pub fn is_officer(signer: &Pubkey, treasury: &Treasury) -> bool {
+    treasury.is_officer(signer)
+}
+
+/// Whether `signer` is one of the guardians `recovery_config` records
+pub fn is_guardian(signer: &Pubkey, recovery_config: &RecoveryConfig) -> bool {
+    recovery_config.guardian_index(signer).is_some()
+}
+
+/// Whether `signer` is the beneficiary `inheritance_plan` records
+pub fn is_beneficiary(signer: &Pubkey, inheritance_plan: &InheritancePlan) -> bool {
+    *signer == inheritance_plan.beneficiary
+}
+
+/// Whether `signer` is one of the institutions `netting_window` records
+pub fn is_institution(signer: &Pubkey, netting_window: &NettingWindow) -> bool {
+    netting_window.institution_index(signer).is_some()
+}
+
+/// Whether `signer` is the sender or the recipient `payment_channel`
+/// records
+pub fn is_channel_party(signer: &Pubkey, payment_channel: &PaymentChannel) -> bool {
+    *signer == payment_channel.sender || *signer == payment_channel.recipient
+}
+
+/// Whether `signer` is one of the recipients `airdrop` records
+pub fn is_airdrop_recipient(signer: &Pubkey, airdrop: &Airdrop) -> bool {
+    airdrop.recipient_index(signer).is_some()
+}
+
+/// Whether `token_account` still lets `delegate`, the program's transfer
+/// delegate, move its whole balance
+///
+/// Recovery and inheritance approve it when configured, but the holder can
+/// revoke it or approve someone else through SPL Token at any time.
+pub fn delegates_balance(token_account: &TokenAccount, delegate: &Pubkey) -> bool {
+    token_account.delegate == COption::Some(*delegate)
+        && token_account.delegated_amount >= token_account.amount
+}
+
+pub fn require_not_paused(config: &Config) -> Result<()> {
+    require!(!config.paused, StablecoinError::Paused);
+    Ok(())
+}
+
+const fn declares<T: Policy>(role: Role, pausable: bool) -> bool {
+    T::ROLE as u8 == role as u8 && T::PAUSABLE == pausable
+}
+
+// Every instruction's policy, checked when the program compiles; changing
+// who may sign an instruction or whether pausing stops it means changing
+// this table, and tests/pause.rs, with it
+const _: () = {
+    assert!(declares::<Initialize<'static>>(Role::Anyone, false));
+    assert!(declares::<ConfigureMinter<'static>>(Role::Admin, false));
+    assert!(declares::<RemoveMinter<'static>>(Role::Admin, false));
+    assert!(declares::<FreezeMinterSet<'static>>(Role::Admin, false));
+    assert!(declares::<UnfreezeMinterSet<'static>>(Role::Admin, false));
+    assert!(declares::<MintTokens<'static>>(Role::Minter, true));
+    assert!(declares::<MintTokensIdempotent<'static>>(Role::Minter, true));
+    assert!(declares::<ReclaimIdempotencyRecord<'static>>(Role::Anyone, false));
+    assert!(declares::<PauseSelf<'static>>(Role::Minter, false));
+    assert!(declares::<ResumeMinter<'static>>(Role::Admin, false));
+    assert!(declares::<BurnTokens<'static>>(Role::Anyone, false));
+    assert!(declares::<SweepDust<'static>>(Role::Anyone, false));
+    assert!(declares::<ConfigureTreasury<'static>>(Role::Admin, false));
+    assert!(declares::<ProposeTreasuryBurn<'static>>(Role::Officer, false));
+    assert!(declares::<ApproveTreasuryBurn<'static>>(Role::Officer, false));
+    assert!(declares::<ExecuteTreasuryBurn<'static>>(Role::Officer, false));
+    assert!(declares::<CancelTreasuryBurn<'static>>(Role::Officer, false));
+    assert!(declares::<ConfigureRecovery<'static>>(Role::Anyone, false));
+    assert!(declares::<InitiateRecovery<'static>>(Role::Guardian, false));
+    assert!(declares::<ApproveRecovery<'static>>(Role::Guardian, false));
+    assert!(declares::<ExecuteRecovery<'static>>(Role::Guardian, false));
+    assert!(declares::<CancelRecovery<'static>>(Role::Anyone, false));
+    assert!(declares::<CloseRecovery<'static>>(Role::Anyone, false));
+    assert!(declares::<ConfigureInheritance<'static>>(Role::Anyone, false));
+    assert!(declares::<CheckIn<'static>>(Role::Anyone, false));
+    assert!(declares::<StartInheritanceClaim<'static>>(Role::Beneficiary, false));
+    assert!(declares::<ClaimInheritance<'static>>(Role::Beneficiary, false));
+    assert!(declares::<CloseInheritance<'static>>(Role::Anyone, false));
+    assert!(declares::<OpenNettingWindow<'static>>(Role::Admin, false));
+    assert!(declares::<QueueObligation<'static>>(Role::Institution, false));
+    assert!(declares::<SettleNet<'static>>(Role::Anyone, false));
+    assert!(declares::<CancelNettingWindow<'static>>(Role::Admin, false));
+    assert!(declares::<RegisterPurposeCode<'static>>(Role::Admin, false));
+    assert!(declares::<RetirePurposeCode<'static>>(Role::Admin, false));
+    assert!(declares::<OpenChannel<'static>>(Role::Anyone, false));
+    assert!(declares::<CloseChannel<'static>>(Role::ChannelParty, false));
+    assert!(declares::<SettleChannel<'static>>(Role::Anyone, false));
+    assert!(declares::<RegisterSubAccount<'static>>(Role::Anyone, false));
+    assert!(declares::<SweepSubAccount<'static>>(Role::Anyone, false));
+    assert!(declares::<OpenAirdrop<'static>>(Role::Officer, false));
+    assert!(declares::<ClaimAirdrop<'static>>(Role::AirdropRecipient, false));
+    assert!(declares::<ClawbackAirdrop<'static>>(Role::Anyone, false));
+    assert!(declares::<ResizeAccount<'static>>(Role::Admin, false));
+    assert!(declares::<SyncSupplyStats<'static>>(Role::Anyone, false));
+    assert!(declares::<Pause<'static>>(Role::Admin, false));
+    assert!(declares::<Unpause<'static>>(Role::Admin, false));
+    #[cfg(feature = "devnet-faucet")]
+    assert!(declares::<crate::Faucet<'static>>(Role::Anyone, true));
+};
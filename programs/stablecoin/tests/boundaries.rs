@@ -0,0 +1,209 @@
+use anchor_litesvm::{Keypair, Signer, TransactionResult};
+use anchor_spl::token::spl_token::error::TokenError;
+use stablecoin::StablecoinError;
+use stablecoin_test_utils::{instructions, TestContext};
+
+const ALLOWANCE: u64 = 1_000_000_000;
+
+fn mint(
+    ctx: &mut TestContext,
+    minter: &Keypair,
+    owner: &Keypair,
+    amount: u64,
+) -> TransactionResult {
+    ctx.execute(
+        instructions::mint_tokens(&minter.pubkey(), &owner.pubkey(), amount),
+        &[minter],
+    )
+}
+
+fn burn(ctx: &mut TestContext, owner: &Keypair, amount: u64) -> TransactionResult {
+    ctx.execute(instructions::burn_tokens(&owner.pubkey(), amount), &[owner])
+}
+
+// ============================================================================
+// Zero Amount Tests
+// ============================================================================
+
+#[test]
+fn test_mint_zero() {
+    let mut ctx = TestContext::new();
+    let minter = ctx.funded_account();
+    let user = ctx.funded_account();
+    ctx.initialize().configure_minter(&minter, ALLOWANCE);
+
+    mint(&mut ctx, &minter, &user, 0).assert_success();
+
+    // The token account is created, but nothing counts against the allowance
+    assert_eq!(ctx.balance(&user), 0);
+    assert_eq!(ctx.mint_state().supply, 0);
+    assert_eq!(ctx.minter_config(&minter).amount_minted, 0);
+}
+
+#[test]
+fn test_mint_zero_with_allowance_used_up() {
+    let mut ctx = TestContext::new();
+    let minter = ctx.funded_account();
+    let user = ctx.funded_account();
+    ctx.initialize()
+        .configure_minter(&minter, ALLOWANCE)
+        .mint(&minter, &user, ALLOWANCE);
+
+    mint(&mut ctx, &minter, &user, 0).assert_success();
+    assert_eq!(ctx.minter_config(&minter).amount_minted, ALLOWANCE);
+}
+
+#[test]
+fn test_burn_zero() {
+    let mut ctx = TestContext::new();
+    let minter = ctx.funded_account();
+    let user = ctx.funded_account();
+    ctx.initialize()
+        .configure_minter(&minter, ALLOWANCE)
+        .mint(&minter, &user, 100);
+
+    burn(&mut ctx, &user, 0).assert_success();
+    assert_eq!(ctx.balance(&user), 100);
+    assert_eq!(ctx.mint_state().supply, 100);
+}
+
+// ============================================================================
+// Allowance Boundary Tests
+// ============================================================================
+
+#[test]
+fn test_mint_exactly_allowance() {
+    let mut ctx = TestContext::new();
+    let minter = ctx.funded_account();
+    let user = ctx.funded_account();
+    ctx.initialize().configure_minter(&minter, ALLOWANCE);
+
+    mint(&mut ctx, &minter, &user, ALLOWANCE).assert_success();
+    assert_eq!(ctx.minter_config(&minter).amount_minted, ALLOWANCE);
+
+    // Not one unit more
+    let result = mint(&mut ctx, &minter, &user, 1);
+    result.assert_error_code(StablecoinError::ExceedsAllowance.into());
+    assert_eq!(ctx.balance(&user), ALLOWANCE);
+}
+
+#[test]
+fn test_mint_one_past_allowance() {
+    let mut ctx = TestContext::new();
+    let minter = ctx.funded_account();
+    let user = ctx.funded_account();
+    ctx.initialize().configure_minter(&minter, ALLOWANCE);
+
+    let result = mint(&mut ctx, &minter, &user, ALLOWANCE + 1);
+    result.assert_error_code(StablecoinError::ExceedsAllowance.into());
+    assert_eq!(ctx.minter_config(&minter).amount_minted, 0);
+}
+
+#[test]
+fn test_repeated_mints_up_to_allowance() {
+    let mut ctx = TestContext::new();
+    let minter = ctx.funded_account();
+    let user = ctx.funded_account();
+    ctx.initialize().configure_minter(&minter, ALLOWANCE);
+
+    ctx.mint(&minter, &user, ALLOWANCE / 2)
+        .mint(&minter, &user, ALLOWANCE / 2 - 1);
+    let result = mint(&mut ctx, &minter, &user, 2);
+    result.assert_error_code(StablecoinError::ExceedsAllowance.into());
+
+    mint(&mut ctx, &minter, &user, 1).assert_success();
+    assert_eq!(ctx.minter_config(&minter).amount_minted, ALLOWANCE);
+}
+
+#[test]
+fn test_allowance_lowered_below_amount_minted() {
+    let mut ctx = TestContext::new();
+    let minter = ctx.funded_account();
+    let user = ctx.funded_account();
+    ctx.initialize()
+        .configure_minter(&minter, ALLOWANCE)
+        .mint(&minter, &user, ALLOWANCE / 2)
+        .configure_minter(&minter, ALLOWANCE / 4);
+
+    // allowance - amount_minted would underflow; the mint must fail
+    // rather than see a wrapped, near-u64::MAX remaining allowance
+    let result = mint(&mut ctx, &minter, &user, 1);
+    result.assert_error_code(StablecoinError::ExceedsAllowance.into());
+    let result = mint(&mut ctx, &minter, &user, 0);
+    result.assert_error_code(StablecoinError::ExceedsAllowance.into());
+    assert_eq!(ctx.minter_config(&minter).amount_minted, ALLOWANCE / 2);
+}
+
+// ============================================================================
+// u64 Limit Tests
+// ============================================================================
+
+#[test]
+fn test_mint_u64_max() {
+    let mut ctx = TestContext::new();
+    let minter = ctx.funded_account();
+    let user = ctx.funded_account();
+    ctx.initialize().configure_minter(&minter, u64::MAX);
+
+    mint(&mut ctx, &minter, &user, u64::MAX).assert_success();
+    assert_eq!(ctx.balance(&user), u64::MAX);
+    assert_eq!(ctx.mint_state().supply, u64::MAX);
+    assert_eq!(ctx.minter_config(&minter).amount_minted, u64::MAX);
+
+    let result = mint(&mut ctx, &minter, &user, 1);
+    result.assert_error_code(StablecoinError::ExceedsAllowance.into());
+}
+
+#[test]
+fn test_mint_u64_max_over_allowance() {
+    let mut ctx = TestContext::new();
+    let minter = ctx.funded_account();
+    let user = ctx.funded_account();
+    ctx.initialize().configure_minter(&minter, ALLOWANCE);
+
+    let result = mint(&mut ctx, &minter, &user, u64::MAX);
+    result.assert_error_code(StablecoinError::ExceedsAllowance.into());
+    assert_eq!(ctx.mint_state().supply, 0);
+}
+
+#[test]
+fn test_supply_cannot_pass_u64_max() {
+    let mut ctx = TestContext::new();
+    let first = ctx.funded_account();
+    let second = ctx.funded_account();
+    let user = ctx.funded_account();
+    ctx.initialize()
+        .configure_minter(&first, u64::MAX)
+        .configure_minter(&second, u64::MAX)
+        .mint(&first, &user, u64::MAX - 1);
+
+    // Each minter is within its own allowance; the supply is the cap
+    mint(&mut ctx, &second, &user, 1).assert_success();
+    assert_eq!(ctx.mint_state().supply, u64::MAX);
+
+    // The same transaction again would be rejected as already processed
+    ctx.svm.expire_blockhash();
+    let result = mint(&mut ctx, &second, &user, 1);
+    result.assert_error_code(TokenError::Overflow as u32);
+    assert_eq!(ctx.mint_state().supply, u64::MAX);
+    assert_eq!(ctx.minter_config(&second).amount_minted, 1);
+}
+
+#[test]
+fn test_burn_more_than_balance() {
+    let mut ctx = TestContext::new();
+    let minter = ctx.funded_account();
+    let user = ctx.funded_account();
+    ctx.initialize()
+        .configure_minter(&minter, ALLOWANCE)
+        .mint(&minter, &user, 100);
+
+    let result = burn(&mut ctx, &user, 101);
+    result.assert_error_code(TokenError::InsufficientFunds as u32);
+    let result = burn(&mut ctx, &user, u64::MAX);
+    result.assert_error_code(TokenError::InsufficientFunds as u32);
+
+    burn(&mut ctx, &user, 100).assert_success();
+    assert_eq!(ctx.balance(&user), 0);
+    assert_eq!(ctx.mint_state().supply, 0);
+}
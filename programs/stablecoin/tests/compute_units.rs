@@ -0,0 +1,90 @@
+use std::{collections::BTreeMap, env, fs};
+
+use anchor_litesvm::{Keypair, Signer};
+use stablecoin_test_utils::{paths, TestContext, FUNDING};
+
+/// Compute units per instruction the program must stay within
+const BASELINE: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/compute_units.txt");
+/// Set to rewrite the baseline from this run instead of checking it
+const UPDATE: &str = "UPDATE_CU_BASELINE";
+
+/// A funded wallet with a fixed key: PDA and associated token account
+/// lookups cost more for some keys than others
+fn wallet(ctx: &mut TestContext, seed: u8) -> Keypair {
+    let wallet = Keypair::new_from_array([seed; 32]);
+    ctx.svm.airdrop(&wallet.pubkey(), FUNDING).unwrap();
+    wallet
+}
+
+/// The most compute units each instruction consumes on any of its
+/// [`paths::deepest_paths`], one transaction per path
+///
+/// `close_channel` counts the signature check sent along with it.
+fn measure() -> BTreeMap<&'static str, u64> {
+    let mut ctx = TestContext::new();
+    ctx.admin = wallet(&mut ctx, 1);
+    let minter = wallet(&mut ctx, 2);
+    let holder = wallet(&mut ctx, 3);
+    let dust = Keypair::new_from_array([4; 32]);
+
+    let mut measured = BTreeMap::new();
+    paths::run(&mut ctx, &minter, &holder, &dust, |instruction, result| {
+        let name = instruction.name();
+        assert!(result.is_success(), "{name} failed: {:?}", result.error());
+        let units = measured.entry(name).or_default();
+        *units = result.compute_units().max(*units);
+    });
+    measured
+}
+
+fn read_baseline() -> BTreeMap<String, u64> {
+    fs::read_to_string(BASELINE)
+        .unwrap_or_default()
+        .lines()
+        .filter(|line| !line.trim().is_empty() && !line.starts_with('#'))
+        .map(|line| {
+            let (name, units) = line
+                .split_once(' ')
+                .unwrap_or_else(|| panic!("malformed baseline line: {line}"));
+            (name.to_string(), units.trim().parse().unwrap())
+        })
+        .collect()
+}
+
+fn write_baseline(measured: &BTreeMap<&str, u64>) {
+    let mut out = format!(
+        "# Compute units per instruction, checked by tests/compute_units.rs\n\
+         # After an intended change: {UPDATE}=1 cargo test -p stablecoin --test compute_units\n"
+    );
+    for (name, units) in measured {
+        out.push_str(&format!("{name} {units}\n"));
+    }
+    fs::write(BASELINE, out).unwrap();
+}
+
+#[test]
+fn test_compute_units() {
+    let measured = measure();
+    for (name, units) in &measured {
+        println!("{name}: {units} CU");
+    }
+    if env::var_os(UPDATE).is_some() {
+        write_baseline(&measured);
+        return;
+    }
+
+    let baseline = read_baseline();
+    let regressions: Vec<String> = measured
+        .iter()
+        .filter_map(|(name, units)| match baseline.get(*name) {
+            Some(limit) if units <= limit => None,
+            Some(limit) => Some(format!("{name}: {units} CU, baseline {limit}")),
+            None => Some(format!("{name}: {units} CU, no baseline")),
+        })
+        .collect();
+    assert!(
+        regressions.is_empty(),
+        "compute units above baseline; if intended, rerun with {UPDATE}=1:\n{}",
+        regressions.join("\n")
+    );
+}
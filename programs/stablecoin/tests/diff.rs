@@ -0,0 +1,110 @@
+use anchor_lang::prelude::Pubkey;
+use anchor_litesvm::Signer;
+use stablecoin_test_utils::{
+    diff::{AccountDiff, Status},
+    instructions, pda, TestContext,
+};
+
+/// The field `name` of the account at `address`, before and after
+fn field<'a>(
+    diff: &'a AccountDiff,
+    address: &Pubkey,
+    name: &str,
+) -> Option<(Option<&'a str>, Option<&'a str>)> {
+    let field = diff.get(address)?.field(name)?;
+    Some((field.before.as_deref(), field.after.as_deref()))
+}
+
+#[test]
+fn test_diff_of_mint() {
+    let mut ctx = TestContext::new();
+    let minter = ctx.funded_account();
+    let user = ctx.funded_account();
+    ctx.initialize().configure_minter(&minter, 1_000);
+
+    let (result, diff) = ctx.execute_with_diff(
+        instructions::mint_tokens(&minter.pubkey(), &user.pubkey(), 100),
+        &[&minter],
+    );
+    result.assert_success();
+    assert_eq!(diff.closed().count(), 0);
+
+    // The user's token account is new, and holds what was minted
+    let token_account = ctx.token_account(&user);
+    let created = diff.get(&token_account).unwrap();
+    assert_eq!(
+        (created.status, created.kind),
+        (Status::Created, "TokenAccount")
+    );
+    assert!(created.lamport_delta() > 0);
+    assert_eq!(
+        field(&diff, &token_account, "amount"),
+        Some((None, Some("100")))
+    );
+
+    // Modified accounts list only the fields that changed
+    let mint = pda::mint().0;
+    assert_eq!(diff.get(&mint).unwrap().status, Status::Modified);
+    assert_eq!(diff.get(&mint).unwrap().lamport_delta(), 0);
+    assert_eq!(
+        field(&diff, &mint, "supply"),
+        Some((Some("0"), Some("100")))
+    );
+    let minter_config = pda::minter_config(&minter.pubkey()).0;
+    assert_eq!(diff.get(&minter_config).unwrap().kind, "MinterConfig");
+    assert_eq!(
+        field(&diff, &minter_config, "amount_minted"),
+        Some((Some("0"), Some("100")))
+    );
+    assert_eq!(field(&diff, &minter_config, "allowance"), None);
+
+    // Nothing changes when the transaction fails
+    let (result, diff) = ctx.execute_with_diff(
+        instructions::mint_tokens(&minter.pubkey(), &user.pubkey(), 1_000),
+        &[&minter],
+    );
+    assert!(!result.is_success());
+    assert!(diff.is_empty(), "{diff}");
+}
+
+#[test]
+fn test_diff_of_clawback() {
+    let mut ctx = TestContext::new();
+    let minter = ctx.funded_account();
+    let funder = ctx.funded_account();
+    let recipient = ctx.funded_account();
+    ctx.initialize()
+        .configure_minter(&minter, 1_000)
+        .mint(&minter, &funder, 1_000)
+        .configure_treasury([&funder, &recipient], 0)
+        .open_airdrop(&funder, 1, &[(&recipient, 300)], 60)
+        .advance_time(60);
+
+    let (result, diff) = ctx.execute_with_diff(
+        instructions::clawback_airdrop(&recipient.pubkey(), &funder.pubkey(), 1),
+        &[&recipient],
+    );
+    result.assert_success();
+
+    // Closed accounts list their fields as they were before the transaction
+    let airdrop = pda::airdrop(&funder.pubkey(), 1).0;
+    let closed = diff.get(&airdrop).unwrap();
+    assert_eq!((closed.status, closed.kind), (Status::Closed, "Airdrop"));
+    assert_eq!(closed.lamport_delta(), -i128::from(closed.lamports_before));
+    assert_eq!(
+        field(&diff, &airdrop, "unclaimed"),
+        Some((Some("[300]"), None))
+    );
+    let custody = pda::airdrop_custody(&funder.pubkey(), 1);
+    assert_eq!(diff.get(&custody).unwrap().status, Status::Closed);
+    assert_eq!(field(&diff, &custody, "amount"), Some((Some("300"), None)));
+
+    // To treasury custody, which the clawback creates
+    let treasury_custody = pda::treasury_custody();
+    assert_eq!(diff.get(&treasury_custody).unwrap().status, Status::Created);
+    assert_eq!(
+        field(&diff, &treasury_custody, "amount"),
+        Some((None, Some("300")))
+    );
+    assert_eq!(diff.changes.len(), 3, "{diff}");
+}
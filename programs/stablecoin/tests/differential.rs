@@ -0,0 +1,338 @@
+use anchor_lang::solana_program::program_option::COption;
+use anchor_litesvm::{Instruction, Keypair, Pubkey, Signer};
+use anchor_spl::token::{spl_token, Mint, TokenAccount};
+use litesvm_utils::TestHelpers;
+use stablecoin_test_utils::{instructions, pda, TestContext};
+
+/// The stablecoin's decimals, stated here rather than read from the program
+/// so a wrong `mint::decimals` shows up as a difference
+const DECIMALS: u8 = 6;
+
+const HOLDERS: usize = 3;
+
+/// What each step does, to the stablecoin through the program and to the
+/// control mint through SPL Token directly
+#[derive(Debug, Clone, Copy)]
+enum Op {
+    Mint { to: usize, amount: u64 },
+    Burn { from: usize, amount: u64 },
+    Transfer { from: usize, to: usize, amount: u64 },
+}
+
+/// The stablecoin next to a control mint with the same decimals and holders
+///
+/// The minter's allowance is unlimited and nothing is paused, so no policy
+/// check can reject a step; what is left must match SPL Token exactly.
+struct Differential {
+    ctx: TestContext,
+    minter: Keypair,
+    control_mint: Pubkey,
+    control_authority: Keypair,
+    holders: Vec<Keypair>,
+}
+
+impl Differential {
+    fn new() -> Self {
+        let mut ctx = TestContext::new();
+        let minter = ctx.funded_account();
+        let control_authority = ctx.funded_account();
+        let holders: Vec<Keypair> = (0..HOLDERS).map(|_| ctx.funded_account()).collect();
+        ctx.initialize().configure_minter(&minter, u64::MAX);
+        let control_mint = ctx
+            .svm
+            .create_token_mint(&control_authority, DECIMALS)
+            .unwrap()
+            .pubkey();
+
+        // Minting nothing creates the holder's token account, as the
+        // associated token program does for the control
+        for holder in &holders {
+            ctx.mint(&minter, holder, 0);
+            ctx.svm
+                .create_associated_token_account(&control_mint, holder)
+                .unwrap();
+        }
+
+        Self {
+            ctx,
+            minter,
+            control_mint,
+            control_authority,
+            holders,
+        }
+    }
+
+    fn control_account(&self, owner: &Keypair) -> Pubkey {
+        anchor_spl::associated_token::get_associated_token_address(
+            &owner.pubkey(),
+            &self.control_mint,
+        )
+    }
+
+    fn control_mint_state(&self) -> Mint {
+        self.ctx.account(&self.control_mint)
+    }
+
+    fn control_token_state(&self, owner: &Keypair) -> TokenAccount {
+        self.ctx.account(&self.control_account(owner))
+    }
+
+    /// The instruction and signer for `op` on each side
+    fn instructions(&self, op: Op) -> [(Instruction, Keypair); 2] {
+        let transfer = |from: &Keypair, source: Pubkey, destination: Pubkey, amount| {
+            let ix = spl_token::instruction::transfer(
+                &spl_token::ID,
+                &source,
+                &destination,
+                &from.pubkey(),
+                &[],
+                amount,
+            )
+            .unwrap();
+            (ix, from.insecure_clone())
+        };
+
+        match op {
+            Op::Mint { to, amount } => {
+                let to = &self.holders[to];
+                let control = spl_token::instruction::mint_to(
+                    &spl_token::ID,
+                    &self.control_mint,
+                    &self.control_account(to),
+                    &self.control_authority.pubkey(),
+                    &[],
+                    amount,
+                )
+                .unwrap();
+                [
+                    (
+                        instructions::mint_tokens(&self.minter.pubkey(), &to.pubkey(), amount),
+                        self.minter.insecure_clone(),
+                    ),
+                    (control, self.control_authority.insecure_clone()),
+                ]
+            }
+            Op::Burn { from, amount } => {
+                let from = &self.holders[from];
+                let control = spl_token::instruction::burn(
+                    &spl_token::ID,
+                    &self.control_account(from),
+                    &self.control_mint,
+                    &from.pubkey(),
+                    &[],
+                    amount,
+                )
+                .unwrap();
+                [
+                    (
+                        instructions::burn_tokens(&from.pubkey(), amount),
+                        from.insecure_clone(),
+                    ),
+                    (control, from.insecure_clone()),
+                ]
+            }
+            Op::Transfer { from, to, amount } => {
+                let (from, to) = (&self.holders[from], &self.holders[to]);
+                [
+                    transfer(
+                        from,
+                        self.ctx.token_account(from),
+                        self.ctx.token_account(to),
+                        amount,
+                    ),
+                    transfer(
+                        from,
+                        self.control_account(from),
+                        self.control_account(to),
+                        amount,
+                    ),
+                ]
+            }
+        }
+    }
+
+    /// Apply `op` to both sides, asserting they succeed or fail alike and
+    /// end up in the same state
+    fn apply(&mut self, op: Op) {
+        // Steps may repeat exactly; each needs a transaction of its own
+        self.ctx.svm.expire_blockhash();
+        let [(stablecoin, stablecoin_signer), (control, control_signer)] = self.instructions(op);
+        let stablecoin = self.ctx.execute(stablecoin, &[&stablecoin_signer]);
+        let control = self.ctx.execute(control, &[&control_signer]);
+
+        assert_eq!(
+            stablecoin.error(),
+            control.error(),
+            "{op:?}: the program and SPL Token disagree"
+        );
+        self.assert_same_state(op);
+    }
+
+    /// Every field of the mints and token accounts matches, other than the
+    /// mint address and the authorities, which are the program's by design
+    fn assert_same_state(&self, op: Op) {
+        let stablecoin = self.ctx.mint_state();
+        let control = self.control_mint_state();
+        assert_eq!(stablecoin.supply, control.supply, "{op:?}: supply");
+        assert_eq!(stablecoin.decimals, control.decimals, "{op:?}: decimals");
+        assert_eq!(
+            stablecoin.is_initialized, control.is_initialized,
+            "{op:?}: mint initialized"
+        );
+
+        for (i, holder) in self.holders.iter().enumerate() {
+            let stablecoin = self.ctx.token_state(holder);
+            let control = self.control_token_state(holder);
+            assert_eq!(
+                spl_token::state::Account {
+                    mint: self.control_mint,
+                    ..*stablecoin
+                },
+                *control,
+                "{op:?}: holder {i}'s token account"
+            );
+        }
+    }
+}
+
+/// A deterministic stream of steps, some of which fail for lack of balance
+struct Ops {
+    state: u64,
+}
+
+impl Ops {
+    fn next_u64(&mut self) -> u64 {
+        // xorshift64
+        self.state ^= self.state << 13;
+        self.state ^= self.state >> 7;
+        self.state ^= self.state << 17;
+        self.state
+    }
+
+    fn below(&mut self, bound: u64) -> u64 {
+        self.next_u64() % bound
+    }
+
+    fn next(&mut self, d: &Differential) -> Op {
+        let holder = self.below(HOLDERS as u64) as usize;
+        let other = self.below(HOLDERS as u64) as usize;
+        // A quarter more than the holder has, so some steps overdraw
+        let balance = d.control_token_state(&d.holders[holder]).amount;
+        let spend = self.below(balance + balance / 4 + 2);
+        match self.below(3) {
+            0 => Op::Mint {
+                to: holder,
+                amount: self.below(1_000_000_000),
+            },
+            1 => Op::Burn {
+                from: holder,
+                amount: spend,
+            },
+            _ => Op::Transfer {
+                from: holder,
+                to: other,
+                amount: spend,
+            },
+        }
+    }
+}
+
+// ============================================================================
+// Differential Tests
+// ============================================================================
+
+#[test]
+fn test_accounts_match_spl_token() {
+    let d = Differential::new();
+    d.assert_same_state(Op::Mint { to: 0, amount: 0 });
+
+    let stablecoin = d.ctx.mint_state();
+    assert_eq!(stablecoin.decimals, DECIMALS);
+    // The one intended difference: the config PDA holds both authorities
+    let config = pda::config().0;
+    assert_eq!(stablecoin.mint_authority, COption::Some(config));
+    assert_eq!(stablecoin.freeze_authority, COption::Some(config));
+
+    // The program's token accounts are the associated token accounts
+    for holder in &d.holders {
+        assert_eq!(
+            d.ctx.token_account(holder),
+            anchor_spl::associated_token::get_associated_token_address(
+                &holder.pubkey(),
+                &pda::mint().0
+            )
+        );
+    }
+}
+
+#[test]
+fn test_scripted_steps_match_spl_token() {
+    let mut d = Differential::new();
+    let steps = [
+        Op::Mint {
+            to: 0,
+            amount: 1_000_000,
+        },
+        Op::Mint {
+            to: 1,
+            amount: 250_000,
+        },
+        Op::Transfer {
+            from: 0,
+            to: 1,
+            amount: 400_000,
+        },
+        Op::Burn {
+            from: 1,
+            amount: 650_000,
+        },
+        // Exactly what is left, then one more than that
+        Op::Burn {
+            from: 0,
+            amount: 600_000,
+        },
+        Op::Burn { from: 0, amount: 1 },
+        Op::Transfer {
+            from: 0,
+            to: 2,
+            amount: 1,
+        },
+        // Zero amounts and transfers to oneself
+        Op::Mint { to: 2, amount: 0 },
+        Op::Burn { from: 2, amount: 0 },
+        Op::Transfer {
+            from: 1,
+            to: 1,
+            amount: 0,
+        },
+        Op::Mint {
+            to: 2,
+            amount: 5_000,
+        },
+        Op::Transfer {
+            from: 2,
+            to: 2,
+            amount: 5_000,
+        },
+        Op::Burn {
+            from: 2,
+            amount: u64::MAX,
+        },
+    ];
+    for op in steps {
+        d.apply(op);
+    }
+    assert_eq!(d.ctx.mint_state().supply, 5_000);
+}
+
+#[test]
+fn test_random_steps_match_spl_token() {
+    let mut d = Differential::new();
+    let mut ops = Ops {
+        state: 0x5eed_cafe_f00d_d00d,
+    };
+    for _ in 0..300 {
+        let op = ops.next(&d);
+        d.apply(op);
+    }
+}
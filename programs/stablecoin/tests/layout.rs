@@ -0,0 +1,164 @@
+use std::{env, fs};
+
+use anchor_lang::{AccountDeserialize, AccountSerialize};
+use anchor_litesvm::{Keypair, Signer};
+use stablecoin::{sizes, Config, MinterConfig};
+use stablecoin_test_utils::{dump, pda, scenario, TestContext, PROGRAM_ID};
+
+/// Set to rewrite the fixtures from this run instead of checking them
+const UPDATE: &str = "UPDATE_LAYOUT_FIXTURES";
+
+const ALLOWANCE: u64 = 1_000_000_000;
+
+/// Fixed keys, so the fixtures are the same on every run
+fn admin() -> Keypair {
+    Keypair::new_from_array([1; 32])
+}
+
+fn minter() -> Keypair {
+    Keypair::new_from_array([2; 32])
+}
+
+/// The config `initialize` creates for [`admin`]
+fn config() -> Config {
+    Config {
+        admin: admin().pubkey(),
+        mint: pda::mint().0,
+        paused: false,
+        bump: pda::config().1,
+        mint_bump: pda::mint().1,
+        delegate_bump: pda::delegate().1,
+        minter_set_freeze_bump: pda::minter_set_freeze().1,
+    }
+}
+
+/// The minter config `configure_minter` creates for [`minter`]
+fn minter_config() -> MinterConfig {
+    MinterConfig {
+        minter: minter().pubkey(),
+        allowance: ALLOWANCE,
+        amount_minted: 0,
+        is_initialized: true,
+        bump: pda::minter_config(&minter().pubkey()).1,
+        pause_bump: pda::minter_pause(&minter().pubkey()).1,
+    }
+}
+
+fn serialize(account: &impl AccountSerialize) -> Vec<u8> {
+    let mut data = Vec::new();
+    account.try_serialize(&mut data).unwrap();
+    data
+}
+
+fn fixture_path(name: &str) -> String {
+    format!("{}/tests/fixtures/{name}.hex", env!("CARGO_MANIFEST_DIR"))
+}
+
+/// Compare `data` with the hex fixture `name`, 32 bytes to a line
+fn assert_fixture(name: &str, data: &[u8]) {
+    let path = fixture_path(name);
+    if env::var_os(UPDATE).is_some() {
+        let lines: Vec<String> = data
+            .chunks(32)
+            .map(|chunk| chunk.iter().map(|byte| format!("{byte:02x}")).collect())
+            .collect();
+        fs::write(&path, lines.join("\n") + "\n").unwrap();
+        return;
+    }
+
+    let hex: String = fs::read_to_string(&path)
+        .unwrap_or_else(|err| panic!("failed to read {path}: {err}"))
+        .split_whitespace()
+        .collect();
+    let expected: Vec<u8> = (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).unwrap())
+        .collect();
+    assert_eq!(
+        data, expected,
+        "{name} layout changed; existing accounts would no longer deserialize. \
+         If the change is intended and migrated, rerun with {UPDATE}=1"
+    );
+}
+
+#[test]
+fn test_config_layout() {
+    let data = serialize(&config());
+    assert_eq!(data.len(), sizes::CONFIG);
+    assert_fixture("config", &data);
+}
+
+#[test]
+fn test_minter_config_layout() {
+    let data = serialize(&minter_config());
+    assert_eq!(data.len(), sizes::MINTER_CONFIG);
+    assert_fixture("minter_config", &data);
+}
+
+#[test]
+fn test_created_accounts_match_fixtures() {
+    let mut ctx = TestContext::with_admin(admin());
+    ctx.initialize().configure_minter(&minter(), ALLOWANCE);
+
+    let config_account = ctx.svm.get_account(&pda::config().0).unwrap();
+    assert_fixture("config", &config_account.data);
+    let minter_account = ctx
+        .svm
+        .get_account(&pda::minter_config(&minter().pubkey()).0)
+        .unwrap();
+    assert_fixture("minter_config", &minter_account.data);
+}
+
+/// A config as `solana account --output json` dumps it, loadable with
+/// `TestContext::load_accounts`
+#[test]
+fn test_dumped_config_matches_layout() {
+    let (address, account) = dump::read(concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/tests/fixtures/accounts/config.json"
+    ));
+    assert_eq!(address, pda::config().0);
+    assert_eq!(account.owner, PROGRAM_ID);
+    assert_eq!(account.data, serialize(&config()));
+    assert_eq!(
+        Config::try_deserialize(&mut account.data.as_slice()).unwrap(),
+        config()
+    );
+}
+
+/// What `gen-fixtures` writes loads back as the scenario left it
+#[test]
+fn test_scenario_accounts_round_trip_through_dump() {
+    let ctx = scenario::run();
+    assert_eq!(
+        ctx.mint_state().supply,
+        scenario::MINTED + scenario::SECOND_MINTED - scenario::BURNED
+    );
+    assert_eq!(
+        ctx.balance(&scenario::holder()),
+        scenario::MINTED - scenario::BURNED
+    );
+
+    let dir = env::temp_dir().join(format!("stablecoin-scenario-{}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    for (name, address) in scenario::accounts() {
+        let account = ctx.svm.get_account(&address).unwrap();
+        dump::write(dir.join(format!("{name}.json")), &address, &account);
+    }
+
+    let mut loaded = TestContext::new();
+    loaded.load_accounts(&dir);
+    fs::remove_dir_all(&dir).unwrap();
+    for (name, address) in scenario::accounts() {
+        assert_eq!(
+            loaded.svm.get_account(&address),
+            ctx.svm.get_account(&address),
+            "{name}"
+        );
+    }
+    assert_eq!(loaded.config().admin, scenario::admin().pubkey());
+    assert_eq!(
+        loaded.minter_config(&scenario::minter()).amount_minted,
+        scenario::MINTED
+    );
+}
@@ -0,0 +1,449 @@
+use anchor_lang::{
+    solana_program::{program_error::ProgramError, program_option::COption, program_pack::Pack},
+    system_program, AccountSerialize,
+};
+use anchor_litesvm::Pubkey;
+use anchor_spl::token::spl_token::state::{Account as TokenAccount, AccountState, Mint};
+use mollusk_svm::{
+    program::{self, loader_keys::LOADER_V3},
+    result::{Check, InstructionResult},
+    Mollusk,
+};
+use mollusk_svm_programs_token::{associated_token, token};
+use solana_account::Account;
+use stablecoin::{sizes, Config, MinterConfig, StablecoinError, SupplyStats};
+use stablecoin_test_utils::{instructions, pda, FUNDING, PROGRAM_ID};
+
+const ALLOWANCE: u64 = 1_000_000_000;
+
+/// Fixed keys, matching tests/compute_units.rs. Mollusk checks no
+/// signatures, so plain keys stand in for the signers.
+const ADMIN: Pubkey = Pubkey::new_from_array([1; 32]);
+const MINTER: Pubkey = Pubkey::new_from_array([2; 32]);
+const HOLDER: Pubkey = Pubkey::new_from_array([3; 32]);
+
+/// The program with SPL Token and Associated Token loaded beside it
+fn mollusk() -> Mollusk {
+    let elf = stablecoin_test_utils::program::elf();
+    let mut mollusk = Mollusk::default();
+    mollusk.add_program_with_elf_and_loader(&PROGRAM_ID, elf, &LOADER_V3);
+    token::add_program(&mut mollusk);
+    associated_token::add_program(&mut mollusk);
+    mollusk
+}
+
+fn serialize(state: &impl AccountSerialize) -> Vec<u8> {
+    let mut data = Vec::new();
+    state.try_serialize(&mut data).unwrap();
+    data
+}
+
+fn wallet() -> Account {
+    Account::new(FUNDING, 0, &system_program::ID)
+}
+
+/// A rent-exempt account holding `state`, owned by the program
+fn program_account(mollusk: &Mollusk, state: &impl AccountSerialize) -> Account {
+    let data = serialize(state);
+    Account {
+        lamports: mollusk.sysvars.rent.minimum_balance(data.len()),
+        data,
+        owner: PROGRAM_ID,
+        executable: false,
+        rent_epoch: 0,
+    }
+}
+
+fn config(paused: bool) -> Config {
+    Config {
+        admin: ADMIN,
+        mint: pda::mint().0,
+        paused,
+        bump: pda::config().1,
+        mint_bump: pda::mint().1,
+        delegate_bump: pda::delegate().1,
+        minter_set_freeze_bump: pda::minter_set_freeze().1,
+    }
+}
+
+fn minter_config(allowance: u64, amount_minted: u64) -> MinterConfig {
+    MinterConfig {
+        minter: MINTER,
+        allowance,
+        amount_minted,
+        is_initialized: true,
+        bump: pda::minter_config(&MINTER).1,
+        pause_bump: pda::minter_pause(&MINTER).1,
+    }
+}
+
+/// Supply stats that last recorded `supply`, at the default clock's time
+fn supply_stats(supply: u64) -> SupplyStats {
+    SupplyStats {
+        supply,
+        updated_at: 0,
+        started_at: 0,
+        cumulative_supply: 0,
+        bump: pda::supply_stats().1,
+    }
+}
+
+fn mint(supply: u64) -> Account {
+    let authority = COption::Some(pda::config().0);
+    token::create_account_for_mint(Mint {
+        mint_authority: authority,
+        supply,
+        decimals: 6,
+        is_initialized: true,
+        freeze_authority: authority,
+    })
+}
+
+/// `owner`'s associated token account holding `amount`
+fn token_account(owner: &Pubkey, amount: u64) -> (Pubkey, Account) {
+    associated_token::create_account_for_associated_token_account(TokenAccount {
+        mint: pda::mint().0,
+        owner: *owner,
+        amount,
+        delegate: COption::None,
+        state: AccountState::Initialized,
+        is_native: COption::None,
+        delegated_amount: 0,
+        close_authority: COption::None,
+    })
+}
+
+/// Where the amounts sit in packed SPL Token accounts
+const MINT_SUPPLY_OFFSET: usize = 36;
+const TOKEN_AMOUNT_OFFSET: usize = 64;
+
+/// Where the recorded supply sits in the supply stats, after the
+/// discriminator
+const STATS_SUPPLY_OFFSET: usize = 8;
+
+/// A deployed stablecoin: config, mint with `supply` and stats recording
+/// it, and a minter that has minted `minted` of `allowance`
+fn deployed(
+    mollusk: &Mollusk,
+    paused: bool,
+    supply: u64,
+    allowance: u64,
+    minted: u64,
+) -> Vec<(Pubkey, Account)> {
+    vec![
+        (ADMIN, wallet()),
+        (MINTER, wallet()),
+        (HOLDER, wallet()),
+        (pda::config().0, program_account(mollusk, &config(paused))),
+        (pda::mint().0, mint(supply)),
+        (
+            pda::supply_stats().0,
+            program_account(mollusk, &supply_stats(supply)),
+        ),
+        (
+            pda::minter_config(&MINTER).0,
+            program_account(mollusk, &minter_config(allowance, minted)),
+        ),
+        (pda::minter_set_freeze().0, Account::default()),
+        (pda::minter_pause(&MINTER).0, Account::default()),
+        (pda::minter_tombstone(&MINTER).0, Account::default()),
+        token::keyed_account(),
+        associated_token::keyed_account(),
+        program::keyed_account_for_system_program(),
+    ]
+}
+
+fn custom(error: StablecoinError) -> Check<'static> {
+    Check::err(ProgramError::Custom(error.into()))
+}
+
+fn report(name: &str, result: &InstructionResult) {
+    println!("{name}: {} CU", result.compute_units_consumed);
+}
+
+// ============================================================================
+// Initialize Tests
+// ============================================================================
+
+#[test]
+fn test_initialize() {
+    let mollusk = mollusk();
+    let accounts = [
+        (ADMIN, wallet()),
+        (pda::config().0, Account::default()),
+        (pda::mint().0, Account::default()),
+        (pda::supply_stats().0, Account::default()),
+        token::keyed_account(),
+        program::keyed_account_for_system_program(),
+    ];
+    let config_data = serialize(&config(false));
+    let stats_data = serialize(&supply_stats(0));
+
+    let result = mollusk.process_and_validate_instruction(
+        &instructions::initialize(&ADMIN),
+        &accounts,
+        &[
+            Check::success(),
+            Check::account(&pda::config().0)
+                .owner(&PROGRAM_ID)
+                .data(&config_data)
+                .rent_exempt()
+                .build(),
+            Check::account(&pda::mint().0)
+                .owner(&token::ID)
+                .space(Mint::LEN)
+                .data_slice(MINT_SUPPLY_OFFSET, &0u64.to_le_bytes())
+                .rent_exempt()
+                .build(),
+            Check::account(&pda::supply_stats().0)
+                .owner(&PROGRAM_ID)
+                .space(sizes::SUPPLY_STATS)
+                .data(&stats_data)
+                .rent_exempt()
+                .build(),
+        ],
+    );
+    report("initialize", &result);
+}
+
+// ============================================================================
+// Minter Tests
+// ============================================================================
+
+#[test]
+fn test_configure_minter() {
+    let mollusk = mollusk();
+    let mut accounts = deployed(&mollusk, false, 0, 0, 0);
+    accounts.retain(|(key, _)| *key != pda::minter_config(&MINTER).0);
+    accounts.push((pda::minter_config(&MINTER).0, Account::default()));
+    let expected = serialize(&minter_config(ALLOWANCE, 0));
+
+    let result = mollusk.process_and_validate_instruction(
+        &instructions::configure_minter(&ADMIN, &MINTER, ALLOWANCE),
+        &accounts,
+        &[
+            Check::success(),
+            Check::account(&pda::minter_config(&MINTER).0)
+                .owner(&PROGRAM_ID)
+                .space(sizes::MINTER_CONFIG)
+                .data(&expected)
+                .rent_exempt()
+                .build(),
+        ],
+    );
+    report("configure_minter", &result);
+}
+
+#[test]
+fn test_configure_minter_update_keeps_amount_minted() {
+    let mollusk = mollusk();
+    let accounts = deployed(&mollusk, false, 300, ALLOWANCE, 300);
+    let expected = serialize(&minter_config(2 * ALLOWANCE, 300));
+
+    let result = mollusk.process_and_validate_instruction(
+        &instructions::configure_minter(&ADMIN, &MINTER, 2 * ALLOWANCE),
+        &accounts,
+        &[
+            Check::success(),
+            Check::account(&pda::minter_config(&MINTER).0)
+                .data(&expected)
+                .build(),
+        ],
+    );
+    report("configure_minter_update", &result);
+}
+
+#[test]
+fn test_remove_minter() {
+    let mollusk = mollusk();
+    let accounts = deployed(&mollusk, false, 0, ALLOWANCE, 0);
+    let rent = mollusk.sysvars.rent.minimum_balance(sizes::MINTER_CONFIG);
+    let tombstone_rent = mollusk
+        .sysvars
+        .rent
+        .minimum_balance(sizes::MINTER_TOMBSTONE);
+
+    let result = mollusk.process_and_validate_instruction(
+        &instructions::remove_minter(&ADMIN, &MINTER),
+        &accounts,
+        &[
+            Check::success(),
+            Check::account(&pda::minter_config(&MINTER).0)
+                .closed()
+                .build(),
+            Check::account(&pda::minter_tombstone(&MINTER).0)
+                .owner(&PROGRAM_ID)
+                .space(sizes::MINTER_TOMBSTONE)
+                .rent_exempt()
+                .build(),
+            // The rent goes back to the admin, less the tombstone's
+            Check::account(&ADMIN)
+                .lamports(FUNDING + rent - tombstone_rent)
+                .build(),
+        ],
+    );
+    report("remove_minter", &result);
+}
+
+// ============================================================================
+// Mint Tokens Tests
+// ============================================================================
+
+#[test]
+fn test_mint_tokens() {
+    let mollusk = mollusk();
+    let mut accounts = deployed(&mollusk, false, 100, ALLOWANCE, 100);
+    let (destination, account) = token_account(&HOLDER, 100);
+    accounts.push((destination, account));
+    let expected = serialize(&minter_config(ALLOWANCE, 600));
+
+    let result = mollusk.process_and_validate_instruction(
+        &instructions::mint_tokens(&MINTER, &HOLDER, 500),
+        &accounts,
+        &[
+            Check::success(),
+            Check::account(&pda::minter_config(&MINTER).0)
+                .data(&expected)
+                .build(),
+            Check::account(&pda::mint().0)
+                .data_slice(MINT_SUPPLY_OFFSET, &600u64.to_le_bytes())
+                .build(),
+            Check::account(&pda::supply_stats().0)
+                .data_slice(STATS_SUPPLY_OFFSET, &600u64.to_le_bytes())
+                .build(),
+            Check::account(&destination)
+                .data_slice(TOKEN_AMOUNT_OFFSET, &600u64.to_le_bytes())
+                .build(),
+        ],
+    );
+    report("mint_tokens", &result);
+}
+
+#[test]
+fn test_mint_tokens_creates_token_account() {
+    let mollusk = mollusk();
+    let mut accounts = deployed(&mollusk, false, 0, ALLOWANCE, 0);
+    let destination = pda::token_account(&HOLDER);
+    accounts.push((destination, Account::default()));
+
+    let result = mollusk.process_and_validate_instruction(
+        &instructions::mint_tokens(&MINTER, &HOLDER, 500),
+        &accounts,
+        &[
+            Check::success(),
+            Check::account(&destination)
+                .owner(&token::ID)
+                .space(TokenAccount::LEN)
+                .data_slice(TOKEN_AMOUNT_OFFSET, &500u64.to_le_bytes())
+                .rent_exempt()
+                .build(),
+        ],
+    );
+    report("mint_tokens_new_account", &result);
+}
+
+#[test]
+fn test_mint_tokens_paused() {
+    let mollusk = mollusk();
+    let mut accounts = deployed(&mollusk, true, 0, ALLOWANCE, 0);
+    accounts.push(token_account(&HOLDER, 0));
+
+    mollusk.process_and_validate_instruction(
+        &instructions::mint_tokens(&MINTER, &HOLDER, 500),
+        &accounts,
+        &[custom(StablecoinError::Paused)],
+    );
+}
+
+#[test]
+fn test_mint_tokens_exceeds_allowance() {
+    let mollusk = mollusk();
+    let mut accounts = deployed(&mollusk, false, 900, 1_000, 900);
+    accounts.push(token_account(&HOLDER, 900));
+
+    mollusk.process_and_validate_instruction(
+        &instructions::mint_tokens(&MINTER, &HOLDER, 101),
+        &accounts,
+        &[custom(StablecoinError::ExceedsAllowance)],
+    );
+}
+
+// ============================================================================
+// Burn Tokens Tests
+// ============================================================================
+
+#[test]
+fn test_burn_tokens() {
+    let mollusk = mollusk();
+    let mut accounts = deployed(&mollusk, false, 500, ALLOWANCE, 500);
+    let (source, account) = token_account(&HOLDER, 500);
+    accounts.push((source, account));
+
+    let result = mollusk.process_and_validate_instruction(
+        &instructions::burn_tokens(&HOLDER, 200),
+        &accounts,
+        &[
+            Check::success(),
+            Check::account(&pda::mint().0)
+                .data_slice(MINT_SUPPLY_OFFSET, &300u64.to_le_bytes())
+                .build(),
+            Check::account(&pda::supply_stats().0)
+                .data_slice(STATS_SUPPLY_OFFSET, &300u64.to_le_bytes())
+                .build(),
+            Check::account(&source)
+                .data_slice(TOKEN_AMOUNT_OFFSET, &300u64.to_le_bytes())
+                .build(),
+        ],
+    );
+    report("burn_tokens", &result);
+}
+
+// ============================================================================
+// Pause/Unpause Tests
+// ============================================================================
+
+#[test]
+fn test_pause() {
+    let mollusk = mollusk();
+    let accounts = deployed(&mollusk, false, 0, ALLOWANCE, 0);
+    let expected = serialize(&config(true));
+
+    let result = mollusk.process_and_validate_instruction(
+        &instructions::pause(&ADMIN),
+        &accounts,
+        &[
+            Check::success(),
+            Check::account(&pda::config().0).data(&expected).build(),
+        ],
+    );
+    report("pause", &result);
+}
+
+#[test]
+fn test_unpause() {
+    let mollusk = mollusk();
+    let accounts = deployed(&mollusk, true, 0, ALLOWANCE, 0);
+    let expected = serialize(&config(false));
+
+    let result = mollusk.process_and_validate_instruction(
+        &instructions::unpause(&ADMIN),
+        &accounts,
+        &[
+            Check::success(),
+            Check::account(&pda::config().0).data(&expected).build(),
+        ],
+    );
+    report("unpause", &result);
+}
+
+#[test]
+fn test_pause_unauthorized() {
+    let mollusk = mollusk();
+    let accounts = deployed(&mollusk, false, 0, ALLOWANCE, 0);
+
+    mollusk.process_and_validate_instruction(
+        &instructions::pause(&HOLDER),
+        &accounts,
+        &[custom(StablecoinError::Unauthorized)],
+    );
+}
@@ -0,0 +1,526 @@
+use anchor_litesvm::{Instruction, Keypair, Signer};
+use anchor_spl::token::spl_token;
+use stablecoin::{
+    StablecoinError, IDEMPOTENCY_KEY_TTL, MIN_CHANNEL_DISPUTE_PERIOD,
+    MIN_INHERITANCE_NOTICE_PERIOD, MIN_RECOVERY_CHALLENGE_PERIOD,
+};
+use stablecoin_test_utils::{
+    instructions::{self, StablecoinInstruction},
+    pda, TestContext,
+};
+
+const ALLOWANCE: u64 = 1_000_000_000;
+const MINTED: u64 = 100_000_000;
+
+/// Every combination of pause flags the config can hold
+const STATES: [PauseState; 2] = [PauseState { paused: false }, PauseState { paused: true }];
+
+#[derive(Debug, Clone, Copy)]
+struct PauseState {
+    paused: bool,
+}
+
+/// The error `instruction` must fail with in `state`, `None` if it must go
+/// through
+///
+/// Deliberately without a wildcard arm: a new instruction does not compile
+/// until it is placed in the matrix.
+fn blocked_by(instruction: &StablecoinInstruction, state: PauseState) -> Option<StablecoinError> {
+    match instruction {
+        StablecoinInstruction::MintTokens { .. }
+        | StablecoinInstruction::MintTokensIdempotent { .. }
+            if state.paused =>
+        {
+            Some(StablecoinError::Paused)
+        }
+        StablecoinInstruction::MintTokens { .. }
+        | StablecoinInstruction::MintTokensIdempotent { .. } => None,
+        // Holders can always leave, and so can the treasury; the admin can
+        // always manage minters and officers and undo or repeat a pause,
+        // guardians and beneficiaries can always recover or inherit a holder,
+        // institutions and channels can always settle what they owe,
+        // holders can always register sub-accounts and sweep them, and
+        // airdrops can always be funded, claimed and clawed back
+        StablecoinInstruction::BurnTokens { .. }
+        | StablecoinInstruction::SweepDust { .. }
+        | StablecoinInstruction::ConfigureMinter { .. }
+        | StablecoinInstruction::RemoveMinter
+        | StablecoinInstruction::FreezeMinterSet
+        | StablecoinInstruction::UnfreezeMinterSet
+        | StablecoinInstruction::ReclaimIdempotencyRecord
+        | StablecoinInstruction::PauseSelf
+        | StablecoinInstruction::ResumeMinter
+        | StablecoinInstruction::ConfigureTreasury { .. }
+        | StablecoinInstruction::ProposeTreasuryBurn { .. }
+        | StablecoinInstruction::ApproveTreasuryBurn
+        | StablecoinInstruction::ExecuteTreasuryBurn
+        | StablecoinInstruction::CancelTreasuryBurn
+        | StablecoinInstruction::ConfigureRecovery { .. }
+        | StablecoinInstruction::InitiateRecovery { .. }
+        | StablecoinInstruction::ApproveRecovery
+        | StablecoinInstruction::ExecuteRecovery
+        | StablecoinInstruction::CancelRecovery
+        | StablecoinInstruction::CloseRecovery
+        | StablecoinInstruction::ConfigureInheritance { .. }
+        | StablecoinInstruction::CheckIn
+        | StablecoinInstruction::StartInheritanceClaim
+        | StablecoinInstruction::ClaimInheritance
+        | StablecoinInstruction::CloseInheritance
+        | StablecoinInstruction::OpenNettingWindow { .. }
+        | StablecoinInstruction::QueueObligation { .. }
+        | StablecoinInstruction::SettleNet
+        | StablecoinInstruction::CancelNettingWindow
+        | StablecoinInstruction::RegisterPurposeCode { .. }
+        | StablecoinInstruction::RetirePurposeCode
+        | StablecoinInstruction::OpenChannel { .. }
+        | StablecoinInstruction::CloseChannel { .. }
+        | StablecoinInstruction::SettleChannel
+        | StablecoinInstruction::RegisterSubAccount { .. }
+        | StablecoinInstruction::SweepSubAccount
+        | StablecoinInstruction::OpenAirdrop { .. }
+        | StablecoinInstruction::ClaimAirdrop
+        | StablecoinInstruction::ClawbackAirdrop
+        | StablecoinInstruction::ResizeAccount
+        | StablecoinInstruction::SyncSupplyStats
+        | StablecoinInstruction::Pause
+        | StablecoinInstruction::Unpause => None,
+        StablecoinInstruction::Initialize => {
+            unreachable!("initialize runs before there is any pause state")
+        }
+    }
+}
+
+/// Key of the idempotent mint the scene makes, whose record has expired
+const EXPIRED_KEY: [u8; 16] = [1; 16];
+
+/// Id of the treasury burn the scene proposes, which needs no more
+/// approvals
+const PROPOSED: u64 = 1;
+
+/// Purpose code the admin has registered in the scene
+const PURPOSE: [u8; 4] = *b"PAYR";
+
+/// Index of the sub-account the holder has registered in the scene
+const SUB_ACCOUNT: u64 = 7;
+
+/// Id of the netting window the scene leaves open
+const OPEN_WINDOW: u64 = 1;
+
+/// Id of the netting window the scene lets close, with an obligation queued
+const CLOSED_WINDOW: u64 = 2;
+
+/// Id of the holder's airdrop to the other wallet the scene leaves open
+const OPEN_AIRDROP: u64 = 1;
+
+/// Id of the holder's airdrop to the other wallet the scene lets close
+const CLOSED_AIRDROP: u64 = 2;
+
+/// An initialized stablecoin in `state`, whose minter has minted to a
+/// holder and to a wallet that is not a minter, once idempotently, and to
+/// treasury custody, whose officers are those two and have proposed a burn
+///
+/// The holder has also moved a token into a second account, `dust`. The
+/// holder and the other wallet have opted into recovery, the holder with
+/// the minter and the other wallet as guardians; the minter has started
+/// recovering the holder to the admin, and the challenge period has passed.
+/// The holder has also made the other wallet its beneficiary, and the other
+/// wallet the minter; neither has checked in for an epoch, the other wallet
+/// has started a claim on the holder's balance, and the notice period has
+/// passed. The admin has registered a purpose code, which the holder has
+/// filed a sub-account under, and opened two netting windows between the
+/// holder and the other wallet; in the one that has since closed, the
+/// holder owes the other wallet a token. The holder has also opened two
+/// airdrops of a token to the other wallet, one of which has closed
+/// unclaimed. The minter has
+/// minted to itself and opened payment channels to both wallets, and closed
+/// the one to the holder, whose dispute period has passed.
+struct Scene {
+    ctx: TestContext,
+    admin: Keypair,
+    minter: Keypair,
+    holder: Keypair,
+    other: Keypair,
+    dust: Keypair,
+}
+
+fn scene(state: PauseState) -> Scene {
+    let mut ctx = TestContext::new();
+    let admin = ctx.admin.insecure_clone();
+    let minter = ctx.funded_account();
+    let holder = ctx.funded_account();
+    let other = ctx.funded_account();
+    let dust = Keypair::new();
+    ctx.initialize()
+        .configure_minter(&minter, ALLOWANCE)
+        .mint(&minter, &holder, MINTED)
+        .mint(&minter, &other, MINTED)
+        .open_token_account(&dust, &holder, 1);
+    ctx.expect_success(
+        instructions::mint_tokens_idempotent(&minter.pubkey(), &holder.pubkey(), 1, EXPIRED_KEY),
+        &[&minter],
+    );
+    ctx.advance_time(IDEMPOTENCY_KEY_TTL as u64);
+    ctx.configure_treasury([&holder, &other], MINTED);
+    ctx.expect_success(
+        instructions::mint_tokens(&minter.pubkey(), &pda::treasury().0, MINTED),
+        &[&minter],
+    );
+    ctx.expect_success(
+        instructions::propose_treasury_burn(&holder.pubkey(), PROPOSED, 1),
+        &[&holder],
+    );
+    ctx.configure_recovery(&holder, &[&minter, &other], 1)
+        .configure_recovery(&other, &[&minter], 1);
+    ctx.expect_success(
+        instructions::initiate_recovery(&minter.pubkey(), &holder.pubkey(), &admin.pubkey()),
+        &[&minter],
+    );
+    ctx.advance_time(MIN_RECOVERY_CHALLENGE_PERIOD as u64);
+    ctx.configure_inheritance(&holder, &other, 1)
+        .configure_inheritance(&other, &minter, 1)
+        .advance_epochs(1);
+    ctx.expect_success(
+        instructions::start_inheritance_claim(&other.pubkey(), &holder.pubkey()),
+        &[&other],
+    );
+    ctx.register_purpose_code(PURPOSE)
+        .register_sub_account(&holder, SUB_ACCOUNT, PURPOSE)
+        .open_netting_window(
+            OPEN_WINDOW,
+            &[&holder, &other],
+            MIN_INHERITANCE_NOTICE_PERIOD * 2,
+        )
+        .open_netting_window(CLOSED_WINDOW, &[&holder, &other], 1)
+        .queue_obligation(&holder, CLOSED_WINDOW, &other, 1);
+    ctx.open_airdrop(
+        &holder,
+        OPEN_AIRDROP,
+        &[(&other, 1)],
+        MIN_INHERITANCE_NOTICE_PERIOD * 2,
+    )
+    .open_airdrop(&holder, CLOSED_AIRDROP, &[(&other, 1)], 1);
+    ctx.mint(&minter, &minter, MINTED)
+        .open_channel(&minter, &holder, 1)
+        .open_channel(&minter, &other, 1);
+    ctx.expect_success(
+        instructions::close_channel(
+            &minter.pubkey(),
+            &minter.pubkey(),
+            &holder.pubkey(),
+            0,
+            [0; 64],
+        ),
+        &[&minter],
+    );
+    ctx.advance_time(MIN_INHERITANCE_NOTICE_PERIOD as u64);
+    if state.paused {
+        ctx.pause();
+    }
+    Scene {
+        ctx,
+        admin,
+        minter,
+        holder,
+        other,
+        dust,
+    }
+}
+
+/// Every instruction that can run once initialized, with its signer, in
+/// each of the ways it can be called
+fn operations(s: &Scene) -> Vec<(Instruction, &Keypair)> {
+    let admin = s.admin.pubkey();
+    vec![
+        (
+            instructions::configure_minter(&admin, &s.other.pubkey(), ALLOWANCE),
+            &s.admin,
+        ),
+        (
+            instructions::configure_minter(&admin, &s.minter.pubkey(), ALLOWANCE * 2),
+            &s.admin,
+        ),
+        (
+            instructions::remove_minter(&admin, &s.minter.pubkey()),
+            &s.admin,
+        ),
+        (instructions::freeze_minter_set(&admin), &s.admin),
+        (
+            instructions::mint_tokens(&s.minter.pubkey(), &s.holder.pubkey(), 1),
+            &s.minter,
+        ),
+        (
+            instructions::mint_tokens_idempotent(
+                &s.minter.pubkey(),
+                &s.holder.pubkey(),
+                1,
+                [2; 16],
+            ),
+            &s.minter,
+        ),
+        (
+            instructions::reclaim_idempotency_record(&s.minter.pubkey(), &EXPIRED_KEY),
+            &s.minter,
+        ),
+        (instructions::pause_self(&s.minter.pubkey()), &s.minter),
+        (instructions::burn_tokens(&s.holder.pubkey(), 1), &s.holder),
+        (
+            instructions::sweep_dust(&s.holder.pubkey(), &[s.dust.pubkey()], 1),
+            &s.holder,
+        ),
+        (
+            instructions::configure_treasury(&admin, [s.holder.pubkey(), s.other.pubkey()], 0),
+            &s.admin,
+        ),
+        (
+            instructions::propose_treasury_burn(&s.holder.pubkey(), PROPOSED + 1, 1),
+            &s.holder,
+        ),
+        (
+            instructions::approve_treasury_burn(&s.other.pubkey(), PROPOSED),
+            &s.other,
+        ),
+        (
+            instructions::execute_treasury_burn(&s.holder.pubkey(), &s.holder.pubkey(), PROPOSED),
+            &s.holder,
+        ),
+        (
+            instructions::cancel_treasury_burn(&s.other.pubkey(), &s.holder.pubkey(), PROPOSED),
+            &s.other,
+        ),
+        (
+            instructions::configure_recovery(
+                &s.holder.pubkey(),
+                vec![s.other.pubkey()],
+                1,
+                MIN_RECOVERY_CHALLENGE_PERIOD,
+            ),
+            &s.holder,
+        ),
+        (
+            instructions::initiate_recovery(&s.minter.pubkey(), &s.other.pubkey(), &admin),
+            &s.minter,
+        ),
+        (
+            instructions::approve_recovery(&s.other.pubkey(), &s.holder.pubkey()),
+            &s.other,
+        ),
+        (
+            instructions::execute_recovery(&s.other.pubkey(), &s.holder.pubkey(), &admin),
+            &s.other,
+        ),
+        (instructions::cancel_recovery(&s.holder.pubkey()), &s.holder),
+        (instructions::close_recovery(&s.holder.pubkey()), &s.holder),
+        (
+            instructions::configure_inheritance(
+                &s.holder.pubkey(),
+                &s.other.pubkey(),
+                1,
+                MIN_INHERITANCE_NOTICE_PERIOD,
+            ),
+            &s.holder,
+        ),
+        (instructions::check_in(&s.holder.pubkey()), &s.holder),
+        (
+            instructions::start_inheritance_claim(&s.minter.pubkey(), &s.other.pubkey()),
+            &s.minter,
+        ),
+        (
+            instructions::claim_inheritance(&s.other.pubkey(), &s.holder.pubkey()),
+            &s.other,
+        ),
+        (
+            instructions::close_inheritance(&s.holder.pubkey()),
+            &s.holder,
+        ),
+        (
+            instructions::open_netting_window(
+                &admin,
+                CLOSED_WINDOW + 1,
+                vec![s.holder.pubkey(), s.other.pubkey()],
+                1,
+            ),
+            &s.admin,
+        ),
+        (
+            instructions::queue_obligation(
+                &s.holder.pubkey(),
+                OPEN_WINDOW,
+                &s.other.pubkey(),
+                1,
+                Some(&PURPOSE),
+            ),
+            &s.holder,
+        ),
+        (
+            instructions::settle_net(
+                &s.holder.pubkey(),
+                &admin,
+                CLOSED_WINDOW,
+                &[s.holder.pubkey(), s.other.pubkey()],
+            ),
+            &s.holder,
+        ),
+        (
+            instructions::cancel_netting_window(
+                &admin,
+                &admin,
+                OPEN_WINDOW,
+                &[s.holder.pubkey(), s.other.pubkey()],
+            ),
+            &s.admin,
+        ),
+        (
+            instructions::register_purpose_code(&admin, *b"RMIT"),
+            &s.admin,
+        ),
+        (
+            instructions::retire_purpose_code(&admin, &admin, &PURPOSE),
+            &s.admin,
+        ),
+        (
+            instructions::open_channel(&s.minter.pubkey(), &admin, 1, MIN_CHANNEL_DISPUTE_PERIOD),
+            &s.minter,
+        ),
+        (
+            instructions::close_channel(
+                &s.other.pubkey(),
+                &s.minter.pubkey(),
+                &s.other.pubkey(),
+                0,
+                [0; 64],
+            ),
+            &s.other,
+        ),
+        (
+            instructions::settle_channel(
+                &s.holder.pubkey(),
+                &s.minter.pubkey(),
+                &s.holder.pubkey(),
+            ),
+            &s.holder,
+        ),
+        (
+            instructions::register_sub_account(&s.holder.pubkey(), SUB_ACCOUNT + 1, &PURPOSE),
+            &s.holder,
+        ),
+        (
+            instructions::sweep_sub_account(&s.holder.pubkey(), SUB_ACCOUNT),
+            &s.holder,
+        ),
+        (
+            instructions::open_airdrop(
+                &s.holder.pubkey(),
+                CLOSED_AIRDROP + 1,
+                vec![s.other.pubkey()],
+                vec![1],
+                1,
+            ),
+            &s.holder,
+        ),
+        (
+            instructions::claim_airdrop(&s.other.pubkey(), &s.holder.pubkey(), OPEN_AIRDROP),
+            &s.other,
+        ),
+        (
+            instructions::clawback_airdrop(&s.other.pubkey(), &s.holder.pubkey(), CLOSED_AIRDROP),
+            &s.other,
+        ),
+        (
+            instructions::resize_account(&admin, &admin, &pda::supply_stats().0),
+            &s.admin,
+        ),
+        (
+            instructions::sync_supply_stats(&s.holder.pubkey()),
+            &s.holder,
+        ),
+        (instructions::pause(&admin), &s.admin),
+        (instructions::unpause(&admin), &s.admin),
+    ]
+}
+
+// ============================================================================
+// Pause Matrix Tests
+// ============================================================================
+
+#[test]
+fn test_pause_matrix() {
+    for state in STATES {
+        let mut s = scene(state);
+        // Every cell starts from the same state
+        let snapshot = s.ctx.svm.clone();
+        let operations: Vec<(Instruction, Keypair)> = operations(&s)
+            .into_iter()
+            .map(|(ix, signer)| (ix, signer.insecure_clone()))
+            .collect();
+
+        for (ix, signer) in operations {
+            s.ctx.svm = snapshot.clone();
+            let instruction = instructions::decode(&ix.data).unwrap();
+            let cell = format!("{} with {state:?}", instruction.name());
+
+            let result = s.ctx.execute(ix, &[&signer]);
+            match blocked_by(&instruction, state) {
+                Some(error) => {
+                    assert!(!result.is_success(), "{cell} went through");
+                    result.assert_error_code(error.into());
+                }
+                None => assert!(
+                    result.is_success(),
+                    "{cell} was blocked: {:?}",
+                    result.error()
+                ),
+            }
+
+            // Only pause and unpause move the flag
+            let paused = match instruction {
+                StablecoinInstruction::Pause if result.is_success() => true,
+                StablecoinInstruction::Unpause if result.is_success() => false,
+                _ => state.paused,
+            };
+            assert_eq!(s.ctx.config().paused, paused, "{cell}: paused afterwards");
+        }
+    }
+}
+
+#[test]
+fn test_pause_leaves_minter_set_freeze_alone() {
+    // The scene leaves the minter set unfrozen, so the matrix cannot reach
+    // unfreeze_minter_set; both directions go through in either state
+    for state in STATES {
+        let mut s = scene(state);
+        s.ctx.freeze_minter_set().unfreeze_minter_set();
+        assert_eq!(s.ctx.config().paused, state.paused, "{state:?}");
+    }
+}
+
+#[test]
+fn test_pause_leaves_minter_self_pause_alone() {
+    // A minter's kill switch works in either state, and the scene leaves
+    // the minter unpaused, so the matrix cannot reach resume_minter
+    for state in STATES {
+        let mut s = scene(state);
+        let minter = s.minter.insecure_clone();
+        s.ctx.pause_self(&minter).resume_minter(&minter);
+        assert_eq!(s.ctx.config().paused, state.paused, "{state:?}");
+    }
+}
+
+#[test]
+fn test_pause_leaves_transfers_alone() {
+    // Pausing is the program's control over minting; SPL Token transfers
+    // never pass through it
+    for state in STATES {
+        let mut s = scene(state);
+        let ix = spl_token::instruction::transfer(
+            &spl_token::ID,
+            &s.ctx.token_account(&s.holder),
+            &s.ctx.token_account(&s.other),
+            &s.holder.pubkey(),
+            &[],
+            MINTED,
+        )
+        .unwrap();
+        s.ctx.expect_success(ix, &[&s.holder]);
+        assert_eq!(s.ctx.balance(&s.other), MINTED * 2, "{state:?}");
+    }
+}
@@ -0,0 +1,231 @@
+use anchor_lang::{
+    error::ErrorCode, prelude::Pubkey, solana_program::program_pack::Pack, AccountSerialize,
+};
+use anchor_litesvm::Signer;
+use anchor_spl::token::spl_token::state::{Account as TokenAccount, Mint};
+use litesvm_utils::AssertionHelpers;
+use stablecoin::{sizes, Config, MinterConfig, StablecoinError};
+use stablecoin_test_utils::{instructions, pda, substitute, TestContext, PROGRAM_ID};
+
+fn serialized_len(account: &impl AccountSerialize) -> usize {
+    let mut data = Vec::new();
+    account.try_serialize(&mut data).unwrap();
+    data.len()
+}
+
+/// Assert every account in `expected` exists with its size and owner, and
+/// holds enough lamports to be rent exempt
+fn assert_rent_exempt(ctx: &TestContext, expected: &[(&str, Pubkey, usize, Pubkey)]) {
+    for (name, address, size, owner) in expected {
+        let account = ctx
+            .svm
+            .get_account(address)
+            .unwrap_or_else(|| panic!("{name} was not created"));
+        assert_eq!(account.data.len(), *size, "{name} size");
+        assert_eq!(account.owner, *owner, "{name} owner");
+        let minimum = ctx.svm.minimum_balance_for_rent_exemption(*size);
+        assert!(
+            account.lamports >= minimum,
+            "{name} holds {} lamports, {minimum} needed to be rent exempt",
+            account.lamports
+        );
+    }
+}
+
+// Every field is fixed size and no padding is reserved, so the declared
+// space is exactly the serialized size at any field values: adding a field
+// means a migration, not a quiet write into spare bytes.
+
+#[test]
+fn test_config_size() {
+    let empty = Config {
+        admin: Pubkey::default(),
+        mint: Pubkey::default(),
+        paused: false,
+        bump: 0,
+        mint_bump: 0,
+        delegate_bump: 0,
+        minter_set_freeze_bump: 0,
+    };
+    let full = Config {
+        admin: Pubkey::new_from_array([u8::MAX; 32]),
+        mint: Pubkey::new_from_array([u8::MAX; 32]),
+        paused: true,
+        bump: u8::MAX,
+        mint_bump: u8::MAX,
+        delegate_bump: u8::MAX,
+        minter_set_freeze_bump: u8::MAX,
+    };
+    assert_eq!(serialized_len(&empty), sizes::CONFIG);
+    assert_eq!(serialized_len(&full), sizes::CONFIG);
+}
+
+#[test]
+fn test_minter_config_size() {
+    let empty = MinterConfig {
+        minter: Pubkey::default(),
+        allowance: 0,
+        amount_minted: 0,
+        is_initialized: false,
+        bump: 0,
+        pause_bump: 0,
+    };
+    let full = MinterConfig {
+        minter: Pubkey::new_from_array([u8::MAX; 32]),
+        allowance: u64::MAX,
+        amount_minted: u64::MAX,
+        is_initialized: true,
+        bump: u8::MAX,
+        pause_bump: u8::MAX,
+    };
+    assert_eq!(serialized_len(&empty), sizes::MINTER_CONFIG);
+    assert_eq!(serialized_len(&full), sizes::MINTER_CONFIG);
+}
+
+#[test]
+fn test_created_accounts_are_rent_exempt() {
+    let mut ctx = TestContext::new();
+    let minter = ctx.funded_account();
+    let user = ctx.funded_account();
+    ctx.initialize()
+        .configure_minter(&minter, 1_000_000_000)
+        .mint(&minter, &user, 100_000_000);
+
+    // Every account the program creates, directly or through CPI
+    let created = [
+        ("config", pda::config().0, sizes::CONFIG, PROGRAM_ID),
+        ("mint", pda::mint().0, Mint::LEN, anchor_spl::token::ID),
+        (
+            "supply stats",
+            pda::supply_stats().0,
+            sizes::SUPPLY_STATS,
+            PROGRAM_ID,
+        ),
+        (
+            "minter config",
+            pda::minter_config(&minter.pubkey()).0,
+            sizes::MINTER_CONFIG,
+            PROGRAM_ID,
+        ),
+        (
+            "token account",
+            ctx.token_account(&user),
+            TokenAccount::LEN,
+            anchor_spl::token::ID,
+        ),
+    ];
+    assert_rent_exempt(&ctx, &created);
+
+    // Updating accounts in place keeps them so
+    ctx.configure_minter(&minter, 2_000_000_000)
+        .mint(&minter, &user, 200_000_000)
+        .burn(&user, 50_000_000)
+        .pause();
+    assert_rent_exempt(&ctx, &created);
+
+    // As does the tombstone removing a minter leaves
+    ctx.remove_minter(&minter);
+    assert_rent_exempt(
+        &ctx,
+        &[(
+            "minter tombstone",
+            pda::minter_tombstone(&minter.pubkey()).0,
+            sizes::MINTER_TOMBSTONE,
+            PROGRAM_ID,
+        )],
+    );
+}
+
+// remove_minter is the only instruction that closes an account. The refund
+// goes to the admin account, which must also be the config's admin and sign,
+// less the rent of the tombstone it pays for; fee payers and anyone else
+// passed in get nothing.
+
+#[test]
+fn test_remove_minter_refunds_admin() {
+    let mut ctx = TestContext::new();
+    let minter = ctx.funded_account();
+    let payer = ctx.funded_account();
+    ctx.initialize().configure_minter(&minter, 1_000_000_000);
+    let admin = ctx.admin.insecure_clone();
+    let minter_config = pda::minter_config(&minter.pubkey()).0;
+    let rent = ctx.svm.get_account(&minter_config).unwrap().lamports;
+    let tombstone_rent = ctx
+        .svm
+        .minimum_balance_for_rent_exemption(sizes::MINTER_TOMBSTONE);
+
+    // A separate fee payer, so the admin's balance moves by the refund alone
+    let before = [admin.pubkey(), payer.pubkey(), minter.pubkey()]
+        .map(|address| ctx.svm.get_balance(&address).unwrap());
+    let ix = instructions::remove_minter(&admin.pubkey(), &minter.pubkey());
+    ctx.expect_success(ix, &[&payer, &admin]);
+    let after = [admin.pubkey(), payer.pubkey(), minter.pubkey()]
+        .map(|address| ctx.svm.get_balance(&address).unwrap());
+
+    assert_eq!(after[0], before[0] + rent - tombstone_rent, "admin");
+    assert!(after[1] < before[1], "fee payer");
+    assert_eq!(after[2], before[2], "minter");
+    ctx.svm.assert_account_closed(&minter_config);
+}
+
+#[test]
+fn test_remove_minter_refunds_excess_lamports() {
+    let mut ctx = TestContext::new();
+    let minter = ctx.funded_account();
+    let payer = ctx.funded_account();
+    ctx.initialize().configure_minter(&minter, 1_000_000_000);
+    let admin = ctx.admin.insecure_clone();
+    let minter_config = pda::minter_config(&minter.pubkey()).0;
+
+    // Lamports anyone sent on top of rent leave with it, not stay behind
+    let mut account = ctx.svm.get_account(&minter_config).unwrap();
+    account.lamports += 1_234_567;
+    let lamports = account.lamports;
+    ctx.svm.set_account(minter_config, account).unwrap();
+
+    let tombstone_rent = ctx
+        .svm
+        .minimum_balance_for_rent_exemption(sizes::MINTER_TOMBSTONE);
+    let before = ctx.svm.get_balance(&admin.pubkey()).unwrap();
+    let ix = instructions::remove_minter(&admin.pubkey(), &minter.pubkey());
+    ctx.expect_success(ix, &[&payer, &admin]);
+    let after = ctx.svm.get_balance(&admin.pubkey()).unwrap();
+
+    assert_eq!(after, before + lamports - tombstone_rent);
+    ctx.svm.assert_account_closed(&minter_config);
+}
+
+#[test]
+fn test_remove_minter_rejects_other_refund_destination() {
+    let mut ctx = TestContext::new();
+    let minter = ctx.funded_account();
+    let attacker = ctx.funded_account();
+    let payer = ctx.funded_account();
+    ctx.initialize().configure_minter(&minter, 1_000_000_000);
+    let admin = ctx.admin.insecure_clone();
+    let minter_config = pda::minter_config(&minter.pubkey()).0;
+    let rent = ctx.svm.get_account(&minter_config).unwrap().lamports;
+
+    // The attacker names themselves as the destination; the minter, whose
+    // config it is, fares no better
+    for destination in [&attacker, &minter] {
+        let before = ctx.svm.get_balance(&destination.pubkey()).unwrap();
+        let ix = instructions::remove_minter(&destination.pubkey(), &minter.pubkey());
+        let result = ctx.expect_failure(ix, &[&payer, destination]);
+        result.assert_error_code(StablecoinError::Unauthorized.into());
+        assert_eq!(ctx.svm.get_balance(&destination.pubkey()).unwrap(), before);
+    }
+
+    // The admin's signature does not carry over to a destination it did
+    // not sign as
+    let ix = substitute(
+        instructions::remove_minter(&admin.pubkey(), &minter.pubkey()),
+        &admin.pubkey(),
+        &attacker.pubkey(),
+    );
+    let result = ctx.expect_failure(ix, &[&admin]);
+    result.assert_error_code(ErrorCode::AccountNotSigner.into());
+
+    assert_eq!(ctx.svm.get_account(&minter_config).unwrap().lamports, rent);
+    assert!(ctx.minter_config(&minter).is_initialized);
+}
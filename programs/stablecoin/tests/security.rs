@@ -0,0 +1,420 @@
+// Known exploit patterns, one named regression per attack on this program's
+// accounts. Every audit finding lands here as a permanent test named after
+// it, next to the pattern it belongs to.
+
+use anchor_lang::{error::ErrorCode, solana_program::system_instruction::transfer, system_program};
+use anchor_litesvm::{Keypair, Pubkey, Signer, TransactionResult};
+use stablecoin::{sizes, MinterConfig, StablecoinError};
+use stablecoin_test_utils::{instructions, pda, substitute, TestContext, PROGRAM_ID};
+
+const ALLOWANCE: u64 = 1_000_000_000;
+const MINTED: u64 = 100_000_000;
+
+/// `SystemError::AccountAlreadyInUse`, from allocating an account twice
+const ACCOUNT_ALREADY_IN_USE: u32 = 0;
+
+/// An initialized stablecoin with a minter that has minted to a holder
+struct Scene {
+    ctx: TestContext,
+    admin: Keypair,
+    minter: Keypair,
+    holder: Keypair,
+    /// Funded, but holds no role
+    attacker: Keypair,
+}
+
+fn scene() -> Scene {
+    let mut ctx = TestContext::new();
+    let admin = ctx.admin.insecure_clone();
+    let minter = ctx.funded_account();
+    let holder = ctx.funded_account();
+    let attacker = ctx.funded_account();
+    ctx.initialize()
+        .configure_minter(&minter, ALLOWANCE)
+        .mint(&minter, &holder, MINTED);
+    Scene {
+        ctx,
+        admin,
+        minter,
+        holder,
+        attacker,
+    }
+}
+
+fn assert_error(result: &TransactionResult, error: ErrorCode) {
+    result.assert_error_code(error.into());
+}
+
+// ============================================================================
+// Arbitrary CPI Tests
+// ============================================================================
+
+#[test]
+fn test_arbitrary_cpi_every_program_account_pinned() {
+    let mut s = scene();
+    let programs = [
+        anchor_spl::token::ID,
+        anchor_spl::associated_token::ID,
+        system_program::ID,
+    ];
+    // Every instruction that takes a program account, with its signer. The
+    // config PDA signs the mint and burn CPIs, so a program the caller picks
+    // would be handed the mint authority.
+    let cases = [
+        (instructions::initialize(&s.admin.pubkey()), &s.admin),
+        (
+            instructions::configure_minter(&s.admin.pubkey(), &s.attacker.pubkey(), ALLOWANCE),
+            &s.admin,
+        ),
+        (
+            instructions::mint_tokens(&s.minter.pubkey(), &s.attacker.pubkey(), MINTED),
+            &s.minter,
+        ),
+        (
+            instructions::burn_tokens(&s.holder.pubkey(), MINTED),
+            &s.holder,
+        ),
+    ];
+
+    // Each case starts from the same state; a success would otherwise change
+    // what the next one sees
+    let snapshot = s.ctx.svm.clone();
+    for (ix, signer) in cases {
+        let expected = programs
+            .iter()
+            .filter(|id| ix.accounts.iter().any(|meta| meta.pubkey == **id));
+        for program in expected {
+            // The program itself stands in for one the attacker deployed,
+            // and the other real programs for a mix-up
+            let substitutes = programs
+                .iter()
+                .chain([&PROGRAM_ID])
+                .filter(|id| *id != program);
+            for fake in substitutes {
+                s.ctx.svm = snapshot.clone();
+                let result = s
+                    .ctx
+                    .execute(substitute(ix.clone(), program, fake), &[signer]);
+                assert_error(&result, ErrorCode::InvalidProgramId);
+            }
+        }
+    }
+}
+
+// ============================================================================
+// Type Cosplay Tests
+// ============================================================================
+
+#[test]
+fn test_type_cosplay_minter_config_as_config() {
+    let mut s = scene();
+    let minter_config = pda::minter_config(&s.minter.pubkey()).0;
+
+    // Every instruction reading the config, handed the minter's config
+    let cases = [
+        (instructions::pause(&s.admin.pubkey()), &s.admin),
+        (
+            instructions::configure_minter(&s.admin.pubkey(), &s.attacker.pubkey(), ALLOWANCE),
+            &s.admin,
+        ),
+        (
+            instructions::burn_tokens(&s.holder.pubkey(), MINTED),
+            &s.holder,
+        ),
+    ];
+    for (ix, signer) in cases {
+        let ix = substitute(ix, &pda::config().0, &minter_config);
+        let result = s.ctx.expect_failure(ix, &[signer]);
+        assert_error(&result, ErrorCode::AccountDiscriminatorMismatch);
+    }
+    assert!(!s.ctx.config().paused);
+    assert_eq!(s.ctx.balance(&s.holder), MINTED);
+}
+
+#[test]
+fn test_type_cosplay_config_as_minter_config() {
+    let mut s = scene();
+    let ix = substitute(
+        instructions::mint_tokens(&s.minter.pubkey(), &s.holder.pubkey(), MINTED),
+        &pda::minter_config(&s.minter.pubkey()).0,
+        &pda::config().0,
+    );
+
+    let result = s.ctx.expect_failure(ix, &[&s.minter]);
+    assert_error(&result, ErrorCode::AccountDiscriminatorMismatch);
+    assert_eq!(s.ctx.balance(&s.holder), MINTED);
+}
+
+#[test]
+fn test_type_cosplay_token_accounts_as_program_accounts() {
+    let mut s = scene();
+    // Real accounts at real addresses, but SPL Token's: the mint for the
+    // config, a token account for the minter config
+    let cases = [
+        (
+            instructions::pause(&s.admin.pubkey()),
+            pda::config().0,
+            pda::mint().0,
+            &s.admin,
+        ),
+        (
+            instructions::mint_tokens(&s.minter.pubkey(), &s.holder.pubkey(), MINTED),
+            pda::minter_config(&s.minter.pubkey()).0,
+            s.ctx.token_account(&s.holder),
+            &s.minter,
+        ),
+    ];
+    for (ix, from, to, signer) in cases {
+        let ix = substitute(ix, &from, &to);
+        let result = s.ctx.expect_failure(ix, &[signer]);
+        assert_error(&result, ErrorCode::AccountOwnedByWrongProgram);
+    }
+    assert!(!s.ctx.config().paused);
+    assert_eq!(s.ctx.balance(&s.holder), MINTED);
+}
+
+#[test]
+fn test_type_cosplay_program_layout_under_another_owner() {
+    let mut s = scene();
+    // Byte for byte the real minter config, discriminator included, but in
+    // an account the attacker owns rather than the program
+    let copy = Pubkey::new_unique();
+    let data = s
+        .ctx
+        .svm
+        .get_account(&pda::minter_config(&s.minter.pubkey()).0)
+        .unwrap()
+        .data;
+    let lamports = s.ctx.svm.minimum_balance_for_rent_exemption(data.len());
+    s.ctx
+        .svm
+        .set_account(
+            copy,
+            solana_account::Account {
+                lamports,
+                data,
+                owner: system_program::ID,
+                executable: false,
+                rent_epoch: 0,
+            },
+        )
+        .unwrap();
+
+    let ix = substitute(
+        instructions::mint_tokens(&s.minter.pubkey(), &s.holder.pubkey(), MINTED),
+        &pda::minter_config(&s.minter.pubkey()).0,
+        &copy,
+    );
+    let result = s.ctx.expect_failure(ix, &[&s.minter]);
+    assert_error(&result, ErrorCode::AccountOwnedByWrongProgram);
+}
+
+// ============================================================================
+// Re-initialization Tests
+// ============================================================================
+
+#[test]
+fn test_reinitialize_config_rejected() {
+    let mut s = scene();
+    let config = s.ctx.config();
+
+    // Another initialize, by anyone, would hand over the admin role
+    for signer in [&s.attacker, &s.admin] {
+        let ix = instructions::initialize(&signer.pubkey());
+        let result = s.ctx.expect_failure(ix, &[signer]);
+        result.assert_error_code(ACCOUNT_ALREADY_IN_USE);
+    }
+    assert_eq!(s.ctx.config(), config);
+    assert_eq!(s.ctx.mint_state().supply, MINTED);
+}
+
+#[test]
+fn test_reconfigure_minter_keeps_amount_minted() {
+    let mut s = scene();
+    // init_if_needed runs on every configure_minter; if it reset the
+    // minter's state, reconfiguring would restore a spent allowance
+    s.ctx.configure_minter(&s.minter, ALLOWANCE);
+
+    let minter_config = s.ctx.minter_config(&s.minter);
+    assert_eq!(
+        minter_config,
+        MinterConfig {
+            allowance: ALLOWANCE,
+            amount_minted: MINTED,
+            ..minter_config
+        }
+    );
+    let result = s.ctx.execute(
+        instructions::mint_tokens(&s.minter.pubkey(), &s.holder.pubkey(), ALLOWANCE),
+        &[&s.minter],
+    );
+    result.assert_error_code(stablecoin::StablecoinError::ExceedsAllowance.into());
+}
+
+// ============================================================================
+// PDA Seed Collision Tests
+// ============================================================================
+
+#[test]
+fn test_pda_seeds_do_not_collide() {
+    let s = scene();
+    let singletons = [pda::config().0, pda::mint().0];
+    // Minters named after the other PDAs, the program, and the parties
+    let keys = [
+        pda::config().0,
+        pda::mint().0,
+        PROGRAM_ID,
+        Pubkey::default(),
+        s.admin.pubkey(),
+        s.minter.pubkey(),
+    ];
+    let minter_configs: Vec<Pubkey> = keys.iter().map(|key| pda::minter_config(key).0).collect();
+
+    assert_ne!(singletons[0], singletons[1]);
+    for (key, address) in keys.iter().zip(&minter_configs) {
+        assert!(
+            !singletons.contains(address),
+            "minter config of {key} is a singleton PDA"
+        );
+    }
+    for (i, address) in minter_configs.iter().enumerate() {
+        assert!(
+            !minter_configs[i + 1..].contains(address),
+            "minter config of {} is shared",
+            keys[i]
+        );
+    }
+}
+
+#[test]
+fn test_minter_named_after_config_gets_own_account() {
+    let mut s = scene();
+    let config = s.ctx.config();
+
+    // Configuring the config PDA's address as a minter must create a new
+    // minter config, not write into the config
+    for minter in [pda::config().0, pda::mint().0] {
+        let ix = instructions::configure_minter(&s.admin.pubkey(), &minter, ALLOWANCE);
+        s.ctx.expect_success(ix, &[&s.admin]);
+        let account = s
+            .ctx
+            .svm
+            .get_account(&pda::minter_config(&minter).0)
+            .unwrap();
+        assert_eq!(account.owner, PROGRAM_ID);
+        assert_eq!(account.data.len(), sizes::MINTER_CONFIG);
+    }
+    assert_eq!(s.ctx.config(), config);
+    assert_eq!(s.ctx.mint_state().supply, MINTED);
+}
+
+// ============================================================================
+// Closed-Account Revival Tests
+// ============================================================================
+
+#[test]
+fn test_revive_minter_config_in_closing_transaction() {
+    let mut s = scene();
+    let minter_config = pda::minter_config(&s.minter.pubkey()).0;
+    let rent = s
+        .ctx
+        .svm
+        .minimum_balance_for_rent_exemption(sizes::MINTER_CONFIG);
+
+    // Closed, then used again before the transaction ends
+    let result = s.ctx.execute_all(
+        vec![
+            instructions::remove_minter(&s.admin.pubkey(), &s.minter.pubkey()),
+            instructions::mint_tokens(&s.minter.pubkey(), &s.holder.pubkey(), MINTED),
+        ],
+        &[&s.admin, &s.minter],
+    );
+    assert_error(&result, ErrorCode::AccountNotInitialized);
+
+    // Closed, refunded, then used again
+    let result = s.ctx.execute_all(
+        vec![
+            instructions::remove_minter(&s.admin.pubkey(), &s.minter.pubkey()),
+            transfer(&s.minter.pubkey(), &minter_config, rent),
+            instructions::mint_tokens(&s.minter.pubkey(), &s.holder.pubkey(), MINTED),
+        ],
+        &[&s.admin, &s.minter],
+    );
+    assert_error(&result, ErrorCode::AccountOwnedByWrongProgram);
+
+    assert_eq!(s.ctx.balance(&s.holder), MINTED);
+    assert_eq!(s.ctx.minter_config(&s.minter).amount_minted, MINTED);
+}
+
+#[test]
+fn test_revive_closed_minter_config_with_lamports() {
+    let mut s = scene();
+    let minter_config = pda::minter_config(&s.minter.pubkey()).0;
+    let rent = s
+        .ctx
+        .svm
+        .minimum_balance_for_rent_exemption(sizes::MINTER_CONFIG);
+    s.ctx.remove_minter(&s.minter);
+
+    // Anyone can fund the closed address; it stays a system account
+    s.ctx.expect_success(
+        transfer(&s.attacker.pubkey(), &minter_config, rent),
+        &[&s.attacker],
+    );
+    let account = s.ctx.svm.get_account(&minter_config).unwrap();
+    assert_eq!(account.owner, system_program::ID);
+    assert!(account.data.is_empty());
+
+    let ix = instructions::mint_tokens(&s.minter.pubkey(), &s.holder.pubkey(), MINTED);
+    let result = s.ctx.expect_failure(ix, &[&s.minter]);
+    assert_error(&result, ErrorCode::AccountOwnedByWrongProgram);
+
+    // Nor can the admin bring the config back; the minter's tombstone
+    // stands in the way, so its key never mints again
+    let ix = instructions::configure_minter(&s.admin.pubkey(), &s.minter.pubkey(), ALLOWANCE);
+    let result = s.ctx.expect_failure(ix, &[&s.admin]);
+    result.assert_error_code(StablecoinError::MinterRetired.into());
+    let account = s.ctx.svm.get_account(&minter_config).unwrap();
+    assert_eq!(account.owner, system_program::ID);
+    assert_eq!(s.ctx.balance(&s.holder), MINTED);
+}
+
+#[test]
+fn test_revive_minter_config_in_removing_transaction() {
+    let mut s = scene();
+
+    // Removed, then configured again before the transaction ends
+    let result = s.ctx.execute_all(
+        vec![
+            instructions::remove_minter(&s.admin.pubkey(), &s.minter.pubkey()),
+            instructions::configure_minter(&s.admin.pubkey(), &s.minter.pubkey(), ALLOWANCE),
+        ],
+        &[&s.admin],
+    );
+    result.assert_error_code(StablecoinError::MinterRetired.into());
+    assert_eq!(s.ctx.minter_config(&s.minter).amount_minted, MINTED);
+    assert!(!s
+        .ctx
+        .account_exists(&pda::minter_tombstone(&s.minter.pubkey()).0));
+}
+
+#[test]
+fn test_prefunded_tombstone_does_not_block_removal() {
+    let mut s = scene();
+    let tombstone = pda::minter_tombstone(&s.minter.pubkey()).0;
+
+    // Lamports sent to the tombstone's address ahead of time leave it a
+    // system account, which init takes over
+    s.ctx.expect_success(
+        transfer(&s.attacker.pubkey(), &tombstone, 1_000_000),
+        &[&s.attacker],
+    );
+    s.ctx.remove_minter(&s.minter);
+
+    let account = s.ctx.svm.get_account(&tombstone).unwrap();
+    assert_eq!(account.owner, PROGRAM_ID);
+    assert_eq!(account.data.len(), sizes::MINTER_TOMBSTONE);
+    assert!(!s
+        .ctx
+        .account_exists(&pda::minter_config(&s.minter.pubkey()).0));
+}
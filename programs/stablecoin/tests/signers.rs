@@ -0,0 +1,261 @@
+use anchor_lang::error::ErrorCode;
+use anchor_litesvm::{Instruction, Keypair, Pubkey, Signer};
+use stablecoin_test_utils::{instructions, pda, TestContext};
+
+const ALLOWANCE: u64 = 1_000_000_000;
+const MINTED: u64 = 100_000_000;
+
+/// An instruction and the wallet that must sign it
+struct Case {
+    name: &'static str,
+    ix: Instruction,
+    authority: Pubkey,
+}
+
+/// Run `case` once for every subset of the wallets among its accounts
+/// signing, on a copy of `ctx` each time, with a separate fee payer
+///
+/// Every subset without the authority must fail with `AccountNotSigner`,
+/// and every subset with it must succeed: signing as a party the
+/// instruction does not require is harmless. PDAs and programs have no
+/// keypair to sign with, so only wallets are toggled.
+fn check_permutations(ctx: &mut TestContext, wallets: &[&Keypair], case: Case) {
+    let payer = ctx.funded_account();
+    let signable: Vec<&Keypair> = wallets
+        .iter()
+        .copied()
+        .filter(|wallet| {
+            case.ix
+                .accounts
+                .iter()
+                .any(|meta| meta.pubkey == wallet.pubkey())
+        })
+        .collect();
+    assert!(
+        signable
+            .iter()
+            .any(|wallet| wallet.pubkey() == case.authority),
+        "{}: the authority is not among the wallets",
+        case.name
+    );
+
+    let snapshot = ctx.svm.clone();
+    for subset in 0..1u32 << signable.len() {
+        let signing: Vec<&Keypair> = signable
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| subset & 1 << i != 0)
+            .map(|(_, wallet)| *wallet)
+            .collect();
+        let mut ix = case.ix.clone();
+        for meta in &mut ix.accounts {
+            meta.is_signer = signing.iter().any(|wallet| wallet.pubkey() == meta.pubkey);
+        }
+        let mut signers = vec![&payer];
+        signers.extend(&signing);
+
+        ctx.svm = snapshot.clone();
+        let result = ctx.execute(ix, &signers);
+        let signed = signing
+            .iter()
+            .any(|wallet| wallet.pubkey() == case.authority);
+        assert_eq!(
+            result.is_success(),
+            signed,
+            "{}: wrong outcome with {} of {} wallets signing",
+            case.name,
+            signing.len(),
+            signable.len()
+        );
+        if !signed {
+            result.assert_error_code(ErrorCode::AccountNotSigner.into());
+        }
+    }
+    ctx.svm = snapshot;
+}
+
+#[test]
+fn test_initialize_signer_permutations() {
+    let mut ctx = TestContext::new();
+    let admin = ctx.admin.insecure_clone();
+    check_permutations(
+        &mut ctx,
+        &[&admin],
+        Case {
+            name: "initialize",
+            ix: instructions::initialize(&admin.pubkey()),
+            authority: admin.pubkey(),
+        },
+    );
+}
+
+#[test]
+fn test_signer_permutations() {
+    let mut ctx = TestContext::new();
+    let admin = ctx.admin.insecure_clone();
+    let minter = ctx.funded_account();
+    let holder = ctx.funded_account();
+    let newcomer = ctx.funded_account();
+    ctx.initialize()
+        .configure_minter(&minter, ALLOWANCE)
+        .mint(&minter, &holder, MINTED);
+    let wallets = [&admin, &minter, &holder, &newcomer];
+
+    let cases = [
+        Case {
+            name: "configure_minter",
+            ix: instructions::configure_minter(&admin.pubkey(), &newcomer.pubkey(), ALLOWANCE),
+            authority: admin.pubkey(),
+        },
+        Case {
+            name: "configure_minter_update",
+            ix: instructions::configure_minter(&admin.pubkey(), &minter.pubkey(), 2 * ALLOWANCE),
+            authority: admin.pubkey(),
+        },
+        Case {
+            name: "remove_minter",
+            ix: instructions::remove_minter(&admin.pubkey(), &minter.pubkey()),
+            authority: admin.pubkey(),
+        },
+        Case {
+            name: "mint_tokens",
+            ix: instructions::mint_tokens(&minter.pubkey(), &holder.pubkey(), MINTED),
+            authority: minter.pubkey(),
+        },
+        Case {
+            name: "pause_self",
+            ix: instructions::pause_self(&minter.pubkey()),
+            authority: minter.pubkey(),
+        },
+        Case {
+            name: "mint_tokens_new_account",
+            ix: instructions::mint_tokens(&minter.pubkey(), &newcomer.pubkey(), MINTED),
+            authority: minter.pubkey(),
+        },
+        Case {
+            name: "burn_tokens",
+            ix: instructions::burn_tokens(&holder.pubkey(), MINTED),
+            authority: holder.pubkey(),
+        },
+        Case {
+            name: "pause",
+            ix: instructions::pause(&admin.pubkey()),
+            authority: admin.pubkey(),
+        },
+        Case {
+            name: "freeze_minter_set",
+            ix: instructions::freeze_minter_set(&admin.pubkey()),
+            authority: admin.pubkey(),
+        },
+    ];
+    for case in cases {
+        check_permutations(&mut ctx, &wallets, case);
+    }
+
+    ctx.pause();
+    check_permutations(
+        &mut ctx,
+        &wallets,
+        Case {
+            name: "unpause",
+            ix: instructions::unpause(&admin.pubkey()),
+            authority: admin.pubkey(),
+        },
+    );
+
+    ctx.unpause().freeze_minter_set();
+    check_permutations(
+        &mut ctx,
+        &wallets,
+        Case {
+            name: "unfreeze_minter_set",
+            ix: instructions::unfreeze_minter_set(&admin.pubkey(), &admin.pubkey()),
+            authority: admin.pubkey(),
+        },
+    );
+
+    ctx.unfreeze_minter_set().pause_self(&minter);
+    check_permutations(
+        &mut ctx,
+        &wallets,
+        Case {
+            name: "resume_minter",
+            ix: instructions::resume_minter(&admin.pubkey(), &minter.pubkey()),
+            authority: admin.pubkey(),
+        },
+    );
+}
+
+#[test]
+fn test_treasury_signer_permutations() {
+    let mut ctx = TestContext::new();
+    let admin = ctx.admin.insecure_clone();
+    let minter = ctx.funded_account();
+    let proposer = ctx.funded_account();
+    let approver = ctx.funded_account();
+    ctx.initialize()
+        .configure_minter(&minter, ALLOWANCE)
+        .configure_treasury([&proposer, &approver], 0);
+    ctx.expect_success(
+        instructions::mint_tokens(&minter.pubkey(), &pda::treasury().0, MINTED),
+        &[&minter],
+    );
+    let wallets = [&admin, &minter, &proposer, &approver];
+
+    let cases = [
+        Case {
+            name: "configure_treasury",
+            ix: instructions::configure_treasury(
+                &admin.pubkey(),
+                [approver.pubkey(), minter.pubkey()],
+                0,
+            ),
+            authority: admin.pubkey(),
+        },
+        Case {
+            name: "propose_treasury_burn",
+            ix: instructions::propose_treasury_burn(&proposer.pubkey(), 1, MINTED),
+            authority: proposer.pubkey(),
+        },
+    ];
+    for case in cases {
+        check_permutations(&mut ctx, &wallets, case);
+    }
+
+    ctx.expect_success(
+        instructions::propose_treasury_burn(&proposer.pubkey(), 1, MINTED),
+        &[&proposer],
+    );
+    check_permutations(
+        &mut ctx,
+        &wallets,
+        Case {
+            name: "approve_treasury_burn",
+            ix: instructions::approve_treasury_burn(&approver.pubkey(), 1),
+            authority: approver.pubkey(),
+        },
+    );
+    check_permutations(
+        &mut ctx,
+        &wallets,
+        Case {
+            name: "cancel_treasury_burn",
+            ix: instructions::cancel_treasury_burn(&approver.pubkey(), &proposer.pubkey(), 1),
+            authority: approver.pubkey(),
+        },
+    );
+
+    ctx.expect_success(
+        instructions::approve_treasury_burn(&approver.pubkey(), 1),
+        &[&approver],
+    );
+    check_permutations(
+        &mut ctx,
+        &wallets,
+        Case {
+            name: "execute_treasury_burn",
+            ix: instructions::execute_treasury_burn(&approver.pubkey(), &proposer.pubkey(), 1),
+            authority: approver.pubkey(),
+        },
+    );
+}
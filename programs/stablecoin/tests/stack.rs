@@ -0,0 +1,43 @@
+use anchor_litesvm::Keypair;
+use stablecoin_test_utils::{paths, TestContext};
+
+/// What the VM reports when a function's frame outgrows the 4KB an SBF
+/// stack frame has
+const STACK_OVERRUN: &str = "Access violation in stack frame";
+
+// ============================================================================
+// Stack Tests
+// ============================================================================
+
+#[test]
+fn test_no_instruction_overruns_stack_frame() {
+    let mut ctx = TestContext::new();
+    let minter = ctx.funded_account();
+    let holder = ctx.funded_account();
+
+    paths::run(
+        &mut ctx,
+        &minter,
+        &holder,
+        &Keypair::new(),
+        |instruction, result| {
+            let overrun = result
+                .error()
+                .into_iter()
+                .chain(result.logs())
+                .find(|line| line.contains(STACK_OVERRUN));
+            assert!(
+                overrun.is_none(),
+                "{} overran its stack frame: {}",
+                instruction.name(),
+                overrun.unwrap()
+            );
+            assert!(
+                result.is_success(),
+                "{} failed: {:?}",
+                instruction.name(),
+                result.error()
+            );
+        },
+    );
+}
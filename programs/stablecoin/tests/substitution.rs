@@ -0,0 +1,402 @@
+use anchor_lang::error::ErrorCode;
+use anchor_litesvm::{Keypair, Pubkey, Signer, TransactionResult};
+use litesvm_utils::TestHelpers;
+use stablecoin::{sizes, Config};
+use stablecoin_test_utils::{instructions, pda, substitute, TestContext, PROGRAM_ID};
+
+const ALLOWANCE: u64 = 1_000_000_000;
+const MINTED: u64 = 100_000_000;
+
+/// An executable that is not the program an instruction expects; the
+/// stablecoin program itself is one litesvm already has loaded
+const FAKE_PROGRAM: Pubkey = PROGRAM_ID;
+
+/// An initialized stablecoin with a minter that has minted to two holders
+struct Scene {
+    ctx: TestContext,
+    minter: Keypair,
+    holder: Keypair,
+    victim: Keypair,
+    /// Funded, but holds no role
+    attacker: Keypair,
+}
+
+fn scene() -> Scene {
+    let mut ctx = TestContext::new();
+    let minter = ctx.funded_account();
+    let holder = ctx.funded_account();
+    let victim = ctx.funded_account();
+    let attacker = ctx.funded_account();
+    ctx.initialize()
+        .configure_minter(&minter, ALLOWANCE)
+        .mint(&minter, &holder, MINTED)
+        .mint(&minter, &victim, MINTED);
+    Scene {
+        ctx,
+        minter,
+        holder,
+        victim,
+        attacker,
+    }
+}
+
+fn assert_error(result: &TransactionResult, error: ErrorCode) {
+    result.assert_error_code(error.into());
+}
+
+// ============================================================================
+// Wrong PDA Tests
+// ============================================================================
+
+#[test]
+fn test_forged_config_rejected() {
+    let mut s = scene();
+    // The real config is paused; the forgery is unpaused and names the
+    // attacker as admin, with the real mint and bumps
+    s.ctx.pause();
+    let forged = Pubkey::new_unique();
+    let config = s.ctx.config();
+    s.ctx.forge_account(
+        &forged,
+        &Config {
+            admin: s.attacker.pubkey(),
+            paused: false,
+            ..config
+        },
+    );
+
+    let attacker = s.attacker.pubkey();
+    let cases = [
+        (
+            instructions::configure_minter(&attacker, &attacker, ALLOWANCE),
+            &s.attacker,
+        ),
+        (
+            instructions::remove_minter(&attacker, &s.minter.pubkey()),
+            &s.attacker,
+        ),
+        (instructions::pause(&attacker), &s.attacker),
+        (instructions::unpause(&attacker), &s.attacker),
+        (
+            instructions::mint_tokens(&s.minter.pubkey(), &s.holder.pubkey(), MINTED),
+            &s.minter,
+        ),
+        (
+            instructions::burn_tokens(&s.holder.pubkey(), MINTED),
+            &s.holder,
+        ),
+    ];
+    for (ix, signer) in cases {
+        let ix = substitute(ix, &pda::config().0, &forged);
+        let result = s.ctx.expect_failure(ix, &[signer]);
+        assert_error(&result, ErrorCode::ConstraintSeeds);
+    }
+
+    assert_eq!(
+        s.ctx.config(),
+        Config {
+            paused: true,
+            ..config
+        }
+    );
+    assert_eq!(s.ctx.balance(&s.holder), MINTED);
+}
+
+#[test]
+fn test_minter_config_as_config_rejected() {
+    let mut s = scene();
+    let admin = s.ctx.admin.insecure_clone();
+    let ix = substitute(
+        instructions::pause(&admin.pubkey()),
+        &pda::config().0,
+        &pda::minter_config(&s.minter.pubkey()).0,
+    );
+
+    let result = s.ctx.expect_failure(ix, &[&admin]);
+    assert_error(&result, ErrorCode::AccountDiscriminatorMismatch);
+    assert!(!s.ctx.config().paused);
+}
+
+#[test]
+fn test_other_minters_config_rejected() {
+    let mut s = scene();
+    let real = pda::minter_config(&s.minter.pubkey()).0;
+
+    // An unconfigured wallet minting under a configured minter's allowance
+    let ix = substitute(
+        instructions::mint_tokens(&s.attacker.pubkey(), &s.attacker.pubkey(), MINTED),
+        &pda::minter_config(&s.attacker.pubkey()).0,
+        &real,
+    );
+    let result = s.ctx.expect_failure(ix, &[&s.attacker]);
+    assert_error(&result, ErrorCode::ConstraintSeeds);
+    assert_eq!(s.ctx.balance(&s.attacker), 0);
+
+    // Closing one minter's config while naming another minter
+    let other = s.ctx.funded_account();
+    s.ctx.configure_minter(&other, ALLOWANCE);
+    let admin = s.ctx.admin.insecure_clone();
+    let ix = substitute(
+        instructions::remove_minter(&admin.pubkey(), &other.pubkey()),
+        &pda::minter_config(&other.pubkey()).0,
+        &real,
+    );
+    let result = s.ctx.expect_failure(ix, &[&admin]);
+    assert_error(&result, ErrorCode::ConstraintSeeds);
+    assert!(s.ctx.minter_config(&s.minter).is_initialized);
+    assert!(s.ctx.minter_config(&other).is_initialized);
+}
+
+#[test]
+fn test_other_minters_pause_rejected() {
+    let mut s = scene();
+    s.ctx.pause_self(&s.minter.insecure_clone());
+
+    // A paused minter naming an unpaused minter's marker, which is empty
+    let other = s.ctx.funded_account();
+    let ix = substitute(
+        instructions::mint_tokens(&s.minter.pubkey(), &s.holder.pubkey(), MINTED),
+        &pda::minter_pause(&s.minter.pubkey()).0,
+        &pda::minter_pause(&other.pubkey()).0,
+    );
+    let result = s.ctx.expect_failure(ix, &[&s.minter]);
+    assert_error(&result, ErrorCode::ConstraintSeeds);
+    assert_eq!(s.ctx.balance(&s.holder), MINTED);
+}
+
+// ============================================================================
+// Fake Program Tests
+// ============================================================================
+
+#[test]
+fn test_fake_token_program_rejected() {
+    let mut ctx = TestContext::new();
+    let admin = ctx.admin.insecure_clone();
+    let ix = substitute(
+        instructions::initialize(&admin.pubkey()),
+        &anchor_spl::token::ID,
+        &FAKE_PROGRAM,
+    );
+    let result = ctx.expect_failure(ix, &[&admin]);
+    assert_error(&result, ErrorCode::InvalidProgramId);
+
+    let mut s = scene();
+    let cases = [
+        (
+            instructions::mint_tokens(&s.minter.pubkey(), &s.holder.pubkey(), MINTED),
+            &s.minter,
+        ),
+        (
+            instructions::burn_tokens(&s.holder.pubkey(), MINTED),
+            &s.holder,
+        ),
+    ];
+    for (ix, signer) in cases {
+        let ix = substitute(ix, &anchor_spl::token::ID, &FAKE_PROGRAM);
+        let result = s.ctx.expect_failure(ix, &[signer]);
+        assert_error(&result, ErrorCode::InvalidProgramId);
+    }
+    assert_eq!(s.ctx.balance(&s.holder), MINTED);
+}
+
+#[test]
+fn test_fake_associated_token_program_rejected() {
+    let mut s = scene();
+    let ix = substitute(
+        instructions::mint_tokens(&s.minter.pubkey(), &s.attacker.pubkey(), MINTED),
+        &anchor_spl::associated_token::ID,
+        &FAKE_PROGRAM,
+    );
+
+    let result = s.ctx.expect_failure(ix, &[&s.minter]);
+    assert_error(&result, ErrorCode::InvalidProgramId);
+    assert_eq!(s.ctx.balance(&s.attacker), 0);
+}
+
+#[test]
+fn test_fake_system_program_rejected() {
+    let mut ctx = TestContext::new();
+    let admin = ctx.admin.insecure_clone();
+    let ix = substitute(
+        instructions::initialize(&admin.pubkey()),
+        &anchor_lang::system_program::ID,
+        &FAKE_PROGRAM,
+    );
+    let result = ctx.expect_failure(ix, &[&admin]);
+    assert_error(&result, ErrorCode::InvalidProgramId);
+
+    let mut s = scene();
+    let admin = s.ctx.admin.insecure_clone();
+    let ix = substitute(
+        instructions::configure_minter(&admin.pubkey(), &s.attacker.pubkey(), ALLOWANCE),
+        &anchor_lang::system_program::ID,
+        &FAKE_PROGRAM,
+    );
+    let result = s.ctx.expect_failure(ix, &[&admin]);
+    assert_error(&result, ErrorCode::InvalidProgramId);
+}
+
+// ============================================================================
+// Foreign Mint Tests
+// ============================================================================
+
+#[test]
+fn test_foreign_mint_rejected() {
+    let mut s = scene();
+    // A mint with the same decimals from a deployment the attacker controls
+    let foreign = s
+        .ctx
+        .svm
+        .create_token_mint(&s.attacker, 6)
+        .unwrap()
+        .pubkey();
+    let attacker_account = s
+        .ctx
+        .svm
+        .create_associated_token_account(&foreign, &s.attacker)
+        .unwrap();
+    s.ctx
+        .svm
+        .mint_to(&foreign, &attacker_account, &s.attacker, MINTED)
+        .unwrap();
+
+    // Minting the real stablecoin's allowance into the foreign mint
+    let ix = substitute(
+        instructions::mint_tokens(&s.minter.pubkey(), &s.attacker.pubkey(), MINTED),
+        &pda::mint().0,
+        &foreign,
+    );
+    let ix = substitute(
+        ix,
+        &pda::token_account(&s.attacker.pubkey()),
+        &attacker_account,
+    );
+    let result = s.ctx.expect_failure(ix, &[&s.minter]);
+    assert_error(&result, ErrorCode::ConstraintSeeds);
+
+    // Burning foreign tokens through the stablecoin program
+    let ix = substitute(
+        instructions::burn_tokens(&s.attacker.pubkey(), MINTED),
+        &pda::mint().0,
+        &foreign,
+    );
+    let ix = substitute(
+        ix,
+        &pda::token_account(&s.attacker.pubkey()),
+        &attacker_account,
+    );
+    let result = s.ctx.expect_failure(ix, &[&s.attacker]);
+    assert_error(&result, ErrorCode::ConstraintSeeds);
+
+    assert_eq!(s.ctx.minter_config(&s.minter).amount_minted, 2 * MINTED);
+    assert_eq!(s.ctx.mint_state().supply, 2 * MINTED);
+}
+
+// ============================================================================
+// Token Account Substitution Tests
+// ============================================================================
+
+#[test]
+fn test_burn_from_other_wallets_account_rejected() {
+    let mut s = scene();
+    let ix = substitute(
+        instructions::burn_tokens(&s.holder.pubkey(), MINTED),
+        &pda::token_account(&s.holder.pubkey()),
+        &pda::token_account(&s.victim.pubkey()),
+    );
+
+    let result = s.ctx.expect_failure(ix, &[&s.holder]);
+    assert_error(&result, ErrorCode::ConstraintTokenOwner);
+    assert_eq!(s.ctx.balance(&s.victim), MINTED);
+}
+
+#[test]
+fn test_burn_from_non_associated_account_rejected() {
+    let mut s = scene();
+    // Owned by the holder and of the right mint, but not at the address
+    // the burn's associated token constraint derives
+    let mint = pda::mint().0;
+    let account = s
+        .ctx
+        .svm
+        .create_token_account(&mint, &s.holder)
+        .unwrap()
+        .pubkey();
+    let ix = substitute(
+        instructions::burn_tokens(&s.holder.pubkey(), 0),
+        &pda::token_account(&s.holder.pubkey()),
+        &account,
+    );
+
+    let result = s.ctx.expect_failure(ix, &[&s.holder]);
+    assert_error(&result, ErrorCode::ConstraintAssociated);
+}
+
+#[test]
+fn test_mint_to_other_wallets_account_rejected() {
+    let mut s = scene();
+    let ix = substitute(
+        instructions::mint_tokens(&s.minter.pubkey(), &s.holder.pubkey(), MINTED),
+        &pda::token_account(&s.holder.pubkey()),
+        &pda::token_account(&s.victim.pubkey()),
+    );
+
+    let result = s.ctx.expect_failure(ix, &[&s.minter]);
+    assert_error(&result, ErrorCode::ConstraintTokenOwner);
+    assert_eq!(s.ctx.balance(&s.victim), MINTED);
+}
+
+#[test]
+fn test_mint_to_non_associated_account_rejected() {
+    let mut s = scene();
+    let mint = pda::mint().0;
+    let account = s
+        .ctx
+        .svm
+        .create_token_account(&mint, &s.holder)
+        .unwrap()
+        .pubkey();
+    let ix = substitute(
+        instructions::mint_tokens(&s.minter.pubkey(), &s.holder.pubkey(), MINTED),
+        &pda::token_account(&s.holder.pubkey()),
+        &account,
+    );
+
+    let result = s.ctx.expect_failure(ix, &[&s.minter]);
+    assert_error(&result, ErrorCode::AccountNotAssociatedTokenAccount);
+    assert_eq!(s.ctx.minter_config(&s.minter).amount_minted, 2 * MINTED);
+}
+
+// ============================================================================
+// Rent Tests
+// ============================================================================
+
+// Anyone can send lamports to an address before the program creates an
+// account there, so an underfunded PDA cannot be made to fail creation.
+// Anchor tops it up instead, and the account must end up rent exempt.
+
+#[test]
+fn test_underfunded_pdas_are_topped_up() {
+    let mut ctx = TestContext::new();
+    let minter = ctx.funded_account();
+    let dust = ctx.svm.minimum_balance_for_rent_exemption(0);
+    let minter_config = pda::minter_config(&minter.pubkey()).0;
+    for address in [pda::config().0, pda::mint().0, minter_config] {
+        ctx.svm.airdrop(&address, dust).unwrap();
+    }
+
+    ctx.initialize().configure_minter(&minter, ALLOWANCE);
+
+    let sized = [
+        (pda::config().0, sizes::CONFIG),
+        (pda::mint().0, anchor_spl::token::Mint::LEN),
+        (minter_config, sizes::MINTER_CONFIG),
+    ];
+    for (address, size) in sized {
+        let account = ctx.svm.get_account(&address).unwrap();
+        assert_eq!(account.data.len(), size);
+        assert!(account.lamports >= ctx.svm.minimum_balance_for_rent_exemption(size));
+    }
+    assert_eq!(ctx.config().admin, ctx.admin.pubkey());
+    assert_eq!(ctx.minter_config(&minter).allowance, ALLOWANCE);
+}
@@ -1,40 +1,21 @@
-use anchor_litesvm::{AnchorLiteSVM, Keypair, Pubkey, Signer};
-use anchor_spl::associated_token::get_associated_token_address;
-use litesvm_utils::{AssertionHelpers, TestHelpers};
-
-// Declare the program to generate client types
-anchor_lang::declare_program!(stablecoin);
-use self::stablecoin::{client, ID as PROGRAM_ID};
-
-// Program IDs
-const TOKEN_PROGRAM_ID: Pubkey = anchor_spl::token::ID;
-const ASSOCIATED_TOKEN_PROGRAM_ID: Pubkey = anchor_spl::associated_token::ID;
-const SYSTEM_PROGRAM_ID: Pubkey = anchor_lang::system_program::ID;
-
-// Helper to create an initialized test context
-fn setup_ctx() -> anchor_litesvm::AnchorContext {
-    AnchorLiteSVM::build_with_program(
-        PROGRAM_ID,
-        include_bytes!("../../../target/deploy/stablecoin.so"),
-    )
-}
-
-// Helper to get PDAs
-fn get_config_pda() -> Pubkey {
-    Pubkey::find_program_address(&[b"config"], &PROGRAM_ID).0
-}
-
-fn get_mint_pda() -> Pubkey {
-    Pubkey::find_program_address(&[b"mint"], &PROGRAM_ID).0
-}
-
-fn get_minter_config_pda(minter: &Pubkey) -> Pubkey {
-    Pubkey::find_program_address(&[b"minter", minter.as_ref()], &PROGRAM_ID).0
-}
-
-fn get_ata(wallet: &Pubkey, mint: &Pubkey) -> Pubkey {
-    get_associated_token_address(wallet, mint)
-}
+use anchor_lang::solana_program::program_option::COption;
+use anchor_lang::{error::ErrorCode, prelude::Pubkey};
+use anchor_litesvm::{EventHelpers, Keypair, Signer};
+use anchor_spl::token::{spl_token, TokenAccount};
+use litesvm_utils::AssertionHelpers;
+use stablecoin::{
+    sizes, AccountRecovered, AccountResized, Airdrop, AirdropClaimed, AirdropClawedBack,
+    AirdropOpened, ChannelClosing, ChannelCounter, ChannelSettled, Config, IdempotencyRecord,
+    InheritanceClaimStarted, InheritanceClaimStopped, InheritanceClaimed, InheritanceConfigured,
+    InheritancePlan, MinterConfig, MinterPause, MinterResumed, MinterSelfPaused, MinterSetFreeze,
+    MinterSetFrozen, MinterSetUnfrozen, MinterTombstone, NetSettled, NettingWindow,
+    ObligationQueued, PaymentChannel, PendingBurn, PurposeCode, RecoveryConfig, RecoveryConfigured,
+    StablecoinError, SubAccount, SubAccountRegistered, SubAccountSwept, SupplyStats, TokensMinted,
+    Treasury, TreasuryBurnApproved, TreasuryBurnCancelled, TreasuryBurnProposed,
+    TreasuryConfigured, IDEMPOTENCY_KEY_TTL, MIN_CHANNEL_DISPUTE_PERIOD,
+    MIN_INHERITANCE_NOTICE_PERIOD, MIN_RECOVERY_CHALLENGE_PERIOD,
+};
+use stablecoin_test_utils::{instructions, pda, substitute, TestContext};
 
 // ============================================================================
 // Initialize Tests
@@ -42,303 +23,336 @@ fn get_ata(wallet: &Pubkey, mint: &Pubkey) -> Pubkey {
 
 #[test]
 fn test_initialize() {
-    let mut ctx = setup_ctx();
-
-    let admin = ctx.svm.create_funded_account(10_000_000_000).unwrap();
-    let config_pda = get_config_pda();
-    let mint_pda = get_mint_pda();
-
-    let ix = ctx
-        .program()
-        .accounts(client::accounts::Initialize {
-            admin: admin.pubkey(),
-            config: config_pda,
+    let mut ctx = TestContext::new();
+    ctx.initialize();
+
+    // Verify accounts were created with the expected state
+    let (config_pda, config_bump) = pda::config();
+    let (mint_pda, mint_bump) = pda::mint();
+    assert_eq!(
+        ctx.config(),
+        Config {
+            admin: ctx.admin.pubkey(),
             mint: mint_pda,
-            token_program: TOKEN_PROGRAM_ID,
-            system_program: SYSTEM_PROGRAM_ID,
-        })
-        .args(client::args::Initialize {})
-        .instruction()
-        .unwrap();
-
-    ctx.execute_instruction(ix, &[&admin])
-        .unwrap()
-        .assert_success();
+            paused: false,
+            bump: config_bump,
+            mint_bump,
+            delegate_bump: pda::delegate().1,
+            minter_set_freeze_bump: pda::minter_set_freeze().1,
+        }
+    );
 
-    // Verify accounts were created
-    assert!(ctx.account_exists(&config_pda), "Config account should exist");
-    assert!(ctx.account_exists(&mint_pda), "Mint account should exist");
+    let mint = ctx.mint_state();
+    assert_eq!(mint.supply, 0);
+    assert_eq!(mint.decimals, 6);
+    assert!(mint.is_initialized);
+    assert_eq!(Option::from(mint.mint_authority), Some(config_pda));
+    assert_eq!(Option::from(mint.freeze_authority), Some(config_pda));
 }
 
 #[test]
 fn test_initialize_twice_fails() {
-    let mut ctx = setup_ctx();
-
-    let admin = ctx.svm.create_funded_account(10_000_000_000).unwrap();
-    let config_pda = get_config_pda();
-    let mint_pda = get_mint_pda();
-
-    let ix = ctx
-        .program()
-        .accounts(client::accounts::Initialize {
-            admin: admin.pubkey(),
-            config: config_pda,
-            mint: mint_pda,
-            token_program: TOKEN_PROGRAM_ID,
-            system_program: SYSTEM_PROGRAM_ID,
-        })
-        .args(client::args::Initialize {})
-        .instruction()
-        .unwrap();
+    let mut ctx = TestContext::new();
 
     // First initialize should succeed
-    ctx.execute_instruction(ix.clone(), &[&admin])
-        .unwrap()
-        .assert_success();
+    ctx.initialize();
 
     // Second initialize should fail
-    let ix2 = ctx
-        .program()
-        .accounts(client::accounts::Initialize {
-            admin: admin.pubkey(),
-            config: config_pda,
-            mint: mint_pda,
-            token_program: TOKEN_PROGRAM_ID,
-            system_program: SYSTEM_PROGRAM_ID,
-        })
-        .args(client::args::Initialize {})
-        .instruction()
-        .unwrap();
-
-    let result = ctx.execute_instruction(ix2, &[&admin]);
-    assert!(
-        result.is_err() || !result.unwrap().is_success(),
-        "Second initialize should fail"
-    );
+    let admin = ctx.admin.insecure_clone();
+    ctx.expect_failure(instructions::initialize(&admin.pubkey()), &[&admin]);
 }
 
 // ============================================================================
 // Configure Minter Tests
 // ============================================================================
 
-fn initialize_program(ctx: &mut anchor_litesvm::AnchorContext, admin: &Keypair) {
-    let config_pda = get_config_pda();
-    let mint_pda = get_mint_pda();
-
-    let ix = ctx
-        .program()
-        .accounts(client::accounts::Initialize {
-            admin: admin.pubkey(),
-            config: config_pda,
-            mint: mint_pda,
-            token_program: TOKEN_PROGRAM_ID,
-            system_program: SYSTEM_PROGRAM_ID,
-        })
-        .args(client::args::Initialize {})
-        .instruction()
-        .unwrap();
-
-    ctx.execute_instruction(ix, &[admin])
-        .expect("Initialize should succeed")
-        .assert_success();
-}
-
 #[test]
 fn test_configure_minter() {
-    let mut ctx = setup_ctx();
-
-    let admin = ctx.svm.create_funded_account(10_000_000_000).unwrap();
-    initialize_program(&mut ctx, &admin);
-
-    let config_pda = get_config_pda();
+    let mut ctx = TestContext::new();
     let minter = Keypair::new();
-    let minter_config_pda = get_minter_config_pda(&minter.pubkey());
-
-    let allowance: u64 = 1_000_000_000;
-
-    let ix = ctx
-        .program()
-        .accounts(client::accounts::ConfigureMinter {
-            admin: admin.pubkey(),
-            config: config_pda,
-            minter: minter.pubkey(),
-            minter_config: minter_config_pda,
-            system_program: SYSTEM_PROGRAM_ID,
-        })
-        .args(client::args::ConfigureMinter { allowance })
-        .instruction()
-        .unwrap();
 
-    ctx.execute_instruction(ix, &[&admin])
-        .unwrap()
-        .assert_success();
+    ctx.initialize().configure_minter(&minter, 1_000_000_000);
 
     // Verify minter config account was created
-    assert!(
-        ctx.account_exists(&minter_config_pda),
-        "Minter config account should exist"
+    assert_eq!(
+        ctx.minter_config(&minter),
+        MinterConfig {
+            minter: minter.pubkey(),
+            allowance: 1_000_000_000,
+            amount_minted: 0,
+            is_initialized: true,
+            bump: pda::minter_config(&minter.pubkey()).1,
+            pause_bump: pda::minter_pause(&minter.pubkey()).1,
+        }
     );
 }
 
 #[test]
 fn test_configure_minter_unauthorized() {
-    let mut ctx = setup_ctx();
-
-    let admin = ctx.svm.create_funded_account(10_000_000_000).unwrap();
-    let unauthorized = ctx.svm.create_funded_account(10_000_000_000).unwrap();
-    initialize_program(&mut ctx, &admin);
-
-    let config_pda = get_config_pda();
+    let mut ctx = TestContext::new();
+    let unauthorized = ctx.funded_account();
     let minter = Keypair::new();
-    let minter_config_pda = get_minter_config_pda(&minter.pubkey());
-
-    let allowance: u64 = 1_000_000_000;
-
-    let ix = ctx
-        .program()
-        .accounts(client::accounts::ConfigureMinter {
-            admin: unauthorized.pubkey(),
-            config: config_pda,
-            minter: minter.pubkey(),
-            minter_config: minter_config_pda,
-            system_program: SYSTEM_PROGRAM_ID,
-        })
-        .args(client::args::ConfigureMinter { allowance })
-        .instruction()
-        .unwrap();
+    ctx.initialize();
 
-    let result = ctx.execute_instruction(ix, &[&unauthorized]);
-    assert!(
-        result.is_err() || !result.unwrap().is_success(),
-        "Unauthorized configure_minter should fail"
-    );
+    let ix =
+        instructions::configure_minter(&unauthorized.pubkey(), &minter.pubkey(), 1_000_000_000);
+    ctx.expect_failure(ix, &[&unauthorized]);
+    assert!(!ctx.account_exists(&pda::minter_config(&minter.pubkey()).0));
 }
 
 #[test]
 fn test_update_minter_allowance() {
-    let mut ctx = setup_ctx();
+    let mut ctx = TestContext::new();
+    let minter = ctx.funded_account();
+    let recipient = Keypair::new();
 
-    let admin = ctx.svm.create_funded_account(10_000_000_000).unwrap();
-    initialize_program(&mut ctx, &admin);
+    // First configure with initial allowance and use some of it
+    ctx.initialize()
+        .configure_minter(&minter, 1_000_000_000)
+        .mint(&minter, &recipient, 300_000_000)
+        .configure_minter(&minter, 2_000_000_000);
+
+    // Updating keeps what was already minted
+    let minter_config = ctx.minter_config(&minter);
+    assert_eq!(minter_config.allowance, 2_000_000_000);
+    assert_eq!(minter_config.amount_minted, 300_000_000);
+}
+
+// ============================================================================
+// Remove Minter Tests
+// ============================================================================
 
-    let config_pda = get_config_pda();
+#[test]
+fn test_remove_minter() {
+    let mut ctx = TestContext::new();
     let minter = Keypair::new();
-    let minter_config_pda = get_minter_config_pda(&minter.pubkey());
+    let minter_config_pda = pda::minter_config(&minter.pubkey()).0;
 
-    // First configure with initial allowance
-    let allowance1: u64 = 1_000_000_000;
+    ctx.initialize().configure_minter(&minter, 1_000_000_000);
 
-    let ix1 = ctx
-        .program()
-        .accounts(client::accounts::ConfigureMinter {
-            admin: admin.pubkey(),
-            config: config_pda,
-            minter: minter.pubkey(),
-            minter_config: minter_config_pda,
-            system_program: SYSTEM_PROGRAM_ID,
-        })
-        .args(client::args::ConfigureMinter {
-            allowance: allowance1,
-        })
-        .instruction()
-        .unwrap();
+    // Verify minter config exists
+    assert!(
+        ctx.account_exists(&minter_config_pda),
+        "Minter config should exist"
+    );
 
-    ctx.execute_instruction(ix1, &[&admin])
-        .expect("First configure should succeed")
-        .assert_success();
+    ctx.remove_minter(&minter);
 
-    // Update with new allowance
-    let allowance2: u64 = 2_000_000_000;
+    // Verify minter config was closed
+    ctx.svm.assert_account_closed(&minter_config_pda);
 
-    let ix2 = ctx
-        .program()
-        .accounts(client::accounts::ConfigureMinter {
-            admin: admin.pubkey(),
-            config: config_pda,
+    // A tombstone takes its place
+    let tombstone = pda::minter_tombstone(&minter.pubkey());
+    assert_eq!(
+        ctx.account::<MinterTombstone>(&tombstone.0),
+        MinterTombstone {
             minter: minter.pubkey(),
-            minter_config: minter_config_pda,
-            system_program: SYSTEM_PROGRAM_ID,
-        })
-        .args(client::args::ConfigureMinter {
-            allowance: allowance2,
-        })
-        .instruction()
-        .unwrap();
+            removed_by: ctx.admin.pubkey(),
+            removed_at: ctx.clock().unix_timestamp,
+            amount_minted: 0,
+            bump: tombstone.1,
+        }
+    );
+}
 
-    ctx.execute_instruction(ix2, &[&admin])
-        .unwrap()
-        .assert_success();
+#[test]
+fn test_removed_minter_cannot_be_configured_again() {
+    let mut ctx = TestContext::new();
+    let admin = ctx.admin.insecure_clone();
+    let minter = ctx.funded_account();
+    let recipient = Keypair::new();
+    ctx.initialize()
+        .configure_minter(&minter, 1_000_000_000)
+        .mint(&minter, &recipient, 300_000_000)
+        .remove_minter(&minter);
+
+    let tombstone = pda::minter_tombstone(&minter.pubkey()).0;
+    assert_eq!(
+        ctx.account::<MinterTombstone>(&tombstone).amount_minted,
+        300_000_000
+    );
+
+    // Not with a fresh allowance, nor the allowance it had
+    for allowance in [1_000_000_000, 2_000_000_000] {
+        let result = ctx.execute(
+            instructions::configure_minter(&admin.pubkey(), &minter.pubkey(), allowance),
+            &[&admin],
+        );
+        result.assert_error_code(StablecoinError::MinterRetired.into());
+    }
+    assert!(!ctx.account_exists(&pda::minter_config(&minter.pubkey()).0));
+
+    // Nor removed twice
+    let result = ctx.execute(
+        instructions::remove_minter(&admin.pubkey(), &minter.pubkey()),
+        &[&admin],
+    );
+    result.assert_error_code(ErrorCode::AccountNotInitialized.into());
+
+    // A new key for the same issuer is a new minter
+    let successor = ctx.funded_account();
+    ctx.configure_minter(&successor, 1_000_000_000);
+    assert_eq!(ctx.minter_config(&successor).amount_minted, 0);
 }
 
 // ============================================================================
-// Remove Minter Tests
+// Minter Set Freeze Tests
 // ============================================================================
 
-fn configure_minter(
-    ctx: &mut anchor_litesvm::AnchorContext,
-    admin: &Keypair,
-    minter: &Pubkey,
-    allowance: u64,
-) {
-    let config_pda = get_config_pda();
-    let minter_config_pda = get_minter_config_pda(minter);
+#[test]
+fn test_freeze_minter_set() {
+    let mut ctx = TestContext::new();
+    let admin = ctx.admin.insecure_clone();
+    let minter = ctx.funded_account();
+    let newcomer = Keypair::new();
+    let recipient = Keypair::new();
+    let freeze = pda::minter_set_freeze();
+    ctx.initialize().configure_minter(&minter, 1_000_000_000);
+
+    let result = ctx.expect_success(instructions::freeze_minter_set(&admin.pubkey()), &[&admin]);
+    let event: MinterSetFrozen = result.parse_event().unwrap();
+    assert_eq!(event.admin, admin.pubkey());
+    assert_eq!(
+        ctx.account::<MinterSetFreeze>(&freeze.0),
+        MinterSetFreeze {
+            frozen_by: admin.pubkey(),
+            frozen_at: ctx.clock().unix_timestamp,
+            bump: freeze.1,
+        }
+    );
 
-    let ix = ctx
-        .program()
-        .accounts(client::accounts::ConfigureMinter {
-            admin: admin.pubkey(),
-            config: config_pda,
-            minter: *minter,
-            minter_config: minter_config_pda,
-            system_program: SYSTEM_PROGRAM_ID,
-        })
-        .args(client::args::ConfigureMinter { allowance })
-        .instruction()
-        .unwrap();
+    // Minters can be neither added, changed nor removed
+    for ix in [
+        instructions::configure_minter(&admin.pubkey(), &newcomer.pubkey(), 1),
+        instructions::configure_minter(&admin.pubkey(), &minter.pubkey(), 1),
+        instructions::remove_minter(&admin.pubkey(), &minter.pubkey()),
+    ] {
+        let result = ctx.execute(ix, &[&admin]);
+        result.assert_error_code(StablecoinError::MinterSetFrozen.into());
+    }
+    assert!(!ctx.account_exists(&pda::minter_config(&newcomer.pubkey()).0));
+
+    // The minters already configured keep minting
+    ctx.mint(&minter, &recipient, 100_000_000);
+    assert_eq!(ctx.balance(&recipient), 100_000_000);
+
+    // Nor can anyone but the admin unfreeze it
+    let other = ctx.funded_account();
+    let result = ctx.execute(
+        instructions::unfreeze_minter_set(&other.pubkey(), &admin.pubkey()),
+        &[&other],
+    );
+    result.assert_error_code(StablecoinError::Unauthorized.into());
+
+    // Unfreezing refunds the admin that froze it. A separate fee payer, so
+    // the admin's balance moves by the refund alone
+    let payer = ctx.funded_account();
+    let rent = ctx.svm.get_account(&freeze.0).unwrap().lamports;
+    let before = ctx.svm.get_balance(&admin.pubkey()).unwrap();
+    let result = ctx.expect_success(
+        instructions::unfreeze_minter_set(&admin.pubkey(), &admin.pubkey()),
+        &[&payer, &admin],
+    );
+    let event: MinterSetUnfrozen = result.parse_event().unwrap();
+    assert_eq!(event.admin, admin.pubkey());
+    ctx.svm.assert_account_closed(&freeze.0);
+    assert_eq!(ctx.svm.get_balance(&admin.pubkey()).unwrap(), before + rent);
 
-    ctx.execute_instruction(ix, &[admin])
-        .expect("Configure minter should succeed")
-        .assert_success();
+    ctx.remove_minter(&minter);
 }
 
 #[test]
-fn test_remove_minter() {
-    let mut ctx = setup_ctx();
-
-    let admin = ctx.svm.create_funded_account(10_000_000_000).unwrap();
-    initialize_program(&mut ctx, &admin);
+fn test_freeze_minter_set_unauthorized() {
+    let mut ctx = TestContext::new();
+    let unauthorized = ctx.funded_account();
+    ctx.initialize();
+
+    let result = ctx.execute(
+        instructions::freeze_minter_set(&unauthorized.pubkey()),
+        &[&unauthorized],
+    );
+    result.assert_error_code(StablecoinError::Unauthorized.into());
+    assert!(!ctx.account_exists(&pda::minter_set_freeze().0));
+}
 
-    let minter = Keypair::new();
-    configure_minter(&mut ctx, &admin, &minter.pubkey(), 1_000_000_000);
+// ============================================================================
+// Minter Self Pause Tests
+// ============================================================================
 
-    let config_pda = get_config_pda();
-    let minter_config_pda = get_minter_config_pda(&minter.pubkey());
+#[test]
+fn test_pause_self() {
+    let mut ctx = TestContext::new();
+    let admin = ctx.admin.insecure_clone();
+    let minter = ctx.funded_account();
+    let other = ctx.funded_account();
+    let recipient = Keypair::new();
+    let pause = pda::minter_pause(&minter.pubkey());
+    ctx.initialize()
+        .configure_minter(&minter, 1_000_000_000)
+        .configure_minter(&other, 1_000_000_000);
+
+    let result = ctx.expect_success(instructions::pause_self(&minter.pubkey()), &[&minter]);
+    let event: MinterSelfPaused = result.parse_event().unwrap();
+    assert_eq!(event.minter, minter.pubkey());
+    assert_eq!(
+        ctx.account::<MinterPause>(&pause.0),
+        MinterPause {
+            minter: minter.pubkey(),
+            paused_at: ctx.clock().unix_timestamp,
+            bump: pause.1,
+        }
+    );
 
-    // Verify minter config exists
-    assert!(
-        ctx.account_exists(&minter_config_pda),
-        "Minter config should exist"
+    // The minter can no longer mint, by either instruction
+    for ix in [
+        instructions::mint_tokens(&minter.pubkey(), &recipient.pubkey(), 1),
+        instructions::mint_tokens_idempotent(&minter.pubkey(), &recipient.pubkey(), 1, [1; 16]),
+    ] {
+        let result = ctx.execute(ix, &[&minter]);
+        result.assert_error_code(StablecoinError::MinterPaused.into());
+    }
+
+    // Other minters are unaffected
+    ctx.mint(&other, &recipient, 100_000_000);
+    assert_eq!(ctx.balance(&recipient), 100_000_000);
+
+    // A stolen key cannot undo the pause
+    let result = ctx.execute(
+        instructions::resume_minter(&minter.pubkey(), &minter.pubkey()),
+        &[&minter],
+    );
+    result.assert_error_code(StablecoinError::Unauthorized.into());
+
+    // The admin resumes it, refunding the minter. A separate fee payer, so
+    // the minter's balance moves by the refund alone
+    let payer = ctx.funded_account();
+    let rent = ctx.svm.get_account(&pause.0).unwrap().lamports;
+    let before = ctx.svm.get_balance(&minter.pubkey()).unwrap();
+    let result = ctx.expect_success(
+        instructions::resume_minter(&admin.pubkey(), &minter.pubkey()),
+        &[&payer, &admin],
+    );
+    let event: MinterResumed = result.parse_event().unwrap();
+    assert_eq!(event.minter, minter.pubkey());
+    assert_eq!(event.admin, admin.pubkey());
+    ctx.svm.assert_account_closed(&pause.0);
+    assert_eq!(
+        ctx.svm.get_balance(&minter.pubkey()).unwrap(),
+        before + rent
     );
 
-    // Remove minter
-    let ix = ctx
-        .program()
-        .accounts(client::accounts::RemoveMinter {
-            admin: admin.pubkey(),
-            config: config_pda,
-            minter: minter.pubkey(),
-            minter_config: minter_config_pda,
-        })
-        .args(client::args::RemoveMinter {})
-        .instruction()
-        .unwrap();
+    ctx.mint(&minter, &recipient, 100_000_000);
+    assert_eq!(ctx.balance(&recipient), 200_000_000);
+}
 
-    ctx.execute_instruction(ix, &[&admin])
-        .unwrap()
-        .assert_success();
+#[test]
+fn test_pause_self_requires_minter() {
+    let mut ctx = TestContext::new();
+    let stranger = ctx.funded_account();
+    ctx.initialize();
 
-    // Verify minter config was closed
-    ctx.svm.assert_account_closed(&minter_config_pda);
+    ctx.expect_failure(instructions::pause_self(&stranger.pubkey()), &[&stranger]);
+    assert!(!ctx.account_exists(&pda::minter_pause(&stranger.pubkey()).0));
 }
 
 // ============================================================================
@@ -347,44 +361,18 @@ fn test_remove_minter() {
 
 #[test]
 fn test_mint_tokens() {
-    let mut ctx = setup_ctx();
-
-    let admin = ctx.svm.create_funded_account(10_000_000_000).unwrap();
-    let minter = ctx.svm.create_funded_account(10_000_000_000).unwrap();
+    let mut ctx = TestContext::new();
+    let minter = ctx.funded_account();
     let recipient = Keypair::new();
+    ctx.initialize().configure_minter(&minter, 1_000_000_000);
 
-    initialize_program(&mut ctx, &admin);
-    configure_minter(&mut ctx, &admin, &minter.pubkey(), 1_000_000_000);
-
-    let config_pda = get_config_pda();
-    let mint_pda = get_mint_pda();
-    let minter_config_pda = get_minter_config_pda(&minter.pubkey());
-    let destination_ata = get_ata(&recipient.pubkey(), &mint_pda);
-
+    let destination_ata = ctx.token_account(&recipient);
     let mint_amount: u64 = 100_000_000;
 
-    let ix = ctx
-        .program()
-        .accounts(client::accounts::MintTokens {
-            minter: minter.pubkey(),
-            config: config_pda,
-            minter_config: minter_config_pda,
-            mint: mint_pda,
-            destination: destination_ata,
-            destination_owner: recipient.pubkey(),
-            token_program: TOKEN_PROGRAM_ID,
-            associated_token_program: ASSOCIATED_TOKEN_PROGRAM_ID,
-            system_program: SYSTEM_PROGRAM_ID,
-        })
-        .args(client::args::MintTokens {
-            amount: mint_amount,
-        })
-        .instruction()
-        .unwrap();
-
-    ctx.execute_instruction(ix, &[&minter])
-        .unwrap()
-        .assert_success();
+    let result = ctx.expect_success(
+        instructions::mint_tokens(&minter.pubkey(), &recipient.pubkey(), mint_amount),
+        &[&minter],
+    );
 
     // Verify destination token account was created and has tokens
     assert!(
@@ -392,543 +380,3054 @@ fn test_mint_tokens() {
         "Destination token account should exist"
     );
     ctx.svm.assert_token_balance(&destination_ata, mint_amount);
+    let token_account = ctx.token_state(&recipient);
+    assert_eq!(token_account.owner, recipient.pubkey());
+    assert_eq!(token_account.mint, pda::mint().0);
+
+    // Verify the allowance and supply moved with the mint
+    assert_eq!(ctx.minter_config(&minter).amount_minted, mint_amount);
+    assert_eq!(ctx.mint_state().supply, mint_amount);
+
+    // Verify the mint was reported through an event
+    let event: TokensMinted = result.parse_event().unwrap();
+    assert_eq!(event.minter, minter.pubkey());
+    assert_eq!(event.destination, destination_ata);
+    assert_eq!(event.amount, mint_amount);
+    assert_eq!(event.remaining_allowance, 900_000_000);
 }
 
 #[test]
 fn test_mint_exceeds_allowance() {
-    let mut ctx = setup_ctx();
-
-    let admin = ctx.svm.create_funded_account(10_000_000_000).unwrap();
-    let minter = ctx.svm.create_funded_account(10_000_000_000).unwrap();
+    let mut ctx = TestContext::new();
+    let minter = ctx.funded_account();
     let recipient = Keypair::new();
+    ctx.initialize().configure_minter(&minter, 100_000_000); // 100 token allowance
 
-    initialize_program(&mut ctx, &admin);
-    configure_minter(&mut ctx, &admin, &minter.pubkey(), 100_000_000); // 100 token allowance
-
-    let config_pda = get_config_pda();
-    let mint_pda = get_mint_pda();
-    let minter_config_pda = get_minter_config_pda(&minter.pubkey());
-    let destination_ata = get_ata(&recipient.pubkey(), &mint_pda);
-
-    // Try to mint more than allowance
-    let mint_amount: u64 = 200_000_000; // 200 tokens (exceeds 100 allowance)
-
-    let ix = ctx
-        .program()
-        .accounts(client::accounts::MintTokens {
-            minter: minter.pubkey(),
-            config: config_pda,
-            minter_config: minter_config_pda,
-            mint: mint_pda,
-            destination: destination_ata,
-            destination_owner: recipient.pubkey(),
-            token_program: TOKEN_PROGRAM_ID,
-            associated_token_program: ASSOCIATED_TOKEN_PROGRAM_ID,
-            system_program: SYSTEM_PROGRAM_ID,
-        })
-        .args(client::args::MintTokens {
-            amount: mint_amount,
-        })
-        .instruction()
-        .unwrap();
+    // Try to mint more than allowance: 200 tokens (exceeds 100 allowance)
+    let ix = instructions::mint_tokens(&minter.pubkey(), &recipient.pubkey(), 200_000_000);
+    ctx.expect_failure(ix, &[&minter]);
 
-    let result = ctx.execute_instruction(ix, &[&minter]);
-    assert!(
-        result.is_err() || !result.unwrap().is_success(),
-        "Mint exceeding allowance should fail"
-    );
+    assert_eq!(ctx.minter_config(&minter).amount_minted, 0);
+    assert_eq!(ctx.mint_state().supply, 0);
 }
 
 #[test]
 fn test_mint_unauthorized() {
-    let mut ctx = setup_ctx();
-
-    let admin = ctx.svm.create_funded_account(10_000_000_000).unwrap();
-    let unauthorized = ctx.svm.create_funded_account(10_000_000_000).unwrap();
+    let mut ctx = TestContext::new();
+    let unauthorized = ctx.funded_account();
     let recipient = Keypair::new();
 
-    initialize_program(&mut ctx, &admin);
     // Note: unauthorized is NOT configured as a minter
+    ctx.initialize();
 
-    let config_pda = get_config_pda();
-    let mint_pda = get_mint_pda();
-    let minter_config_pda = get_minter_config_pda(&unauthorized.pubkey());
-    let destination_ata = get_ata(&recipient.pubkey(), &mint_pda);
-
-    let mint_amount: u64 = 100_000_000;
-
-    let ix = ctx
-        .program()
-        .accounts(client::accounts::MintTokens {
-            minter: unauthorized.pubkey(),
-            config: config_pda,
-            minter_config: minter_config_pda,
-            mint: mint_pda,
-            destination: destination_ata,
-            destination_owner: recipient.pubkey(),
-            token_program: TOKEN_PROGRAM_ID,
-            associated_token_program: ASSOCIATED_TOKEN_PROGRAM_ID,
-            system_program: SYSTEM_PROGRAM_ID,
-        })
-        .args(client::args::MintTokens {
-            amount: mint_amount,
-        })
-        .instruction()
-        .unwrap();
-
-    let result = ctx.execute_instruction(ix, &[&unauthorized]);
-    assert!(
-        result.is_err() || !result.unwrap().is_success(),
-        "Unauthorized mint should fail"
-    );
+    let ix = instructions::mint_tokens(&unauthorized.pubkey(), &recipient.pubkey(), 100_000_000);
+    ctx.expect_failure(ix, &[&unauthorized]);
+    assert_eq!(ctx.mint_state().supply, 0);
 }
 
 // ============================================================================
-// Burn Tokens Tests
+// Idempotent Mint Tests
 // ============================================================================
 
-fn mint_tokens(
-    ctx: &mut anchor_litesvm::AnchorContext,
-    minter: &Keypair,
-    recipient: &Pubkey,
-    amount: u64,
-) {
-    let config_pda = get_config_pda();
-    let mint_pda = get_mint_pda();
-    let minter_config_pda = get_minter_config_pda(&minter.pubkey());
-    let destination_ata = get_ata(recipient, &mint_pda);
-
-    let ix = ctx
-        .program()
-        .accounts(client::accounts::MintTokens {
+#[test]
+fn test_idempotent_mint_once_per_key() {
+    let mut ctx = TestContext::new();
+    let minter = ctx.funded_account();
+    let recipient = Keypair::new();
+    let key = [7; 16];
+    ctx.initialize().configure_minter(&minter, 1_000_000_000);
+
+    let ix = instructions::mint_tokens_idempotent(
+        &minter.pubkey(),
+        &recipient.pubkey(),
+        100_000_000,
+        key,
+    );
+    ctx.expect_success(ix.clone(), &[&minter]);
+    assert_eq!(ctx.balance(&recipient), 100_000_000);
+
+    let record = ctx.idempotency_record(&minter, &key);
+    assert_eq!(
+        record,
+        IdempotencyRecord {
             minter: minter.pubkey(),
-            config: config_pda,
-            minter_config: minter_config_pda,
-            mint: mint_pda,
-            destination: destination_ata,
-            destination_owner: *recipient,
-            token_program: TOKEN_PROGRAM_ID,
-            associated_token_program: ASSOCIATED_TOKEN_PROGRAM_ID,
-            system_program: SYSTEM_PROGRAM_ID,
-        })
-        .args(client::args::MintTokens { amount })
-        .instruction()
-        .unwrap();
+            key,
+            amount: 100_000_000,
+            expires_at: ctx.clock().unix_timestamp + IDEMPOTENCY_KEY_TTL,
+            bump: pda::idempotency_record(&minter.pubkey(), &key).1,
+        }
+    );
 
-    ctx.execute_instruction(ix, &[minter])
-        .expect("Mint should succeed")
-        .assert_success();
+    // A retry of the same request, as a backend resends a transaction that
+    // timed out, mints nothing
+    ctx.svm.expire_blockhash();
+    let result = ctx.execute(ix, &[&minter]);
+    assert!(!result.is_success(), "Minted twice with one key");
+    result.assert_error_code(StablecoinError::DuplicateMint.into());
+    assert_eq!(ctx.balance(&recipient), 100_000_000);
+    assert_eq!(ctx.minter_config(&minter).amount_minted, 100_000_000);
+    assert_eq!(ctx.idempotency_record(&minter, &key), record);
+
+    // Another key mints again
+    ctx.expect_success(
+        instructions::mint_tokens_idempotent(
+            &minter.pubkey(),
+            &recipient.pubkey(),
+            100_000_000,
+            [8; 16],
+        ),
+        &[&minter],
+    );
+    assert_eq!(ctx.balance(&recipient), 200_000_000);
 }
 
 #[test]
-fn test_burn_tokens() {
-    let mut ctx = setup_ctx();
-
-    let admin = ctx.svm.create_funded_account(10_000_000_000).unwrap();
-    let minter = ctx.svm.create_funded_account(10_000_000_000).unwrap();
-    let user = ctx.svm.create_funded_account(10_000_000_000).unwrap();
-
-    initialize_program(&mut ctx, &admin);
-    configure_minter(&mut ctx, &admin, &minter.pubkey(), 1_000_000_000);
-    mint_tokens(&mut ctx, &minter, &user.pubkey(), 100_000_000);
-
-    let config_pda = get_config_pda();
-    let mint_pda = get_mint_pda();
-    let user_ata = get_ata(&user.pubkey(), &mint_pda);
+fn test_idempotency_keys_are_per_minter() {
+    let mut ctx = TestContext::new();
+    let minter = ctx.funded_account();
+    let other_minter = ctx.funded_account();
+    let recipient = Keypair::new();
+    let key = [7; 16];
+    ctx.initialize()
+        .configure_minter(&minter, 1_000_000_000)
+        .configure_minter(&other_minter, 1_000_000_000);
+
+    for minter in [&minter, &other_minter] {
+        ctx.expect_success(
+            instructions::mint_tokens_idempotent(&minter.pubkey(), &recipient.pubkey(), 1, key),
+            &[minter],
+        );
+    }
+    assert_eq!(ctx.balance(&recipient), 2);
+}
 
-    // Burn tokens
-    let burn_amount: u64 = 50_000_000;
+#[test]
+fn test_reclaim_idempotency_record() {
+    let mut ctx = TestContext::new();
+    let minter = ctx.funded_account();
+    let recipient = Keypair::new();
+    let key = [7; 16];
+    let record = pda::idempotency_record(&minter.pubkey(), &key).0;
+    ctx.initialize().configure_minter(&minter, 1_000_000_000);
+    let mint = instructions::mint_tokens_idempotent(&minter.pubkey(), &recipient.pubkey(), 1, key);
+    ctx.expect_success(mint.clone(), &[&minter]);
+    let reclaim = instructions::reclaim_idempotency_record(&minter.pubkey(), &key);
+
+    // Not before the record expires
+    ctx.advance_time(IDEMPOTENCY_KEY_TTL as u64 - 1);
+    let result = ctx.execute(reclaim.clone(), &[&minter]);
+    assert!(!result.is_success(), "Reclaimed a live record");
+    result.assert_error_code(StablecoinError::IdempotencyRecordNotExpired.into());
+
+    // Nor by anyone but the minter that made it
+    ctx.advance_time(1);
+    let other = ctx.funded_account();
+    let result = ctx.execute(
+        substitute(reclaim.clone(), &minter.pubkey(), &other.pubkey()),
+        &[&other],
+    );
+    assert!(!result.is_success(), "Reclaimed another minter's record");
+
+    // Then the rent goes back to the minter, and the key is free again. A
+    // separate fee payer, so the minter's balance moves by the refund alone
+    let payer = ctx.funded_account();
+    let rent = ctx.svm.get_account(&record).unwrap().lamports;
+    let before = ctx.svm.get_balance(&minter.pubkey()).unwrap();
+    ctx.expect_success(reclaim, &[&payer, &minter]);
+    ctx.svm.assert_account_closed(&record);
+    assert_eq!(
+        ctx.svm.get_balance(&minter.pubkey()).unwrap(),
+        before + rent
+    );
 
-    let ix = ctx
-        .program()
-        .accounts(client::accounts::BurnTokens {
-            owner: user.pubkey(),
-            config: config_pda,
-            mint: mint_pda,
-            token_account: user_ata,
-            token_program: TOKEN_PROGRAM_ID,
-        })
-        .args(client::args::BurnTokens {
-            amount: burn_amount,
-        })
-        .instruction()
-        .unwrap();
+    ctx.svm.expire_blockhash();
+    ctx.expect_success(mint, &[&minter]);
+    assert_eq!(ctx.balance(&recipient), 2);
+}
 
-    ctx.execute_instruction(ix, &[&user])
-        .unwrap()
-        .assert_success();
+// ============================================================================
+// Burn Tokens Tests
+// ============================================================================
 
-    // Verify remaining balance
-    ctx.svm.assert_token_balance(&user_ata, 50_000_000);
+#[test]
+fn test_burn_tokens() {
+    let mut ctx = TestContext::new();
+    let minter = ctx.funded_account();
+    let user = ctx.funded_account();
+
+    ctx.initialize()
+        .configure_minter(&minter, 1_000_000_000)
+        .mint(&minter, &user, 100_000_000)
+        .burn(&user, 50_000_000);
+
+    // Verify remaining balance and supply
+    assert_eq!(ctx.balance(&user), 50_000_000);
+    assert_eq!(ctx.mint_state().supply, 50_000_000);
 }
 
 #[test]
 fn test_burn_more_than_balance() {
-    let mut ctx = setup_ctx();
-
-    let admin = ctx.svm.create_funded_account(10_000_000_000).unwrap();
-    let minter = ctx.svm.create_funded_account(10_000_000_000).unwrap();
-    let user = ctx.svm.create_funded_account(10_000_000_000).unwrap();
-
-    initialize_program(&mut ctx, &admin);
-    configure_minter(&mut ctx, &admin, &minter.pubkey(), 1_000_000_000);
-    mint_tokens(&mut ctx, &minter, &user.pubkey(), 100_000_000); // 100 tokens
-
-    let config_pda = get_config_pda();
-    let mint_pda = get_mint_pda();
-    let user_ata = get_ata(&user.pubkey(), &mint_pda);
-
-    // Try to burn more than balance
-    let burn_amount: u64 = 200_000_000; // 200 tokens (only have 100)
-
-    let ix = ctx
-        .program()
-        .accounts(client::accounts::BurnTokens {
-            owner: user.pubkey(),
-            config: config_pda,
-            mint: mint_pda,
-            token_account: user_ata,
-            token_program: TOKEN_PROGRAM_ID,
-        })
-        .args(client::args::BurnTokens {
-            amount: burn_amount,
-        })
-        .instruction()
-        .unwrap();
-
-    let result = ctx.execute_instruction(ix, &[&user]);
-    assert!(
-        result.is_err() || !result.unwrap().is_success(),
-        "Burn more than balance should fail"
+    let mut ctx = TestContext::new();
+    let minter = ctx.funded_account();
+    let user = ctx.funded_account();
+
+    ctx.initialize()
+        .configure_minter(&minter, 1_000_000_000)
+        .mint(&minter, &user, 100_000_000); // 100 tokens
+
+    // Try to burn more than balance: 200 tokens (only have 100)
+    ctx.expect_failure(
+        instructions::burn_tokens(&user.pubkey(), 200_000_000),
+        &[&user],
     );
+    assert_eq!(ctx.balance(&user), 100_000_000);
+    assert_eq!(ctx.mint_state().supply, 100_000_000);
 }
 
 // ============================================================================
-// Pause/Unpause Tests
+// Sweep Dust Tests
 // ============================================================================
 
 #[test]
-fn test_pause() {
-    let mut ctx = setup_ctx();
-
-    let admin = ctx.svm.create_funded_account(10_000_000_000).unwrap();
-    initialize_program(&mut ctx, &admin);
+fn test_sweep_dust() {
+    let mut ctx = TestContext::new();
+    let minter = ctx.funded_account();
+    let owner = ctx.funded_account();
+    let payer = ctx.funded_account();
+    let (first, second) = (Keypair::new(), Keypair::new());
+    ctx.initialize()
+        .configure_minter(&minter, 1_000_000_000)
+        .mint(&minter, &owner, 100)
+        .open_token_account(&first, &owner, 7)
+        .open_token_account(&second, &owner, 0);
+    let rent: u64 = [&first, &second]
+        .iter()
+        .map(|account| ctx.svm.get_account(&account.pubkey()).unwrap().lamports)
+        .sum();
+
+    // A separate fee payer, so the owner's balance moves by the refunds alone
+    let before = ctx.svm.get_balance(&owner.pubkey()).unwrap();
+    ctx.expect_success(
+        instructions::sweep_dust(&owner.pubkey(), &[first.pubkey(), second.pubkey()], 7),
+        &[&payer, &owner],
+    );
 
-    let config_pda = get_config_pda();
+    assert_eq!(ctx.balance(&owner), 100);
+    assert_eq!(ctx.mint_state().supply, 100);
+    ctx.svm.assert_account_closed(&first.pubkey());
+    ctx.svm.assert_account_closed(&second.pubkey());
+    assert_eq!(ctx.svm.get_balance(&owner.pubkey()).unwrap(), before + rent);
+}
 
-    let ix = ctx
-        .program()
-        .accounts(client::accounts::Pause {
-            admin: admin.pubkey(),
-            config: config_pda,
-        })
-        .args(client::args::Pause {})
-        .instruction()
-        .unwrap();
+#[test]
+fn test_sweep_dust_above_threshold() {
+    let mut ctx = TestContext::new();
+    let minter = ctx.funded_account();
+    let owner = ctx.funded_account();
+    let (dust, savings) = (Keypair::new(), Keypair::new());
+    ctx.initialize()
+        .configure_minter(&minter, 1_000_000_000)
+        .mint(&minter, &owner, 100)
+        .open_token_account(&dust, &owner, 1)
+        .open_token_account(&savings, &owner, 50);
+
+    let result = ctx.execute(
+        instructions::sweep_dust(&owner.pubkey(), &[dust.pubkey(), savings.pubkey()], 10),
+        &[&owner],
+    );
+    assert!(!result.is_success(), "Swept an account above the threshold");
+    result.assert_error_code(StablecoinError::NotDust.into());
 
-    ctx.execute_instruction(ix, &[&admin])
-        .unwrap()
-        .assert_success();
+    // Nothing moved, not even the dust listed first
+    assert_eq!(ctx.balance(&owner), 49);
+    assert!(ctx.svm.get_account(&dust.pubkey()).is_some());
 }
 
 #[test]
-fn test_pause_unauthorized() {
-    let mut ctx = setup_ctx();
-
-    let admin = ctx.svm.create_funded_account(10_000_000_000).unwrap();
-    let unauthorized = ctx.svm.create_funded_account(10_000_000_000).unwrap();
-    initialize_program(&mut ctx, &admin);
-
-    let config_pda = get_config_pda();
-
-    let ix = ctx
-        .program()
-        .accounts(client::accounts::Pause {
-            admin: unauthorized.pubkey(),
-            config: config_pda,
-        })
-        .args(client::args::Pause {})
-        .instruction()
-        .unwrap();
-
-    let result = ctx.execute_instruction(ix, &[&unauthorized]);
-    assert!(
-        result.is_err() || !result.unwrap().is_success(),
-        "Unauthorized pause should fail"
+fn test_sweep_dust_rejects_other_accounts() {
+    let mut ctx = TestContext::new();
+    let minter = ctx.funded_account();
+    let owner = ctx.funded_account();
+    let victim = ctx.funded_account();
+    let (dust, victims_dust) = (Keypair::new(), Keypair::new());
+    ctx.initialize()
+        .configure_minter(&minter, 1_000_000_000)
+        .mint(&minter, &owner, 100)
+        .mint(&minter, &victim, 100)
+        .open_token_account(&dust, &owner, 1)
+        .open_token_account(&victims_dust, &victim, 1);
+
+    let cases = [
+        (victims_dust.pubkey(), ErrorCode::ConstraintTokenOwner),
+        (
+            pda::token_account(&owner.pubkey()),
+            ErrorCode::ConstraintDuplicateMutableAccount,
+        ),
+        (pda::config().0, ErrorCode::AccountOwnedByWrongProgram),
+    ];
+    for (source, error) in cases {
+        let result = ctx.execute(
+            instructions::sweep_dust(&owner.pubkey(), &[dust.pubkey(), source], 1),
+            &[&owner],
+        );
+        assert!(!result.is_success(), "Swept {source}");
+        result.assert_error_code(error.into());
+    }
+
+    // Nor can one account be swept twice
+    let result = ctx.execute(
+        instructions::sweep_dust(&owner.pubkey(), &[dust.pubkey(), dust.pubkey()], 1),
+        &[&owner],
     );
+    assert!(!result.is_success(), "Swept an account twice");
+    assert_eq!(ctx.balance(&owner), 99);
+    assert_eq!(ctx.balance(&victim), 99);
 }
 
-fn pause_program(ctx: &mut anchor_litesvm::AnchorContext, admin: &Keypair) {
-    let config_pda = get_config_pda();
+// ============================================================================
+// Treasury Burn Tests
+// ============================================================================
 
-    let ix = ctx
-        .program()
-        .accounts(client::accounts::Pause {
-            admin: admin.pubkey(),
-            config: config_pda,
-        })
-        .args(client::args::Pause {})
-        .instruction()
-        .unwrap();
+/// Treasury custody's balance
+fn custody_balance(ctx: &TestContext) -> u64 {
+    ctx.account::<TokenAccount>(&pda::treasury_custody()).amount
+}
 
-    ctx.execute_instruction(ix, &[admin])
-        .expect("Pause should succeed")
-        .assert_success();
+/// An initialized stablecoin with `amount` in treasury custody, whose
+/// officers are the two keypairs returned
+fn treasury(ctx: &mut TestContext, threshold: u64, amount: u64) -> (Keypair, Keypair) {
+    let minter = ctx.funded_account();
+    let officers = (ctx.funded_account(), ctx.funded_account());
+    ctx.initialize()
+        .configure_minter(&minter, amount)
+        .configure_treasury([&officers.0, &officers.1], threshold);
+    ctx.expect_success(
+        instructions::mint_tokens(&minter.pubkey(), &pda::treasury().0, amount),
+        &[&minter],
+    );
+    officers
 }
 
 #[test]
-fn test_mint_when_paused() {
-    let mut ctx = setup_ctx();
-
-    let admin = ctx.svm.create_funded_account(10_000_000_000).unwrap();
-    let minter = ctx.svm.create_funded_account(10_000_000_000).unwrap();
-    let recipient = Keypair::new();
-
-    initialize_program(&mut ctx, &admin);
-    configure_minter(&mut ctx, &admin, &minter.pubkey(), 1_000_000_000);
-    pause_program(&mut ctx, &admin);
-
-    let config_pda = get_config_pda();
-    let mint_pda = get_mint_pda();
-    let minter_config_pda = get_minter_config_pda(&minter.pubkey());
-    let destination_ata = get_ata(&recipient.pubkey(), &mint_pda);
-
-    // Try to mint when paused
-    let mint_amount: u64 = 100_000_000;
-
-    let ix = ctx
-        .program()
-        .accounts(client::accounts::MintTokens {
-            minter: minter.pubkey(),
-            config: config_pda,
-            minter_config: minter_config_pda,
-            mint: mint_pda,
-            destination: destination_ata,
-            destination_owner: recipient.pubkey(),
-            token_program: TOKEN_PROGRAM_ID,
-            associated_token_program: ASSOCIATED_TOKEN_PROGRAM_ID,
-            system_program: SYSTEM_PROGRAM_ID,
-        })
-        .args(client::args::MintTokens {
-            amount: mint_amount,
-        })
-        .instruction()
-        .unwrap();
+fn test_configure_treasury() {
+    let mut ctx = TestContext::new();
+    let (first, second) = treasury(&mut ctx, 100_000_000, 0);
+
+    assert_eq!(
+        ctx.treasury(),
+        Treasury {
+            officers: [first.pubkey(), second.pubkey()],
+            threshold: 100_000_000,
+            bump: pda::treasury().1,
+        }
+    );
 
-    let result = ctx.execute_instruction(ix, &[&minter]);
-    assert!(
-        result.is_err() || !result.unwrap().is_success(),
-        "Mint when paused should fail"
+    // Reconfiguring replaces both
+    let admin = ctx.admin.insecure_clone();
+    let third = Keypair::new();
+    let result = ctx.expect_success(
+        instructions::configure_treasury(&admin.pubkey(), [second.pubkey(), third.pubkey()], 0),
+        &[&admin],
+    );
+    let event: TreasuryConfigured = result.parse_event().unwrap();
+    assert_eq!(
+        event,
+        TreasuryConfigured {
+            admin: admin.pubkey(),
+            officers: [second.pubkey(), third.pubkey()],
+            threshold: 0,
+        }
     );
+    assert_eq!(ctx.treasury().officers, [second.pubkey(), third.pubkey()]);
+    assert_eq!(ctx.treasury().threshold, 0);
 }
 
 #[test]
-fn test_unpause() {
-    let mut ctx = setup_ctx();
+fn test_configure_treasury_rejects_invalid_officers() {
+    let mut ctx = TestContext::new();
+    let admin = ctx.admin.insecure_clone();
+    let officer = Pubkey::new_unique();
+    ctx.initialize();
+
+    for officers in [[officer, officer], [officer, Pubkey::default()]] {
+        let result = ctx.execute(
+            instructions::configure_treasury(&admin.pubkey(), officers, 0),
+            &[&admin],
+        );
+        assert!(!result.is_success(), "Appointed {officers:?}");
+        result.assert_error_code(StablecoinError::InvalidOfficers.into());
+    }
+
+    // Only the admin appoints officers
+    let attacker = ctx.funded_account();
+    let result = ctx.execute(
+        instructions::configure_treasury(&attacker.pubkey(), [attacker.pubkey(), officer], 0),
+        &[&attacker],
+    );
+    assert!(!result.is_success(), "Non-admin appointed officers");
+    result.assert_error_code(StablecoinError::Unauthorized.into());
+}
 
-    let admin = ctx.svm.create_funded_account(10_000_000_000).unwrap();
-    initialize_program(&mut ctx, &admin);
-    pause_program(&mut ctx, &admin);
+#[test]
+fn test_treasury_burn_above_threshold_needs_both_officers() {
+    let mut ctx = TestContext::new();
+    let (first, second) = treasury(&mut ctx, 100_000_000, 1_000_000_000);
 
-    let config_pda = get_config_pda();
+    let result = ctx.expect_success(
+        instructions::propose_treasury_burn(&first.pubkey(), 1, 500_000_000),
+        &[&first],
+    );
+    let event: TreasuryBurnProposed = result.parse_event().unwrap();
+    assert_eq!(
+        event,
+        TreasuryBurnProposed {
+            id: 1,
+            proposer: first.pubkey(),
+            amount: 500_000_000,
+        }
+    );
+    assert_eq!(
+        ctx.pending_burn(1),
+        PendingBurn {
+            id: 1,
+            proposer: first.pubkey(),
+            amount: 500_000_000,
+            approvals: [first.pubkey(), Pubkey::default()],
+            bump: pda::pending_burn(1).1,
+        }
+    );
 
-    let ix = ctx
-        .program()
-        .accounts(client::accounts::Unpause {
-            admin: admin.pubkey(),
-            config: config_pda,
-        })
-        .args(client::args::Unpause {})
-        .instruction()
-        .unwrap();
+    // The proposer's approval alone is not enough, and counts once
+    let execute = instructions::execute_treasury_burn(&first.pubkey(), &first.pubkey(), 1);
+    let result = ctx.execute(execute.clone(), &[&first]);
+    assert!(!result.is_success(), "Burned with one approval");
+    result.assert_error_code(StablecoinError::InsufficientApprovals.into());
+    let result = ctx.execute(
+        instructions::approve_treasury_burn(&first.pubkey(), 1),
+        &[&first],
+    );
+    assert!(!result.is_success(), "Approved twice");
+    result.assert_error_code(StablecoinError::AlreadyApproved.into());
+    assert_eq!(custody_balance(&ctx), 1_000_000_000);
 
-    ctx.execute_instruction(ix, &[&admin])
+    let result = ctx.expect_success(
+        instructions::approve_treasury_burn(&second.pubkey(), 1),
+        &[&second],
+    );
+    let event: TreasuryBurnApproved = result.parse_event().unwrap();
+    assert_eq!(
+        event,
+        TreasuryBurnApproved {
+            id: 1,
+            officer: second.pubkey(),
+        }
+    );
+    let rent = ctx
+        .svm
+        .get_account(&pda::pending_burn(1).0)
         .unwrap()
-        .assert_success();
+        .lamports;
+    let before = ctx.svm.get_balance(&first.pubkey()).unwrap();
+    // Either officer can execute it, with its rent back to the proposer
+    ctx.expect_success(
+        instructions::execute_treasury_burn(&second.pubkey(), &first.pubkey(), 1),
+        &[&second],
+    );
+    assert_eq!(custody_balance(&ctx), 500_000_000);
+    assert_eq!(ctx.mint_state().supply, 500_000_000);
+    ctx.svm.assert_account_closed(&pda::pending_burn(1).0);
+    assert_eq!(ctx.svm.get_balance(&first.pubkey()).unwrap(), before + rent);
 }
 
 #[test]
-fn test_mint_after_unpause() {
-    let mut ctx = setup_ctx();
-
-    let admin = ctx.svm.create_funded_account(10_000_000_000).unwrap();
-    let minter = ctx.svm.create_funded_account(10_000_000_000).unwrap();
-    let recipient = Keypair::new();
+fn test_treasury_burn_within_threshold_needs_one_officer() {
+    let mut ctx = TestContext::new();
+    let (first, _) = treasury(&mut ctx, 100_000_000, 1_000_000_000);
 
-    initialize_program(&mut ctx, &admin);
-    configure_minter(&mut ctx, &admin, &minter.pubkey(), 1_000_000_000);
-    pause_program(&mut ctx, &admin);
-
-    let config_pda = get_config_pda();
+    ctx.expect_success(
+        instructions::propose_treasury_burn(&first.pubkey(), 1, 100_000_000),
+        &[&first],
+    );
+    ctx.expect_success(
+        instructions::execute_treasury_burn(&first.pubkey(), &first.pubkey(), 1),
+        &[&first],
+    );
+    assert_eq!(custody_balance(&ctx), 900_000_000);
+    assert_eq!(ctx.mint_state().supply, 900_000_000);
+}
 
-    // Unpause
-    let unpause_ix = ctx
-        .program()
-        .accounts(client::accounts::Unpause {
-            admin: admin.pubkey(),
-            config: config_pda,
-        })
-        .args(client::args::Unpause {})
-        .instruction()
-        .unwrap();
+#[test]
+fn test_treasury_burn_requires_officer() {
+    let mut ctx = TestContext::new();
+    let (first, _) = treasury(&mut ctx, 0, 1_000_000_000);
+    let attacker = ctx.funded_account();
+
+    let result = ctx.execute(
+        instructions::propose_treasury_burn(&attacker.pubkey(), 1, 1_000_000_000),
+        &[&attacker],
+    );
+    assert!(!result.is_success(), "Non-officer proposed a burn");
+    result.assert_error_code(StablecoinError::NotOfficer.into());
 
-    ctx.execute_instruction(unpause_ix, &[&admin])
-        .expect("Unpause should succeed")
-        .assert_success();
+    ctx.expect_success(
+        instructions::propose_treasury_burn(&first.pubkey(), 1, 1_000_000_000),
+        &[&first],
+    );
+    for ix in [
+        instructions::approve_treasury_burn(&attacker.pubkey(), 1),
+        instructions::execute_treasury_burn(&attacker.pubkey(), &first.pubkey(), 1),
+        instructions::cancel_treasury_burn(&attacker.pubkey(), &first.pubkey(), 1),
+    ] {
+        let name = instructions::decode(&ix.data).unwrap().name();
+        let result = ctx.execute(ix, &[&attacker]);
+        assert!(!result.is_success(), "Non-officer ran {name}");
+        result.assert_error_code(StablecoinError::NotOfficer.into());
+    }
+
+    // Nor does the rent of a proposal go anywhere but its proposer
+    let result = ctx.execute(
+        instructions::cancel_treasury_burn(&first.pubkey(), &attacker.pubkey(), 1),
+        &[&first],
+    );
+    assert!(
+        !result.is_success(),
+        "Refunded a proposal to another account"
+    );
+    result.assert_error_code(ErrorCode::ConstraintHasOne.into());
+
+    // Custody has no owner that could sign a plain burn
+    let result = ctx.execute(
+        substitute(
+            instructions::burn_tokens(&first.pubkey(), 1),
+            &pda::token_account(&first.pubkey()),
+            &pda::treasury_custody(),
+        ),
+        &[&first],
+    );
+    assert!(
+        !result.is_success(),
+        "Burned from custody without approvals"
+    );
+    assert_eq!(custody_balance(&ctx), 1_000_000_000);
+}
 
-    // Now mint should work
-    let mint_pda = get_mint_pda();
-    let minter_config_pda = get_minter_config_pda(&minter.pubkey());
-    let destination_ata = get_ata(&recipient.pubkey(), &mint_pda);
+#[test]
+fn test_replaced_officer_approvals_do_not_count() {
+    let mut ctx = TestContext::new();
+    let (first, second) = treasury(&mut ctx, 0, 1_000_000_000);
+    let third = ctx.funded_account();
+
+    ctx.expect_success(
+        instructions::propose_treasury_burn(&first.pubkey(), 1, 1),
+        &[&first],
+    );
+    ctx.configure_treasury([&second, &third], 0);
 
-    let mint_amount: u64 = 100_000_000;
+    let result = ctx.execute(
+        instructions::execute_treasury_burn(&second.pubkey(), &first.pubkey(), 1),
+        &[&second],
+    );
+    assert!(
+        !result.is_success(),
+        "Counted a replaced officer's approval"
+    );
+    result.assert_error_code(StablecoinError::InsufficientApprovals.into());
 
-    let mint_ix = ctx
-        .program()
-        .accounts(client::accounts::MintTokens {
-            minter: minter.pubkey(),
-            config: config_pda,
-            minter_config: minter_config_pda,
-            mint: mint_pda,
-            destination: destination_ata,
-            destination_owner: recipient.pubkey(),
-            token_program: TOKEN_PROGRAM_ID,
-            associated_token_program: ASSOCIATED_TOKEN_PROGRAM_ID,
-            system_program: SYSTEM_PROGRAM_ID,
-        })
-        .args(client::args::MintTokens {
-            amount: mint_amount,
-        })
-        .instruction()
-        .unwrap();
+    // The officers now in place can still carry it
+    ctx.expect_success(
+        instructions::approve_treasury_burn(&second.pubkey(), 1),
+        &[&second],
+    );
+    ctx.expect_success(
+        instructions::approve_treasury_burn(&third.pubkey(), 1),
+        &[&third],
+    );
+    ctx.svm.expire_blockhash();
+    ctx.expect_success(
+        instructions::execute_treasury_burn(&second.pubkey(), &first.pubkey(), 1),
+        &[&second],
+    );
+    assert_eq!(custody_balance(&ctx), 1_000_000_000 - 1);
+}
+
+#[test]
+fn test_cancel_treasury_burn() {
+    let mut ctx = TestContext::new();
+    let (first, second) = treasury(&mut ctx, 0, 1_000_000_000);
 
-    ctx.execute_instruction(mint_ix, &[&minter])
+    ctx.expect_success(
+        instructions::propose_treasury_burn(&first.pubkey(), 1, 1_000_000_000),
+        &[&first],
+    );
+    let rent = ctx
+        .svm
+        .get_account(&pda::pending_burn(1).0)
         .unwrap()
-        .assert_success();
+        .lamports;
+    let before = ctx.svm.get_balance(&first.pubkey()).unwrap();
+    let result = ctx.expect_success(
+        instructions::cancel_treasury_burn(&second.pubkey(), &first.pubkey(), 1),
+        &[&second],
+    );
+    let event: TreasuryBurnCancelled = result.parse_event().unwrap();
+    assert_eq!(
+        event,
+        TreasuryBurnCancelled {
+            id: 1,
+            officer: second.pubkey(),
+        }
+    );
+    ctx.svm.assert_account_closed(&pda::pending_burn(1).0);
+    assert_eq!(ctx.svm.get_balance(&first.pubkey()).unwrap(), before + rent);
 
-    ctx.svm.assert_token_balance(&destination_ata, mint_amount);
+    // Nothing is left to approve, and custody is untouched
+    let result = ctx.execute(
+        instructions::approve_treasury_burn(&second.pubkey(), 1),
+        &[&second],
+    );
+    assert!(!result.is_success(), "Approved a cancelled burn");
+    assert_eq!(custody_balance(&ctx), 1_000_000_000);
 }
 
 // ============================================================================
-// Integration Tests
+// Social Recovery Tests
 // ============================================================================
 
+/// An initialized stablecoin whose holder has `amount` and has opted into
+/// recovery by three guardians, two of which must approve
+fn recoverable(ctx: &mut TestContext, amount: u64) -> (Keypair, [Keypair; 3]) {
+    let minter = ctx.funded_account();
+    let holder = ctx.funded_account();
+    let guardians = [
+        ctx.funded_account(),
+        ctx.funded_account(),
+        ctx.funded_account(),
+    ];
+    ctx.initialize()
+        .configure_minter(&minter, amount)
+        .mint(&minter, &holder, amount)
+        .configure_recovery(&holder, &guardians.each_ref(), 2);
+    (holder, guardians)
+}
+
 #[test]
-fn test_full_stablecoin_flow() {
-    let mut ctx = setup_ctx();
+fn test_configure_recovery() {
+    let mut ctx = TestContext::new();
+    let (holder, guardians) = recoverable(&mut ctx, 1_000_000);
+
+    assert_eq!(
+        ctx.recovery_config(&holder),
+        RecoveryConfig {
+            owner: holder.pubkey(),
+            guardians: guardians.iter().map(|guardian| guardian.pubkey()).collect(),
+            quorum: 2,
+            challenge_period: MIN_RECOVERY_CHALLENGE_PERIOD,
+            new_owner: Pubkey::default(),
+            initiated_at: 0,
+            approvals: 0,
+            bump: pda::recovery_config(&holder.pubkey()).1,
+        }
+    );
+    // The delegate can move the whole balance, whatever it grows to
+    let token_account = ctx.token_state(&holder);
+    assert_eq!(token_account.delegate, COption::Some(pda::delegate().0));
+    assert_eq!(token_account.delegated_amount, u64::MAX);
+
+    // Reconfiguring replaces the guardians and any recovery in progress
+    ctx.expect_success(
+        instructions::initiate_recovery(
+            &guardians[0].pubkey(),
+            &holder.pubkey(),
+            &Pubkey::new_unique(),
+        ),
+        &[&guardians[0]],
+    );
+    let result = ctx.expect_success(
+        instructions::configure_recovery(
+            &holder.pubkey(),
+            vec![guardians[2].pubkey()],
+            1,
+            MIN_RECOVERY_CHALLENGE_PERIOD,
+        ),
+        &[&holder],
+    );
+    let event: RecoveryConfigured = result.parse_event().unwrap();
+    assert_eq!(
+        event,
+        RecoveryConfigured {
+            owner: holder.pubkey(),
+            guardians: vec![guardians[2].pubkey()],
+            quorum: 1,
+            challenge_period: MIN_RECOVERY_CHALLENGE_PERIOD,
+        }
+    );
+    let recovery_config = ctx.recovery_config(&holder);
+    assert_eq!(recovery_config.guardians, [guardians[2].pubkey()]);
+    assert_eq!(recovery_config.quorum, 1);
+    assert_eq!(recovery_config.new_owner, Pubkey::default());
+    assert_eq!(recovery_config.approvals, 0);
+}
+
+#[test]
+fn test_configure_recovery_rejects_invalid_settings() {
+    let mut ctx = TestContext::new();
+    let (holder, _) = recoverable(&mut ctx, 1_000_000);
+    let guardian = Pubkey::new_unique();
+    let period = MIN_RECOVERY_CHALLENGE_PERIOD;
+
+    for (guardians, quorum, challenge_period) in [
+        (vec![], 0, period),
+        (vec![guardian], 0, period),
+        (vec![guardian], 2, period),
+        (vec![guardian, guardian], 1, period),
+        (vec![guardian, Pubkey::default()], 1, period),
+        (vec![guardian, holder.pubkey()], 1, period),
+        ((0..6).map(|_| Pubkey::new_unique()).collect(), 1, period),
+        (vec![guardian], 1, period - 1),
+    ] {
+        let result = ctx.execute(
+            instructions::configure_recovery(
+                &holder.pubkey(),
+                guardians.clone(),
+                quorum,
+                challenge_period,
+            ),
+            &[&holder],
+        );
+        assert!(
+            !result.is_success(),
+            "Configured {guardians:?}, quorum {quorum}, period {challenge_period}"
+        );
+        result.assert_error_code(StablecoinError::InvalidRecoveryConfig.into());
+    }
+}
+
+#[test]
+fn test_recovery_needs_quorum_and_challenge_period() {
+    let mut ctx = TestContext::new();
+    let (holder, guardians) = recoverable(&mut ctx, 1_000_000);
+    let new_owner = Keypair::new();
+
+    ctx.expect_success(
+        instructions::initiate_recovery(
+            &guardians[0].pubkey(),
+            &holder.pubkey(),
+            &new_owner.pubkey(),
+        ),
+        &[&guardians[0]],
+    );
+    let recovery_config = ctx.recovery_config(&holder);
+    assert_eq!(recovery_config.new_owner, new_owner.pubkey());
+    assert_eq!(recovery_config.initiated_at, ctx.clock().unix_timestamp);
+    assert_eq!(recovery_config.approvals, 0b001);
+
+    // The initiator's approval alone is not a quorum, and counts once
+    let execute = instructions::execute_recovery(
+        &guardians[0].pubkey(),
+        &holder.pubkey(),
+        &new_owner.pubkey(),
+    );
+    let result = ctx.execute(execute.clone(), &[&guardians[0]]);
+    assert!(!result.is_success(), "Recovered with one approval");
+    result.assert_error_code(StablecoinError::RecoveryNotApproved.into());
+    let result = ctx.execute(
+        instructions::approve_recovery(&guardians[0].pubkey(), &holder.pubkey()),
+        &[&guardians[0]],
+    );
+    assert!(!result.is_success(), "Approved twice");
+    result.assert_error_code(StablecoinError::GuardianAlreadyApproved.into());
+
+    // A quorum still waits out the challenge period
+    ctx.expect_success(
+        instructions::approve_recovery(&guardians[2].pubkey(), &holder.pubkey()),
+        &[&guardians[2]],
+    );
+    assert_eq!(ctx.recovery_config(&holder).approvals, 0b101);
+    ctx.advance_time(MIN_RECOVERY_CHALLENGE_PERIOD as u64 - 1);
+    let result = ctx.execute(execute.clone(), &[&guardians[0]]);
+    assert!(
+        !result.is_success(),
+        "Recovered during the challenge period"
+    );
+    result.assert_error_code(StablecoinError::RecoveryChallengeActive.into());
+    assert_eq!(ctx.balance(&holder), 1_000_000);
+
+    ctx.advance_time(1);
+    let result = ctx.execute(execute, &[&guardians[0]]);
+    assert!(result.is_success(), "Recovery failed: {:?}", result.error());
+    let event: AccountRecovered = result.parse_event().unwrap();
+    assert_eq!(
+        event,
+        AccountRecovered {
+            owner: holder.pubkey(),
+            new_owner: new_owner.pubkey(),
+            amount: 1_000_000,
+        }
+    );
+    assert_eq!(ctx.balance(&holder), 0);
+    assert_eq!(ctx.balance(&new_owner), 1_000_000);
+    assert_eq!(ctx.mint_state().supply, 1_000_000);
+
+    // The settings stay for the next recovery
+    let recovery_config = ctx.recovery_config(&holder);
+    assert_eq!(recovery_config.new_owner, Pubkey::default());
+    assert_eq!(recovery_config.approvals, 0);
+    assert_eq!(recovery_config.guardians.len(), 3);
+}
 
-    let admin = ctx.svm.create_funded_account(10_000_000_000).unwrap();
-    let minter = ctx.svm.create_funded_account(10_000_000_000).unwrap();
-    let user1 = ctx.svm.create_funded_account(10_000_000_000).unwrap();
-    let user2 = ctx.svm.create_funded_account(10_000_000_000).unwrap();
+#[test]
+fn test_recovery_requires_guardian() {
+    let mut ctx = TestContext::new();
+    let (holder, guardians) = recoverable(&mut ctx, 1_000_000);
+    let attacker = ctx.funded_account();
+
+    let result = ctx.execute(
+        instructions::initiate_recovery(&attacker.pubkey(), &holder.pubkey(), &attacker.pubkey()),
+        &[&attacker],
+    );
+    assert!(!result.is_success(), "Non-guardian initiated a recovery");
+    result.assert_error_code(StablecoinError::NotGuardian.into());
+
+    ctx.expect_success(
+        instructions::initiate_recovery(
+            &guardians[0].pubkey(),
+            &holder.pubkey(),
+            &guardians[0].pubkey(),
+        ),
+        &[&guardians[0]],
+    );
+    ctx.advance_time(MIN_RECOVERY_CHALLENGE_PERIOD as u64);
+    for ix in [
+        instructions::approve_recovery(&attacker.pubkey(), &holder.pubkey()),
+        instructions::execute_recovery(
+            &attacker.pubkey(),
+            &holder.pubkey(),
+            &guardians[0].pubkey(),
+        ),
+    ] {
+        let name = instructions::decode(&ix.data).unwrap().name();
+        let result = ctx.execute(ix, &[&attacker]);
+        assert!(!result.is_success(), "Non-guardian ran {name}");
+        result.assert_error_code(StablecoinError::NotGuardian.into());
+    }
+
+    // Nor can another guardian redirect the recovery in progress
+    let result = ctx.execute(
+        instructions::initiate_recovery(
+            &guardians[1].pubkey(),
+            &holder.pubkey(),
+            &guardians[1].pubkey(),
+        ),
+        &[&guardians[1]],
+    );
+    assert!(!result.is_success(), "Replaced a recovery in progress");
+    result.assert_error_code(StablecoinError::RecoveryInProgress.into());
+    ctx.expect_success(
+        instructions::approve_recovery(&guardians[1].pubkey(), &holder.pubkey()),
+        &[&guardians[1]],
+    );
+    let result = ctx.execute(
+        instructions::execute_recovery(
+            &guardians[1].pubkey(),
+            &holder.pubkey(),
+            &guardians[1].pubkey(),
+        ),
+        &[&guardians[1]],
+    );
+    assert!(!result.is_success(), "Recovered to another wallet");
+    result.assert_error_code(ErrorCode::ConstraintAddress.into());
+    assert_eq!(ctx.balance(&holder), 1_000_000);
+}
 
-    // 1. Initialize
-    initialize_program(&mut ctx, &admin);
+#[test]
+fn test_owner_cancels_and_closes_recovery() {
+    let mut ctx = TestContext::new();
+    let (holder, guardians) = recoverable(&mut ctx, 1_000_000);
+
+    ctx.expect_success(
+        instructions::initiate_recovery(
+            &guardians[0].pubkey(),
+            &holder.pubkey(),
+            &guardians[0].pubkey(),
+        ),
+        &[&guardians[0]],
+    );
+    ctx.expect_success(
+        instructions::approve_recovery(&guardians[1].pubkey(), &holder.pubkey()),
+        &[&guardians[1]],
+    );
+    ctx.expect_success(instructions::cancel_recovery(&holder.pubkey()), &[&holder]);
+    ctx.advance_time(MIN_RECOVERY_CHALLENGE_PERIOD as u64);
+
+    // Nothing is left to approve or execute
+    let result = ctx.execute(
+        instructions::execute_recovery(
+            &guardians[0].pubkey(),
+            &holder.pubkey(),
+            &guardians[0].pubkey(),
+        ),
+        &[&guardians[0]],
+    );
+    assert!(!result.is_success(), "Executed a cancelled recovery");
+    let result = ctx.execute(
+        instructions::approve_recovery(&guardians[2].pubkey(), &holder.pubkey()),
+        &[&guardians[2]],
+    );
+    assert!(!result.is_success(), "Approved a cancelled recovery");
+    result.assert_error_code(StablecoinError::NoRecoveryInProgress.into());
+    let result = ctx.execute(instructions::cancel_recovery(&holder.pubkey()), &[&holder]);
+    assert!(!result.is_success(), "Cancelled twice");
+    result.assert_error_code(StablecoinError::NoRecoveryInProgress.into());
+    assert_eq!(ctx.balance(&holder), 1_000_000);
+
+    // Closing opts out and returns the rent, paid to a separate fee payer
+    let payer = ctx.funded_account();
+    let rent = ctx
+        .svm
+        .get_account(&pda::recovery_config(&holder.pubkey()).0)
+        .unwrap()
+        .lamports;
+    let before = ctx.svm.get_balance(&holder.pubkey()).unwrap();
+    ctx.expect_success(
+        instructions::close_recovery(&holder.pubkey()),
+        &[&payer, &holder],
+    );
+    ctx.svm
+        .assert_account_closed(&pda::recovery_config(&holder.pubkey()).0);
+    assert_eq!(
+        ctx.svm.get_balance(&holder.pubkey()).unwrap(),
+        before + rent
+    );
+    // and takes the delegate away with it
+    let token_account = ctx.token_state(&holder);
+    assert_eq!(token_account.delegate, COption::None);
+    assert_eq!(token_account.delegated_amount, 0);
+}
 
-    // 2. Configure minter
-    configure_minter(&mut ctx, &admin, &minter.pubkey(), 1_000_000_000);
+#[test]
+fn test_recovery_needs_delegate() {
+    let mut ctx = TestContext::new();
+    let (holder, guardians) = recoverable(&mut ctx, 1_000_000);
+    let new_owner = Keypair::new();
+    let token_account = ctx.token_account(&holder);
+    let initiate = instructions::initiate_recovery(
+        &guardians[0].pubkey(),
+        &holder.pubkey(),
+        &new_owner.pubkey(),
+    );
 
-    // 3. Mint to user1
-    mint_tokens(&mut ctx, &minter, &user1.pubkey(), 100_000_000);
+    // A delegate that cannot move the whole balance is no use to a recovery
+    for delegate in [
+        spl_token::instruction::revoke(&spl_token::ID, &token_account, &holder.pubkey(), &[])
+            .unwrap(),
+        spl_token::instruction::approve(
+            &spl_token::ID,
+            &token_account,
+            &pda::delegate().0,
+            &holder.pubkey(),
+            &[],
+            999_999,
+        )
+        .unwrap(),
+    ] {
+        ctx.expect_success(delegate, &[&holder]);
+        let result = ctx.execute(initiate.clone(), &[&guardians[0]]);
+        assert!(!result.is_success(), "Initiated without the delegate");
+        result.assert_error_code(StablecoinError::DelegateNotApproved.into());
+    }
+
+    // Configuring again approves it
+    ctx.configure_recovery(&holder, &guardians.each_ref(), 2);
+    ctx.expect_success(initiate, &[&guardians[0]]);
+    ctx.expect_success(
+        instructions::approve_recovery(&guardians[1].pubkey(), &holder.pubkey()),
+        &[&guardians[1]],
+    );
+    ctx.advance_time(MIN_RECOVERY_CHALLENGE_PERIOD as u64);
+
+    // Approving someone else takes the delegate away just the same
+    let other = Pubkey::new_unique();
+    ctx.expect_success(
+        spl_token::instruction::approve(
+            &spl_token::ID,
+            &token_account,
+            &other,
+            &holder.pubkey(),
+            &[],
+            u64::MAX,
+        )
+        .unwrap(),
+        &[&holder],
+    );
+    let result = ctx.execute(
+        instructions::execute_recovery(
+            &guardians[0].pubkey(),
+            &holder.pubkey(),
+            &new_owner.pubkey(),
+        ),
+        &[&guardians[0]],
+    );
+    assert!(!result.is_success(), "Recovered without the delegate");
+    result.assert_error_code(StablecoinError::DelegateNotApproved.into());
+    assert_eq!(ctx.balance(&holder), 1_000_000);
 
-    // 4. Mint to user2
-    mint_tokens(&mut ctx, &minter, &user2.pubkey(), 200_000_000);
+    // and closing the settings leaves a delegate the holder chose alone
+    ctx.expect_success(instructions::close_recovery(&holder.pubkey()), &[&holder]);
+    assert_eq!(ctx.token_state(&holder).delegate, COption::Some(other));
+}
 
-    // 5. User1 burns some tokens
-    let config_pda = get_config_pda();
-    let mint_pda = get_mint_pda();
-    let user1_ata = get_ata(&user1.pubkey(), &mint_pda);
+#[test]
+fn test_close_recovery_keeps_delegate_for_inheritance() {
+    let mut ctx = TestContext::new();
+    let (holder, _) = recoverable(&mut ctx, 1_000_000);
+    let beneficiary = ctx.funded_account();
+    ctx.configure_inheritance(&holder, &beneficiary, 2);
+
+    // The plan still needs the delegate the two share
+    ctx.expect_success(instructions::close_recovery(&holder.pubkey()), &[&holder]);
+    let token_account = ctx.token_state(&holder);
+    assert_eq!(token_account.delegate, COption::Some(pda::delegate().0));
+    assert_eq!(token_account.delegated_amount, u64::MAX);
+}
 
-    let burn_amount: u64 = 50_000_000;
+// ============================================================================
+// Inheritance Tests
+// ============================================================================
 
-    let burn_ix = ctx
-        .program()
-        .accounts(client::accounts::BurnTokens {
-            owner: user1.pubkey(),
-            config: config_pda,
-            mint: mint_pda,
-            token_account: user1_ata,
-            token_program: TOKEN_PROGRAM_ID,
-        })
-        .args(client::args::BurnTokens {
-            amount: burn_amount,
-        })
-        .instruction()
-        .unwrap();
+/// An initialized stablecoin whose holder has `amount` and has made a
+/// beneficiary able to claim it after two epochs without a check-in
+fn inheritable(ctx: &mut TestContext, amount: u64) -> (Keypair, Keypair) {
+    let minter = ctx.funded_account();
+    let holder = ctx.funded_account();
+    let beneficiary = ctx.funded_account();
+    ctx.initialize()
+        .configure_minter(&minter, amount)
+        .mint(&minter, &holder, amount)
+        .configure_inheritance(&holder, &beneficiary, 2);
+    (holder, beneficiary)
+}
+
+#[test]
+fn test_configure_inheritance() {
+    let mut ctx = TestContext::new();
+    ctx.advance_epochs(5);
+    let (holder, beneficiary) = inheritable(&mut ctx, 1_000_000);
+
+    assert_eq!(
+        ctx.inheritance_plan(&holder),
+        InheritancePlan {
+            owner: holder.pubkey(),
+            beneficiary: beneficiary.pubkey(),
+            inactivity_epochs: 2,
+            notice_period: MIN_INHERITANCE_NOTICE_PERIOD,
+            last_check_in_epoch: 5,
+            claim_started_at: 0,
+            bump: pda::inheritance_plan(&holder.pubkey()).1,
+        }
+    );
+    // The delegate can move the whole balance, whatever it grows to
+    let token_account = ctx.token_state(&holder);
+    assert_eq!(token_account.delegate, COption::Some(pda::delegate().0));
+    assert_eq!(token_account.delegated_amount, u64::MAX);
+
+    // Reconfiguring replaces the plan
+    let heir = Pubkey::new_unique();
+    let result = ctx.expect_success(
+        instructions::configure_inheritance(
+            &holder.pubkey(),
+            &heir,
+            3,
+            MIN_INHERITANCE_NOTICE_PERIOD,
+        ),
+        &[&holder],
+    );
+    let event: InheritanceConfigured = result.parse_event().unwrap();
+    assert_eq!(
+        event,
+        InheritanceConfigured {
+            owner: holder.pubkey(),
+            beneficiary: heir,
+            inactivity_epochs: 3,
+            notice_period: MIN_INHERITANCE_NOTICE_PERIOD,
+        }
+    );
+    assert_eq!(ctx.inheritance_plan(&holder).beneficiary, heir);
+
+    let period = MIN_INHERITANCE_NOTICE_PERIOD;
+    for (beneficiary, inactivity_epochs, notice_period) in [
+        (Pubkey::default(), 1, period),
+        (holder.pubkey(), 1, period),
+        (beneficiary.pubkey(), 0, period),
+        (beneficiary.pubkey(), 1, period - 1),
+    ] {
+        let result = ctx.execute(
+            instructions::configure_inheritance(
+                &holder.pubkey(),
+                &beneficiary,
+                inactivity_epochs,
+                notice_period,
+            ),
+            &[&holder],
+        );
+        assert!(
+            !result.is_success(),
+            "Configured {beneficiary}, {inactivity_epochs} epochs, notice {notice_period}"
+        );
+        result.assert_error_code(StablecoinError::InvalidInheritancePlan.into());
+    }
+}
+
+#[test]
+fn test_inheritance_needs_inactivity_and_notice_period() {
+    let mut ctx = TestContext::new();
+    let (holder, beneficiary) = inheritable(&mut ctx, 1_000_000);
+    let start = instructions::start_inheritance_claim(&beneficiary.pubkey(), &holder.pubkey());
+    let claim = instructions::claim_inheritance(&beneficiary.pubkey(), &holder.pubkey());
+
+    // Nothing to claim before a claim is started
+    let result = ctx.execute(claim.clone(), &[&beneficiary]);
+    assert!(!result.is_success(), "Claimed without starting a claim");
+    result.assert_error_code(StablecoinError::NoInheritanceClaim.into());
+
+    ctx.advance_epochs(1);
+    let result = ctx.execute(start.clone(), &[&beneficiary]);
+    assert!(!result.is_success(), "Started a claim on an active holder");
+    result.assert_error_code(StablecoinError::HolderStillActive.into());
+
+    ctx.advance_epochs(1);
+    let result = ctx.execute(start.clone(), &[&beneficiary]);
+    assert!(result.is_success(), "Start failed: {:?}", result.error());
+    let started_at = ctx.clock().unix_timestamp;
+    let event: InheritanceClaimStarted = result.parse_event().unwrap();
+    assert_eq!(
+        event,
+        InheritanceClaimStarted {
+            owner: holder.pubkey(),
+            beneficiary: beneficiary.pubkey(),
+            claimable_at: started_at + MIN_INHERITANCE_NOTICE_PERIOD,
+        }
+    );
+    assert_eq!(ctx.inheritance_plan(&holder).claim_started_at, started_at);
+    let result = ctx.execute(start, &[&beneficiary]);
+    assert!(!result.is_success(), "Started a second claim");
+    result.assert_error_code(StablecoinError::InheritanceClaimInProgress.into());
+
+    ctx.advance_time(MIN_INHERITANCE_NOTICE_PERIOD as u64 - 1);
+    let result = ctx.execute(claim.clone(), &[&beneficiary]);
+    assert!(!result.is_success(), "Claimed during the notice period");
+    result.assert_error_code(StablecoinError::InheritanceNoticeActive.into());
+    assert_eq!(ctx.balance(&holder), 1_000_000);
+
+    ctx.advance_time(1);
+    let result = ctx.execute(claim, &[&beneficiary]);
+    assert!(result.is_success(), "Claim failed: {:?}", result.error());
+    let event: InheritanceClaimed = result.parse_event().unwrap();
+    assert_eq!(
+        event,
+        InheritanceClaimed {
+            owner: holder.pubkey(),
+            beneficiary: beneficiary.pubkey(),
+            amount: 1_000_000,
+        }
+    );
+    assert_eq!(ctx.balance(&holder), 0);
+    assert_eq!(ctx.balance(&beneficiary), 1_000_000);
+    assert_eq!(ctx.mint_state().supply, 1_000_000);
+
+    // The plan stays for tokens that arrive later
+    assert_eq!(ctx.inheritance_plan(&holder).claim_started_at, 0);
+}
+
+#[test]
+fn test_holder_who_checks_in_is_not_claimable() {
+    let mut ctx = TestContext::new();
+    let (holder, beneficiary) = inheritable(&mut ctx, 1_000_000);
+    let start = instructions::start_inheritance_claim(&beneficiary.pubkey(), &holder.pubkey());
+    let spend = spl_token::instruction::burn(
+        &spl_token::ID,
+        &ctx.token_account(&holder),
+        &pda::mint().0,
+        &holder.pubkey(),
+        &[],
+        1_000,
+    )
+    .unwrap();
+
+    // A wallet that checks in with every transaction keeps the holder
+    // covered for as long as they keep using it
+    for _ in 0..5 {
+        ctx.advance_epochs(1);
+        let result = ctx.execute_all(
+            vec![spend.clone(), instructions::check_in(&holder.pubkey())],
+            &[&holder],
+        );
+        assert!(result.is_success(), "Spend failed: {:?}", result.error());
+        let result = ctx.execute(start.clone(), &[&beneficiary]);
+        assert!(!result.is_success(), "Started a claim on an active holder");
+        result.assert_error_code(StablecoinError::HolderStillActive.into());
+    }
+
+    // Spending alone is not a check-in, so the plan lapses
+    for _ in 0..2 {
+        ctx.advance_epochs(1)
+            .expect_success(spend.clone(), &[&holder]);
+    }
+    ctx.expect_success(start, &[&beneficiary]);
+
+    // but the holder still has the notice period to check in
+    ctx.expect_success(instructions::check_in(&holder.pubkey()), &[&holder]);
+    ctx.advance_time(MIN_INHERITANCE_NOTICE_PERIOD as u64);
+    let result = ctx.execute(
+        instructions::claim_inheritance(&beneficiary.pubkey(), &holder.pubkey()),
+        &[&beneficiary],
+    );
+    assert!(!result.is_success(), "Claimed from an active holder");
+    result.assert_error_code(StablecoinError::NoInheritanceClaim.into());
+    assert_eq!(ctx.balance(&holder), 993_000);
+    assert_eq!(ctx.balance(&beneficiary), 0);
+}
+
+#[test]
+fn test_inheritance_requires_beneficiary() {
+    let mut ctx = TestContext::new();
+    let (holder, beneficiary) = inheritable(&mut ctx, 1_000_000);
+    let attacker = ctx.funded_account();
+    ctx.advance_epochs(2);
+
+    let result = ctx.execute(
+        instructions::start_inheritance_claim(&attacker.pubkey(), &holder.pubkey()),
+        &[&attacker],
+    );
+    assert!(!result.is_success(), "Non-beneficiary started a claim");
+    result.assert_error_code(StablecoinError::NotBeneficiary.into());
+
+    ctx.expect_success(
+        instructions::start_inheritance_claim(&beneficiary.pubkey(), &holder.pubkey()),
+        &[&beneficiary],
+    );
+    ctx.advance_time(MIN_INHERITANCE_NOTICE_PERIOD as u64);
+    let result = ctx.execute(
+        instructions::claim_inheritance(&attacker.pubkey(), &holder.pubkey()),
+        &[&attacker],
+    );
+    assert!(!result.is_success(), "Non-beneficiary claimed");
+    result.assert_error_code(StablecoinError::NotBeneficiary.into());
+    assert_eq!(ctx.balance(&holder), 1_000_000);
+}
 
-    ctx.execute_instruction(burn_ix, &[&user1])
+#[test]
+fn test_check_in_stops_claim() {
+    let mut ctx = TestContext::new();
+    let (holder, beneficiary) = inheritable(&mut ctx, 1_000_000);
+    ctx.advance_epochs(2);
+    ctx.expect_success(
+        instructions::start_inheritance_claim(&beneficiary.pubkey(), &holder.pubkey()),
+        &[&beneficiary],
+    );
+
+    let result = ctx.execute(instructions::check_in(&holder.pubkey()), &[&holder]);
+    assert!(result.is_success(), "Check-in failed: {:?}", result.error());
+    let event: InheritanceClaimStopped = result.parse_event().unwrap();
+    assert_eq!(
+        event,
+        InheritanceClaimStopped {
+            owner: holder.pubkey(),
+        }
+    );
+    let inheritance_plan = ctx.inheritance_plan(&holder);
+    assert_eq!(inheritance_plan.claim_started_at, 0);
+    assert_eq!(inheritance_plan.last_check_in_epoch, ctx.clock().epoch);
+
+    // The claim is gone, and the holder is active again
+    ctx.advance_time(MIN_INHERITANCE_NOTICE_PERIOD as u64);
+    let result = ctx.execute(
+        instructions::claim_inheritance(&beneficiary.pubkey(), &holder.pubkey()),
+        &[&beneficiary],
+    );
+    assert!(!result.is_success(), "Claimed after a check-in");
+    result.assert_error_code(StablecoinError::NoInheritanceClaim.into());
+    let result = ctx.execute(
+        instructions::start_inheritance_claim(&beneficiary.pubkey(), &holder.pubkey()),
+        &[&beneficiary],
+    );
+    assert!(!result.is_success(), "Started a claim after a check-in");
+    result.assert_error_code(StablecoinError::HolderStillActive.into());
+    assert_eq!(ctx.balance(&holder), 1_000_000);
+
+    // Closing opts out and returns the rent
+    let rent = ctx
+        .svm
+        .get_account(&pda::inheritance_plan(&holder.pubkey()).0)
         .unwrap()
-        .assert_success();
+        .lamports;
+    let payer = ctx.funded_account();
+    let before = ctx.svm.get_balance(&holder.pubkey()).unwrap();
+    ctx.expect_success(
+        instructions::close_inheritance(&holder.pubkey()),
+        &[&payer, &holder],
+    );
+    ctx.svm
+        .assert_account_closed(&pda::inheritance_plan(&holder.pubkey()).0);
+    assert_eq!(
+        ctx.svm.get_balance(&holder.pubkey()).unwrap(),
+        before + rent
+    );
+    // and takes the delegate away with it
+    let token_account = ctx.token_state(&holder);
+    assert_eq!(token_account.delegate, COption::None);
+    assert_eq!(token_account.delegated_amount, 0);
+}
 
-    // Verify user1 balance after burn
-    ctx.svm.assert_token_balance(&user1_ata, 50_000_000);
+#[test]
+fn test_inheritance_needs_delegate() {
+    let mut ctx = TestContext::new();
+    let (holder, beneficiary) = inheritable(&mut ctx, 1_000_000);
+    let token_account = ctx.token_account(&holder);
+    let start = instructions::start_inheritance_claim(&beneficiary.pubkey(), &holder.pubkey());
+
+    // No claim starts on a balance the program can no longer move
+    ctx.expect_success(
+        spl_token::instruction::revoke(&spl_token::ID, &token_account, &holder.pubkey(), &[])
+            .unwrap(),
+        &[&holder],
+    );
+    ctx.advance_epochs(2);
+    let result = ctx.execute(start.clone(), &[&beneficiary]);
+    assert!(!result.is_success(), "Started a claim without the delegate");
+    result.assert_error_code(StablecoinError::DelegateNotApproved.into());
+
+    // Configuring again approves it, and counts as a check-in
+    ctx.configure_inheritance(&holder, &beneficiary, 2)
+        .advance_epochs(2);
+    ctx.expect_success(start, &[&beneficiary]);
+    ctx.advance_time(MIN_INHERITANCE_NOTICE_PERIOD as u64);
+
+    // Approving someone else during the notice period stops the claim too
+    ctx.expect_success(
+        spl_token::instruction::approve(
+            &spl_token::ID,
+            &token_account,
+            &Pubkey::new_unique(),
+            &holder.pubkey(),
+            &[],
+            u64::MAX,
+        )
+        .unwrap(),
+        &[&holder],
+    );
+    let result = ctx.execute(
+        instructions::claim_inheritance(&beneficiary.pubkey(), &holder.pubkey()),
+        &[&beneficiary],
+    );
+    assert!(!result.is_success(), "Claimed without the delegate");
+    result.assert_error_code(StablecoinError::DelegateNotApproved.into());
+    assert_eq!(ctx.balance(&holder), 1_000_000);
+}
 
-    // 6. Pause and unpause
-    pause_program(&mut ctx, &admin);
+#[test]
+fn test_recovery_and_inheritance_share_the_delegate() {
+    let mut ctx = TestContext::new();
+    let (holder, _) = inheritable(&mut ctx, 1_000_000);
+    let guardian = ctx.funded_account();
+    ctx.configure_recovery(&holder, &[&guardian], 1);
+
+    // Closing the plan leaves the delegate to recovery
+    ctx.expect_success(
+        instructions::close_inheritance(&holder.pubkey()),
+        &[&holder],
+    );
+    let token_account = ctx.token_state(&holder);
+    assert_eq!(token_account.delegate, COption::Some(pda::delegate().0));
+    assert_eq!(token_account.delegated_amount, u64::MAX);
 
-    let unpause_ix = ctx
-        .program()
-        .accounts(client::accounts::Unpause {
-            admin: admin.pubkey(),
-            config: config_pda,
-        })
-        .args(client::args::Unpause {})
-        .instruction()
-        .unwrap();
+    // and the last to close revokes it
+    ctx.expect_success(instructions::close_recovery(&holder.pubkey()), &[&holder]);
+    assert_eq!(ctx.token_state(&holder).delegate, COption::None);
+}
+
+// ============================================================================
+// Netting Tests
+// ============================================================================
+
+/// An initialized stablecoin with three institutions holding `amount`
+/// each, in netting window 1, which closes after `duration` seconds
+fn netting(ctx: &mut TestContext, amount: u64, duration: i64) -> [Keypair; 3] {
+    let minter = ctx.funded_account();
+    let institutions = [
+        ctx.funded_account(),
+        ctx.funded_account(),
+        ctx.funded_account(),
+    ];
+    ctx.initialize().configure_minter(&minter, amount * 3);
+    for institution in &institutions {
+        ctx.mint(&minter, institution, amount);
+    }
+    let [a, b, c] = &institutions;
+    ctx.open_netting_window(1, &[a, b, c], duration);
+    institutions
+}
+
+#[test]
+fn test_open_netting_window() {
+    let mut ctx = TestContext::new();
+    let [a, b, c] = netting(&mut ctx, 1_000, 60);
+    let closes_at = ctx.clock().unix_timestamp + 60;
+    assert_eq!(
+        ctx.netting_window(1),
+        NettingWindow {
+            id: 1,
+            opened_by: ctx.admin.pubkey(),
+            institutions: vec![a.pubkey(), b.pubkey(), c.pubkey()],
+            debits: vec![0; 3],
+            credits: vec![0; 3],
+            escrowed: vec![0; 3],
+            closes_at,
+            bump: pda::netting_window(1).1,
+        }
+    );
+    assert_eq!(
+        ctx.account::<TokenAccount>(&pda::netting_custody(1)).amount,
+        0
+    );
+
+    let admin = ctx.admin.insecure_clone();
+    for (institutions, duration) in [
+        (vec![a.pubkey()], 60),
+        (vec![a.pubkey(), a.pubkey()], 60),
+        (vec![a.pubkey(), Pubkey::default()], 60),
+        (vec![a.pubkey(), b.pubkey()], 0),
+        ((0..9).map(|_| Pubkey::new_unique()).collect(), 60),
+    ] {
+        let result = ctx.execute(
+            instructions::open_netting_window(&admin.pubkey(), 2, institutions.clone(), duration),
+            &[&admin],
+        );
+        assert!(
+            !result.is_success(),
+            "Opened {institutions:?} for {duration} seconds"
+        );
+        result.assert_error_code(StablecoinError::InvalidNettingWindow.into());
+    }
+
+    // Only the admin opens windows
+    let result = ctx.execute(
+        instructions::open_netting_window(&a.pubkey(), 2, vec![a.pubkey(), b.pubkey()], 60),
+        &[&a],
+    );
+    assert!(!result.is_success(), "Non-admin opened a window");
+    result.assert_error_code(StablecoinError::Unauthorized.into());
+}
+
+#[test]
+fn test_settle_net_moves_only_net_positions() {
+    let mut ctx = TestContext::new();
+    let [a, b, c] = netting(&mut ctx, 1_000, 60);
+
+    let result = ctx.execute(
+        instructions::queue_obligation(&a.pubkey(), 1, &b.pubkey(), 300, None),
+        &[&a],
+    );
+    assert!(result.is_success(), "Queue failed: {:?}", result.error());
+    let event: ObligationQueued = result.parse_event().unwrap();
+    assert_eq!(
+        event,
+        ObligationQueued {
+            id: 1,
+            debtor: a.pubkey(),
+            creditor: b.pubkey(),
+            amount: 300,
+            purpose: None,
+        }
+    );
+    ctx.queue_obligation(&b, 1, &c, 200)
+        .queue_obligation(&c, 1, &a, 100);
+    let netting_window = ctx.netting_window(1);
+    assert_eq!(netting_window.debits, vec![300, 200, 100]);
+    assert_eq!(netting_window.credits, vec![100, 300, 200]);
+    // Each debtor escrows what its net debit grew by; a owed before it was
+    // owed anything, while b and c were owed more than they owe
+    assert_eq!(netting_window.escrowed, vec![300, 0, 0]);
+    assert_eq!(ctx.balance(&a), 700);
+    assert_eq!(
+        ctx.account::<TokenAccount>(&pda::netting_custody(1)).amount,
+        300
+    );
+
+    let settle = instructions::settle_net(
+        &a.pubkey(),
+        &ctx.admin.pubkey(),
+        1,
+        &[a.pubkey(), b.pubkey(), c.pubkey()],
+    );
+    let result = ctx.execute(settle.clone(), &[&a]);
+    assert!(!result.is_success(), "Settled an open window");
+    result.assert_error_code(StablecoinError::NettingWindowOpen.into());
+
+    ctx.advance_time(60);
+    let result = ctx.execute(
+        instructions::queue_obligation(&a.pubkey(), 1, &b.pubkey(), 1, None),
+        &[&a],
+    );
+    assert!(!result.is_success(), "Queued in a closed window");
+    result.assert_error_code(StablecoinError::NettingWindowClosed.into());
+
+    // Anyone settles; the admin gets the rent of the window and its
+    // custody back
+    let rent = [pda::netting_window(1).0, pda::netting_custody(1)]
+        .iter()
+        .map(|address| ctx.svm.get_account(address).unwrap().lamports)
+        .sum::<u64>();
+    let before = ctx.svm.get_balance(&ctx.admin.pubkey()).unwrap();
+    let anyone = ctx.funded_account();
+    let settle = substitute(settle, &a.pubkey(), &anyone.pubkey());
+    let result = ctx.execute(settle, &[&anyone]);
+    assert!(result.is_success(), "Settle failed: {:?}", result.error());
+    let event: NetSettled = result.parse_event().unwrap();
+    assert_eq!(
+        event,
+        NetSettled {
+            id: 1,
+            gross: 600,
+            net: 200,
+        }
+    );
+    assert_eq!(ctx.balance(&a), 800);
+    assert_eq!(ctx.balance(&b), 1_100);
+    assert_eq!(ctx.balance(&c), 1_100);
+    ctx.svm.assert_account_closed(&pda::netting_window(1).0);
+    ctx.svm.assert_account_closed(&pda::netting_custody(1));
+    assert_eq!(
+        ctx.svm.get_balance(&ctx.admin.pubkey()).unwrap(),
+        before + rent
+    );
+}
+
+#[test]
+fn test_queue_obligation_requires_institutions() {
+    let mut ctx = TestContext::new();
+    let [a, b, _] = netting(&mut ctx, 1_000, 60);
+    let outsider = ctx.funded_account();
+
+    let result = ctx.execute(
+        instructions::queue_obligation(&outsider.pubkey(), 1, &a.pubkey(), 100, None),
+        &[&outsider],
+    );
+    assert!(!result.is_success(), "Non-institution queued an obligation");
+    result.assert_error_code(StablecoinError::NotInstitution.into());
+
+    for (creditor, amount) in [(outsider.pubkey(), 100), (a.pubkey(), 100), (b.pubkey(), 0)] {
+        let result = ctx.execute(
+            instructions::queue_obligation(&a.pubkey(), 1, &creditor, amount, None),
+            &[&a],
+        );
+        assert!(!result.is_success(), "Queued {amount} to {creditor}");
+        result.assert_error_code(StablecoinError::InvalidObligation.into());
+    }
+}
+
+#[test]
+fn test_settle_net_checks_token_accounts_and_cancel() {
+    let mut ctx = TestContext::new();
+    let [a, b, c] = netting(&mut ctx, 1_000, 60);
+    ctx.queue_obligation(&a, 1, &b, 300).advance_time(60);
+
+    // The token accounts must be the institutions', in the window's order
+    let admin = ctx.admin.pubkey();
+    for institutions in [
+        vec![a.pubkey(), b.pubkey()],
+        vec![b.pubkey(), a.pubkey(), c.pubkey()],
+    ] {
+        let result = ctx.execute(
+            instructions::settle_net(&a.pubkey(), &admin, 1, &institutions),
+            &[&a],
+        );
+        assert!(!result.is_success(), "Settled with {institutions:?}");
+        result.assert_error_code(StablecoinError::InvalidSettlementAccounts.into());
+    }
+    assert_eq!(ctx.balance(&a), 700);
+
+    // The admin drops the window, refunding what was escrowed
+    let institutions = [a.pubkey(), b.pubkey(), c.pubkey()];
+    let result = ctx.execute(
+        instructions::cancel_netting_window(&a.pubkey(), &admin, 1, &institutions),
+        &[&a],
+    );
+    assert!(!result.is_success(), "Non-admin cancelled a window");
+    result.assert_error_code(StablecoinError::Unauthorized.into());
+    let admin = ctx.admin.insecure_clone();
+    let result = ctx.execute(
+        instructions::cancel_netting_window(
+            &admin.pubkey(),
+            &admin.pubkey(),
+            1,
+            &institutions[..2],
+        ),
+        &[&admin],
+    );
+    assert!(
+        !result.is_success(),
+        "Cancelled without every token account"
+    );
+    result.assert_error_code(StablecoinError::InvalidSettlementAccounts.into());
+    ctx.expect_success(
+        instructions::cancel_netting_window(&admin.pubkey(), &admin.pubkey(), 1, &institutions),
+        &[&admin],
+    );
+    ctx.svm.assert_account_closed(&pda::netting_window(1).0);
+    ctx.svm.assert_account_closed(&pda::netting_custody(1));
+    assert_eq!(ctx.balance(&a), 1_000);
+    assert_eq!(ctx.balance(&b), 1_000);
+}
+
+#[test]
+fn test_underfunded_debtor_cannot_queue() {
+    let mut ctx = TestContext::new();
+    let [a, b, c] = netting(&mut ctx, 1_000, 60);
+
+    // What a is owed offsets what it owes, so it escrows only the
+    // difference
+    ctx.queue_obligation(&b, 1, &a, 800)
+        .queue_obligation(&a, 1, &b, 1_500);
+    assert_eq!(ctx.netting_window(1).escrowed, vec![700, 800, 0]);
+    assert_eq!(ctx.balance(&a), 300);
+
+    // An obligation a cannot escrow is never queued
+    let result = ctx.execute(
+        instructions::queue_obligation(&a.pubkey(), 1, &c.pubkey(), 400, None),
+        &[&a],
+    );
+    assert!(!result.is_success(), "Queued an obligation without escrow");
+    result.assert_error_code(StablecoinError::ObligationUnderfunded.into());
+    let netting_window = ctx.netting_window(1);
+    assert_eq!(netting_window.debits, vec![1_500, 800, 0]);
+    assert_eq!(netting_window.escrowed, vec![700, 800, 0]);
+
+    // Spending what is left does not stop settlement
+    ctx.burn(&a, 300).advance_time(60);
+    let admin = ctx.admin.pubkey();
+    ctx.expect_success(
+        instructions::settle_net(
+            &c.pubkey(),
+            &admin,
+            1,
+            &[a.pubkey(), b.pubkey(), c.pubkey()],
+        ),
+        &[&c],
+    );
+    assert_eq!(ctx.balance(&a), 0);
+    assert_eq!(ctx.balance(&b), 1_700);
+    assert_eq!(ctx.balance(&c), 1_000);
+    ctx.svm.assert_account_closed(&pda::netting_custody(1));
+}
+
+#[test]
+fn test_obligation_carries_registered_purpose_code() {
+    let mut ctx = TestContext::new();
+    let [a, b, _] = netting(&mut ctx, 1_000, 60);
+    let admin = ctx.admin.insecure_clone();
+    ctx.register_purpose_code(*b"PAYR");
+    assert_eq!(
+        ctx.account::<PurposeCode>(&pda::purpose_code(b"PAYR").0),
+        PurposeCode {
+            code: *b"PAYR",
+            registered_by: admin.pubkey(),
+            bump: pda::purpose_code(b"PAYR").1,
+        }
+    );
+
+    let result = ctx.execute(
+        instructions::queue_obligation(&a.pubkey(), 1, &b.pubkey(), 300, Some(b"PAYR")),
+        &[&a],
+    );
+    assert!(result.is_success(), "Queue failed: {:?}", result.error());
+    let event: ObligationQueued = result.parse_event().unwrap();
+    assert_eq!(event.purpose, Some(*b"PAYR"));
+
+    // Only registered codes can be stated
+    let result = ctx.execute(
+        instructions::queue_obligation(&a.pubkey(), 1, &b.pubkey(), 300, Some(b"RMIT")),
+        &[&a],
+    );
+    assert!(!result.is_success(), "Queued with an unregistered code");
+    result.assert_error_code(ErrorCode::AccountNotInitialized.into());
 
-    ctx.execute_instruction(unpause_ix, &[&admin])
+    // Only the admin registers codes, and only readable ones
+    let result = ctx.execute(
+        instructions::register_purpose_code(&a.pubkey(), *b"RMIT"),
+        &[&a],
+    );
+    assert!(!result.is_success(), "Non-admin registered a code");
+    result.assert_error_code(StablecoinError::Unauthorized.into());
+    for code in [*b"rmit", *b"RM T", [0; 4]] {
+        let result = ctx.execute(
+            instructions::register_purpose_code(&admin.pubkey(), code),
+            &[&admin],
+        );
+        assert!(!result.is_success(), "Registered {code:?}");
+        result.assert_error_code(StablecoinError::InvalidPurposeCode.into());
+    }
+
+    // Retiring a code returns its rent and stops new obligations using it
+    let rent = ctx
+        .svm
+        .get_account(&pda::purpose_code(b"PAYR").0)
         .unwrap()
+        .lamports;
+    let before = ctx.svm.get_balance(&admin.pubkey()).unwrap();
+    let result = ctx.execute(
+        instructions::retire_purpose_code(&a.pubkey(), &admin.pubkey(), b"PAYR"),
+        &[&a],
+    );
+    assert!(!result.is_success(), "Non-admin retired a code");
+    result.assert_error_code(StablecoinError::Unauthorized.into());
+    let payer = ctx.funded_account();
+    let retire = instructions::retire_purpose_code(&admin.pubkey(), &admin.pubkey(), b"PAYR");
+    ctx.execute_all(vec![retire], &[&payer, &admin])
         .assert_success();
+    ctx.svm.assert_account_closed(&pda::purpose_code(b"PAYR").0);
+    assert_eq!(ctx.svm.get_balance(&admin.pubkey()).unwrap(), before + rent);
+    let result = ctx.execute(
+        instructions::queue_obligation(&a.pubkey(), 1, &b.pubkey(), 300, Some(b"PAYR")),
+        &[&a],
+    );
+    assert!(!result.is_success(), "Queued with a retired code");
+    result.assert_error_code(ErrorCode::AccountNotInitialized.into());
+}
 
-    // 7. Remove minter
-    let minter_config_pda = get_minter_config_pda(&minter.pubkey());
+// ============================================================================
+// Payment Channel Tests
+// ============================================================================
 
-    let remove_minter_ix = ctx
-        .program()
-        .accounts(client::accounts::RemoveMinter {
+/// An initialized stablecoin whose sender holds `amount` and has opened a
+/// channel depositing `deposit` of it to a recipient
+fn channel(ctx: &mut TestContext, amount: u64, deposit: u64) -> (Keypair, Keypair) {
+    let minter = ctx.funded_account();
+    let sender = ctx.funded_account();
+    let recipient = ctx.funded_account();
+    ctx.initialize()
+        .configure_minter(&minter, amount)
+        .mint(&minter, &sender, amount)
+        .open_channel(&sender, &recipient, deposit);
+    (sender, recipient)
+}
+
+#[test]
+fn test_open_channel() {
+    let mut ctx = TestContext::new();
+    let (sender, recipient) = channel(&mut ctx, 1_000, 600);
+
+    assert_eq!(
+        ctx.payment_channel(&sender, &recipient),
+        PaymentChannel {
+            sender: sender.pubkey(),
+            recipient: recipient.pubkey(),
+            deposit: 600,
+            dispute_period: MIN_CHANNEL_DISPUTE_PERIOD,
+            opened_at: ctx.clock().unix_timestamp,
+            nonce: 0,
+            amount: 0,
+            closes_at: 0,
+            bump: pda::payment_channel(&sender.pubkey(), &recipient.pubkey()).1,
+        }
+    );
+    assert_eq!(ctx.balance(&sender), 400);
+    let custody = pda::channel_custody(&sender.pubkey(), &recipient.pubkey());
+    assert_eq!(ctx.account::<TokenAccount>(&custody).amount, 600);
+
+    let other = ctx.funded_account();
+    let period = MIN_CHANNEL_DISPUTE_PERIOD;
+    for (recipient, deposit, dispute_period) in [
+        (Pubkey::default(), 100, period),
+        (sender.pubkey(), 100, period),
+        (other.pubkey(), 0, period),
+        (other.pubkey(), 100, period - 1),
+    ] {
+        let result = ctx.execute(
+            instructions::open_channel(&sender.pubkey(), &recipient, deposit, dispute_period),
+            &[&sender],
+        );
+        assert!(
+            !result.is_success(),
+            "Opened to {recipient} with {deposit}, dispute period {dispute_period}"
+        );
+        result.assert_error_code(StablecoinError::InvalidPaymentChannel.into());
+    }
+}
+
+#[test]
+fn test_open_channel_with_existing_custody() {
+    let mut ctx = TestContext::new();
+    let minter = ctx.funded_account();
+    let sender = ctx.funded_account();
+    let recipient = ctx.funded_account();
+    ctx.initialize()
+        .configure_minter(&minter, 1_000)
+        .mint(&minter, &sender, 600);
+
+    // Sending to the channel's address before it opens creates its custody
+    let (channel, _) = pda::payment_channel(&sender.pubkey(), &recipient.pubkey());
+    ctx.expect_success(
+        instructions::mint_tokens(&minter.pubkey(), &channel, 50),
+        &[&minter],
+    );
+    let custody = pda::channel_custody(&sender.pubkey(), &recipient.pubkey());
+    assert_eq!(ctx.account::<TokenAccount>(&custody).amount, 50);
+
+    // The channel opens over it, and settlement refunds what was sent along
+    // with the rest of the deposit
+    ctx.open_channel(&sender, &recipient, 600);
+    assert_eq!(ctx.account::<TokenAccount>(&custody).amount, 650);
+    let close = ctx.close_channel_instructions(&recipient, &sender, &recipient, 200);
+    ctx.execute_all(close, &[&recipient]).assert_success();
+    ctx.advance_time(MIN_CHANNEL_DISPUTE_PERIOD as u64);
+    let anyone = ctx.funded_account();
+    ctx.expect_success(
+        instructions::settle_channel(&anyone.pubkey(), &sender.pubkey(), &recipient.pubkey()),
+        &[&anyone],
+    );
+    assert_eq!(ctx.balance(&recipient), 200);
+    assert_eq!(ctx.balance(&sender), 450);
+}
+
+#[test]
+fn test_channel_pays_highest_signed_state() {
+    let mut ctx = TestContext::new();
+    let (sender, recipient) = channel(&mut ctx, 1_000, 1_000);
+
+    // The recipient closes with a balance update the sender signed
+    let close = ctx.close_channel_instructions(&recipient, &sender, &recipient, 300);
+    let result = ctx.execute_all(close, &[&recipient]);
+    assert!(result.is_success(), "Close failed: {:?}", result.error());
+    let closes_at = ctx.clock().unix_timestamp + MIN_CHANNEL_DISPUTE_PERIOD;
+    let event: ChannelClosing = result.parse_event().unwrap();
+    assert_eq!(
+        event,
+        ChannelClosing {
+            sender: sender.pubkey(),
+            recipient: recipient.pubkey(),
+            amount: 300,
+            closes_at,
+        }
+    );
+
+    // The sender cannot answer with an older one, but the recipient can
+    // still raise it
+    let older = ctx.close_channel_instructions(&sender, &sender, &recipient, 200);
+    let result = ctx.execute_all(older, &[&sender]);
+    assert!(!result.is_success(), "Submitted an older balance update");
+    result.assert_error_code(StablecoinError::InvalidChannelState.into());
+    let later = ctx.close_channel_instructions(&recipient, &sender, &recipient, 500);
+    ctx.execute_all(later, &[&recipient]).assert_success();
+    let payment_channel = ctx.payment_channel(&sender, &recipient);
+    assert_eq!(payment_channel.amount, 500);
+    assert_eq!(payment_channel.closes_at, closes_at);
+
+    let anyone = ctx.funded_account();
+    let settle =
+        instructions::settle_channel(&anyone.pubkey(), &sender.pubkey(), &recipient.pubkey());
+    let result = ctx.execute(settle.clone(), &[&anyone]);
+    assert!(!result.is_success(), "Settled during the dispute period");
+    result.assert_error_code(StablecoinError::ChannelDisputeActive.into());
+
+    ctx.advance_time(MIN_CHANNEL_DISPUTE_PERIOD as u64);
+    let late = ctx.close_channel_instructions(&recipient, &sender, &recipient, 600);
+    let result = ctx.execute_all(late, &[&recipient]);
+    assert!(!result.is_success(), "Submitted after the dispute period");
+    result.assert_error_code(StablecoinError::ChannelDisputeOver.into());
+
+    // Anyone settles, creating the recipient's token account; the sender
+    // gets the rest and the rent back
+    let rent = ctx
+        .svm
+        .get_account(&pda::payment_channel(&sender.pubkey(), &recipient.pubkey()).0)
+        .unwrap()
+        .lamports;
+    let before = ctx.svm.get_balance(&sender.pubkey()).unwrap();
+    let result = ctx.execute(settle, &[&anyone]);
+    assert!(result.is_success(), "Settle failed: {:?}", result.error());
+    let event: ChannelSettled = result.parse_event().unwrap();
+    assert_eq!(
+        event,
+        ChannelSettled {
+            sender: sender.pubkey(),
+            recipient: recipient.pubkey(),
+            amount: 500,
+            refund: 500,
+        }
+    );
+    assert_eq!(ctx.balance(&recipient), 500);
+    assert_eq!(ctx.balance(&sender), 500);
+    ctx.svm
+        .assert_account_closed(&pda::payment_channel(&sender.pubkey(), &recipient.pubkey()).0);
+    ctx.svm
+        .assert_account_closed(&pda::channel_custody(&sender.pubkey(), &recipient.pubkey()));
+    assert!(ctx.svm.get_balance(&sender.pubkey()).unwrap() > before + rent);
+}
+
+#[test]
+fn test_close_channel_requires_sender_signature() {
+    let mut ctx = TestContext::new();
+    let (sender, recipient) = channel(&mut ctx, 1_000, 1_000);
+
+    // Raising the amount needs the Ed25519 check right before
+    let signature = ctx.sign_channel_state(&sender, &recipient, 300);
+    let close = instructions::close_channel(
+        &recipient.pubkey(),
+        &sender.pubkey(),
+        &recipient.pubkey(),
+        300,
+        signature,
+    );
+    let result = ctx.execute(close, &[&recipient]);
+    assert!(!result.is_success(), "Closed without the signature check");
+    result.assert_error_code(StablecoinError::InvalidChannelSignature.into());
+
+    // A checked signature over another amount does not count
+    let mut close = ctx.close_channel_instructions(&recipient, &sender, &recipient, 100);
+    close[1] = instructions::close_channel(
+        &recipient.pubkey(),
+        &sender.pubkey(),
+        &recipient.pubkey(),
+        300,
+        signature,
+    );
+    let result = ctx.execute_all(close, &[&recipient]);
+    assert!(
+        !result.is_success(),
+        "Closed with a signature over another amount"
+    );
+    result.assert_error_code(StablecoinError::InvalidChannelSignature.into());
+
+    let close = ctx.close_channel_instructions(&recipient, &sender, &recipient, 1_001);
+    let result = ctx.execute_all(close, &[&recipient]);
+    assert!(!result.is_success(), "Closed above the deposit");
+    result.assert_error_code(StablecoinError::InvalidChannelState.into());
+
+    let outsider = ctx.funded_account();
+    let close = ctx.close_channel_instructions(&outsider, &sender, &recipient, 300);
+    let result = ctx.execute_all(close, &[&outsider]);
+    assert!(!result.is_success(), "Outsider closed the channel");
+    result.assert_error_code(StablecoinError::NotChannelParty.into());
+
+    // The sender closes with nothing paid, then a reopened channel does not
+    // take the old channel's balance updates
+    ctx.expect_success(
+        instructions::close_channel(
+            &sender.pubkey(),
+            &sender.pubkey(),
+            &recipient.pubkey(),
+            0,
+            [0; 64],
+        ),
+        &[&sender],
+    );
+    ctx.advance_time(MIN_CHANNEL_DISPUTE_PERIOD as u64);
+    let old = ctx.close_channel_instructions(&recipient, &sender, &recipient, 300);
+    let anyone = ctx.funded_account();
+    ctx.expect_success(
+        instructions::settle_channel(&anyone.pubkey(), &sender.pubkey(), &recipient.pubkey()),
+        &[&anyone],
+    );
+    assert_eq!(ctx.balance(&sender), 1_000);
+    ctx.open_channel(&sender, &recipient, 1_000);
+    assert_eq!(ctx.payment_channel(&sender, &recipient).nonce, 1);
+    let counter: ChannelCounter =
+        ctx.account(&pda::channel_counter(&sender.pubkey(), &recipient.pubkey()).0);
+    assert_eq!(
+        counter,
+        ChannelCounter {
+            opened: 2,
+            bump: pda::channel_counter(&sender.pubkey(), &recipient.pubkey()).1,
+        }
+    );
+    let result = ctx.execute_all(old, &[&recipient]);
+    assert!(!result.is_success(), "Replayed a balance update");
+    result.assert_error_code(StablecoinError::InvalidChannelSignature.into());
+}
+
+// ============================================================================
+// Sub-Account Tests
+// ============================================================================
+
+#[test]
+fn test_register_sub_account() {
+    let mut ctx = TestContext::new();
+    let owner = ctx.funded_account();
+    let other = ctx.funded_account();
+    ctx.initialize().register_purpose_code(*b"DEPO");
+
+    let result = ctx.expect_success(
+        instructions::register_sub_account(&owner.pubkey(), 42, b"DEPO"),
+        &[&owner],
+    );
+    let (address, bump) = pda::sub_account(&owner.pubkey(), 42);
+    assert_eq!(
+        ctx.sub_account(&owner, 42),
+        SubAccount {
+            owner: owner.pubkey(),
+            index: 42,
+            purpose: *b"DEPO",
+            bump,
+        }
+    );
+    let custody = pda::sub_account_custody(&owner.pubkey(), 42);
+    let event: SubAccountRegistered = result.parse_event().unwrap();
+    assert_eq!(
+        event,
+        SubAccountRegistered {
+            owner: owner.pubkey(),
+            index: 42,
+            purpose: *b"DEPO",
+            custody,
+        }
+    );
+    let custody_state = ctx.account::<TokenAccount>(&custody);
+    assert_eq!(custody_state.owner, address);
+    assert_eq!(custody_state.amount, 0);
+
+    // Each index is registered once, but other owners number their own
+    let result = ctx.execute(
+        instructions::register_sub_account(&owner.pubkey(), 42, b"DEPO"),
+        &[&owner],
+    );
+    assert!(!result.is_success(), "Registered an index twice");
+    ctx.register_sub_account(&other, 42, *b"DEPO");
+    assert_ne!(pda::sub_account(&other.pubkey(), 42).0, address);
+
+    // Only registered purpose codes can be filed under
+    let result = ctx.execute(
+        instructions::register_sub_account(&owner.pubkey(), 43, b"RMIT"),
+        &[&owner],
+    );
+    assert!(!result.is_success(), "Registered with an unregistered code");
+    result.assert_error_code(ErrorCode::AccountNotInitialized.into());
+}
+
+#[test]
+fn test_register_sub_account_with_existing_custody() {
+    let mut ctx = TestContext::new();
+    let minter = ctx.funded_account();
+    let owner = ctx.funded_account();
+    ctx.initialize()
+        .configure_minter(&minter, 1_000)
+        .mint(&minter, &owner, 100)
+        .register_purpose_code(*b"DEPO");
+
+    // Sending to a sub-account address before it is registered creates its
+    // custody
+    let (address, _) = pda::sub_account(&owner.pubkey(), 7);
+    ctx.expect_success(
+        instructions::mint_tokens(&minter.pubkey(), &address, 250),
+        &[&minter],
+    );
+    let custody = pda::sub_account_custody(&owner.pubkey(), 7);
+    assert_eq!(ctx.account::<TokenAccount>(&custody).amount, 250);
+
+    // Registration takes the custody as it is, and what was sent is sweepable
+    ctx.register_sub_account(&owner, 7, *b"DEPO")
+        .sweep_sub_account(&owner, 7);
+    assert_eq!(ctx.account::<TokenAccount>(&custody).amount, 0);
+    assert_eq!(ctx.token_state(&owner).amount, 350);
+}
+
+#[test]
+fn test_sweep_sub_account() {
+    let mut ctx = TestContext::new();
+    let minter = ctx.funded_account();
+    let owner = ctx.funded_account();
+    let customer = ctx.funded_account();
+    ctx.initialize()
+        .configure_minter(&minter, 1_000)
+        .mint(&minter, &owner, 100)
+        .mint(&minter, &customer, 500)
+        .register_purpose_code(*b"DEPO")
+        .register_sub_account(&owner, 1, *b"DEPO");
+    let custody = pda::sub_account_custody(&owner.pubkey(), 1);
+
+    // Deposits arrive by plain transfer or by minting to the sub-account
+    let deposit = spl_token::instruction::transfer(
+        &spl_token::ID,
+        &ctx.token_account(&customer),
+        &custody,
+        &customer.pubkey(),
+        &[],
+        300,
+    )
+    .unwrap();
+    ctx.expect_success(deposit, &[&customer]);
+    ctx.expect_success(
+        instructions::mint_tokens(
+            &minter.pubkey(),
+            &pda::sub_account(&owner.pubkey(), 1).0,
+            200,
+        ),
+        &[&minter],
+    );
+    assert_eq!(ctx.account::<TokenAccount>(&custody).amount, 500);
+
+    // Nobody else can sweep it, even naming the owner's sub-account
+    let ix = instructions::sweep_sub_account(&customer.pubkey(), 1);
+    let ix = substitute(
+        ix,
+        &pda::sub_account(&customer.pubkey(), 1).0,
+        &pda::sub_account(&owner.pubkey(), 1).0,
+    );
+    let ix = substitute(
+        ix,
+        &pda::sub_account_custody(&customer.pubkey(), 1),
+        &custody,
+    );
+    let result = ctx.execute(ix, &[&customer]);
+    assert!(!result.is_success(), "Swept another owner's sub-account");
+    result.assert_error_code(ErrorCode::ConstraintSeeds.into());
+
+    let result = ctx.expect_success(
+        instructions::sweep_sub_account(&owner.pubkey(), 1),
+        &[&owner],
+    );
+    let event: SubAccountSwept = result.parse_event().unwrap();
+    assert_eq!(
+        event,
+        SubAccountSwept {
+            owner: owner.pubkey(),
+            index: 1,
+            amount: 500,
+        }
+    );
+    assert_eq!(ctx.balance(&owner), 600);
+
+    // Custody stays open for later deposits, and an empty sweep is a no-op
+    assert_eq!(ctx.account::<TokenAccount>(&custody).amount, 0);
+    ctx.sweep_sub_account(&owner, 1);
+    assert_eq!(ctx.balance(&owner), 600);
+}
+
+// ============================================================================
+// Airdrop Tests
+// ============================================================================
+
+#[test]
+fn test_open_airdrop() {
+    let mut ctx = TestContext::new();
+    let minter = ctx.funded_account();
+    let funder = ctx.funded_account();
+    let officer = Keypair::new();
+    let alice = Keypair::new();
+    let bob = Keypair::new();
+    let stranger = ctx.funded_account();
+    ctx.initialize()
+        .configure_minter(&minter, 1_000)
+        .mint(&minter, &funder, 1_000)
+        .mint(&minter, &stranger, 1_000);
+
+    // Only a treasury officer funds an airdrop, since what is left goes to
+    // treasury custody
+    let open = |funder: &Keypair| {
+        instructions::open_airdrop(&funder.pubkey(), 1, vec![alice.pubkey()], vec![1], 1)
+    };
+    let result = ctx.execute(open(&funder), &[&funder]);
+    result.assert_error_code(ErrorCode::AccountNotInitialized.into());
+    ctx.configure_treasury([&funder, &officer], 0);
+    let result = ctx.execute(open(&stranger), &[&stranger]);
+    result.assert_error_code(StablecoinError::NotOfficer.into());
+
+    let result = ctx.expect_success(
+        instructions::open_airdrop(
+            &funder.pubkey(),
+            1,
+            vec![alice.pubkey(), bob.pubkey()],
+            vec![300, 200],
+            3_600,
+        ),
+        &[&funder],
+    );
+    let closes_at = ctx.clock().unix_timestamp + 3_600;
+    assert_eq!(
+        ctx.airdrop(&funder, 1),
+        Airdrop {
+            funder: funder.pubkey(),
+            id: 1,
+            recipients: vec![alice.pubkey(), bob.pubkey()],
+            unclaimed: vec![300, 200],
+            closes_at,
+            bump: pda::airdrop(&funder.pubkey(), 1).1,
+        }
+    );
+    let event: AirdropOpened = result.parse_event().unwrap();
+    assert_eq!(
+        event,
+        AirdropOpened {
+            funder: funder.pubkey(),
+            id: 1,
+            total: 500,
+            closes_at,
+        }
+    );
+    let custody = pda::airdrop_custody(&funder.pubkey(), 1);
+    let custody_state = ctx.account::<TokenAccount>(&custody);
+    assert_eq!(custody_state.owner, pda::airdrop(&funder.pubkey(), 1).0);
+    assert_eq!(custody_state.amount, 500);
+    assert_eq!(ctx.balance(&funder), 500);
+
+    // Every recipient once, each with something to claim, for some time
+    for (recipients, amounts, duration) in [
+        (vec![alice.pubkey(), alice.pubkey()], vec![1, 1], 1),
+        (vec![alice.pubkey()], vec![1, 1], 1),
+        (vec![alice.pubkey()], vec![0], 1),
+        (vec![Pubkey::default()], vec![1], 1),
+        (vec![alice.pubkey()], vec![1], 0),
+        (vec![], vec![], 1),
+    ] {
+        let result = ctx.execute(
+            instructions::open_airdrop(&funder.pubkey(), 2, recipients, amounts, duration),
+            &[&funder],
+        );
+        result.assert_error_code(StablecoinError::InvalidAirdrop.into());
+    }
+    assert!(!ctx.account_exists(&pda::airdrop(&funder.pubkey(), 2).0));
+}
+
+#[test]
+fn test_claim_airdrop() {
+    let mut ctx = TestContext::new();
+    let minter = ctx.funded_account();
+    let funder = ctx.funded_account();
+    let alice = ctx.funded_account();
+    let bob = ctx.funded_account();
+    let stranger = ctx.funded_account();
+    ctx.initialize()
+        .configure_minter(&minter, 1_000)
+        .mint(&minter, &funder, 1_000)
+        .configure_treasury([&funder, &stranger], 0)
+        .open_airdrop(&funder, 1, &[(&alice, 300), (&bob, 200)], 3_600);
+
+    // Claiming creates the recipient's token account
+    let result = ctx.expect_success(
+        instructions::claim_airdrop(&alice.pubkey(), &funder.pubkey(), 1),
+        &[&alice],
+    );
+    let event: AirdropClaimed = result.parse_event().unwrap();
+    assert_eq!(
+        event,
+        AirdropClaimed {
+            funder: funder.pubkey(),
+            id: 1,
+            recipient: alice.pubkey(),
+            amount: 300,
+        }
+    );
+    assert_eq!(ctx.balance(&alice), 300);
+    assert_eq!(ctx.airdrop(&funder, 1).unclaimed, vec![0, 200]);
+
+    let result = ctx.execute(
+        instructions::claim_airdrop(&alice.pubkey(), &funder.pubkey(), 1),
+        &[&alice],
+    );
+    result.assert_error_code(StablecoinError::AirdropClaimed.into());
+    let result = ctx.execute(
+        instructions::claim_airdrop(&stranger.pubkey(), &funder.pubkey(), 1),
+        &[&stranger],
+    );
+    result.assert_error_code(StablecoinError::NotAirdropRecipient.into());
+
+    // Claims end when the airdrop closes
+    ctx.advance_time(3_600);
+    let result = ctx.execute(
+        instructions::claim_airdrop(&bob.pubkey(), &funder.pubkey(), 1),
+        &[&bob],
+    );
+    result.assert_error_code(StablecoinError::AirdropClosed.into());
+    assert_eq!(ctx.balance(&bob), 0);
+}
+
+#[test]
+fn test_clawback_airdrop() {
+    let mut ctx = TestContext::new();
+    let minter = ctx.funded_account();
+    let funder = ctx.funded_account();
+    let alice = ctx.funded_account();
+    let bob = Keypair::new();
+    let crank = ctx.funded_account();
+    ctx.initialize()
+        .configure_minter(&minter, 1_000)
+        .mint(&minter, &funder, 1_000)
+        .configure_treasury([&funder, &crank], 0)
+        .open_airdrop(&funder, 1, &[(&alice, 300), (&bob, 200)], 3_600)
+        .claim_airdrop(&alice, &funder, 1);
+
+    let ix = instructions::clawback_airdrop(&crank.pubkey(), &funder.pubkey(), 1);
+    let result = ctx.execute(ix.clone(), &[&crank]);
+    result.assert_error_code(StablecoinError::AirdropOpen.into());
+
+    // Anyone can claw it back once closed, creating treasury custody; the
+    // tokens go to the treasury and the rent back to the funder
+    ctx.advance_time(3_600);
+    let (airdrop, _) = pda::airdrop(&funder.pubkey(), 1);
+    let custody = pda::airdrop_custody(&funder.pubkey(), 1);
+    let rent = ctx.svm.get_account(&airdrop).unwrap().lamports
+        + ctx.svm.get_account(&custody).unwrap().lamports;
+    let before = ctx.svm.get_balance(&funder.pubkey()).unwrap();
+    let funded = ctx.token_state(&funder).amount;
+    let result = ctx.expect_success(ix, &[&crank]);
+    let event: AirdropClawedBack = result.parse_event().unwrap();
+    assert_eq!(
+        event,
+        AirdropClawedBack {
+            funder: funder.pubkey(),
+            id: 1,
+            amount: 200,
+        }
+    );
+    assert_eq!(
+        ctx.account::<TokenAccount>(&pda::treasury_custody()).amount,
+        200
+    );
+    assert_eq!(ctx.token_state(&funder).amount, funded);
+    assert!(!ctx.account_exists(&airdrop));
+    assert!(!ctx.account_exists(&custody));
+    assert_eq!(
+        ctx.svm.get_balance(&funder.pubkey()).unwrap(),
+        before + rent
+    );
+
+    // The id is free again
+    ctx.open_airdrop(&funder, 1, &[(&alice, 100)], 1);
+}
+
+#[test]
+fn test_open_airdrop_with_existing_custody() {
+    let mut ctx = TestContext::new();
+    let minter = ctx.funded_account();
+    let funder = ctx.funded_account();
+    let officer = Keypair::new();
+    let alice = ctx.funded_account();
+    ctx.initialize()
+        .configure_minter(&minter, 1_000)
+        .mint(&minter, &funder, 500)
+        .configure_treasury([&funder, &officer], 0);
+
+    // Sending to the airdrop's address before it opens creates its custody
+    let (airdrop, _) = pda::airdrop(&funder.pubkey(), 1);
+    ctx.expect_success(
+        instructions::mint_tokens(&minter.pubkey(), &airdrop, 50),
+        &[&minter],
+    );
+    let custody = pda::airdrop_custody(&funder.pubkey(), 1);
+    assert_eq!(ctx.account::<TokenAccount>(&custody).amount, 50);
+
+    // The airdrop opens over it, and the clawback takes what was sent along
+    // with what was not claimed
+    ctx.open_airdrop(&funder, 1, &[(&alice, 300)], 60)
+        .claim_airdrop(&alice, &funder, 1);
+    assert_eq!(ctx.balance(&alice), 300);
+    ctx.advance_time(60);
+    ctx.expect_success(
+        instructions::clawback_airdrop(&alice.pubkey(), &funder.pubkey(), 1),
+        &[&alice],
+    );
+    assert_eq!(
+        ctx.account::<TokenAccount>(&pda::treasury_custody()).amount,
+        50
+    );
+}
+
+// ============================================================================
+// Resize Tests
+// ============================================================================
+
+/// Cut the account at `address` down to `len` bytes and the rent that
+/// size needs, as an older version of the program would have laid it out
+fn truncate_account(ctx: &mut TestContext, address: &Pubkey, len: usize) {
+    let mut account = ctx.svm.get_account(address).unwrap();
+    account.data.truncate(len);
+    account.lamports = ctx.svm.minimum_balance_for_rent_exemption(len);
+    ctx.svm.set_account(*address, account).unwrap();
+}
+
+#[test]
+fn test_resize_account() {
+    let mut ctx = TestContext::new();
+    let admin = ctx.admin.insecure_clone();
+    let payer = ctx.funded_account();
+    ctx.initialize();
+
+    // A config without its last two fields, which no longer deserializes
+    let config = ctx.config();
+    let address = pda::config().0;
+    truncate_account(&mut ctx, &address, sizes::CONFIG - 2);
+    let top_up = ctx.svm.minimum_balance_for_rent_exemption(sizes::CONFIG)
+        - ctx
+            .svm
+            .minimum_balance_for_rent_exemption(sizes::CONFIG - 2);
+    let before = ctx.svm.get_balance(&payer.pubkey()).unwrap();
+
+    let result = ctx.expect_success(
+        instructions::resize_account(&admin.pubkey(), &payer.pubkey(), &address),
+        &[&admin, &payer],
+    );
+    let event: AccountResized = result.parse_event().unwrap();
+    assert_eq!(
+        event,
+        AccountResized {
             admin: admin.pubkey(),
-            config: config_pda,
-            minter: minter.pubkey(),
-            minter_config: minter_config_pda,
-        })
-        .args(client::args::RemoveMinter {})
-        .instruction()
+            account: address,
+            old_len: sizes::CONFIG as u32 - 2,
+            new_len: sizes::CONFIG as u32,
+            top_up,
+        }
+    );
+    // The new fields are bumps, which are derived again
+    assert_eq!(ctx.config(), config);
+    assert_eq!(
+        ctx.svm.get_account(&address).unwrap().lamports,
+        ctx.svm.minimum_balance_for_rent_exemption(sizes::CONFIG)
+    );
+    assert_eq!(
+        ctx.svm.get_balance(&payer.pubkey()).unwrap(),
+        before - top_up
+    );
+
+    // An account already at its size is left as it is
+    let result = ctx.expect_success(
+        instructions::resize_account(&admin.pubkey(), &payer.pubkey(), &pda::supply_stats().0),
+        &[&admin, &payer],
+    );
+    let event: AccountResized = result.parse_event().unwrap();
+    assert_eq!(
+        (event.old_len, event.top_up),
+        (sizes::SUPPLY_STATS as u32, 0)
+    );
+}
+
+#[test]
+fn test_resize_minter_config_then_mint() {
+    let mut ctx = TestContext::new();
+    let admin = ctx.admin.insecure_clone();
+    let minter = ctx.funded_account();
+    let holder = Keypair::new();
+    ctx.initialize().configure_minter(&minter, 1_000_000_000);
+
+    // A minter config laid out before the pause marker's bump
+    let minter_config = ctx.minter_config(&minter);
+    let address = pda::minter_config(&minter.pubkey()).0;
+    truncate_account(&mut ctx, &address, sizes::MINTER_CONFIG - 1);
+    ctx.expect_success(
+        instructions::resize_account(&admin.pubkey(), &admin.pubkey(), &address),
+        &[&admin],
+    );
+    assert_eq!(ctx.minter_config(&minter), minter_config);
+
+    ctx.mint(&minter, &holder, 100);
+    assert_eq!(ctx.balance(&holder), 100);
+}
+
+/// The upgrade sequence the README describes, from the layouts before the
+/// stored bumps
+#[test]
+fn test_upgrade_sequence() {
+    let mut ctx = TestContext::new();
+    let admin = ctx.admin.insecure_clone();
+    let minter = ctx.funded_account();
+    let holder = Keypair::new();
+    ctx.initialize()
+        .configure_minter(&minter, 1_000_000_000)
+        .pause();
+
+    // The accounts as the version before the bumps laid them out
+    let config = pda::config().0;
+    let minter_config = pda::minter_config(&minter.pubkey()).0;
+    truncate_account(&mut ctx, &config, sizes::CONFIG - 2);
+    truncate_account(&mut ctx, &minter_config, sizes::MINTER_CONFIG - 1);
+    let resize =
+        |address: &Pubkey| instructions::resize_account(&admin.pubkey(), &admin.pubkey(), address);
+    let mint = instructions::mint_tokens(&minter.pubkey(), &holder.pubkey(), 100);
+
+    // Nothing that reads the config works until it is resized
+    let result = ctx.execute(mint.clone(), &[&minter]);
+    result.assert_error_code(ErrorCode::AccountDidNotDeserialize.into());
+    ctx.expect_success(resize(&config), &[&admin]);
+    ctx.unpause();
+
+    // Nor can the minter mint until its own config is resized
+    ctx.svm.expire_blockhash();
+    let result = ctx.execute(mint.clone(), &[&minter]);
+    result.assert_error_code(ErrorCode::AccountDidNotDeserialize.into());
+    ctx.expect_success(resize(&minter_config), &[&admin]);
+
+    ctx.svm.expire_blockhash();
+    ctx.expect_success(mint, &[&minter]);
+    assert_eq!(ctx.balance(&holder), 100);
+}
+
+#[test]
+fn test_resize_account_only_by_admin() {
+    let mut ctx = TestContext::new();
+    let stranger = ctx.funded_account();
+    ctx.initialize();
+
+    let result = ctx.execute(
+        instructions::resize_account(
+            &stranger.pubkey(),
+            &stranger.pubkey(),
+            &pda::supply_stats().0,
+        ),
+        &[&stranger],
+    );
+    result.assert_error_code(StablecoinError::Unauthorized.into());
+}
+
+#[test]
+fn test_resize_account_rejects_other_accounts() {
+    let mut ctx = TestContext::new();
+    let admin = ctx.admin.insecure_clone();
+    let minter = ctx.funded_account();
+    ctx.initialize().configure_minter(&minter, 1_000);
+    let resize =
+        |address: &Pubkey| instructions::resize_account(&admin.pubkey(), &admin.pubkey(), address);
+
+    // Longer than this version lays it out: a newer version wrote it
+    let address = pda::minter_config(&minter.pubkey()).0;
+    let mut account = ctx.svm.get_account(&address).unwrap();
+    account.data.push(0);
+    ctx.svm.set_account(address, account).unwrap();
+    let result = ctx.execute(resize(&address), &[&admin]);
+    result.assert_error_code(StablecoinError::NewerAccountVersion.into());
+
+    // Not one of the program's types
+    let unknown = Pubkey::new_unique();
+    let mut account = ctx.svm.get_account(&pda::supply_stats().0).unwrap();
+    account.data[..8].copy_from_slice(&[0; 8]);
+    ctx.svm.set_account(unknown, account).unwrap();
+    let result = ctx.execute(resize(&unknown), &[&admin]);
+    result.assert_error_code(StablecoinError::CannotResize.into());
+
+    // Not the program's account at all
+    let result = ctx.execute(resize(&pda::mint().0), &[&admin]);
+    result.assert_error_code(ErrorCode::ConstraintOwner.into());
+}
+
+// ============================================================================
+// Supply Stats Tests
+// ============================================================================
+
+#[test]
+fn test_supply_stats_accumulate_on_supply_changes() {
+    let mut ctx = TestContext::new();
+    let minter = ctx.funded_account();
+    let user = ctx.funded_account();
+    ctx.initialize();
+    let started_at = ctx.clock().unix_timestamp;
+    assert_eq!(
+        ctx.supply_stats(),
+        SupplyStats {
+            supply: 0,
+            updated_at: started_at,
+            started_at,
+            cumulative_supply: 0,
+            bump: pda::supply_stats().1,
+        }
+    );
+
+    // 100 for 10 seconds, then 300 for 20, then 250
+    ctx.configure_minter(&minter, 1_000_000_000)
+        .mint(&minter, &user, 100)
+        .advance_time(10)
+        .mint(&minter, &user, 200)
+        .advance_time(20)
+        .burn(&user, 50);
+
+    let supply_stats = ctx.supply_stats();
+    assert_eq!(supply_stats.supply, 250);
+    assert_eq!(supply_stats.updated_at, started_at + 30);
+    assert_eq!(supply_stats.started_at, started_at);
+    assert_eq!(supply_stats.cumulative_supply, 100 * 10 + 300 * 20);
+}
+
+#[test]
+fn test_sync_supply_stats_records_direct_burns() {
+    let mut ctx = TestContext::new();
+    let minter = ctx.funded_account();
+    let user = ctx.funded_account();
+    ctx.initialize()
+        .configure_minter(&minter, 1_000_000_000)
+        .mint(&minter, &user, 100);
+
+    // A burn straight through SPL Token bypasses the program
+    let burn = spl_token::instruction::burn(
+        &spl_token::ID,
+        &ctx.token_account(&user),
+        &pda::mint().0,
+        &user.pubkey(),
+        &[],
+        40,
+    )
+    .unwrap();
+    ctx.advance_time(10).expect_success(burn, &[&user]);
+    assert_eq!(ctx.supply_stats().supply, 100);
+
+    // Until a sync records it, the old supply keeps accumulating
+    let anyone = ctx.funded_account();
+    ctx.advance_time(10).expect_success(
+        instructions::sync_supply_stats(&anyone.pubkey()),
+        &[&anyone],
+    );
+    let supply_stats = ctx.supply_stats();
+    assert_eq!(supply_stats.supply, 60);
+    assert_eq!(supply_stats.cumulative_supply, 100 * 20);
+
+    // Syncing again only accumulates the recorded supply
+    ctx.advance_time(10).expect_success(
+        instructions::sync_supply_stats(&anyone.pubkey()),
+        &[&anyone],
+    );
+    assert_eq!(ctx.supply_stats().cumulative_supply, 100 * 20 + 60 * 10);
+}
+
+#[test]
+fn test_sync_supply_stats_creates_stats_after_upgrade() {
+    let mut ctx = TestContext::new();
+    let minter = ctx.funded_account();
+    let user = ctx.funded_account();
+    ctx.initialize()
+        .configure_minter(&minter, 1_000_000_000)
+        .mint(&minter, &user, 100);
+
+    // A program deployed before the stats existed has a config but no stats
+    ctx.svm
+        .set_account(pda::supply_stats().0, Default::default())
         .unwrap();
+    let result = ctx.execute(
+        instructions::mint_tokens(&minter.pubkey(), &user.pubkey(), 60),
+        &[&minter],
+    );
+    result.assert_error_code(ErrorCode::AccountNotInitialized.into());
 
-    ctx.execute_instruction(remove_minter_ix, &[&admin])
-        .unwrap()
-        .assert_success();
+    // Anyone can create them, from the supply as it stands
+    let anyone = ctx.funded_account();
+    ctx.advance_time(10).expect_success(
+        instructions::sync_supply_stats(&anyone.pubkey()),
+        &[&anyone],
+    );
+    let now = ctx.clock().unix_timestamp;
+    assert_eq!(
+        ctx.supply_stats(),
+        SupplyStats {
+            supply: 100,
+            updated_at: now,
+            started_at: now,
+            cumulative_supply: 0,
+            bump: pda::supply_stats().1,
+        }
+    );
 
-    // Verify minter was removed
-    ctx.svm.assert_account_closed(&minter_config_pda);
+    ctx.advance_time(10).mint(&minter, &user, 50);
+    let supply_stats = ctx.supply_stats();
+    assert_eq!(supply_stats.supply, 150);
+    assert_eq!(supply_stats.cumulative_supply, 100 * 10);
 }
 
+// ============================================================================
+// Pause/Unpause Tests
+// ============================================================================
+
 #[test]
-fn test_multiple_minters() {
-    let mut ctx = setup_ctx();
+fn test_pause() {
+    let mut ctx = TestContext::new();
+    ctx.initialize().pause();
+    assert!(ctx.config().paused);
+}
 
-    let admin = ctx.svm.create_funded_account(10_000_000_000).unwrap();
-    let minter1 = ctx.svm.create_funded_account(10_000_000_000).unwrap();
-    let minter2 = ctx.svm.create_funded_account(10_000_000_000).unwrap();
-    let user = Keypair::new();
+#[test]
+fn test_pause_unauthorized() {
+    let mut ctx = TestContext::new();
+    let unauthorized = ctx.funded_account();
+    ctx.initialize();
 
-    initialize_program(&mut ctx, &admin);
+    ctx.expect_failure(
+        instructions::pause(&unauthorized.pubkey()),
+        &[&unauthorized],
+    );
+    assert!(!ctx.config().paused);
+}
 
-    // Configure two minters with different allowances
-    configure_minter(&mut ctx, &admin, &minter1.pubkey(), 500_000_000);
-    configure_minter(&mut ctx, &admin, &minter2.pubkey(), 1_000_000_000);
+#[test]
+fn test_mint_when_paused() {
+    let mut ctx = TestContext::new();
+    let minter = ctx.funded_account();
+    let recipient = Keypair::new();
 
-    // Both minters mint to the same user
-    mint_tokens(&mut ctx, &minter1, &user.pubkey(), 100_000_000);
-    mint_tokens(&mut ctx, &minter2, &user.pubkey(), 200_000_000);
+    ctx.initialize()
+        .configure_minter(&minter, 1_000_000_000)
+        .pause();
 
-    // Verify user received tokens from both minters
-    let mint_pda = get_mint_pda();
-    let user_ata = get_ata(&user.pubkey(), &mint_pda);
+    // Try to mint when paused
+    let ix = instructions::mint_tokens(&minter.pubkey(), &recipient.pubkey(), 100_000_000);
+    ctx.expect_failure(ix, &[&minter]);
+
+    assert_eq!(ctx.balance(&recipient), 0);
+    assert_eq!(ctx.minter_config(&minter).amount_minted, 0);
+}
+
+#[test]
+fn test_unpause() {
+    let mut ctx = TestContext::new();
+    ctx.initialize().pause().unpause();
+    assert!(!ctx.config().paused);
+}
+
+#[test]
+fn test_mint_after_unpause() {
+    let mut ctx = TestContext::new();
+    let minter = ctx.funded_account();
+    let recipient = Keypair::new();
 
+    // Minting works again once unpaused
+    ctx.initialize()
+        .configure_minter(&minter, 1_000_000_000)
+        .pause()
+        .unpause()
+        .mint(&minter, &recipient, 100_000_000);
+
+    let destination_ata = ctx.token_account(&recipient);
+    ctx.svm.assert_token_balance(&destination_ata, 100_000_000);
+}
+
+// ============================================================================
+// Stored Bump Tests
+// ============================================================================
+
+#[test]
+fn test_stored_bumps_derive_addresses() {
+    // Mints derive the config, mint, minter config and pause marker
+    // addresses from the bumps stored at creation instead of searching for
+    // them again; a stored bump that is off by one no longer matches
+    let mut ctx = TestContext::new();
+    let minter = ctx.funded_account();
+    let recipient = Keypair::new();
+    ctx.initialize()
+        .configure_minter(&minter, 1_000_000_000)
+        .mint(&minter, &recipient, 1);
+    let snapshot = ctx.svm.clone();
+    let config = ctx.config();
+    let minter_config = ctx.minter_config(&minter);
+
+    let off_by_one = |bump: u8| bump.wrapping_sub(1);
+    for account in ["config", "mint", "minter_config", "minter_pause"] {
+        ctx.svm = snapshot.clone();
+        match account {
+            "config" => ctx.forge_account(
+                &pda::config().0,
+                &Config {
+                    bump: off_by_one(config.bump),
+                    ..config
+                },
+            ),
+            "mint" => ctx.forge_account(
+                &pda::config().0,
+                &Config {
+                    mint_bump: off_by_one(config.mint_bump),
+                    ..config
+                },
+            ),
+            "minter_config" => ctx.forge_account(
+                &pda::minter_config(&minter.pubkey()).0,
+                &MinterConfig {
+                    bump: off_by_one(minter_config.bump),
+                    ..minter_config
+                },
+            ),
+            // Not a way past the pause marker to another, empty address
+            _ => ctx.forge_account(
+                &pda::minter_config(&minter.pubkey()).0,
+                &MinterConfig {
+                    pause_bump: off_by_one(minter_config.pause_bump),
+                    ..minter_config
+                },
+            ),
+        }
+
+        let result = ctx.execute(
+            instructions::mint_tokens(&minter.pubkey(), &recipient.pubkey(), 1),
+            &[&minter],
+        );
+        assert!(!result.is_success(), "{account}: minted with a wrong bump");
+        result.assert_error_code(ErrorCode::ConstraintSeeds.into());
+    }
+}
+
+// ============================================================================
+// Integration Tests
+// ============================================================================
+
+#[test]
+fn test_full_stablecoin_flow() {
+    let mut ctx = TestContext::new();
+    let minter = ctx.funded_account();
+    let user1 = ctx.funded_account();
+    let user2 = ctx.funded_account();
+
+    ctx.initialize()
+        .configure_minter(&minter, 1_000_000_000)
+        .mint(&minter, &user1, 100_000_000)
+        .mint(&minter, &user2, 200_000_000)
+        .burn(&user1, 50_000_000);
+
+    // Verify balances, supply and allowance use after the burn
+    assert_eq!(ctx.balance(&user1), 50_000_000);
+    assert_eq!(ctx.balance(&user2), 200_000_000);
+    assert_eq!(ctx.mint_state().supply, 250_000_000);
+    assert_eq!(ctx.minter_config(&minter).amount_minted, 300_000_000);
+
+    ctx.pause().unpause().remove_minter(&minter);
+
+    // Verify minter was removed
+    ctx.svm
+        .assert_account_closed(&pda::minter_config(&minter.pubkey()).0);
+}
+
+#[test]
+fn test_multiple_minters() {
+    let mut ctx = TestContext::new();
+    let minter1 = ctx.funded_account();
+    let minter2 = ctx.funded_account();
+    let user = Keypair::new();
+
+    // Configure two minters with different allowances, both minting to the same user
+    ctx.initialize()
+        .configure_minter(&minter1, 500_000_000)
+        .configure_minter(&minter2, 1_000_000_000)
+        .mint(&minter1, &user, 100_000_000)
+        .mint(&minter2, &user, 200_000_000);
+
+    // Verify user received tokens from both minters
+    let user_ata = ctx.token_account(&user);
     assert!(
         ctx.account_exists(&user_ata),
         "User should have token account"
     );
     ctx.svm.assert_token_balance(&user_ata, 300_000_000);
+
+    // Each minter's allowance is tracked separately
+    assert_eq!(ctx.minter_config(&minter1).amount_minted, 100_000_000);
+    assert_eq!(ctx.minter_config(&minter2).amount_minted, 200_000_000);
+}
+
+#[test]
+fn test_interleaved_minters_accounting() {
+    const MINTERS: usize = 8;
+    const RECIPIENTS: usize = 12;
+    const STEPS: usize = 240;
+
+    let mut ctx = TestContext::new();
+    let minters: Vec<Keypair> = (0..MINTERS).map(|_| ctx.funded_account()).collect();
+    let recipients: Vec<Keypair> = (0..RECIPIENTS).map(|_| Keypair::new()).collect();
+    // Uneven allowances, so the smaller ones run out partway through
+    let allowances: Vec<u64> = (1..=MINTERS as u64).map(|i| i * 400_000).collect();
+    ctx.initialize();
+    for (minter, allowance) in minters.iter().zip(&allowances) {
+        ctx.configure_minter(minter, *allowance);
+    }
+
+    // What each minter should have debited and each recipient received
+    let mut debited = [0u64; MINTERS];
+    let mut received = [0u64; RECIPIENTS];
+    let mut rejected = 0;
+
+    for step in 0..STEPS {
+        // Every fourth step batches three minters into one transaction,
+        // which lands whole or not at all
+        let batch = if step % 4 == 3 { 3 } else { 1 };
+        let mints: Vec<(usize, usize, u64)> = (0..batch)
+            .map(|i| {
+                let k = step * 3 + i;
+                (
+                    (step + i * 3) % MINTERS,
+                    k * 7 % RECIPIENTS,
+                    1_000 + (k as u64 * 7_919) % 50_000,
+                )
+            })
+            .collect();
+        let fits = mints
+            .iter()
+            .all(|&(m, _, amount)| debited[m] + amount <= allowances[m]);
+
+        let ixs = mints
+            .iter()
+            .map(|&(m, r, amount)| {
+                instructions::mint_tokens(&minters[m].pubkey(), &recipients[r].pubkey(), amount)
+            })
+            .collect();
+        let signers: Vec<&Keypair> = mints.iter().map(|&(m, _, _)| &minters[m]).collect();
+        ctx.svm.expire_blockhash();
+        let result = ctx.execute_all(ixs, &signers);
+
+        if fits {
+            result.assert_success();
+            for &(m, r, amount) in &mints {
+                debited[m] += amount;
+                received[r] += amount;
+            }
+        } else {
+            result.assert_error_code(StablecoinError::ExceedsAllowance.into());
+            rejected += 1;
+        }
+    }
+    // Otherwise the allowances were too generous to test anything
+    assert!(rejected > 0);
+
+    let mut total_debited = 0;
+    for (m, minter) in minters.iter().enumerate() {
+        let config = ctx.minter_config(minter);
+        assert_eq!(config.amount_minted, debited[m], "minter {m}");
+        assert!(config.amount_minted <= config.allowance, "minter {m}");
+        total_debited += config.amount_minted;
+    }
+    for (r, recipient) in recipients.iter().enumerate() {
+        assert_eq!(ctx.balance(recipient), received[r], "recipient {r}");
+    }
+    assert_eq!(ctx.mint_state().supply, total_debited);
+    assert_eq!(received.iter().sum::<u64>(), total_debited);
+}
+
+// ============================================================================
+// Multi-Instruction Transaction Tests
+// ============================================================================
+
+#[test]
+fn test_mints_over_allowance_in_one_transaction() {
+    let mut ctx = TestContext::new();
+    let minter = ctx.funded_account();
+    let recipient = Keypair::new();
+    ctx.initialize().configure_minter(&minter, 150_000_000);
+
+    // Each mint fits the allowance alone, but not both together
+    let ix = instructions::mint_tokens(&minter.pubkey(), &recipient.pubkey(), 100_000_000);
+    let result = ctx.execute_all(vec![ix.clone(), ix], &[&minter]);
+    result.assert_error_code(StablecoinError::ExceedsAllowance.into());
+
+    // The first mint, and the token account it created, are rolled back
+    assert_eq!(ctx.minter_config(&minter).amount_minted, 0);
+    assert_eq!(ctx.mint_state().supply, 0);
+    assert!(!ctx.account_exists(&ctx.token_account(&recipient)));
+}
+
+#[test]
+fn test_mints_up_to_allowance_in_one_transaction() {
+    let mut ctx = TestContext::new();
+    let minter = ctx.funded_account();
+    let recipient = Keypair::new();
+    ctx.initialize().configure_minter(&minter, 200_000_000);
+
+    let ix = instructions::mint_tokens(&minter.pubkey(), &recipient.pubkey(), 100_000_000);
+    ctx.execute_all(vec![ix.clone(), ix], &[&minter])
+        .assert_success();
+
+    assert_eq!(ctx.minter_config(&minter).amount_minted, 200_000_000);
+    assert_eq!(ctx.balance(&recipient), 200_000_000);
+}
+
+#[test]
+fn test_pause_then_mint_in_one_transaction() {
+    let mut ctx = TestContext::new();
+    let minter = ctx.funded_account();
+    let recipient = Keypair::new();
+    ctx.initialize().configure_minter(&minter, 1_000_000_000);
+
+    // The mint sees the pause from earlier in the same transaction
+    let admin = ctx.admin.insecure_clone();
+    let result = ctx.execute_all(
+        vec![
+            instructions::pause(&admin.pubkey()),
+            instructions::mint_tokens(&minter.pubkey(), &recipient.pubkey(), 100_000_000),
+        ],
+        &[&admin, &minter],
+    );
+    result.assert_error_code(StablecoinError::Paused.into());
+
+    // ...and its failure undoes the pause
+    assert!(!ctx.config().paused);
+    assert_eq!(ctx.mint_state().supply, 0);
+}
+
+#[test]
+fn test_mint_then_pause_in_one_transaction() {
+    let mut ctx = TestContext::new();
+    let minter = ctx.funded_account();
+    let recipient = Keypair::new();
+    ctx.initialize().configure_minter(&minter, 1_000_000_000);
+
+    let admin = ctx.admin.insecure_clone();
+    ctx.execute_all(
+        vec![
+            instructions::mint_tokens(&minter.pubkey(), &recipient.pubkey(), 100_000_000),
+            instructions::pause(&admin.pubkey()),
+        ],
+        &[&admin, &minter],
+    )
+    .assert_success();
+
+    assert!(ctx.config().paused);
+    assert_eq!(ctx.balance(&recipient), 100_000_000);
+}
+
+#[test]
+fn test_unpause_then_mint_in_one_transaction() {
+    let mut ctx = TestContext::new();
+    let minter = ctx.funded_account();
+    let recipient = Keypair::new();
+    ctx.initialize()
+        .configure_minter(&minter, 1_000_000_000)
+        .pause();
+
+    let admin = ctx.admin.insecure_clone();
+    ctx.execute_all(
+        vec![
+            instructions::unpause(&admin.pubkey()),
+            instructions::mint_tokens(&minter.pubkey(), &recipient.pubkey(), 100_000_000),
+        ],
+        &[&admin, &minter],
+    )
+    .assert_success();
+
+    assert!(!ctx.config().paused);
+    assert_eq!(ctx.balance(&recipient), 100_000_000);
+}
+
+// ============================================================================
+// Clock Tests
+// ============================================================================
+
+#[test]
+fn test_warp_clock() {
+    let mut ctx = TestContext::new();
+
+    ctx.warp_to_slot(1_000).warp_to_timestamp(1_700_000_000);
+    let clock = ctx.clock();
+    assert_eq!(clock.slot, 1_000);
+    assert_eq!(clock.unix_timestamp, 1_700_000_000);
+
+    // An hour is 9,000 slots at 400ms each
+    ctx.advance_time(3_600);
+    let clock = ctx.clock();
+    assert_eq!(clock.slot, 10_000);
+    assert_eq!(clock.unix_timestamp, 1_700_003_600);
+}
+
+#[test]
+fn test_minting_is_not_time_limited() {
+    let mut ctx = TestContext::new();
+    let minter = ctx.funded_account();
+    let recipient = Keypair::new();
+
+    // The same mint, a year apart, draws on the same allowance
+    ctx.initialize()
+        .configure_minter(&minter, 200_000_000)
+        .mint(&minter, &recipient, 100_000_000)
+        .advance_time(365 * 24 * 60 * 60)
+        .mint(&minter, &recipient, 100_000_000);
+    assert_eq!(ctx.minter_config(&minter).amount_minted, 200_000_000);
+
+    let ix = instructions::mint_tokens(&minter.pubkey(), &recipient.pubkey(), 1);
+    ctx.advance_time(365 * 24 * 60 * 60)
+        .expect_failure(ix, &[&minter]);
 }